@@ -0,0 +1,1013 @@
+//! Derive and procedural macros for [`bidir-map`](https://crates.io/crates/bidir-map).
+//!
+//! Usually used through `bidir-map`'s `derive` and `macros` features, which re-export the derives
+//! and `include_bidir_map!` and `named_bidir_map!` respectively.
+//!
+//! # Examples
+//!
+//! ```
+//! #[macro_use] extern crate bidir_map_derive;
+//! extern crate bidir_map;
+//!
+//! #[derive(BidirMapping, Clone, Copy, Debug, PartialEq)]
+//! #[bidir(second = "str")]
+//! enum Opcode {
+//!     #[bidir("GET")]
+//!     Get,
+//!     #[bidir("PUT")]
+//!     Put,
+//! }
+//!
+//! # fn main() {
+//! assert_eq!(Opcode::Get.to_mapped(), "GET");
+//! assert_eq!(Opcode::from_mapped("PUT"), Some(Opcode::Put));
+//! assert_eq!(Opcode::from_mapped("DELETE"), None);
+//! assert_eq!(Opcode::bidir_map().get_by_second(&"GET"), Some(&Opcode::Get));
+//! # }
+//! ```
+
+
+extern crate proc_macro;
+
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+use std::path::Path;
+use std::{env, fs};
+
+
+/// Derives a bidirectional mapping between a field-less enum and a set of constant values.
+///
+/// Every variant must be annotated with `#[bidir(value)]`, and no two variants may share a value.
+/// The type of the values is given by `#[bidir(second = "Type")]` on the enum, where `"str"` (the default) means `&'static str`.
+///
+/// The generated inherent functions are:
+///
+///   * `bidir_map() -> &'static BidirMap<Self, Type>`, built on first use,
+///   * `to_mapped(&self) -> Type`,
+///   * `from_mapped(&Type) -> Option<Self>` (taking a `&str` for string mappings).
+///
+/// `bidir_map()` requires the enum to implement `PartialEq`; the other two have no requirements.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use] extern crate bidir_map_derive;
+/// extern crate bidir_map;
+///
+/// #[derive(BidirMapping, Debug, PartialEq)]
+/// #[bidir(second = "u8")]
+/// enum Colour {
+///     #[bidir(1)]
+///     Red,
+///     #[bidir(2)]
+///     Green,
+/// }
+///
+/// # fn main() {
+/// assert_eq!(Colour::Green.to_mapped(), 2);
+/// assert_eq!(Colour::from_mapped(&1), Some(Colour::Red));
+/// # }
+/// ```
+///
+/// A value used twice is rejected:
+///
+/// ```compile_fail
+/// #[macro_use] extern crate bidir_map_derive;
+/// extern crate bidir_map;
+///
+/// #[derive(BidirMapping, PartialEq)]
+/// enum Opcode {
+///     #[bidir("GET")]
+///     Get,
+///     #[bidir("GET")]
+///     Fetch,
+/// }
+/// # fn main() {}
+/// ```
+///
+/// Even when it's spelled differently:
+///
+/// ```compile_fail
+/// #[macro_use] extern crate bidir_map_derive;
+/// extern crate bidir_map;
+///
+/// #[derive(BidirMapping, PartialEq)]
+/// #[bidir(second = "u8")]
+/// enum Colour {
+///     #[bidir(1)]
+///     Red,
+///     #[bidir(0x1)]
+///     Crimson,
+/// }
+/// # fn main() {}
+/// ```
+///
+/// As is a variant without a value:
+///
+/// ```compile_fail
+/// #[macro_use] extern crate bidir_map_derive;
+/// extern crate bidir_map;
+///
+/// #[derive(BidirMapping, PartialEq)]
+/// enum Opcode {
+///     #[bidir("GET")]
+///     Get,
+///     Put,
+/// }
+/// # fn main() {}
+/// ```
+#[proc_macro_derive(BidirMapping, attributes(bidir))]
+pub fn derive_bidir_mapping(input: TokenStream) -> TokenStream {
+	match parse_enum(input, "BidirMapping").and_then(|e| bidir_mapping(&e)) {
+		Ok(tokens) => tokens,
+		Err(err) => err.into_compile_error(),
+	}
+}
+
+/// Derives `bidir_map::EnumLike` for a field-less enum, numbering the variants from 0 in declaration order.
+///
+/// Explicit discriminants don't affect the numbering.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use] extern crate bidir_map_derive;
+/// extern crate bidir_map;
+///
+/// use bidir_map::{BidirEnumMap, EnumLike};
+///
+/// #[derive(EnumLike, Debug, PartialEq)]
+/// enum Level {
+///     Low,
+///     High = 10,
+/// }
+///
+/// # fn main() {
+/// assert_eq!(Level::VARIANTS, 2);
+/// assert_eq!(Level::High.to_index(), 1);
+/// assert_eq!(Level::from_index(0), Some(Level::Low));
+///
+/// let mut map = BidirEnumMap::new();
+/// map.insert(Level::High, 3.3);
+/// assert_eq!(map.get_by_first(&Level::High), Some(&3.3));
+/// # }
+/// ```
+#[proc_macro_derive(EnumLike)]
+pub fn derive_enum_like(input: TokenStream) -> TokenStream {
+	match parse_enum(input, "EnumLike").and_then(|e| enum_like(&e)) {
+		Ok(tokens) => tokens,
+		Err(err) => err.into_compile_error(),
+	}
+}
+
+/// Embeds a two-column, tab-separated file as a `bidir_map::StaticBidirMap`, validated at compile time.
+///
+/// Invoked as `include_bidir_map!("path", FirstType, SecondType)`, where a relative path is resolved against the directory
+/// of the invoking crate's `Cargo.toml`. Each line holds a pair, its K/Vs separated by a single tab;
+/// empty lines and ones starting with `#` are skipped. The supported types are the integers of up to 64 bits, `char`, `bool`,
+/// and `&'static str`, whose K/Vs are taken verbatim, surrounding whitespace included.
+///
+/// A line that doesn't parse, or a K/V repeated within a column, fails the build with the file and line of the culprit.
+/// The expansion is a constant expression, with the indices sorted by the macro, and makes the crate rebuild when the file changes.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use] extern crate bidir_map_derive;
+/// extern crate bidir_map;
+///
+/// use bidir_map::StaticBidirMap;
+///
+/// static STATUS: StaticBidirMap<u16, &str> = include_bidir_map!("tests/data/status.tsv", u16, &'static str);
+///
+/// # fn main() {
+/// assert_eq!(STATUS.get_by_first(&404), Some(&"Not Found"));
+/// assert_eq!(STATUS.get_by_second("OK"), Some(&200));
+/// # }
+/// ```
+///
+/// A file repeating a K/V in either column doesn't compile:
+///
+/// ```compile_fail
+/// #[macro_use] extern crate bidir_map_derive;
+/// extern crate bidir_map;
+///
+/// use bidir_map::StaticBidirMap;
+///
+/// // tests/data/status_duplicate.tsv:3: duplicate second K/V "OK", already on line 1
+/// static STATUS: StaticBidirMap<u16, &str> = include_bidir_map!("tests/data/status_duplicate.tsv", u16, &'static str);
+/// # fn main() {}
+/// ```
+#[proc_macro]
+pub fn include_bidir_map(input: TokenStream) -> TokenStream {
+	match parse_include(input).and_then(|i| include(&i)) {
+		Ok(tokens) => tokens,
+		Err(err) => err.into_compile_error_expr(),
+	}
+}
+
+/// Generates a newtype around a `bidir_map::BidirMap`, whose methods name the columns after their meaning.
+///
+/// Invoked as `named_bidir_map!(Name, first: FirstType, second: SecondType)`, optionally with attributes and a visibility before `Name`,
+/// this defines `Name` wrapping a `BidirMap<FirstType, SecondType>`, with the inherent methods
+///
+///   * `new()` and `with_capacity()`,
+///   * `insert(first, second)`,
+///   * `get_by_first()`, `get_mut_by_first()`, `contains_first()` and `remove_by_first()`,
+///   * `get_by_second()`, `get_mut_by_second()`, `contains_second()` and `remove_by_second()`,
+///   * `into_inner()`,
+///
+/// where `first` and `second` are the given column names, and which behave like `BidirMap`'s methods of the same shape.
+/// `Name` dereferences to the `BidirMap` for everything else, like `len()` and `iter()`,
+/// converts from and into it, and implements `Default`, `FromIterator`, `Extend` and `IntoIterator`.
+/// Traits that depend on the K/V types, like `Clone` and `Debug`, can be derived by attributes.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use] extern crate bidir_map_derive;
+/// extern crate bidir_map;
+///
+/// named_bidir_map!(
+///     /// Countries and their dialling prefixes
+///     #[derive(Clone, Debug, PartialEq)]
+///     pub PhonePrefixMap, country: String, prefix: u16
+/// );
+///
+/// # fn main() {
+/// let mut prefixes = PhonePrefixMap::new();
+/// prefixes.insert("PL".to_string(), 48);
+/// prefixes.insert("FR".to_string(), 33);
+///
+/// assert_eq!(prefixes.get_by_country("PL"), Some(&48));
+/// assert_eq!(prefixes.get_by_prefix(&33).map(String::as_str), Some("FR"));
+/// assert!(prefixes.contains_prefix(&48));
+/// assert_eq!(prefixes.remove_by_country("FR"), Some(("FR".to_string(), 33)));
+///
+/// // Anything else is the BidirMap's
+/// assert_eq!(prefixes.len(), 1);
+/// let map: bidir_map::BidirMap<String, u16> = prefixes.into();
+/// assert_eq!(map.get_by_first("PL"), Some(&48));
+/// # }
+/// ```
+///
+/// The columns need different names:
+///
+/// ```compile_fail
+/// #[macro_use] extern crate bidir_map_derive;
+/// extern crate bidir_map;
+///
+/// named_bidir_map!(Renames, name: String, name: String);
+/// # fn main() {}
+/// ```
+///
+/// And each one a type:
+///
+/// ```compile_fail
+/// #[macro_use] extern crate bidir_map_derive;
+/// extern crate bidir_map;
+///
+/// named_bidir_map!(PhonePrefixMap, country: String, prefix);
+/// # fn main() {}
+/// ```
+#[proc_macro]
+pub fn named_bidir_map(input: TokenStream) -> TokenStream {
+	match parse_named(input).and_then(|n| named(&n)) {
+		Ok(tokens) => tokens,
+		Err(err) => err.into_compile_error(),
+	}
+}
+
+
+struct Error {
+	span: Span,
+	message: String,
+}
+
+impl Error {
+	fn new<M: Into<String>>(span: Span, message: M) -> Error {
+		Error{
+			span,
+			message: message.into(),
+		}
+	}
+
+	fn into_compile_error(self) -> TokenStream {
+		let mut semi = Punct::new(';', Spacing::Alone);
+		semi.set_span(self.span);
+
+		let mut tokens = self.into_compile_error_expr();
+		tokens.extend(Some(TokenTree::Punct(semi)));
+		tokens
+	}
+
+	/// The `compile_error!()` invocation, for expression position
+	fn into_compile_error_expr(self) -> TokenStream {
+		let mut message = Literal::string(&self.message);
+		message.set_span(self.span);
+		let mut args = Group::new(Delimiter::Parenthesis, TokenStream::from(TokenTree::Literal(message)));
+		args.set_span(self.span);
+		let mut bang = Punct::new('!', Spacing::Alone);
+		bang.set_span(self.span);
+
+		TokenStream::from_iter(vec![TokenTree::Ident(Ident::new("compile_error", self.span)),
+		                            TokenTree::Punct(bang),
+		                            TokenTree::Group(args)])
+	}
+}
+
+
+struct Enum {
+	name: Ident,
+	/// The arguments of every `#[bidir(...)]` attribute on the enum itself
+	attrs: Vec<Group>,
+	variants: Vec<Variant>,
+}
+
+struct Variant {
+	name: Ident,
+	/// The arguments of every `#[bidir(...)]` attribute on the variant
+	attrs: Vec<Group>,
+}
+
+fn parse_enum(input: TokenStream, derive: &str) -> Result<Enum, Error> {
+	let mut tokens = input.into_iter().peekable();
+	let attrs = take_bidir_attrs(&mut tokens);
+
+	loop {
+		match tokens.next() {
+			Some(TokenTree::Ident(ref kw)) if kw.to_string() == "enum" => break,
+			Some(TokenTree::Ident(ref kw)) if kw.to_string() == "struct" || kw.to_string() == "union" => {
+				return Err(Error::new(kw.span(), format!("#[derive({})] is only supported on enums", derive)))
+			}
+			Some(_) => {}
+			None => return Err(Error::new(Span::call_site(), "expected an enum")),
+		}
+	}
+
+	let name = match tokens.next() {
+		Some(TokenTree::Ident(name)) => name,
+		_ => return Err(Error::new(Span::call_site(), "expected the enum's name")),
+	};
+
+	let body = match tokens.next() {
+		Some(TokenTree::Group(ref body)) if body.delimiter() == Delimiter::Brace => body.stream(),
+		Some(tt) => return Err(Error::new(tt.span(), format!("#[derive({})] doesn't support generic enums", derive))),
+		None => return Err(Error::new(name.span(), "expected the enum's body")),
+	};
+
+	let mut variants = Vec::new();
+	let mut body = body.into_iter().peekable();
+	while body.peek().is_some() {
+		let attrs = take_bidir_attrs(&mut body);
+		let name = match body.next() {
+			Some(TokenTree::Ident(name)) => name,
+			Some(tt) => return Err(Error::new(tt.span(), "expected a variant name")),
+			None => break,
+		};
+
+		// Skip an explicit discriminant, reject fields
+		for tt in body.by_ref() {
+			match tt {
+				TokenTree::Punct(ref p) if p.as_char() == ',' => break,
+				TokenTree::Group(ref g) if g.delimiter() != Delimiter::None => {
+					return Err(Error::new(g.span(), format!("#[derive({})] requires all variants to be field-less", derive)))
+				}
+				_ => {}
+			}
+		}
+
+		variants.push(Variant{
+			name,
+			attrs,
+		});
+	}
+
+	Ok(Enum{
+		name,
+		attrs,
+		variants,
+	})
+}
+
+/// Consumes leading outer attributes, returning the argument groups of the `#[bidir(...)]` ones
+fn take_bidir_attrs<I: Iterator<Item = TokenTree>>(tokens: &mut std::iter::Peekable<I>) -> Vec<Group> {
+	let mut attrs = Vec::new();
+	loop {
+		match tokens.peek() {
+			Some(TokenTree::Punct(p)) if p.as_char() == '#' => {}
+			_ => return attrs,
+		}
+		tokens.next();
+
+		if let Some(TokenTree::Group(attr)) = tokens.next() {
+			let mut inner = attr.stream().into_iter();
+			match (inner.next(), inner.next()) {
+				(Some(TokenTree::Ident(ref path)), Some(TokenTree::Group(args))) if path.to_string() == "bidir" => attrs.push(args),
+				_ => {}
+			}
+		}
+	}
+}
+
+
+fn bidir_mapping(e: &Enum) -> Result<TokenStream, Error> {
+	let mut second_type = None;
+	for attr in &e.attrs {
+		let tokens: Vec<_> = attr.stream().into_iter().collect();
+		match (tokens.first(), tokens.get(1), tokens.get(2), tokens.len()) {
+			(Some(TokenTree::Ident(key)), Some(TokenTree::Punct(eq)), Some(TokenTree::Literal(ty)), 3)
+				if key.to_string() == "second" && eq.as_char() == '=' => {
+				let ty = ty.to_string();
+				if !ty.starts_with('"') || !ty.ends_with('"') || ty.len() < 2 {
+					return Err(Error::new(attr.span(), "expected #[bidir(second = \"Type\")]"));
+				}
+				second_type = Some(ty[1..ty.len() - 1].to_string());
+			}
+			_ => return Err(Error::new(attr.span(), "expected #[bidir(second = \"Type\")]")),
+		}
+	}
+	let second_type = second_type.unwrap_or_else(|| "str".to_string());
+	let is_str = second_type == "str";
+
+	let mut values: Vec<(String, TokenStream)> = Vec::with_capacity(e.variants.len());
+	for variant in &e.variants {
+		let value = match variant.attrs.len() {
+			1 => &variant.attrs[0],
+			0 => return Err(Error::new(variant.name.span(), format!("variant `{}` has no #[bidir(value)] mapping", variant.name))),
+			_ => return Err(Error::new(variant.attrs[1].span(), format!("variant `{}` has more than one #[bidir(value)] mapping", variant.name))),
+		};
+		if value.stream().is_empty() {
+			return Err(Error::new(value.span(), format!("variant `{}` has an empty #[bidir()] mapping", variant.name)));
+		}
+
+		let value_str = value.stream().to_string();
+		if let Some(idx) = values.iter().position(|v| v.0 == value_str) {
+			return Err(Error::new(value.span(),
+			                      format!("variants `{}` and `{}` are both mapped to {}", e.variants[idx].name, variant.name, value_str)));
+		}
+		values.push((value_str, value.stream()));
+	}
+
+	let name = e.name.to_string();
+	let (value_type, query_type, query_expr) = if is_str {
+		("&'static str".to_string(), "str".to_string(), "value")
+	} else {
+		(second_type.clone(), second_type, "*value")
+	};
+
+	let mut inserts = String::new();
+	let mut to_arms = String::new();
+	let mut from_arms = String::new();
+	for (idx, (variant, value)) in e.variants.iter().zip(&values).enumerate() {
+		inserts.push_str(&format!("map.insert({}::{}, {});\n", name, variant.name, value.0));
+		to_arms.push_str(&format!("{}::{} => {},\n", name, variant.name, value.0));
+		from_arms.push_str(&format!("__bidir_value_{} => ::std::option::Option::Some({}::{}),\n", idx, name, variant.name));
+	}
+
+	// from_mapped() denies unreachable patterns, which catches the duplicates the textual comparison above can't, like 1 and 0x1.
+	// Its patterns are the values' own tokens, spliced in below: rustc doesn't report that lint in code spanned to the derive
+	let generated = format!(r#"
+		impl {name} {{
+			/// Returns the map between every variant and the value it's mapped to, building it on first use.
+			#[allow(dead_code)]
+			pub fn bidir_map() -> &'static ::bidir_map::BidirMap<{name}, {value_type}> {{
+				static MAP: ::std::sync::OnceLock<::bidir_map::BidirMap<{name}, {value_type}>> = ::std::sync::OnceLock::new();
+				MAP.get_or_init(|| {{
+					let mut map = ::bidir_map::BidirMap::with_capacity({count});
+					{inserts}
+					map
+				}})
+			}}
+
+			/// Returns the value this variant is mapped to.
+			#[allow(dead_code)]
+			pub fn to_mapped(&self) -> {value_type} {{
+				match *self {{
+					{to_arms}
+				}}
+			}}
+
+			/// Returns the variant mapped to the specified value, if any.
+			#[allow(dead_code)]
+			#[deny(unreachable_patterns)]
+			pub fn from_mapped(value: &{query_type}) -> ::std::option::Option<{name}> {{
+				match {query_expr} {{
+					{from_arms}
+					_ => ::std::option::Option::None,
+				}}
+			}}
+		}}
+	"#,
+	                        name = name,
+	                        value_type = value_type,
+	                        query_type = query_type,
+	                        query_expr = query_expr,
+	                        count = e.variants.len(),
+	                        inserts = inserts,
+	                        to_arms = to_arms,
+	                        from_arms = from_arms);
+
+	generated.parse()
+		.map(|generated| splice_values(generated, &values))
+		.map_err(|_| Error::new(e.name.span(), "#[derive(BidirMapping)] generated invalid code; check the #[bidir] values"))
+}
+
+/// Replaces every `__bidir_value_N` placeholder with the tokens of the Nth value, keeping their spans
+fn splice_values(tokens: TokenStream, values: &[(String, TokenStream)]) -> TokenStream {
+	tokens.into_iter()
+		.map(|token| match token {
+			TokenTree::Ident(ref ident) if ident.to_string().starts_with("__bidir_value_") => {
+				let idx: usize = ident.to_string()["__bidir_value_".len()..].parse().unwrap();
+				TokenTree::Group(Group::new(Delimiter::None, values[idx].1.clone()))
+			}
+			TokenTree::Group(group) => {
+				let mut spliced = Group::new(group.delimiter(), splice_values(group.stream(), values));
+				spliced.set_span(group.span());
+				TokenTree::Group(spliced)
+			}
+			token => token,
+		})
+		.collect()
+}
+
+fn enum_like(e: &Enum) -> Result<TokenStream, Error> {
+	let name = e.name.to_string();
+	let mut to_arms = String::new();
+	let mut from_arms = String::new();
+	for (idx, variant) in e.variants.iter().enumerate() {
+		to_arms.push_str(&format!("{}::{} => {},\n", name, variant.name, idx));
+		from_arms.push_str(&format!("{} => ::std::option::Option::Some({}::{}),\n", idx, name, variant.name));
+	}
+
+	let generated = format!(r#"
+		impl ::bidir_map::EnumLike for {name} {{
+			const VARIANTS: usize = {count};
+			type Array<T> = [T; {count}];
+
+			fn to_index(&self) -> usize {{
+				match *self {{
+					{to_arms}
+				}}
+			}}
+
+			fn from_index(index: usize) -> ::std::option::Option<{name}> {{
+				match index {{
+					{from_arms}
+					_ => ::std::option::Option::None,
+				}}
+			}}
+
+			fn array_from_fn<T, F: ::std::ops::FnMut(usize) -> T>(f: F) -> [T; {count}] {{
+				::std::array::from_fn(f)
+			}}
+		}}
+	"#,
+	                        name = name,
+	                        count = e.variants.len(),
+	                        to_arms = to_arms,
+	                        from_arms = from_arms);
+
+	generated.parse().map_err(|_| Error::new(e.name.span(), "#[derive(EnumLike)] generated invalid code"))
+}
+
+
+struct Include {
+	/// As written in the invocation, for error messages
+	path: String,
+	first: Kind,
+	second: Kind,
+}
+
+/// The types `include_bidir_map!` can parse a column as
+#[derive(Clone, Copy)]
+enum Kind {
+	Int{
+		suffix: &'static str,
+		min: i128,
+		max: i128,
+	},
+	Char,
+	Bool,
+	Str,
+}
+
+/// A parsed K/V, ordered as its type is
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Value {
+	/// The value and the literal suffix of its type
+	Int(i128, &'static str),
+	Char(char),
+	Bool(bool),
+	Str(String),
+}
+
+impl Value {
+	fn to_literal(&self) -> String {
+		match *self {
+			Value::Int(v, suffix) => format!("{}{}", v, suffix),
+			Value::Char(c) => Literal::character(c).to_string(),
+			Value::Bool(b) => b.to_string(),
+			Value::Str(ref s) => Literal::string(s).to_string(),
+		}
+	}
+}
+
+fn parse_include(input: TokenStream) -> Result<Include, Error> {
+	let mut args = vec![Vec::new()];
+	for tt in input {
+		match tt {
+			TokenTree::Punct(ref p) if p.as_char() == ',' => args.push(Vec::new()),
+			tt => args.last_mut().unwrap().push(tt),
+		}
+	}
+	if args.last().is_some_and(|arg| arg.is_empty()) && args.len() > 1 {
+		args.pop();
+	}
+	if args.len() != 3 {
+		return Err(Error::new(Span::call_site(), "expected include_bidir_map!(\"path\", FirstType, SecondType)"));
+	}
+
+	let path = match args[0].as_slice() {
+		[TokenTree::Literal(lit)] => {
+			let lit = lit.to_string();
+			if !lit.starts_with('"') || !lit.ends_with('"') || lit.len() < 2 || lit.contains('\\') {
+				return Err(Error::new(args[0][0].span(), "expected the path as a plain string literal"));
+			}
+			lit[1..lit.len() - 1].to_string()
+		}
+		_ => return Err(Error::new(Span::call_site(), "expected the path as a plain string literal")),
+	};
+
+	Ok(Include{
+		path,
+		first: parse_kind(&args[1])?,
+		second: parse_kind(&args[2])?,
+	})
+}
+
+fn parse_kind(ty: &[TokenTree]) -> Result<Kind, Error> {
+	let name: String = TokenStream::from_iter(ty.iter().cloned()).to_string().split_whitespace().collect();
+	let int = |suffix, min, max| Kind::Int{
+		suffix,
+		min,
+		max,
+	};
+	Ok(match &name[..] {
+		"u8" => int("u8", 0, u8::MAX as i128),
+		"u16" => int("u16", 0, u16::MAX as i128),
+		"u32" => int("u32", 0, u32::MAX as i128),
+		"u64" => int("u64", 0, u64::MAX as i128),
+		"usize" => int("usize", 0, u64::MAX as i128),
+		"i8" => int("i8", i8::MIN as i128, i8::MAX as i128),
+		"i16" => int("i16", i16::MIN as i128, i16::MAX as i128),
+		"i32" => int("i32", i32::MIN as i128, i32::MAX as i128),
+		"i64" => int("i64", i64::MIN as i128, i64::MAX as i128),
+		"isize" => int("isize", i64::MIN as i128, i64::MAX as i128),
+		"char" => Kind::Char,
+		"bool" => Kind::Bool,
+		"&'staticstr" | "&str" => Kind::Str,
+		_ => {
+			let span = ty.first().map_or_else(Span::call_site, TokenTree::span);
+			return Err(Error::new(span, format!("include_bidir_map! can't parse `{}`; expected an integer of up to 64 bits, char, bool or &'static str", name)));
+		}
+	})
+}
+
+fn parse_value(field: &str, kind: Kind) -> Result<Value, String> {
+	match kind {
+		Kind::Int{ suffix, min, max } => {
+			match field.trim().parse::<i128>() {
+				Ok(v) if min <= v && v <= max => Ok(Value::Int(v, suffix)),
+				Ok(_) => Err(format!("{} is out of range for {}", field.trim(), suffix)),
+				Err(_) => Err(format!("{:?} isn't an integer", field)),
+			}
+		}
+		Kind::Char => {
+			let mut chars = field.chars();
+			match (chars.next(), chars.next()) {
+				(Some(c), None) => Ok(Value::Char(c)),
+				_ => Err(format!("{:?} isn't a single char", field)),
+			}
+		}
+		Kind::Bool => {
+			match field.trim() {
+				"true" => Ok(Value::Bool(true)),
+				"false" => Ok(Value::Bool(false)),
+				_ => Err(format!("{:?} isn't a bool", field)),
+			}
+		}
+		Kind::Str => Ok(Value::Str(field.to_string())),
+	}
+}
+
+fn include(i: &Include) -> Result<TokenStream, Error> {
+	let full_path = match env::var("CARGO_MANIFEST_DIR") {
+		Ok(dir) => Path::new(&dir).join(&i.path),
+		Err(_) => Path::new(&i.path).to_path_buf(),
+	};
+	let text = fs::read_to_string(&full_path).map_err(|err| Error::new(Span::call_site(), format!("couldn't read {}: {}", i.path, err)))?;
+
+	let mut pairs: Vec<(Value, Value)> = Vec::new();
+	let mut first_lines = BTreeMap::new();
+	let mut second_lines = BTreeMap::new();
+	for (line_no, line) in text.lines().enumerate().map(|(idx, line)| (idx + 1, line)) {
+		if line.trim().is_empty() || line.starts_with('#') {
+			continue;
+		}
+		let at = |message: String| Error::new(Span::call_site(), format!("{}:{}: {}", i.path, line_no, message));
+
+		let mut fields = line.split('\t');
+		let (first, second) = match (fields.next(), fields.next(), fields.next()) {
+			(Some(first), Some(second), None) => (first, second),
+			_ => return Err(at("expected two tab-separated columns".to_string())),
+		};
+		let first = parse_value(first, i.first).map_err(&at)?;
+		let second = parse_value(second, i.second).map_err(&at)?;
+
+		if let Some(seen) = first_lines.insert(first.clone(), line_no) {
+			return Err(at(format!("duplicate first K/V {}, already on line {}", first.to_literal(), seen)));
+		}
+		if let Some(seen) = second_lines.insert(second.clone(), line_no) {
+			return Err(at(format!("duplicate second K/V {}, already on line {}", second.to_literal(), seen)));
+		}
+		pairs.push((first, second));
+	}
+
+	let mut by_first: Vec<usize> = (0..pairs.len()).collect();
+	by_first.sort_by(|&l, &r| pairs[l].0.cmp(&pairs[r].0));
+	let mut by_second: Vec<usize> = (0..pairs.len()).collect();
+	by_second.sort_by(|&l, &r| pairs[l].1.cmp(&pairs[r].1));
+
+	let mut table = String::new();
+	for (first, second) in &pairs {
+		table.push_str(&format!("({}, {}),\n", first.to_literal(), second.to_literal()));
+	}
+	let join = |indices: &[usize]| indices.iter().map(|idx| idx.to_string()).collect::<Vec<_>>().join(", ");
+
+	let generated = format!(r#"
+		{{
+			const _: &str = include_str!({full_path});
+			::bidir_map::StaticBidirMap::from_parts(&[
+				{table}
+			], &[{by_first}], &[{by_second}])
+		}}
+	"#,
+	                        full_path = Literal::string(&full_path.to_string_lossy()),
+	                        table = table,
+	                        by_first = join(&by_first),
+	                        by_second = join(&by_second));
+
+	generated.parse().map_err(|_| Error::new(Span::call_site(), "include_bidir_map! generated invalid code"))
+}
+
+
+struct Named {
+	/// The attributes and the visibility of the newtype, as written
+	prefix: String,
+	name: Ident,
+	first: Column,
+	second: Column,
+}
+
+/// A column of a `named_bidir_map!`
+struct Column {
+	name: Ident,
+	/// As written
+	ty: String,
+}
+
+/// Splits the tokens at the commas outside of angle brackets
+fn split_args(input: TokenStream) -> Vec<Vec<TokenTree>> {
+	let mut args = vec![Vec::new()];
+	let mut depth = 0usize;
+	let mut after_dash = false;
+	for tt in input {
+		match tt {
+			TokenTree::Punct(ref p) if p.as_char() == ',' && depth == 0 => {
+				args.push(Vec::new());
+				after_dash = false;
+				continue;
+			}
+			TokenTree::Punct(ref p) if p.as_char() == '<' => depth += 1,
+			// Not the `>` of an `->`
+			TokenTree::Punct(ref p) if p.as_char() == '>' && !after_dash => depth = depth.saturating_sub(1),
+			_ => {}
+		}
+		after_dash = matches!(tt, TokenTree::Punct(ref p) if p.as_char() == '-' && p.spacing() == Spacing::Joint);
+		args.last_mut().unwrap().push(tt);
+	}
+	if args.last().is_some_and(|arg| arg.is_empty()) && args.len() > 1 {
+		args.pop();
+	}
+	args
+}
+
+fn parse_named(input: TokenStream) -> Result<Named, Error> {
+	const USAGE: &str = "expected named_bidir_map!(Name, first: FirstType, second: SecondType)";
+
+	let args = split_args(input);
+	if args.len() != 3 {
+		return Err(Error::new(Span::call_site(), USAGE));
+	}
+
+	let (name, prefix) = match args[0].split_last() {
+		Some((TokenTree::Ident(name), prefix)) if name.to_string() != "pub" => (name.clone(), TokenStream::from_iter(prefix.iter().cloned()).to_string()),
+		Some((tt, _)) => return Err(Error::new(tt.span(), USAGE)),
+		None => return Err(Error::new(Span::call_site(), USAGE)),
+	};
+
+	let first = parse_column(&args[1], USAGE)?;
+	let second = parse_column(&args[2], USAGE)?;
+	if first.name.to_string() == second.name.to_string() {
+		return Err(Error::new(second.name.span(), format!("both columns are named `{}`", first.name)));
+	}
+
+	Ok(Named{
+		prefix,
+		name,
+		first,
+		second,
+	})
+}
+
+fn parse_column(arg: &[TokenTree], usage: &str) -> Result<Column, Error> {
+	match arg {
+		[TokenTree::Ident(name), TokenTree::Punct(colon), ty @ ..] if colon.as_char() == ':' && !ty.is_empty() => {
+			Ok(Column{
+				name: name.clone(),
+				ty: TokenStream::from_iter(ty.iter().cloned()).to_string(),
+			})
+		}
+		[TokenTree::Ident(name), ..] => Err(Error::new(name.span(), format!("expected a type for column `{}`, as in `{}: Type`", name, name))),
+		_ => Err(Error::new(arg.first().map_or_else(Span::call_site, TokenTree::span), usage)),
+	}
+}
+
+fn named(n: &Named) -> Result<TokenStream, Error> {
+	let name = n.name.to_string();
+	let (first, second) = (n.first.name.to_string(), n.second.name.to_string());
+	let (t1, t2) = (&n.first.ty, &n.second.ty);
+
+	let generated = format!(r#"
+		{prefix} struct {name}(::bidir_map::BidirMap<{t1}, {t2}>);
+
+		#[allow(dead_code)]
+		impl {name} {{
+			/// Create a new empty instance of `{name}`
+			pub fn new() -> Self {{
+				{name}(::bidir_map::BidirMap::new())
+			}}
+
+			/// Create a new empty instance of `{name}` with the specified capacity.
+			pub fn with_capacity(capacity: usize) -> Self {{
+				{name}(::bidir_map::BidirMap::with_capacity(capacity))
+			}}
+
+			/// Inserts a {first}-{second} pair into the map, returning the pair it displaced; see `BidirMap::insert()`.
+			pub fn insert(&mut self, {first}: {t1}, {second}: {t2}) -> ::std::option::Option<({t1}, {t2})> {{
+				self.0.insert({first}, {second})
+			}}
+
+			/// Returns a reference to the {second} corresponding to the {first}.
+			pub fn get_by_{first}<Q>(&self, {first}: &Q) -> ::std::option::Option<&{t2}>
+				where {t1}: ::std::borrow::Borrow<Q>,
+				      Q: ::std::cmp::PartialEq + ?::std::marker::Sized,
+			{{
+				self.0.get_by_first({first})
+			}}
+
+			/// Returns a reference to the {first} corresponding to the {second}.
+			pub fn get_by_{second}<Q>(&self, {second}: &Q) -> ::std::option::Option<&{t1}>
+				where {t2}: ::std::borrow::Borrow<Q>,
+				      Q: ::std::cmp::PartialEq + ?::std::marker::Sized,
+			{{
+				self.0.get_by_second({second})
+			}}
+
+			/// Returns a mutable reference to the {second} corresponding to the {first}.
+			pub fn get_mut_by_{first}<Q>(&mut self, {first}: &Q) -> ::std::option::Option<&mut {t2}>
+				where {t1}: ::std::borrow::Borrow<Q>,
+				      Q: ::std::cmp::PartialEq + ?::std::marker::Sized,
+			{{
+				self.0.get_mut_by_first({first})
+			}}
+
+			/// Returns a mutable reference to the {first} corresponding to the {second}.
+			pub fn get_mut_by_{second}<Q>(&mut self, {second}: &Q) -> ::std::option::Option<&mut {t1}>
+				where {t2}: ::std::borrow::Borrow<Q>,
+				      Q: ::std::cmp::PartialEq + ?::std::marker::Sized,
+			{{
+				self.0.get_mut_by_second({second})
+			}}
+
+			/// Check if the map contains the {first}
+			pub fn contains_{first}<Q>(&self, {first}: &Q) -> bool
+				where {t1}: ::std::borrow::Borrow<Q>,
+				      Q: ::std::cmp::PartialEq + ?::std::marker::Sized,
+			{{
+				self.0.contains_first_key({first})
+			}}
+
+			/// Check if the map contains the {second}
+			pub fn contains_{second}<Q>(&self, {second}: &Q) -> bool
+				where {t2}: ::std::borrow::Borrow<Q>,
+				      Q: ::std::cmp::PartialEq + ?::std::marker::Sized,
+			{{
+				self.0.contains_second_key({second})
+			}}
+
+			/// Removes the pair with the {first} from the map, returning it if it was in the map.
+			pub fn remove_by_{first}<Q>(&mut self, {first}: &Q) -> ::std::option::Option<({t1}, {t2})>
+				where {t1}: ::std::borrow::Borrow<Q>,
+				      Q: ::std::cmp::PartialEq + ?::std::marker::Sized,
+			{{
+				self.0.remove_by_first({first})
+			}}
+
+			/// Removes the pair with the {second} from the map, returning it if it was in the map.
+			pub fn remove_by_{second}<Q>(&mut self, {second}: &Q) -> ::std::option::Option<({t1}, {t2})>
+				where {t2}: ::std::borrow::Borrow<Q>,
+				      Q: ::std::cmp::PartialEq + ?::std::marker::Sized,
+			{{
+				self.0.remove_by_second({second})
+			}}
+
+			/// Unwraps the map.
+			pub fn into_inner(self) -> ::bidir_map::BidirMap<{t1}, {t2}> {{
+				self.0
+			}}
+		}}
+
+		impl ::std::default::Default for {name} {{
+			fn default() -> Self {{
+				{name}::new()
+			}}
+		}}
+
+		impl ::std::ops::Deref for {name} {{
+			type Target = ::bidir_map::BidirMap<{t1}, {t2}>;
+
+			fn deref(&self) -> &Self::Target {{
+				&self.0
+			}}
+		}}
+
+		impl ::std::ops::DerefMut for {name} {{
+			fn deref_mut(&mut self) -> &mut Self::Target {{
+				&mut self.0
+			}}
+		}}
+
+		impl ::std::convert::From<::bidir_map::BidirMap<{t1}, {t2}>> for {name} {{
+			fn from(map: ::bidir_map::BidirMap<{t1}, {t2}>) -> Self {{
+				{name}(map)
+			}}
+		}}
+
+		impl ::std::convert::From<{name}> for ::bidir_map::BidirMap<{t1}, {t2}> {{
+			fn from(map: {name}) -> Self {{
+				map.0
+			}}
+		}}
+
+		impl ::std::iter::FromIterator<({t1}, {t2})> for {name} {{
+			fn from_iter<T: ::std::iter::IntoIterator<Item = ({t1}, {t2})>>(iter: T) -> Self {{
+				{name}(::std::iter::FromIterator::from_iter(iter))
+			}}
+		}}
+
+		impl ::std::iter::Extend<({t1}, {t2})> for {name} {{
+			fn extend<T: ::std::iter::IntoIterator<Item = ({t1}, {t2})>>(&mut self, iter: T) {{
+				self.0.extend(iter)
+			}}
+		}}
+
+		impl ::std::iter::IntoIterator for {name} {{
+			type Item = ({t1}, {t2});
+			type IntoIter = <::bidir_map::BidirMap<{t1}, {t2}> as ::std::iter::IntoIterator>::IntoIter;
+
+			fn into_iter(self) -> Self::IntoIter {{
+				self.0.into_iter()
+			}}
+		}}
+
+		impl<'a> ::std::iter::IntoIterator for &'a {name} {{
+			type Item = &'a ({t1}, {t2});
+			type IntoIter = ::bidir_map::Iter<'a, {t1}, {t2}>;
+
+			fn into_iter(self) -> Self::IntoIter {{
+				self.0.iter()
+			}}
+		}}
+	"#,
+	                        prefix = n.prefix,
+	                        name = name,
+	                        first = first,
+	                        second = second,
+	                        t1 = t1,
+	                        t2 = t2);
+
+	generated.parse().map_err(|_| Error::new(n.name.span(), "named_bidir_map! generated invalid code"))
+}
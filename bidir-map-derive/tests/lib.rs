@@ -0,0 +1,143 @@
+#[macro_use]
+extern crate bidir_map_derive;
+extern crate bidir_map;
+
+use bidir_map::{BidirEnumMap, BidirMap, EnumLike};
+
+
+#[derive(BidirMapping, Clone, Copy, Debug, PartialEq)]
+#[bidir(second = "str")]
+pub enum Opcode {
+	/// Doc comments don't get in the way
+	#[bidir("GET")]
+	Get,
+	#[bidir("PUT")]
+	Put,
+	#[bidir("DELETE")]
+	Delete = 10,
+}
+
+#[derive(BidirMapping, Debug, PartialEq)]
+#[bidir(second = "i16")]
+enum Signed {
+	#[bidir(-1)]
+	Negative,
+	#[bidir(0)]
+	Zero,
+	#[bidir(1)]
+	Positive,
+}
+
+named_bidir_map!(
+	/// Attributes are kept
+	#[derive(Clone, Debug, PartialEq)]
+	pub(crate) PhonePrefixMap, country: String, prefix: u16,
+);
+
+named_bidir_map!(Outcomes, code: u8, outcome: Result<u16, char>);
+
+#[test]
+fn named_accessors() {
+	let mut prefixes: PhonePrefixMap = vec![("PL".to_string(), 48), ("FR".to_string(), 33)].into_iter().collect();
+	assert_eq!(prefixes.insert("DE".to_string(), 49), None);
+	assert_eq!(prefixes.insert("DE".to_string(), 490), Some(("DE".to_string(), 49)));
+
+	assert_eq!(prefixes.get_by_country("FR"), Some(&33));
+	assert_eq!(prefixes.get_by_prefix(&490).map(String::as_str), Some("DE"));
+	assert!(prefixes.contains_country("PL"));
+	assert!(!prefixes.contains_prefix(&49));
+	*prefixes.get_mut_by_country("DE").unwrap() = 49;
+	prefixes.get_mut_by_prefix(&48).unwrap().make_ascii_lowercase();
+	assert_eq!(prefixes.remove_by_prefix(&33), Some(("FR".to_string(), 33)));
+	assert_eq!(prefixes.remove_by_country("FR"), None);
+
+	let copy = prefixes.clone();
+	assert_eq!(copy, prefixes);
+	assert_eq!((&prefixes).into_iter().count(), 2);
+	let map: BidirMap<String, u16> = prefixes.into_inner();
+	assert_eq!(map.get_by_first("pl"), Some(&48));
+	assert_eq!(PhonePrefixMap::from(map).into_iter().map(|(_, prefix)| prefix).sum::<u16>(), 48 + 49);
+}
+
+#[test]
+fn named_with_generic_types() {
+	let mut outcomes = Outcomes::default();
+	outcomes.insert(0, Ok(200));
+	outcomes.extend(vec![(1, Err('x'))]);
+	assert_eq!(outcomes.get_by_outcome(&Err('x')), Some(&1));
+	assert_eq!(outcomes.get_by_code(&0), Some(&Ok(200)));
+	assert_eq!(outcomes.len(), 2);
+}
+
+#[derive(BidirMapping, Debug, PartialEq)]
+enum DefaultsToStr {
+	#[bidir("only")]
+	Only,
+}
+
+#[derive(EnumLike, BidirMapping, Clone, Copy, Debug, PartialEq)]
+enum Both {
+	#[bidir("first")]
+	First = 5,
+	/// Attributes don't get in the way
+	#[bidir("second")]
+	Second,
+	#[bidir("third")]
+	Third = 1,
+}
+
+#[derive(EnumLike)]
+enum Empty {}
+
+
+#[test]
+fn string_mapping_round_trips() {
+	for &op in &[Opcode::Get, Opcode::Put, Opcode::Delete] {
+		assert_eq!(Opcode::from_mapped(op.to_mapped()), Some(op));
+	}
+	assert_eq!(Opcode::Delete.to_mapped(), "DELETE");
+	assert_eq!(Opcode::from_mapped("get"), None);
+}
+
+#[test]
+fn generated_map_holds_every_variant() {
+	let map = Opcode::bidir_map();
+	assert_eq!(map.len(), 3);
+	assert_eq!(map.get_by_first(&Opcode::Put), Some(&"PUT"));
+	assert_eq!(map.get_by_second("DELETE"), Some(&Opcode::Delete));
+	assert!(std::ptr::eq(map, Opcode::bidir_map()));
+}
+
+#[test]
+fn non_string_mapping() {
+	assert_eq!(Signed::Negative.to_mapped(), -1);
+	assert_eq!(Signed::from_mapped(&1), Some(Signed::Positive));
+	assert_eq!(Signed::from_mapped(&2), None);
+	assert_eq!(Signed::bidir_map().get_by_second(&0), Some(&Signed::Zero));
+}
+
+#[test]
+fn second_type_defaults_to_str() {
+	assert_eq!(DefaultsToStr::Only.to_mapped(), "only");
+	assert_eq!(DefaultsToStr::from_mapped("only"), Some(DefaultsToStr::Only));
+}
+
+#[test]
+fn enum_like_numbers_in_declaration_order() {
+	assert_eq!(Both::VARIANTS, 3);
+	assert_eq!(Empty::VARIANTS, 0);
+	for (idx, &variant) in [Both::First, Both::Second, Both::Third].iter().enumerate() {
+		assert_eq!(variant.to_index(), idx);
+		assert_eq!(Both::from_index(idx), Some(variant));
+	}
+	assert_eq!(Both::from_index(3), None);
+	assert!(Empty::from_index(0).is_none());
+
+	let mut map = BidirEnumMap::new();
+	for &(variant, value) in Both::bidir_map().iter() {
+		map.insert(variant, value);
+	}
+	assert_eq!(map.get_by_second("third"), Some(Both::Third));
+	map.remove_by_first(&Both::Second);
+	assert_eq!(map.iter().collect::<Vec<_>>(), [(Both::First, &"first"), (Both::Third, &"third")]);
+}
@@ -0,0 +1,101 @@
+//! Inserting pairs next to existing ones, identified by either K/V.
+
+
+use core::borrow::Borrow;
+use {BidirMap, InsertError};
+
+
+impl<Kv1: PartialEq, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
+	/// Inserts a K/V-K/V pair just before the pair with the specified first K/V in iteration order.
+	///
+	/// Pairs sharing a K/V with the new one are removed first, as with `insert()`, though without disturbing the order of the rest;
+	/// if that's the anchor itself, the new pair takes its place.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map: BidirMap<_, _> = vec![(1, "a"), (3, "c")].into_iter().collect();
+	/// assert_eq!(map.insert_before_first(&3, 2, "b"), Ok(()));
+	/// assert_eq!(map.iter().cloned().collect::<Vec<_>>(), [(1, "a"), (2, "b"), (3, "c")]);
+	///
+	/// assert_eq!(map.insert_before_first(&4, 0, "z").unwrap_err().pair, (0, "z"));
+	/// ```
+	pub fn insert_before_first<Q>(&mut self, anchor: &Q, kv1: Kv1, kv2: Kv2) -> Result<(), InsertError<Kv1, Kv2>>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		let anchor = self.cont.iter().position(|kvs| *anchor == *kvs.0.borrow());
+		self.insert_adjacent(anchor, kv1, kv2)
+	}
+
+	/// Inserts a K/V-K/V pair just after the pair with the specified first K/V in iteration order.
+	///
+	/// Pairs sharing a K/V with the new one are removed first, see `insert_before_first()`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map: BidirMap<_, _> = vec![(1, "a"), (3, "c")].into_iter().collect();
+	/// assert_eq!(map.insert_after_first(&1, 2, "b"), Ok(()));
+	/// assert_eq!(map.iter().cloned().collect::<Vec<_>>(), [(1, "a"), (2, "b"), (3, "c")]);
+	/// ```
+	pub fn insert_after_first<Q>(&mut self, anchor: &Q, kv1: Kv1, kv2: Kv2) -> Result<(), InsertError<Kv1, Kv2>>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		let anchor = self.cont.iter().position(|kvs| *anchor == *kvs.0.borrow());
+		self.insert_adjacent(anchor.map(|idx| idx + 1), kv1, kv2)
+	}
+
+	/// Inserts a K/V-K/V pair just before the pair with the specified second K/V in iteration order.
+	///
+	/// Pairs sharing a K/V with the new one are removed first, see `insert_before_first()`.
+	pub fn insert_before_second<Q>(&mut self, anchor: &Q, kv1: Kv1, kv2: Kv2) -> Result<(), InsertError<Kv1, Kv2>>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		let anchor = self.cont.iter().position(|kvs| *anchor == *kvs.1.borrow());
+		self.insert_adjacent(anchor, kv1, kv2)
+	}
+
+	/// Inserts a K/V-K/V pair just after the pair with the specified second K/V in iteration order.
+	///
+	/// Pairs sharing a K/V with the new one are removed first, see `insert_before_first()`.
+	pub fn insert_after_second<Q>(&mut self, anchor: &Q, kv1: Kv1, kv2: Kv2) -> Result<(), InsertError<Kv1, Kv2>>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		let anchor = self.cont.iter().position(|kvs| *anchor == *kvs.1.borrow());
+		self.insert_adjacent(anchor.map(|idx| idx + 1), kv1, kv2)
+	}
+
+	/// Inserts the pair at `slot`, as counted before removing the pairs it collides with
+	fn insert_adjacent(&mut self, slot: Option<usize>, kv1: Kv1, kv2: Kv2) -> Result<(), InsertError<Kv1, Kv2>> {
+		let mut slot = match slot {
+			Some(slot) => slot,
+			None => return Err(InsertError{pair: (kv1, kv2)}),
+		};
+
+		let by_first = self.cont.iter().position(|kvs| kvs.0 == kv1);
+		let by_second = self.cont.iter().position(|kvs| kvs.1 == kv2);
+		if by_first.is_none() && by_second.is_none() {
+			self.assert_room();
+		}
+		let (lower, higher) = if by_first < by_second { (by_first, by_second) } else { (by_second, by_first) };
+		for idx in higher.into_iter().chain(lower.filter(|&idx| Some(idx) != higher)) {
+			self.cont.remove(idx);
+			if idx < slot {
+				slot -= 1;
+			}
+		}
+
+		self.cont.insert(slot, (kv1, kv2));
+		self.rebuild_index();
+		Ok(())
+	}
+}
+
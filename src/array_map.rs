@@ -0,0 +1,420 @@
+//! A fixed-capacity bidirectional map that never allocates.
+
+
+use core::borrow::Borrow;
+use core::{array, slice};
+
+
+/// A bidirectional map storing at most `N` pairs inline.
+///
+/// The API mirrors that of `BidirMap`, except that `insert()` hands the pair back instead of growing past `N` entries,
+/// which makes this type usable on targets without an allocator.
+///
+/// Performance: `O(n)`, mostly.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct BidirArrayMap<Kv1: PartialEq, Kv2: PartialEq, const N: usize> {
+	// The first `len` slots are occupied, the rest are `None`
+	cont: [Option<(Kv1, Kv2)>; N],
+	len: usize,
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq, const N: usize> BidirArrayMap<Kv1, Kv2, N> {
+	/// Create a new empty instance of `BidirArrayMap`
+	pub fn new() -> Self {
+		BidirArrayMap{
+			cont: array::from_fn(|_| None),
+			len: 0,
+		}
+	}
+
+	/// Returns the number of elements the map can hold, i.e. `N`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirArrayMap;
+	///
+	/// let a = BidirArrayMap::<u8, char, 4>::new();
+	/// assert_eq!(a.capacity(), 4);
+	/// ```
+	pub fn capacity(&self) -> usize {
+		N
+	}
+
+	/// Clears the map, removing all entries.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirArrayMap;
+	///
+	/// let mut a = BidirArrayMap::<_, _, 2>::new();
+	/// a.insert(1, "a").unwrap();
+	/// a.clear();
+	/// assert!(a.is_empty());
+	/// ```
+	pub fn clear(&mut self) {
+		for slot in &mut self.cont[..self.len] {
+			*slot = None;
+		}
+		self.len = 0;
+	}
+
+	/// Inserts a K/V-K/V pair into the map.
+	///
+	/// The uniqueness semantics are those of `BidirMap::insert()`:
+	/// if the map did have either K/V present, the pair holding it is replaced and returned as `Ok(Some(..))`.
+	///
+	/// If the map is full and the pair would displace nothing, it's returned as `Err`, leaving the map untouched.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirArrayMap;
+	///
+	/// let mut map = BidirArrayMap::<_, _, 1>::new();
+	/// assert_eq!(map.insert(1, "a"), Ok(None));
+	/// assert_eq!(map.insert(2, "b"), Err((2, "b")));
+	/// assert_eq!(map.insert(1, "b"), Ok(Some((1, "a"))));
+	/// ```
+	pub fn insert(&mut self, kv1: Kv1, kv2: Kv2) -> Result<Option<(Kv1, Kv2)>, (Kv1, Kv2)> {
+		if self.is_full() && !self.contains_first_key(&kv1) && !self.contains_second_key(&kv2) {
+			return Err((kv1, kv2));
+		}
+
+		let by_first = self.remove_by_first(&kv1);
+		let by_second = self.remove_by_second(&kv2);
+
+		self.cont[self.len] = Some((kv1, kv2));
+		self.len += 1;
+
+		Ok(by_first.or(by_second))
+	}
+
+	/// Gets an iterator over the entries of the map.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirArrayMap;
+	///
+	/// let mut map = BidirArrayMap::<_, _, 4>::new();
+	/// map.insert(1, "a").unwrap();
+	/// map.insert(2, "b").unwrap();
+	///
+	/// let first = map.iter().next().unwrap();
+	/// assert_eq!(*first, (1, "a"));
+	/// ```
+	pub fn iter<'s>(&'s self) -> Iter<'s, Kv1, Kv2> {
+		Iter{
+			iter: self.cont[..self.len].iter(),
+		}
+	}
+
+	/// Gets a mutable iterator over the entries of the map.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirArrayMap;
+	///
+	/// let mut map = BidirArrayMap::<_, _, 4>::new();
+	/// map.insert("a", 1).unwrap();
+	/// map.insert("b", 2).unwrap();
+	///
+	/// for kv in map.iter_mut() {
+	///     kv.1 += 10;
+	/// }
+	/// assert_eq!(map.get_by_first(&"b"), Some(&12));
+	/// ```
+	pub fn iter_mut<'s>(&'s mut self) -> IterMut<'s, Kv1, Kv2> {
+		IterMut{
+			iter: self.cont[..self.len].iter_mut(),
+		}
+	}
+
+	/// Gets an iterator over the first K/V of the map.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirArrayMap;
+	///
+	/// let mut a = BidirArrayMap::<_, _, 4>::new();
+	/// a.insert(1, "a").unwrap();
+	/// a.insert(2, "b").unwrap();
+	///
+	/// let keys: Vec<_> = a.first_col().cloned().collect();
+	/// assert_eq!(keys, [1, 2]);
+	/// ```
+	pub fn first_col<'s>(&'s self) -> FirstColumn<'s, Kv1, Kv2> {
+		FirstColumn{
+			iter: self.iter(),
+		}
+	}
+
+	/// Gets an iterator over the second K/V of the map.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirArrayMap;
+	///
+	/// let mut a = BidirArrayMap::<_, _, 4>::new();
+	/// a.insert(1, "a").unwrap();
+	/// a.insert(2, "b").unwrap();
+	///
+	/// let keys: Vec<_> = a.second_col().cloned().collect();
+	/// assert_eq!(keys, ["a", "b"]);
+	/// ```
+	pub fn second_col<'s>(&'s self) -> SecondColumn<'s, Kv1, Kv2> {
+		SecondColumn{
+			iter: self.iter(),
+		}
+	}
+
+	/// Returns the number of elements in the map.
+	pub fn len(&self) -> usize {
+		self.len
+	}
+
+	/// Returns true if the map contains no elements.
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	/// Returns true if the map holds `N` elements and can't accept new ones.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirArrayMap;
+	///
+	/// let mut a = BidirArrayMap::<_, _, 1>::new();
+	/// assert!(!a.is_full());
+	/// a.insert(1, "a").unwrap();
+	/// assert!(a.is_full());
+	/// ```
+	pub fn is_full(&self) -> bool {
+		self.len == N
+	}
+
+
+	/// Returns a reference to the second K/V corresponding to the first K/V.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirArrayMap;
+	///
+	/// let mut map = BidirArrayMap::<_, _, 4>::new();
+	/// map.insert(1, "a").unwrap();
+	/// assert_eq!(map.get_by_first(&1), Some(&"a"));
+	/// assert_eq!(map.get_by_first(&2), None);
+	/// ```
+	pub fn get_by_first<Q>(&self, key: &Q) -> Option<&Kv2>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.iter().find(|kvs| *key == *kvs.0.borrow()).map(|kvs| &kvs.1)
+	}
+
+	/// Returns a reference to the first K/V corresponding to the second K/V.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirArrayMap;
+	///
+	/// let mut map = BidirArrayMap::<_, _, 4>::new();
+	/// map.insert(1, "a").unwrap();
+	/// assert_eq!(map.get_by_second(&"a"), Some(&1));
+	/// assert_eq!(map.get_by_second(&"b"), None);
+	/// ```
+	pub fn get_by_second<Q>(&self, key: &Q) -> Option<&Kv1>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.iter().find(|kvs| *key == *kvs.1.borrow()).map(|kvs| &kvs.0)
+	}
+
+	/// Check if the map contains the first K/V
+	pub fn contains_first_key<Q>(&self, key: &Q) -> bool
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.iter().any(|kvs| *key == *kvs.0.borrow())
+	}
+
+	/// Check if the map contains the second K/V
+	pub fn contains_second_key<Q>(&self, key: &Q) -> bool
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.iter().any(|kvs| *key == *kvs.1.borrow())
+	}
+
+	/// Returns a mutable reference to the second K/V corresponding to the first K/V.
+	pub fn get_mut_by_first<Q>(&mut self, key: &Q) -> Option<&mut Kv2>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.iter_mut().find(|kvs| *key == *kvs.0.borrow()).map(|&mut (_, ref mut kv2)| kv2)
+	}
+
+	/// Returns a mutable reference to the first K/V corresponding to the second K/V.
+	pub fn get_mut_by_second<Q>(&mut self, key: &Q) -> Option<&mut Kv1>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.iter_mut().find(|kvs| *key == *kvs.1.borrow()).map(|&mut (ref mut kv1, _)| kv1)
+	}
+
+	/// Removes the pair corresponding to the first K/V from the map, returning it if the key was previously in the map.
+	///
+	/// Like `BidirMap::remove_by_first()`, the last pair is moved into the vacated slot.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirArrayMap;
+	///
+	/// let mut map = BidirArrayMap::<_, _, 4>::new();
+	/// map.insert(1, "a").unwrap();
+	/// assert_eq!(map.remove_by_first(&1), Some((1, "a")));
+	/// assert_eq!(map.remove_by_first(&1), None);
+	/// ```
+	pub fn remove_by_first<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.iter().position(|kvs| *key == *kvs.0.borrow()).and_then(|idx| self.swap_remove(idx))
+	}
+
+	/// Removes the pair corresponding to the second K/V from the map, returning it if the key was previously in the map.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirArrayMap;
+	///
+	/// let mut map = BidirArrayMap::<_, _, 4>::new();
+	/// map.insert(1, "a").unwrap();
+	/// assert_eq!(map.remove_by_second(&"a"), Some((1, "a")));
+	/// assert_eq!(map.remove_by_second(&"b"), None);
+	/// ```
+	pub fn remove_by_second<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.iter().position(|kvs| *key == *kvs.1.borrow()).and_then(|idx| self.swap_remove(idx))
+	}
+
+	fn swap_remove(&mut self, idx: usize) -> Option<(Kv1, Kv2)> {
+		self.len -= 1;
+		self.cont.swap(idx, self.len);
+		self.cont[self.len].take()
+	}
+}
+
+
+impl<Kv1: PartialEq, Kv2: PartialEq, const N: usize> Default for BidirArrayMap<Kv1, Kv2, N> {
+	fn default() -> Self {
+		BidirArrayMap::new()
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq, const N: usize> IntoIterator for BidirArrayMap<Kv1, Kv2, N> {
+	type Item = (Kv1, Kv2);
+	type IntoIter = IntoIter<Kv1, Kv2, N>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		IntoIter{
+			iter: IntoIterator::into_iter(self.cont),
+		}
+	}
+}
+
+impl<'a, Kv1: PartialEq, Kv2: PartialEq, const N: usize> IntoIterator for &'a BidirArrayMap<Kv1, Kv2, N> {
+	type Item = &'a (Kv1, Kv2);
+	type IntoIter = Iter<'a, Kv1, Kv2>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+
+/// An iterator over the K/V pairs contained in a `BidirArrayMap`.
+///
+/// See documentation of `BidirArrayMap::iter()` for more.
+pub struct Iter<'a, Kv1: 'a, Kv2: 'a> {
+	iter: slice::Iter<'a, Option<(Kv1, Kv2)>>,
+}
+
+impl<'a, Kv1, Kv2> Iterator for Iter<'a, Kv1, Kv2> {
+	type Item = &'a (Kv1, Kv2);
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next().and_then(Option::as_ref)
+	}
+}
+
+
+/// An iterator over mutable K/V pairs contained in a `BidirArrayMap`.
+///
+/// See documentation of `BidirArrayMap::iter_mut()` for more.
+pub struct IterMut<'a, Kv1: 'a, Kv2: 'a> {
+	iter: slice::IterMut<'a, Option<(Kv1, Kv2)>>,
+}
+
+impl<'a, Kv1, Kv2> Iterator for IterMut<'a, Kv1, Kv2> {
+	type Item = &'a mut (Kv1, Kv2);
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next().and_then(Option::as_mut)
+	}
+}
+
+
+/// An iterator the first set of K/Vs in a `BidirArrayMap`.
+///
+/// See documentation of `BidirArrayMap::first_col()` for more.
+pub struct FirstColumn<'a, Kv1: 'a, Kv2: 'a> {
+	iter: Iter<'a, Kv1, Kv2>,
+}
+
+impl<'a, Kv1, Kv2> Iterator for FirstColumn<'a, Kv1, Kv2> {
+	type Item = &'a Kv1;
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next().map(|kvs| &kvs.0)
+	}
+}
+
+
+/// An iterator the second set of K/Vs in a `BidirArrayMap`.
+///
+/// See documentation of `BidirArrayMap::second_col()` for more.
+pub struct SecondColumn<'a, Kv1: 'a, Kv2: 'a> {
+	iter: Iter<'a, Kv1, Kv2>,
+}
+
+impl<'a, Kv1, Kv2> Iterator for SecondColumn<'a, Kv1, Kv2> {
+	type Item = &'a Kv2;
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next().map(|kvs| &kvs.1)
+	}
+}
+
+
+/// An owning iterator over the K/V pairs of a `BidirArrayMap`.
+///
+/// See documentation of `BidirArrayMap::into_iter()` for more.
+pub struct IntoIter<Kv1, Kv2, const N: usize> {
+	iter: array::IntoIter<Option<(Kv1, Kv2)>, N>,
+}
+
+impl<Kv1, Kv2, const N: usize> Iterator for IntoIter<Kv1, Kv2, N> {
+	type Item = (Kv1, Kv2);
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next().and_then(|kvs| kvs)
+	}
+}
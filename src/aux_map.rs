@@ -0,0 +1,287 @@
+//! A `BidirMap` carrying a payload alongside each pair.
+
+
+use core::borrow::Borrow;
+use core::iter::{Extend, FromIterator, Zip};
+use core::slice;
+use alloc::vec::Vec;
+use BidirMap;
+
+
+/// A bidirectional map storing an auxiliary value with each pair, which isn't a key and needn't be unique.
+///
+/// The payloads follow their pairs through every insertion and removal, and are handed back with them.
+/// The pairs are kept in a `BidirMap`, so they end up in the same order as they would there, and its hash index can be used
+/// (see `build_index()`).
+///
+/// Performance: `O(n)`, mostly; that of the underlying `BidirMap`.
+///
+/// # Examples
+///
+/// ```
+/// use bidir_map::AuxBidirMap;
+///
+/// let mut map = AuxBidirMap::new();
+/// map.insert(1, "alice", "2024-01-01");
+/// map.insert(2, "bob", "2024-02-01");
+///
+/// assert_eq!(map.get_with_aux_by_first(&2), Some((&"bob", &"2024-02-01")));
+/// *map.aux_mut_by_second(&"alice").unwrap() = "2024-03-01";
+/// assert_eq!(map.remove_by_first(&1), Some((1, "alice", "2024-03-01")));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuxBidirMap<Kv1: PartialEq, Kv2: PartialEq, Aux> {
+	map: BidirMap<Kv1, Kv2>,
+	/// The payload of the pair at the same position in `map`
+	aux: Vec<Aux>,
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq, Aux> AuxBidirMap<Kv1, Kv2, Aux> {
+	/// Create a new empty instance of `AuxBidirMap`
+	pub fn new() -> Self {
+		AuxBidirMap::with_capacity(0)
+	}
+
+	/// Create a new empty instance of `AuxBidirMap` with the specified capacity.
+	///
+	/// It will be able to hold at least `capacity` elements without reallocating.
+	pub fn with_capacity(capacity: usize) -> Self {
+		AuxBidirMap{
+			map: BidirMap::with_capacity(capacity),
+			aux: Vec::with_capacity(capacity),
+		}
+	}
+
+	/// Gets a reference to the underlying `BidirMap` of the pairs.
+	pub fn inner(&self) -> &BidirMap<Kv1, Kv2> {
+		&self.map
+	}
+
+	/// Splits the map into the `BidirMap` of the pairs and their payloads, in the same order.
+	pub fn into_parts(self) -> (BidirMap<Kv1, Kv2>, Vec<Aux>) {
+		(self.map, self.aux)
+	}
+
+	/// Clears the map, removing all entries.
+	pub fn clear(&mut self) {
+		self.map.clear();
+		self.aux.clear();
+	}
+
+	/// Inserts a K/V-K/V pair with its payload into the map.
+	///
+	/// Like `BidirMap::insert()`, pairs holding `kv1` or `kv2` are removed and the one holding `kv1` is preferably returned,
+	/// along with its payload.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::AuxBidirMap;
+	///
+	/// let mut map = AuxBidirMap::new();
+	/// map.insert(1, 'a', 10);
+	/// map.insert(2, 'b', 20);
+	/// assert_eq!(map.insert(1, 'b', 30), Some((1, 'a', 10)));
+	/// assert_eq!(map.get_with_aux_by_second(&'b'), Some((&1, &30)));
+	/// assert_eq!(map.len(), 1);
+	/// ```
+	pub fn insert(&mut self, kv1: Kv1, kv2: Kv2, aux: Aux) -> Option<(Kv1, Kv2, Aux)> {
+		let (by_first, mut by_second) = self.map.positions_of(&kv1, &kv2);
+
+		let by_first = by_first.map(|idx| {
+			// As in `BidirMap::insert()`
+			if by_second == Some(idx) {
+				by_second = None;
+			} else if by_second == Some(self.len() - 1) {
+				by_second = Some(idx);
+			}
+			self.swap_remove_at(idx)
+		});
+		let by_second = by_second.map(|idx| self.swap_remove_at(idx));
+
+		self.map.push(kv1, kv2);
+		self.aux.push(aux);
+
+		by_first.or(by_second)
+	}
+
+	/// Removes the pair corresponding to the first K/V from the map, returning it and its payload if the key was previously in the map.
+	pub fn remove_by_first<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2, Aux)>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.get_index_of_first(key).map(|idx| self.swap_remove_at(idx))
+	}
+
+	/// Removes the pair corresponding to the second K/V from the map, returning it and its payload if the key was previously in the map.
+	pub fn remove_by_second<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2, Aux)>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.get_index_of_second(key).map(|idx| self.swap_remove_at(idx))
+	}
+
+	/// Returns a reference to the second K/V corresponding to the first K/V.
+	pub fn get_by_first<Q>(&self, key: &Q) -> Option<&Kv2>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.get_by_first(key)
+	}
+
+	/// Returns a reference to the first K/V corresponding to the second K/V.
+	pub fn get_by_second<Q>(&self, key: &Q) -> Option<&Kv1>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.get_by_second(key)
+	}
+
+	/// Returns references to the second K/V and the payload corresponding to the first K/V.
+	pub fn get_with_aux_by_first<Q>(&self, key: &Q) -> Option<(&Kv2, &Aux)>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.get_index_of_first(key).map(|idx| (&self.map.cont[idx].1, &self.aux[idx]))
+	}
+
+	/// Returns references to the first K/V and the payload corresponding to the second K/V.
+	pub fn get_with_aux_by_second<Q>(&self, key: &Q) -> Option<(&Kv1, &Aux)>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.get_index_of_second(key).map(|idx| (&self.map.cont[idx].0, &self.aux[idx]))
+	}
+
+	/// Returns a mutable reference to the payload of the pair with the first K/V.
+	///
+	/// Unlike the K/Vs, the payload can be changed freely.
+	pub fn aux_mut_by_first<Q>(&mut self, key: &Q) -> Option<&mut Aux>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.get_index_of_first(key).map(move |idx| &mut self.aux[idx])
+	}
+
+	/// Returns a mutable reference to the payload of the pair with the second K/V.
+	///
+	/// Unlike the K/Vs, the payload can be changed freely.
+	pub fn aux_mut_by_second<Q>(&mut self, key: &Q) -> Option<&mut Aux>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.get_index_of_second(key).map(move |idx| &mut self.aux[idx])
+	}
+
+	/// Check if the map contains the first K/V
+	pub fn contains_first_key<Q>(&self, key: &Q) -> bool
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.contains_first_key(key)
+	}
+
+	/// Check if the map contains the second K/V
+	pub fn contains_second_key<Q>(&self, key: &Q) -> bool
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.contains_second_key(key)
+	}
+
+	/// Gets an iterator over the entries of the map, with their payloads.
+	pub fn iter<'s>(&'s self) -> Iter<'s, Kv1, Kv2, Aux> {
+		Iter{
+			iter: self.map.cont.iter().zip(self.aux.iter()),
+		}
+	}
+
+	/// Returns the number of elements in the map.
+	pub fn len(&self) -> usize {
+		self.map.len()
+	}
+
+	/// Returns true if the map contains no elements.
+	pub fn is_empty(&self) -> bool {
+		self.map.is_empty()
+	}
+
+	/// Builds a hash index over both columns of the pairs, see `BidirMap::build_index()`.
+	#[cfg(feature = "std")]
+	pub fn build_index(&mut self)
+		where Kv1: ::core::hash::Hash + Eq,
+		      Kv2: ::core::hash::Hash + Eq,
+	{
+		self.map.build_index();
+	}
+
+	fn swap_remove_at(&mut self, idx: usize) -> (Kv1, Kv2, Aux) {
+		let (kv1, kv2) = self.map.swap_remove_at(idx);
+		(kv1, kv2, self.aux.swap_remove(idx))
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq, Aux> Default for AuxBidirMap<Kv1, Kv2, Aux> {
+	fn default() -> Self {
+		AuxBidirMap::new()
+	}
+}
+
+impl<'a, Kv1: PartialEq, Kv2: PartialEq, Aux> IntoIterator for &'a AuxBidirMap<Kv1, Kv2, Aux> {
+	type Item = (&'a Kv1, &'a Kv2, &'a Aux);
+	type IntoIter = Iter<'a, Kv1, Kv2, Aux>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq, Aux> FromIterator<(Kv1, Kv2, Aux)> for AuxBidirMap<Kv1, Kv2, Aux> {
+	/// Like `BidirMap`'s, this doesn't check for duplicates.
+	fn from_iter<T: IntoIterator<Item=(Kv1, Kv2, Aux)>>(iter: T) -> Self {
+		let mut map = AuxBidirMap::new();
+		map.extend(iter);
+		map
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq, Aux> Extend<(Kv1, Kv2, Aux)> for AuxBidirMap<Kv1, Kv2, Aux> {
+	/// Like `BidirMap`'s, this doesn't check for duplicates.
+	fn extend<T: IntoIterator<Item=(Kv1, Kv2, Aux)>>(&mut self, iter: T) {
+		for (kv1, kv2, aux) in iter {
+			self.map.push(kv1, kv2);
+			self.aux.push(aux);
+		}
+	}
+}
+
+/// Gives each pair the default payload.
+impl<Kv1: PartialEq, Kv2: PartialEq, Aux: Default> From<BidirMap<Kv1, Kv2>> for AuxBidirMap<Kv1, Kv2, Aux> {
+	fn from(map: BidirMap<Kv1, Kv2>) -> Self {
+		AuxBidirMap{
+			aux: (0..map.len()).map(|_| Aux::default()).collect(),
+			map,
+		}
+	}
+}
+
+
+/// An iterator over the K/V pairs contained in an `AuxBidirMap`, with their payloads.
+///
+/// See documentation of `AuxBidirMap::iter()` for more.
+pub struct Iter<'a, Kv1: 'a, Kv2: 'a, Aux: 'a> {
+	iter: Zip<slice::Iter<'a, (Kv1, Kv2)>, slice::Iter<'a, Aux>>,
+}
+
+impl<'a, Kv1, Kv2, Aux> Iterator for Iter<'a, Kv1, Kv2, Aux> {
+	type Item = (&'a Kv1, &'a Kv2, &'a Aux);
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next().map(|(kvs, aux)| (&kvs.0, &kvs.1, aux))
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.iter.size_hint()
+	}
+}
+
+impl<'a, Kv1, Kv2, Aux> ExactSizeIterator for Iter<'a, Kv1, Kv2, Aux> {}
@@ -0,0 +1,93 @@
+//! Aliases of `BidirMap`'s methods under the names the `bimap` crate uses, to ease migrating from it.
+//!
+//! `bimap`'s left values are `BidirMap`'s first K/Vs, and its right values the second ones.
+
+
+use core::borrow::Borrow;
+use {BidirMap, FirstColumn, SecondColumn};
+
+
+/// `bimap`'s name for `BidirMap`.
+pub type BiMap<L, R> = BidirMap<L, R>;
+
+impl<L: PartialEq, R: PartialEq> BidirMap<L, R> {
+	/// `bimap`'s name for `get_by_first()`.
+	pub fn get_by_left<Q>(&self, left: &Q) -> Option<&R>
+		where L: Borrow<Q>,
+		      Q: PartialEq + ?Sized,
+	{
+		self.get_by_first(left)
+	}
+
+	/// `bimap`'s name for `get_by_second()`.
+	pub fn get_by_right<Q>(&self, right: &Q) -> Option<&L>
+		where R: Borrow<Q>,
+		      Q: PartialEq + ?Sized,
+	{
+		self.get_by_second(right)
+	}
+
+	/// `bimap`'s name for `contains_first_key()`.
+	pub fn contains_left<Q>(&self, left: &Q) -> bool
+		where L: Borrow<Q>,
+		      Q: PartialEq + ?Sized,
+	{
+		self.contains_first_key(left)
+	}
+
+	/// `bimap`'s name for `contains_second_key()`.
+	pub fn contains_right<Q>(&self, right: &Q) -> bool
+		where R: Borrow<Q>,
+		      Q: PartialEq + ?Sized,
+	{
+		self.contains_second_key(right)
+	}
+
+	/// `bimap`'s name for `remove_by_first()`.
+	pub fn remove_by_left<Q>(&mut self, left: &Q) -> Option<(L, R)>
+		where L: Borrow<Q>,
+		      Q: PartialEq + ?Sized,
+	{
+		self.remove_by_first(left)
+	}
+
+	/// `bimap`'s name for `remove_by_second()`.
+	pub fn remove_by_right<Q>(&mut self, right: &Q) -> Option<(L, R)>
+		where R: Borrow<Q>,
+		      Q: PartialEq + ?Sized,
+	{
+		self.remove_by_second(right)
+	}
+
+	/// Inserts the pair only if neither value is in the map yet, handing it back otherwise, as in `bimap`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BiMap;
+	///
+	/// let mut map = BiMap::new();
+	/// assert_eq!(map.insert_no_overwrite('a', 1), Ok(()));
+	/// assert_eq!(map.insert_no_overwrite('b', 1), Err(('b', 1)));
+	/// assert_eq!(map.get_by_right(&1), Some(&'a'));
+	/// ```
+	pub fn insert_no_overwrite(&mut self, left: L, right: R) -> Result<(), (L, R)> {
+		if self.cont.iter().any(|kvs| kvs.0 == left || kvs.1 == right) {
+			Err((left, right))
+		} else {
+			self.assert_room();
+			self.push(left, right);
+			Ok(())
+		}
+	}
+
+	/// `bimap`'s name for `first_col()`.
+	pub fn left_values<'s>(&'s self) -> FirstColumn<'s, L, R> {
+		self.first_col()
+	}
+
+	/// `bimap`'s name for `second_col()`.
+	pub fn right_values<'s>(&'s self) -> SecondColumn<'s, L, R> {
+		self.second_col()
+	}
+}
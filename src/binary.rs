@@ -0,0 +1,167 @@
+//! A compact length-prefixed binary encoding of `BidirMap`s, for persisting them without serde.
+//!
+//! The format is the magic bytes `b"BDMP"`, a version byte (currently `1`), the number of pairs as a little-endian `u64`,
+//! and then the pairs in iteration order, first K/V then second, each as per its `BinaryKey` implementation.
+
+
+use std::io::{self, Read, Write};
+use std::string::String;
+use std::vec::Vec;
+use {BidirMap, CapacityError};
+
+
+const MAGIC: &[u8; 4] = b"BDMP";
+const VERSION: u8 = 1;
+
+/// The most pairs preallocated for while reading, so a corrupted count can't exhaust memory before the input runs out
+const MAX_PREALLOCATED: usize = 1024;
+
+
+/// A K/V type with a binary encoding, for `BidirMap::write_to()` and `BidirMap::read_from()`.
+///
+/// Integers are encoded as little-endian bytes of their width,
+/// `String`s and `Vec<u8>`s as their length as a little-endian `u64` followed by their bytes.
+pub trait BinaryKey: Sized {
+	/// Writes the K/V's encoding.
+	fn write_key<W: Write>(&self, w: &mut W) -> io::Result<()>;
+
+	/// Reads a K/V back, failing with `UnexpectedEof` on truncated input and `InvalidData` on malformed input.
+	fn read_key<R: Read>(r: &mut R) -> io::Result<Self>;
+}
+
+macro_rules! impl_binary_key_for_int {
+	($($int:ty),*) => {$(
+		impl BinaryKey for $int {
+			fn write_key<W: Write>(&self, w: &mut W) -> io::Result<()> {
+				w.write_all(&self.to_le_bytes())
+			}
+
+			fn read_key<R: Read>(r: &mut R) -> io::Result<Self> {
+				let mut bytes = [0; core::mem::size_of::<$int>()];
+				r.read_exact(&mut bytes)?;
+				Ok(<$int>::from_le_bytes(bytes))
+			}
+		}
+	)*};
+}
+
+impl_binary_key_for_int!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+impl BinaryKey for Vec<u8> {
+	fn write_key<W: Write>(&self, w: &mut W) -> io::Result<()> {
+		(self.len() as u64).write_key(w)?;
+		w.write_all(self)
+	}
+
+	fn read_key<R: Read>(r: &mut R) -> io::Result<Self> {
+		let len = u64::read_key(r)?;
+		let mut bytes = Vec::new();
+		r.take(len).read_to_end(&mut bytes)?;
+		if (bytes.len() as u64) < len {
+			return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "K/V cut short"));
+		}
+		Ok(bytes)
+	}
+}
+
+impl BinaryKey for String {
+	fn write_key<W: Write>(&self, w: &mut W) -> io::Result<()> {
+		(self.len() as u64).write_key(w)?;
+		w.write_all(self.as_bytes())
+	}
+
+	fn read_key<R: Read>(r: &mut R) -> io::Result<Self> {
+		String::from_utf8(Vec::read_key(r)?).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+	}
+}
+
+
+impl<Kv1: PartialEq + BinaryKey, Kv2: PartialEq + BinaryKey> BidirMap<Kv1, Kv2> {
+	/// Writes the map in the format described in the module documentation.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert(1u32, "one".to_string());
+	///
+	/// let mut bytes = Vec::new();
+	/// map.write_to(&mut bytes).unwrap();
+	/// assert_eq!(BidirMap::read_from(&bytes[..]).unwrap(), map);
+	/// ```
+	pub fn write_to<W: Write>(&self, mut w: W) -> io::Result<()> {
+		w.write_all(MAGIC)?;
+		w.write_all(&[VERSION])?;
+		(self.cont.len() as u64).write_key(&mut w)?;
+		for (kv1, kv2) in &self.cont {
+			kv1.write_key(&mut w)?;
+			kv2.write_key(&mut w)?;
+		}
+		w.flush()
+	}
+
+	/// Reads a map written by `write_to()`, in the same iteration order.
+	///
+	/// Fails with `InvalidData` on a wrong header or a K/V repeated within a column, and with `UnexpectedEof` on truncated input.
+	pub fn read_from<R: Read>(r: R) -> io::Result<Self> {
+		read_bounded(r, None)
+	}
+
+	/// Reads a map written by `write_to()` like `read_from()`, bounded to `max_len` pairs as by `BidirMap::set_max_len()`.
+	///
+	/// Fails with `InvalidData` carrying a `CapacityError` if the map has more pairs than that,
+	/// which is known from its header, before any pairs are read.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::{BidirMap, CapacityError};
+	///
+	/// let map: BidirMap<u8, u8> = vec![(1, 2), (3, 4)].into_iter().collect();
+	/// let mut bytes = Vec::new();
+	/// map.write_to(&mut bytes).unwrap();
+	///
+	/// assert_eq!(BidirMap::<u8, u8>::read_from_with_max_len(&bytes[..], 2).unwrap().max_len(), Some(2));
+	/// let err = BidirMap::<u8, u8>::read_from_with_max_len(&bytes[..], 1).unwrap_err();
+	/// assert_eq!(*err.into_inner().unwrap().downcast::<CapacityError>().unwrap(), CapacityError{capacity: 1});
+	/// ```
+	pub fn read_from_with_max_len<R: Read>(r: R, max_len: usize) -> io::Result<Self> {
+		read_bounded(r, Some(max_len))
+	}
+}
+
+
+fn read_bounded<Kv1, Kv2, R>(mut r: R, max_len: Option<usize>) -> io::Result<BidirMap<Kv1, Kv2>>
+	where Kv1: PartialEq + BinaryKey,
+	      Kv2: PartialEq + BinaryKey,
+	      R  : Read,
+{
+	let mut header = [0; 5];
+	r.read_exact(&mut header)?;
+	if header[..4] != MAGIC[..] {
+		return Err(io::Error::new(io::ErrorKind::InvalidData, "not an encoded BidirMap"));
+	}
+	if header[4] != VERSION {
+		return Err(io::Error::new(io::ErrorKind::InvalidData, "unsupported BidirMap encoding version"));
+	}
+
+	let len = u64::read_key(&mut r)?;
+	if let Some(max_len) = max_len {
+		if len > max_len as u64 {
+			return Err(io::Error::new(io::ErrorKind::InvalidData, CapacityError{capacity: max_len}));
+		}
+	}
+	let mut cont = Vec::with_capacity(if len < MAX_PREALLOCATED as u64 { len as usize } else { MAX_PREALLOCATED });
+	for _ in 0..len {
+		let kv1 = Kv1::read_key(&mut r)?;
+		let kv2 = Kv2::read_key(&mut r)?;
+		cont.push((kv1, kv2));
+	}
+
+	let mut map = BidirMap::from_cont(cont);
+	map.validate().map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+	map.max_len = max_len;
+	Ok(map)
+}
@@ -0,0 +1,217 @@
+//! A `BidirMap` variant holding a bounded number of pairs, evicting the oldest.
+
+
+use core::borrow::Borrow;
+use alloc::collections::vec_deque::{self, VecDeque};
+use BidirMap;
+
+
+/// A bidirectional map of at most `max_len()` pairs, which evicts the oldest pair when inserting into a full map.
+///
+/// Pairs are kept in insertion order, oldest first, and removals preserve that order,
+/// so eviction is always first in, first out.
+/// Replacing pairs via `insert()` doesn't grow the map, and so doesn't evict anything;
+/// the replacement counts as newly inserted.
+///
+/// Performance: `O(n)`, mostly; `O(1)` eviction.
+///
+/// # Examples
+///
+/// ```
+/// use bidir_map::BoundedBidirMap;
+///
+/// let mut sessions = BoundedBidirMap::with_max_len(2);
+/// sessions.insert("token-a", 1);
+/// sessions.insert("token-b", 2);
+/// assert_eq!(sessions.insert("token-c", 3), Some(("token-a", 1)));
+/// assert_eq!(sessions.get_by_second(&1), None);
+/// ```
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct BoundedBidirMap<Kv1: PartialEq, Kv2: PartialEq> {
+	/// Oldest first
+	cont: VecDeque<(Kv1, Kv2)>,
+	max_len: usize,
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> BoundedBidirMap<Kv1, Kv2> {
+	/// Create a new empty instance of `BoundedBidirMap`, which will hold at most `max_len` pairs.
+	///
+	/// # Panics
+	///
+	/// If `max_len` is 0.
+	pub fn with_max_len(max_len: usize) -> Self {
+		assert!(max_len != 0, "BoundedBidirMap must be able to hold a pair");
+		BoundedBidirMap{
+			cont: VecDeque::new(),
+			max_len,
+		}
+	}
+
+	/// Returns the maximum number of pairs the map holds.
+	pub fn max_len(&self) -> usize {
+		self.max_len
+	}
+
+	/// Clears the map, removing all entries.
+	pub fn clear(&mut self) {
+		self.cont.clear()
+	}
+
+	/// Inserts a K/V-K/V pair into the map as its newest, returning a pair no longer in the map.
+	///
+	/// If either K/V was present, the pairs holding them are removed and one returned, as with `BidirMap::insert()`.
+	/// Otherwise, if the map was full, the oldest pair is evicted and returned.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BoundedBidirMap;
+	///
+	/// let mut map = BoundedBidirMap::with_max_len(2);
+	/// map.insert(1, "a");
+	/// map.insert(2, "b");
+	/// assert_eq!(map.insert(1, "c"), Some((1, "a")));   // replaced, nothing evicted
+	/// assert_eq!(map.insert(3, "d"), Some((2, "b")));   // evicted
+	/// assert_eq!(map.iter().cloned().collect::<Vec<_>>(), [(1, "c"), (3, "d")]);
+	/// ```
+	pub fn insert(&mut self, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)> {
+		let by_first = self.cont.iter().position(|kvs| kvs.0 == kv1).and_then(|idx| self.cont.remove(idx));
+		let by_second = self.cont.iter().position(|kvs| kvs.1 == kv2).and_then(|idx| self.cont.remove(idx));
+		let evicted = if self.cont.len() == self.max_len {
+			self.cont.pop_front()
+		} else {
+			None
+		};
+
+		self.cont.push_back((kv1, kv2));
+
+		by_first.or(by_second).or(evicted)
+	}
+
+	/// Gets an iterator over the entries of the map, oldest first.
+	pub fn iter<'s>(&'s self) -> Iter<'s, Kv1, Kv2> {
+		Iter{
+			iter: self.cont.iter(),
+		}
+	}
+
+	/// Returns the number of elements in the map.
+	pub fn len(&self) -> usize {
+		self.cont.len()
+	}
+
+	/// Returns true if the map contains no elements.
+	pub fn is_empty(&self) -> bool {
+		self.cont.is_empty()
+	}
+
+	/// Returns true if the next insertion of new K/Vs will evict a pair.
+	pub fn is_full(&self) -> bool {
+		self.cont.len() == self.max_len
+	}
+
+	/// Returns the pair that'll be evicted next.
+	pub fn oldest(&self) -> Option<&(Kv1, Kv2)> {
+		self.cont.front()
+	}
+
+	/// Removes the pair that'll be evicted next, returning it.
+	pub fn pop_oldest(&mut self) -> Option<(Kv1, Kv2)> {
+		self.cont.pop_front()
+	}
+
+
+	/// Returns a reference to the second K/V corresponding to the first K/V.
+	pub fn get_by_first<Q>(&self, key: &Q) -> Option<&Kv2>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.cont.iter().find(|kvs| *key == *kvs.0.borrow()).map(|kvs| &kvs.1)
+	}
+
+	/// Returns a reference to the first K/V corresponding to the second K/V.
+	pub fn get_by_second<Q>(&self, key: &Q) -> Option<&Kv1>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.cont.iter().find(|kvs| *key == *kvs.1.borrow()).map(|kvs| &kvs.0)
+	}
+
+	/// Check if the map contains the first K/V
+	pub fn contains_first_key<Q>(&self, key: &Q) -> bool
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.get_by_first(key).is_some()
+	}
+
+	/// Check if the map contains the second K/V
+	pub fn contains_second_key<Q>(&self, key: &Q) -> bool
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.get_by_second(key).is_some()
+	}
+
+	/// Removes the pair corresponding to the first K/V from the map, returning it if the key was previously in the map.
+	///
+	/// The remaining pairs keep their age order.
+	pub fn remove_by_first<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.cont.iter().position(|kvs| *key == *kvs.0.borrow()).and_then(|idx| self.cont.remove(idx))
+	}
+
+	/// Removes the pair corresponding to the second K/V from the map, returning it if the key was previously in the map.
+	///
+	/// The remaining pairs keep their age order.
+	pub fn remove_by_second<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.cont.iter().position(|kvs| *key == *kvs.1.borrow()).and_then(|idx| self.cont.remove(idx))
+	}
+}
+
+
+impl<'a, Kv1: PartialEq, Kv2: PartialEq> IntoIterator for &'a BoundedBidirMap<Kv1, Kv2> {
+	type Item = &'a (Kv1, Kv2);
+	type IntoIter = Iter<'a, Kv1, Kv2>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> IntoIterator for BoundedBidirMap<Kv1, Kv2> {
+	type Item = (Kv1, Kv2);
+	type IntoIter = vec_deque::IntoIter<(Kv1, Kv2)>;
+
+	/// Oldest first.
+	fn into_iter(self) -> Self::IntoIter {
+		self.cont.into_iter()
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> From<BoundedBidirMap<Kv1, Kv2>> for BidirMap<Kv1, Kv2> {
+	/// The pairs are ordered oldest first.
+	fn from(map: BoundedBidirMap<Kv1, Kv2>) -> Self {
+		map.into_iter().collect()
+	}
+}
+
+
+/// An iterator over the K/V pairs contained in a `BoundedBidirMap`, oldest first.
+///
+/// See documentation of `BoundedBidirMap::iter()` for more.
+pub struct Iter<'a, Kv1: 'a, Kv2: 'a> {
+	iter: vec_deque::Iter<'a, (Kv1, Kv2)>,
+}
+
+impl<'a, Kv1, Kv2> Iterator for Iter<'a, Kv1, Kv2> {
+	type Item = &'a (Kv1, Kv2);
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next()
+	}
+}
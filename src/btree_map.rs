@@ -0,0 +1,616 @@
+//! An ordered bidirectional map backed by two `BTreeMap`s.
+
+
+use core::borrow::Borrow;
+use core::iter::{Extend, FromIterator};
+use core::ops::RangeBounds;
+use alloc::collections::{btree_map, BTreeMap};
+use BidirMap;
+
+
+/// A bidirectional map kept sorted by both columns, backed by two `BTreeMap`s.
+///
+/// The interface mirrors that of `BidirMap`, plus range queries and access to the smallest and largest pairs;
+/// insertion and removal have the same semantics, so the two are interchangeable in generic code.
+/// Iteration yields `(&Kv1, &Kv2)`, sorted by the first K/V.
+///
+/// Like `BidirHashMap`, each K/V is stored twice, once as a key of each tree, so inserting requires `Clone` on both of them.
+///
+/// Performance: `O(log n)`.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BidirBTreeMap<Kv1: Ord, Kv2: Ord> {
+	first: BTreeMap<Kv1, Kv2>,
+	second: BTreeMap<Kv2, Kv1>,
+}
+
+impl<Kv1: Ord, Kv2: Ord> BidirBTreeMap<Kv1, Kv2> {
+	/// Create a new empty instance of `BidirBTreeMap`
+	pub fn new() -> Self {
+		BidirBTreeMap{
+			first: BTreeMap::new(),
+			second: BTreeMap::new(),
+		}
+	}
+
+	/// Clears the map, removing all entries.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirBTreeMap;
+	///
+	/// let mut a = BidirBTreeMap::new();
+	/// a.insert(1, "a");
+	/// a.clear();
+	/// assert!(a.is_empty());
+	/// ```
+	pub fn clear(&mut self) {
+		self.first.clear();
+		self.second.clear();
+	}
+
+	/// Inserts a K/V-K/V pair into the map.
+	///
+	/// If the map did not have this K/V-K/V pair present, `None` is returned.
+	///
+	/// If the map did have this K/V-K/V pair present, it's updated and the old K/V-K/V pair is returned.
+	///
+	/// If `kv1` and `kv2` were each present in a different pair, both of those are removed and the one holding `kv1` is returned.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirBTreeMap;
+	///
+	/// let mut map = BidirBTreeMap::new();
+	/// map.insert(1, "a");
+	/// map.insert(2, "b");
+	/// assert_eq!(map.insert(1, "b"), Some((1, "a")));
+	/// assert_eq!(map.len(), 1);
+	/// assert_eq!(map.get_by_second(&"b"), Some(&1));
+	/// ```
+	pub fn insert(&mut self, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)>
+		where Kv1: Clone,
+		      Kv2: Clone,
+	{
+		let by_first = self.remove_by_first(&kv1);
+		let by_second = self.remove_by_second(&kv2);
+
+		self.first.insert(kv1.clone(), kv2.clone());
+		self.second.insert(kv2, kv1);
+
+		by_first.or(by_second)
+	}
+
+	/// Gets an iterator over the entries of the map, sorted by the first K/V.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirBTreeMap;
+	///
+	/// let mut map = BidirBTreeMap::new();
+	/// map.insert(3, "a");
+	/// map.insert(1, "c");
+	/// map.insert(2, "b");
+	///
+	/// for kv in map.iter() {
+	///     println!("{}: {}", kv.0, kv.1);
+	/// }
+	///
+	/// let first = map.iter().next().unwrap();
+	/// assert_eq!(first, (&1, &"c"));
+	/// ```
+	pub fn iter<'s>(&'s self) -> Iter<'s, Kv1, Kv2> {
+		Iter{
+			iter: self.first.iter(),
+		}
+	}
+
+	/// Gets an iterator over the first K/V of the map, in ascending order.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirBTreeMap;
+	///
+	/// let mut a = BidirBTreeMap::new();
+	/// a.insert(2, "a");
+	/// a.insert(1, "b");
+	///
+	/// let keys: Vec<_> = a.first_col().cloned().collect();
+	/// assert_eq!(keys, [1, 2]);
+	/// ```
+	pub fn first_col<'s>(&'s self) -> FirstColumn<'s, Kv1, Kv2> {
+		FirstColumn{
+			iter: self.first.keys(),
+		}
+	}
+
+	/// Gets an iterator over the second K/V of the map, in ascending order.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirBTreeMap;
+	///
+	/// let mut a = BidirBTreeMap::new();
+	/// a.insert(1, "b");
+	/// a.insert(2, "a");
+	///
+	/// let keys: Vec<_> = a.second_col().cloned().collect();
+	/// assert_eq!(keys, ["a", "b"]);
+	/// ```
+	pub fn second_col<'s>(&'s self) -> SecondColumn<'s, Kv1, Kv2> {
+		SecondColumn{
+			iter: self.second.keys(),
+		}
+	}
+
+	/// Returns the number of elements in the map.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirBTreeMap;
+	///
+	/// let mut a = BidirBTreeMap::new();
+	/// assert_eq!(a.len(), 0);
+	/// a.insert(1, "a");
+	/// assert_eq!(a.len(), 1);
+	/// ```
+	pub fn len(&self) -> usize {
+		self.first.len()
+	}
+
+	/// Returns true if the map contains no elements.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirBTreeMap;
+	///
+	/// let mut a = BidirBTreeMap::new();
+	/// assert!(a.is_empty());
+	/// a.insert(1, "a");
+	/// assert!(!a.is_empty());
+	/// ```
+	pub fn is_empty(&self) -> bool {
+		self.first.is_empty()
+	}
+
+
+	/// Returns a reference to the second K/V corresponding to the first K/V.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirBTreeMap;
+	///
+	/// let mut map = BidirBTreeMap::new();
+	/// map.insert(1, "a");
+	/// assert_eq!(map.get_by_first(&1), Some(&"a"));
+	/// assert_eq!(map.get_by_first(&2), None);
+	/// ```
+	pub fn get_by_first<Q>(&self, key: &Q) -> Option<&Kv2>
+		where Kv1: Borrow<Q>,
+		      Q  : Ord + ?Sized,
+	{
+		self.first.get(key)
+	}
+
+	/// Returns a reference to the first K/V corresponding to the second K/V.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirBTreeMap;
+	///
+	/// let mut map = BidirBTreeMap::new();
+	/// map.insert(1, "a");
+	/// assert_eq!(map.get_by_second(&"a"), Some(&1));
+	/// assert_eq!(map.get_by_second(&"b"), None);
+	/// ```
+	pub fn get_by_second<Q>(&self, key: &Q) -> Option<&Kv1>
+		where Kv2: Borrow<Q>,
+		      Q  : Ord + ?Sized,
+	{
+		self.second.get(key)
+	}
+
+	/// Check if the map contains the first K/V
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirBTreeMap;
+	///
+	/// let mut map = BidirBTreeMap::new();
+	/// map.insert(1, "a");
+	/// assert_eq!(map.contains_first_key(&1), true);
+	/// assert_eq!(map.contains_first_key(&2), false);
+	/// ```
+	pub fn contains_first_key<Q>(&self, key: &Q) -> bool
+		where Kv1: Borrow<Q>,
+		      Q  : Ord + ?Sized,
+	{
+		self.first.contains_key(key)
+	}
+
+	/// Check if the map contains the second K/V
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirBTreeMap;
+	///
+	/// let mut map = BidirBTreeMap::new();
+	/// map.insert(1, "a");
+	/// assert_eq!(map.contains_second_key(&"a"), true);
+	/// assert_eq!(map.contains_second_key(&"b"), false);
+	/// ```
+	pub fn contains_second_key<Q>(&self, key: &Q) -> bool
+		where Kv2: Borrow<Q>,
+		      Q  : Ord + ?Sized,
+	{
+		self.second.contains_key(key)
+	}
+
+	/// Removes the pair corresponding to the first K/V from the map, returning it if the key was previously in the map.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirBTreeMap;
+	///
+	/// let mut map = BidirBTreeMap::new();
+	/// map.insert(1, "a");
+	/// assert_eq!(map.remove_by_first(&1), Some((1, "a")));
+	/// assert_eq!(map.remove_by_first(&1), None);
+	/// ```
+	pub fn remove_by_first<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv1: Borrow<Q>,
+		      Q  : Ord + ?Sized,
+	{
+		let (kv1, kv2) = self.first.remove_entry(key)?;
+		self.second.remove(&kv2);
+		Some((kv1, kv2))
+	}
+
+	/// Removes the pair corresponding to the second K/V from the map, returning it if the key was previously in the map.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirBTreeMap;
+	///
+	/// let mut map = BidirBTreeMap::new();
+	/// map.insert(1, "a");
+	/// assert_eq!(map.remove_by_second(&"a"), Some((1, "a")));
+	/// assert_eq!(map.remove_by_second(&"b"), None);
+	/// ```
+	pub fn remove_by_second<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv2: Borrow<Q>,
+		      Q  : Ord + ?Sized,
+	{
+		let (kv2, kv1) = self.second.remove_entry(key)?;
+		self.first.remove(&kv1);
+		Some((kv1, kv2))
+	}
+
+
+	/// Gets an iterator over the pairs whose first K/V lies in the given range, sorted by the first K/V.
+	///
+	/// # Panics
+	///
+	/// Like `BTreeMap::range()`, panics if the range's start is greater than its end, or if both of its bounds are the same and excluded.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirBTreeMap;
+	///
+	/// let map: BidirBTreeMap<_, _> = (1..6).map(|i| (i, i * 10)).collect();
+	/// let pairs: Vec<_> = map.range_by_first(2..4).collect();
+	/// assert_eq!(pairs, [(&2, &20), (&3, &30)]);
+	/// ```
+	pub fn range_by_first<'s, Q, R>(&'s self, range: R) -> Range<'s, Kv1, Kv2>
+		where Kv1: Borrow<Q>,
+		      Q  : Ord + ?Sized,
+		      R  : RangeBounds<Q>,
+	{
+		Range{
+			iter: self.first.range(range),
+		}
+	}
+
+	/// Gets an iterator over the pairs whose second K/V lies in the given range, sorted by the second K/V.
+	///
+	/// The pairs are still yielded as `(&Kv1, &Kv2)`.
+	///
+	/// # Panics
+	///
+	/// Like `BTreeMap::range()`, panics if the range's start is greater than its end, or if both of its bounds are the same and excluded.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirBTreeMap;
+	///
+	/// let map: BidirBTreeMap<_, _> = (1..6).map(|i| (i, 60 - i * 10)).collect();
+	/// let pairs: Vec<_> = map.range_by_second(20..=30).collect();
+	/// assert_eq!(pairs, [(&4, &20), (&3, &30)]);
+	/// ```
+	pub fn range_by_second<'s, Q, R>(&'s self, range: R) -> RangeBySecond<'s, Kv1, Kv2>
+		where Kv2: Borrow<Q>,
+		      Q  : Ord + ?Sized,
+		      R  : RangeBounds<Q>,
+	{
+		RangeBySecond{
+			iter: self.second.range(range),
+		}
+	}
+
+	/// Returns the pair with the smallest first K/V, if any.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirBTreeMap;
+	///
+	/// let mut map = BidirBTreeMap::new();
+	/// assert_eq!(map.first_pair(), None);
+	/// map.insert(2, "a");
+	/// map.insert(1, "b");
+	/// assert_eq!(map.first_pair(), Some((&1, &"b")));
+	/// ```
+	pub fn first_pair(&self) -> Option<(&Kv1, &Kv2)> {
+		self.first.iter().next()
+	}
+
+	/// Returns the pair with the largest first K/V, if any.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirBTreeMap;
+	///
+	/// let mut map = BidirBTreeMap::new();
+	/// map.insert(2, "a");
+	/// map.insert(1, "b");
+	/// assert_eq!(map.last_pair(), Some((&2, &"a")));
+	/// ```
+	pub fn last_pair(&self) -> Option<(&Kv1, &Kv2)> {
+		self.first.iter().next_back()
+	}
+
+	/// Removes and returns the pair with the smallest first K/V, if any.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirBTreeMap;
+	///
+	/// let mut map = BidirBTreeMap::new();
+	/// map.insert(2, "a");
+	/// map.insert(1, "b");
+	/// assert_eq!(map.pop_first(), Some((1, "b")));
+	/// assert_eq!(map.pop_first(), Some((2, "a")));
+	/// assert_eq!(map.pop_first(), None);
+	/// ```
+	pub fn pop_first(&mut self) -> Option<(Kv1, Kv2)> {
+		let (kv1, kv2) = self.first.pop_first()?;
+		self.second.remove(&kv2);
+		Some((kv1, kv2))
+	}
+
+	/// Removes and returns the pair with the largest first K/V, if any.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirBTreeMap;
+	///
+	/// let mut map = BidirBTreeMap::new();
+	/// map.insert(2, "a");
+	/// map.insert(1, "b");
+	/// assert_eq!(map.pop_last(), Some((2, "a")));
+	/// assert!(!map.contains_second_key(&"a"));
+	/// ```
+	pub fn pop_last(&mut self) -> Option<(Kv1, Kv2)> {
+		let (kv1, kv2) = self.first.pop_last()?;
+		self.second.remove(&kv2);
+		Some((kv1, kv2))
+	}
+}
+
+
+impl<Kv1: Ord, Kv2: Ord> Default for BidirBTreeMap<Kv1, Kv2> {
+	fn default() -> Self {
+		BidirBTreeMap::new()
+	}
+}
+
+impl<Kv1: Ord, Kv2: Ord> IntoIterator for BidirBTreeMap<Kv1, Kv2> {
+	type Item = (Kv1, Kv2);
+	type IntoIter = IntoIter<Kv1, Kv2>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		IntoIter{
+			iter: self.first.into_iter(),
+		}
+	}
+}
+
+impl<'a, Kv1: Ord, Kv2: Ord> IntoIterator for &'a BidirBTreeMap<Kv1, Kv2> {
+	type Item = (&'a Kv1, &'a Kv2);
+	type IntoIter = Iter<'a, Kv1, Kv2>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+impl<Kv1: Ord + Clone, Kv2: Ord + Clone> FromIterator<(Kv1, Kv2)> for BidirBTreeMap<Kv1, Kv2> {
+	fn from_iter<T: IntoIterator<Item=(Kv1, Kv2)>>(iter: T) -> Self {
+		let mut map = BidirBTreeMap::new();
+		map.extend(iter);
+		map
+	}
+}
+
+impl<Kv1: Ord + Clone, Kv2: Ord + Clone> Extend<(Kv1, Kv2)> for BidirBTreeMap<Kv1, Kv2> {
+	fn extend<T: IntoIterator<Item=(Kv1, Kv2)>>(&mut self, iter: T) {
+		for (kv1, kv2) in iter {
+			self.insert(kv1, kv2);
+		}
+	}
+}
+
+impl<'a, Kv1: Ord + Clone, Kv2: Ord + Clone> Extend<(&'a Kv1, &'a Kv2)> for BidirBTreeMap<Kv1, Kv2> {
+	fn extend<T: IntoIterator<Item=(&'a Kv1, &'a Kv2)>>(&mut self, iter: T) {
+		for (kv1, kv2) in iter {
+			self.insert(kv1.clone(), kv2.clone());
+		}
+	}
+}
+
+impl<Kv1: Ord + Clone, Kv2: Ord + Clone> From<BidirMap<Kv1, Kv2>> for BidirBTreeMap<Kv1, Kv2> {
+	fn from(map: BidirMap<Kv1, Kv2>) -> Self {
+		map.into_iter().collect()
+	}
+}
+
+impl<Kv1: Ord, Kv2: Ord> From<BidirBTreeMap<Kv1, Kv2>> for BidirMap<Kv1, Kv2> {
+	/// The resulting map is ordered by the first K/V.
+	fn from(map: BidirBTreeMap<Kv1, Kv2>) -> Self {
+		// Both columns are already unique, so there's nothing to check
+		map.into_iter().collect()
+	}
+}
+
+
+/// An iterator over the K/V pairs contained in a `BidirBTreeMap`.
+///
+/// See documentation of `BidirBTreeMap::iter()` for more.
+pub struct Iter<'a, Kv1: 'a, Kv2: 'a> {
+	iter: btree_map::Iter<'a, Kv1, Kv2>,
+}
+
+impl<'a, Kv1, Kv2> Iterator for Iter<'a, Kv1, Kv2> {
+	type Item = (&'a Kv1, &'a Kv2);
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next()
+	}
+}
+
+impl<'a, Kv1, Kv2> DoubleEndedIterator for Iter<'a, Kv1, Kv2> {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		self.iter.next_back()
+	}
+}
+
+
+/// An iterator the first set of K/Vs in a `BidirBTreeMap`.
+///
+/// See documentation of `BidirBTreeMap::first_col()` for more.
+pub struct FirstColumn<'a, Kv1: 'a, Kv2: 'a> {
+	iter: btree_map::Keys<'a, Kv1, Kv2>,
+}
+
+impl<'a, Kv1, Kv2> Iterator for FirstColumn<'a, Kv1, Kv2> {
+	type Item = &'a Kv1;
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next()
+	}
+}
+
+impl<'a, Kv1, Kv2> DoubleEndedIterator for FirstColumn<'a, Kv1, Kv2> {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		self.iter.next_back()
+	}
+}
+
+
+/// An iterator the second set of K/Vs in a `BidirBTreeMap`.
+///
+/// See documentation of `BidirBTreeMap::second_col()` for more.
+pub struct SecondColumn<'a, Kv1: 'a, Kv2: 'a> {
+	iter: btree_map::Keys<'a, Kv2, Kv1>,
+}
+
+impl<'a, Kv1, Kv2> Iterator for SecondColumn<'a, Kv1, Kv2> {
+	type Item = &'a Kv2;
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next()
+	}
+}
+
+impl<'a, Kv1, Kv2> DoubleEndedIterator for SecondColumn<'a, Kv1, Kv2> {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		self.iter.next_back()
+	}
+}
+
+
+/// An iterator over a range of the K/V pairs in a `BidirBTreeMap`, by the first K/V.
+///
+/// See documentation of `BidirBTreeMap::range_by_first()` for more.
+pub struct Range<'a, Kv1: 'a, Kv2: 'a> {
+	iter: btree_map::Range<'a, Kv1, Kv2>,
+}
+
+impl<'a, Kv1, Kv2> Iterator for Range<'a, Kv1, Kv2> {
+	type Item = (&'a Kv1, &'a Kv2);
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next()
+	}
+}
+
+impl<'a, Kv1, Kv2> DoubleEndedIterator for Range<'a, Kv1, Kv2> {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		self.iter.next_back()
+	}
+}
+
+
+/// An iterator over a range of the K/V pairs in a `BidirBTreeMap`, by the second K/V.
+///
+/// See documentation of `BidirBTreeMap::range_by_second()` for more.
+pub struct RangeBySecond<'a, Kv1: 'a, Kv2: 'a> {
+	iter: btree_map::Range<'a, Kv2, Kv1>,
+}
+
+impl<'a, Kv1, Kv2> Iterator for RangeBySecond<'a, Kv1, Kv2> {
+	type Item = (&'a Kv1, &'a Kv2);
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next().map(|(kv2, kv1)| (kv1, kv2))
+	}
+}
+
+impl<'a, Kv1, Kv2> DoubleEndedIterator for RangeBySecond<'a, Kv1, Kv2> {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		self.iter.next_back().map(|(kv2, kv1)| (kv1, kv2))
+	}
+}
+
+
+/// An owning iterator over the K/V pairs of a `BidirBTreeMap`, sorted by the first K/V.
+///
+/// See documentation of `BidirBTreeMap::into_iter()` for more.
+pub struct IntoIter<Kv1, Kv2> {
+	iter: btree_map::IntoIter<Kv1, Kv2>,
+}
+
+impl<Kv1, Kv2> Iterator for IntoIter<Kv1, Kv2> {
+	type Item = (Kv1, Kv2);
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next()
+	}
+}
+
+impl<Kv1, Kv2> DoubleEndedIterator for IntoIter<Kv1, Kv2> {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		self.iter.next_back()
+	}
+}
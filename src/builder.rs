@@ -0,0 +1,184 @@
+//! Building `BidirMap`s with construction-time options, see `BidirMapBuilder`.
+
+
+use core::cmp::Ordering;
+use core::fmt;
+#[cfg(feature = "std")]
+use core::hash::Hash;
+use {BidirMap, BuildError, Column, DuplicateKeyError, OnDuplicate};
+
+
+/// A builder for `BidirMap`s, collecting construction options before taking in all pairs in one pass.
+///
+/// # Examples
+///
+/// ```
+/// use bidir_map::{BidirMap, OnDuplicate};
+///
+/// let map = BidirMap::builder()
+///     .capacity(3)
+///     .on_duplicate(OnDuplicate::FirstWins)
+///     .sorted_by_first()
+///     .build_from_iter(vec![(3, "c"), (1, "a"), (2, "a")])
+///     .unwrap();
+/// assert_eq!(map.iter().cloned().collect::<Vec<_>>(), [(1, "a"), (3, "c")]);
+/// ```
+pub struct BidirMapBuilder<Kv1: PartialEq, Kv2: PartialEq> {
+	capacity: usize,
+	on_duplicate: OnDuplicate,
+	by_first: Option<fn(&Kv1, &Kv1) -> Ordering>,
+	by_second: Option<fn(&Kv2, &Kv2) -> Ordering>,
+	#[cfg(feature = "std")]
+	build_index: Option<fn(&mut BidirMap<Kv1, Kv2>)>,
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
+	/// Create a `BidirMapBuilder` with the default options, equivalent to `BidirMapBuilder::new()`.
+	pub fn builder() -> BidirMapBuilder<Kv1, Kv2> {
+		BidirMapBuilder::new()
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> BidirMapBuilder<Kv1, Kv2> {
+	/// Create a builder with no preallocation, `OnDuplicate::Error`, insertion order and no hash index.
+	pub fn new() -> Self {
+		BidirMapBuilder{
+			capacity: 0,
+			on_duplicate: OnDuplicate::Error,
+			by_first: None,
+			by_second: None,
+			#[cfg(feature = "std")]
+			build_index: None,
+		}
+	}
+
+	/// Preallocate for at least `capacity` pairs, or for as many as the input hints at, if more.
+	pub fn capacity(mut self, capacity: usize) -> Self {
+		self.capacity = capacity;
+		self
+	}
+
+	/// Set what to do with input pairs repeating a K/V of an earlier one.
+	pub fn on_duplicate(mut self, on_duplicate: OnDuplicate) -> Self {
+		self.on_duplicate = on_duplicate;
+		self
+	}
+
+	/// Sort the built map by its first K/Vs; ties can't occur, since the first K/Vs are unique.
+	///
+	/// Conflicts with `sorted_by_second()`.
+	pub fn sorted_by_first(mut self) -> Self
+		where Kv1: Ord,
+	{
+		self.by_first = Some(Ord::cmp);
+		self
+	}
+
+	/// Sort the built map by its second K/Vs.
+	///
+	/// Conflicts with `sorted_by_first()`.
+	pub fn sorted_by_second(mut self) -> Self
+		where Kv2: Ord,
+	{
+		self.by_second = Some(Ord::cmp);
+		self
+	}
+
+	/// Build the map's hash index before taking in any pairs, as by `BidirMap::build_index()`,
+	/// so that duplicates are found in `O(1)` each instead of by scanning.
+	///
+	/// The built map keeps the index.
+	#[cfg(feature = "std")]
+	pub fn indexed(mut self) -> Self
+		where Kv1: Hash + Eq,
+		      Kv2: Hash + Eq,
+	{
+		self.build_index = Some(BidirMap::build_index);
+		self
+	}
+
+	/// Build an empty map with the chosen options.
+	pub fn build(self) -> Result<BidirMap<Kv1, Kv2>, BuildError> {
+		self.build_from_iter(None)
+	}
+
+	/// Build a map of the pairs of `iter`, in order, applying the duplicate policy as it goes.
+	///
+	/// Performance: `O(n)` with `indexed()`, `O(n²)` otherwise, plus `O(n log n)` for sorting.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::{BidirMap, BuildError, Column, DuplicateKeyError};
+	///
+	/// let res = BidirMap::builder().build_from_iter(vec![(1, "a"), (2, "a")]);
+	/// assert_eq!(res, Err(BuildError::Duplicate(DuplicateKeyError{column: Column::Second, index: Some(1)})));
+	/// ```
+	pub fn build_from_iter<I: IntoIterator<Item = (Kv1, Kv2)>>(self, iter: I) -> Result<BidirMap<Kv1, Kv2>, BuildError> {
+		if self.by_first.is_some() && self.by_second.is_some() {
+			return Err(BuildError::ConflictingOrder);
+		}
+
+		let iter = iter.into_iter();
+		let mut map = BidirMap::with_capacity(self.capacity.max(iter.size_hint().0));
+		#[cfg(feature = "std")]
+		{
+			if let Some(build_index) = self.build_index {
+				build_index(&mut map);
+			}
+		}
+
+		for (idx, (kv1, kv2)) in iter.enumerate() {
+			if self.on_duplicate == OnDuplicate::LastWins {
+				map.insert(kv1, kv2);
+				continue;
+			}
+
+			let column = if map.position_of_first(&kv1).is_some() {
+				Some(Column::First)
+			} else if map.position_of_second(&kv2).is_some() {
+				Some(Column::Second)
+			} else {
+				None
+			};
+			match column {
+				None => map.push(kv1, kv2),
+				Some(column) if self.on_duplicate == OnDuplicate::Error => {
+					return Err(BuildError::Duplicate(DuplicateKeyError{
+						column,
+						index: Some(idx),
+					}));
+				}
+				Some(_) => {}
+			}
+		}
+
+		if let Some(cmp) = self.by_first {
+			map.cont.sort_by(|lhs, rhs| cmp(&lhs.0, &rhs.0));
+			map.rebuild_index();
+		} else if let Some(cmp) = self.by_second {
+			map.cont.sort_by(|lhs, rhs| cmp(&lhs.1, &rhs.1));
+			map.rebuild_index();
+		}
+		Ok(map)
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> Default for BidirMapBuilder<Kv1, Kv2> {
+	fn default() -> Self {
+		BidirMapBuilder::new()
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> fmt::Debug for BidirMapBuilder<Kv1, Kv2> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mut builder = f.debug_struct("BidirMapBuilder");
+		builder.field("capacity", &self.capacity)
+			.field("on_duplicate", &self.on_duplicate)
+			.field("sorted_by_first", &self.by_first.is_some())
+			.field("sorted_by_second", &self.by_second.is_some());
+		#[cfg(feature = "std")]
+		builder.field("indexed", &self.build_index.is_some());
+		builder.finish()
+	}
+}
@@ -0,0 +1,184 @@
+//! A read-only view resolving lookups through a stack of `BidirMap`s.
+
+
+use core::borrow::Borrow;
+use alloc::vec::Vec;
+use {BidirMap, Iter as MapIter};
+
+
+/// A read-only overlay of `BidirMap`s, consulted in order, such that earlier layers shadow later ones.
+///
+/// A pair of a layer is in effect unless an earlier layer holds either of its K/Vs:
+/// an override of `(1, "x")` hides a default of `(1, "a")` by the first K/V and one of `(2, "x")` by the second.
+/// Lookups and iteration only ever see the pairs in effect, so the view is itself a bidirectional map.
+///
+/// Performance: `O(l·n)` per lookup and per element iterated over, for `l` layers of `n` pairs;
+/// less with layers that have a hash index (see `BidirMap::build_index()`).
+///
+/// # Examples
+///
+/// ```
+/// use bidir_map::{BidirMap, ChainedBidirMap};
+///
+/// let mut defaults = BidirMap::new();
+/// defaults.insert("save", "Ctrl+S");
+/// defaults.insert("quit", "Ctrl+Q");
+/// let mut user = BidirMap::new();
+/// user.insert("save", "F2");
+///
+/// let keymap = ChainedBidirMap::new(vec![&user, &defaults]);
+/// assert_eq!(keymap.get_by_first(&"save"), Some(&"F2"));
+/// assert_eq!(keymap.get_by_second(&"Ctrl+S"), None);
+/// assert_eq!(keymap.get_by_second(&"Ctrl+Q"), Some(&"quit"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct ChainedBidirMap<'a, Kv1: PartialEq + 'a, Kv2: PartialEq + 'a> {
+	layers: Vec<&'a BidirMap<Kv1, Kv2>>,
+}
+
+impl<'a, Kv1: PartialEq, Kv2: PartialEq> ChainedBidirMap<'a, Kv1, Kv2> {
+	/// Create a view of the specified layers, earliest taking precedence.
+	pub fn new(layers: Vec<&'a BidirMap<Kv1, Kv2>>) -> Self {
+		ChainedBidirMap{
+			layers,
+		}
+	}
+
+	/// Adds a layer below all the others, i.e. with the lowest precedence.
+	pub fn push_layer(&mut self, layer: &'a BidirMap<Kv1, Kv2>) {
+		self.layers.push(layer);
+	}
+
+	/// Gets the layers, earliest taking precedence.
+	pub fn layers(&self) -> &[&'a BidirMap<Kv1, Kv2>] {
+		&self.layers
+	}
+
+	/// Returns a reference to the second K/V corresponding to the first K/V in the first layer that holds it,
+	/// unless that pair is shadowed by an earlier layer holding its second K/V.
+	pub fn get_by_first<Q>(&self, key: &Q) -> Option<&'a Kv2>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		for (i, layer) in self.layers.iter().enumerate() {
+			if let Some(kv2) = layer.get_by_first(key) {
+				return if self.layers[..i].iter().any(|l| l.contains_second_key(kv2)) { None } else { Some(kv2) };
+			}
+		}
+		None
+	}
+
+	/// Returns a reference to the first K/V corresponding to the second K/V in the first layer that holds it,
+	/// unless that pair is shadowed by an earlier layer holding its first K/V.
+	pub fn get_by_second<Q>(&self, key: &Q) -> Option<&'a Kv1>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		for (i, layer) in self.layers.iter().enumerate() {
+			if let Some(kv1) = layer.get_by_second(key) {
+				return if self.layers[..i].iter().any(|l| l.contains_first_key(kv1)) { None } else { Some(kv1) };
+			}
+		}
+		None
+	}
+
+	/// Check if a pair in effect holds the first K/V
+	pub fn contains_first_key<Q>(&self, key: &Q) -> bool
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.get_by_first(key).is_some()
+	}
+
+	/// Check if a pair in effect holds the second K/V
+	pub fn contains_second_key<Q>(&self, key: &Q) -> bool
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.get_by_second(key).is_some()
+	}
+
+	/// Gets an iterator over the pairs in effect, layer by layer, each in the layer's order.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::{BidirMap, ChainedBidirMap};
+	///
+	/// let mut top = BidirMap::new();
+	/// top.insert(1, 'x');
+	/// let mut bottom = BidirMap::new();
+	/// bottom.insert(1, 'a');
+	/// bottom.insert(2, 'x');
+	/// bottom.insert(3, 'c');
+	///
+	/// let chained = ChainedBidirMap::new(vec![&top, &bottom]);
+	/// assert_eq!(chained.iter().collect::<Vec<_>>(), [&(1, 'x'), &(3, 'c')]);
+	/// ```
+	pub fn iter<'s>(&'s self) -> Iter<'s, Kv1, Kv2> {
+		Iter{
+			layers: &self.layers,
+			layer: 0,
+			iter: None,
+		}
+	}
+
+	/// Merges the pairs in effect into a single `BidirMap`, in the order of `iter()`.
+	pub fn flatten(&self) -> BidirMap<Kv1, Kv2>
+		where Kv1: Clone,
+		      Kv2: Clone,
+	{
+		self.iter().cloned().collect()
+	}
+}
+
+impl<'a, 'b, Kv1: PartialEq, Kv2: PartialEq> IntoIterator for &'b ChainedBidirMap<'a, Kv1, Kv2> {
+	type Item = &'b (Kv1, Kv2);
+	type IntoIter = Iter<'b, Kv1, Kv2>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+
+/// An iterator over the pairs in effect in a `ChainedBidirMap`.
+///
+/// See documentation of `ChainedBidirMap::iter()` for more.
+pub struct Iter<'a, Kv1: PartialEq + 'a, Kv2: PartialEq + 'a> {
+	layers: &'a [&'a BidirMap<Kv1, Kv2>],
+	/// The index of the layer `iter` is over
+	layer: usize,
+	iter: Option<MapIter<'a, Kv1, Kv2>>,
+}
+
+impl<'a, Kv1: PartialEq, Kv2: PartialEq> Iterator for Iter<'a, Kv1, Kv2> {
+	type Item = &'a (Kv1, Kv2);
+	fn next(&mut self) -> Option<Self::Item> {
+		loop {
+			if self.iter.is_none() {
+				self.iter = Some(self.layers.get(self.layer)?.iter());
+			}
+			match self.iter.as_mut().and_then(Iterator::next) {
+				Some(kvs) => {
+					let shadowing = &self.layers[..self.layer];
+					if !shadowing.iter().any(|l| l.contains_first_key(&kvs.0) || l.contains_second_key(&kvs.1)) {
+						return Some(kvs);
+					}
+				}
+				None => {
+					self.iter = None;
+					self.layer += 1;
+				}
+			}
+		}
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let (current, rest) = match self.iter {
+			Some(ref iter) => (iter.size_hint().1.unwrap_or(0), self.layer + 1),
+			None => (0, self.layer),
+		};
+		(0, Some(current + self.layers.iter().skip(rest).map(|l| l.len()).sum::<usize>()))
+	}
+}
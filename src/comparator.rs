@@ -0,0 +1,219 @@
+//! A `BidirMap` variant comparing K/Vs with custom equality relations.
+
+
+use core::borrow::Borrow;
+use alloc::vec::{self, Vec};
+use {BidirMap, Iter};
+
+
+/// An equality relation on `K`, standing in for `PartialEq` in `BidirMapWith`.
+///
+/// Like `PartialEq::eq()`, it should be symmetric and transitive.
+///
+/// Implemented for closures `Fn(&K, &K) -> bool`, though a named type keeps the map's type nameable.
+pub trait Equality<K: ?Sized> {
+	/// Whether the two K/Vs count as the same
+	fn eq(&self, lhs: &K, rhs: &K) -> bool;
+}
+
+impl<K: ?Sized, F: Fn(&K, &K) -> bool> Equality<K> for F {
+	fn eq(&self, lhs: &K, rhs: &K) -> bool {
+		self(lhs, rhs)
+	}
+}
+
+/// `PartialEq`, for the column of a `BidirMapWith` that needs no custom equality.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub struct Natural;
+
+impl<K: PartialEq + ?Sized> Equality<K> for Natural {
+	fn eq(&self, lhs: &K, rhs: &K) -> bool {
+		lhs == rhs
+	}
+}
+
+/// Equality of strings ignoring ASCII case, as per `str::eq_ignore_ascii_case()`.
+///
+/// Further normalization, like Unicode case folding or NFC, can be had by implementing `Equality` for another type.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub struct CaseInsensitive;
+
+impl<K: AsRef<str> + ?Sized> Equality<K> for CaseInsensitive {
+	fn eq(&self, lhs: &K, rhs: &K) -> bool {
+		lhs.as_ref().eq_ignore_ascii_case(rhs.as_ref())
+	}
+}
+
+
+/// A bidirectional map whose columns are compared with the specified `Equality` relations, rather than `PartialEq`.
+///
+/// The relations are used by all lookups and removals, and to find the pairs an inserted one collides with,
+/// while the K/Vs are stored as supplied: a map comparing case-insensitively still returns keys in their original case.
+///
+/// The relations are type parameters, so the map is `Clone` if they are, and comparing the relations themselves never comes up.
+/// They're not part of the map's data: to serialize one, serialize `iter()` and rebuild with `with_comparators()` and `insert()`.
+///
+/// Performance: `O(n)`, mostly.
+///
+/// # Examples
+///
+/// ```
+/// use bidir_map::comparator::{BidirMapWith, CaseInsensitive, Natural};
+///
+/// let mut users = BidirMapWith::with_comparators(CaseInsensitive, Natural);
+/// users.insert("Alice".to_string(), 1);
+///
+/// assert_eq!(users.get_by_first("ALICE"), Some(&1));
+/// assert_eq!(users.get_by_second(&1).map(|name| &name[..]), Some("Alice"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct BidirMapWith<Kv1, Kv2, E1: Equality<Kv1>, E2: Equality<Kv2>> {
+	cont: Vec<(Kv1, Kv2)>,
+	first_eq: E1,
+	second_eq: E2,
+}
+
+impl<Kv1, Kv2, E1: Equality<Kv1>, E2: Equality<Kv2>> BidirMapWith<Kv1, Kv2, E1, E2> {
+	/// Create a new empty instance of `BidirMapWith`, comparing first K/Vs with `first_eq` and second K/Vs with `second_eq`
+	pub fn with_comparators(first_eq: E1, second_eq: E2) -> Self {
+		BidirMapWith{
+			cont: Vec::new(),
+			first_eq,
+			second_eq,
+		}
+	}
+
+	/// Clears the map, removing all entries.
+	pub fn clear(&mut self) {
+		self.cont.clear()
+	}
+
+	/// Inserts a K/V-K/V pair into the map, see `BidirMap::insert()`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::comparator::{BidirMapWith, CaseInsensitive};
+	///
+	/// let mut map = BidirMapWith::with_comparators(CaseInsensitive, |l: &u32, r: &u32| l % 10 == r % 10);
+	/// map.insert("foo", 1);
+	/// assert_eq!(map.insert("FOO", 12), Some(("foo", 1)));
+	/// assert_eq!(map.insert("bar", 2), Some(("FOO", 12)));
+	/// assert_eq!(map.len(), 1);
+	/// ```
+	pub fn insert(&mut self, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)> {
+		let by_first = self.cont.iter().position(|kvs| self.first_eq.eq(&kvs.0, &kv1)).map(|idx| self.cont.swap_remove(idx));
+		let by_second = self.cont.iter().position(|kvs| self.second_eq.eq(&kvs.1, &kv2)).map(|idx| self.cont.swap_remove(idx));
+
+		self.cont.push((kv1, kv2));
+
+		by_first.or(by_second)
+	}
+
+	/// Gets an iterator over the entries of the map, with the K/Vs as inserted.
+	pub fn iter<'s>(&'s self) -> Iter<'s, Kv1, Kv2> {
+		Iter{
+			iter: self.cont.iter(),
+		}
+	}
+
+	/// Returns the number of elements in the map.
+	pub fn len(&self) -> usize {
+		self.cont.len()
+	}
+
+	/// Returns true if the map contains no elements.
+	pub fn is_empty(&self) -> bool {
+		self.cont.is_empty()
+	}
+
+
+	/// Returns a reference to the second K/V corresponding to the first K/V, as per the first column's relation.
+	pub fn get_by_first<Q>(&self, key: &Q) -> Option<&Kv2>
+		where Kv1: Borrow<Q>,
+		      Q  : ?Sized,
+		      E1 : Equality<Q>,
+	{
+		self.cont.iter().find(|kvs| self.first_eq.eq(key, kvs.0.borrow())).map(|kvs| &kvs.1)
+	}
+
+	/// Returns a reference to the first K/V corresponding to the second K/V, as per the second column's relation.
+	pub fn get_by_second<Q>(&self, key: &Q) -> Option<&Kv1>
+		where Kv2: Borrow<Q>,
+		      Q  : ?Sized,
+		      E2 : Equality<Q>,
+	{
+		self.cont.iter().find(|kvs| self.second_eq.eq(key, kvs.1.borrow())).map(|kvs| &kvs.0)
+	}
+
+	/// Check if the map contains the first K/V, as per the first column's relation
+	pub fn contains_first_key<Q>(&self, key: &Q) -> bool
+		where Kv1: Borrow<Q>,
+		      Q  : ?Sized,
+		      E1 : Equality<Q>,
+	{
+		self.get_by_first(key).is_some()
+	}
+
+	/// Check if the map contains the second K/V, as per the second column's relation
+	pub fn contains_second_key<Q>(&self, key: &Q) -> bool
+		where Kv2: Borrow<Q>,
+		      Q  : ?Sized,
+		      E2 : Equality<Q>,
+	{
+		self.get_by_second(key).is_some()
+	}
+
+	/// Removes the pair corresponding to the first K/V from the map, returning it if the key was previously in the map.
+	pub fn remove_by_first<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv1: Borrow<Q>,
+		      Q  : ?Sized,
+		      E1 : Equality<Q>,
+	{
+		self.cont.iter().position(|kvs| self.first_eq.eq(key, kvs.0.borrow())).map(|idx| self.cont.swap_remove(idx))
+	}
+
+	/// Removes the pair corresponding to the second K/V from the map, returning it if the key was previously in the map.
+	pub fn remove_by_second<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv2: Borrow<Q>,
+		      Q  : ?Sized,
+		      E2 : Equality<Q>,
+	{
+		self.cont.iter().position(|kvs| self.second_eq.eq(key, kvs.1.borrow())).map(|idx| self.cont.swap_remove(idx))
+	}
+}
+
+
+impl<Kv1, Kv2, E1: Equality<Kv1>, E2: Equality<Kv2>> IntoIterator for BidirMapWith<Kv1, Kv2, E1, E2> {
+	type Item = (Kv1, Kv2);
+	type IntoIter = vec::IntoIter<(Kv1, Kv2)>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.cont.into_iter()
+	}
+}
+
+impl<'a, Kv1, Kv2, E1: Equality<Kv1>, E2: Equality<Kv2>> IntoIterator for &'a BidirMapWith<Kv1, Kv2, E1, E2> {
+	type Item = &'a (Kv1, Kv2);
+	type IntoIter = Iter<'a, Kv1, Kv2>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+impl<Kv1, Kv2, E1: Equality<Kv1>, E2: Equality<Kv2>> Extend<(Kv1, Kv2)> for BidirMapWith<Kv1, Kv2, E1, E2> {
+	/// Unlike `BidirMap`'s, this `insert()`s each pair, as the relations aren't otherwise checked anywhere.
+	fn extend<T: IntoIterator<Item=(Kv1, Kv2)>>(&mut self, iter: T) {
+		for (kv1, kv2) in iter {
+			self.insert(kv1, kv2);
+		}
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq, E1: Equality<Kv1>, E2: Equality<Kv2>> From<BidirMapWith<Kv1, Kv2, E1, E2>> for BidirMap<Kv1, Kv2> {
+	/// The pairs aren't checked for collisions under `PartialEq`; there can only be any if a relation tells apart K/Vs `PartialEq` considers equal.
+	fn from(map: BidirMapWith<Kv1, Kv2, E1, E2>) -> Self {
+		BidirMap::from_cont(map.cont)
+	}
+}
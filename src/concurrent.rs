@@ -0,0 +1,303 @@
+//! A bidirectional map for sharing between threads, with sharded locking.
+
+
+use core::borrow::Borrow;
+use core::hash::{BuildHasher, Hash};
+use core::sync::atomic::{AtomicUsize, Ordering};
+use std::collections::hash_map::{HashMap, RandomState};
+use std::sync::{RwLock, RwLockWriteGuard};
+use alloc::vec::Vec;
+use BidirMap;
+
+
+/// The number of shards per direction used by `ConcurrentBidirMap::new()`
+const DEFAULT_SHARDS: usize = 16;
+
+
+/// A bidirectional map shareable between threads, where operations on unrelated K/Vs mostly don't contend.
+///
+/// Each direction is split into shards, hash maps behind their own `RwLock`s.
+/// A lookup only takes the read lock of the shard holding its key,
+/// while a mutation write-locks every shard it touches in either direction, in a fixed order, for its whole duration.
+/// So no operation ever observes the two directions disagreeing,
+/// and the map behaves as if every operation happened at once.
+///
+/// As with `BidirHashMap`, K/Vs are stored once per direction, so inserting requires `Clone`;
+/// lookups return clones rather than references, which couldn't outlive the locks.
+///
+/// # Panics
+///
+/// All methods panic if a thread panicked while holding one of the map's locks.
+///
+/// # Examples
+///
+/// ```
+/// use bidir_map::ConcurrentBidirMap;
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// let peers = Arc::new(ConcurrentBidirMap::new());
+/// let threads: Vec<_> = (0..4).map(|id| {
+///     let peers = peers.clone();
+///     thread::spawn(move || { peers.insert(id, format!("10.0.0.{}", id)); })
+/// }).collect();
+/// for thread in threads {
+///     thread.join().unwrap();
+/// }
+///
+/// assert_eq!(peers.len(), 4);
+/// assert_eq!(peers.get_by_second("10.0.0.2"), Some(2));
+/// ```
+pub struct ConcurrentBidirMap<Kv1: Hash + Eq, Kv2: Hash + Eq> {
+	first: Vec<RwLock<HashMap<Kv1, Kv2>>>,
+	second: Vec<RwLock<HashMap<Kv2, Kv1>>>,
+	hasher: RandomState,
+	len: AtomicUsize,
+}
+
+/// The write guards of one mutation, first-direction shards first, each set in ascending order
+struct Guards<'a, Kv1: 'a, Kv2: 'a> {
+	first: Vec<(usize, RwLockWriteGuard<'a, HashMap<Kv1, Kv2>>)>,
+	second: Vec<(usize, RwLockWriteGuard<'a, HashMap<Kv2, Kv1>>)>,
+}
+
+/// The outcome of a mutation attempt
+enum Attempt<R> {
+	Done(R),
+	/// Retry while also holding these shards
+	Need(Option<usize>, Option<usize>),
+}
+
+impl<Kv1: Hash + Eq, Kv2: Hash + Eq> ConcurrentBidirMap<Kv1, Kv2> {
+	/// Create a new empty instance of `ConcurrentBidirMap`
+	pub fn new() -> Self {
+		ConcurrentBidirMap::with_shards(DEFAULT_SHARDS)
+	}
+
+	/// Create a new empty instance of `ConcurrentBidirMap` with the specified number of shards per direction.
+	///
+	/// # Panics
+	///
+	/// If `shards` is 0.
+	pub fn with_shards(shards: usize) -> Self {
+		assert!(shards != 0, "ConcurrentBidirMap needs at least one shard");
+		ConcurrentBidirMap{
+			first: (0..shards).map(|_| RwLock::new(HashMap::new())).collect(),
+			second: (0..shards).map(|_| RwLock::new(HashMap::new())).collect(),
+			hasher: RandomState::new(),
+			len: AtomicUsize::new(0),
+		}
+	}
+
+	/// Returns the number of elements in the map.
+	///
+	/// Other threads may have changed it by the time it's returned.
+	pub fn len(&self) -> usize {
+		self.len.load(Ordering::SeqCst)
+	}
+
+	/// Returns true if the map contains no elements, see `len()`.
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Returns a clone of the second K/V corresponding to the first K/V.
+	pub fn get_by_first<Q>(&self, key: &Q) -> Option<Kv2>
+		where Kv1: Borrow<Q>,
+		      Kv2: Clone,
+		      Q  : Hash + Eq + ?Sized,
+	{
+		self.first[self.shard_of(key)].read().unwrap().get(key).cloned()
+	}
+
+	/// Returns a clone of the first K/V corresponding to the second K/V.
+	pub fn get_by_second<Q>(&self, key: &Q) -> Option<Kv1>
+		where Kv2: Borrow<Q>,
+		      Kv1: Clone,
+		      Q  : Hash + Eq + ?Sized,
+	{
+		self.second[self.shard_of(key)].read().unwrap().get(key).cloned()
+	}
+
+	/// Check if the map contains the first K/V
+	pub fn contains_first_key<Q>(&self, key: &Q) -> bool
+		where Kv1: Borrow<Q>,
+		      Q  : Hash + Eq + ?Sized,
+	{
+		self.first[self.shard_of(key)].read().unwrap().contains_key(key)
+	}
+
+	/// Check if the map contains the second K/V
+	pub fn contains_second_key<Q>(&self, key: &Q) -> bool
+		where Kv2: Borrow<Q>,
+		      Q  : Hash + Eq + ?Sized,
+	{
+		self.second[self.shard_of(key)].read().unwrap().contains_key(key)
+	}
+
+	fn shard_of<Q: Hash + ?Sized>(&self, key: &Q) -> usize {
+		(self.hasher.hash_one(key) % self.first.len() as u64) as usize
+	}
+
+	/// Repeatedly write-locks the wanted shards and runs `f`, adding any further shards it needs, until it's done
+	fn locked<R, F>(&self, first: usize, second: usize, mut f: F) -> R
+		where F: FnMut(&mut Guards<Kv1, Kv2>) -> Attempt<R>,
+	{
+		let (mut want_first, mut want_second) = (Vec::with_capacity(2), Vec::with_capacity(2));
+		want_first.push(first);
+		want_second.push(second);
+		loop {
+			want_first.sort_unstable();
+			want_first.dedup();
+			want_second.sort_unstable();
+			want_second.dedup();
+
+			let mut guards = Guards{
+				first: want_first.iter().map(|&idx| (idx, self.first[idx].write().unwrap())).collect(),
+				second: want_second.iter().map(|&idx| (idx, self.second[idx].write().unwrap())).collect(),
+			};
+			match f(&mut guards) {
+				Attempt::Done(result) => return result,
+				Attempt::Need(first, second) => {
+					want_first.extend(first);
+					want_second.extend(second);
+				}
+			}
+		}
+	}
+}
+
+impl<Kv1: Hash + Eq + Clone, Kv2: Hash + Eq + Clone> ConcurrentBidirMap<Kv1, Kv2> {
+	/// Inserts a K/V-K/V pair into the map, see `BidirMap::insert()`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::ConcurrentBidirMap;
+	///
+	/// let map = ConcurrentBidirMap::new();
+	/// map.insert(1, "a");
+	/// map.insert(2, "b");
+	/// assert_eq!(map.insert(1, "b"), Some((1, "a")));
+	/// assert_eq!(map.len(), 1);
+	/// ```
+	pub fn insert(&self, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)> {
+		let (shard1, shard2) = (self.shard_of(&kv1), self.shard_of(&kv2));
+		self.locked(shard1, shard2, |guards| {
+			// The shards of the other halves of the pairs about to be displaced
+			let old2 = guards.first(shard1).get(&kv1).map(|kv2| self.shard_of(kv2));
+			let old1 = guards.second(shard2).get(&kv2).map(|kv1| self.shard_of(kv1));
+			if old2.is_some_and(|idx| !guards.holds_second(idx)) || old1.is_some_and(|idx| !guards.holds_first(idx)) {
+				return Attempt::Need(old1, old2);
+			}
+
+			let by_first = guards.first(shard1).remove_entry(&kv1).map(|(old1, old2)| {
+				let shard = self.shard_of(&old2);
+				guards.second(shard).remove(&old2);
+				(old1, old2)
+			});
+			let by_second = guards.second(shard2).remove_entry(&kv2).map(|(old2, old1)| {
+				let shard = self.shard_of(&old1);
+				guards.first(shard).remove(&old1);
+				(old1, old2)
+			});
+
+			guards.first(shard1).insert(kv1.clone(), kv2.clone());
+			guards.second(shard2).insert(kv2.clone(), kv1.clone());
+			let removed = by_first.is_some() as usize + by_second.is_some() as usize;
+			self.len.fetch_add(1, Ordering::SeqCst);
+			self.len.fetch_sub(removed, Ordering::SeqCst);
+			Attempt::Done(by_first.or(by_second))
+		})
+	}
+
+	/// Removes the pair corresponding to the first K/V from the map, returning it if the key was previously in the map.
+	pub fn remove_by_first<Q>(&self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv1: Borrow<Q>,
+		      Q  : Hash + Eq + ?Sized,
+	{
+		let shard1 = self.shard_of(key);
+		let shard2 = match self.first[shard1].read().unwrap().get(key) {
+			Some(kv2) => self.shard_of(kv2),
+			None => return None,
+		};
+		self.locked(shard1, shard2, |guards| {
+			let shard2 = match guards.first(shard1).get(key) {
+				Some(kv2) => self.shard_of(kv2),
+				None => return Attempt::Done(None),
+			};
+			if !guards.holds_second(shard2) {
+				return Attempt::Need(None, Some(shard2));
+			}
+
+			let (kv1, kv2) = guards.first(shard1).remove_entry(key).unwrap();
+			guards.second(shard2).remove(&kv2);
+			self.len.fetch_sub(1, Ordering::SeqCst);
+			Attempt::Done(Some((kv1, kv2)))
+		})
+	}
+
+	/// Removes the pair corresponding to the second K/V from the map, returning it if the key was previously in the map.
+	pub fn remove_by_second<Q>(&self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv2: Borrow<Q>,
+		      Q  : Hash + Eq + ?Sized,
+	{
+		let shard2 = self.shard_of(key);
+		let shard1 = match self.second[shard2].read().unwrap().get(key) {
+			Some(kv1) => self.shard_of(kv1),
+			None => return None,
+		};
+		self.locked(shard1, shard2, |guards| {
+			let shard1 = match guards.second(shard2).get(key) {
+				Some(kv1) => self.shard_of(kv1),
+				None => return Attempt::Done(None),
+			};
+			if !guards.holds_first(shard1) {
+				return Attempt::Need(Some(shard1), None);
+			}
+
+			let (kv2, kv1) = guards.second(shard2).remove_entry(key).unwrap();
+			guards.first(shard1).remove(&kv1);
+			self.len.fetch_sub(1, Ordering::SeqCst);
+			Attempt::Done(Some((kv1, kv2)))
+		})
+	}
+
+	/// Copies the map's pairs as of a single point in time, in no particular order.
+	///
+	/// This read-locks every shard, so it blocks all mutations while it runs.
+	pub fn snapshot(&self) -> BidirMap<Kv1, Kv2> {
+		let first: Vec<_> = self.first.iter().map(|shard| shard.read().unwrap()).collect();
+		let second: Vec<_> = self.second.iter().map(|shard| shard.read().unwrap()).collect();
+		debug_assert_eq!(first.iter().map(|shard| shard.len()).sum::<usize>(), second.iter().map(|shard| shard.len()).sum::<usize>());
+		debug_assert!(first.iter().flat_map(|shard| shard.iter()).all(|(kv1, kv2)| second[self.shard_of(kv2)].get(kv2) == Some(kv1)));
+
+		let pairs: Vec<_> = first.iter().flat_map(|shard| shard.iter()).map(|(kv1, kv2)| (kv1.clone(), kv2.clone())).collect();
+		BidirMap::from_cont(pairs)
+	}
+}
+
+impl<'a, Kv1, Kv2> Guards<'a, Kv1, Kv2> {
+	fn first(&mut self, idx: usize) -> &mut HashMap<Kv1, Kv2> {
+		&mut self.first.iter_mut().find(|guard| guard.0 == idx).expect("shard not locked").1
+	}
+
+	fn second(&mut self, idx: usize) -> &mut HashMap<Kv2, Kv1> {
+		&mut self.second.iter_mut().find(|guard| guard.0 == idx).expect("shard not locked").1
+	}
+
+	fn holds_first(&self, idx: usize) -> bool {
+		self.first.iter().any(|guard| guard.0 == idx)
+	}
+
+	fn holds_second(&self, idx: usize) -> bool {
+		self.second.iter().any(|guard| guard.0 == idx)
+	}
+}
+
+
+impl<Kv1: Hash + Eq, Kv2: Hash + Eq> Default for ConcurrentBidirMap<Kv1, Kv2> {
+	fn default() -> Self {
+		ConcurrentBidirMap::new()
+	}
+}
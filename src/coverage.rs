@@ -0,0 +1,136 @@
+//! Checking that a `BidirMap` maps every element of a known domain.
+
+
+use core::borrow::Borrow;
+#[cfg(feature = "std")]
+use core::hash::{BuildHasher, Hash};
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+use BidirMap;
+
+
+impl<Kv1: PartialEq, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
+	/// Checks that every element of `domain` is a first K/V in the map, or returns all those that aren't, in the order of `domain`.
+	///
+	/// Performance: `O(n·m)`, for `m` elements of `domain`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut names = BidirMap::new();
+	/// names.insert("de", "Germany");
+	/// names.insert("fr", "France");
+	///
+	/// assert_eq!(names.covers_first(vec!["fr", "de"]), Ok(()));
+	/// assert_eq!(names.covers_first(vec!["de", "it", "fr", "es"]), Err(vec!["it", "es"]));
+	/// ```
+	pub fn covers_first<Q, I>(&self, domain: I) -> Result<(), Vec<Q>>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq,
+		      I  : IntoIterator<Item = Q>,
+	{
+		misses(domain.into_iter().filter(|key| !self.contains_first_key(key)).collect())
+	}
+
+	/// Checks that every element of `domain` is a second K/V in the map, or returns all those that aren't, in the order of `domain`.
+	///
+	/// Performance: `O(n·m)`, for `m` elements of `domain`.
+	pub fn covers_second<Q, I>(&self, domain: I) -> Result<(), Vec<Q>>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq,
+		      I  : IntoIterator<Item = Q>,
+	{
+		misses(domain.into_iter().filter(|key| !self.contains_second_key(key)).collect())
+	}
+
+	/// Check if the map is a bijection between the two domains:
+	/// every element of `firsts` is a first K/V and every element of `seconds` a second K/V,
+	/// and every pair has its first K/V in `firsts` and its second K/V in `seconds`.
+	///
+	/// Performance: `O(n·(m₁ + m₂))`, for `m₁` and `m₂` elements of the domains.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert(1, 'a');
+	/// map.insert(2, 'b');
+	///
+	/// assert!(map.is_bijection_between(&[1, 2], &['b', 'a']));
+	/// assert!(!map.is_bijection_between(&[1], &['a']));
+	/// assert!(!map.is_bijection_between(&[1, 2, 3], &['a', 'b', 'c']));
+	/// ```
+	pub fn is_bijection_between<Q1, Q2>(&self, firsts: &[Q1], seconds: &[Q2]) -> bool
+		where Kv1: Borrow<Q1>,
+		      Kv2: Borrow<Q2>,
+		      Q1 : PartialEq,
+		      Q2 : PartialEq,
+	{
+		firsts.iter().all(|key| self.contains_first_key(key)) && seconds.iter().all(|key| self.contains_second_key(key)) &&
+		self.cont.iter().all(|kvs| firsts.contains(kvs.0.borrow()) && seconds.contains(kvs.1.borrow()))
+	}
+
+	/// Check if the other map holds exactly the map's pairs, each with its K/Vs swapped, in any order.
+	///
+	/// Maps of different lengths are told apart without looking at their pairs.
+	///
+	/// Performance: `O(n²)`, without allocating.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let codes: BidirMap<_, _> = vec![("de", 49), ("fr", 33)].into_iter().collect();
+	/// let countries: BidirMap<_, _> = vec![(33, "fr"), (49, "de")].into_iter().collect();
+	/// assert!(codes.is_inverse_of(&countries));
+	///
+	/// let stale: BidirMap<_, _> = vec![(33, "fr"), (49, "at")].into_iter().collect();
+	/// assert!(!codes.is_inverse_of(&stale));
+	/// ```
+	pub fn is_inverse_of(&self, other: &BidirMap<Kv2, Kv1>) -> bool {
+		self.cont.len() == other.cont.len() && self.cont.iter().all(|kvs| other.cont.iter().any(|other| other.0 == kvs.1 && other.1 == kvs.0))
+	}
+
+	/// Check if the `HashMap` maps exactly every second K/V of the map to its first K/V.
+	///
+	/// Like `is_inverse_of()`, for a reverse mapping kept in a `HashMap`.
+	///
+	/// Performance: `O(n)`, without allocating.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	/// use std::collections::HashMap;
+	///
+	/// let codes: BidirMap<_, _> = vec![("de", 49), ("fr", 33)].into_iter().collect();
+	/// let mut countries = HashMap::new();
+	/// countries.insert(49, "de");
+	/// countries.insert(33, "fr");
+	/// assert!(codes.is_inverse_of_hash_map(&countries));
+	///
+	/// countries.insert(43, "at");
+	/// assert!(!codes.is_inverse_of_hash_map(&countries));
+	/// ```
+	#[cfg(feature = "std")]
+	pub fn is_inverse_of_hash_map<S: BuildHasher>(&self, other: &HashMap<Kv2, Kv1, S>) -> bool
+		where Kv2: Hash + Eq,
+	{
+		self.cont.len() == other.len() && self.cont.iter().all(|kvs| other.get(&kvs.1) == Some(&kvs.0))
+	}
+}
+
+
+fn misses<Q>(misses: Vec<Q>) -> Result<(), Vec<Q>> {
+	if misses.is_empty() {
+		Ok(())
+	} else {
+		Err(misses)
+	}
+}
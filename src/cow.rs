@@ -0,0 +1,179 @@
+//! A `BidirMap` shared between clones until one of them is modified, see `CowBidirMap`.
+
+
+use core::borrow::Borrow;
+use core::fmt;
+use core::iter::FromIterator;
+use core::ops::Deref;
+use alloc::sync::Arc;
+use BidirMap;
+
+
+/// A `BidirMap` behind an `Arc`, so that `clone()` takes `O(1)`, copied on the first modification of a shared instance.
+///
+/// The map dereferences to the `BidirMap`, so all reading methods are the plain map's, with no overhead but the dereference.
+/// Modifications go through `make_mut()`, which copies the whole map if other clones share it, as `Arc::make_mut()` does;
+/// only this path requires `Clone` on the K/Vs. A map that isn't shared, or no longer is, is modified in place.
+///
+/// `PersistentBidirMap` copies only the chunks a modification touches, for maps modified after many clones,
+/// at the cost of lookups going through the chunks.
+///
+/// # Examples
+///
+/// ```
+/// use bidir_map::{BidirMap, CowBidirMap};
+///
+/// let table: BidirMap<_, _> = vec![(1, "one"), (2, "two")].into_iter().collect();
+/// let shared = CowBidirMap::from(table);
+///
+/// let mut tweaked = shared.clone();
+/// assert!(tweaked.is_shared());
+/// tweaked.insert(3, "three");
+/// assert!(!tweaked.is_shared());
+///
+/// assert_eq!(shared.len(), 2);
+/// assert_eq!(tweaked.get_by_second(&"three"), Some(&3));
+/// ```
+pub struct CowBidirMap<Kv1: PartialEq, Kv2: PartialEq> {
+	map: Arc<BidirMap<Kv1, Kv2>>,
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> CowBidirMap<Kv1, Kv2> {
+	/// Create a new empty instance of `CowBidirMap`
+	pub fn new() -> Self {
+		CowBidirMap::from(BidirMap::new())
+	}
+
+	/// Check if other clones share the map, so that modifying it would copy it.
+	pub fn is_shared(&self) -> bool {
+		Arc::strong_count(&self.map) != 1
+	}
+
+	/// Check if both maps are clones sharing the same pairs, not merely equal ones.
+	pub fn ptr_eq(&self, other: &Self) -> bool {
+		Arc::ptr_eq(&self.map, &other.map)
+	}
+
+	/// Clears the map, removing all entries.
+	///
+	/// A shared map is let go of rather than copied, for an empty one with its settings and indexes.
+	pub fn clear(&mut self) {
+		match Arc::get_mut(&mut self.map) {
+			Some(map) => map.clear(),
+			None => self.map = Arc::new(self.map.empty_like()),
+		}
+	}
+}
+
+impl<Kv1: PartialEq + Clone, Kv2: PartialEq + Clone> CowBidirMap<Kv1, Kv2> {
+	/// Gets a mutable reference to the map, copying it first if other clones share it.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::CowBidirMap;
+	///
+	/// let mut map = CowBidirMap::new();
+	/// map.make_mut().insert('a', 1);
+	///
+	/// let snapshot = map.clone();
+	/// map.make_mut().retain(|_, &kv2| kv2 != 1);
+	/// assert!(map.is_empty());
+	/// assert_eq!(snapshot.len(), 1);
+	/// ```
+	pub fn make_mut(&mut self) -> &mut BidirMap<Kv1, Kv2> {
+		Arc::make_mut(&mut self.map)
+	}
+
+	/// Unwraps the map, copying it if other clones share it.
+	pub fn into_inner(self) -> BidirMap<Kv1, Kv2> {
+		Arc::try_unwrap(self.map).unwrap_or_else(|map| (*map).clone())
+	}
+
+	/// Inserts a K/V-K/V pair into the map, as `BidirMap::insert()` does, copying the map first if it's shared.
+	pub fn insert(&mut self, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)> {
+		self.make_mut().insert(kv1, kv2)
+	}
+
+	/// Removes the pair with the first K/V from the map, returning it if the key was previously in the map.
+	///
+	/// The map is only copied if it's shared and has the key.
+	pub fn remove_by_first<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		if self.map.contains_first_key(key) {
+			self.make_mut().remove_by_first(key)
+		} else {
+			None
+		}
+	}
+
+	/// Removes the pair with the second K/V from the map, returning it if the key was previously in the map.
+	///
+	/// The map is only copied if it's shared and has the key.
+	pub fn remove_by_second<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		if self.map.contains_second_key(key) {
+			self.make_mut().remove_by_second(key)
+		} else {
+			None
+		}
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> Deref for CowBidirMap<Kv1, Kv2> {
+	type Target = BidirMap<Kv1, Kv2>;
+
+	fn deref(&self) -> &BidirMap<Kv1, Kv2> {
+		&self.map
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> Clone for CowBidirMap<Kv1, Kv2> {
+	fn clone(&self) -> Self {
+		CowBidirMap{
+			map: self.map.clone(),
+		}
+	}
+}
+
+impl<Kv1: PartialEq + fmt::Debug, Kv2: PartialEq + fmt::Debug> fmt::Debug for CowBidirMap<Kv1, Kv2> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.map.fmt(f)
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> PartialEq for CowBidirMap<Kv1, Kv2> {
+	fn eq(&self, other: &Self) -> bool {
+		self.map == other.map
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> Default for CowBidirMap<Kv1, Kv2> {
+	fn default() -> Self {
+		CowBidirMap::new()
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> From<BidirMap<Kv1, Kv2>> for CowBidirMap<Kv1, Kv2> {
+	fn from(map: BidirMap<Kv1, Kv2>) -> Self {
+		CowBidirMap{
+			map: Arc::new(map),
+		}
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> FromIterator<(Kv1, Kv2)> for CowBidirMap<Kv1, Kv2> {
+	fn from_iter<I: IntoIterator<Item=(Kv1, Kv2)>>(iter: I) -> Self {
+		CowBidirMap::from(BidirMap::from_iter(iter))
+	}
+}
+
+impl<Kv1: PartialEq + Clone, Kv2: PartialEq + Clone> Extend<(Kv1, Kv2)> for CowBidirMap<Kv1, Kv2> {
+	fn extend<I: IntoIterator<Item=(Kv1, Kv2)>>(&mut self, iter: I) {
+		self.make_mut().extend(iter)
+	}
+}
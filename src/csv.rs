@@ -0,0 +1,237 @@
+//! Reading `BidirMap`s from two-column CSV, and writing them as such.
+//!
+//! Fields may be quoted with `"`, within which the delimiter, line breaks and doubled `""`s are taken literally, as per RFC 4180.
+//! Records end with `\n` or `\r\n`; blank lines are skipped.
+
+
+use core::fmt::Display;
+use core::mem;
+use core::str::FromStr;
+use std::io::{self, Read, Write};
+use std::string::{String, ToString};
+use std::vec::Vec;
+use {BidirMap, Column, CsvImportError, OnDuplicate};
+
+
+/// How `BidirMap::from_csv_reader()` reads its input.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct CsvOptions {
+	/// Whether the first record is a header, to be skipped; `true` by default
+	pub has_header: bool,
+	/// The character between the two fields of a record; `','` by default
+	pub delimiter: char,
+	/// What to do with records repeating a K/V; `OnDuplicate::Error` by default
+	pub on_duplicate: OnDuplicate,
+	/// The most pairs to read, failing with `CsvImportError::Full` on a record past that,
+	/// and to bound the map to, as by `BidirMap::set_max_len()`; unbounded by default
+	pub max_len: Option<usize>,
+}
+
+impl Default for CsvOptions {
+	fn default() -> Self {
+		CsvOptions{
+			has_header: true,
+			delimiter: ',',
+			on_duplicate: OnDuplicate::Error,
+			max_len: None,
+		}
+	}
+}
+
+
+impl BidirMap<String, String> {
+	/// Reads a map from two-column CSV, first column into first K/Vs.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::{BidirMap, Column, CsvImportError};
+	/// use bidir_map::csv::CsvOptions;
+	///
+	/// let csv = "code,name\nde,Germany\nfr,France\n";
+	/// let map = BidirMap::from_csv_reader(csv.as_bytes(), CsvOptions::default()).unwrap();
+	/// assert_eq!(map.get_by_second("France").map(|c| &c[..]), Some("fr"));
+	///
+	/// match BidirMap::from_csv_reader("code,name\nde,Germany\nat,Germany\n".as_bytes(), CsvOptions::default()) {
+	///     Err(CsvImportError::Duplicate{line: 3, column: Column::Second, earlier_line: 2}) => {}
+	///     other => panic!("{:?}", other),
+	/// }
+	/// ```
+	pub fn from_csv_reader<R: Read>(r: R, options: CsvOptions) -> Result<Self, CsvImportError> {
+		read_pairs(r, options, Some, Some)
+	}
+}
+
+impl<Kv1: PartialEq + FromStr, Kv2: PartialEq + FromStr> BidirMap<Kv1, Kv2> {
+	/// Reads a map from two-column CSV like `from_csv_reader()`, parsing the fields with `FromStr`.
+	///
+	/// Fields that fail to parse are reported as `CsvImportError::Parse`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	/// use bidir_map::csv::CsvOptions;
+	///
+	/// let options = CsvOptions{has_header: false, delimiter: ';', ..CsvOptions::default()};
+	/// let map: BidirMap<u16, char> = BidirMap::from_csv_reader_parsed("80;h\n443;s".as_bytes(), options).unwrap();
+	/// assert_eq!(map.get_by_second(&'s'), Some(&443));
+	/// ```
+	pub fn from_csv_reader_parsed<R: Read>(r: R, options: CsvOptions) -> Result<Self, CsvImportError> {
+		read_pairs(r, options, |field| field.parse().ok(), |field| field.parse().ok())
+	}
+}
+
+impl<Kv1: PartialEq + Display, Kv2: PartialEq + Display> BidirMap<Kv1, Kv2> {
+	/// Writes the map as two-column CSV in iteration order, optionally after a header, with `\n` line breaks.
+	///
+	/// Fields are quoted where needed to be read back by `from_csv_reader()` with the same delimiter.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert(1, "one");
+	/// map.insert(2, "two, too");
+	///
+	/// let mut csv = Vec::new();
+	/// map.write_csv(&mut csv, ',', Some(("id", "name"))).unwrap();
+	/// assert_eq!(String::from_utf8(csv).unwrap(), "id,name\n1,one\n2,\"two, too\"\n");
+	/// ```
+	pub fn write_csv<W: Write>(&self, mut w: W, delimiter: char, header: Option<(&str, &str)>) -> io::Result<()> {
+		if let Some((first, second)) = header {
+			write_record(&mut w, delimiter, first, second)?;
+		}
+		for (kv1, kv2) in &self.cont {
+			write_record(&mut w, delimiter, &kv1.to_string(), &kv2.to_string())?;
+		}
+		w.flush()
+	}
+}
+
+
+fn read_pairs<R, Kv1, Kv2, P1, P2>(mut r: R, options: CsvOptions, mut parse_first: P1, mut parse_second: P2) -> Result<BidirMap<Kv1, Kv2>, CsvImportError>
+	where R : Read,
+	      Kv1: PartialEq,
+	      Kv2: PartialEq,
+	      P1 : FnMut(String) -> Option<Kv1>,
+	      P2 : FnMut(String) -> Option<Kv2>,
+{
+	let mut text = String::new();
+	r.read_to_string(&mut text)?;
+
+	let mut cont: Vec<(Kv1, Kv2)> = Vec::new();
+	// The line of each pair in `cont`
+	let mut lines: Vec<usize> = Vec::new();
+	for (n, (line, fields)) in records(&text, options.delimiter)?.into_iter().enumerate() {
+		if n == 0 && options.has_header {
+			continue;
+		}
+		if fields.len() != 2 {
+			return Err(CsvImportError::FieldCount{line, fields: fields.len()});
+		}
+		let mut fields = fields.into_iter();
+		let kv1 = parse_first(fields.next().unwrap()).ok_or(CsvImportError::Parse{line, column: Column::First})?;
+		let kv2 = parse_second(fields.next().unwrap()).ok_or(CsvImportError::Parse{line, column: Column::Second})?;
+
+		match options.on_duplicate {
+			OnDuplicate::Error => {
+				if let Some(idx) = cont.iter().position(|kvs| kvs.0 == kv1) {
+					return Err(CsvImportError::Duplicate{line, column: Column::First, earlier_line: lines[idx]});
+				}
+				if let Some(idx) = cont.iter().position(|kvs| kvs.1 == kv2) {
+					return Err(CsvImportError::Duplicate{line, column: Column::Second, earlier_line: lines[idx]});
+				}
+			}
+			OnDuplicate::FirstWins => {
+				if cont.iter().any(|kvs| kvs.0 == kv1 || kvs.1 == kv2) {
+					continue;
+				}
+			}
+			OnDuplicate::LastWins => {
+				if let Some(idx) = cont.iter().position(|kvs| kvs.0 == kv1) {
+					cont.swap_remove(idx);
+					lines.swap_remove(idx);
+				}
+				if let Some(idx) = cont.iter().position(|kvs| kvs.1 == kv2) {
+					cont.swap_remove(idx);
+					lines.swap_remove(idx);
+				}
+			}
+		}
+		if let Some(max_len) = options.max_len {
+			if cont.len() >= max_len {
+				return Err(CsvImportError::Full{line, capacity: max_len});
+			}
+		}
+		cont.push((kv1, kv2));
+		lines.push(line);
+	}
+	let mut map = BidirMap::from_cont(cont);
+	map.max_len = options.max_len;
+	Ok(map)
+}
+
+/// Splits `text` into records, with the lines they start on, skipping blank lines
+fn records(text: &str, delimiter: char) -> Result<Vec<(usize, Vec<String>)>, CsvImportError> {
+	let mut records = Vec::new();
+	let mut chars = text.chars().peekable();
+	let mut line = 1;
+	while chars.peek().is_some() {
+		let start = line;
+		let mut fields = Vec::new();
+		let mut field = String::new();
+		let mut quoted = false;
+		loop {
+			match chars.next() {
+				None if quoted => return Err(CsvImportError::UnterminatedQuote{line: start}),
+				None => break,
+				Some('"') if quoted => {
+					if chars.peek() == Some(&'"') {
+						chars.next();
+						field.push('"');
+					} else {
+						quoted = false;
+					}
+				}
+				Some('"') if field.is_empty() => quoted = true,
+				Some(c) if quoted => {
+					if c == '\n' {
+						line += 1;
+					}
+					field.push(c);
+				}
+				Some(c) if c == delimiter => fields.push(mem::take(&mut field)),
+				Some('\n') => {
+					line += 1;
+					break;
+				}
+				Some('\r') if chars.peek() == Some(&'\n') => {}
+				Some(c) => field.push(c),
+			}
+		}
+
+		if !fields.is_empty() || !field.is_empty() {
+			fields.push(field);
+			records.push((start, fields));
+		}
+	}
+	Ok(records)
+}
+
+fn write_record<W: Write>(w: &mut W, delimiter: char, first: &str, second: &str) -> io::Result<()> {
+	write_field(w, delimiter, first)?;
+	write!(w, "{}", delimiter)?;
+	write_field(w, delimiter, second)?;
+	w.write_all(b"\n")
+}
+
+fn write_field<W: Write>(w: &mut W, delimiter: char, field: &str) -> io::Result<()> {
+	if field.contains([delimiter, '"', '\n', '\r']) {
+		write!(w, "\"{}\"", field.replace('"', "\"\""))
+	} else {
+		w.write_all(field.as_bytes())
+	}
+}
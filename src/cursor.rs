@@ -0,0 +1,160 @@
+//! A cursor for walking a `BidirMap` in iteration order, editing it along the way.
+
+
+use BidirMap;
+
+
+/// A cursor over the pairs of a `BidirMap`, able to remove and insert pairs where it is.
+///
+/// The cursor is either at a pair or at the "ghost" position past the last pair and before the first one, as in `LinkedList`'s cursors:
+/// moving past either end lands on the ghost, and moving on from there wraps around to the other end.
+/// The cursor of an empty map is always at the ghost.
+///
+/// Removals and insertions preserve the order of the remaining pairs.
+///
+/// Obtained from `BidirMap::cursor_mut()`.
+pub struct CursorMut<'a, Kv1: PartialEq + 'a, Kv2: PartialEq + 'a> {
+	map: &'a mut BidirMap<Kv1, Kv2>,
+	/// `None` at the ghost
+	index: Option<usize>,
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
+	/// Gets a cursor at the first pair in iteration order, or at the ghost if the map is empty.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map: BidirMap<_, _> = vec![(1, "a"), (2, "b"), (3, "c")].into_iter().collect();
+	/// let mut cursor = map.cursor_mut();
+	/// cursor.move_next();
+	/// assert_eq!(cursor.remove_current(), Some((2, "b")));
+	/// assert_eq!(cursor.current(), Some((&3, &"c")));
+	/// cursor.insert_after(4, "d");
+	///
+	/// assert_eq!(map.iter().cloned().collect::<Vec<_>>(), [(1, "a"), (3, "c"), (4, "d")]);
+	/// ```
+	pub fn cursor_mut<'s>(&'s mut self) -> CursorMut<'s, Kv1, Kv2> {
+		let index = if self.cont.is_empty() { None } else { Some(0) };
+		CursorMut{
+			map: self,
+			index,
+		}
+	}
+}
+
+impl<'a, Kv1: PartialEq, Kv2: PartialEq> CursorMut<'a, Kv1, Kv2> {
+	/// Returns the pair at the cursor, or `None` at the ghost.
+	pub fn current(&self) -> Option<(&Kv1, &Kv2)> {
+		self.index.map(|idx| {
+			let kvs = &self.map.cont[idx];
+			(&kvs.0, &kvs.1)
+		})
+	}
+
+	/// Returns the iteration order index of the pair at the cursor, or `None` at the ghost.
+	pub fn index(&self) -> Option<usize> {
+		self.index
+	}
+
+	/// Moves to the next pair, to the ghost from the last pair, or to the first pair from the ghost.
+	pub fn move_next(&mut self) {
+		let next = match self.index {
+			Some(idx) => idx + 1,
+			None => 0,
+		};
+		self.index = if next < self.map.cont.len() { Some(next) } else { None };
+	}
+
+	/// Moves to the previous pair, to the ghost from the first pair, or to the last pair from the ghost.
+	pub fn move_prev(&mut self) {
+		self.index = match self.index {
+			Some(idx) => idx.checked_sub(1),
+			None => self.map.cont.len().checked_sub(1),
+		};
+	}
+
+	/// Removes the pair at the cursor, moving to the pair after it, or to the ghost if it was the last one.
+	///
+	/// Returns `None`, not removing anything, at the ghost.
+	pub fn remove_current(&mut self) -> Option<(Kv1, Kv2)> {
+		let idx = self.index?;
+		let kvs = self.map.shift_remove_at(idx);
+		if idx == self.map.cont.len() {
+			self.index = None;
+		}
+		Some(kvs)
+	}
+
+	/// Inserts a K/V-K/V pair just after the cursor, or at the front at the ghost, without moving the cursor.
+	///
+	/// Pairs sharing a K/V with the new one are removed first, as with `BidirMap::insert()`, which pair is returned.
+	/// If that's the pair at the cursor, the new pair takes its place, and the cursor is at the new pair.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map: BidirMap<_, _> = vec![(1, "a"), (2, "b")].into_iter().collect();
+	/// let mut cursor = map.cursor_mut();
+	/// assert_eq!(cursor.insert_after(3, "b"), Some((2, "b")));
+	/// assert_eq!(cursor.insert_after(1, "z"), Some((1, "a")));
+	/// assert_eq!(cursor.current(), Some((&1, &"z")));
+	///
+	/// assert_eq!(map.iter().cloned().collect::<Vec<_>>(), [(1, "z"), (3, "b")]);
+	/// ```
+	pub fn insert_after(&mut self, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)> {
+		let slot = self.index.map_or(0, |idx| idx + 1);
+		self.insert_at(slot, kv1, kv2)
+	}
+
+	/// Inserts a K/V-K/V pair just before the cursor, or at the back at the ghost, without moving the cursor.
+	///
+	/// Pairs sharing a K/V with the new one are removed first, see `insert_after()`.
+	pub fn insert_before(&mut self, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)> {
+		let slot = self.index.unwrap_or(self.map.cont.len());
+		self.insert_at(slot, kv1, kv2)
+	}
+
+	/// Inserts the pair at `slot`, as counted before removing the pairs it collides with, keeping the cursor at its pair
+	fn insert_at(&mut self, mut slot: usize, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)> {
+		let by_first = self.map.cont.iter().position(|kvs| kvs.0 == kv1);
+		let by_second = self.map.cont.iter().position(|kvs| kvs.1 == kv2).filter(|&idx| Some(idx) != by_first);
+		if by_first.is_none() && by_second.is_none() {
+			self.map.assert_room();
+		}
+
+		let mut displaced_current = false;
+		let (lower, higher) = if by_first < by_second { (by_first, by_second) } else { (by_second, by_first) };
+		let mut removed = [None, None];
+		for (idx, removed) in higher.into_iter().chain(lower).zip(removed.iter_mut()) {
+			*removed = Some(self.map.cont.remove(idx));
+			if idx < slot {
+				slot -= 1;
+			}
+			match self.index {
+				Some(current) if idx < current => self.index = Some(current - 1),
+				Some(current) if idx == current => {
+					self.index = None;
+					displaced_current = true;
+				}
+				_ => {}
+			}
+		}
+
+		self.map.cont.insert(slot, (kv1, kv2));
+		self.map.rebuild_index();
+		self.index = match self.index {
+			_ if displaced_current => Some(slot),
+			Some(current) if slot <= current => Some(current + 1),
+			index => index,
+		};
+
+		let [higher, lower] = removed;
+		let (by_first_pair, by_second_pair) = if by_first < by_second { (lower, higher) } else { (higher, lower) };
+		by_first_pair.or(by_second_pair)
+	}
+}
@@ -0,0 +1,107 @@
+//! Parsing `BidirMap`s from, and writing them as, compact delimited pair strings like `"a=1,b=2"`.
+
+
+use core::fmt::{Display, Write};
+use core::str::FromStr;
+use alloc::string::{String, ToString};
+use {BidirMap, Column, ParseError, ParseErrorKind};
+
+
+impl BidirMap<String, String> {
+	/// Parses a map from segments separated by `pair_sep`, each a first K/V and a second K/V separated by the first `kv_sep`.
+	///
+	/// Empty segments, as from leading, trailing or doubled pair separators, are skipped. Nothing is trimmed.
+	/// A segment without a K/V separator, or repeating a K/V of an earlier segment, is reported.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::{BidirMap, Column, ParseErrorKind};
+	///
+	/// let map = BidirMap::parse_delimited("old1=new1,old2=new2,", ',', '=').unwrap();
+	/// assert_eq!(map.get_by_second("new2").map(|k| &k[..]), Some("old2"));
+	///
+	/// let err = BidirMap::parse_delimited("old1=new1,old2", ',', '=').unwrap_err();
+	/// assert_eq!((&err.segment[..], err.kind), ("old2", ParseErrorKind::MissingSeparator));
+	/// ```
+	pub fn parse_delimited(s: &str, pair_sep: char, kv_sep: char) -> Result<Self, ParseError> {
+		parse(s, pair_sep, kv_sep, |kv1| Some(kv1.to_string()), |kv2| Some(kv2.to_string()))
+	}
+}
+
+impl<Kv1: PartialEq + FromStr, Kv2: PartialEq + FromStr> BidirMap<Kv1, Kv2> {
+	/// Parses a map like `parse_delimited()`, parsing the K/Vs with `FromStr`.
+	///
+	/// K/Vs that fail to parse are reported as `ParseErrorKind::Invalid`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let map: BidirMap<char, u16> = BidirMap::parse_delimited_typed("h:80;s:443", ';', ':').unwrap();
+	/// assert_eq!(map.get_by_second(&443), Some(&'s'));
+	/// ```
+	pub fn parse_delimited_typed(s: &str, pair_sep: char, kv_sep: char) -> Result<Self, ParseError> {
+		parse(s, pair_sep, kv_sep, |kv1| kv1.parse().ok(), |kv2| kv2.parse().ok())
+	}
+}
+
+impl<Kv1: PartialEq + Display, Kv2: PartialEq + Display> BidirMap<Kv1, Kv2> {
+	/// Writes the map as segments separated by `pair_sep`, each the first K/V, `kv_sep` and the second K/V, in iteration order.
+	///
+	/// Nothing is escaped, so this only parses back via `parse_delimited()` if no first K/V contains either separator,
+	/// and no second K/V contains the pair separator.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert("a", 1);
+	/// map.insert("b", 2);
+	/// assert_eq!(map.to_delimited_string(',', '='), "a=1,b=2");
+	/// ```
+	pub fn to_delimited_string(&self, pair_sep: char, kv_sep: char) -> String {
+		let mut s = String::new();
+		for (idx, (kv1, kv2)) in self.cont.iter().enumerate() {
+			if idx != 0 {
+				s.push(pair_sep);
+			}
+			write!(s, "{}{}{}", kv1, kv_sep, kv2).unwrap();
+		}
+		s
+	}
+}
+
+
+fn parse<Kv1, Kv2, P1, P2>(s: &str, pair_sep: char, kv_sep: char, mut parse_first: P1, mut parse_second: P2) -> Result<BidirMap<Kv1, Kv2>, ParseError>
+	where Kv1: PartialEq,
+	      Kv2: PartialEq,
+	      P1 : FnMut(&str) -> Option<Kv1>,
+	      P2 : FnMut(&str) -> Option<Kv2>,
+{
+	let mut map = BidirMap::new();
+	for (index, segment) in s.split(pair_sep).filter(|segment| !segment.is_empty()).enumerate() {
+		let error = |kind| {
+			ParseError{
+				segment: segment.to_string(),
+				index,
+				kind,
+			}
+		};
+
+		let (kv1, kv2) = segment.split_once(kv_sep).ok_or_else(|| error(ParseErrorKind::MissingSeparator))?;
+		let kv1 = parse_first(kv1).ok_or_else(|| error(ParseErrorKind::Invalid(Column::First)))?;
+		let kv2 = parse_second(kv2).ok_or_else(|| error(ParseErrorKind::Invalid(Column::Second)))?;
+		if map.contains_first_key(&kv1) {
+			return Err(error(ParseErrorKind::Duplicate(Column::First)));
+		}
+		if map.contains_second_key(&kv2) {
+			return Err(error(ParseErrorKind::Duplicate(Column::Second)));
+		}
+		map.push(kv1, kv2);
+	}
+	Ok(map)
+}
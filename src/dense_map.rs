@@ -0,0 +1,313 @@
+//! A bidirectional map whose first K/Vs are small integers, looked up by indexing.
+
+
+use core::borrow::Borrow;
+use core::fmt;
+use core::iter::{Extend, FromIterator, Zip};
+use core::slice;
+use alloc::vec::Vec;
+
+
+/// Marks a slot of a `DenseBidirMap` whose key isn't in the map
+const VACANT: usize = usize::MAX;
+
+
+/// An unsigned integer K/V type, usable as an index into a `DenseBidirMap`'s slots.
+pub trait DenseKey: Copy + PartialEq {
+	/// Returns the index of the slot for this K/V.
+	fn to_index(self) -> usize;
+}
+
+macro_rules! impl_dense_key {
+	($($int:ty),*) => {$(
+		impl DenseKey for $int {
+			fn to_index(self) -> usize {
+				self as usize
+			}
+		}
+	)*};
+}
+
+impl_dense_key!(u8, u16, u32, usize);
+
+
+/// A bidirectional map from small, dense integers, storing where each key's pair is in a slot indexed by the key.
+///
+/// The pairs themselves are kept in two compact `Vec`s, one per column, like `BidirSoaMap`,
+/// so lookups by the second K/V scan only the pairs, however large the keys.
+/// Insertion and removal leave the pairs in the same order as `BidirMap` would.
+///
+/// The slots grow to one past the largest key inserted, at a `usize` each, and aren't shrunk by removals;
+/// see `key_capacity()` and `shrink_to_fit()`.
+///
+/// Performance: `O(1)` lookups by the first K/V, `O(n)` by the second.
+///
+/// # Examples
+///
+/// ```
+/// use bidir_map::DenseBidirMap;
+///
+/// let mut map = DenseBidirMap::new();
+/// map.insert(404u16, "Not Found");
+/// map.insert(200u16, "OK");
+///
+/// assert_eq!(map.get_by_first(&200), Some(&"OK"));
+/// assert_eq!(map.get_by_second(&"Not Found"), Some(&404));
+/// assert_eq!(map.key_capacity(), 405);
+/// ```
+#[derive(Clone)]
+pub struct DenseBidirMap<Kv1: DenseKey, Kv2: PartialEq> {
+	first: Vec<Kv1>,
+	second: Vec<Kv2>,
+	/// The position of each key's pair in `first` and `second`, or `VACANT`
+	slots: Vec<usize>,
+}
+
+impl<Kv1: DenseKey, Kv2: PartialEq> DenseBidirMap<Kv1, Kv2> {
+	/// Create a new empty instance of `DenseBidirMap`
+	pub fn new() -> Self {
+		DenseBidirMap::with_capacity(0)
+	}
+
+	/// Create a new empty instance of `DenseBidirMap` with the specified capacity.
+	///
+	/// It will be able to hold at least `capacity` elements without reallocating its columns;
+	/// the slots are allocated as keys are inserted.
+	pub fn with_capacity(capacity: usize) -> Self {
+		DenseBidirMap{
+			first: Vec::with_capacity(capacity),
+			second: Vec::with_capacity(capacity),
+			slots: Vec::new(),
+		}
+	}
+
+	/// Clears the map, removing all entries.
+	///
+	/// The slots are kept, for reuse.
+	pub fn clear(&mut self) {
+		self.first.clear();
+		self.second.clear();
+		for slot in &mut self.slots {
+			*slot = VACANT;
+		}
+	}
+
+	/// Inserts a K/V-K/V pair into the map, growing the slots if `kv1` is past them.
+	///
+	/// Like `BidirMap::insert()`, pairs holding `kv1` or `kv2` are removed and the one holding `kv1` is preferably returned.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::DenseBidirMap;
+	///
+	/// let mut map = DenseBidirMap::new();
+	/// assert_eq!(map.insert(1u8, 'a'), None);
+	/// assert_eq!(map.insert(1u8, 'b'), Some((1, 'a')));
+	/// assert_eq!(map.insert(2u8, 'b'), Some((1, 'b')));
+	/// assert_eq!(map.len(), 1);
+	/// ```
+	pub fn insert(&mut self, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)> {
+		let by_first = self.position_of_first(&kv1).map(|idx| self.swap_remove_at(idx));
+		let by_second = self.position_of_second(&kv2).map(|idx| self.swap_remove_at(idx));
+
+		let key = kv1.to_index();
+		if key >= self.slots.len() {
+			self.slots.resize(key + 1, VACANT);
+		}
+		self.slots[key] = self.first.len();
+		self.first.push(kv1);
+		self.second.push(kv2);
+
+		by_first.or(by_second)
+	}
+
+	/// Removes the pair corresponding to the first K/V from the map, returning it if the key was previously in the map.
+	pub fn remove_by_first(&mut self, key: &Kv1) -> Option<(Kv1, Kv2)> {
+		self.position_of_first(key).map(|idx| self.swap_remove_at(idx))
+	}
+
+	/// Removes the pair corresponding to the second K/V from the map, returning it if the key was previously in the map.
+	pub fn remove_by_second<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.position_of_second(key).map(|idx| self.swap_remove_at(idx))
+	}
+
+	/// Returns a reference to the second K/V corresponding to the first K/V.
+	pub fn get_by_first(&self, key: &Kv1) -> Option<&Kv2> {
+		self.position_of_first(key).map(|idx| &self.second[idx])
+	}
+
+	/// Returns a reference to the first K/V corresponding to the second K/V.
+	pub fn get_by_second<Q>(&self, key: &Q) -> Option<&Kv1>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.position_of_second(key).map(|idx| &self.first[idx])
+	}
+
+	/// Returns a mutable reference to the second K/V corresponding to the first K/V.
+	///
+	/// The second K/V mustn't be changed to one already in the map.
+	pub fn get_mut_by_first(&mut self, key: &Kv1) -> Option<&mut Kv2> {
+		self.position_of_first(key).map(move |idx| &mut self.second[idx])
+	}
+
+	/// Check if the map contains the first K/V
+	pub fn contains_first_key(&self, key: &Kv1) -> bool {
+		self.position_of_first(key).is_some()
+	}
+
+	/// Check if the map contains the second K/V
+	pub fn contains_second_key<Q>(&self, key: &Q) -> bool
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.position_of_second(key).is_some()
+	}
+
+	/// Gets an iterator over the entries of the map.
+	pub fn iter<'s>(&'s self) -> Iter<'s, Kv1, Kv2> {
+		Iter{
+			iter: self.first.iter().zip(self.second.iter()),
+		}
+	}
+
+	/// Returns the number of elements in the map.
+	pub fn len(&self) -> usize {
+		self.first.len()
+	}
+
+	/// Returns true if the map contains no elements.
+	pub fn is_empty(&self) -> bool {
+		self.first.is_empty()
+	}
+
+	/// Returns the number of elements the map can hold without reallocating its columns.
+	pub fn capacity(&self) -> usize {
+		self.first.capacity().min(self.second.capacity())
+	}
+
+	/// Returns the number of slots, i.e. one past the largest key that can be inserted without growing them.
+	///
+	/// Each slot takes a `usize`, whether its key is in the map or not.
+	pub fn key_capacity(&self) -> usize {
+		self.slots.len()
+	}
+
+	/// Drops the slots past the largest key in the map and shrinks all storage to fit.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::DenseBidirMap;
+	///
+	/// let mut map = DenseBidirMap::new();
+	/// map.insert(3u32, "c");
+	/// map.insert(60_000u32, "big");
+	/// assert_eq!(map.key_capacity(), 60_001);
+	///
+	/// map.remove_by_second(&"big");
+	/// map.shrink_to_fit();
+	/// assert_eq!(map.key_capacity(), 4);
+	/// ```
+	pub fn shrink_to_fit(&mut self) {
+		let used = self.first.iter().map(|kv1| kv1.to_index() + 1).max().unwrap_or(0);
+		self.slots.truncate(used);
+		self.slots.shrink_to_fit();
+		self.first.shrink_to_fit();
+		self.second.shrink_to_fit();
+	}
+
+	fn position_of_first(&self, key: &Kv1) -> Option<usize> {
+		self.slots.get(key.to_index()).cloned().filter(|&idx| idx != VACANT)
+	}
+
+	fn position_of_second<Q>(&self, key: &Q) -> Option<usize>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.second.iter().position(|k| *key == *k.borrow())
+	}
+
+	/// Same as `BidirMap`'s, so both types leave the pairs in the same order
+	fn swap_remove_at(&mut self, idx: usize) -> (Kv1, Kv2) {
+		let kvs = (self.first.swap_remove(idx), self.second.swap_remove(idx));
+		self.slots[kvs.0.to_index()] = VACANT;
+		if let Some(moved) = self.first.get(idx) {
+			self.slots[moved.to_index()] = idx;
+		}
+		kvs
+	}
+}
+
+impl<Kv1: DenseKey, Kv2: PartialEq> Default for DenseBidirMap<Kv1, Kv2> {
+	fn default() -> Self {
+		DenseBidirMap::new()
+	}
+}
+
+impl<Kv1: DenseKey + fmt::Debug, Kv2: PartialEq + fmt::Debug> fmt::Debug for DenseBidirMap<Kv1, Kv2> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_map().entries(self.iter()).finish()
+	}
+}
+
+/// Compares the pairs, in order; the slots don't matter.
+impl<Kv1: DenseKey, Kv2: PartialEq> PartialEq for DenseBidirMap<Kv1, Kv2> {
+	fn eq(&self, other: &Self) -> bool {
+		self.first == other.first && self.second == other.second
+	}
+}
+
+impl<Kv1: DenseKey + Eq, Kv2: Eq> Eq for DenseBidirMap<Kv1, Kv2> {}
+
+impl<'a, Kv1: DenseKey, Kv2: PartialEq> IntoIterator for &'a DenseBidirMap<Kv1, Kv2> {
+	type Item = (&'a Kv1, &'a Kv2);
+	type IntoIter = Iter<'a, Kv1, Kv2>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+impl<Kv1: DenseKey, Kv2: PartialEq> FromIterator<(Kv1, Kv2)> for DenseBidirMap<Kv1, Kv2> {
+	/// Unlike `BidirMap`'s, this inserts each pair as by `insert()`, since the slots can only point at one pair each.
+	fn from_iter<T: IntoIterator<Item=(Kv1, Kv2)>>(iter: T) -> Self {
+		let mut map = DenseBidirMap::new();
+		map.extend(iter);
+		map
+	}
+}
+
+impl<Kv1: DenseKey, Kv2: PartialEq> Extend<(Kv1, Kv2)> for DenseBidirMap<Kv1, Kv2> {
+	/// Unlike `BidirMap`'s, this inserts each pair as by `insert()`, since the slots can only point at one pair each.
+	fn extend<T: IntoIterator<Item=(Kv1, Kv2)>>(&mut self, iter: T) {
+		for (kv1, kv2) in iter {
+			self.insert(kv1, kv2);
+		}
+	}
+}
+
+
+/// An iterator over the K/V pairs contained in a `DenseBidirMap`.
+///
+/// See documentation of `DenseBidirMap::iter()` for more.
+pub struct Iter<'a, Kv1: 'a, Kv2: 'a> {
+	iter: Zip<slice::Iter<'a, Kv1>, slice::Iter<'a, Kv2>>,
+}
+
+impl<'a, Kv1, Kv2> Iterator for Iter<'a, Kv1, Kv2> {
+	type Item = (&'a Kv1, &'a Kv2);
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next()
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.iter.size_hint()
+	}
+}
+
+impl<'a, Kv1, Kv2> ExactSizeIterator for Iter<'a, Kv1, Kv2> {}
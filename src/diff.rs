@@ -0,0 +1,166 @@
+//! The differences between two `BidirMap`s' pairs, see `BidirMap::diff()` and `assert_bidir_eq!`.
+
+
+use core::fmt;
+use alloc::vec::Vec;
+use BidirMap;
+use storage::Storage;
+
+
+impl<Kv1: PartialEq, Kv2: PartialEq, S: Storage<Kv1, Kv2>> BidirMap<Kv1, Kv2, S> {
+	/// Compares the map's pairs with the other map's, regardless of order, sorting the differences by kind.
+	///
+	/// Pairs in only one of the maps are remappings if they share a K/V with a pair only in the other,
+	/// and are reported as only in the map otherwise.
+	///
+	/// Performance: `O(n·m)`, for `m` pairs in the other map.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let old: BidirMap<_, _> = vec![(1, 'a'), (2, 'b'), (3, 'c')].into_iter().collect();
+	/// let new: BidirMap<_, _> = vec![(2, 'b'), (1, 'x'), (4, 'd')].into_iter().collect();
+	///
+	/// let diff = old.diff(&new);
+	/// assert_eq!(diff.only_left, [&(3, 'c')]);
+	/// assert_eq!(diff.only_right, [&(4, 'd')]);
+	/// assert_eq!(diff.remapped_first, [(&1, &'a', &'x')]);
+	/// assert!(diff.remapped_second.is_empty());
+	/// assert!(old.diff(&old).is_empty());
+	/// ```
+	pub fn diff<'a, S2: Storage<Kv1, Kv2>>(&'a self, other: &'a BidirMap<Kv1, Kv2, S2>) -> Diff<'a, Kv1, Kv2> {
+		let left: Vec<_> = self.cont.iter().filter(|kvs| !other.cont.contains(kvs)).collect();
+		let right: Vec<_> = other.cont.iter().filter(|kvs| !self.cont.contains(kvs)).collect();
+		let shares = |kvs: &(Kv1, Kv2), others: &[&(Kv1, Kv2)]| others.iter().any(|other| other.0 == kvs.0 || other.1 == kvs.1);
+
+		Diff{
+			only_left: left.iter().cloned().filter(|kvs| !shares(kvs, &right)).collect(),
+			only_right: right.iter().cloned().filter(|kvs| !shares(kvs, &left)).collect(),
+			remapped_first: left.iter()
+				.filter_map(|l| right.iter().find(|r| r.0 == l.0).map(|r| (&l.0, &l.1, &r.1)))
+				.collect(),
+			remapped_second: left.iter()
+				.filter_map(|l| right.iter().find(|r| r.1 == l.1).map(|r| (&l.1, &l.0, &r.0)))
+				.collect(),
+		}
+	}
+}
+
+
+/// The differences between two maps' pairs, as returned by `BidirMap::diff()`, in the order of the maps' pairs.
+///
+/// A pair in only one of the maps sharing a K/V with a pair only in the other is reported as a remapping of that K/V,
+/// once for each K/V it shares; the other pairs in only one map are reported as such.
+///
+/// Displays as a list of the differences by kind, one per line, leaving out the kinds with none.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diff<'a, Kv1, Kv2> {
+	/// The pairs only in the left map, sharing no K/V with any pair only in the right map
+	pub only_left: Vec<&'a (Kv1, Kv2)>,
+	/// The pairs only in the right map, sharing no K/V with any pair only in the left map
+	pub only_right: Vec<&'a (Kv1, Kv2)>,
+	/// The first K/Vs in both maps, each with its second K/V in the left map and in the right map
+	pub remapped_first: Vec<(&'a Kv1, &'a Kv2, &'a Kv2)>,
+	/// The second K/Vs in both maps, each with its first K/V in the left map and in the right map
+	pub remapped_second: Vec<(&'a Kv2, &'a Kv1, &'a Kv1)>,
+}
+
+impl<'a, Kv1, Kv2> Diff<'a, Kv1, Kv2> {
+	/// Check if the maps hold the same pairs.
+	pub fn is_empty(&self) -> bool {
+		self.only_left.is_empty() && self.only_right.is_empty() && self.remapped_first.is_empty() && self.remapped_second.is_empty()
+	}
+}
+
+impl<'a, Kv1: fmt::Debug, Kv2: fmt::Debug> fmt::Display for Diff<'a, Kv1, Kv2> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if self.is_empty() {
+			return write!(f, "no differences");
+		}
+
+		if !self.only_left.is_empty() {
+			writeln!(f, "only in left:")?;
+			for kvs in &self.only_left {
+				writeln!(f, "    {:?}", kvs)?;
+			}
+		}
+		if !self.only_right.is_empty() {
+			writeln!(f, "only in right:")?;
+			for kvs in &self.only_right {
+				writeln!(f, "    {:?}", kvs)?;
+			}
+		}
+		if !self.remapped_first.is_empty() {
+			writeln!(f, "first K/Vs remapped:")?;
+			for &(kv1, left, right) in &self.remapped_first {
+				writeln!(f, "    {:?} => {:?} in left, {:?} in right", kv1, left, right)?;
+			}
+		}
+		if !self.remapped_second.is_empty() {
+			writeln!(f, "second K/Vs remapped:")?;
+			for &(kv2, left, right) in &self.remapped_second {
+				writeln!(f, "    {:?} => {:?} in left, {:?} in right", kv2, left, right)?;
+			}
+		}
+		Ok(())
+	}
+}
+
+
+/// Assert that two `BidirMap`s hold the same pairs, regardless of order as with `eq_unordered()`, or panic with their `Diff`.
+///
+/// Unlike `assert_eq!`, the panic message lists only the differences, sorted by kind, instead of both maps whole;
+/// only `PartialEq` and `Debug` are required of the K/Vs.
+/// A format string and its arguments may follow the maps, to be printed along with the differences.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use] extern crate bidir_map;
+/// use bidir_map::BidirMap;
+///
+/// # fn main() {
+/// let mut map = BidirMap::new();
+/// map.insert(1, "a");
+/// map.insert(2, "b");
+///
+/// let expected: BidirMap<_, _> = vec![(2, "b"), (1, "a")].into_iter().collect();
+/// assert_bidir_eq!(map, expected);
+/// assert_bidir_eq!(map, expected, "after {} insertions", 2);
+/// # }
+/// ```
+///
+/// A mismatch panics with:
+///
+/// ```text
+/// assertion failed: `left` and `right` BidirMaps differ
+/// only in left:
+///     (3, "c")
+/// first K/Vs remapped:
+///     1 => "a" in left, "x" in right
+/// ```
+#[macro_export]
+macro_rules! assert_bidir_eq {
+	($left:expr, $right:expr $(,)*) => {
+		match (&$left, &$right) {
+			(left, right) => {
+				let diff = $crate::BidirMap::diff(left, right);
+				if !diff.is_empty() {
+					panic!("assertion failed: `left` and `right` BidirMaps differ\n{}", diff);
+				}
+			}
+		}
+	};
+	($left:expr, $right:expr, $($arg:tt)+) => {
+		match (&$left, &$right) {
+			(left, right) => {
+				let diff = $crate::BidirMap::diff(left, right);
+				if !diff.is_empty() {
+					panic!("assertion failed: `left` and `right` BidirMaps differ: {}\n{}", format_args!($($arg)+), diff);
+				}
+			}
+		}
+	};
+}
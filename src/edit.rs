@@ -0,0 +1,138 @@
+//! Bulk edits of a `BidirMap` through a temporary hash index, see `BidirMap::edit()`.
+
+
+use core::borrow::Borrow;
+use std::hash::Hash;
+use {BidirMap, Iter};
+
+
+/// A session of `O(1)` lookups, insertions and removals on a `BidirMap`, see `BidirMap::edit()`.
+///
+/// All edits go straight to the map, so when the session is dropped, the map holds its final state;
+/// only the hash index is dropped with it, unless the map had one before the session began.
+#[derive(Debug)]
+pub struct EditSession<'a, Kv1: PartialEq + 'a, Kv2: PartialEq + 'a> {
+	map: &'a mut BidirMap<Kv1, Kv2>,
+	had_index: bool,
+}
+
+impl<Kv1: PartialEq + Hash + Eq, Kv2: PartialEq + Hash + Eq> BidirMap<Kv1, Kv2> {
+	/// Starts a session of bulk edits, building a hash index over both columns for its duration.
+	///
+	/// Lookups, insertions and removals through the session take `O(1)`, for the one-off `O(n)` cost of the index,
+	/// so a burst of edits to a large map stays linear without switching to a hash-backed map for good.
+	/// The session holds the map mutably borrowed; once it's dropped, the map is back to linear scans,
+	/// unless it already had an index built by `build_index()`, which it keeps.
+	///
+	/// Removals move the last pair into the vacated position, as `remove_by_*()` do.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map: BidirMap<_, _> = (0..1000).map(|i| (i, i.to_string())).collect();
+	/// {
+	///     let mut session = map.edit();
+	///     for i in 0..1000 {
+	///         if i % 2 == 0 {
+	///             session.remove_by_first(&i);
+	///         } else {
+	///             session.insert(i, format!("odd {}", i));
+	///         }
+	///     }
+	///     assert_eq!(session.get_by_second("odd 7"), Some(&7));
+	/// }
+	///
+	/// assert_eq!(map.len(), 500);
+	/// assert!(!map.has_index());
+	/// ```
+	pub fn edit<'s>(&'s mut self) -> EditSession<'s, Kv1, Kv2> {
+		let had_index = self.has_index();
+		if !had_index {
+			self.build_index();
+		}
+		EditSession{
+			map: self,
+			had_index,
+		}
+	}
+}
+
+impl<'a, Kv1: PartialEq + Hash + Eq, Kv2: PartialEq + Hash + Eq> EditSession<'a, Kv1, Kv2> {
+	/// Inserts a K/V-K/V pair into the map, as `BidirMap::insert()` does.
+	pub fn insert(&mut self, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)> {
+		self.map.insert(kv1, kv2)
+	}
+
+	/// Removes the pair with the first K/V from the map, returning it if the key was previously in the map.
+	pub fn remove_by_first<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv1: Borrow<Q>,
+		      Q  : Hash + PartialEq + ?Sized,
+	{
+		self.map.remove_by_first_indexed(key)
+	}
+
+	/// Removes the pair with the second K/V from the map, returning it if the key was previously in the map.
+	pub fn remove_by_second<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv2: Borrow<Q>,
+		      Q  : Hash + PartialEq + ?Sized,
+	{
+		self.map.remove_by_second_indexed(key)
+	}
+
+	/// Returns a reference to the second K/V corresponding to the first K/V.
+	pub fn get_by_first<Q>(&self, key: &Q) -> Option<&Kv2>
+		where Kv1: Borrow<Q>,
+		      Q  : Hash + PartialEq + ?Sized,
+	{
+		self.map.get_by_first_indexed(key)
+	}
+
+	/// Returns a reference to the first K/V corresponding to the second K/V.
+	pub fn get_by_second<Q>(&self, key: &Q) -> Option<&Kv1>
+		where Kv2: Borrow<Q>,
+		      Q  : Hash + PartialEq + ?Sized,
+	{
+		self.map.get_by_second_indexed(key)
+	}
+
+	/// Check if the map contains the first K/V
+	pub fn contains_first_key<Q>(&self, key: &Q) -> bool
+		where Kv1: Borrow<Q>,
+		      Q  : Hash + PartialEq + ?Sized,
+	{
+		self.map.contains_first_key_indexed(key)
+	}
+
+	/// Check if the map contains the second K/V
+	pub fn contains_second_key<Q>(&self, key: &Q) -> bool
+		where Kv2: Borrow<Q>,
+		      Q  : Hash + PartialEq + ?Sized,
+	{
+		self.map.contains_second_key_indexed(key)
+	}
+
+	/// Gets an iterator over the entries of the map.
+	pub fn iter<'s>(&'s self) -> Iter<'s, Kv1, Kv2> {
+		self.map.iter()
+	}
+
+	/// Returns the number of elements in the map.
+	pub fn len(&self) -> usize {
+		self.map.len()
+	}
+
+	/// Returns true if the map contains no elements.
+	pub fn is_empty(&self) -> bool {
+		self.map.is_empty()
+	}
+}
+
+impl<'a, Kv1: PartialEq, Kv2: PartialEq> Drop for EditSession<'a, Kv1, Kv2> {
+	fn drop(&mut self) {
+		if !self.had_index {
+			self.map.drop_index();
+		}
+	}
+}
@@ -0,0 +1,607 @@
+//! In-place manipulation of `BidirMap` pairs, looked up by either K/V.
+
+
+use core::borrow::Borrow;
+use BidirMap;
+use Column;
+
+
+impl<Kv1: PartialEq, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
+	/// Gets the entry for the specified first K/V, for in-place manipulation.
+	///
+	/// The map is scanned once up front (or the hash index consulted, if built).
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert("a", 1);
+	/// for key in &["a", "b"] {
+	///     map.entry_by_first(*key).and_modify(|v| *v += 10).or_insert(2);
+	/// }
+	/// assert_eq!(map.get_by_first(&"a"), Some(&11));
+	/// assert_eq!(map.get_by_first(&"b"), Some(&2));
+	/// ```
+	pub fn entry_by_first<'s>(&'s mut self, key: Kv1) -> EntryByFirst<'s, Kv1, Kv2> {
+		match self.position_of_first(&key) {
+			Some(idx) => EntryByFirst::Occupied(OccupiedEntryByFirst{
+				map: self,
+				idx,
+			}),
+			None => EntryByFirst::Vacant(VacantEntryByFirst{
+				map: self,
+				key,
+			}),
+		}
+	}
+
+	/// Gets the entry for the specified first K/V by reference, for in-place manipulation.
+	///
+	/// Unlike `entry_by_first()`, this only converts the key to an owned first K/V when a pair is actually inserted,
+	/// so hits on, say, `String` keys don't allocate.
+	/// The lookup always scans the map, as `Q` can't be hashed.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map: BidirMap<String, u32> = BidirMap::new();
+	/// *map.entry_by_first_ref("a").or_insert(0) += 1;
+	/// *map.entry_by_first_ref("a").or_insert(0) += 1;
+	/// assert_eq!(map.get_by_first("a"), Some(&2));
+	/// ```
+	pub fn entry_by_first_ref<'s, 'q, Q>(&'s mut self, key: &'q Q) -> EntryRefByFirst<'s, 'q, Kv1, Kv2, Q>
+		where Kv1: Borrow<Q> + From<&'q Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		match self.cont.iter().position(|kvs| *key == *kvs.0.borrow()) {
+			Some(idx) => EntryRefByFirst::Occupied(OccupiedEntryByFirst{
+				map: self,
+				idx,
+			}),
+			None => EntryRefByFirst::Vacant(VacantEntryRefByFirst{
+				map: self,
+				key,
+			}),
+		}
+	}
+
+	/// Gets the entry for the specified second K/V, for in-place manipulation.
+	///
+	/// The map is scanned once up front (or the hash index consulted, if built).
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut ids = BidirMap::new();
+	/// let mut next_id = 0;
+	/// for name in &["x", "y", "x"] {
+	///     ids.entry_by_second(*name).or_insert_with(|| { next_id += 1; next_id });
+	/// }
+	/// assert_eq!(ids.get_by_second(&"x"), Some(&1));
+	/// assert_eq!(ids.get_by_second(&"y"), Some(&2));
+	/// ```
+	pub fn entry_by_second<'s>(&'s mut self, key: Kv2) -> EntryBySecond<'s, Kv1, Kv2> {
+		match self.position_of_second(&key) {
+			Some(idx) => EntryBySecond::Occupied(OccupiedEntryBySecond{
+				map: self,
+				idx,
+			}),
+			None => EntryBySecond::Vacant(VacantEntryBySecond{
+				map: self,
+				key,
+			}),
+		}
+	}
+
+	/// Updates the second K/V of the pair with the specified first K/V with `update`, or inserts a pair with the result of `insert`.
+	///
+	/// The resulting second K/V may collide with a different pair, on either path;
+	/// that pair is removed, as with `insert()`, and returned with which path was taken.
+	/// An updated pair keeps its place in iteration order, the pairs after a displaced one moving down one position;
+	/// an inserted one goes to the end.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	/// use bidir_map::entry::Upserted;
+	///
+	/// let mut map = BidirMap::new();
+	/// assert_eq!(map.upsert_by_first("a", || 1, |v| *v += 1), Upserted::Inserted(None));
+	/// assert_eq!(map.upsert_by_first("a", || 1, |v| *v += 1), Upserted::Updated(None));
+	/// assert_eq!(map.get_by_first(&"a"), Some(&2));
+	///
+	/// map.insert("b", 3);
+	/// assert_eq!(map.upsert_by_first("a", || 1, |v| *v += 1), Upserted::Updated(Some(("b", 3))));
+	/// ```
+	pub fn upsert_by_first<I, U>(&mut self, kv1: Kv1, insert: I, update: U) -> Upserted<Kv1, Kv2>
+		where I: FnOnce() -> Kv2,
+		      U: FnOnce(&mut Kv2),
+	{
+		match self.position_of_first(&kv1) {
+			Some(idx) => {
+				self.mark_index_stale();
+				update(&mut self.cont[idx].1);
+
+				// Out of the map while looking its new second K/V up, so that can't find the pair itself
+				let kvs = self.cont.remove(idx);
+				let displaced = self.position_of_second(&kvs.1).map(|other| (other, self.shift_remove_at(other)));
+				let idx = match displaced {
+					Some((other, _)) if other < idx => idx - 1,
+					_ => idx,
+				};
+				self.cont.insert(idx, kvs);
+				self.rebuild_index();
+				Upserted::Updated(displaced.map(|(_, displaced)| displaced))
+			}
+			None => {
+				let kv2 = insert();
+				let displaced = self.position_of_second(&kv2).map(|idx| self.swap_remove_at(idx));
+				if displaced.is_none() {
+					self.assert_room();
+				}
+				self.push(kv1, kv2);
+				Upserted::Inserted(displaced)
+			}
+		}
+	}
+}
+
+
+/// A view into a single pair of a `BidirMap`, by its first K/V, which may be vacant or occupied.
+///
+/// Constructed by `BidirMap::entry_by_first()`.
+pub enum EntryByFirst<'a, Kv1: 'a + PartialEq, Kv2: 'a + PartialEq> {
+	/// There's a pair with the first K/V
+	Occupied(OccupiedEntryByFirst<'a, Kv1, Kv2>),
+	/// There's no pair with the first K/V
+	Vacant(VacantEntryByFirst<'a, Kv1, Kv2>),
+}
+
+/// A pair of a `BidirMap`, looked up by its first K/V; part of `EntryByFirst`.
+pub struct OccupiedEntryByFirst<'a, Kv1: 'a + PartialEq, Kv2: 'a + PartialEq> {
+	map: &'a mut BidirMap<Kv1, Kv2>,
+	idx: usize,
+}
+
+/// A missing pair of a `BidirMap`, with the first K/V it was looked up by; part of `EntryByFirst`.
+pub struct VacantEntryByFirst<'a, Kv1: 'a + PartialEq, Kv2: 'a + PartialEq> {
+	map: &'a mut BidirMap<Kv1, Kv2>,
+	key: Kv1,
+}
+
+impl<'a, Kv1: PartialEq, Kv2: PartialEq> EntryByFirst<'a, Kv1, Kv2> {
+	/// Returns the first K/V the entry was looked up by.
+	pub fn key(&self) -> &Kv1 {
+		match *self {
+			EntryByFirst::Occupied(ref entry) => entry.key(),
+			EntryByFirst::Vacant(ref entry) => entry.key(),
+		}
+	}
+
+	/// Returns the pair's second K/V, inserting the specified one first if vacant.
+	///
+	/// A different pair already holding the second K/V is removed, as with `BidirMap::insert()`;
+	/// use `or_insert_checked()` to refuse that instead.
+	pub fn or_insert(self, default: Kv2) -> &'a mut Kv2 {
+		match self {
+			EntryByFirst::Occupied(entry) => entry.into_mut(),
+			EntryByFirst::Vacant(entry) => entry.insert(default),
+		}
+	}
+
+	/// Returns the pair's second K/V, inserting the result of `default` first if vacant.
+	///
+	/// A different pair already holding the second K/V is removed, as with `or_insert()`.
+	pub fn or_insert_with<F: FnOnce() -> Kv2>(self, default: F) -> &'a mut Kv2 {
+		match self {
+			EntryByFirst::Occupied(entry) => entry.into_mut(),
+			EntryByFirst::Vacant(entry) => entry.insert(default()),
+		}
+	}
+
+	/// Returns the pair's second K/V, inserting the specified one first if vacant –
+	/// unless another pair already holds it, in which case it's handed back.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert(1, "a");
+	/// assert_eq!(map.entry_by_first(2).or_insert_checked("a"), Err("a"));
+	/// assert_eq!(map.entry_by_first(2).or_insert_checked("b"), Ok(&mut "b"));
+	/// assert_eq!(map.entry_by_first(1).or_insert_checked("c"), Ok(&mut "a"));
+	/// ```
+	pub fn or_insert_checked(self, default: Kv2) -> Result<&'a mut Kv2, Kv2> {
+		match self {
+			EntryByFirst::Occupied(entry) => Ok(entry.into_mut()),
+			EntryByFirst::Vacant(entry) => entry.insert_checked(default),
+		}
+	}
+
+	/// Calls `f` on the pair's second K/V if occupied.
+	pub fn and_modify<F: FnOnce(&mut Kv2)>(self, f: F) -> Self {
+		match self {
+			EntryByFirst::Occupied(mut entry) => {
+				f(entry.get_mut());
+				EntryByFirst::Occupied(entry)
+			}
+			EntryByFirst::Vacant(entry) => EntryByFirst::Vacant(entry),
+		}
+	}
+}
+
+impl<'a, Kv1: PartialEq, Kv2: PartialEq + Default> EntryByFirst<'a, Kv1, Kv2> {
+	/// Returns the pair's second K/V, inserting `Kv2::default()` first if vacant.
+	pub fn or_default(self) -> &'a mut Kv2 {
+		self.or_insert_with(Kv2::default)
+	}
+}
+
+impl<'a, Kv1: PartialEq, Kv2: PartialEq> OccupiedEntryByFirst<'a, Kv1, Kv2> {
+	/// Returns the pair's first K/V.
+	pub fn key(&self) -> &Kv1 {
+		&self.map.cont[self.idx].0
+	}
+
+	/// Returns the pair's second K/V.
+	pub fn get(&self) -> &Kv2 {
+		&self.map.cont[self.idx].1
+	}
+
+	/// Returns the pair's second K/V, mutably.
+	pub fn get_mut(&mut self) -> &mut Kv2 {
+		self.map.mark_index_stale();
+		&mut self.map.cont[self.idx].1
+	}
+
+	/// Converts the entry into a mutable reference to the pair's second K/V, bound to the map's lifetime.
+	pub fn into_mut(self) -> &'a mut Kv2 {
+		self.map.mark_index_stale();
+		&mut self.map.cont[self.idx].1
+	}
+
+	/// Replaces the pair's second K/V, returning the old one.
+	///
+	/// As with `BidirMap::insert()`, a different pair already holding the new second K/V is removed,
+	/// and the updated pair moves to the end of the map.
+	pub fn insert(&mut self, kv2: Kv2) -> Kv2 {
+		let (kv1, old) = self.map.swap_remove_at(self.idx);
+		self.idx = push_displacing(self.map, kv1, kv2, Column::First, false);
+		old
+	}
+
+	/// Removes the pair from the map, returning it.
+	pub fn remove(self) -> (Kv1, Kv2) {
+		self.map.swap_remove_at(self.idx)
+	}
+}
+
+impl<'a, Kv1: PartialEq, Kv2: PartialEq> VacantEntryByFirst<'a, Kv1, Kv2> {
+	/// Returns the first K/V the entry was looked up by.
+	pub fn key(&self) -> &Kv1 {
+		&self.key
+	}
+
+	/// Takes back the first K/V the entry was looked up by.
+	pub fn into_key(self) -> Kv1 {
+		self.key
+	}
+
+	/// Inserts the pair, returning its second K/V.
+	///
+	/// A different pair already holding the second K/V is removed, as with `BidirMap::insert()`.
+	pub fn insert(self, kv2: Kv2) -> &'a mut Kv2 {
+		let idx = push_displacing(self.map, self.key, kv2, Column::First, true);
+		self.map.mark_index_stale();
+		&mut self.map.cont[idx].1
+	}
+
+	/// Inserts the pair, returning its second K/V – unless another pair already holds it, in which case it's handed back.
+	pub fn insert_checked(self, kv2: Kv2) -> Result<&'a mut Kv2, Kv2> {
+		if self.map.position_of_second(&kv2).is_some() {
+			Err(kv2)
+		} else {
+			Ok(self.insert(kv2))
+		}
+	}
+}
+
+
+/// A view into a single pair of a `BidirMap`, by a borrowed form of its first K/V, which may be vacant or occupied.
+///
+/// Constructed by `BidirMap::entry_by_first_ref()`.
+pub enum EntryRefByFirst<'a, 'q, Kv1: 'a + PartialEq, Kv2: 'a + PartialEq, Q: 'q + ?Sized> {
+	/// There's a pair with the first K/V
+	Occupied(OccupiedEntryByFirst<'a, Kv1, Kv2>),
+	/// There's no pair with the first K/V
+	Vacant(VacantEntryRefByFirst<'a, 'q, Kv1, Kv2, Q>),
+}
+
+/// A missing pair of a `BidirMap`, with the borrowed first K/V it was looked up by; part of `EntryRefByFirst`.
+pub struct VacantEntryRefByFirst<'a, 'q, Kv1: 'a + PartialEq, Kv2: 'a + PartialEq, Q: 'q + ?Sized> {
+	map: &'a mut BidirMap<Kv1, Kv2>,
+	key: &'q Q,
+}
+
+impl<'a, 'q, Kv1, Kv2, Q> EntryRefByFirst<'a, 'q, Kv1, Kv2, Q>
+	where Kv1: PartialEq + Borrow<Q> + From<&'q Q>,
+	      Kv2: PartialEq,
+	      Q  : PartialEq + ?Sized,
+{
+	/// Returns the first K/V the entry was looked up by.
+	pub fn key(&self) -> &Q {
+		match *self {
+			EntryRefByFirst::Occupied(ref entry) => entry.key().borrow(),
+			EntryRefByFirst::Vacant(ref entry) => entry.key(),
+		}
+	}
+
+	/// Returns the pair's second K/V, inserting the specified one first if vacant, see `EntryByFirst::or_insert()`.
+	pub fn or_insert(self, default: Kv2) -> &'a mut Kv2 {
+		match self {
+			EntryRefByFirst::Occupied(entry) => entry.into_mut(),
+			EntryRefByFirst::Vacant(entry) => entry.insert(default),
+		}
+	}
+
+	/// Returns the pair's second K/V, inserting the result of `default` first if vacant, see `EntryByFirst::or_insert()`.
+	pub fn or_insert_with<F: FnOnce() -> Kv2>(self, default: F) -> &'a mut Kv2 {
+		match self {
+			EntryRefByFirst::Occupied(entry) => entry.into_mut(),
+			EntryRefByFirst::Vacant(entry) => entry.insert(default()),
+		}
+	}
+
+	/// Returns the pair's second K/V, inserting the specified one first if vacant –
+	/// unless another pair already holds it, in which case it's handed back.
+	pub fn or_insert_checked(self, default: Kv2) -> Result<&'a mut Kv2, Kv2> {
+		match self {
+			EntryRefByFirst::Occupied(entry) => Ok(entry.into_mut()),
+			EntryRefByFirst::Vacant(entry) => entry.insert_checked(default),
+		}
+	}
+
+	/// Calls `f` on the pair's second K/V if occupied.
+	pub fn and_modify<F: FnOnce(&mut Kv2)>(self, f: F) -> Self {
+		match self {
+			EntryRefByFirst::Occupied(mut entry) => {
+				f(entry.get_mut());
+				EntryRefByFirst::Occupied(entry)
+			}
+			EntryRefByFirst::Vacant(entry) => EntryRefByFirst::Vacant(entry),
+		}
+	}
+}
+
+impl<'a, 'q, Kv1, Kv2, Q> EntryRefByFirst<'a, 'q, Kv1, Kv2, Q>
+	where Kv1: PartialEq + Borrow<Q> + From<&'q Q>,
+	      Kv2: PartialEq + Default,
+	      Q  : PartialEq + ?Sized,
+{
+	/// Returns the pair's second K/V, inserting `Kv2::default()` first if vacant.
+	pub fn or_default(self) -> &'a mut Kv2 {
+		self.or_insert_with(Kv2::default)
+	}
+}
+
+impl<'a, 'q, Kv1, Kv2, Q> VacantEntryRefByFirst<'a, 'q, Kv1, Kv2, Q>
+	where Kv1: PartialEq + From<&'q Q>,
+	      Kv2: PartialEq,
+	      Q  : ?Sized,
+{
+	/// Returns the first K/V the entry was looked up by.
+	pub fn key(&self) -> &'q Q {
+		self.key
+	}
+
+	/// Inserts the pair, converting the key to an owned first K/V, and returns its second K/V.
+	///
+	/// A different pair already holding the second K/V is removed, as with `BidirMap::insert()`.
+	pub fn insert(self, kv2: Kv2) -> &'a mut Kv2 {
+		VacantEntryByFirst{
+			map: self.map,
+			key: Kv1::from(self.key),
+		}.insert(kv2)
+	}
+
+	/// Inserts the pair, returning its second K/V – unless another pair already holds it, in which case it's handed back
+	/// without the key having been converted.
+	pub fn insert_checked(self, kv2: Kv2) -> Result<&'a mut Kv2, Kv2> {
+		if self.map.position_of_second(&kv2).is_some() {
+			Err(kv2)
+		} else {
+			Ok(self.insert(kv2))
+		}
+	}
+}
+
+
+/// A view into a single pair of a `BidirMap`, by its second K/V, which may be vacant or occupied.
+///
+/// Constructed by `BidirMap::entry_by_second()`.
+pub enum EntryBySecond<'a, Kv1: 'a + PartialEq, Kv2: 'a + PartialEq> {
+	/// There's a pair with the second K/V
+	Occupied(OccupiedEntryBySecond<'a, Kv1, Kv2>),
+	/// There's no pair with the second K/V
+	Vacant(VacantEntryBySecond<'a, Kv1, Kv2>),
+}
+
+/// A pair of a `BidirMap`, looked up by its second K/V; part of `EntryBySecond`.
+pub struct OccupiedEntryBySecond<'a, Kv1: 'a + PartialEq, Kv2: 'a + PartialEq> {
+	map: &'a mut BidirMap<Kv1, Kv2>,
+	idx: usize,
+}
+
+/// A missing pair of a `BidirMap`, with the second K/V it was looked up by; part of `EntryBySecond`.
+pub struct VacantEntryBySecond<'a, Kv1: 'a + PartialEq, Kv2: 'a + PartialEq> {
+	map: &'a mut BidirMap<Kv1, Kv2>,
+	key: Kv2,
+}
+
+impl<'a, Kv1: PartialEq, Kv2: PartialEq> EntryBySecond<'a, Kv1, Kv2> {
+	/// Returns the second K/V the entry was looked up by.
+	pub fn key(&self) -> &Kv2 {
+		match *self {
+			EntryBySecond::Occupied(ref entry) => entry.key(),
+			EntryBySecond::Vacant(ref entry) => entry.key(),
+		}
+	}
+
+	/// Returns the pair's first K/V, inserting the specified one first if vacant.
+	///
+	/// A different pair already holding the first K/V is removed, as with `BidirMap::insert()`;
+	/// use `or_insert_checked()` to refuse that instead.
+	pub fn or_insert(self, default: Kv1) -> &'a mut Kv1 {
+		match self {
+			EntryBySecond::Occupied(entry) => entry.into_mut(),
+			EntryBySecond::Vacant(entry) => entry.insert(default),
+		}
+	}
+
+	/// Returns the pair's first K/V, inserting the result of `default` first if vacant.
+	///
+	/// A different pair already holding the first K/V is removed, as with `or_insert()`.
+	pub fn or_insert_with<F: FnOnce() -> Kv1>(self, default: F) -> &'a mut Kv1 {
+		match self {
+			EntryBySecond::Occupied(entry) => entry.into_mut(),
+			EntryBySecond::Vacant(entry) => entry.insert(default()),
+		}
+	}
+
+	/// Returns the pair's first K/V, inserting the specified one first if vacant –
+	/// unless another pair already holds it, in which case it's handed back.
+	pub fn or_insert_checked(self, default: Kv1) -> Result<&'a mut Kv1, Kv1> {
+		match self {
+			EntryBySecond::Occupied(entry) => Ok(entry.into_mut()),
+			EntryBySecond::Vacant(entry) => entry.insert_checked(default),
+		}
+	}
+
+	/// Calls `f` on the pair's first K/V if occupied.
+	pub fn and_modify<F: FnOnce(&mut Kv1)>(self, f: F) -> Self {
+		match self {
+			EntryBySecond::Occupied(mut entry) => {
+				f(entry.get_mut());
+				EntryBySecond::Occupied(entry)
+			}
+			EntryBySecond::Vacant(entry) => EntryBySecond::Vacant(entry),
+		}
+	}
+}
+
+impl<'a, Kv1: PartialEq + Default, Kv2: PartialEq> EntryBySecond<'a, Kv1, Kv2> {
+	/// Returns the pair's first K/V, inserting `Kv1::default()` first if vacant.
+	pub fn or_default(self) -> &'a mut Kv1 {
+		self.or_insert_with(Kv1::default)
+	}
+}
+
+impl<'a, Kv1: PartialEq, Kv2: PartialEq> OccupiedEntryBySecond<'a, Kv1, Kv2> {
+	/// Returns the pair's second K/V.
+	pub fn key(&self) -> &Kv2 {
+		&self.map.cont[self.idx].1
+	}
+
+	/// Returns the pair's first K/V.
+	pub fn get(&self) -> &Kv1 {
+		&self.map.cont[self.idx].0
+	}
+
+	/// Returns the pair's first K/V, mutably.
+	pub fn get_mut(&mut self) -> &mut Kv1 {
+		self.map.mark_index_stale();
+		&mut self.map.cont[self.idx].0
+	}
+
+	/// Converts the entry into a mutable reference to the pair's first K/V, bound to the map's lifetime.
+	pub fn into_mut(self) -> &'a mut Kv1 {
+		self.map.mark_index_stale();
+		&mut self.map.cont[self.idx].0
+	}
+
+	/// Replaces the pair's first K/V, returning the old one.
+	///
+	/// As with `BidirMap::insert()`, a different pair already holding the new first K/V is removed,
+	/// and the updated pair moves to the end of the map.
+	pub fn insert(&mut self, kv1: Kv1) -> Kv1 {
+		let (old, kv2) = self.map.swap_remove_at(self.idx);
+		self.idx = push_displacing(self.map, kv1, kv2, Column::Second, false);
+		old
+	}
+
+	/// Removes the pair from the map, returning it.
+	pub fn remove(self) -> (Kv1, Kv2) {
+		self.map.swap_remove_at(self.idx)
+	}
+}
+
+impl<'a, Kv1: PartialEq, Kv2: PartialEq> VacantEntryBySecond<'a, Kv1, Kv2> {
+	/// Returns the second K/V the entry was looked up by.
+	pub fn key(&self) -> &Kv2 {
+		&self.key
+	}
+
+	/// Takes back the second K/V the entry was looked up by.
+	pub fn into_key(self) -> Kv2 {
+		self.key
+	}
+
+	/// Inserts the pair, returning its first K/V.
+	///
+	/// A different pair already holding the first K/V is removed, as with `BidirMap::insert()`.
+	pub fn insert(self, kv1: Kv1) -> &'a mut Kv1 {
+		let idx = push_displacing(self.map, kv1, self.key, Column::Second, true);
+		self.map.mark_index_stale();
+		&mut self.map.cont[idx].0
+	}
+
+	/// Inserts the pair, returning its first K/V – unless another pair already holds it, in which case it's handed back.
+	pub fn insert_checked(self, kv1: Kv1) -> Result<&'a mut Kv1, Kv1> {
+		if self.map.position_of_first(&kv1).is_some() {
+			Err(kv1)
+		} else {
+			Ok(self.insert(kv1))
+		}
+	}
+}
+
+
+/// Which path `BidirMap::upsert_by_first()` took, with the pair removed for holding the resulting second K/V, if any.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub enum Upserted<Kv1, Kv2> {
+	/// There was no pair with the first K/V, so one was inserted
+	Inserted(Option<(Kv1, Kv2)>),
+	/// The existing pair's second K/V was updated
+	Updated(Option<(Kv1, Kv2)>),
+}
+
+
+/// Pushes a pair whose K/V in the `known_free` column is in no pair yet, first removing the pair holding its other K/V, if any.
+///
+/// A pair that's `growing` the map, rather than replacing one already taken out of it, needs room in it unless it displaces another.
+///
+/// Returns the index of the new pair.
+fn push_displacing<Kv1: PartialEq, Kv2: PartialEq>(map: &mut BidirMap<Kv1, Kv2>, kv1: Kv1, kv2: Kv2, known_free: Column, growing: bool) -> usize {
+	let other = match known_free {
+		Column::First => map.position_of_second(&kv2),
+		Column::Second => map.position_of_first(&kv1),
+	};
+	match other {
+		Some(idx) => {
+			map.swap_remove_at(idx);
+		}
+		None if growing => map.assert_room(),
+		None => {}
+	}
+	map.push(kv1, kv2);
+	map.cont.len() - 1
+}
@@ -0,0 +1,322 @@
+//! A bidirectional map keyed by the variants of a field-less enum, stored densely.
+
+
+use core::borrow::Borrow;
+use core::fmt;
+use core::iter::Enumerate;
+use core::slice;
+
+
+/// A field-less enum, or any other type with a small, fixed set of values, numbered from 0.
+///
+/// Derivable with `#[derive(EnumLike)]` under the `derive` feature, numbering the variants in declaration order.
+///
+/// # Examples
+///
+/// ```
+/// use bidir_map::EnumLike;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Suit {
+///     Clubs,
+///     Hearts,
+/// }
+///
+/// impl EnumLike for Suit {
+///     const VARIANTS: usize = 2;
+///     type Array<T> = [T; 2];
+///
+///     fn to_index(&self) -> usize {
+///         match *self {
+///             Suit::Clubs => 0,
+///             Suit::Hearts => 1,
+///         }
+///     }
+///
+///     fn from_index(index: usize) -> Option<Self> {
+///         match index {
+///             0 => Some(Suit::Clubs),
+///             1 => Some(Suit::Hearts),
+///             _ => None,
+///         }
+///     }
+///
+///     fn array_from_fn<T, F: FnMut(usize) -> T>(f: F) -> [T; 2] {
+///         core::array::from_fn(f)
+///     }
+/// }
+///
+/// assert_eq!(Suit::from_index(Suit::Hearts.to_index()), Some(Suit::Hearts));
+/// ```
+pub trait EnumLike: Sized {
+	/// The number of variants
+	const VARIANTS: usize;
+
+	/// An array holding a `T` per variant, i.e. `[T; VARIANTS]`
+	type Array<T>: AsRef<[T]> + AsMut<[T]>;
+
+	/// Returns the index of this variant, less than `VARIANTS`.
+	fn to_index(&self) -> usize;
+
+	/// Returns the variant with the specified index, or `None` if it's at least `VARIANTS`.
+	fn from_index(index: usize) -> Option<Self>;
+
+	/// Creates an array whose element at each index is `f(index)`.
+	fn array_from_fn<T, F: FnMut(usize) -> T>(f: F) -> Self::Array<T>;
+}
+
+impl EnumLike for bool {
+	const VARIANTS: usize = 2;
+	type Array<T> = [T; 2];
+
+	fn to_index(&self) -> usize {
+		*self as usize
+	}
+
+	fn from_index(index: usize) -> Option<Self> {
+		match index {
+			0 => Some(false),
+			1 => Some(true),
+			_ => None,
+		}
+	}
+
+	fn array_from_fn<T, F: FnMut(usize) -> T>(f: F) -> [T; 2] {
+		core::array::from_fn(f)
+	}
+}
+
+
+/// A bidirectional map between the variants of an `EnumLike` type and values, holding a slot per variant inline.
+///
+/// Lookups by variant index the slots directly; lookups by value scan them.
+/// Iteration is in order of the variants' indices.
+/// As the variants aren't stored, they're handed out by value rather than as references.
+///
+/// Performance: `O(1)` lookups by the first K/V, `O(n)` by the second.
+///
+/// # Examples
+///
+/// ```
+/// use bidir_map::{BidirEnumMap, EnumLike};
+///
+/// #[derive(Clone, Copy, Debug, PartialEq)]
+/// enum Light {
+///     Red,
+///     Amber,
+///     Green,
+/// }
+/// # impl EnumLike for Light {
+/// #     const VARIANTS: usize = 3;
+/// #     type Array<T> = [T; 3];
+/// #     fn to_index(&self) -> usize { *self as usize }
+/// #     fn from_index(index: usize) -> Option<Self> { [Light::Red, Light::Amber, Light::Green].get(index).cloned() }
+/// #     fn array_from_fn<T, F: FnMut(usize) -> T>(f: F) -> [T; 3] { core::array::from_fn(f) }
+/// # }
+///
+/// let mut map = BidirEnumMap::new();
+/// map.insert(Light::Green, "go");
+/// map.insert(Light::Red, "stop");
+///
+/// assert_eq!(map.get_by_first(&Light::Red), Some(&"stop"));
+/// assert_eq!(map.get_by_second(&"go"), Some(Light::Green));
+/// assert_eq!(map.get_by_first(&Light::Amber), None);
+/// assert_eq!(map.iter().collect::<Vec<_>>(), [(Light::Red, &"stop"), (Light::Green, &"go")]);
+/// ```
+pub struct BidirEnumMap<E: EnumLike, V: PartialEq> {
+	slots: E::Array<Option<V>>,
+	len: usize,
+}
+
+impl<E: EnumLike, V: PartialEq> BidirEnumMap<E, V> {
+	/// Create a new empty instance of `BidirEnumMap`
+	pub fn new() -> Self {
+		BidirEnumMap{
+			slots: E::array_from_fn(|_| None),
+			len: 0,
+		}
+	}
+
+	/// Clears the map, removing all entries.
+	pub fn clear(&mut self) {
+		for slot in self.slots.as_mut() {
+			*slot = None;
+		}
+		self.len = 0;
+	}
+
+	/// Inserts a variant-K/V pair into the map.
+	///
+	/// Like `BidirMap::insert()`, pairs holding `kv1` or `kv2` are removed and the one holding `kv1` is preferably returned.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirEnumMap;
+	///
+	/// let mut map = BidirEnumMap::new();
+	/// assert_eq!(map.insert(false, 'f'), None);
+	/// assert_eq!(map.insert(false, 'F'), Some((false, 'f')));
+	/// assert_eq!(map.insert(true, 'F'), Some((false, 'F')));
+	/// assert_eq!(map.len(), 1);
+	/// ```
+	pub fn insert(&mut self, kv1: E, kv2: V) -> Option<(E, V)> {
+		let idx = kv1.to_index();
+		let by_second = self.index_of_second(&kv2).filter(|&i| i != idx).map(|i| self.take(i));
+
+		let by_first = self.slots.as_mut()[idx].replace(kv2).map(|kv2| (kv1, kv2));
+		if by_first.is_none() {
+			self.len += 1;
+		}
+		by_first.or(by_second)
+	}
+
+	/// Removes the pair with the variant and returns it.
+	pub fn remove_by_first(&mut self, key: &E) -> Option<(E, V)> {
+		let idx = key.to_index();
+		if self.slots.as_ref()[idx].is_some() { Some(self.take(idx)) } else { None }
+	}
+
+	/// Removes the pair with the second K/V and returns it.
+	pub fn remove_by_second<Q>(&mut self, key: &Q) -> Option<(E, V)>
+		where V: Borrow<Q>,
+		      Q: PartialEq + ?Sized,
+	{
+		self.index_of_second(key).map(|idx| self.take(idx))
+	}
+
+	/// Returns a reference to the second K/V corresponding to the variant.
+	pub fn get_by_first(&self, key: &E) -> Option<&V> {
+		self.slots.as_ref()[key.to_index()].as_ref()
+	}
+
+	/// Returns a mutable reference to the second K/V corresponding to the variant.
+	///
+	/// The second K/V mustn't be changed to one already in the map.
+	pub fn get_mut_by_first(&mut self, key: &E) -> Option<&mut V> {
+		self.slots.as_mut()[key.to_index()].as_mut()
+	}
+
+	/// Returns the variant corresponding to the second K/V.
+	pub fn get_by_second<Q>(&self, key: &Q) -> Option<E>
+		where V: Borrow<Q>,
+		      Q: PartialEq + ?Sized,
+	{
+		self.index_of_second(key).map(Self::variant)
+	}
+
+	/// Check if the map contains the variant
+	pub fn contains_first_key(&self, key: &E) -> bool {
+		self.get_by_first(key).is_some()
+	}
+
+	/// Check if the map contains the second K/V
+	pub fn contains_second_key<Q>(&self, key: &Q) -> bool
+		where V: Borrow<Q>,
+		      Q: PartialEq + ?Sized,
+	{
+		self.index_of_second(key).is_some()
+	}
+
+	/// Gets an iterator over the entries of the map, in order of the variants.
+	pub fn iter<'s>(&'s self) -> Iter<'s, E, V> {
+		Iter{
+			iter: self.slots.as_ref().iter().enumerate(),
+			len: self.len,
+			variant: Self::variant,
+		}
+	}
+
+	/// Returns the number of elements in the map.
+	pub fn len(&self) -> usize {
+		self.len
+	}
+
+	/// Returns true if the map contains no elements.
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	fn index_of_second<Q>(&self, key: &Q) -> Option<usize>
+		where V: Borrow<Q>,
+		      Q: PartialEq + ?Sized,
+	{
+		self.slots.as_ref().iter().position(|slot| slot.as_ref().is_some_and(|kv2| *key == *kv2.borrow()))
+	}
+
+	fn take(&mut self, idx: usize) -> (E, V) {
+		self.len -= 1;
+		(Self::variant(idx), self.slots.as_mut()[idx].take().unwrap())
+	}
+
+	fn variant(idx: usize) -> E {
+		E::from_index(idx).expect("EnumLike::from_index() rejected an index below VARIANTS")
+	}
+}
+
+impl<E: EnumLike, V: PartialEq> Default for BidirEnumMap<E, V> {
+	fn default() -> Self {
+		BidirEnumMap::new()
+	}
+}
+
+impl<E: EnumLike, V: PartialEq + Clone> Clone for BidirEnumMap<E, V> {
+	fn clone(&self) -> Self {
+		let slots = self.slots.as_ref();
+		BidirEnumMap{
+			slots: E::array_from_fn(|idx| slots[idx].clone()),
+			len: self.len,
+		}
+	}
+}
+
+impl<E: EnumLike + fmt::Debug, V: PartialEq + fmt::Debug> fmt::Debug for BidirEnumMap<E, V> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_map().entries(self.iter()).finish()
+	}
+}
+
+impl<E: EnumLike, V: PartialEq> PartialEq for BidirEnumMap<E, V> {
+	fn eq(&self, other: &Self) -> bool {
+		self.slots.as_ref() == other.slots.as_ref()
+	}
+}
+
+impl<E: EnumLike, V: Eq> Eq for BidirEnumMap<E, V> {}
+
+impl<'a, E: EnumLike, V: PartialEq> IntoIterator for &'a BidirEnumMap<E, V> {
+	type Item = (E, &'a V);
+	type IntoIter = Iter<'a, E, V>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+
+/// An iterator over the variant-K/V pairs contained in a `BidirEnumMap`.
+///
+/// See documentation of `BidirEnumMap::iter()` for more.
+pub struct Iter<'a, E, V: 'a> {
+	iter: Enumerate<slice::Iter<'a, Option<V>>>,
+	len: usize,
+	variant: fn(usize) -> E,
+}
+
+impl<'a, E, V> Iterator for Iter<'a, E, V> {
+	type Item = (E, &'a V);
+	fn next(&mut self) -> Option<Self::Item> {
+		let variant = self.variant;
+		let kvs = self.iter.by_ref().find_map(|(idx, slot)| slot.as_ref().map(|kv2| (variant(idx), kv2)));
+		if kvs.is_some() {
+			self.len -= 1;
+		}
+		kvs
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(self.len, Some(self.len))
+	}
+}
+
+impl<'a, E, V> ExactSizeIterator for Iter<'a, E, V> {}
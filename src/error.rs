@@ -0,0 +1,395 @@
+//! Errors returned by the fallible methods of the maps in this crate.
+//!
+//! All of them implement `Debug` and `Display`, and, with the `std` feature, `std::error::Error`.
+
+
+use core::fmt;
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "csv")]
+use std::io;
+
+
+/// One of the two columns of a bidirectional map.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum Column {
+	/// The first K/Vs
+	First,
+	/// The second K/Vs
+	Second,
+}
+
+/// A K/V occurring in more than one pair, where it must be unique.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct DuplicateKeyError {
+	/// The column with the repeated K/V
+	pub column: Column,
+	/// The index of the offending pair in the input, if known
+	pub index: Option<usize>,
+}
+
+/// Two columns that were to be zipped into pairs, but weren't of the same length.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct LengthMismatchError {
+	/// The number of first K/Vs
+	pub first: usize,
+	/// The number of second K/Vs
+	pub second: usize,
+}
+
+/// A pair that didn't fit into a map of bounded size.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct CapacityError {
+	/// The most pairs the map holds
+	pub capacity: usize,
+}
+
+/// Two pairs of a `BidirMap` sharing a K/V, as reported by `BidirMap::validate()`.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct InvariantError {
+	/// The column with the repeated K/V
+	pub column: Column,
+	/// The indices of the two pairs in iteration order, the smaller one first
+	pub indices: (usize, usize),
+}
+
+/// A rename table refused by `BidirMap::remap_{first,second}_keys()`, because two pairs would then share a K/V.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct RemapError {
+	/// The column being renamed
+	pub column: Column,
+	/// The indices of the two pairs in iteration order, the smaller one first
+	pub indices: (usize, usize),
+}
+
+/// A pair refused by `BidirMap::insert_{before,after}_{first,second}()` because the anchor wasn't in the map.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct InsertError<Kv1, Kv2> {
+	/// The pair that was to be inserted, handed back
+	pub pair: (Kv1, Kv2),
+}
+
+/// A pair refused by `BidirMap::try_insert()` because its K/V in `column` is already in the map,
+/// which the map's duplicate policy forbids with `OnDuplicate::Error`.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct DuplicateInsertError<Kv1, Kv2> {
+	/// The column with the repeated K/V
+	pub column: Column,
+	/// The pair that was to be inserted, handed back
+	pub pair: (Kv1, Kv2),
+}
+
+/// A batch refused by `BidirMap::extend_atomic()`, because one of its pairs repeats a K/V of the map or of an earlier pair of the batch.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct BatchInsertError<Kv1, Kv2> {
+	/// The index in the batch of the first pair repeating a K/V
+	pub index: usize,
+	/// The column with the repeated K/V
+	pub column: Column,
+	/// The index in the batch of the earlier pair with the K/V, or `None` if the K/V is in the map
+	pub earlier: Option<usize>,
+	/// The pair repeating the K/V, handed back
+	pub pair: (Kv1, Kv2),
+}
+
+/// A pair rejected by the validator of a `ValidatedBidirMap`.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct ValidationError {
+	/// Why the validator rejected the pair
+	pub message: Cow<'static, str>,
+}
+
+/// Why `BidirSortedMap::from_sorted_vec()` rejected its input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FromSortedVecError {
+	/// The pair at `index` in the input has a first K/V smaller than its predecessor's
+	Unsorted{
+		index: usize,
+	},
+	/// A pair in the input repeats an earlier pair's K/V; the error's `index` is always known
+	Duplicate(DuplicateKeyError),
+}
+
+/// Why `BidirMapBuilder::build_from_iter()` or `BidirMapBuilder::build()` failed.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum BuildError {
+	/// Both `sorted_by_first()` and `sorted_by_second()` were requested
+	ConflictingOrder,
+	/// A pair in the input repeats an earlier pair's K/V, with `OnDuplicate::Error`; the error's `index` is always known
+	Duplicate(DuplicateKeyError),
+}
+
+/// An edit refused by a `Transaction`, which leaves the map as it was before the edit.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub enum TransactionError<Kv1, Kv2> {
+	/// The pair to insert shares its K/V in `column` with a pair in the map; the pair is handed back
+	Conflict{
+		column: Column,
+		pair: (Kv1, Kv2),
+	},
+	/// No pair has the K/V in `column` to remove by
+	NotFound{
+		column: Column,
+	},
+}
+
+/// A segment of a delimited pair string that `BidirMap::parse_delimited()` rejected.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct ParseError {
+	/// The offending segment, between pair separators
+	pub segment: String,
+	/// The index of the segment among the non-empty ones
+	pub index: usize,
+	/// What's wrong with the segment
+	pub kind: ParseErrorKind,
+}
+
+/// What's wrong with a segment reported by a `ParseError`.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum ParseErrorKind {
+	/// The segment has no K/V separator
+	MissingSeparator,
+	/// The K/V in the column couldn't be parsed into its type
+	Invalid(Column),
+	/// The K/V in the column repeats one of an earlier segment
+	Duplicate(Column),
+}
+
+/// Why `BidirMap::from_csv_reader()` rejected its input.
+///
+/// Line numbers count from 1, and are those of the line a record starts on, header included.
+#[cfg(feature = "csv")]
+#[derive(Debug)]
+pub enum CsvImportError {
+	/// Reading failed, or the input wasn't UTF-8
+	Io(io::Error),
+	/// A quoted field wasn't closed before the end of the input
+	UnterminatedQuote{
+		line: usize,
+	},
+	/// A record didn't have exactly two fields
+	FieldCount{
+		line: usize,
+		fields: usize,
+	},
+	/// A field couldn't be parsed into its K/V type
+	Parse{
+		line: usize,
+		column: Column,
+	},
+	/// A record repeated a K/V of the record on `earlier_line`, with `OnDuplicate::Error`
+	Duplicate{
+		line: usize,
+		column: Column,
+		earlier_line: usize,
+	},
+	/// A record would have grown the map past `CsvOptions::max_len`, of `capacity` pairs
+	Full{
+		line: usize,
+		capacity: usize,
+	},
+}
+
+
+impl Column {
+	fn name(self) -> &'static str {
+		match self {
+			Column::First => "first",
+			Column::Second => "second",
+		}
+	}
+}
+
+impl fmt::Display for DuplicateKeyError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self.index {
+			Some(index) => write!(f, "pair {} repeats an earlier pair's {} K/V", index, self.column.name()),
+			None => write!(f, "a {} K/V is repeated", self.column.name()),
+		}
+	}
+}
+
+impl fmt::Display for LengthMismatchError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "got {} first K/Vs but {} second K/Vs", self.first, self.second)
+	}
+}
+
+impl fmt::Display for CapacityError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "map is full at {} pairs", self.capacity)
+	}
+}
+
+impl fmt::Display for InvariantError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "pairs {} and {} share their {} K/V", self.indices.0, self.indices.1, self.column.name())
+	}
+}
+
+impl fmt::Display for RemapError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "renaming would make pairs {} and {} share their {} K/V", self.indices.0, self.indices.1, self.column.name())
+	}
+}
+
+impl<Kv1, Kv2> fmt::Display for InsertError<Kv1, Kv2> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("anchor not in the map")
+	}
+}
+
+impl<Kv1, Kv2> fmt::Display for DuplicateInsertError<Kv1, Kv2> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} K/V already in the map", self.column.name())
+	}
+}
+
+impl<Kv1, Kv2> fmt::Display for BatchInsertError<Kv1, Kv2> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self.earlier {
+			Some(earlier) => write!(f, "pair {} of the batch repeats pair {}'s {} K/V", self.index, earlier, self.column.name()),
+			None => write!(f, "pair {} of the batch repeats a {} K/V of the map", self.index, self.column.name()),
+		}
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl ValidationError {
+	/// Create an error with the message, a `&'static str` or a `String`.
+	pub fn new<M: Into<Cow<'static, str>>>(message: M) -> ValidationError {
+		ValidationError{
+			message: message.into(),
+		}
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for ValidationError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "pair rejected: {}", self.message)
+	}
+}
+
+impl fmt::Display for FromSortedVecError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			FromSortedVecError::Unsorted{index} => write!(f, "pair {} is out of order by the first K/V", index),
+			FromSortedVecError::Duplicate(_) => f.write_str("pairs aren't unique"),
+		}
+	}
+}
+
+impl fmt::Display for BuildError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			BuildError::ConflictingOrder => f.write_str("can't sort by both the first and the second K/Vs"),
+			BuildError::Duplicate(ref err) => write!(f, "couldn't build map: {}", err),
+		}
+	}
+}
+
+impl<Kv1, Kv2> fmt::Display for TransactionError<Kv1, Kv2> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			TransactionError::Conflict{column, ..} => write!(f, "{} K/V already in the map", column.name()),
+			TransactionError::NotFound{column} => write!(f, "{} K/V not in the map", column.name()),
+		}
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for ParseError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self.kind {
+			ParseErrorKind::MissingSeparator => write!(f, "segment {} ({:?}) has no K/V separator", self.index, self.segment),
+			ParseErrorKind::Invalid(column) => write!(f, "segment {} ({:?}) has an invalid {} K/V", self.index, self.segment, column.name()),
+			ParseErrorKind::Duplicate(column) => write!(f, "segment {} ({:?}) repeats an earlier {} K/V", self.index, self.segment, column.name()),
+		}
+	}
+}
+
+#[cfg(feature = "csv")]
+impl fmt::Display for CsvImportError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			CsvImportError::Io(ref err) => write!(f, "couldn't read CSV: {}", err),
+			CsvImportError::UnterminatedQuote{line} => write!(f, "line {}: unterminated quoted field", line),
+			CsvImportError::FieldCount{line, fields} => write!(f, "line {}: expected 2 fields, got {}", line, fields),
+			CsvImportError::Parse{line, column} => write!(f, "line {}: invalid {} K/V", line, column.name()),
+			CsvImportError::Duplicate{line, column, earlier_line} => {
+				write!(f, "line {}: {} K/V repeated from line {}", line, column.name(), earlier_line)
+			}
+			CsvImportError::Full{line, capacity} => write!(f, "line {}: map is full at {} pairs", line, capacity),
+		}
+	}
+}
+
+#[cfg(feature = "csv")]
+impl From<io::Error> for CsvImportError {
+	fn from(err: io::Error) -> Self {
+		CsvImportError::Io(err)
+	}
+}
+
+
+#[cfg(feature = "std")]
+mod std_impls {
+	use std::error::Error;
+	use std::fmt;
+	use super::*;
+
+	impl Error for DuplicateKeyError {}
+
+	impl Error for LengthMismatchError {}
+
+	impl Error for CapacityError {}
+
+	impl Error for InvariantError {}
+
+	impl Error for RemapError {}
+
+	impl<Kv1: fmt::Debug, Kv2: fmt::Debug> Error for InsertError<Kv1, Kv2> {}
+
+	impl<Kv1: fmt::Debug, Kv2: fmt::Debug> Error for DuplicateInsertError<Kv1, Kv2> {}
+
+	impl<Kv1: fmt::Debug, Kv2: fmt::Debug> Error for BatchInsertError<Kv1, Kv2> {}
+
+	impl Error for ValidationError {}
+
+	impl Error for FromSortedVecError {
+		fn source(&self) -> Option<&(dyn Error + 'static)> {
+			match *self {
+				FromSortedVecError::Unsorted{..} => None,
+				FromSortedVecError::Duplicate(ref err) => Some(err),
+			}
+		}
+	}
+
+	impl Error for BuildError {
+		fn source(&self) -> Option<&(dyn Error + 'static)> {
+			match *self {
+				BuildError::ConflictingOrder => None,
+				BuildError::Duplicate(ref err) => Some(err),
+			}
+		}
+	}
+
+	impl<Kv1: fmt::Debug, Kv2: fmt::Debug> Error for TransactionError<Kv1, Kv2> {}
+
+	impl Error for ParseError {}
+
+	#[cfg(feature = "csv")]
+	impl Error for CsvImportError {
+		fn source(&self) -> Option<&(dyn Error + 'static)> {
+			match *self {
+				CsvImportError::Io(ref err) => Some(err),
+				_ => None,
+			}
+		}
+	}
+}
@@ -0,0 +1,209 @@
+//! A `BidirMap` wrapper falling back to functions for K/Vs not in the map, see `BidirMap::with_fallbacks()`.
+
+
+use core::borrow::Borrow;
+use core::iter::Extend;
+use {BidirMap, Iter};
+
+
+/// The outcome of a `FallbackBidirMap::resolve_by_*()` lookup.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum Resolved<'a, T: 'a> {
+	/// The K/V was in the map, paired with this one
+	Mapped(&'a T),
+	/// The K/V wasn't in the map, and the fallback produced this one
+	FellBack(T),
+	/// The K/V wasn't in the map, and the fallback produced none
+	Unknown,
+}
+
+impl<'a, T> Resolved<'a, T> {
+	/// Returns the resolved K/V, whether mapped or fallen back to, cloning a mapped one.
+	pub fn into_owned(self) -> Option<T>
+		where T: Clone,
+	{
+		match self {
+			Resolved::Mapped(kv) => Some(kv.clone()),
+			Resolved::FellBack(kv) => Some(kv),
+			Resolved::Unknown => None,
+		}
+	}
+
+	/// Returns a reference to the resolved K/V, whether mapped or fallen back to.
+	pub fn as_ref(&self) -> Option<&T> {
+		match *self {
+			Resolved::Mapped(kv) => Some(kv),
+			Resolved::FellBack(ref kv) => Some(kv),
+			Resolved::Unknown => None,
+		}
+	}
+
+	/// Check if the K/V was in the map.
+	pub fn is_mapped(&self) -> bool {
+		matches!(*self, Resolved::Mapped(_))
+	}
+}
+
+
+/// A `BidirMap` consulting a function in either direction for K/Vs that aren't in the map, like an identity for unmapped codes.
+///
+/// Only the `resolve_by_*()` lookups consult the fallbacks; all other methods treat the map as a plain `BidirMap`.
+/// The fallbacks never insert into the map, so they can't break its invariants,
+/// but nor are their results checked against it: a fallen-back K/V may well be in the map, paired with another one.
+///
+/// # Examples
+///
+/// ```
+/// use bidir_map::{BidirMap, Resolved};
+///
+/// // Lowercase letters are transcoded to their uppercase forms, other ASCII characters are passed through
+/// let mut map = BidirMap::with_fallbacks(|&kv1: &u8| if kv1.is_ascii() { Some(kv1 as char) } else { None },
+///                                        |&kv2: &char| if kv2.is_ascii() { Some(kv2 as u8) } else { None });
+/// map.extend((b'a'..=b'z').map(|kv1| (kv1, kv1.to_ascii_uppercase() as char)));
+///
+/// assert_eq!(map.resolve_by_first(&b'q'), Resolved::Mapped(&'Q'));
+/// assert_eq!(map.resolve_by_first(&b'?'), Resolved::FellBack('?'));
+/// assert_eq!(map.resolve_by_first(&0xFF), Resolved::Unknown);
+/// assert_eq!(map.resolve_by_second(&'Q'), Resolved::Mapped(&b'q'));
+/// assert_eq!(map.get_by_first(&b'?'), None);
+/// ```
+#[derive(Clone, Debug)]
+pub struct FallbackBidirMap<Kv1: PartialEq, Kv2: PartialEq, F: Fn(&Kv1) -> Option<Kv2>, B: Fn(&Kv2) -> Option<Kv1>> {
+	map: BidirMap<Kv1, Kv2>,
+	forward: F,
+	backward: B,
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
+	/// Create a new empty `FallbackBidirMap`, resolving first K/Vs not in the map with `forward` and second ones with `backward`.
+	pub fn with_fallbacks<F, B>(forward: F, backward: B) -> FallbackBidirMap<Kv1, Kv2, F, B>
+		where F: Fn(&Kv1) -> Option<Kv2>,
+		      B: Fn(&Kv2) -> Option<Kv1>,
+	{
+		BidirMap::new().falling_back(forward, backward)
+	}
+
+	/// Wraps the map into a `FallbackBidirMap`, resolving first K/Vs not in the map with `forward` and second ones with `backward`.
+	pub fn falling_back<F, B>(self, forward: F, backward: B) -> FallbackBidirMap<Kv1, Kv2, F, B>
+		where F: Fn(&Kv1) -> Option<Kv2>,
+		      B: Fn(&Kv2) -> Option<Kv1>,
+	{
+		FallbackBidirMap{
+			map: self,
+			forward,
+			backward,
+		}
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq, F: Fn(&Kv1) -> Option<Kv2>, B: Fn(&Kv2) -> Option<Kv1>> FallbackBidirMap<Kv1, Kv2, F, B> {
+	/// Looks up the second K/V corresponding to the first K/V in the map, or else produces one by the forward fallback.
+	pub fn resolve_by_first<'s>(&'s self, key: &Kv1) -> Resolved<'s, Kv2> {
+		match self.map.get_by_first(key) {
+			Some(kv2) => Resolved::Mapped(kv2),
+			None => (self.forward)(key).map_or(Resolved::Unknown, Resolved::FellBack),
+		}
+	}
+
+	/// Looks up the first K/V corresponding to the second K/V in the map, or else produces one by the backward fallback.
+	pub fn resolve_by_second<'s>(&'s self, key: &Kv2) -> Resolved<'s, Kv1> {
+		match self.map.get_by_second(key) {
+			Some(kv1) => Resolved::Mapped(kv1),
+			None => (self.backward)(key).map_or(Resolved::Unknown, Resolved::FellBack),
+		}
+	}
+
+	/// Unwraps the underlying `BidirMap` and the forward and backward fallbacks.
+	pub fn into_parts(self) -> (BidirMap<Kv1, Kv2>, F, B) {
+		(self.map, self.forward, self.backward)
+	}
+
+	/// Gets a reference to the underlying `BidirMap`.
+	pub fn inner(&self) -> &BidirMap<Kv1, Kv2> {
+		&self.map
+	}
+
+	/// Gets a mutable reference to the underlying `BidirMap`, for its methods not mirrored here.
+	pub fn inner_mut(&mut self) -> &mut BidirMap<Kv1, Kv2> {
+		&mut self.map
+	}
+
+	/// Clears the map, removing all entries.
+	pub fn clear(&mut self) {
+		self.map.clear()
+	}
+
+	/// Inserts a K/V-K/V pair into the map, as `BidirMap::insert()` does.
+	pub fn insert(&mut self, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)> {
+		self.map.insert(kv1, kv2)
+	}
+
+	/// Removes the pair with the first K/V from the map, returning it if the key was previously in the map.
+	pub fn remove_by_first<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.remove_by_first(key)
+	}
+
+	/// Removes the pair with the second K/V from the map, returning it if the key was previously in the map.
+	pub fn remove_by_second<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.remove_by_second(key)
+	}
+
+	/// Returns a reference to the second K/V corresponding to the first K/V in the map, without falling back.
+	pub fn get_by_first<Q>(&self, key: &Q) -> Option<&Kv2>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.get_by_first(key)
+	}
+
+	/// Returns a reference to the first K/V corresponding to the second K/V in the map, without falling back.
+	pub fn get_by_second<Q>(&self, key: &Q) -> Option<&Kv1>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.get_by_second(key)
+	}
+
+	/// Check if the map contains the first K/V, without falling back.
+	pub fn contains_first_key<Q>(&self, key: &Q) -> bool
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.contains_first_key(key)
+	}
+
+	/// Check if the map contains the second K/V, without falling back.
+	pub fn contains_second_key<Q>(&self, key: &Q) -> bool
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.contains_second_key(key)
+	}
+
+	/// Gets an iterator over the entries of the map.
+	pub fn iter<'s>(&'s self) -> Iter<'s, Kv1, Kv2> {
+		self.map.iter()
+	}
+
+	/// Returns the number of elements in the map.
+	pub fn len(&self) -> usize {
+		self.map.len()
+	}
+
+	/// Returns true if the map contains no elements.
+	pub fn is_empty(&self) -> bool {
+		self.map.is_empty()
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq, F: Fn(&Kv1) -> Option<Kv2>, B: Fn(&Kv2) -> Option<Kv1>> Extend<(Kv1, Kv2)> for FallbackBidirMap<Kv1, Kv2, F, B> {
+	fn extend<T: IntoIterator<Item=(Kv1, Kv2)>>(&mut self, iter: T) {
+		self.map.extend(iter)
+	}
+}
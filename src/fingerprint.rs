@@ -0,0 +1,278 @@
+//! A `BidirMap` variant comparing hash fingerprints of the K/Vs before the K/Vs themselves.
+
+
+use core::borrow::Borrow;
+use core::fmt;
+use core::hash::{BuildHasher, Hash};
+use core::iter::{Extend, FromIterator};
+use std::collections::hash_map::RandomState;
+use std::vec::Vec;
+use {BidirMap, Iter};
+
+
+/// A bidirectional map storing a 64-bit hash of each K/V next to its pair, so that scans can skip most full comparisons.
+///
+/// This behaves exactly like `BidirMap`, including the order pairs end up in, but lookups compare the fingerprints first
+/// and only compare K/Vs whose fingerprints match. That pays off for K/Vs that are expensive to compare, like long `String`s,
+/// at the cost of hashing each K/V once on insertion and each key once per lookup.
+/// Fingerprints that collide merely cost an extra comparison.
+///
+/// Lookups by a borrowed form rely on it hashing like the K/V, as `Borrow` requires.
+/// There are no `iter_mut()` and `get_mut_by_*()`, since a K/V changed in place would no longer match its fingerprint.
+///
+/// Performance: `O(n)`, mostly.
+///
+/// # Examples
+///
+/// ```
+/// use bidir_map::FingerprintedBidirMap;
+///
+/// let mut map = FingerprintedBidirMap::new();
+/// map.insert("/usr/share/doc/bidir-map/README.md".to_string(), 1);
+/// map.insert("/usr/share/doc/bidir-map/LICENSE".to_string(), 2);
+///
+/// assert_eq!(map.get_by_first("/usr/share/doc/bidir-map/LICENSE"), Some(&2));
+/// assert_eq!(map.get_by_second(&1).map(String::as_str), Some("/usr/share/doc/bidir-map/README.md"));
+/// ```
+#[derive(Clone)]
+pub struct FingerprintedBidirMap<Kv1: Hash + Eq, Kv2: Hash + Eq, S = RandomState> {
+	cont: Vec<(Kv1, Kv2)>,
+	/// The hashes of the K/Vs of the pair at the same index of `cont`
+	fingerprints: Vec<(u64, u64)>,
+	hash_builder: S,
+}
+
+impl<Kv1: Hash + Eq, Kv2: Hash + Eq> FingerprintedBidirMap<Kv1, Kv2, RandomState> {
+	/// Create a new empty instance of `FingerprintedBidirMap`
+	pub fn new() -> Self {
+		FingerprintedBidirMap::with_hasher(RandomState::new())
+	}
+
+	/// Create a new empty instance of `FingerprintedBidirMap` with the specified capacity.
+	///
+	/// It will be able to hold at least `capacity` elements without reallocating.
+	pub fn with_capacity(capacity: usize) -> Self {
+		FingerprintedBidirMap::with_capacity_and_hasher(capacity, RandomState::new())
+	}
+}
+
+impl<Kv1: Hash + Eq, Kv2: Hash + Eq, S: BuildHasher> FingerprintedBidirMap<Kv1, Kv2, S> {
+	/// Create a new empty instance of `FingerprintedBidirMap` which will use the given hash builder to fingerprint both columns.
+	pub fn with_hasher(hash_builder: S) -> Self {
+		FingerprintedBidirMap::with_capacity_and_hasher(0, hash_builder)
+	}
+
+	/// Create a new empty instance of `FingerprintedBidirMap` with the specified capacity,
+	/// using the given hash builder to fingerprint both columns.
+	///
+	/// It will be able to hold at least `capacity` elements without reallocating.
+	pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> Self {
+		FingerprintedBidirMap{
+			cont: Vec::with_capacity(capacity),
+			fingerprints: Vec::with_capacity(capacity),
+			hash_builder,
+		}
+	}
+
+	/// Returns a reference to the map's hash builder.
+	pub fn hasher(&self) -> &S {
+		&self.hash_builder
+	}
+
+	/// Clears the map, removing all entries.
+	pub fn clear(&mut self) {
+		self.cont.clear();
+		self.fingerprints.clear();
+	}
+
+	/// Inserts a K/V-K/V pair into the map, as `BidirMap::insert()` does.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::FingerprintedBidirMap;
+	///
+	/// let mut map = FingerprintedBidirMap::new();
+	/// map.insert(1, "a");
+	/// map.insert(2, "b");
+	/// assert_eq!(map.insert(1, "b"), Some((1, "a")));
+	/// assert_eq!(map.len(), 1);
+	/// ```
+	pub fn insert(&mut self, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)> {
+		let fingerprints = (self.hash_builder.hash_one(&kv1), self.hash_builder.hash_one(&kv2));
+		let by_first = self.position_by(|kvs, f| f.0 == fingerprints.0 && kvs.0 == kv1).map(|idx| self.swap_remove_at(idx));
+		let by_second = self.position_by(|kvs, f| f.1 == fingerprints.1 && kvs.1 == kv2).map(|idx| self.swap_remove_at(idx));
+
+		self.cont.push((kv1, kv2));
+		self.fingerprints.push(fingerprints);
+
+		by_first.or(by_second)
+	}
+
+	/// Removes the pair corresponding to the first K/V from the map, returning it if the key was previously in the map.
+	pub fn remove_by_first<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv1: Borrow<Q>,
+		      Q  : Hash + Eq + ?Sized,
+	{
+		self.position_of_first(key).map(|idx| self.swap_remove_at(idx))
+	}
+
+	/// Removes the pair corresponding to the second K/V from the map, returning it if the key was previously in the map.
+	pub fn remove_by_second<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv2: Borrow<Q>,
+		      Q  : Hash + Eq + ?Sized,
+	{
+		self.position_of_second(key).map(|idx| self.swap_remove_at(idx))
+	}
+
+	/// Retains only the pairs for which `f` returns `true`, keeping their order.
+	pub fn retain<F: FnMut(&Kv1, &Kv2) -> bool>(&mut self, mut f: F) {
+		let mut kept = 0;
+		for idx in 0..self.cont.len() {
+			if f(&self.cont[idx].0, &self.cont[idx].1) {
+				self.cont.swap(kept, idx);
+				self.fingerprints.swap(kept, idx);
+				kept += 1;
+			}
+		}
+		self.cont.truncate(kept);
+		self.fingerprints.truncate(kept);
+	}
+
+	/// Returns a reference to the second K/V corresponding to the first K/V.
+	pub fn get_by_first<Q>(&self, key: &Q) -> Option<&Kv2>
+		where Kv1: Borrow<Q>,
+		      Q  : Hash + Eq + ?Sized,
+	{
+		self.position_of_first(key).map(|idx| &self.cont[idx].1)
+	}
+
+	/// Returns a reference to the first K/V corresponding to the second K/V.
+	pub fn get_by_second<Q>(&self, key: &Q) -> Option<&Kv1>
+		where Kv2: Borrow<Q>,
+		      Q  : Hash + Eq + ?Sized,
+	{
+		self.position_of_second(key).map(|idx| &self.cont[idx].0)
+	}
+
+	/// Check if the map contains the first K/V
+	pub fn contains_first_key<Q>(&self, key: &Q) -> bool
+		where Kv1: Borrow<Q>,
+		      Q  : Hash + Eq + ?Sized,
+	{
+		self.position_of_first(key).is_some()
+	}
+
+	/// Check if the map contains the second K/V
+	pub fn contains_second_key<Q>(&self, key: &Q) -> bool
+		where Kv2: Borrow<Q>,
+		      Q  : Hash + Eq + ?Sized,
+	{
+		self.position_of_second(key).is_some()
+	}
+
+	/// Gets an iterator over the entries of the map.
+	pub fn iter<'s>(&'s self) -> Iter<'s, Kv1, Kv2> {
+		Iter{
+			iter: self.cont.iter(),
+		}
+	}
+
+	/// Returns the number of elements in the map.
+	pub fn len(&self) -> usize {
+		self.cont.len()
+	}
+
+	/// Returns true if the map contains no elements.
+	pub fn is_empty(&self) -> bool {
+		self.cont.is_empty()
+	}
+
+	fn position_of_first<Q>(&self, key: &Q) -> Option<usize>
+		where Kv1: Borrow<Q>,
+		      Q  : Hash + Eq + ?Sized,
+	{
+		let fingerprint = self.hash_builder.hash_one(key);
+		self.position_by(|kvs, f| f.0 == fingerprint && *key == *kvs.0.borrow())
+	}
+
+	fn position_of_second<Q>(&self, key: &Q) -> Option<usize>
+		where Kv2: Borrow<Q>,
+		      Q  : Hash + Eq + ?Sized,
+	{
+		let fingerprint = self.hash_builder.hash_one(key);
+		self.position_by(|kvs, f| f.1 == fingerprint && *key == *kvs.1.borrow())
+	}
+
+	/// `pred` gets the fingerprints too, and must check them before comparing K/Vs
+	fn position_by<F: FnMut(&(Kv1, Kv2), &(u64, u64)) -> bool>(&self, mut pred: F) -> Option<usize> {
+		self.cont.iter().zip(&self.fingerprints).position(|(kvs, f)| pred(kvs, f))
+	}
+
+	/// Same as `BidirMap`'s, so both types leave the pairs in the same order
+	fn swap_remove_at(&mut self, idx: usize) -> (Kv1, Kv2) {
+		self.fingerprints.swap_remove(idx);
+		self.cont.swap_remove(idx)
+	}
+}
+
+impl<Kv1: Hash + Eq, Kv2: Hash + Eq, S: BuildHasher + Default> Default for FingerprintedBidirMap<Kv1, Kv2, S> {
+	fn default() -> Self {
+		FingerprintedBidirMap::with_hasher(S::default())
+	}
+}
+
+impl<Kv1: Hash + Eq + fmt::Debug, Kv2: Hash + Eq + fmt::Debug, S> fmt::Debug for FingerprintedBidirMap<Kv1, Kv2, S> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_map().entries(self.cont.iter().map(|kvs| (&kvs.0, &kvs.1))).finish()
+	}
+}
+
+/// Compares the pairs, in order; the hash builders don't matter.
+impl<Kv1: Hash + Eq, Kv2: Hash + Eq, S> PartialEq for FingerprintedBidirMap<Kv1, Kv2, S> {
+	fn eq(&self, other: &Self) -> bool {
+		self.cont == other.cont
+	}
+}
+
+impl<Kv1: Hash + Eq, Kv2: Hash + Eq, S> Eq for FingerprintedBidirMap<Kv1, Kv2, S> {}
+
+impl<'a, Kv1: Hash + Eq, Kv2: Hash + Eq, S: BuildHasher> IntoIterator for &'a FingerprintedBidirMap<Kv1, Kv2, S> {
+	type Item = &'a (Kv1, Kv2);
+	type IntoIter = Iter<'a, Kv1, Kv2>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+impl<Kv1: Hash + Eq, Kv2: Hash + Eq, S: BuildHasher + Default> FromIterator<(Kv1, Kv2)> for FingerprintedBidirMap<Kv1, Kv2, S> {
+	/// Like `BidirMap`'s, this doesn't check for duplicates.
+	fn from_iter<T: IntoIterator<Item=(Kv1, Kv2)>>(iter: T) -> Self {
+		let mut map = FingerprintedBidirMap::default();
+		map.extend(iter);
+		map
+	}
+}
+
+impl<Kv1: Hash + Eq, Kv2: Hash + Eq, S: BuildHasher> Extend<(Kv1, Kv2)> for FingerprintedBidirMap<Kv1, Kv2, S> {
+	/// Like `BidirMap`'s, this doesn't check for duplicates.
+	fn extend<T: IntoIterator<Item=(Kv1, Kv2)>>(&mut self, iter: T) {
+		for (kv1, kv2) in iter {
+			self.fingerprints.push((self.hash_builder.hash_one(&kv1), self.hash_builder.hash_one(&kv2)));
+			self.cont.push((kv1, kv2));
+		}
+	}
+}
+
+impl<Kv1: Hash + Eq, Kv2: Hash + Eq, S: BuildHasher + Default> From<BidirMap<Kv1, Kv2>> for FingerprintedBidirMap<Kv1, Kv2, S> {
+	fn from(map: BidirMap<Kv1, Kv2>) -> Self {
+		map.into_iter().collect()
+	}
+}
+
+impl<Kv1: Hash + Eq, Kv2: Hash + Eq, S> From<FingerprintedBidirMap<Kv1, Kv2, S>> for BidirMap<Kv1, Kv2> {
+	fn from(map: FingerprintedBidirMap<Kv1, Kv2, S>) -> Self {
+		map.cont.into_iter().collect()
+	}
+}
@@ -0,0 +1,201 @@
+//! An immutable `BidirMap` snapshot with logarithmic lookups.
+
+
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use alloc::vec::Vec;
+use {BidirMap, FirstColumn, Iter, SecondColumn};
+
+
+/// An immutable bidirectional map with prebuilt lookup indexes.
+///
+/// Created with `BidirMap::freeze()`, which sorts two index permutations once so both `get_by_first()` and `get_by_second()`
+/// run in `O(log n)`; iteration still yields the pairs in the order the original map had them.
+///
+/// Having no interior mutability, it's `Send + Sync` whenever the keys are, so it can be shared freely behind an `Arc`.
+///
+/// Performance: `O(log n)` lookups.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct FrozenBidirMap<Kv1: Ord, Kv2: Ord> {
+	cont: Vec<(Kv1, Kv2)>,
+	by_first: Vec<usize>,
+	by_second: Vec<usize>,
+}
+
+impl<Kv1: Ord, Kv2: Ord> BidirMap<Kv1, Kv2> {
+	/// Converts the map into an immutable `FrozenBidirMap`, building its lookup indexes.
+	///
+	/// This takes `O(n log n)` once, after which all lookups take `O(log n)`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert(1, "a");
+	/// map.insert(2, "b");
+	///
+	/// let frozen = map.freeze();
+	/// assert_eq!(frozen.get_by_first(&2), Some(&"b"));
+	/// assert_eq!(frozen.get_by_second(&"a"), Some(&1));
+	/// ```
+	pub fn freeze(self) -> FrozenBidirMap<Kv1, Kv2> {
+		let cont = self.cont;
+
+		let mut by_first: Vec<_> = (0..cont.len()).collect();
+		by_first.sort_by(|&l, &r| cont[l].0.cmp(&cont[r].0));
+		let mut by_second: Vec<_> = (0..cont.len()).collect();
+		by_second.sort_by(|&l, &r| cont[l].1.cmp(&cont[r].1));
+
+		FrozenBidirMap{
+			cont,
+			by_first,
+			by_second,
+		}
+	}
+}
+
+impl<Kv1: Ord, Kv2: Ord> FrozenBidirMap<Kv1, Kv2> {
+	/// Converts the frozen map back into a mutable `BidirMap`, dropping the indexes.
+	///
+	/// The pairs keep the order they had before `freeze()`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert(1, "a");
+	///
+	/// let mut thawed = map.clone().freeze().thaw();
+	/// assert_eq!(thawed, map);
+	/// thawed.insert(2, "b");
+	/// ```
+	pub fn thaw(self) -> BidirMap<Kv1, Kv2> {
+		BidirMap::from_cont(self.cont)
+	}
+
+	/// Gets an iterator over the entries of the map, in the order of the map it was frozen from.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert(2, "b");
+	/// map.insert(1, "a");
+	///
+	/// let frozen = map.freeze();
+	/// assert_eq!(*frozen.iter().next().unwrap(), (2, "b"));
+	/// ```
+	pub fn iter<'s>(&'s self) -> Iter<'s, Kv1, Kv2> {
+		Iter{
+			iter: self.cont.iter(),
+		}
+	}
+
+	/// Gets an iterator over the first K/V of the map.
+	pub fn first_col<'s>(&'s self) -> FirstColumn<'s, Kv1, Kv2> {
+		FirstColumn{
+			iter: self.cont.iter(),
+		}
+	}
+
+	/// Gets an iterator over the second K/V of the map.
+	pub fn second_col<'s>(&'s self) -> SecondColumn<'s, Kv1, Kv2> {
+		SecondColumn{
+			iter: self.cont.iter(),
+		}
+	}
+
+	/// Returns the number of elements in the map.
+	pub fn len(&self) -> usize {
+		self.cont.len()
+	}
+
+	/// Returns true if the map contains no elements.
+	pub fn is_empty(&self) -> bool {
+		self.cont.is_empty()
+	}
+
+
+	/// Returns a reference to the second K/V corresponding to the first K/V.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert("a".to_string(), 1);
+	///
+	/// let frozen = map.freeze();
+	/// assert_eq!(frozen.get_by_first("a"), Some(&1));
+	/// assert_eq!(frozen.get_by_first("b"), None);
+	/// ```
+	pub fn get_by_first<Q>(&self, key: &Q) -> Option<&Kv2>
+		where Kv1: Borrow<Q>,
+		      Q  : Ord + ?Sized,
+	{
+		self.find(&self.by_first, |kvs| kvs.0.borrow().cmp(key)).map(|kvs| &kvs.1)
+	}
+
+	/// Returns a reference to the first K/V corresponding to the second K/V.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert(1, "a".to_string());
+	///
+	/// let frozen = map.freeze();
+	/// assert_eq!(frozen.get_by_second("a"), Some(&1));
+	/// assert_eq!(frozen.get_by_second("b"), None);
+	/// ```
+	pub fn get_by_second<Q>(&self, key: &Q) -> Option<&Kv1>
+		where Kv2: Borrow<Q>,
+		      Q  : Ord + ?Sized,
+	{
+		self.find(&self.by_second, |kvs| kvs.1.borrow().cmp(key)).map(|kvs| &kvs.0)
+	}
+
+	/// Check if the map contains the first K/V
+	pub fn contains_first_key<Q>(&self, key: &Q) -> bool
+		where Kv1: Borrow<Q>,
+		      Q  : Ord + ?Sized,
+	{
+		self.get_by_first(key).is_some()
+	}
+
+	/// Check if the map contains the second K/V
+	pub fn contains_second_key<Q>(&self, key: &Q) -> bool
+		where Kv2: Borrow<Q>,
+		      Q  : Ord + ?Sized,
+	{
+		self.get_by_second(key).is_some()
+	}
+
+	fn find<F: Fn(&(Kv1, Kv2)) -> Ordering>(&self, index: &[usize], cmp: F) -> Option<&(Kv1, Kv2)> {
+		index.binary_search_by(|&idx| cmp(&self.cont[idx])).ok().map(|pos| &self.cont[index[pos]])
+	}
+}
+
+impl<Kv1: Ord, Kv2: Ord> From<FrozenBidirMap<Kv1, Kv2>> for BidirMap<Kv1, Kv2> {
+	fn from(frozen: FrozenBidirMap<Kv1, Kv2>) -> Self {
+		frozen.thaw()
+	}
+}
+
+impl<'a, Kv1: Ord, Kv2: Ord> IntoIterator for &'a FrozenBidirMap<Kv1, Kv2> {
+	type Item = &'a (Kv1, Kv2);
+	type IntoIter = Iter<'a, Kv1, Kv2>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
@@ -0,0 +1,82 @@
+//! Finding the K/Vs closest to a misspelled one, by Levenshtein distance, for "did you mean" suggestions.
+
+
+use alloc::vec::Vec;
+use BidirMap;
+
+
+impl<Kv1: PartialEq + AsRef<str>, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
+	/// Returns the pair whose first K/V is closest to `query`, within `max_distance` edits, with its distance.
+	///
+	/// Distances are Levenshtein distances over `char`s; of equally close pairs, the earliest in iteration order is returned.
+	///
+	/// Performance: `O(n·m·l)`, for `query`'s length `m` and the length `l` of the longest first K/V.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut aliases = BidirMap::new();
+	/// aliases.insert("co", "checkout");
+	/// aliases.insert("ci", "commit");
+	/// aliases.insert("st", "status");
+	///
+	/// assert_eq!(aliases.closest_by_first("cp", 1), Some((&"co", &"checkout", 1)));
+	/// assert_eq!(aliases.closest_by_first("xyz", 2), None);
+	/// ```
+	pub fn closest_by_first(&self, query: &str, max_distance: usize) -> Option<(&Kv1, &Kv2, usize)> {
+		self.closest_n_by_first(query, max_distance, 1).pop()
+	}
+
+	/// Returns up to `n` pairs whose first K/Vs are within `max_distance` edits of `query`, closest first, with their distances.
+	///
+	/// Ties are in iteration order, as for `closest_by_first()`.
+	pub fn closest_n_by_first(&self, query: &str, max_distance: usize, n: usize) -> Vec<(&Kv1, &Kv2, usize)> {
+		let query: Vec<char> = query.chars().collect();
+		closest_n(self.cont.iter().map(|kvs| (&kvs.0, &kvs.1, levenshtein(&query, kvs.0.as_ref()))), max_distance, n)
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq + AsRef<str>> BidirMap<Kv1, Kv2> {
+	/// Returns the pair whose second K/V is closest to `query`, within `max_distance` edits, with its distance.
+	///
+	/// See `closest_by_first()` for how distances are measured and ties broken.
+	pub fn closest_by_second(&self, query: &str, max_distance: usize) -> Option<(&Kv1, &Kv2, usize)> {
+		self.closest_n_by_second(query, max_distance, 1).pop()
+	}
+
+	/// Returns up to `n` pairs whose second K/Vs are within `max_distance` edits of `query`, closest first, with their distances.
+	///
+	/// Ties are in iteration order, as for `closest_by_first()`.
+	pub fn closest_n_by_second(&self, query: &str, max_distance: usize, n: usize) -> Vec<(&Kv1, &Kv2, usize)> {
+		let query: Vec<char> = query.chars().collect();
+		closest_n(self.cont.iter().map(|kvs| (&kvs.0, &kvs.1, levenshtein(&query, kvs.1.as_ref()))), max_distance, n)
+	}
+}
+
+
+fn closest_n<'a, Kv1, Kv2, I>(candidates: I, max_distance: usize, n: usize) -> Vec<(&'a Kv1, &'a Kv2, usize)>
+	where I: Iterator<Item = (&'a Kv1, &'a Kv2, usize)>,
+{
+	let mut closest: Vec<_> = candidates.filter(|&(_, _, distance)| distance <= max_distance).collect();
+	// Stable, so ties stay in iteration order
+	closest.sort_by_key(|&(_, _, distance)| distance);
+	closest.truncate(n);
+	closest
+}
+
+/// The number of `char` insertions, removals and substitutions turning `lhs` into `rhs`
+fn levenshtein(lhs: &[char], rhs: &str) -> usize {
+	let mut row: Vec<usize> = (0..lhs.len() + 1).collect();
+	for (j, r) in rhs.chars().enumerate() {
+		let mut diagonal = row[0];
+		row[0] = j + 1;
+		for (i, &l) in lhs.iter().enumerate() {
+			let substituted = diagonal + (l != r) as usize;
+			diagonal = row[i + 1];
+			row[i + 1] = substituted.min(row[i] + 1).min(diagonal + 1);
+		}
+	}
+	row[lhs.len()]
+}
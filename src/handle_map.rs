@@ -0,0 +1,296 @@
+//! A `BidirMap` variant handing out stable handles to its pairs.
+
+
+use core::borrow::Borrow;
+use alloc::vec::Vec;
+use {BidirMap, Iter};
+
+
+/// Identifies a pair of a `HandleBidirMap` for as long as it's in the map.
+///
+/// Obtained from `HandleBidirMap::insert_full()` or `handle_of_*()`.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct PairHandle {
+	slot: usize,
+	generation: u32,
+}
+
+/// A bidirectional map whose pairs can be referred to by `PairHandle`s, which stay valid across unrelated insertions and removals.
+///
+/// A handle is invalidated by its pair being removed, or replaced by `insert()`ing a pair sharing a K/V with it;
+/// the handle of the replacement is different.
+/// Handles aren't resurrected by later pairs reusing their storage.
+///
+/// The pairs are stored densely, like in `BidirMap`, and refer back to slots recording their current positions.
+///
+/// Performance: `O(n)`, mostly; `O(1)` lookups by handle.
+///
+/// # Examples
+///
+/// ```
+/// use bidir_map::HandleBidirMap;
+///
+/// let mut map = HandleBidirMap::new();
+/// let (a, _) = map.insert_full(1, "a");
+/// map.insert(2, "b");
+///
+/// map.remove_by_first(&2);
+/// assert_eq!(map.get_by_handle(a), Some((&1, &"a")));
+/// map.remove_by_first(&1);
+/// assert_eq!(map.get_by_handle(a), None);
+/// ```
+#[derive(Clone, Debug)]
+pub struct HandleBidirMap<Kv1: PartialEq, Kv2: PartialEq> {
+	cont: Vec<(Kv1, Kv2)>,
+	/// The slot of each pair in `cont`
+	owners: Vec<usize>,
+	slots: Vec<Slot>,
+	/// Slots currently without a pair
+	free: Vec<usize>,
+}
+
+#[derive(Clone, Debug)]
+struct Slot {
+	/// Bumped whenever the slot's pair is removed
+	generation: u32,
+	/// Index into `cont`, if occupied
+	position: Option<usize>,
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> HandleBidirMap<Kv1, Kv2> {
+	/// Create a new empty instance of `HandleBidirMap`
+	pub fn new() -> Self {
+		HandleBidirMap{
+			cont: Vec::new(),
+			owners: Vec::new(),
+			slots: Vec::new(),
+			free: Vec::new(),
+		}
+	}
+
+	/// Create a new empty instance of `HandleBidirMap` with the specified capacity.
+	///
+	/// It will be able to hold at least `capacity` elements without reallocating.
+	pub fn with_capacity(capacity: usize) -> Self {
+		HandleBidirMap{
+			cont: Vec::with_capacity(capacity),
+			owners: Vec::with_capacity(capacity),
+			slots: Vec::with_capacity(capacity),
+			free: Vec::new(),
+		}
+	}
+
+	/// Clears the map, removing all entries and invalidating all handles.
+	pub fn clear(&mut self) {
+		while !self.cont.is_empty() {
+			let last = self.cont.len() - 1;
+			self.remove_at(last);
+		}
+	}
+
+	/// Inserts a K/V-K/V pair into the map, see `BidirMap::insert()`.
+	pub fn insert(&mut self, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)> {
+		self.insert_full(kv1, kv2).1
+	}
+
+	/// Inserts a K/V-K/V pair into the map, returning its handle and the pair it replaced, see `BidirMap::insert()`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::HandleBidirMap;
+	///
+	/// let mut map = HandleBidirMap::new();
+	/// let (a, replaced) = map.insert_full(1, "a");
+	/// assert_eq!(replaced, None);
+	///
+	/// let (b, replaced) = map.insert_full(1, "b");
+	/// assert_eq!(replaced, Some((1, "a")));
+	/// assert_eq!(map.get_by_handle(a), None);
+	/// assert_eq!(map.get_by_handle(b), Some((&1, &"b")));
+	/// ```
+	pub fn insert_full(&mut self, kv1: Kv1, kv2: Kv2) -> (PairHandle, Option<(Kv1, Kv2)>) {
+		let by_first = self.cont.iter().position(|kvs| kvs.0 == kv1).map(|idx| self.remove_at(idx));
+		let by_second = self.cont.iter().position(|kvs| kvs.1 == kv2).map(|idx| self.remove_at(idx));
+
+		let slot = match self.free.pop() {
+			Some(slot) => slot,
+			None => {
+				self.slots.push(Slot{
+					generation: 0,
+					position: None,
+				});
+				self.slots.len() - 1
+			}
+		};
+		self.slots[slot].position = Some(self.cont.len());
+		self.cont.push((kv1, kv2));
+		self.owners.push(slot);
+
+		(PairHandle{slot, generation: self.slots[slot].generation}, by_first.or(by_second))
+	}
+
+	/// Returns the pair the handle refers to, or `None` if it's been removed or replaced.
+	pub fn get_by_handle(&self, handle: PairHandle) -> Option<(&Kv1, &Kv2)> {
+		self.position_of(handle).map(|idx| (&self.cont[idx].0, &self.cont[idx].1))
+	}
+
+	/// Returns the pair the handle refers to with its second K/V mutable, or `None` if it's been removed or replaced.
+	pub fn get_mut_by_handle(&mut self, handle: PairHandle) -> Option<(&Kv1, &mut Kv2)> {
+		let idx = self.position_of(handle)?;
+		let kvs = &mut self.cont[idx];
+		Some((&kvs.0, &mut kvs.1))
+	}
+
+	/// Check if the handle still refers to a pair in the map
+	pub fn contains_handle(&self, handle: PairHandle) -> bool {
+		self.position_of(handle).is_some()
+	}
+
+	/// Removes the pair the handle refers to from the map, returning it if it was still in the map.
+	pub fn remove_by_handle(&mut self, handle: PairHandle) -> Option<(Kv1, Kv2)> {
+		self.position_of(handle).map(|idx| self.remove_at(idx))
+	}
+
+	/// Returns the handle of the pair with the specified first K/V.
+	pub fn handle_of_first<Q>(&self, key: &Q) -> Option<PairHandle>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.cont.iter().position(|kvs| *key == *kvs.0.borrow()).map(|idx| self.handle_at(idx))
+	}
+
+	/// Returns the handle of the pair with the specified second K/V.
+	pub fn handle_of_second<Q>(&self, key: &Q) -> Option<PairHandle>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.cont.iter().position(|kvs| *key == *kvs.1.borrow()).map(|idx| self.handle_at(idx))
+	}
+
+	/// Gets an iterator over the entries of the map.
+	pub fn iter<'s>(&'s self) -> Iter<'s, Kv1, Kv2> {
+		Iter{
+			iter: self.cont.iter(),
+		}
+	}
+
+	/// Returns the number of elements in the map.
+	pub fn len(&self) -> usize {
+		self.cont.len()
+	}
+
+	/// Returns true if the map contains no elements.
+	pub fn is_empty(&self) -> bool {
+		self.cont.is_empty()
+	}
+
+
+	/// Returns a reference to the second K/V corresponding to the first K/V.
+	pub fn get_by_first<Q>(&self, key: &Q) -> Option<&Kv2>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.cont.iter().find(|kvs| *key == *kvs.0.borrow()).map(|kvs| &kvs.1)
+	}
+
+	/// Returns a reference to the first K/V corresponding to the second K/V.
+	pub fn get_by_second<Q>(&self, key: &Q) -> Option<&Kv1>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.cont.iter().find(|kvs| *key == *kvs.1.borrow()).map(|kvs| &kvs.0)
+	}
+
+	/// Check if the map contains the first K/V
+	pub fn contains_first_key<Q>(&self, key: &Q) -> bool
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.get_by_first(key).is_some()
+	}
+
+	/// Check if the map contains the second K/V
+	pub fn contains_second_key<Q>(&self, key: &Q) -> bool
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.get_by_second(key).is_some()
+	}
+
+	/// Removes the pair corresponding to the first K/V from the map, returning it if the key was previously in the map.
+	pub fn remove_by_first<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.cont.iter().position(|kvs| *key == *kvs.0.borrow()).map(|idx| self.remove_at(idx))
+	}
+
+	/// Removes the pair corresponding to the second K/V from the map, returning it if the key was previously in the map.
+	pub fn remove_by_second<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.cont.iter().position(|kvs| *key == *kvs.1.borrow()).map(|idx| self.remove_at(idx))
+	}
+
+	fn position_of(&self, handle: PairHandle) -> Option<usize> {
+		match self.slots.get(handle.slot) {
+			Some(slot) if slot.generation == handle.generation => slot.position,
+			_ => None,
+		}
+	}
+
+	fn handle_at(&self, idx: usize) -> PairHandle {
+		let slot = self.owners[idx];
+		PairHandle{slot, generation: self.slots[slot].generation}
+	}
+
+	/// `swap_remove()`s the pair, retiring its slot and updating that of the pair moved into its place
+	fn remove_at(&mut self, idx: usize) -> (Kv1, Kv2) {
+		let slot = self.owners.swap_remove(idx);
+		self.slots[slot].generation = self.slots[slot].generation.wrapping_add(1);
+		self.slots[slot].position = None;
+		self.free.push(slot);
+
+		if let Some(&moved) = self.owners.get(idx) {
+			self.slots[moved].position = Some(idx);
+		}
+		self.cont.swap_remove(idx)
+	}
+}
+
+
+impl<Kv1: PartialEq, Kv2: PartialEq> Default for HandleBidirMap<Kv1, Kv2> {
+	fn default() -> Self {
+		HandleBidirMap::new()
+	}
+}
+
+impl<'a, Kv1: PartialEq, Kv2: PartialEq> IntoIterator for &'a HandleBidirMap<Kv1, Kv2> {
+	type Item = &'a (Kv1, Kv2);
+	type IntoIter = Iter<'a, Kv1, Kv2>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> From<BidirMap<Kv1, Kv2>> for HandleBidirMap<Kv1, Kv2> {
+	fn from(map: BidirMap<Kv1, Kv2>) -> Self {
+		let cont = map.cont;
+		let len = cont.len();
+		HandleBidirMap{
+			cont,
+			owners: (0..len).collect(),
+			slots: (0..len).map(|idx| Slot{generation: 0, position: Some(idx)}).collect(),
+			free: Vec::new(),
+		}
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> From<HandleBidirMap<Kv1, Kv2>> for BidirMap<Kv1, Kv2> {
+	fn from(map: HandleBidirMap<Kv1, Kv2>) -> Self {
+		BidirMap::from_cont(map.cont)
+	}
+}
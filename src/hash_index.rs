@@ -0,0 +1,299 @@
+//! The optional hash index kept by a `BidirMap`, see `BidirMap::build_index()`.
+
+
+use core::borrow::Borrow;
+use std::collections::HashMap;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+use alloc::vec::Vec;
+use BidirMap;
+
+
+impl<Kv1: PartialEq, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
+	/// Builds a hash index over both columns, making insertions and lookups `O(1)`.
+	///
+	/// Once built, the index is kept in sync by every mutating method until `drop_index()` is called;
+	/// after keys have been changed in place via `iter_mut()` or `get_mut_by_*()` it's rebuilt on the next mutation.
+	///
+	/// The plain `get_by_*()`, `contains_*()` and `remove_by_*()` methods use the index when queried by the K/V type itself,
+	/// but keep scanning for borrowed forms of it, which aren't required to be hashable;
+	/// their `_indexed()` counterparts also use the index for those, like a `&str` looking up `String` K/Vs.
+	///
+	/// The index always hashes with std's `RandomState`; `BidirHashMap` lets the hasher be chosen.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert("a".to_string(), 1);
+	/// map.build_index();
+	///
+	/// map.insert("b".to_string(), 2);
+	/// assert_eq!(map.get_by_first_indexed("b"), Some(&2));
+	/// assert_eq!(map.get_by_second_indexed(&1), Some(&"a".to_string()));
+	/// ```
+	pub fn build_index(&mut self)
+		where Kv1: Hash + Eq,
+		      Kv2: Hash + Eq,
+	{
+		self.index = Some(HashIndex::new(&self.cont));
+	}
+
+	/// Drops the hash index, if any, returning to plain linear scans.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::<u8, u8>::new();
+	/// map.build_index();
+	/// assert!(map.has_index());
+	/// map.drop_index();
+	/// assert!(!map.has_index());
+	/// ```
+	pub fn drop_index(&mut self) {
+		self.index = None;
+	}
+
+	/// Check if `build_index()` was called and the index wasn't dropped since.
+	pub fn has_index(&self) -> bool {
+		self.index.is_some()
+	}
+
+	/// Returns a reference to the second K/V corresponding to the first K/V, using the hash index if present.
+	///
+	/// Without the index (or while it's waiting to be rebuilt) this is equivalent to `get_by_first()`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert("a".to_string(), 1);
+	/// map.build_index();
+	/// assert_eq!(map.get_by_first_indexed("a"), Some(&1));
+	/// assert_eq!(map.get_by_first_indexed("b"), None);
+	/// ```
+	pub fn get_by_first_indexed<Q>(&self, key: &Q) -> Option<&Kv2>
+		where Kv1: Borrow<Q>,
+		      Q  : Hash + PartialEq + ?Sized,
+	{
+		self.position_by_first_indexed(key).map(|idx| &self.cont[idx].1)
+	}
+
+	/// Returns a reference to the first K/V corresponding to the second K/V, using the hash index if present.
+	///
+	/// Without the index (or while it's waiting to be rebuilt) this is equivalent to `get_by_second()`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert(1, "a".to_string());
+	/// map.build_index();
+	/// assert_eq!(map.get_by_second_indexed("a"), Some(&1));
+	/// assert_eq!(map.get_by_second_indexed("b"), None);
+	/// ```
+	pub fn get_by_second_indexed<Q>(&self, key: &Q) -> Option<&Kv1>
+		where Kv2: Borrow<Q>,
+		      Q  : Hash + PartialEq + ?Sized,
+	{
+		self.position_by_second_indexed(key).map(|idx| &self.cont[idx].0)
+	}
+
+	/// Check if the map contains the first K/V, using the hash index if present.
+	pub fn contains_first_key_indexed<Q>(&self, key: &Q) -> bool
+		where Kv1: Borrow<Q>,
+		      Q  : Hash + PartialEq + ?Sized,
+	{
+		self.position_by_first_indexed(key).is_some()
+	}
+
+	/// Check if the map contains the second K/V, using the hash index if present.
+	pub fn contains_second_key_indexed<Q>(&self, key: &Q) -> bool
+		where Kv2: Borrow<Q>,
+		      Q  : Hash + PartialEq + ?Sized,
+	{
+		self.position_by_second_indexed(key).is_some()
+	}
+
+	/// Removes the pair corresponding to the first K/V from the map, using the hash index if present.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert("a".to_string(), 1);
+	/// map.build_index();
+	/// assert_eq!(map.remove_by_first_indexed("a"), Some(("a".to_string(), 1)));
+	/// assert_eq!(map.remove_by_first_indexed("a"), None);
+	/// ```
+	pub fn remove_by_first_indexed<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv1: Borrow<Q>,
+		      Q  : Hash + PartialEq + ?Sized,
+	{
+		self.refresh_index();
+		self.position_by_first_indexed(key).map(|idx| self.swap_remove_at(idx))
+	}
+
+	/// Removes the pair corresponding to the second K/V from the map, using the hash index if present.
+	pub fn remove_by_second_indexed<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv2: Borrow<Q>,
+		      Q  : Hash + PartialEq + ?Sized,
+	{
+		self.refresh_index();
+		self.position_by_second_indexed(key).map(|idx| self.swap_remove_at(idx))
+	}
+
+	fn position_by_first_indexed<Q>(&self, key: &Q) -> Option<usize>
+		where Kv1: Borrow<Q>,
+		      Q  : Hash + PartialEq + ?Sized,
+	{
+		match self.index {
+			Some(ref index) if !index.stale => index.position_by_first(&self.cont, index.hash_query(key), |k| *key == *k.borrow()),
+			_ => self.cont.iter().position(|kvs| *key == *kvs.0.borrow()),
+		}
+	}
+
+	fn position_by_second_indexed<Q>(&self, key: &Q) -> Option<usize>
+		where Kv2: Borrow<Q>,
+		      Q  : Hash + PartialEq + ?Sized,
+	{
+		match self.index {
+			Some(ref index) if !index.stale => index.position_by_second(&self.cont, index.hash_query(key), |k| *key == *k.borrow()),
+			_ => self.cont.iter().position(|kvs| *key == *kvs.1.borrow()),
+		}
+	}
+}
+
+
+/// Buckets of pair indices, keyed by the hashes of both columns.
+///
+/// The hash functions are captured when the index is built, where `Kv1: Hash` and `Kv2: Hash` are known,
+/// so that the map can keep the index in sync from methods that don't require hashable keys.
+#[derive(Clone)]
+pub struct HashIndex<Kv1, Kv2> {
+	hasher: RandomState,
+	hash_first: fn(&RandomState, &Kv1) -> u64,
+	hash_second: fn(&RandomState, &Kv2) -> u64,
+	first: HashMap<u64, Vec<usize>>,
+	second: HashMap<u64, Vec<usize>>,
+	/// Set when keys may have been changed in place, the index must then be rebuilt before it's used again
+	pub stale: bool,
+}
+
+fn hash_of<T: Hash + ?Sized>(hasher: &RandomState, val: &T) -> u64 {
+	hasher.hash_one(val)
+}
+
+impl<Kv1: Hash, Kv2: Hash> HashIndex<Kv1, Kv2> {
+	pub fn new(cont: &[(Kv1, Kv2)]) -> Self {
+		let mut index = HashIndex{
+			hasher: RandomState::new(),
+			hash_first: hash_of::<Kv1>,
+			hash_second: hash_of::<Kv2>,
+			first: HashMap::with_capacity(cont.len()),
+			second: HashMap::with_capacity(cont.len()),
+			stale: false,
+		};
+		index.rebuild(cont);
+		index
+	}
+}
+
+impl<Kv1, Kv2> HashIndex<Kv1, Kv2> {
+	pub fn hash_query<Q: Hash + ?Sized>(&self, key: &Q) -> u64 {
+		hash_of(&self.hasher, key)
+	}
+
+	/// An index of no pairs with the same hasher and hash functions
+	pub fn empty_like(&self) -> Self {
+		HashIndex{
+			hasher: self.hasher.clone(),
+			hash_first: self.hash_first,
+			hash_second: self.hash_second,
+			first: HashMap::new(),
+			second: HashMap::new(),
+			stale: false,
+		}
+	}
+
+	pub fn rebuild(&mut self, cont: &[(Kv1, Kv2)]) {
+		self.clear();
+		for idx in 0..cont.len() {
+			self.push(cont, idx);
+		}
+		self.stale = false;
+	}
+
+	pub fn clear(&mut self) {
+		self.first.clear();
+		self.second.clear();
+	}
+
+	/// Record that `cont[idx]` was just added
+	pub fn push(&mut self, cont: &[(Kv1, Kv2)], idx: usize) {
+		let (h1, h2) = ((self.hash_first)(&self.hasher, &cont[idx].0), (self.hash_second)(&self.hasher, &cont[idx].1));
+		self.first.entry(h1).or_default().push(idx);
+		self.second.entry(h2).or_default().push(idx);
+	}
+
+	/// Record that `cont[idx]` is about to be `swap_remove()`d
+	pub fn swap_remove(&mut self, cont: &[(Kv1, Kv2)], idx: usize) {
+		let last = cont.len() - 1;
+		let (h1, h2) = ((self.hash_first)(&self.hasher, &cont[idx].0), (self.hash_second)(&self.hasher, &cont[idx].1));
+		Self::remove_from(&mut self.first, h1, idx);
+		Self::remove_from(&mut self.second, h2, idx);
+
+		if idx != last {
+			let (h1, h2) = ((self.hash_first)(&self.hasher, &cont[last].0), (self.hash_second)(&self.hasher, &cont[last].1));
+			Self::relabel(&mut self.first, h1, last, idx);
+			Self::relabel(&mut self.second, h2, last, idx);
+		}
+	}
+
+	pub fn position_by_first<F: FnMut(&Kv1) -> bool>(&self, cont: &[(Kv1, Kv2)], hash: u64, mut pred: F) -> Option<usize> {
+		self.first.get(&hash).and_then(|bucket| bucket.iter().cloned().find(|&idx| pred(&cont[idx].0)))
+	}
+
+	pub fn position_by_second<F: FnMut(&Kv2) -> bool>(&self, cont: &[(Kv1, Kv2)], hash: u64, mut pred: F) -> Option<usize> {
+		self.second.get(&hash).and_then(|bucket| bucket.iter().cloned().find(|&idx| pred(&cont[idx].1)))
+	}
+
+	pub fn hash_first(&self, kv1: &Kv1) -> u64 {
+		(self.hash_first)(&self.hasher, kv1)
+	}
+
+	pub fn hash_second(&self, kv2: &Kv2) -> u64 {
+		(self.hash_second)(&self.hasher, kv2)
+	}
+
+	fn remove_from(buckets: &mut HashMap<u64, Vec<usize>>, hash: u64, idx: usize) {
+		let now_empty = match buckets.get_mut(&hash) {
+			Some(bucket) => {
+				bucket.retain(|&i| i != idx);
+				bucket.is_empty()
+			}
+			None => false,
+		};
+		if now_empty {
+			buckets.remove(&hash);
+		}
+	}
+
+	fn relabel(buckets: &mut HashMap<u64, Vec<usize>>, hash: u64, from: usize, to: usize) {
+		if let Some(bucket) = buckets.get_mut(&hash) {
+			for i in bucket.iter_mut().filter(|i| **i == from) {
+				*i = to;
+			}
+		}
+	}
+}
@@ -0,0 +1,505 @@
+//! A bidirectional map backed by two `HashMap`s.
+
+
+use core::borrow::Borrow;
+use core::fmt;
+use core::hash::{BuildHasher, Hash};
+use core::iter::{Extend, FromIterator};
+use std::collections::{hash_map, HashMap};
+use std::collections::hash_map::RandomState;
+use BidirMap;
+
+
+/// A bidirectional map with `O(1)` operations, backed by two `HashMap`s.
+///
+/// The interface mirrors that of `BidirMap`, so switching between the two is mostly mechanical, with these differences:
+///
+///   * iteration order is unspecified, rather than following insertion order,
+///   * iterators yield `(&Kv1, &Kv2)` rather than `&(Kv1, Kv2)`,
+///   * there are no `iter_mut()` and `get_mut_by_*()`, since a key changed in place would desynchronise the two tables.
+///
+/// Each K/V is stored twice, once as a key of each table, which is why inserting requires `Clone` on both of them.
+/// That trades memory for not needing reference counting (which would make the map `!Send` or slower)
+/// or an index indirection (which std's `HashMap` can't express without its unstable raw entry API).
+///
+/// Like `HashMap`, the hashing algorithm can be replaced via the `S` parameter, defaulting to `RandomState`;
+/// see `with_hasher()`.
+///
+/// Performance: `O(1)`, mostly.
+#[derive(Clone)]
+pub struct BidirHashMap<Kv1: Hash + Eq, Kv2: Hash + Eq, S = RandomState> {
+	first: HashMap<Kv1, Kv2, S>,
+	second: HashMap<Kv2, Kv1, S>,
+}
+
+impl<Kv1: Hash + Eq, Kv2: Hash + Eq> BidirHashMap<Kv1, Kv2, RandomState> {
+	/// Create a new empty instance of `BidirHashMap`
+	pub fn new() -> Self {
+		BidirHashMap{
+			first: HashMap::new(),
+			second: HashMap::new(),
+		}
+	}
+
+	/// Create a new empty instance of `BidirHashMap` with the specified capacity.
+	///
+	/// It will be able to hold at least `capacity` elements without reallocating.
+	pub fn with_capacity(capacity: usize) -> Self {
+		BidirHashMap{
+			first: HashMap::with_capacity(capacity),
+			second: HashMap::with_capacity(capacity),
+		}
+	}
+}
+
+impl<Kv1: Hash + Eq, Kv2: Hash + Eq, S: BuildHasher> BidirHashMap<Kv1, Kv2, S> {
+	/// Create a new empty instance of `BidirHashMap` which will use the given hash builder to hash both columns.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirHashMap;
+	/// use std::collections::hash_map::RandomState;
+	///
+	/// let mut map = BidirHashMap::with_hasher(RandomState::new());
+	/// map.insert(1, "a");
+	/// assert_eq!(map.get_by_second(&"a"), Some(&1));
+	/// ```
+	pub fn with_hasher(hash_builder: S) -> Self
+		where S: Clone,
+	{
+		BidirHashMap{
+			first: HashMap::with_hasher(hash_builder.clone()),
+			second: HashMap::with_hasher(hash_builder),
+		}
+	}
+
+	/// Create a new empty instance of `BidirHashMap` with the specified capacity, using the given hash builder to hash both columns.
+	///
+	/// It will be able to hold at least `capacity` elements without reallocating.
+	pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> Self
+		where S: Clone,
+	{
+		BidirHashMap{
+			first: HashMap::with_capacity_and_hasher(capacity, hash_builder.clone()),
+			second: HashMap::with_capacity_and_hasher(capacity, hash_builder),
+		}
+	}
+
+	/// Returns a reference to the map's `BuildHasher`.
+	pub fn hasher(&self) -> &S {
+		self.first.hasher()
+	}
+
+	/// Clears the map, removing all entries.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirHashMap;
+	///
+	/// let mut a = BidirHashMap::new();
+	/// a.insert(1, "a");
+	/// a.clear();
+	/// assert!(a.is_empty());
+	/// ```
+	pub fn clear(&mut self) {
+		self.first.clear();
+		self.second.clear();
+	}
+
+	/// Inserts a K/V-K/V pair into the map.
+	///
+	/// If the map did not have this K/V-K/V pair present, `None` is returned.
+	///
+	/// If the map did have this K/V-K/V pair present, it's updated and the old K/V-K/V pair is returned.
+	///
+	/// If `kv1` and `kv2` were each present in a different pair, both of those are removed and the one holding `kv1` is returned.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirHashMap;
+	///
+	/// let mut map = BidirHashMap::new();
+	/// map.insert(1, "a");
+	/// map.insert(2, "b");
+	/// assert_eq!(map.insert(1, "b"), Some((1, "a")));
+	/// assert_eq!(map.len(), 1);
+	/// assert_eq!(map.get_by_second(&"b"), Some(&1));
+	/// ```
+	pub fn insert(&mut self, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)>
+		where Kv1: Clone,
+		      Kv2: Clone,
+	{
+		let by_first = self.remove_by_first(&kv1);
+		let by_second = self.remove_by_second(&kv2);
+
+		self.first.insert(kv1.clone(), kv2.clone());
+		self.second.insert(kv2, kv1);
+
+		by_first.or(by_second)
+	}
+
+	/// Gets an iterator over the entries of the map, in arbitrary order.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirHashMap;
+	///
+	/// let mut map = BidirHashMap::new();
+	/// map.insert(1, "a");
+	/// map.insert(2, "b");
+	/// map.insert(3, "c");
+	///
+	/// for kv in map.iter() {
+	///     println!("{}: {}", kv.0, kv.1);
+	/// }
+	///
+	/// let mut pairs: Vec<_> = map.iter().collect();
+	/// pairs.sort();
+	/// assert_eq!(pairs[0], (&1, &"a"));
+	/// ```
+	pub fn iter<'s>(&'s self) -> Iter<'s, Kv1, Kv2> {
+		Iter{
+			iter: self.first.iter(),
+		}
+	}
+
+	/// Gets an iterator over the first K/V of the map, in arbitrary order.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirHashMap;
+	///
+	/// let mut a = BidirHashMap::new();
+	/// a.insert(1, "a");
+	/// a.insert(2, "b");
+	///
+	/// let mut keys: Vec<_> = a.first_col().cloned().collect();
+	/// keys.sort();
+	/// assert_eq!(keys, [1, 2]);
+	/// ```
+	pub fn first_col<'s>(&'s self) -> FirstColumn<'s, Kv1, Kv2> {
+		FirstColumn{
+			iter: self.first.keys(),
+		}
+	}
+
+	/// Gets an iterator over the second K/V of the map, in arbitrary order.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirHashMap;
+	///
+	/// let mut a = BidirHashMap::new();
+	/// a.insert(1, "a");
+	/// a.insert(2, "b");
+	///
+	/// let mut keys: Vec<_> = a.second_col().cloned().collect();
+	/// keys.sort();
+	/// assert_eq!(keys, ["a", "b"]);
+	/// ```
+	pub fn second_col<'s>(&'s self) -> SecondColumn<'s, Kv1, Kv2> {
+		SecondColumn{
+			iter: self.second.keys(),
+		}
+	}
+
+	/// Returns the number of elements in the map.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirHashMap;
+	///
+	/// let mut a = BidirHashMap::new();
+	/// assert_eq!(a.len(), 0);
+	/// a.insert(1, "a");
+	/// assert_eq!(a.len(), 1);
+	/// ```
+	pub fn len(&self) -> usize {
+		self.first.len()
+	}
+
+	/// Returns true if the map contains no elements.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirHashMap;
+	///
+	/// let mut a = BidirHashMap::new();
+	/// assert!(a.is_empty());
+	/// a.insert(1, "a");
+	/// assert!(!a.is_empty());
+	/// ```
+	pub fn is_empty(&self) -> bool {
+		self.first.is_empty()
+	}
+
+
+	/// Returns a reference to the second K/V corresponding to the first K/V.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirHashMap;
+	///
+	/// let mut map = BidirHashMap::new();
+	/// map.insert(1, "a");
+	/// assert_eq!(map.get_by_first(&1), Some(&"a"));
+	/// assert_eq!(map.get_by_first(&2), None);
+	/// ```
+	pub fn get_by_first<Q>(&self, key: &Q) -> Option<&Kv2>
+		where Kv1: Borrow<Q>,
+		      Q  : Hash + Eq + ?Sized,
+	{
+		self.first.get(key)
+	}
+
+	/// Returns a reference to the first K/V corresponding to the second K/V.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirHashMap;
+	///
+	/// let mut map = BidirHashMap::new();
+	/// map.insert(1, "a");
+	/// assert_eq!(map.get_by_second(&"a"), Some(&1));
+	/// assert_eq!(map.get_by_second(&"b"), None);
+	/// ```
+	pub fn get_by_second<Q>(&self, key: &Q) -> Option<&Kv1>
+		where Kv2: Borrow<Q>,
+		      Q  : Hash + Eq + ?Sized,
+	{
+		self.second.get(key)
+	}
+
+	/// Check if the map contains the first K/V
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirHashMap;
+	///
+	/// let mut map = BidirHashMap::new();
+	/// map.insert(1, "a");
+	/// assert_eq!(map.contains_first_key(&1), true);
+	/// assert_eq!(map.contains_first_key(&2), false);
+	/// ```
+	pub fn contains_first_key<Q>(&self, key: &Q) -> bool
+		where Kv1: Borrow<Q>,
+		      Q  : Hash + Eq + ?Sized,
+	{
+		self.first.contains_key(key)
+	}
+
+	/// Check if the map contains the second K/V
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirHashMap;
+	///
+	/// let mut map = BidirHashMap::new();
+	/// map.insert(1, "a");
+	/// assert_eq!(map.contains_second_key(&"a"), true);
+	/// assert_eq!(map.contains_second_key(&"b"), false);
+	/// ```
+	pub fn contains_second_key<Q>(&self, key: &Q) -> bool
+		where Kv2: Borrow<Q>,
+		      Q  : Hash + Eq + ?Sized,
+	{
+		self.second.contains_key(key)
+	}
+
+	/// Removes the pair corresponding to the first K/V from the map, returning it if the key was previously in the map.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirHashMap;
+	///
+	/// let mut map = BidirHashMap::new();
+	/// map.insert(1, "a");
+	/// assert_eq!(map.remove_by_first(&1), Some((1, "a")));
+	/// assert_eq!(map.remove_by_first(&1), None);
+	/// ```
+	pub fn remove_by_first<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv1: Borrow<Q>,
+		      Q  : Hash + Eq + ?Sized,
+	{
+		let (kv1, kv2) = self.first.remove_entry(key)?;
+		self.second.remove(&kv2);
+		Some((kv1, kv2))
+	}
+
+	/// Removes the pair corresponding to the second K/V from the map, returning it if the key was previously in the map.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirHashMap;
+	///
+	/// let mut map = BidirHashMap::new();
+	/// map.insert(1, "a");
+	/// assert_eq!(map.remove_by_second(&"a"), Some((1, "a")));
+	/// assert_eq!(map.remove_by_second(&"b"), None);
+	/// ```
+	pub fn remove_by_second<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv2: Borrow<Q>,
+		      Q  : Hash + Eq + ?Sized,
+	{
+		let (kv2, kv1) = self.second.remove_entry(key)?;
+		self.first.remove(&kv1);
+		Some((kv1, kv2))
+	}
+}
+
+
+impl<Kv1: Hash + Eq + fmt::Debug, Kv2: Hash + Eq + fmt::Debug, S> fmt::Debug for BidirHashMap<Kv1, Kv2, S> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("BidirHashMap").field("first", &self.first).field("second", &self.second).finish()
+	}
+}
+
+impl<Kv1: Hash + Eq, Kv2: Hash + Eq, S: BuildHasher> PartialEq for BidirHashMap<Kv1, Kv2, S> {
+	fn eq(&self, other: &Self) -> bool {
+		// The second table is determined by the first
+		self.first == other.first
+	}
+}
+
+impl<Kv1: Hash + Eq, Kv2: Hash + Eq, S: BuildHasher> Eq for BidirHashMap<Kv1, Kv2, S> {}
+
+impl<Kv1: Hash + Eq, Kv2: Hash + Eq, S: BuildHasher + Default> Default for BidirHashMap<Kv1, Kv2, S> {
+	fn default() -> Self {
+		BidirHashMap{
+			first: HashMap::default(),
+			second: HashMap::default(),
+		}
+	}
+}
+
+impl<Kv1: Hash + Eq, Kv2: Hash + Eq, S> IntoIterator for BidirHashMap<Kv1, Kv2, S> {
+	type Item = (Kv1, Kv2);
+	type IntoIter = IntoIter<Kv1, Kv2>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		IntoIter{
+			iter: self.first.into_iter(),
+		}
+	}
+}
+
+impl<'a, Kv1: Hash + Eq, Kv2: Hash + Eq, S: BuildHasher> IntoIterator for &'a BidirHashMap<Kv1, Kv2, S> {
+	type Item = (&'a Kv1, &'a Kv2);
+	type IntoIter = Iter<'a, Kv1, Kv2>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+impl<Kv1: Hash + Eq + Clone, Kv2: Hash + Eq + Clone, S: BuildHasher + Default> FromIterator<(Kv1, Kv2)> for BidirHashMap<Kv1, Kv2, S> {
+	fn from_iter<T: IntoIterator<Item=(Kv1, Kv2)>>(iter: T) -> Self {
+		let mut map = BidirHashMap::default();
+		map.extend(iter);
+		map
+	}
+}
+
+impl<Kv1: Hash + Eq + Clone, Kv2: Hash + Eq + Clone, S: BuildHasher> Extend<(Kv1, Kv2)> for BidirHashMap<Kv1, Kv2, S> {
+	fn extend<T: IntoIterator<Item=(Kv1, Kv2)>>(&mut self, iter: T) {
+		for (kv1, kv2) in iter {
+			self.insert(kv1, kv2);
+		}
+	}
+}
+
+impl<'a, Kv1: Hash + Eq + Clone, Kv2: Hash + Eq + Clone, S: BuildHasher> Extend<(&'a Kv1, &'a Kv2)> for BidirHashMap<Kv1, Kv2, S> {
+	fn extend<T: IntoIterator<Item=(&'a Kv1, &'a Kv2)>>(&mut self, iter: T) {
+		for (kv1, kv2) in iter {
+			self.insert(kv1.clone(), kv2.clone());
+		}
+	}
+}
+
+impl<Kv1: Hash + Eq + Clone, Kv2: Hash + Eq + Clone> From<BidirMap<Kv1, Kv2>> for BidirHashMap<Kv1, Kv2, RandomState> {
+	fn from(map: BidirMap<Kv1, Kv2>) -> Self {
+		map.into_iter().collect()
+	}
+}
+
+impl<Kv1: Hash + Eq, Kv2: Hash + Eq, S> From<BidirHashMap<Kv1, Kv2, S>> for BidirMap<Kv1, Kv2> {
+	/// The resulting map's order is the `BidirHashMap`'s arbitrary iteration order.
+	fn from(map: BidirHashMap<Kv1, Kv2, S>) -> Self {
+		// Both columns are already unique, so there's nothing to check
+		map.into_iter().collect()
+	}
+}
+
+
+/// An iterator over the K/V pairs contained in a `BidirHashMap`.
+///
+/// See documentation of `BidirHashMap::iter()` for more.
+pub struct Iter<'a, Kv1: 'a, Kv2: 'a> {
+	iter: hash_map::Iter<'a, Kv1, Kv2>,
+}
+
+impl<'a, Kv1, Kv2> Iterator for Iter<'a, Kv1, Kv2> {
+	type Item = (&'a Kv1, &'a Kv2);
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next()
+	}
+}
+
+
+/// An iterator the first set of K/Vs in a `BidirHashMap`.
+///
+/// See documentation of `BidirHashMap::first_col()` for more.
+pub struct FirstColumn<'a, Kv1: 'a, Kv2: 'a> {
+	iter: hash_map::Keys<'a, Kv1, Kv2>,
+}
+
+impl<'a, Kv1, Kv2> Iterator for FirstColumn<'a, Kv1, Kv2> {
+	type Item = &'a Kv1;
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next()
+	}
+}
+
+
+/// An iterator the second set of K/Vs in a `BidirHashMap`.
+///
+/// See documentation of `BidirHashMap::second_col()` for more.
+pub struct SecondColumn<'a, Kv1: 'a, Kv2: 'a> {
+	iter: hash_map::Keys<'a, Kv2, Kv1>,
+}
+
+impl<'a, Kv1, Kv2> Iterator for SecondColumn<'a, Kv1, Kv2> {
+	type Item = &'a Kv2;
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next()
+	}
+}
+
+
+/// An owning iterator over the K/V pairs of a `BidirHashMap`.
+///
+/// See documentation of `BidirHashMap::into_iter()` for more.
+pub struct IntoIter<Kv1, Kv2> {
+	iter: hash_map::IntoIter<Kv1, Kv2>,
+}
+
+impl<Kv1, Kv2> Iterator for IntoIter<Kv1, Kv2> {
+	type Item = (Kv1, Kv2);
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next()
+	}
+}
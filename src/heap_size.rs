@@ -0,0 +1,145 @@
+//! Measuring the heap memory a `BidirMap` owns, see `HeapSize`.
+
+
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::mem;
+use {BidirMap, TotalOrd};
+
+
+/// A K/V type that can report the heap memory it owns.
+///
+/// `heap_size()` counts the bytes of the allocations owned by the value, not the value itself,
+/// recursing into whatever they hold; an allocation's spare capacity is counted with it, since it's owned all the same.
+/// Types without any allocations of their own get the default, so for them the impl is empty:
+///
+/// ```
+/// use bidir_map::HeapSize;
+///
+/// #[derive(PartialEq)]
+/// struct Point(i32, i32);
+///
+/// impl HeapSize for Point {}
+/// ```
+///
+/// Borrowed data, behind references, counts for nothing;
+/// data shared behind `Rc`s or `Arc`s isn't the value's alone, so it's not implemented for them.
+pub trait HeapSize {
+	/// Returns the number of bytes allocated on the heap by the value, and owned by it.
+	fn heap_size(&self) -> usize {
+		0
+	}
+}
+
+macro_rules! impl_heap_size_none {
+	($($t:ty),*) => {$(
+		impl HeapSize for $t {}
+	)*};
+}
+
+impl_heap_size_none!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, char, bool, ());
+
+impl<T: ?Sized> HeapSize for &T {}
+
+impl HeapSize for String {
+	fn heap_size(&self) -> usize {
+		self.capacity()
+	}
+}
+
+impl HeapSize for Box<str> {
+	fn heap_size(&self) -> usize {
+		self.len()
+	}
+}
+
+impl<'a> HeapSize for Cow<'a, str> {
+	fn heap_size(&self) -> usize {
+		match *self {
+			Cow::Borrowed(_) => 0,
+			Cow::Owned(ref owned) => owned.heap_size(),
+		}
+	}
+}
+
+impl<T: HeapSize> HeapSize for Vec<T> {
+	fn heap_size(&self) -> usize {
+		self.capacity() * mem::size_of::<T>() + self.iter().map(HeapSize::heap_size).sum::<usize>()
+	}
+}
+
+impl<T: HeapSize> HeapSize for Box<T> {
+	fn heap_size(&self) -> usize {
+		mem::size_of::<T>() + (**self).heap_size()
+	}
+}
+
+impl<T: HeapSize> HeapSize for Option<T> {
+	fn heap_size(&self) -> usize {
+		self.as_ref().map_or(0, HeapSize::heap_size)
+	}
+}
+
+impl<T: HeapSize, const N: usize> HeapSize for [T; N] {
+	fn heap_size(&self) -> usize {
+		self.iter().map(HeapSize::heap_size).sum()
+	}
+}
+
+impl<A: HeapSize, B: HeapSize> HeapSize for (A, B) {
+	fn heap_size(&self) -> usize {
+		self.0.heap_size() + self.1.heap_size()
+	}
+}
+
+impl<F> HeapSize for TotalOrd<F> {}
+
+impl<Kv1: HeapSize, Kv2: HeapSize> HeapSize for BidirMap<Kv1, Kv2> {
+	fn heap_size(&self) -> usize {
+		self.heap_size_with_spare()
+	}
+}
+
+
+impl<Kv1: HeapSize, Kv2: HeapSize> BidirMap<Kv1, Kv2> {
+	/// Returns the number of bytes on the heap taken by the pairs and owned by their K/Vs, as reported by `HeapSize`.
+	///
+	/// The backing vector's spare capacity isn't counted, see `heap_size_with_spare()`,
+	/// nor are the indexes built by `build_index()` or `build_sorted_indexes()`.
+	///
+	/// Performance: `O(n)`, or as much as the K/Vs' `heap_size()`s take.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	/// use std::mem;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert(1u32, String::with_capacity(100));
+	/// assert_eq!(map.heap_size(), mem::size_of::<(u32, String)>() + 100);
+	/// ```
+	pub fn heap_size(&self) -> usize {
+		self.cont.len() * mem::size_of::<(Kv1, Kv2)>() + self.cont.iter().map(HeapSize::heap_size).sum::<usize>()
+	}
+
+	/// Returns the number of bytes on the heap owned by the map, being `heap_size()` and the backing vector's spare capacity.
+	///
+	/// `shrink_to_fit()` gives up as much of the spare capacity as it can.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::with_capacity(10);
+	/// map.insert(1u64, 2u64);
+	/// assert_eq!(map.heap_size(), 16);
+	/// assert_eq!(map.heap_size_with_spare(), map.capacity() * 16);
+	/// ```
+	pub fn heap_size_with_spare(&self) -> usize {
+		self.heap_size() + (self.cont.capacity() - self.cont.len()) * mem::size_of::<(Kv1, Kv2)>()
+	}
+}
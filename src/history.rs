@@ -0,0 +1,321 @@
+//! A `BidirMap` wrapper recording its edits to be undone and redone, see `BidirMap::with_history()`.
+
+
+use core::borrow::Borrow;
+use core::mem;
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use {BidirMap, Iter};
+
+
+/// A `BidirMap` keeping a bounded history of its edits, which `undo()` reverts and `redo()` reapplies.
+///
+/// Every editing method records one step of history, however many pairs it changes:
+/// an `insert()` along with the pairs it displaced, a whole `extend()`, `retain()` or `clear()`.
+/// Undoing restores the map exactly as it was before the step, iteration order included.
+/// Edits that change nothing, like removing a K/V that isn't in the map, aren't recorded.
+///
+/// Once the history holds `depth` steps, recording another forgets the oldest.
+/// Undone steps can be redone until the next edit, which forgets them, as in any editor.
+///
+/// The history owns the pairs removed by the steps it holds, so the pairs handed back by `insert()` and `remove_by_*()` are clones,
+/// and they, like `extend()`, require `Clone` on the K/Vs; `undo()`, `redo()`, `retain()` and `clear()` don't.
+///
+/// # Examples
+///
+/// ```
+/// use bidir_map::BidirMap;
+///
+/// let mut map = BidirMap::with_history(10);
+/// map.insert(1, "a");
+/// map.insert(2, "b");
+/// map.insert(1, "b");
+/// assert_eq!(map.iter().collect::<Vec<_>>(), [&(1, "b")]);
+///
+/// assert!(map.undo());
+/// assert_eq!(map.iter().collect::<Vec<_>>(), [&(1, "a"), &(2, "b")]);
+/// assert!(map.redo());
+/// assert_eq!(map.len(), 1);
+///
+/// assert!(map.undo());
+/// map.insert(3, "c");
+/// assert!(!map.redo());
+/// assert_eq!(map.history_len(), 3);
+/// ```
+#[derive(Clone, Debug)]
+pub struct HistoryBidirMap<Kv1: PartialEq, Kv2: PartialEq> {
+	map: BidirMap<Kv1, Kv2>,
+	depth: usize,
+	/// The steps to undo, the latest last, each its edits' inverses in the order they were made
+	undo: VecDeque<Vec<Edit<Kv1, Kv2>>>,
+	/// The steps undone, to redo, the latest undone last
+	redo: Vec<Vec<Edit<Kv1, Kv2>>>,
+}
+
+/// An edit of the map's pairs, each having another as its inverse, see `Edit::apply()`
+#[derive(Clone, Debug)]
+enum Edit<Kv1, Kv2> {
+	Push((Kv1, Kv2)),
+	Pop,
+	/// Pushes the pair and swaps it to the index, undoing a `SwapRemove` there
+	SwapInsert(usize, (Kv1, Kv2)),
+	SwapRemove(usize),
+	Insert(usize, (Kv1, Kv2)),
+	Remove(usize),
+	/// Puts back all pairs, undoing a `Clear`
+	Restore(Vec<(Kv1, Kv2)>),
+	Clear,
+}
+
+impl<Kv1, Kv2> Edit<Kv1, Kv2> {
+	/// Applies the edit to the pairs, returning its inverse
+	fn apply(self, cont: &mut Vec<(Kv1, Kv2)>) -> Self {
+		match self {
+			Edit::Push(kvs) => {
+				cont.push(kvs);
+				Edit::Pop
+			}
+			Edit::Pop => Edit::Push(cont.pop().unwrap()),
+			Edit::SwapInsert(idx, kvs) => {
+				cont.push(kvs);
+				let last = cont.len() - 1;
+				cont.swap(idx, last);
+				Edit::SwapRemove(idx)
+			}
+			Edit::SwapRemove(idx) => Edit::SwapInsert(idx, cont.swap_remove(idx)),
+			Edit::Insert(idx, kvs) => {
+				cont.insert(idx, kvs);
+				Edit::Remove(idx)
+			}
+			Edit::Remove(idx) => Edit::Insert(idx, cont.remove(idx)),
+			Edit::Restore(kvs) => {
+				*cont = kvs;
+				Edit::Clear
+			}
+			Edit::Clear => Edit::Restore(mem::take(cont)),
+		}
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
+	/// Create a new empty `HistoryBidirMap`, remembering its last `depth` steps of edits.
+	pub fn with_history(depth: usize) -> HistoryBidirMap<Kv1, Kv2> {
+		HistoryBidirMap{
+			map: BidirMap::new(),
+			depth,
+			undo: VecDeque::new(),
+			redo: Vec::new(),
+		}
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> HistoryBidirMap<Kv1, Kv2> {
+	/// Reverts the latest step of edits not yet undone, returning whether there was one.
+	pub fn undo(&mut self) -> bool {
+		match self.undo.pop_back() {
+			Some(step) => {
+				let redo = self.apply(step);
+				self.redo.push(redo);
+				true
+			}
+			None => false,
+		}
+	}
+
+	/// Reapplies the latest step undone, returning whether there was one that no edit has forgotten since.
+	pub fn redo(&mut self) -> bool {
+		match self.redo.pop() {
+			Some(step) => {
+				let undo = self.apply(step);
+				self.undo.push_back(undo);
+				true
+			}
+			None => false,
+		}
+	}
+
+	/// Returns the number of steps that can be undone.
+	pub fn history_len(&self) -> usize {
+		self.undo.len()
+	}
+
+	/// Returns the number of undone steps that can be redone.
+	pub fn redo_len(&self) -> usize {
+		self.redo.len()
+	}
+
+	/// Forgets all steps to undo and redo, keeping the map as it is.
+	pub fn clear_history(&mut self) {
+		self.undo.clear();
+		self.redo.clear();
+	}
+
+	/// Unwraps the underlying `BidirMap`, forgetting the history.
+	pub fn into_inner(self) -> BidirMap<Kv1, Kv2> {
+		self.map
+	}
+
+	/// Gets a reference to the underlying `BidirMap`.
+	pub fn inner(&self) -> &BidirMap<Kv1, Kv2> {
+		&self.map
+	}
+
+	/// Returns a reference to the second K/V corresponding to the first K/V.
+	pub fn get_by_first<Q>(&self, key: &Q) -> Option<&Kv2>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.get_by_first(key)
+	}
+
+	/// Returns a reference to the first K/V corresponding to the second K/V.
+	pub fn get_by_second<Q>(&self, key: &Q) -> Option<&Kv1>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.get_by_second(key)
+	}
+
+	/// Check if the map contains the first K/V.
+	pub fn contains_first_key<Q>(&self, key: &Q) -> bool
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.contains_first_key(key)
+	}
+
+	/// Check if the map contains the second K/V.
+	pub fn contains_second_key<Q>(&self, key: &Q) -> bool
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.contains_second_key(key)
+	}
+
+	/// Gets an iterator over the entries of the map.
+	pub fn iter<'s>(&'s self) -> Iter<'s, Kv1, Kv2> {
+		self.map.iter()
+	}
+
+	/// Returns the number of elements in the map.
+	pub fn len(&self) -> usize {
+		self.map.len()
+	}
+
+	/// Returns true if the map contains no elements.
+	pub fn is_empty(&self) -> bool {
+		self.map.is_empty()
+	}
+
+	/// Retains only the pairs for which `f` returns `true`, as `BidirMap::retain()` does, recording the removals as one step.
+	///
+	/// Performance: `O(n·r)`, for `r` pairs removed.
+	pub fn retain<F: FnMut(&Kv1, &Kv2) -> bool>(&mut self, mut f: F) {
+		let mut step = Vec::new();
+		// Back to front, so that the removals don't move the pairs yet to be looked at
+		for idx in (0..self.map.cont.len()).rev() {
+			if !f(&self.map.cont[idx].0, &self.map.cont[idx].1) {
+				let kvs = self.map.shift_remove_at(idx);
+				step.push(Edit::Insert(idx, kvs));
+			}
+		}
+		self.record(step);
+	}
+
+	/// Clears the map, removing all entries, recorded as one step.
+	pub fn clear(&mut self) {
+		if !self.map.is_empty() {
+			let kvs = mem::take(&mut self.map.cont);
+			self.map.rebuild_index();
+			self.record(Vec::from([Edit::Restore(kvs)]));
+		}
+	}
+
+	/// Applies the step's edits latest first, returning their inverses in the order applied, as the step to take it back
+	fn apply(&mut self, step: Vec<Edit<Kv1, Kv2>>) -> Vec<Edit<Kv1, Kv2>> {
+		let inverse = step.into_iter().rev().map(|edit| edit.apply(&mut self.map.cont)).collect();
+		self.map.rebuild_index();
+		inverse
+	}
+
+	/// Records the inverses of a step of edits, unless it changed nothing
+	fn record(&mut self, step: Vec<Edit<Kv1, Kv2>>) {
+		if step.is_empty() {
+			return;
+		}
+		self.redo.clear();
+		if self.depth == 0 {
+			return;
+		}
+		if self.undo.len() == self.depth {
+			self.undo.pop_front();
+		}
+		self.undo.push_back(step);
+	}
+}
+
+impl<Kv1: PartialEq + Clone, Kv2: PartialEq + Clone> HistoryBidirMap<Kv1, Kv2> {
+	/// Inserts a K/V-K/V pair into the map, as `BidirMap::insert()` does, recording it along with the pairs it displaced.
+	pub fn insert(&mut self, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)> {
+		let mut step = Vec::new();
+		let displaced = self.insert_recorded(kv1, kv2, &mut step);
+		self.record(step);
+		displaced
+	}
+
+	/// Inserts all pairs, as `BidirMap::extend()` does, recording them as one step.
+	pub fn extend<I: IntoIterator<Item=(Kv1, Kv2)>>(&mut self, iter: I) {
+		let mut step = Vec::new();
+		for (kv1, kv2) in iter {
+			self.insert_recorded(kv1, kv2, &mut step);
+		}
+		self.record(step);
+	}
+
+	/// Removes the pair with the first K/V from the map, returning it if the key was previously in the map.
+	pub fn remove_by_first<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		let idx = self.map.find_first(key);
+		idx.map(|idx| self.swap_remove_recorded(idx))
+	}
+
+	/// Removes the pair with the second K/V from the map, returning it if the key was previously in the map.
+	pub fn remove_by_second<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		let idx = self.map.find_second(key);
+		idx.map(|idx| self.swap_remove_recorded(idx))
+	}
+
+	fn insert_recorded(&mut self, kv1: Kv1, kv2: Kv2, step: &mut Vec<Edit<Kv1, Kv2>>) -> Option<(Kv1, Kv2)> {
+		let by_first = self.map.position_of_first(&kv1).map(|idx| {
+			let kvs = self.map.swap_remove_at(idx);
+			step.push(Edit::SwapInsert(idx, kvs.clone()));
+			kvs
+		});
+		let by_second = self.map.position_of_second(&kv2).map(|idx| {
+			let kvs = self.map.swap_remove_at(idx);
+			step.push(Edit::SwapInsert(idx, kvs.clone()));
+			kvs
+		});
+		self.map.push(kv1, kv2);
+		step.push(Edit::Pop);
+		by_first.or(by_second)
+	}
+
+	fn swap_remove_recorded(&mut self, idx: usize) -> (Kv1, Kv2) {
+		let kvs = self.map.swap_remove_at(idx);
+		self.record(Vec::from([Edit::SwapInsert(idx, kvs.clone())]));
+		kvs
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> Default for HistoryBidirMap<Kv1, Kv2> {
+	/// A map remembering 100 steps of edits
+	fn default() -> Self {
+		BidirMap::with_history(100)
+	}
+}
@@ -0,0 +1,203 @@
+//! An interner mapping values to dense `usize` ids.
+
+
+use core::borrow::Borrow;
+use core::iter::{Enumerate, Extend, FromIterator};
+use core::slice;
+use alloc::vec::Vec;
+use BidirMap;
+
+
+/// A bidirectional map between values and dense ids, as used for string interning or node numbering.
+///
+/// Each value is assigned the next free id, `len()`, the first time it's interned, and keeps it for the map's lifetime:
+/// the ids are always exactly `0..len()`.
+/// To uphold that, there's no way to insert a pair with a chosen id, nor to remove single values.
+///
+/// Performance: `O(1)` `resolve()`, `O(n)` `lookup()` and `get_or_intern()`.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct BidirIndexMap<T: PartialEq> {
+	/// The value with id `i` is at index `i`
+	values: Vec<T>,
+}
+
+impl<T: PartialEq> BidirIndexMap<T> {
+	/// Create a new empty instance of `BidirIndexMap`
+	pub fn new() -> Self {
+		BidirIndexMap{
+			values: Vec::new(),
+		}
+	}
+
+	/// Create a new empty instance of `BidirIndexMap` with the specified capacity.
+	///
+	/// It will be able to hold at least `capacity` values without reallocating.
+	pub fn with_capacity(capacity: usize) -> Self {
+		BidirIndexMap{
+			values: Vec::with_capacity(capacity),
+		}
+	}
+
+	/// Returns the id of the value, assigning it the next free one if it wasn't interned yet.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirIndexMap;
+	///
+	/// let mut ids = BidirIndexMap::new();
+	/// assert_eq!(ids.get_or_intern("a"), 0);
+	/// assert_eq!(ids.get_or_intern("b"), 1);
+	/// assert_eq!(ids.get_or_intern("a"), 0);
+	/// assert_eq!(ids.len(), 2);
+	/// ```
+	pub fn get_or_intern(&mut self, value: T) -> usize {
+		match self.lookup(&value) {
+			Some(id) => id,
+			None => {
+				self.values.push(value);
+				self.values.len() - 1
+			}
+		}
+	}
+
+	/// Returns the value with the specified id.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirIndexMap;
+	///
+	/// let mut ids = BidirIndexMap::new();
+	/// let id = ids.get_or_intern("a");
+	/// assert_eq!(ids.resolve(id), Some(&"a"));
+	/// assert_eq!(ids.resolve(id + 1), None);
+	/// ```
+	pub fn resolve(&self, id: usize) -> Option<&T> {
+		self.values.get(id)
+	}
+
+	/// Returns the id of the value, if it was interned.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirIndexMap;
+	///
+	/// let mut ids = BidirIndexMap::new();
+	/// ids.get_or_intern("a".to_string());
+	/// assert_eq!(ids.lookup("a"), Some(0));
+	/// assert_eq!(ids.lookup("b"), None);
+	/// ```
+	pub fn lookup<Q>(&self, value: &Q) -> Option<usize>
+		where T: Borrow<Q>,
+		      Q: PartialEq + ?Sized,
+	{
+		self.values.iter().position(|v| *value == *v.borrow())
+	}
+
+	/// Check if the value was interned.
+	pub fn contains<Q>(&self, value: &Q) -> bool
+		where T: Borrow<Q>,
+		      Q: PartialEq + ?Sized,
+	{
+		self.lookup(value).is_some()
+	}
+
+	/// Gets an iterator over the ids and their values, by ascending id.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirIndexMap;
+	///
+	/// let ids: BidirIndexMap<_> = vec!["a", "b", "a"].into_iter().collect();
+	/// let pairs: Vec<_> = ids.iter().collect();
+	/// assert_eq!(pairs, [(0, &"a"), (1, &"b")]);
+	/// ```
+	pub fn iter<'s>(&'s self) -> Iter<'s, T> {
+		Iter{
+			iter: self.values.iter().enumerate(),
+		}
+	}
+
+	/// Gets the values as a slice, indexed by their ids.
+	pub fn as_slice(&self) -> &[T] {
+		&self.values
+	}
+
+	/// Returns the number of values interned, which is also the next id to be assigned.
+	pub fn len(&self) -> usize {
+		self.values.len()
+	}
+
+	/// Returns true if no values were interned.
+	pub fn is_empty(&self) -> bool {
+		self.values.is_empty()
+	}
+
+	/// Clears the map, so that ids start from 0 again.
+	pub fn clear(&mut self) {
+		self.values.clear()
+	}
+
+	/// Unwraps the values, indexed by their ids.
+	pub fn into_vec(self) -> Vec<T> {
+		self.values
+	}
+}
+
+
+impl<T: PartialEq> Default for BidirIndexMap<T> {
+	fn default() -> Self {
+		BidirIndexMap::new()
+	}
+}
+
+impl<T: PartialEq> FromIterator<T> for BidirIndexMap<T> {
+	/// Interns all values in order, so the first distinct one gets id 0, and so on.
+	fn from_iter<I: IntoIterator<Item=T>>(iter: I) -> Self {
+		let mut map = BidirIndexMap::new();
+		map.extend(iter);
+		map
+	}
+}
+
+impl<T: PartialEq> Extend<T> for BidirIndexMap<T> {
+	fn extend<I: IntoIterator<Item=T>>(&mut self, iter: I) {
+		for value in iter {
+			self.get_or_intern(value);
+		}
+	}
+}
+
+impl<T: PartialEq> From<BidirIndexMap<T>> for BidirMap<T, usize> {
+	/// The pairs are ordered by id.
+	fn from(map: BidirIndexMap<T>) -> Self {
+		map.values.into_iter().enumerate().map(|(id, value)| (value, id)).collect()
+	}
+}
+
+impl<'a, T: PartialEq> IntoIterator for &'a BidirIndexMap<T> {
+	type Item = (usize, &'a T);
+	type IntoIter = Iter<'a, T>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+
+/// An iterator over the ids and values of a `BidirIndexMap`.
+///
+/// See documentation of `BidirIndexMap::iter()` for more.
+pub struct Iter<'a, T: 'a> {
+	iter: Enumerate<slice::Iter<'a, T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+	type Item = (usize, &'a T);
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next()
+	}
+}
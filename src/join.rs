@@ -0,0 +1,110 @@
+//! Joining two `BidirMap`s sharing their first column.
+
+
+use core::slice;
+use BidirMap;
+
+
+impl<Kv1: PartialEq, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
+	/// Gets an iterator over the pairs of this map, in iteration order,
+	/// each with the second K/V of `other` for the same first K/V, if `other` has one.
+	///
+	/// Performance: `O(n·m)`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let names: BidirMap<_, _> = vec![(1, "one"), (2, "two")].into_iter().collect();
+	/// let paths: BidirMap<_, _> = vec![(2, "/2"), (3, "/3")].into_iter().collect();
+	///
+	/// assert_eq!(names.join_by_first(&paths).collect::<Vec<_>>(), [(&1, &"one", None), (&2, &"two", Some(&"/2"))]);
+	/// ```
+	pub fn join_by_first<'a, Kv3: PartialEq>(&'a self, other: &'a BidirMap<Kv1, Kv3>) -> JoinByFirst<'a, Kv1, Kv2, Kv3> {
+		JoinByFirst{
+			iter: self.cont.iter(),
+			other,
+		}
+	}
+
+	/// Gets an iterator over the first K/Vs in both this map and `other`, in this map's iteration order,
+	/// each with the second K/Vs from both maps.
+	///
+	/// Performance: `O(n·m)`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let names: BidirMap<_, _> = vec![(1, "one"), (2, "two")].into_iter().collect();
+	/// let paths: BidirMap<_, _> = vec![(2, "/2"), (3, "/3")].into_iter().collect();
+	///
+	/// assert_eq!(names.inner_join_by_first(&paths).collect::<Vec<_>>(), [(&2, &"two", &"/2")]);
+	/// ```
+	pub fn inner_join_by_first<'a, Kv3: PartialEq>(&'a self, other: &'a BidirMap<Kv1, Kv3>) -> InnerJoinByFirst<'a, Kv1, Kv2, Kv3> {
+		InnerJoinByFirst{
+			join: self.join_by_first(other),
+		}
+	}
+
+	/// Gets an iterator over the first K/Vs in both this map and `other`, in this map's iteration order.
+	///
+	/// Those only in this map are those `join_by_first()` yields without a third K/V;
+	/// those only in `other`, those `other.join_by_first(self)` does.
+	///
+	/// Performance: `O(n·m)`.
+	pub fn common_first_keys<'a, Kv3: PartialEq>(&'a self, other: &'a BidirMap<Kv1, Kv3>) -> CommonFirstKeys<'a, Kv1, Kv2, Kv3> {
+		CommonFirstKeys{
+			join: self.inner_join_by_first(other),
+		}
+	}
+}
+
+
+/// An iterator over the pairs of a `BidirMap`, with the matching second K/Vs of another, if any.
+///
+/// See documentation of `BidirMap::join_by_first()` for more.
+pub struct JoinByFirst<'a, Kv1: PartialEq + 'a, Kv2: 'a, Kv3: PartialEq + 'a> {
+	iter: slice::Iter<'a, (Kv1, Kv2)>,
+	other: &'a BidirMap<Kv1, Kv3>,
+}
+
+impl<'a, Kv1: PartialEq, Kv2, Kv3: PartialEq> Iterator for JoinByFirst<'a, Kv1, Kv2, Kv3> {
+	type Item = (&'a Kv1, &'a Kv2, Option<&'a Kv3>);
+	fn next(&mut self) -> Option<Self::Item> {
+		let other = self.other;
+		self.iter.next().map(|kvs| (&kvs.0, &kvs.1, other.cont.iter().find(|okvs| okvs.0 == kvs.0).map(|okvs| &okvs.1)))
+	}
+}
+
+
+/// An iterator over the first K/Vs two `BidirMap`s share, with both their second K/Vs.
+///
+/// See documentation of `BidirMap::inner_join_by_first()` for more.
+pub struct InnerJoinByFirst<'a, Kv1: PartialEq + 'a, Kv2: 'a, Kv3: PartialEq + 'a> {
+	join: JoinByFirst<'a, Kv1, Kv2, Kv3>,
+}
+
+impl<'a, Kv1: PartialEq, Kv2, Kv3: PartialEq> Iterator for InnerJoinByFirst<'a, Kv1, Kv2, Kv3> {
+	type Item = (&'a Kv1, &'a Kv2, &'a Kv3);
+	fn next(&mut self) -> Option<Self::Item> {
+		self.join.by_ref().filter_map(|(kv1, kv2, kv3)| kv3.map(|kv3| (kv1, kv2, kv3))).next()
+	}
+}
+
+
+/// An iterator over the first K/Vs two `BidirMap`s share.
+///
+/// See documentation of `BidirMap::common_first_keys()` for more.
+pub struct CommonFirstKeys<'a, Kv1: PartialEq + 'a, Kv2: 'a, Kv3: PartialEq + 'a> {
+	join: InnerJoinByFirst<'a, Kv1, Kv2, Kv3>,
+}
+
+impl<'a, Kv1: PartialEq, Kv2, Kv3: PartialEq> Iterator for CommonFirstKeys<'a, Kv1, Kv2, Kv3> {
+	type Item = &'a Kv1;
+	fn next(&mut self) -> Option<Self::Item> {
+		self.join.next().map(|(kv1, _, _)| kv1)
+	}
+}
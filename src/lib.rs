@@ -12,12 +12,276 @@
 //! assert_eq!(map.get_by_second(&"a"), Some(&1));
 //! assert_eq!(map.get_by_second(&"b"), None);
 //! ```
+//!
+//! # Features
+//!
+//! The `alloc` feature (implied by `std`, which is on by default) enables the heap-backed `BidirMap`, `BidirSoaMap`, `BidirSortedMap`, `BidirBTreeMap` and `DenseBidirMap`.
+//! Without it the crate is `no_std` and only the fixed-capacity `BidirArrayMap` and `BidirEnumMap` are available.
+//! `std` additionally enables `BidirHashMap`, `FingerprintedBidirMap` and `BidirMap`'s optional hash index.
+//! The `csv` feature adds reading and writing `BidirMap`s as two-column CSV.
+//! The `fuzzy` feature adds finding the K/Vs of a `BidirMap` closest to a misspelled one.
+//! The `stats` feature makes `BidirMap` count its lookups, hits and misses, insertions, removals and K/V comparisons.
+//! The `bimap-compat` feature adds aliases of `BidirMap`'s methods under the names of the `bimap` crate's.
+//!
+//! The `derive` feature re-exports `#[derive(BidirMapping)]` and `#[derive(EnumLike)]` from `bidir-map-derive`,
+//! and the `macros` feature `include_bidir_map!`, embedding a two-column file as a `StaticBidirMap` checked at compile time,
+//! and `named_bidir_map!`, generating a `BidirMap` newtype whose methods are named after its columns.
+
+#![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+#[cfg(any(feature = "derive", feature = "macros"))]
+extern crate bidir_map_derive;
 
-use std::borrow::Borrow;
-use std::slice;
-use std::iter::{Extend, FromIterator};
-use std::vec;
+#[cfg(feature = "alloc")]
+mod anchored;
+pub mod array_map;
+#[cfg(feature = "alloc")]
+pub mod aux_map;
+#[cfg(feature = "std")]
+pub mod binary;
+#[cfg(feature = "bimap-compat")]
+pub mod bimap_compat;
+#[cfg(feature = "alloc")]
+pub mod bounded;
+#[cfg(feature = "alloc")]
+pub mod builder;
+#[cfg(feature = "alloc")]
+pub mod btree_map;
+#[cfg(feature = "alloc")]
+pub mod chained;
+#[cfg(feature = "alloc")]
+pub mod comparator;
+#[cfg(feature = "std")]
+mod concurrent;
+#[cfg(feature = "alloc")]
+mod coverage;
+#[cfg(feature = "alloc")]
+pub mod cow;
+#[cfg(feature = "csv")]
+pub mod csv;
+#[cfg(feature = "alloc")]
+pub mod cursor;
+#[cfg(feature = "alloc")]
+mod delimited;
+#[cfg(feature = "alloc")]
+pub mod dense_map;
+#[cfg(feature = "alloc")]
+pub mod diff;
+#[cfg(feature = "std")]
+pub mod edit;
+#[cfg(feature = "alloc")]
+pub mod entry;
+pub mod enum_map;
+pub mod error;
+#[cfg(feature = "alloc")]
+pub mod fallback;
+#[cfg(feature = "std")]
+pub mod fingerprint;
+#[cfg(feature = "alloc")]
+mod frozen;
+#[cfg(feature = "fuzzy")]
+mod fuzzy;
+#[cfg(feature = "alloc")]
+pub mod handle_map;
+#[cfg(feature = "std")]
+mod hash_index;
+#[cfg(feature = "alloc")]
+pub mod history;
+#[cfg(feature = "alloc")]
+pub mod index_map;
+#[cfg(feature = "std")]
+pub mod hash_map;
+#[cfg(feature = "alloc")]
+pub mod heap_size;
+#[cfg(feature = "alloc")]
+pub mod join;
+#[cfg(feature = "alloc")]
+pub mod lru;
+#[cfg(feature = "alloc")]
+mod mru;
+#[cfg(feature = "alloc")]
+pub mod multi_map;
+#[cfg(feature = "alloc")]
+pub mod observed;
+#[cfg(feature = "alloc")]
+mod pair_eq;
+#[cfg(feature = "alloc")]
+mod permutation;
+#[cfg(feature = "alloc")]
+pub mod persistent;
+#[cfg(feature = "alloc")]
+mod prefix;
+pub mod primitive;
+#[cfg(feature = "alloc")]
+mod raw;
+#[cfg(feature = "alloc")]
+mod remap;
+#[cfg(feature = "std")]
+mod remove_all;
+#[cfg(feature = "alloc")]
+pub mod scoped;
+#[cfg(feature = "alloc")]
+mod shrink;
+#[cfg(feature = "alloc")]
+pub mod soa_map;
+#[cfg(feature = "alloc")]
+pub mod splice;
+#[cfg(feature = "alloc")]
+mod sorted_insert;
+#[cfg(feature = "alloc")]
+mod sorted_index;
+#[cfg(feature = "alloc")]
+pub mod sorted_map;
+pub mod static_map;
+#[cfg(feature = "alloc")]
+pub mod tagged;
+#[cfg(feature = "alloc")]
+pub mod testkit;
+#[cfg(feature = "alloc")]
+mod stats;
+#[cfg(feature = "alloc")]
+pub mod storage;
+#[cfg(feature = "alloc")]
+pub mod tombstone;
+pub mod total_ord;
+pub mod traits;
+#[cfg(feature = "alloc")]
+pub mod transaction;
+#[cfg(feature = "alloc")]
+pub mod tri_map;
+#[cfg(feature = "alloc")]
+mod validate;
+#[cfg(feature = "alloc")]
+pub mod validated;
+#[cfg(feature = "alloc")]
+pub mod view;
+#[cfg(feature = "alloc")]
+pub mod weak;
+
+pub use array_map::BidirArrayMap;
+#[cfg(feature = "alloc")]
+pub use aux_map::AuxBidirMap;
+#[cfg(feature = "bimap-compat")]
+pub use bimap_compat::BiMap;
+#[cfg(feature = "alloc")]
+pub use bounded::BoundedBidirMap;
+#[cfg(feature = "alloc")]
+pub use btree_map::BidirBTreeMap;
+#[cfg(feature = "alloc")]
+pub use builder::BidirMapBuilder;
+#[cfg(feature = "alloc")]
+pub use chained::ChainedBidirMap;
+#[cfg(feature = "alloc")]
+pub use comparator::BidirMapWith;
+#[cfg(feature = "std")]
+pub use concurrent::ConcurrentBidirMap;
+#[cfg(feature = "alloc")]
+pub use cow::CowBidirMap;
+#[cfg(feature = "alloc")]
+pub use cursor::CursorMut;
+pub use enum_map::{BidirEnumMap, EnumLike};
+#[cfg(feature = "alloc")]
+pub use dense_map::{DenseBidirMap, DenseKey};
+#[cfg(feature = "alloc")]
+pub use diff::Diff;
+#[cfg(feature = "std")]
+pub use edit::EditSession;
+#[cfg(feature = "alloc")]
+pub use entry::{EntryByFirst, EntryBySecond, EntryRefByFirst, Upserted};
+#[cfg(feature = "csv")]
+pub use error::CsvImportError;
+#[cfg(feature = "alloc")]
+pub use error::{ParseError, ValidationError};
+pub use error::{BatchInsertError, BuildError, CapacityError, Column, DuplicateInsertError, DuplicateKeyError, FromSortedVecError, InsertError, InvariantError, LengthMismatchError, ParseErrorKind, RemapError, TransactionError};
+#[cfg(feature = "alloc")]
+pub use fallback::{FallbackBidirMap, Resolved};
+#[cfg(feature = "std")]
+pub use fingerprint::FingerprintedBidirMap;
+#[cfg(feature = "alloc")]
+pub use frozen::FrozenBidirMap;
+#[cfg(feature = "alloc")]
+pub use handle_map::{HandleBidirMap, PairHandle};
+#[cfg(feature = "std")]
+pub use hash_map::BidirHashMap;
+#[cfg(feature = "alloc")]
+pub use heap_size::HeapSize;
+#[cfg(feature = "alloc")]
+pub use history::HistoryBidirMap;
+#[cfg(feature = "alloc")]
+pub use index_map::BidirIndexMap;
+#[cfg(feature = "alloc")]
+pub use lru::LruBidirMap;
+#[cfg(feature = "alloc")]
+pub use mru::MruBidirMap;
+#[cfg(feature = "alloc")]
+pub use multi_map::BidirMultiMap;
+#[cfg(feature = "alloc")]
+pub use observed::{MapEvent, ObservedBidirMap};
+#[cfg(feature = "alloc")]
+pub use persistent::PersistentBidirMap;
+pub use primitive::PrimitiveKey;
+#[cfg(feature = "alloc")]
+pub use scoped::ScopedInsert;
+#[cfg(feature = "alloc")]
+pub use shrink::ShrinkPolicy;
+#[cfg(feature = "alloc")]
+pub use soa_map::BidirSoaMap;
+#[cfg(feature = "alloc")]
+pub use splice::Splice;
+#[cfg(feature = "alloc")]
+pub use sorted_map::BidirSortedMap;
+pub use static_map::StaticBidirMap;
+#[cfg(feature = "stats")]
+pub use stats::MapStats;
+#[cfg(feature = "alloc")]
+pub use tagged::{First, Second, TaggedBidirMap};
+#[cfg(feature = "alloc")]
+pub use tombstone::TombstoneBidirMap;
+pub use total_ord::TotalOrd;
+#[cfg(feature = "alloc")]
+pub use transaction::Transaction;
+#[cfg(feature = "alloc")]
+pub use tri_map::TriMap;
+#[cfg(feature = "alloc")]
+pub use validate::{DuplicatePolicy, OnDuplicate, RepairPolicy};
+#[cfg(feature = "alloc")]
+pub use validated::ValidatedBidirMap;
+#[cfg(feature = "alloc")]
+pub use weak::WeakBidirMap;
+#[cfg(feature = "derive")]
+pub use bidir_map_derive::{BidirMapping, EnumLike};
+#[cfg(feature = "macros")]
+pub use bidir_map_derive::{include_bidir_map, named_bidir_map};
+
+#[cfg(feature = "alloc")]
+use core::any::TypeId;
+#[cfg(feature = "alloc")]
+use core::borrow::Borrow;
+use core::slice;
+#[cfg(feature = "alloc")]
+use core::fmt;
+#[cfg(feature = "alloc")]
+use core::hash::{Hash, Hasher};
+#[cfg(feature = "alloc")]
+use core::iter::{Extend, FromIterator};
+#[cfg(feature = "alloc")]
+use core::marker::PhantomData;
+#[cfg(feature = "alloc")]
+use core::mem;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use hash_index::HashIndex;
+#[cfg(feature = "alloc")]
+use sorted_index::SortedIndexes;
+#[cfg(feature = "alloc")]
+use stats::Counters;
+#[cfg(feature = "alloc")]
+use storage::Storage;
 
 
 /// Create a `BidirMap` from a set of K/V-K/V pairs.
@@ -41,6 +305,7 @@ use std::vec;
 /// # assert_eq!(map, best_map);
 /// # }
 /// ```
+#[cfg(feature = "alloc")]
 #[macro_export]
 macro_rules! bidir_map {
 	(@single $($x:tt)*) => (());
@@ -64,27 +329,235 @@ macro_rules! bidir_map {
 /// The interface is based on that of `BTreeMap`, except, that for all functions, where one would supply a key, there are two functions,
 /// each treating one of the types as keys (`get()` -> `get_by_{first,second}()`).
 ///
-/// Performance: `O(n)`, mostly.
-#[derive(Clone, Debug, Hash, PartialEq, Eq)]
-pub struct BidirMap<Kv1: PartialEq, Kv2: PartialEq> {
-	cont: Vec<(Kv1, Kv2)>,
+/// Performance: `O(n)`, mostly; see `build_index()` for `O(1)` lookups and `build_sorted_indexes()` for `O(log n)` ones.
+///
+/// # K/V bounds
+///
+/// The type itself puts no bounds on the K/Vs: a map of K/Vs that can't be compared can still be built by `collect()`,
+/// iterated, debug-printed, and edited by position, with `swap_remove_index()`, `shift_remove_index()` and `retain()`.
+/// Only the methods finding pairs by a K/V, inserting included, require `PartialEq`.
+///
+/// # K/Vs not equal to themselves
+///
+/// Only `PartialEq` is required of the K/Vs, so some, like `f64::NAN`, may not equal themselves.
+/// Such a K/V can't be looked up, or removed by `remove_by_*()`, since no key matches it.
+/// To keep them from piling up, `insert()` treats all K/Vs not equal to themselves as the same K/V of their column,
+/// so inserting one displaces the pair already holding one; in debug builds, it panics instead.
+/// Pairs holding them can still be removed by position, with `swap_remove_index()` and `shift_remove_index()`, or by `retain()`.
+///
+/// Wrapping floats in `TotalOrd` makes every K/V equal itself.
+///
+/// # Storage
+///
+/// The pairs are kept in a `Vec` by default, or in any other `Storage` given to `with_storage()`.
+/// All of the methods finding, inserting or removing pairs one by one work with any storage;
+/// the rest, including the constructors other than `with_storage()` and the conversions into other maps, need a `Vec`.
+#[cfg(feature = "alloc")]
+#[derive(Clone)]
+pub struct BidirMap<Kv1, Kv2, S = Vec<(Kv1, Kv2)>> {
+	cont: S,
+	#[cfg(feature = "std")]
+	index: Option<HashIndex<Kv1, Kv2>>,
+	sorted_indexes: Option<SortedIndexes<Kv1, Kv2>>,
+	generation: u64,
+	duplicate_policy: Option<DuplicatePolicy>,
+	max_len: Option<usize>,
+	shrink_policy: ShrinkPolicy,
+	stats: Counters,
 }
 
-impl<Kv1: PartialEq, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
+#[cfg(feature = "alloc")]
+impl<Kv1, Kv2> BidirMap<Kv1, Kv2> {
 	/// Create a new empty instance of `BidirMap`
 	pub fn new() -> Self {
-		BidirMap{
-			cont: Vec::new(),
-		}
+		BidirMap::from_cont(Vec::new())
 	}
 
 	/// Create a new empty instance of `BidirMap` with the specified capacity.
 	///
 	/// It will be able to hold at least `capacity` elements without reallocating.
 	pub fn with_capacity(capacity: usize) -> Self {
-		BidirMap{
-			cont: Vec::with_capacity(capacity),
+		BidirMap::from_cont(Vec::with_capacity(capacity))
+	}
+
+	/// Create a new empty instance of `BidirMap` whose `insert()` and `extend()` handle pairs sharing a K/V with one in the map
+	/// as `policy` says, rather than always replacing the pairs in the map.
+	///
+	/// For each column in which the pair's K/V is already in the map, the column's `OnDuplicate` applies:
+	///
+	///   * `LastWins` replaces the pair in the map with the new one, as without a policy,
+	///   * `FirstWins` keeps the pair in the map, dropping the new one, and
+	///   * `Error` refuses the new pair; `try_insert()` reports that, `insert()` and `extend()` panic.
+	///
+	/// If the pair conflicts in both columns, with different pairs, `Error` for either column takes precedence,
+	/// then `FirstWins`, so that the new pair only displaces pairs if both columns allow it.
+	/// Reinserting a pair whose K/Vs are both in the same pair of the map always updates that pair.
+	///
+	/// The policy is kept by clones, but doesn't apply to `FromIterator`, which builds a map without one;
+	/// `BidirMapBuilder::on_duplicate()` builds from pairs under a policy for both columns.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::{BidirMap, DuplicatePolicy, OnDuplicate};
+	///
+	/// let mut map = BidirMap::with_duplicate_policy(DuplicatePolicy{
+	///     on_first: OnDuplicate::LastWins,
+	///     on_second: OnDuplicate::FirstWins,
+	/// });
+	/// map.insert(1, "a");
+	/// assert_eq!(map.insert(1, "b"), Some((1, "a")));
+	/// assert_eq!(map.insert(2, "b"), Some((2, "b")));
+	/// assert_eq!(map.iter().cloned().collect::<Vec<_>>(), [(1, "b")]);
+	/// ```
+	pub fn with_duplicate_policy(policy: DuplicatePolicy) -> Self {
+		let mut map = BidirMap::new();
+		map.duplicate_policy = Some(policy);
+		map
+	}
+
+	/// Create a new empty instance of `BidirMap` which refuses to grow past `max_len` pairs, see `set_max_len()`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::{BidirMap, CapacityError};
+	///
+	/// let mut map = BidirMap::with_max_len(1);
+	/// assert_eq!(map.try_insert_within_max_len(1, "a"), Ok(None));
+	/// assert_eq!(map.try_insert_within_max_len(2, "b"), Err(CapacityError{capacity: 1}));
+	/// assert_eq!(map.try_insert_within_max_len(1, "b"), Ok(Some((1, "a"))));
+	/// ```
+	pub fn with_max_len(max_len: usize) -> Self {
+		let mut map = BidirMap::new();
+		map.max_len = Some(max_len);
+		map
+	}
+
+	/// Shrinks the capacity of the map as much as possible.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::with_capacity(10);
+	/// map.insert(1, 2);
+	/// map.shrink_to_fit();
+	/// assert!(map.capacity() < 10);
+	/// ```
+	pub fn shrink_to_fit(&mut self) {
+		self.cont.shrink_to_fit()
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<Kv1, Kv2, S: Storage<Kv1, Kv2>> BidirMap<Kv1, Kv2, S> {
+	/// Create a new empty instance of `BidirMap` keeping its pairs in `storage`, which is cleared first.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut buffer = Vec::with_capacity(16);
+	/// buffer.push((1, "a"));
+	/// let map: BidirMap<u8, &str> = BidirMap::with_storage(buffer);
+	/// assert!(map.is_empty());
+	/// assert!(map.capacity() >= 16);
+	/// ```
+	pub fn with_storage(mut storage: S) -> Self {
+		storage.clear();
+		BidirMap::from_cont(storage)
+	}
+
+	/// Unwraps the map into its storage, holding its pairs in iteration order.
+	pub fn into_storage(self) -> S {
+		self.cont
+	}
+
+	/// Returns the map's duplicate policy, if it was created with one by `with_duplicate_policy()`.
+	pub fn duplicate_policy(&self) -> Option<DuplicatePolicy> {
+		self.duplicate_policy
+	}
+
+	/// Bounds the map to `max_len` pairs, keeping any pairs past the bound, but refusing to add more than it.
+	///
+	/// Unlike a `BoundedBidirMap`, a full map never evicts pairs to make room: inserting a pair that would grow it is refused.
+	/// `try_insert_within_max_len()`, `extend_within_max_len()` report that; all other insertions,
+	/// including `insert()`, `extend()` and those through entries, panic.
+	/// Inserting a pair sharing a K/V with one in the map doesn't grow it, as it displaces the old pair,
+	/// so such updates are always allowed, even if the map is over the bound.
+	///
+	/// The bound is kept by clones, but doesn't apply to `FromIterator`, which builds a map without one;
+	/// `read_from_with_max_len()` and `CsvOptions::max_len` read maps with one.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map: BidirMap<_, _> = vec![(1, "a"), (2, "b"), (3, "c")].into_iter().collect();
+	/// map.set_max_len(2);
+	/// assert!(map.try_insert_within_max_len(4, "d").is_err());
+	/// map.remove_by_first(&1);
+	/// assert!(map.try_insert_within_max_len(4, "d").is_err());
+	/// map.remove_by_first(&2);
+	/// assert!(map.try_insert_within_max_len(4, "d").is_ok());
+	/// ```
+	pub fn set_max_len(&mut self, max_len: usize) {
+		self.max_len = Some(max_len);
+	}
+
+	/// Returns the most pairs the map may grow to, if it's bounded by `with_max_len()` or `set_max_len()`.
+	pub fn max_len(&self) -> Option<usize> {
+		self.max_len
+	}
+
+	/// Makes the map give back its storage's spare capacity after removing pairs, once it uses less of it than `policy` allows.
+	///
+	/// The policy is checked after every removal: by `remove_by_*()`, `remove_full_by_*()`, `shift_remove_full_by_*()`,
+	/// `swap_remove_index()`, `shift_remove_index()`, `remove_all_by_*()`, `retain()` and `clear()`;
+	/// a pair displaced by an insertion makes room for the new one, so insertions never shrink the map.
+	/// Then, with `ShrinkPolicy::WhenBelow`, a map using less than `utilization` of its capacity, and with more than `min_capacity`,
+	/// shrinks to a capacity that it uses halfway between `utilization` and fully, but not below `min_capacity`.
+	/// The room left keeps a map hovering around one size from reallocating on every removal and insertion,
+	/// as long as `utilization` is below a half: a growing `Vec` doubles its capacity, leaving it half used.
+	///
+	/// Maps shrink only as far as their storage can; `InlineStorage` never does.
+	/// `ShrinkPolicy::Never` is the default, and the policy is kept by clones, but doesn't apply to `FromIterator`.
+	///
+	/// # Panics
+	///
+	/// If `utilization` isn't at least 0 and below 1; from 1 on, a map would shrink to fit on every removal.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::{BidirMap, ShrinkPolicy};
+	///
+	/// let mut map: BidirMap<_, _> = (0..1000).map(|i| (i, -i)).collect();
+	/// map.set_shrink_policy(ShrinkPolicy::WhenBelow{utilization: 0.25, min_capacity: 64});
+	///
+	/// map.retain(|&kv1, _| kv1 < 500);
+	/// assert!(map.capacity() >= 1000);
+	/// map.retain(|&kv1, _| kv1 < 100);
+	/// assert!(map.capacity() >= 100 && map.capacity() < 200);
+	///
+	/// map.clear();
+	/// assert_eq!(map.capacity(), 64);
+	/// ```
+	pub fn set_shrink_policy(&mut self, policy: ShrinkPolicy) {
+		if let ShrinkPolicy::WhenBelow{utilization, ..} = policy {
+			assert!((0.0..1.0).contains(&utilization), "BidirMap::set_shrink_policy(): utilization {} isn't at least 0 and below 1", utilization);
 		}
+		self.shrink_policy = policy;
+		self.reclaim();
+	}
+
+	/// Returns the map's shrink policy, `ShrinkPolicy::Never` unless set by `set_shrink_policy()`.
+	pub fn shrink_policy(&self) -> ShrinkPolicy {
+		self.shrink_policy
 	}
 
 	/// Clears the map, removing all entries.
@@ -100,27 +573,10 @@ impl<Kv1: PartialEq, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
 	/// assert!(a.is_empty());
 	/// ```
 	pub fn clear(&mut self) {
-		self.cont.clear()
-	}
-
-	/// Inserts a K/V-K/V pair into the map.
-	///
-	/// If the map did not have this K/V-K/V pair present, `None` is returned.
-	///
-	/// If the map did have this K/V-K/V pair present, it's updated and the old K/V-K/V pair is returned.
-	pub fn insert(&mut self, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)> {
-		let retval =
-			if self.contains_first_key(&kv1) {
-				self.remove_by_first(&kv1)
-			} else if self.contains_second_key(&kv2) {
-				self.remove_by_second(&kv2)
-			} else {
-				None
-			};
-
-		self.cont.push((kv1, kv2));
-
-		retval
+		self.stats.removed(self.cont.len());
+		self.cont.clear();
+		self.rebuild_index();
+		self.reclaim();
 	}
 
 	/// Gets an iterator over the entries of the map.
@@ -136,7 +592,7 @@ impl<Kv1: PartialEq, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
 	/// map.insert(3, "c");
 	///
 	/// for kv in map.iter() {
-	/// 	println!("{}: {}", kv.0, kv.1);
+	///     println!("{}: {}", kv.0, kv.1);
 	/// }
 	///
 	/// let first = map.iter().next().unwrap();
@@ -162,12 +618,13 @@ impl<Kv1: PartialEq, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
 	///
 	/// // add 10 to the value if the key isn't "a"
 	/// for kv in map.iter_mut() {
-	/// 	if &kv.0 != &"a" {
-	/// 		kv.1 += 10;
-	/// 	}
+	///     if &kv.0 != &"a" {
+	///         kv.1 += 10;
+	///     }
 	/// }
 	/// ```
 	pub fn iter_mut<'s>(&'s mut self) -> IterMut<'s, Kv1, Kv2> {
+		self.mark_index_stale();
 		IterMut{
 			iter: self.cont.iter_mut(),
 		}
@@ -245,6 +702,320 @@ impl<Kv1: PartialEq, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
 		self.cont.is_empty()
 	}
 
+	/// Returns the number of elements the map can hold without reallocating.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let map = BidirMap::<u8, u8>::with_capacity(10);
+	/// assert!(map.capacity() >= 10);
+	/// ```
+	pub fn capacity(&self) -> usize {
+		self.cont.capacity()
+	}
+
+	/// Returns the map's generation, which changes with every mutation, so that an unchanged generation means unchanged contents.
+	///
+	/// Handing out mutable references, as by `iter_mut()` or `get_mut_by_*()`, counts as a mutation, whether or not they're written through.
+	/// The converse doesn't hold: a generation may change without the contents changing, e.g. by a rolled-back `transaction()`.
+	///
+	/// Clones start at the generation of their original, and generations don't take part in comparisons or hashing.
+	/// The counter is a wrapping `u64`, so a generation only repeats after 2⁶⁴ mutations.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// let seen = map.generation();
+	/// map.insert(1, "a");
+	/// assert_ne!(map.generation(), seen);
+	///
+	/// let seen = map.generation();
+	/// assert_eq!(map.get_by_first(&1), Some(&"a"));
+	/// assert_eq!(map.generation(), seen);
+	/// ```
+	pub fn generation(&self) -> u64 {
+		self.generation
+	}
+
+	/// Retains only the pairs for which `f` returns `true`.
+	///
+	/// The retained pairs keep their order.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map: BidirMap<_, _> = vec![(1, "a"), (2, "b"), (3, "c")].into_iter().collect();
+	/// map.retain(|&kv1, _| kv1 != 2);
+	/// assert_eq!(map.iter().cloned().collect::<Vec<_>>(), [(1, "a"), (3, "c")]);
+	/// ```
+	pub fn retain<F: FnMut(&Kv1, &Kv2) -> bool>(&mut self, mut f: F) {
+		let len = self.cont.len();
+		self.cont.retain(|kvs| f(&kvs.0, &kvs.1));
+		if self.cont.len() != len {
+			self.stats.removed(len - self.cont.len());
+			self.rebuild_index();
+			self.reclaim();
+		}
+	}
+
+	/// Removes the pair at the specified position in iteration order, returning it, or `None` if out of range.
+	///
+	/// Like `remove_by_*()`, this takes `O(1)` by moving the last pair into the vacated position,
+	/// so only indices of the removed and the last pair change.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map: BidirMap<_, _> = vec![(1, "a"), (2, "b"), (3, "c")].into_iter().collect();
+	/// assert_eq!(map.swap_remove_index(0), Some((1, "a")));
+	/// assert_eq!(map.swap_remove_index(2), None);
+	/// assert_eq!(map.iter().cloned().collect::<Vec<_>>(), [(3, "c"), (2, "b")]);
+	/// ```
+	pub fn swap_remove_index(&mut self, idx: usize) -> Option<(Kv1, Kv2)> {
+		if idx < self.cont.len() {
+			let kvs = self.swap_remove_at(idx);
+			self.reclaim();
+			Some(kvs)
+		} else {
+			None
+		}
+	}
+
+	/// Removes the pair at the specified position in iteration order, returning it, or `None` if out of range.
+	///
+	/// This preserves the order of the remaining pairs, shifting all after it down by one, and so takes `O(n)`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map: BidirMap<_, _> = vec![(1, "a"), (2, "b"), (3, "c")].into_iter().collect();
+	/// assert_eq!(map.shift_remove_index(0), Some((1, "a")));
+	/// assert_eq!(map.shift_remove_index(2), None);
+	/// assert_eq!(map.iter().cloned().collect::<Vec<_>>(), [(2, "b"), (3, "c")]);
+	/// ```
+	pub fn shift_remove_index(&mut self, idx: usize) -> Option<(Kv1, Kv2)> {
+		if idx < self.cont.len() {
+			let kvs = self.shift_remove_at(idx);
+			self.reclaim();
+			Some(kvs)
+		} else {
+			None
+		}
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<Kv1: PartialEq, Kv2: PartialEq, S: Storage<Kv1, Kv2>> BidirMap<Kv1, Kv2, S> {
+	/// Inserts a K/V-K/V pair into the map.
+	///
+	/// If the map did not have this K/V-K/V pair present, `None` is returned.
+	///
+	/// If the map did have this K/V-K/V pair present, it's updated and the old K/V-K/V pair is returned.
+	///
+	/// If `kv1` and `kv2` were each present in a different pair, both of those are removed and the one holding `kv1` is returned.
+	/// The one holding `kv2` is dropped and can't be recovered; look it up with `get_by_second()` first if it's needed.
+	///
+	/// Without a hash index, the pairs are scanned once for both K/Vs.
+	///
+	/// With a duplicate policy (see `with_duplicate_policy()`), a pair may instead be dropped, and then it's the one returned.
+	///
+	/// # Panics
+	///
+	/// If the map's duplicate policy refuses the pair with `OnDuplicate::Error`.
+	///
+	/// If the pair would grow the map past its `max_len()`.
+	///
+	/// In debug builds, if `kv1` or `kv2` isn't equal to itself; see the type-level documentation.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert(1, "a");
+	/// map.insert(2, "b");
+	/// assert_eq!(map.insert(1, "b"), Some((1, "a")));
+	/// assert_eq!(map.len(), 1);
+	/// assert_eq!(map.get_by_second(&"b"), Some(&1));
+	/// ```
+	pub fn insert(&mut self, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)> {
+		match self.try_insert(kv1, kv2) {
+			Ok(displaced) => displaced,
+			Err(err) => panic!("BidirMap::insert(): {}, which the duplicate policy forbids", err),
+		}
+	}
+
+	/// Inserts a K/V-K/V pair into the map like `insert()`, but hands the pair back instead of panicking
+	/// if the map's duplicate policy refuses it.
+	///
+	/// Without a duplicate policy, or one without `OnDuplicate::Error`, this never fails.
+	/// It still panics if the pair would grow the map past its `max_len()`; see `try_insert_within_max_len()`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::{BidirMap, Column, DuplicateInsertError, DuplicatePolicy, OnDuplicate};
+	///
+	/// let mut map = BidirMap::with_duplicate_policy(DuplicatePolicy::both(OnDuplicate::Error));
+	/// assert_eq!(map.try_insert(1, "a"), Ok(None));
+	/// assert_eq!(map.try_insert(2, "a"), Err(DuplicateInsertError{column: Column::Second, pair: (2, "a")}));
+	/// assert_eq!(map.try_insert(1, "a"), Ok(Some((1, "a"))));
+	/// ```
+	pub fn try_insert(&mut self, kv1: Kv1, kv2: Kv2) -> Result<Option<(Kv1, Kv2)>, DuplicateInsertError<Kv1, Kv2>> {
+		let (mut by_first, mut by_second) = self.positions_of(&kv1, &kv2);
+		// A K/V equal to another equals itself, so only ones not found need checking
+		if by_first.is_none() && !total_ord::equals_itself(&kv1) {
+			if cfg!(debug_assertions) {
+				panic!("BidirMap::insert(): the first K/V isn't equal to itself");
+			}
+			by_first = self.cont.iter().position(|kvs| !total_ord::equals_itself(&kvs.0));
+		}
+		if by_second.is_none() && !total_ord::equals_itself(&kv2) {
+			if cfg!(debug_assertions) {
+				panic!("BidirMap::insert(): the second K/V isn't equal to itself");
+			}
+			by_second = self.cont.iter().position(|kvs| !total_ord::equals_itself(&kvs.1));
+		}
+
+		if let Some(policy) = self.duplicate_policy {
+			if by_first.is_none() || by_first != by_second {
+				let conflicts = [(Column::First, by_first.is_some(), policy.on_first), (Column::Second, by_second.is_some(), policy.on_second)];
+				if let Some(&(column, _, _)) = conflicts.iter().find(|&&(_, found, on)| found && on == OnDuplicate::Error) {
+					return Err(DuplicateInsertError{
+						column,
+						pair: (kv1, kv2),
+					});
+				}
+				if conflicts.iter().any(|&(_, found, on)| found && on == OnDuplicate::FirstWins) {
+					return Ok(Some((kv1, kv2)));
+				}
+			}
+		}
+
+		if by_first.is_none() && by_second.is_none() {
+			self.assert_room();
+		}
+
+		let by_first = by_first.map(|idx| {
+			// The pair holding `kv2` is gone with this one, or takes its place if it was the last
+			if by_second == Some(idx) {
+				by_second = None;
+			} else if by_second == Some(self.cont.len() - 1) {
+				by_second = Some(idx);
+			}
+			self.swap_remove_at(idx)
+		});
+		let by_second = by_second.map(|idx| self.swap_remove_at(idx));
+
+		self.push(kv1, kv2);
+
+		Ok(by_first.or(by_second))
+	}
+
+	/// Inserts a K/V-K/V pair into the map like `insert()`, but only if that doesn't need to grow the map's storage.
+	///
+	/// If the map is at its `capacity()` and the pair shares no K/V with a pair in the map, the pair is handed back.
+	/// Displacing one or two pairs frees room before the pair is added, so that never grows the storage either.
+	///
+	/// With a hash index (see `build_index()`) the index's own bookkeeping may still allocate.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::with_capacity(1);
+	/// let capacity = map.capacity();
+	/// for i in 0..capacity {
+	///     assert_eq!(map.try_insert_within_capacity(i, i), Ok(None));
+	/// }
+	/// assert_eq!(map.try_insert_within_capacity(capacity, capacity), Err((capacity, capacity)));
+	/// assert_eq!(map.try_insert_within_capacity(0, capacity), Ok(Some((0, 0))));
+	/// assert_eq!(map.capacity(), capacity);
+	/// ```
+	pub fn try_insert_within_capacity(&mut self, kv1: Kv1, kv2: Kv2) -> Result<Option<(Kv1, Kv2)>, (Kv1, Kv2)> {
+		if self.cont.len() == self.cont.capacity() && self.position_of_first(&kv1).is_none() && self.position_of_second(&kv2).is_none() {
+			return Err((kv1, kv2));
+		}
+		Ok(self.insert(kv1, kv2))
+	}
+
+	/// Inserts a K/V-K/V pair into the map like `insert()`, unless that would grow it past its `max_len()`.
+	///
+	/// If the map is full and the pair shares no K/V with a pair in the map, the pair is dropped and the map left as it was.
+	/// A pair displacing others, one or two, never grows the map, so it's inserted even into a full map.
+	///
+	/// Without a bound on the map's length, this never fails.
+	///
+	/// # Panics
+	///
+	/// If the map's duplicate policy refuses the pair with `OnDuplicate::Error`, as with `insert()`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::{BidirMap, CapacityError};
+	///
+	/// let mut map = BidirMap::with_max_len(2);
+	/// map.insert(1, "a");
+	/// map.insert(2, "b");
+	/// assert_eq!(map.try_insert_within_max_len(3, "c"), Err(CapacityError{capacity: 2}));
+	/// assert_eq!(map.try_insert_within_max_len(3, "b"), Ok(Some((2, "b"))));
+	/// assert_eq!(map.try_insert_within_max_len(1, "b"), Ok(Some((1, "a"))));
+	/// assert_eq!(map.len(), 1);
+	/// ```
+	pub fn try_insert_within_max_len(&mut self, kv1: Kv1, kv2: Kv2) -> Result<Option<(Kv1, Kv2)>, CapacityError> {
+		if let Some(max_len) = self.max_len {
+			if self.cont.len() >= max_len && self.position_of_first(&kv1).is_none() && self.position_of_second(&kv2).is_none() {
+				return Err(CapacityError{capacity: max_len});
+			}
+		}
+		Ok(self.insert(kv1, kv2))
+	}
+
+	/// Inserts the pairs as `try_insert_within_max_len()` does, returning those refused because the map was full, in order.
+	///
+	/// Pairs are inserted like with `insert()`, so unlike with `extend()` they may displace ones in the map or earlier in the batch,
+	/// and one updating a pair is inserted even once the map is full.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::with_max_len(3);
+	/// map.insert(1, "a");
+	/// let refused = map.extend_within_max_len(vec![(2, "b"), (3, "c"), (4, "d"), (1, "A")]);
+	/// assert_eq!(refused, [(4, "d")]);
+	/// assert_eq!(map.get_by_first(&1), Some(&"A"));
+	/// ```
+	pub fn extend_within_max_len<I: IntoIterator<Item=(Kv1, Kv2)>>(&mut self, iter: I) -> Vec<(Kv1, Kv2)> {
+		let mut refused = Vec::new();
+		for (kv1, kv2) in iter {
+			let fits = match self.max_len {
+				Some(max_len) => self.cont.len() < max_len || self.position_of_first(&kv1).is_some() || self.position_of_second(&kv2).is_some(),
+				None => true,
+			};
+			if fits {
+				self.insert(kv1, kv2);
+			} else {
+				refused.push((kv1, kv2));
+			}
+		}
+		refused
+	}
 
 	/// Returns a reference to the second K/V corresponding to the first K/V.
 	///
@@ -258,11 +1029,11 @@ impl<Kv1: PartialEq, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
 	/// assert_eq!(map.get_by_first(&1), Some(&"a"));
 	/// assert_eq!(map.get_by_first(&2), None);
 	/// ```
-	pub fn get_by_first<Q: ?Sized>(&self, key: &Q) -> Option<&Kv2>
+	pub fn get_by_first<Q>(&self, key: &Q) -> Option<&Kv2>
 		where Kv1: Borrow<Q>,
-		      Q  : PartialEq<Kv1>,
+		      Q  : PartialEq + ?Sized,
 	{
-		self.cont.iter().find(|&kvs| *key == kvs.0).map(|ref kvs| &kvs.1)
+		self.lookup_first(key).map(|idx| &self.cont[idx].1)
 	}
 
 	/// Returns a reference to the first K/V corresponding to the second K/V.
@@ -277,11 +1048,11 @@ impl<Kv1: PartialEq, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
 	/// assert_eq!(map.get_by_second(&"a"), Some(&1));
 	/// assert_eq!(map.get_by_second(&"b"), None);
 	/// ```
-	pub fn get_by_second<Q: ?Sized>(&self, key: &Q) -> Option<&Kv1>
+	pub fn get_by_second<Q>(&self, key: &Q) -> Option<&Kv1>
 		where Kv2: Borrow<Q>,
-		      Q  : PartialEq<Kv2>,
+		      Q  : PartialEq + ?Sized,
 	{
-		self.cont.iter().find(|&kvs| *key == kvs.1).map(|ref kvs| &kvs.0)
+		self.lookup_second(key).map(|idx| &self.cont[idx].0)
 	}
 
 	/// Check if the map contains the first K/V
@@ -296,11 +1067,11 @@ impl<Kv1: PartialEq, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
 	/// assert_eq!(map.contains_first_key(&1), true);
 	/// assert_eq!(map.contains_first_key(&2), false);
 	/// ```
-	pub fn contains_first_key<Q: ?Sized>(&self, key: &Q) -> bool
+	pub fn contains_first_key<Q>(&self, key: &Q) -> bool
 		where Kv1: Borrow<Q>,
-		      Q  : PartialEq<Kv1>,
+		      Q  : PartialEq + ?Sized,
 	{
-		self.cont.iter().any(|ref kvs| *key == kvs.0)
+		self.lookup_first(key).is_some()
 	}
 
 	/// Check if the map contains the second K/V
@@ -315,11 +1086,11 @@ impl<Kv1: PartialEq, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
 	/// assert_eq!(map.contains_second_key(&"a"), true);
 	/// assert_eq!(map.contains_second_key(&"b"), false);
 	/// ```
-	pub fn contains_second_key<Q: ?Sized>(&self, key: &Q) -> bool
+	pub fn contains_second_key<Q>(&self, key: &Q) -> bool
 		where Kv2: Borrow<Q>,
-		      Q  : PartialEq<Kv2>,
+		      Q  : PartialEq + ?Sized,
 	{
-		self.cont.iter().any(|ref kvs| *key == kvs.1)
+		self.lookup_second(key).is_some()
 	}
 
 	/// Returns a mutable reference to the second K/V corresponding to the first K/V.
@@ -336,11 +1107,12 @@ impl<Kv1: PartialEq, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
 	/// }
 	/// assert_eq!(map.get_by_first(&1), Some(&"b"));
 	/// ```
-	pub fn get_mut_by_first<Q: ?Sized>(&mut self, key: &Q) -> Option<&mut Kv2>
+	pub fn get_mut_by_first<Q>(&mut self, key: &Q) -> Option<&mut Kv2>
 		where Kv1: Borrow<Q>,
-		      Q  : PartialEq<Kv1>,
+		      Q  : PartialEq + ?Sized,
 	{
-		self.cont.iter_mut().find(|ref kvs| *key == kvs.0).map(|&mut (_, ref mut kv2)| kv2)
+		self.mark_index_stale();
+		self.lookup_first(key).map(move |idx| &mut self.cont[idx].1)
 	}
 
 	/// Returns a mutable reference to the first K/V corresponding to the second K/V.
@@ -357,11 +1129,12 @@ impl<Kv1: PartialEq, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
 	/// }
 	/// assert_eq!(map.get_by_second(&"a"), Some(&2));
 	/// ```
-	pub fn get_mut_by_second<Q: ?Sized>(&mut self, key: &Q) -> Option<&mut Kv1>
+	pub fn get_mut_by_second<Q>(&mut self, key: &Q) -> Option<&mut Kv1>
 		where Kv2: Borrow<Q>,
-		      Q  : PartialEq<Kv2>,
+		      Q  : PartialEq + ?Sized,
 	{
-		self.cont.iter_mut().find(|ref kvs| *key == kvs.1).map(|&mut (ref mut kv1, _)| kv1)
+		self.mark_index_stale();
+		self.lookup_second(key).map(move |idx| &mut self.cont[idx].0)
 	}
 
 	/// Removes the pair corresponding to the first K/V from the map, returning it if the key was previously in the map.
@@ -376,11 +1149,13 @@ impl<Kv1: PartialEq, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
 	/// assert_eq!(map.remove_by_first(&1), Some((1, "a")));
 	/// assert_eq!(map.remove_by_first(&1), None);
 	/// ```
-	pub fn remove_by_first<Q: ?Sized>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+	pub fn remove_by_first<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
 		where Kv1: Borrow<Q>,
-		      Q  : PartialEq<Kv1>,
+		      Q  : PartialEq + ?Sized,
 	{
-		self.cont.iter().position(|ref kvs| *key == kvs.0).map(|idx| self.cont.swap_remove(idx))
+		let removed = self.find_first(key).map(|idx| self.swap_remove_at(idx));
+		self.reclaim();
+		removed
 	}
 
 	/// Removes the pair corresponding to the first K/V from the map, returning it if the key was previously in the map.
@@ -395,35 +1170,552 @@ impl<Kv1: PartialEq, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
 	/// assert_eq!(map.remove_by_second(&"a"), Some((1, "a")));
 	/// assert_eq!(map.remove_by_second(&"b"), None);
 	/// ```
-	pub fn remove_by_second<Q: ?Sized>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+	pub fn remove_by_second<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		let removed = self.find_second(key).map(|idx| self.swap_remove_at(idx));
+		self.reclaim();
+		removed
+	}
+
+	/// Returns the position in iteration order of the pair with the specified first K/V.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let map: BidirMap<_, _> = vec![(1, "a"), (2, "b")].into_iter().collect();
+	/// assert_eq!(map.get_index_of_first(&2), Some(1));
+	/// assert_eq!(map.get_index_of_first(&3), None);
+	/// ```
+	pub fn get_index_of_first<Q>(&self, key: &Q) -> Option<usize>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.lookup_first(key)
+	}
+
+	/// Returns the position in iteration order of the pair with the specified second K/V.
+	pub fn get_index_of_second<Q>(&self, key: &Q) -> Option<usize>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.lookup_second(key)
+	}
+
+	/// Removes the pair corresponding to the first K/V from the map like `remove_by_first()`,
+	/// returning it with the position it had in iteration order.
+	///
+	/// The last pair is moved into that position, unless it's the one removed; see `swap_remove_index()`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map: BidirMap<_, _> = vec![(1, "a"), (2, "b"), (3, "c")].into_iter().collect();
+	/// assert_eq!(map.remove_full_by_first(&1), Some((0, 1, "a")));
+	/// assert_eq!(map.get_index_of_first(&3), Some(0));
+	/// ```
+	pub fn remove_full_by_first<Q>(&mut self, key: &Q) -> Option<(usize, Kv1, Kv2)>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		let removed = self.find_first(key).map(|idx| {
+			let (kv1, kv2) = self.swap_remove_at(idx);
+			(idx, kv1, kv2)
+		});
+		self.reclaim();
+		removed
+	}
+
+	/// Removes the pair corresponding to the second K/V from the map like `remove_by_second()`,
+	/// returning it with the position it had in iteration order.
+	///
+	/// The last pair is moved into that position, unless it's the one removed; see `swap_remove_index()`.
+	pub fn remove_full_by_second<Q>(&mut self, key: &Q) -> Option<(usize, Kv1, Kv2)>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		let removed = self.find_second(key).map(|idx| {
+			let (kv1, kv2) = self.swap_remove_at(idx);
+			(idx, kv1, kv2)
+		});
+		self.reclaim();
+		removed
+	}
+
+	/// Removes the pair corresponding to the first K/V from the map, preserving the order of the rest,
+	/// returning it with the position it had in iteration order.
+	///
+	/// The pairs after it each move down one position; see `shift_remove_index()`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map: BidirMap<_, _> = vec![(1, "a"), (2, "b"), (3, "c")].into_iter().collect();
+	/// assert_eq!(map.shift_remove_full_by_first(&1), Some((0, 1, "a")));
+	/// assert_eq!(map.get_index_of_first(&3), Some(1));
+	/// ```
+	pub fn shift_remove_full_by_first<Q>(&mut self, key: &Q) -> Option<(usize, Kv1, Kv2)>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		let removed = self.find_first(key).map(|idx| {
+			let (kv1, kv2) = self.shift_remove_at(idx);
+			(idx, kv1, kv2)
+		});
+		self.reclaim();
+		removed
+	}
+
+	/// Removes the pair corresponding to the second K/V from the map, preserving the order of the rest,
+	/// returning it with the position it had in iteration order.
+	///
+	/// The pairs after it each move down one position; see `shift_remove_index()`.
+	pub fn shift_remove_full_by_second<Q>(&mut self, key: &Q) -> Option<(usize, Kv1, Kv2)>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		let removed = self.find_second(key).map(|idx| {
+			let (kv1, kv2) = self.shift_remove_at(idx);
+			(idx, kv1, kv2)
+		});
+		self.reclaim();
+		removed
+	}
+}
+
+// Storage and index bookkeeping; every change to `cont` goes through these so the indexes and the generation stay in sync.
+#[cfg(feature = "alloc")]
+impl<Kv1, Kv2, S: Storage<Kv1, Kv2>> BidirMap<Kv1, Kv2, S> {
+	fn from_cont(cont: S) -> Self {
+		BidirMap{
+			cont,
+			#[cfg(feature = "std")]
+			index: None,
+			sorted_indexes: None,
+			generation: 0,
+			duplicate_policy: None,
+			max_len: None,
+			shrink_policy: ShrinkPolicy::Never,
+			stats: Counters::default(),
+		}
+	}
+
+	/// An empty map with this one's settings, indexes and statistics, as `clear()` would leave it, but without touching this one
+	fn empty_like(&self) -> Self
+		where S: Default,
+	{
+		let stats = self.stats.clone();
+		stats.removed(self.cont.len());
+		BidirMap{
+			cont: S::default(),
+			#[cfg(feature = "std")]
+			index: self.index.as_ref().map(|index| index.empty_like()),
+			sorted_indexes: self.sorted_indexes.as_ref().map(|indexes| indexes.empty_like()),
+			generation: self.generation.wrapping_add(1),
+			duplicate_policy: self.duplicate_policy,
+			max_len: self.max_len,
+			shrink_policy: self.shrink_policy,
+			stats,
+		}
+	}
+
+	/// Panics if adding a pair would grow the map past its `max_len`
+	fn assert_room(&self) {
+		if let Some(max_len) = self.max_len {
+			if self.cont.len() >= max_len {
+				panic!("BidirMap: {}, refusing to grow past max_len()", CapacityError{capacity: max_len});
+			}
+		}
+	}
+
+	/// Pushes a pair, which callers adding one that displaces none first check there's room for
+	fn push(&mut self, kv1: Kv1, kv2: Kv2) {
+		self.refresh_index();
+		self.generation = self.generation.wrapping_add(1);
+		self.stats.inserted();
+		self.cont.push((kv1, kv2));
+		#[cfg(feature = "std")]
+		{
+			if let Some(ref mut index) = self.index {
+				index.push(&self.cont, self.cont.len() - 1);
+			}
+		}
+		if let Some(ref mut indexes) = self.sorted_indexes {
+			indexes.push(&self.cont, self.cont.len() - 1);
+		}
+	}
+
+	fn swap_remove_at(&mut self, idx: usize) -> (Kv1, Kv2) {
+		self.refresh_index();
+		self.generation = self.generation.wrapping_add(1);
+		self.stats.removed(1);
+		#[cfg(feature = "std")]
+		{
+			if let Some(ref mut index) = self.index {
+				index.swap_remove(&self.cont, idx);
+			}
+		}
+		if let Some(ref mut indexes) = self.sorted_indexes {
+			indexes.swap_remove(&self.cont, idx);
+		}
+		self.cont.swap_remove(idx)
+	}
+
+	fn shift_remove_at(&mut self, idx: usize) -> (Kv1, Kv2) {
+		let kvs = self.cont.remove(idx);
+		self.stats.removed(1);
+		self.rebuild_index();
+		kvs
+	}
+
+	/// Call after removing pairs, to shrink the storage as the shrink policy says
+	fn reclaim(&mut self) {
+		if let Some(capacity) = self.shrink_policy.shrink_to(self.cont.len(), self.cont.capacity()) {
+			self.cont.shrink_to(capacity);
+		}
+	}
+
+	/// Call before handing out mutable references to keys
+	fn mark_index_stale(&mut self) {
+		self.generation = self.generation.wrapping_add(1);
+		#[cfg(feature = "std")]
+		{
+			if let Some(ref mut index) = self.index {
+				index.stale = true;
+			}
+		}
+		if let Some(ref mut indexes) = self.sorted_indexes {
+			indexes.stale = true;
+		}
+	}
+
+	/// Call after changes to `cont` that aren't tracked via `push()` and `swap_remove_at()`
+	fn rebuild_index(&mut self) {
+		self.generation = self.generation.wrapping_add(1);
+		#[cfg(feature = "std")]
+		{
+			if let Some(ref mut index) = self.index {
+				index.rebuild(&self.cont);
+			}
+		}
+		if let Some(ref mut indexes) = self.sorted_indexes {
+			indexes.rebuild(&self.cont);
+		}
+	}
+
+	fn refresh_index(&mut self) {
+		#[cfg(feature = "std")]
+		{
+			if let Some(ref mut index) = self.index {
+				if index.stale {
+					index.rebuild(&self.cont);
+				}
+			}
+		}
+		if let Some(ref mut indexes) = self.sorted_indexes {
+			if indexes.stale {
+				indexes.rebuild(&self.cont);
+			}
+		}
+	}
+}
+
+// Finding pairs, counted by the stats
+#[cfg(feature = "alloc")]
+impl<Kv1: PartialEq, Kv2: PartialEq, S: Storage<Kv1, Kv2>> BidirMap<Kv1, Kv2, S> {
+	fn position_of_first(&mut self, kv1: &Kv1) -> Option<usize> {
+		self.refresh_index();
+		#[cfg(feature = "std")]
+		{
+			if let Some(ref index) = self.index {
+				return index.position_by_first(&self.cont, index.hash_first(kv1), |k| k == kv1);
+			}
+		}
+		if let Some(ref indexes) = self.sorted_indexes {
+			return indexes.position_by_first(&self.cont, kv1);
+		}
+		let found = self.cont.iter().position(|kvs| kvs.0 == *kv1);
+		self.stats.compared(found.map_or(self.cont.len(), |idx| idx + 1));
+		found
+	}
+
+	fn position_of_second(&mut self, kv2: &Kv2) -> Option<usize> {
+		self.refresh_index();
+		#[cfg(feature = "std")]
+		{
+			if let Some(ref index) = self.index {
+				return index.position_by_second(&self.cont, index.hash_second(kv2), |k| k == kv2);
+			}
+		}
+		if let Some(ref indexes) = self.sorted_indexes {
+			return indexes.position_by_second(&self.cont, kv2);
+		}
+		let found = self.cont.iter().position(|kvs| kvs.1 == *kv2);
+		self.stats.compared(found.map_or(self.cont.len(), |idx| idx + 1));
+		found
+	}
+
+	/// The positions of the pairs holding `kv1` and `kv2`, found in a single scan without indexes
+	fn positions_of(&mut self, kv1: &Kv1, kv2: &Kv2) -> (Option<usize>, Option<usize>) {
+		#[cfg(feature = "std")]
+		{
+			if self.index.is_some() {
+				return (self.position_of_first(kv1), self.position_of_second(kv2));
+			}
+		}
+		if self.sorted_indexes.is_some() {
+			return (self.position_of_first(kv1), self.position_of_second(kv2));
+		}
+
+		let mut by_first = None;
+		let mut by_second = None;
+		let mut compared = 0;
+		for (idx, kvs) in self.cont.iter().enumerate() {
+			if by_first.is_none() {
+				compared += 1;
+				if kvs.0 == *kv1 {
+					by_first = Some(idx);
+				}
+			}
+			if by_second.is_none() {
+				compared += 1;
+				if kvs.1 == *kv2 {
+					by_second = Some(idx);
+				}
+			}
+			if by_first.is_some() && by_second.is_some() {
+				break;
+			}
+		}
+		self.stats.compared(compared);
+		(by_first, by_second)
+	}
+
+	/// The position of the pair with the specified first K/V, found through the indexes if `Q` is `Kv1`,
+	/// or by a scan counted only as comparisons
+	fn find_first<Q>(&self, key: &Q) -> Option<usize>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		#[cfg(feature = "std")]
+		{
+			if let Some(ref index) = self.index {
+				if !index.stale {
+					if let Some(hash) = with_key(key, |kv1: &Kv1| index.hash_first(kv1)) {
+						return index.position_by_first(&self.cont, hash, |k| *key == *k.borrow());
+					}
+				}
+			}
+		}
+		if let Some(ref indexes) = self.sorted_indexes {
+			if !indexes.stale {
+				if let Some(found) = with_key(key, |kv1: &Kv1| indexes.position_by_first(&self.cont, kv1)) {
+					return found;
+				}
+			}
+		}
+		let found = self.cont.iter().position(|kvs| *key == *kvs.0.borrow());
+		self.stats.compared(found.map_or(self.cont.len(), |idx| idx + 1));
+		found
+	}
+
+	/// The position of the pair with the specified second K/V, found through the indexes if `Q` is `Kv2`,
+	/// or by a scan counted only as comparisons
+	fn find_second<Q>(&self, key: &Q) -> Option<usize>
 		where Kv2: Borrow<Q>,
-		      Q  : PartialEq<Kv2>,
+		      Q  : PartialEq + ?Sized,
+	{
+		#[cfg(feature = "std")]
+		{
+			if let Some(ref index) = self.index {
+				if !index.stale {
+					if let Some(hash) = with_key(key, |kv2: &Kv2| index.hash_second(kv2)) {
+						return index.position_by_second(&self.cont, hash, |k| *key == *k.borrow());
+					}
+				}
+			}
+		}
+		if let Some(ref indexes) = self.sorted_indexes {
+			if !indexes.stale {
+				if let Some(found) = with_key(key, |kv2: &Kv2| indexes.position_by_second(&self.cont, kv2)) {
+					return found;
+				}
+			}
+		}
+		let found = self.cont.iter().position(|kvs| *key == *kvs.1.borrow());
+		self.stats.compared(found.map_or(self.cont.len(), |idx| idx + 1));
+		found
+	}
+
+	/// `find_first()`, counted as a lookup
+	fn lookup_first<Q>(&self, key: &Q) -> Option<usize>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
 	{
-		self.cont.iter().position(|ref kvs| *key == kvs.1).map(|idx| self.cont.swap_remove(idx))
+		let found = self.find_first(key);
+		self.stats.lookup(Column::First, found.is_some());
+		found
+	}
+
+	/// `find_second()`, counted as a lookup
+	fn lookup_second<Q>(&self, key: &Q) -> Option<usize>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		let found = self.find_second(key);
+		self.stats.lookup(Column::Second, found.is_some());
+		found
+	}
+}
+
+/// Calls `f` with `key` as a `&K` if `Q` is `K` itself, which lets the lookups by any `Q` use the indexes keyed by `K`
+///
+/// `f` only has the `&K` for the call, so it can't outlive `key`.
+#[cfg(feature = "alloc")]
+fn with_key<Q: ?Sized, K: Borrow<Q>, R, F: FnOnce(&K) -> R>(key: &Q, f: F) -> Option<R> {
+	if type_id::<Q>() == type_id::<K>() {
+		// SAFETY: `Q` and `K` have the same type but for lifetimes, so `Q` is sized too.
+		// For `K: Borrow<Q>` to hold then, `Q` must be `K` lifetimes included, as coherence rules out implementing `Borrow`
+		// between two types differing only in lifetimes next to std's `impl<T> Borrow<T> for T`
+		Some(f(unsafe { &*(key as *const Q as *const K) }))
+	} else {
+		None
+	}
+}
+
+/// `TypeId::of()` without the `'static` bound, the lifetimes making no difference to a `TypeId`
+#[cfg(feature = "alloc")]
+fn type_id<T: ?Sized>() -> TypeId {
+	trait NonStaticAny {
+		fn type_id(&self) -> TypeId where Self: 'static;
+	}
+
+	impl<T: ?Sized> NonStaticAny for PhantomData<T> {
+		fn type_id(&self) -> TypeId where Self: 'static {
+			TypeId::of::<T>()
+		}
+	}
+
+	let phantom = PhantomData::<T>;
+	let phantom: &dyn NonStaticAny = &phantom;
+	// SAFETY: the extended lifetime only lets the `TypeId` be read, with no value of `T` to outlive
+	unsafe { mem::transmute::<&dyn NonStaticAny, &(dyn NonStaticAny + 'static)>(phantom) }.type_id()
+}
+
+
+#[cfg(feature = "alloc")]
+impl<Kv1: fmt::Debug, Kv2: fmt::Debug, S: Storage<Kv1, Kv2>> fmt::Debug for BidirMap<Kv1, Kv2, S> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("BidirMap").field("cont", &&self.cont[..]).finish()
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<Kv1: PartialEq, Kv2: PartialEq, S: Storage<Kv1, Kv2>> PartialEq for BidirMap<Kv1, Kv2, S> {
+	fn eq(&self, other: &Self) -> bool {
+		self.cont[..] == other.cont[..]
 	}
 }
 
+#[cfg(feature = "alloc")]
+impl<Kv1: Eq, Kv2: Eq, S: Storage<Kv1, Kv2>> Eq for BidirMap<Kv1, Kv2, S> {}
 
-impl<Kv1: PartialEq, Kv2: PartialEq> IntoIterator for BidirMap<Kv1, Kv2> {
+#[cfg(feature = "alloc")]
+impl<Kv1: Hash, Kv2: Hash, S: Storage<Kv1, Kv2>> Hash for BidirMap<Kv1, Kv2, S> {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.cont[..].hash(state)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<Kv1, Kv2, S: Storage<Kv1, Kv2>> AsRef<[(Kv1, Kv2)]> for BidirMap<Kv1, Kv2, S> {
+	fn as_ref(&self) -> &[(Kv1, Kv2)] {
+		&self.cont
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<Kv1, Kv2> Default for BidirMap<Kv1, Kv2> {
+	fn default() -> Self {
+		BidirMap::new()
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<Kv1, Kv2, S: Storage<Kv1, Kv2>> IntoIterator for BidirMap<Kv1, Kv2, S> {
 	type Item = (Kv1, Kv2);
-	type IntoIter = vec::IntoIter<Self::Item>;
+	type IntoIter = S::IntoIter;
 
 	fn into_iter(self) -> Self::IntoIter {
-		return self.cont.into_iter()
+		self.cont.into_iter()
 	}
 }
 
-impl<Kv1: PartialEq, Kv2: PartialEq> FromIterator<(Kv1, Kv2)> for BidirMap<Kv1, Kv2> {
+#[cfg(feature = "alloc")]
+impl<Kv1, Kv2, S: Storage<Kv1, Kv2> + Default> FromIterator<(Kv1, Kv2)> for BidirMap<Kv1, Kv2, S> {
 	fn from_iter<T: IntoIterator<Item=(Kv1, Kv2)>>(iter: T) -> Self {
-		BidirMap{
-			cont: Vec::from_iter(iter),
+		let mut cont = S::default();
+		for kvs in iter {
+			cont.push(kvs);
 		}
+		BidirMap::from_cont(cont)
 	}
 }
 
-impl<Kv1: PartialEq, Kv2: PartialEq> Extend<(Kv1, Kv2)> for BidirMap<Kv1, Kv2> {
+#[cfg(feature = "alloc")]
+impl<Kv1: PartialEq, Kv2: PartialEq, S: Storage<Kv1, Kv2>> Extend<(Kv1, Kv2)> for BidirMap<Kv1, Kv2, S> {
 	fn extend<T: IntoIterator<Item=(Kv1, Kv2)>>(&mut self, iter: T) {
-		self.cont.extend(iter)
+		for (kv1, kv2) in iter {
+			if self.duplicate_policy.is_some() {
+				self.insert(kv1, kv2);
+			} else {
+				self.assert_room();
+				self.push(kv1, kv2);
+			}
+		}
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, Kv1: PartialEq + Clone, Kv2: PartialEq + Clone, S: Storage<Kv1, Kv2> + Default> FromIterator<(&'a Kv1, &'a Kv2)> for BidirMap<Kv1, Kv2, S> {
+	fn from_iter<T: IntoIterator<Item=(&'a Kv1, &'a Kv2)>>(iter: T) -> Self {
+		let mut map = BidirMap::with_storage(S::default());
+		map.extend(iter);
+		map
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, Kv1: PartialEq + Clone, Kv2: PartialEq + Clone, S: Storage<Kv1, Kv2> + Default> FromIterator<&'a (Kv1, Kv2)> for BidirMap<Kv1, Kv2, S> {
+	fn from_iter<T: IntoIterator<Item=&'a (Kv1, Kv2)>>(iter: T) -> Self {
+		let mut map = BidirMap::with_storage(S::default());
+		map.extend(iter);
+		map
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, Kv1: PartialEq + Clone, Kv2: PartialEq + Clone, S: Storage<Kv1, Kv2>> Extend<(&'a Kv1, &'a Kv2)> for BidirMap<Kv1, Kv2, S> {
+	fn extend<T: IntoIterator<Item=(&'a Kv1, &'a Kv2)>>(&mut self, iter: T) {
+		for (kv1, kv2) in iter {
+			self.insert(kv1.clone(), kv2.clone());
+		}
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, Kv1: PartialEq + Clone, Kv2: PartialEq + Clone, S: Storage<Kv1, Kv2>> Extend<&'a (Kv1, Kv2)> for BidirMap<Kv1, Kv2, S> {
+	fn extend<T: IntoIterator<Item=&'a (Kv1, Kv2)>>(&mut self, iter: T) {
+		for (kv1, kv2) in iter {
+			self.insert(kv1.clone(), kv2.clone());
+		}
 	}
 }
 
@@ -468,7 +1760,7 @@ pub struct FirstColumn<'a, Kv1: 'a, Kv2: 'a> {
 impl<'a, Kv1, Kv2> Iterator for FirstColumn<'a, Kv1, Kv2> {
 	type Item = &'a Kv1;
 	fn next(&mut self) -> Option<Self::Item> {
-		self.iter.next().map(|ref kvs| &kvs.0)
+		self.iter.next().map(|kvs| &kvs.0)
 	}
 }
 
@@ -483,6 +1775,41 @@ pub struct SecondColumn<'a, Kv1: 'a, Kv2: 'a> {
 impl<'a, Kv1, Kv2> Iterator for SecondColumn<'a, Kv1, Kv2> {
 	type Item = &'a Kv2;
 	fn next(&mut self) -> Option<Self::Item> {
-		self.iter.next().map(|ref kvs| &kvs.1)
+		self.iter.next().map(|kvs| &kvs.1)
+	}
+}
+
+
+/// An iterator over the K/V pairs of a map whose first or second K/V starts with a prefix, in iteration order.
+///
+/// See documentation of `BidirMap::iter_by_first_prefix()` for more.
+pub struct PrefixIter<'a, Kv1: 'a, Kv2: 'a> {
+	iter: slice::Iter<'a, (Kv1, Kv2)>,
+	prefix: &'a [u8],
+	key: fn(&(Kv1, Kv2)) -> &[u8],
+}
+
+impl<'a, Kv1, Kv2> Iterator for PrefixIter<'a, Kv1, Kv2> {
+	type Item = (&'a Kv1, &'a Kv2);
+	fn next(&mut self) -> Option<Self::Item> {
+		let (prefix, key) = (self.prefix, self.key);
+		self.iter.by_ref().find(|kvs| key(kvs).starts_with(prefix)).map(|kvs| (&kvs.0, &kvs.1))
+	}
+}
+
+
+/// An iterator over the K/V pairs of a map at a range of positions in an index, such as those whose second K/Vs start with a prefix.
+///
+/// See documentation of `BidirSortedMap::iter_by_second_prefix()` for more.
+pub struct IndexedPrefixIter<'a, Kv1: 'a, Kv2: 'a> {
+	indices: slice::Iter<'a, usize>,
+	cont: &'a [(Kv1, Kv2)],
+}
+
+impl<'a, Kv1, Kv2> Iterator for IndexedPrefixIter<'a, Kv1, Kv2> {
+	type Item = (&'a Kv1, &'a Kv2);
+	fn next(&mut self) -> Option<Self::Item> {
+		let cont = self.cont;
+		self.indices.next().map(|&idx| (&cont[idx].0, &cont[idx].1))
 	}
 }
@@ -0,0 +1,308 @@
+//! A `BidirMap` variant holding a bounded number of pairs, evicting the least recently used.
+
+
+use core::borrow::Borrow;
+use alloc::vec::Vec;
+use BidirMap;
+
+
+/// The end of the recency list
+const NIL: usize = usize::MAX;
+
+
+/// A bidirectional map of at most `capacity()` pairs, which evicts the least recently used pair when inserting into a full map.
+///
+/// A pair is used by being inserted, or found by `get_by_first()` or `get_by_second()`, which therefore take `&mut self`;
+/// `peek_by_first()` and `peek_by_second()` look pairs up without touching their recency.
+///
+/// Recency is tracked by a doubly linked list threaded through the pairs' storage,
+/// so refreshing a pair and evicting the least recently used one take `O(1)` once found.
+///
+/// Performance: `O(n)`, mostly; `O(1)` eviction.
+///
+/// # Examples
+///
+/// ```
+/// use bidir_map::LruBidirMap;
+///
+/// let mut cache = LruBidirMap::with_capacity(2);
+/// cache.insert(1, "a");
+/// cache.insert(2, "b");
+/// cache.get_by_second(&"a");
+/// assert_eq!(cache.insert(3, "c"), Some((2, "b")));
+/// ```
+#[derive(Clone, Debug)]
+pub struct LruBidirMap<Kv1: PartialEq, Kv2: PartialEq> {
+	nodes: Vec<Node<Kv1, Kv2>>,
+	/// The most recently used pair
+	head: usize,
+	/// The least recently used pair
+	tail: usize,
+	capacity: usize,
+}
+
+#[derive(Clone, Debug)]
+struct Node<Kv1, Kv2> {
+	kvs: (Kv1, Kv2),
+	/// The next more recently used pair
+	prev: usize,
+	/// The next less recently used pair
+	next: usize,
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> LruBidirMap<Kv1, Kv2> {
+	/// Create a new empty instance of `LruBidirMap`, which will hold at most `capacity` pairs.
+	///
+	/// # Panics
+	///
+	/// If `capacity` is 0.
+	pub fn with_capacity(capacity: usize) -> Self {
+		assert!(capacity != 0, "LruBidirMap must be able to hold a pair");
+		LruBidirMap{
+			nodes: Vec::with_capacity(capacity),
+			head: NIL,
+			tail: NIL,
+			capacity,
+		}
+	}
+
+	/// Returns the maximum number of pairs the map holds.
+	pub fn capacity(&self) -> usize {
+		self.capacity
+	}
+
+	/// Clears the map, removing all entries.
+	pub fn clear(&mut self) {
+		self.nodes.clear();
+		self.head = NIL;
+		self.tail = NIL;
+	}
+
+	/// Inserts a K/V-K/V pair into the map as its most recently used, returning a pair no longer in the map.
+	///
+	/// If either K/V was present, the pairs holding them are removed and one returned, as with `BidirMap::insert()`.
+	/// Otherwise, if the map was full, the least recently used pair is evicted and returned.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::LruBidirMap;
+	///
+	/// let mut map = LruBidirMap::with_capacity(2);
+	/// map.insert(1, "a");
+	/// map.insert(2, "b");
+	/// assert_eq!(map.insert(1, "c"), Some((1, "a")));   // replaced, nothing evicted
+	/// assert_eq!(map.insert(3, "d"), Some((2, "b")));   // evicted
+	/// ```
+	pub fn insert(&mut self, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)> {
+		let by_first = self.nodes.iter().position(|node| node.kvs.0 == kv1).map(|idx| self.remove_at(idx));
+		let by_second = self.nodes.iter().position(|node| node.kvs.1 == kv2).map(|idx| self.remove_at(idx));
+		let evicted = if self.nodes.len() == self.capacity {
+			let lru = self.tail;
+			Some(self.remove_at(lru))
+		} else {
+			None
+		};
+
+		self.nodes.push(Node{
+			kvs: (kv1, kv2),
+			prev: NIL,
+			next: NIL,
+		});
+		let idx = self.nodes.len() - 1;
+		self.push_front(idx);
+
+		by_first.or(by_second).or(evicted)
+	}
+
+	/// Gets an iterator over the entries of the map, most recently used first.
+	pub fn iter<'s>(&'s self) -> Iter<'s, Kv1, Kv2> {
+		Iter{
+			nodes: &self.nodes,
+			next: self.head,
+		}
+	}
+
+	/// Returns the number of elements in the map.
+	pub fn len(&self) -> usize {
+		self.nodes.len()
+	}
+
+	/// Returns true if the map contains no elements.
+	pub fn is_empty(&self) -> bool {
+		self.nodes.is_empty()
+	}
+
+	/// Returns the pair that'll be evicted next.
+	pub fn least_recently_used(&self) -> Option<&(Kv1, Kv2)> {
+		self.nodes.get(self.tail).map(|node| &node.kvs)
+	}
+
+
+	/// Returns a reference to the second K/V corresponding to the first K/V, marking the pair as most recently used.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::LruBidirMap;
+	///
+	/// let mut map = LruBidirMap::with_capacity(2);
+	/// map.insert(1, "a");
+	/// map.insert(2, "b");
+	/// assert_eq!(map.get_by_first(&1), Some(&"a"));
+	/// assert_eq!(map.least_recently_used(), Some(&(2, "b")));
+	/// ```
+	pub fn get_by_first<Q>(&mut self, key: &Q) -> Option<&Kv2>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		let idx = self.nodes.iter().position(|node| *key == *node.kvs.0.borrow())?;
+		self.touch(idx);
+		Some(&self.nodes[idx].kvs.1)
+	}
+
+	/// Returns a reference to the first K/V corresponding to the second K/V, marking the pair as most recently used.
+	pub fn get_by_second<Q>(&mut self, key: &Q) -> Option<&Kv1>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		let idx = self.nodes.iter().position(|node| *key == *node.kvs.1.borrow())?;
+		self.touch(idx);
+		Some(&self.nodes[idx].kvs.0)
+	}
+
+	/// Returns a reference to the second K/V corresponding to the first K/V, leaving recency untouched.
+	pub fn peek_by_first<Q>(&self, key: &Q) -> Option<&Kv2>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.nodes.iter().find(|node| *key == *node.kvs.0.borrow()).map(|node| &node.kvs.1)
+	}
+
+	/// Returns a reference to the first K/V corresponding to the second K/V, leaving recency untouched.
+	pub fn peek_by_second<Q>(&self, key: &Q) -> Option<&Kv1>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.nodes.iter().find(|node| *key == *node.kvs.1.borrow()).map(|node| &node.kvs.0)
+	}
+
+	/// Check if the map contains the first K/V, leaving recency untouched.
+	pub fn contains_first_key<Q>(&self, key: &Q) -> bool
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.peek_by_first(key).is_some()
+	}
+
+	/// Check if the map contains the second K/V, leaving recency untouched.
+	pub fn contains_second_key<Q>(&self, key: &Q) -> bool
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.peek_by_second(key).is_some()
+	}
+
+	/// Removes the pair corresponding to the first K/V from the map, returning it if the key was previously in the map.
+	pub fn remove_by_first<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.nodes.iter().position(|node| *key == *node.kvs.0.borrow()).map(|idx| self.remove_at(idx))
+	}
+
+	/// Removes the pair corresponding to the second K/V from the map, returning it if the key was previously in the map.
+	pub fn remove_by_second<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.nodes.iter().position(|node| *key == *node.kvs.1.borrow()).map(|idx| self.remove_at(idx))
+	}
+
+	fn touch(&mut self, idx: usize) {
+		if self.head != idx {
+			self.unlink(idx);
+			self.push_front(idx);
+		}
+	}
+
+	fn push_front(&mut self, idx: usize) {
+		self.nodes[idx].prev = NIL;
+		self.nodes[idx].next = self.head;
+		match self.head {
+			NIL => self.tail = idx,
+			head => self.nodes[head].prev = idx,
+		}
+		self.head = idx;
+	}
+
+	fn unlink(&mut self, idx: usize) {
+		let (prev, next) = (self.nodes[idx].prev, self.nodes[idx].next);
+		match prev {
+			NIL => self.head = next,
+			prev => self.nodes[prev].next = next,
+		}
+		match next {
+			NIL => self.tail = prev,
+			next => self.nodes[next].prev = prev,
+		}
+	}
+
+	/// `swap_remove()`s the node, re-pointing the neighbours of the one moved into its place
+	fn remove_at(&mut self, idx: usize) -> (Kv1, Kv2) {
+		self.unlink(idx);
+		let node = self.nodes.swap_remove(idx);
+		if idx != self.nodes.len() {
+			let (prev, next) = (self.nodes[idx].prev, self.nodes[idx].next);
+			match prev {
+				NIL => self.head = idx,
+				prev => self.nodes[prev].next = idx,
+			}
+			match next {
+				NIL => self.tail = idx,
+				next => self.nodes[next].prev = idx,
+			}
+		}
+		node.kvs
+	}
+}
+
+
+impl<'a, Kv1: PartialEq, Kv2: PartialEq> IntoIterator for &'a LruBidirMap<Kv1, Kv2> {
+	type Item = &'a (Kv1, Kv2);
+	type IntoIter = Iter<'a, Kv1, Kv2>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> From<LruBidirMap<Kv1, Kv2>> for BidirMap<Kv1, Kv2> {
+	/// The pairs are ordered most recently used first.
+	fn from(mut map: LruBidirMap<Kv1, Kv2>) -> Self {
+		let mut cont = Vec::with_capacity(map.len());
+		while !map.is_empty() {
+			let mru = map.head;
+			cont.push(map.remove_at(mru));
+		}
+		BidirMap::from_cont(cont)
+	}
+}
+
+
+/// An iterator over the K/V pairs contained in a `LruBidirMap`, most recently used first.
+///
+/// See documentation of `LruBidirMap::iter()` for more.
+pub struct Iter<'a, Kv1: 'a, Kv2: 'a> {
+	nodes: &'a [Node<Kv1, Kv2>],
+	next: usize,
+}
+
+impl<'a, Kv1, Kv2> Iterator for Iter<'a, Kv1, Kv2> {
+	type Item = &'a (Kv1, Kv2);
+	fn next(&mut self) -> Option<Self::Item> {
+		let node = self.nodes.get(self.next)?;
+		self.next = node.next;
+		Some(&node.kvs)
+	}
+}
@@ -0,0 +1,230 @@
+//! A `BidirMap` wrapper remembering the last successful lookup in each direction.
+
+
+use core::borrow::Borrow;
+use core::cell::Cell;
+use {BidirMap, Iter};
+
+
+/// A `BidirMap` which caches the position of the last pair found by each of `get_by_first()` and `get_by_second()`.
+///
+/// Repeated lookups of the same key then skip the linear scan.
+/// The cached positions are dropped by every mutating method, and are additionally checked against the key before being used,
+/// so a lookup never returns a pair other than the one `BidirMap` itself would.
+///
+/// The cache lives in `Cell`s, so unlike `BidirMap` this type isn't `Sync`.
+///
+/// # Examples
+///
+/// ```
+/// use bidir_map::MruBidirMap;
+///
+/// let mut map = MruBidirMap::new();
+/// map.insert(1, "a");
+/// map.insert(2, "b");
+///
+/// assert_eq!(map.get_by_first(&2), Some(&"b"));
+/// assert_eq!(map.get_by_first(&2), Some(&"b")); // answered from the cache
+/// ```
+#[derive(Clone, Debug)]
+pub struct MruBidirMap<Kv1: PartialEq, Kv2: PartialEq> {
+	map: BidirMap<Kv1, Kv2>,
+	last_first: Cell<Option<usize>>,
+	last_second: Cell<Option<usize>>,
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> MruBidirMap<Kv1, Kv2> {
+	/// Create a new empty instance of `MruBidirMap`
+	pub fn new() -> Self {
+		MruBidirMap::from(BidirMap::new())
+	}
+
+	/// Unwraps the underlying `BidirMap`.
+	pub fn into_inner(self) -> BidirMap<Kv1, Kv2> {
+		self.map
+	}
+
+	/// Gets a reference to the underlying `BidirMap`.
+	///
+	/// Lookups through it don't use or update the cache.
+	pub fn inner(&self) -> &BidirMap<Kv1, Kv2> {
+		&self.map
+	}
+
+	/// Gets a mutable reference to the underlying `BidirMap`, dropping the cached positions.
+	pub fn inner_mut(&mut self) -> &mut BidirMap<Kv1, Kv2> {
+		self.invalidate();
+		&mut self.map
+	}
+
+	/// Clears the map, removing all entries.
+	pub fn clear(&mut self) {
+		self.invalidate();
+		self.map.clear()
+	}
+
+	/// Inserts a K/V-K/V pair into the map, see `BidirMap::insert()`.
+	pub fn insert(&mut self, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)> {
+		self.invalidate();
+		self.map.insert(kv1, kv2)
+	}
+
+	/// Gets an iterator over the entries of the map.
+	pub fn iter<'s>(&'s self) -> Iter<'s, Kv1, Kv2> {
+		self.map.iter()
+	}
+
+	/// Returns the number of elements in the map.
+	pub fn len(&self) -> usize {
+		self.map.len()
+	}
+
+	/// Returns true if the map contains no elements.
+	pub fn is_empty(&self) -> bool {
+		self.map.is_empty()
+	}
+
+
+	/// Returns a reference to the second K/V corresponding to the first K/V, checking the last pair found this way first.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::MruBidirMap;
+	///
+	/// let mut map = MruBidirMap::new();
+	/// map.insert(1, "a");
+	/// assert_eq!(map.get_by_first(&1), Some(&"a"));
+	/// assert_eq!(map.get_by_first(&2), None);
+	/// ```
+	pub fn get_by_first<Q>(&self, key: &Q) -> Option<&Kv2>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		let cont = &self.map.cont;
+		if let Some(kvs) = self.last_first.get().and_then(|idx| cont.get(idx)) {
+			if *key == *kvs.0.borrow() {
+				return Some(&kvs.1);
+			}
+		}
+
+		let idx = cont.iter().position(|kvs| *key == *kvs.0.borrow())?;
+		self.last_first.set(Some(idx));
+		Some(&cont[idx].1)
+	}
+
+	/// Returns a reference to the first K/V corresponding to the second K/V, checking the last pair found this way first.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::MruBidirMap;
+	///
+	/// let mut map = MruBidirMap::new();
+	/// map.insert(1, "a");
+	/// assert_eq!(map.get_by_second(&"a"), Some(&1));
+	/// assert_eq!(map.get_by_second(&"b"), None);
+	/// ```
+	pub fn get_by_second<Q>(&self, key: &Q) -> Option<&Kv1>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		let cont = &self.map.cont;
+		if let Some(kvs) = self.last_second.get().and_then(|idx| cont.get(idx)) {
+			if *key == *kvs.1.borrow() {
+				return Some(&kvs.0);
+			}
+		}
+
+		let idx = cont.iter().position(|kvs| *key == *kvs.1.borrow())?;
+		self.last_second.set(Some(idx));
+		Some(&cont[idx].0)
+	}
+
+	/// Check if the map contains the first K/V, using and updating the cache.
+	pub fn contains_first_key<Q>(&self, key: &Q) -> bool
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.get_by_first(key).is_some()
+	}
+
+	/// Check if the map contains the second K/V, using and updating the cache.
+	pub fn contains_second_key<Q>(&self, key: &Q) -> bool
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.get_by_second(key).is_some()
+	}
+
+	/// Returns a mutable reference to the second K/V corresponding to the first K/V, dropping the cached positions.
+	pub fn get_mut_by_first<Q>(&mut self, key: &Q) -> Option<&mut Kv2>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.invalidate();
+		self.map.get_mut_by_first(key)
+	}
+
+	/// Returns a mutable reference to the first K/V corresponding to the second K/V, dropping the cached positions.
+	pub fn get_mut_by_second<Q>(&mut self, key: &Q) -> Option<&mut Kv1>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.invalidate();
+		self.map.get_mut_by_second(key)
+	}
+
+	/// Removes the pair corresponding to the first K/V from the map, see `BidirMap::remove_by_first()`.
+	pub fn remove_by_first<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.invalidate();
+		self.map.remove_by_first(key)
+	}
+
+	/// Removes the pair corresponding to the second K/V from the map, see `BidirMap::remove_by_second()`.
+	pub fn remove_by_second<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.invalidate();
+		self.map.remove_by_second(key)
+	}
+
+	fn invalidate(&mut self) {
+		self.last_first.set(None);
+		self.last_second.set(None);
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> From<BidirMap<Kv1, Kv2>> for MruBidirMap<Kv1, Kv2> {
+	fn from(map: BidirMap<Kv1, Kv2>) -> Self {
+		MruBidirMap{
+			map,
+			last_first: Cell::new(None),
+			last_second: Cell::new(None),
+		}
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> From<MruBidirMap<Kv1, Kv2>> for BidirMap<Kv1, Kv2> {
+	fn from(map: MruBidirMap<Kv1, Kv2>) -> Self {
+		map.into_inner()
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> Default for MruBidirMap<Kv1, Kv2> {
+	fn default() -> Self {
+		MruBidirMap::new()
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> PartialEq for MruBidirMap<Kv1, Kv2> {
+	fn eq(&self, other: &Self) -> bool {
+		self.map == other.map
+	}
+}
+
+impl<Kv1: Eq, Kv2: Eq> Eq for MruBidirMap<Kv1, Kv2> {}
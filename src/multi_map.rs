@@ -0,0 +1,336 @@
+//! A bidirectional map allowing several pairs to share a second K/V.
+
+
+use core::borrow::Borrow;
+use core::iter::{Extend, FromIterator};
+use core::slice;
+use alloc::vec::{self, Vec};
+use {BidirMap, FirstColumn, Iter, SecondColumn};
+
+
+/// A bidirectional map whose first K/Vs are unique, but whose second K/Vs may repeat.
+///
+/// That is, every first K/V maps to exactly one second K/V, and every second K/V to any number of first K/Vs
+/// (many aliases to one canonical name, say).
+///
+/// The interface is that of `BidirMap`, except:
+///
+///   * `insert()` only displaces the pair with the same first K/V, if any,
+///   * `get_by_second()` returns an iterator over all first K/Vs mapped to the second K/V,
+///   * `remove_by_second()` removes all pairs with the second K/V, returning them in a `Vec`,
+///   * there's no `get_mut_by_second()`, as there's no single K/V to return.
+///
+/// Performance: `O(n)`, mostly.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct BidirMultiMap<Kv1: PartialEq, Kv2: PartialEq> {
+	cont: Vec<(Kv1, Kv2)>,
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> BidirMultiMap<Kv1, Kv2> {
+	/// Create a new empty instance of `BidirMultiMap`
+	pub fn new() -> Self {
+		BidirMultiMap{
+			cont: Vec::new(),
+		}
+	}
+
+	/// Create a new empty instance of `BidirMultiMap` with the specified capacity.
+	///
+	/// It will be able to hold at least `capacity` elements without reallocating.
+	pub fn with_capacity(capacity: usize) -> Self {
+		BidirMultiMap{
+			cont: Vec::with_capacity(capacity),
+		}
+	}
+
+	/// Clears the map, removing all entries.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMultiMap;
+	///
+	/// let mut a = BidirMultiMap::new();
+	/// a.insert(1, "a");
+	/// a.clear();
+	/// assert!(a.is_empty());
+	/// ```
+	pub fn clear(&mut self) {
+		self.cont.clear()
+	}
+
+	/// Inserts a K/V-K/V pair into the map.
+	///
+	/// If the map had a pair with the same first K/V, it's replaced and returned; pairs sharing the second K/V are kept.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMultiMap;
+	///
+	/// let mut map = BidirMultiMap::new();
+	/// assert_eq!(map.insert("colour", "color"), None);
+	/// assert_eq!(map.insert("colr", "color"), None);
+	/// assert_eq!(map.insert("colr", "colour"), Some(("colr", "color")));
+	/// assert_eq!(map.len(), 2);
+	/// ```
+	pub fn insert(&mut self, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)> {
+		let retval = self.cont.iter().position(|kvs| kvs.0 == kv1).map(|idx| self.cont.swap_remove(idx));
+		self.cont.push((kv1, kv2));
+		retval
+	}
+
+	/// Gets an iterator over the entries of the map.
+	pub fn iter<'s>(&'s self) -> Iter<'s, Kv1, Kv2> {
+		Iter{
+			iter: self.cont.iter(),
+		}
+	}
+
+	/// Gets an iterator over the first K/V of the map.
+	pub fn first_col<'s>(&'s self) -> FirstColumn<'s, Kv1, Kv2> {
+		FirstColumn{
+			iter: self.cont.iter(),
+		}
+	}
+
+	/// Gets an iterator over the second K/V of the map, which may yield the same one several times.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMultiMap;
+	///
+	/// let mut a = BidirMultiMap::new();
+	/// a.insert(1, "a");
+	/// a.insert(2, "a");
+	///
+	/// let keys: Vec<_> = a.second_col().cloned().collect();
+	/// assert_eq!(keys, ["a", "a"]);
+	/// ```
+	pub fn second_col<'s>(&'s self) -> SecondColumn<'s, Kv1, Kv2> {
+		SecondColumn{
+			iter: self.cont.iter(),
+		}
+	}
+
+	/// Returns the number of pairs in the map.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMultiMap;
+	///
+	/// let mut a = BidirMultiMap::new();
+	/// assert_eq!(a.len(), 0);
+	/// a.insert(1, "a");
+	/// a.insert(2, "a");
+	/// assert_eq!(a.len(), 2);
+	/// ```
+	pub fn len(&self) -> usize {
+		self.cont.len()
+	}
+
+	/// Returns true if the map contains no elements.
+	pub fn is_empty(&self) -> bool {
+		self.cont.is_empty()
+	}
+
+
+	/// Returns a reference to the second K/V corresponding to the first K/V.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMultiMap;
+	///
+	/// let mut map = BidirMultiMap::new();
+	/// map.insert(1, "a");
+	/// assert_eq!(map.get_by_first(&1), Some(&"a"));
+	/// assert_eq!(map.get_by_first(&2), None);
+	/// ```
+	pub fn get_by_first<Q>(&self, key: &Q) -> Option<&Kv2>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.cont.iter().find(|kvs| *key == *kvs.0.borrow()).map(|kvs| &kvs.1)
+	}
+
+	/// Gets an iterator over the first K/Vs corresponding to the second K/V, in the map's order.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMultiMap;
+	///
+	/// let mut map = BidirMultiMap::new();
+	/// map.insert(1, "a");
+	/// map.insert(2, "b");
+	/// map.insert(3, "a");
+	///
+	/// let firsts: Vec<_> = map.get_by_second(&"a").cloned().collect();
+	/// assert_eq!(firsts, [1, 3]);
+	/// assert_eq!(map.get_by_second(&"c").next(), None);
+	/// ```
+	pub fn get_by_second<'s, 'q, Q>(&'s self, key: &'q Q) -> GetBySecond<'s, 'q, Kv1, Kv2, Q>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		GetBySecond{
+			iter: self.cont.iter(),
+			key,
+		}
+	}
+
+	/// Check if the map contains the first K/V
+	pub fn contains_first_key<Q>(&self, key: &Q) -> bool
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.cont.iter().any(|kvs| *key == *kvs.0.borrow())
+	}
+
+	/// Check if the map contains the second K/V
+	pub fn contains_second_key<Q>(&self, key: &Q) -> bool
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.cont.iter().any(|kvs| *key == *kvs.1.borrow())
+	}
+
+	/// Returns a mutable reference to the second K/V corresponding to the first K/V.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMultiMap;
+	///
+	/// let mut map = BidirMultiMap::new();
+	/// map.insert(1, "a");
+	/// if let Some(x) = map.get_mut_by_first(&1) {
+	///     *x = "b";
+	/// }
+	/// assert_eq!(map.get_by_first(&1), Some(&"b"));
+	/// ```
+	pub fn get_mut_by_first<Q>(&mut self, key: &Q) -> Option<&mut Kv2>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.cont.iter_mut().find(|kvs| *key == *kvs.0.borrow()).map(|kvs| &mut kvs.1)
+	}
+
+	/// Removes the pair corresponding to the first K/V from the map, returning it if the key was previously in the map.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMultiMap;
+	///
+	/// let mut map = BidirMultiMap::new();
+	/// map.insert(1, "a");
+	/// assert_eq!(map.remove_by_first(&1), Some((1, "a")));
+	/// assert_eq!(map.remove_by_first(&1), None);
+	/// ```
+	pub fn remove_by_first<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.cont.iter().position(|kvs| *key == *kvs.0.borrow()).map(|idx| self.cont.swap_remove(idx))
+	}
+
+	/// Removes all pairs corresponding to the second K/V from the map, returning them in the map's order.
+	///
+	/// The remaining pairs keep their relative order.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMultiMap;
+	///
+	/// let mut map = BidirMultiMap::new();
+	/// map.insert(1, "a");
+	/// map.insert(2, "b");
+	/// map.insert(3, "a");
+	/// assert_eq!(map.remove_by_second(&"a"), [(1, "a"), (3, "a")]);
+	/// assert_eq!(map.remove_by_second(&"a"), []);
+	/// assert_eq!(map.len(), 1);
+	/// ```
+	pub fn remove_by_second<Q>(&mut self, key: &Q) -> Vec<(Kv1, Kv2)>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		let (removed, kept) = self.cont.drain(..).partition(|kvs| *key == *kvs.1.borrow());
+		self.cont = kept;
+		removed
+	}
+}
+
+
+impl<Kv1: PartialEq, Kv2: PartialEq> Default for BidirMultiMap<Kv1, Kv2> {
+	fn default() -> Self {
+		BidirMultiMap::new()
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> IntoIterator for BidirMultiMap<Kv1, Kv2> {
+	type Item = (Kv1, Kv2);
+	type IntoIter = vec::IntoIter<(Kv1, Kv2)>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.cont.into_iter()
+	}
+}
+
+impl<'a, Kv1: PartialEq, Kv2: PartialEq> IntoIterator for &'a BidirMultiMap<Kv1, Kv2> {
+	type Item = &'a (Kv1, Kv2);
+	type IntoIter = Iter<'a, Kv1, Kv2>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> FromIterator<(Kv1, Kv2)> for BidirMultiMap<Kv1, Kv2> {
+	fn from_iter<T: IntoIterator<Item=(Kv1, Kv2)>>(iter: T) -> Self {
+		let mut map = BidirMultiMap::new();
+		map.extend(iter);
+		map
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> Extend<(Kv1, Kv2)> for BidirMultiMap<Kv1, Kv2> {
+	fn extend<T: IntoIterator<Item=(Kv1, Kv2)>>(&mut self, iter: T) {
+		for (kv1, kv2) in iter {
+			self.insert(kv1, kv2);
+		}
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> From<BidirMap<Kv1, Kv2>> for BidirMultiMap<Kv1, Kv2> {
+	/// Every `BidirMap` is a valid `BidirMultiMap`, so this just takes over its pairs.
+	fn from(map: BidirMap<Kv1, Kv2>) -> Self {
+		BidirMultiMap{
+			cont: map.cont,
+		}
+	}
+}
+
+
+/// An iterator over the first K/Vs mapped to a second K/V in a `BidirMultiMap`.
+///
+/// See documentation of `BidirMultiMap::get_by_second()` for more.
+pub struct GetBySecond<'a, 'q, Kv1: 'a, Kv2: 'a, Q: 'q + ?Sized> {
+	iter: slice::Iter<'a, (Kv1, Kv2)>,
+	key: &'q Q,
+}
+
+impl<'a, 'q, Kv1, Kv2, Q> Iterator for GetBySecond<'a, 'q, Kv1, Kv2, Q>
+	where Kv2: Borrow<Q>,
+	      Q  : PartialEq + ?Sized,
+{
+	type Item = &'a Kv1;
+	fn next(&mut self) -> Option<Self::Item> {
+		let key = self.key;
+		self.iter.find(|kvs| *key == *kvs.1.borrow()).map(|kvs| &kvs.0)
+	}
+}
@@ -0,0 +1,208 @@
+//! A `BidirMap` wrapper reporting every change made through it to an observer.
+
+
+use core::borrow::Borrow;
+use core::iter::Extend;
+use {BidirMap, Iter};
+
+
+/// A change to an `ObservedBidirMap`, with references to the pairs affected.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum MapEvent<Kv1, Kv2> {
+	/// The pair was inserted, displacing none
+	Inserted(Kv1, Kv2),
+	/// The pair was removed, either outright or by an insertion which already reported another pair replaced
+	Removed(Kv1, Kv2),
+	/// The `old` pair, sharing a K/V with the inserted `new` one, was displaced by it
+	Replaced{
+		old: (Kv1, Kv2),
+		new: (Kv1, Kv2),
+	},
+	/// The pair was removed by `clear()`
+	Cleared(Kv1, Kv2),
+}
+
+/// A `BidirMap` calling an observer once for each pair affected by each mutation.
+///
+/// When an insertion displaces two pairs, the one holding the first K/V is reported `Replaced`
+/// and the one holding the second K/V `Removed`.
+///
+/// # Examples
+///
+/// ```
+/// use bidir_map::{BidirMap, MapEvent};
+///
+/// let mut log = Vec::new();
+/// {
+///     let mut map = BidirMap::new().observed(|event: MapEvent<&i32, &char>| log.push(format!("{:?}", event)));
+///     map.insert(1, 'a');
+///     map.insert(1, 'b');
+///     map.clear();
+/// }
+/// assert_eq!(log, ["Inserted(1, 'a')", "Replaced { old: (1, 'a'), new: (1, 'b') }", "Cleared(1, 'b')"]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ObservedBidirMap<Kv1: PartialEq, Kv2: PartialEq, F: FnMut(MapEvent<&Kv1, &Kv2>)> {
+	map: BidirMap<Kv1, Kv2>,
+	observer: F,
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
+	/// Wraps the map into an `ObservedBidirMap` reporting changes to `observer`.
+	///
+	/// The pairs already in the map aren't reported.
+	pub fn observed<F: FnMut(MapEvent<&Kv1, &Kv2>)>(self, observer: F) -> ObservedBidirMap<Kv1, Kv2, F> {
+		ObservedBidirMap{
+			map: self,
+			observer,
+		}
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq, F: FnMut(MapEvent<&Kv1, &Kv2>)> ObservedBidirMap<Kv1, Kv2, F> {
+	/// Replaces the observer, returning the previous one.
+	pub fn set_observer(&mut self, observer: F) -> F {
+		core::mem::replace(&mut self.observer, observer)
+	}
+
+	/// Unwraps the underlying `BidirMap` and the observer.
+	pub fn into_parts(self) -> (BidirMap<Kv1, Kv2>, F) {
+		(self.map, self.observer)
+	}
+
+	/// Gets a reference to the underlying `BidirMap`.
+	pub fn inner(&self) -> &BidirMap<Kv1, Kv2> {
+		&self.map
+	}
+
+	/// Clears the map, reporting each pair `Cleared`.
+	pub fn clear(&mut self) {
+		for (kv1, kv2) in &self.map.cont {
+			(self.observer)(MapEvent::Cleared(kv1, kv2));
+		}
+		self.map.clear();
+	}
+
+	/// Inserts a K/V-K/V pair into the map, as `BidirMap::insert()` does.
+	pub fn insert(&mut self, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)> {
+		let by_first = self.map.position_of_first(&kv1).map(|idx| self.map.swap_remove_at(idx));
+		let by_second = self.map.position_of_second(&kv2).map(|idx| self.map.swap_remove_at(idx));
+		self.map.push(kv1, kv2);
+
+		let new = self.map.cont.last().unwrap();
+		match (&by_first, &by_second) {
+			(None, None) => (self.observer)(MapEvent::Inserted(&new.0, &new.1)),
+			(Some(old), None) | (None, Some(old)) => {
+				(self.observer)(MapEvent::Replaced{
+					old: (&old.0, &old.1),
+					new: (&new.0, &new.1),
+				})
+			}
+			(Some(old), Some(other)) => {
+				(self.observer)(MapEvent::Replaced{
+					old: (&old.0, &old.1),
+					new: (&new.0, &new.1),
+				});
+				(self.observer)(MapEvent::Removed(&other.0, &other.1));
+			}
+		}
+
+		by_first.or(by_second)
+	}
+
+	/// Removes the pair with the first K/V, reporting it `Removed`.
+	pub fn remove_by_first<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		let kvs = self.map.remove_by_first(key);
+		self.report_removed(kvs)
+	}
+
+	/// Removes the pair with the second K/V, reporting it `Removed`.
+	pub fn remove_by_second<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		let kvs = self.map.remove_by_second(key);
+		self.report_removed(kvs)
+	}
+
+	/// Retains only the pairs for which `f` returns `true`, reporting the others `Removed`.
+	///
+	/// The retained pairs keep their order.
+	pub fn retain<R: FnMut(&Kv1, &Kv2) -> bool>(&mut self, mut f: R) {
+		let observer = &mut self.observer;
+		self.map.cont.retain(|kvs| {
+			let keep = f(&kvs.0, &kvs.1);
+			if !keep {
+				observer(MapEvent::Removed(&kvs.0, &kvs.1));
+			}
+			keep
+		});
+		self.map.rebuild_index();
+	}
+
+	/// Returns a reference to the second K/V corresponding to the first K/V.
+	pub fn get_by_first<Q>(&self, key: &Q) -> Option<&Kv2>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.get_by_first(key)
+	}
+
+	/// Returns a reference to the first K/V corresponding to the second K/V.
+	pub fn get_by_second<Q>(&self, key: &Q) -> Option<&Kv1>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.get_by_second(key)
+	}
+
+	/// Check if the map contains the first K/V
+	pub fn contains_first_key<Q>(&self, key: &Q) -> bool
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.contains_first_key(key)
+	}
+
+	/// Check if the map contains the second K/V
+	pub fn contains_second_key<Q>(&self, key: &Q) -> bool
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.contains_second_key(key)
+	}
+
+	/// Gets an iterator over the entries of the map.
+	pub fn iter<'s>(&'s self) -> Iter<'s, Kv1, Kv2> {
+		self.map.iter()
+	}
+
+	/// Returns the number of elements in the map.
+	pub fn len(&self) -> usize {
+		self.map.len()
+	}
+
+	/// Returns true if the map contains no elements.
+	pub fn is_empty(&self) -> bool {
+		self.map.is_empty()
+	}
+
+	fn report_removed(&mut self, kvs: Option<(Kv1, Kv2)>) -> Option<(Kv1, Kv2)> {
+		if let Some((ref kv1, ref kv2)) = kvs {
+			(self.observer)(MapEvent::Removed(kv1, kv2));
+		}
+		kvs
+	}
+}
+
+/// Inserts each pair as by `insert()`, unlike `BidirMap`'s `extend()`, so that displaced pairs get reported.
+impl<Kv1: PartialEq, Kv2: PartialEq, F: FnMut(MapEvent<&Kv1, &Kv2>)> Extend<(Kv1, Kv2)> for ObservedBidirMap<Kv1, Kv2, F> {
+	fn extend<T: IntoIterator<Item=(Kv1, Kv2)>>(&mut self, iter: T) {
+		for (kv1, kv2) in iter {
+			self.insert(kv1, kv2);
+		}
+	}
+}
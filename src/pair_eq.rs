@@ -0,0 +1,91 @@
+//! Comparing `BidirMap`s with lists of pairs, see `BidirMap::eq_unordered()`.
+
+
+use alloc::vec::Vec;
+use BidirMap;
+use storage::Storage;
+
+
+impl<Kv1: PartialEq, Kv2: PartialEq, S: Storage<Kv1, Kv2>> BidirMap<Kv1, Kv2, S> {
+	/// Check if the map holds exactly the pairs, in the same order.
+	///
+	/// Comparing the map with `==` against a slice, an array or a `Vec` of pairs ignores their order,
+	/// see `eq_unordered()`; this is its order-sensitive counterpart, as `==` between two maps is.
+	///
+	/// Performance: `O(n)`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let map: BidirMap<_, _> = vec![(1, 'a'), (2, 'b')].into_iter().collect();
+	/// assert!(map.eq_ordered(&[(1, 'a'), (2, 'b')]));
+	/// assert!(!map.eq_ordered(&[(2, 'b'), (1, 'a')]));
+	/// assert_eq!(map, [(2, 'b'), (1, 'a')]);
+	/// ```
+	pub fn eq_ordered<P: AsRef<[(Kv1, Kv2)]> + ?Sized>(&self, pairs: &P) -> bool {
+		self.cont[..] == *pairs.as_ref()
+	}
+
+	/// Check if the map holds exactly the pairs, in any order.
+	///
+	/// This is what `==` against a slice, an array or a `Vec` of pairs, on either side, compares.
+	/// `==` between two maps compares their pairs in order, as a `Vec`'s; pass the other map here to ignore it.
+	/// Since the map's pairs are unique, a list holding some pair twice never equals it.
+	///
+	/// Performance: `O(n²)`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let map: BidirMap<_, _> = vec![(1, 'a'), (2, 'b')].into_iter().collect();
+	/// let reordered: BidirMap<_, _> = vec![(2, 'b'), (1, 'a')].into_iter().collect();
+	/// assert!(map != reordered);
+	/// assert!(map.eq_unordered(&reordered));
+	/// assert!(map.eq_unordered(&[(2, 'b'), (1, 'a')]));
+	/// ```
+	pub fn eq_unordered<P: AsRef<[(Kv1, Kv2)]> + ?Sized>(&self, pairs: &P) -> bool {
+		let pairs = pairs.as_ref();
+		self.cont.len() == pairs.len() && self.cont.iter().all(|kvs| pairs.contains(kvs))
+	}
+}
+
+
+impl<Kv1: PartialEq, Kv2: PartialEq, S: Storage<Kv1, Kv2>> PartialEq<[(Kv1, Kv2)]> for BidirMap<Kv1, Kv2, S> {
+	fn eq(&self, other: &[(Kv1, Kv2)]) -> bool {
+		self.eq_unordered(other)
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq, S: Storage<Kv1, Kv2>, const N: usize> PartialEq<[(Kv1, Kv2); N]> for BidirMap<Kv1, Kv2, S> {
+	fn eq(&self, other: &[(Kv1, Kv2); N]) -> bool {
+		self.eq_unordered(other)
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq, S: Storage<Kv1, Kv2>> PartialEq<Vec<(Kv1, Kv2)>> for BidirMap<Kv1, Kv2, S> {
+	fn eq(&self, other: &Vec<(Kv1, Kv2)>) -> bool {
+		self.eq_unordered(other)
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq, S: Storage<Kv1, Kv2>> PartialEq<BidirMap<Kv1, Kv2, S>> for [(Kv1, Kv2)] {
+	fn eq(&self, other: &BidirMap<Kv1, Kv2, S>) -> bool {
+		other.eq_unordered(self)
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq, S: Storage<Kv1, Kv2>, const N: usize> PartialEq<BidirMap<Kv1, Kv2, S>> for [(Kv1, Kv2); N] {
+	fn eq(&self, other: &BidirMap<Kv1, Kv2, S>) -> bool {
+		other.eq_unordered(self)
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq, S: Storage<Kv1, Kv2>> PartialEq<BidirMap<Kv1, Kv2, S>> for Vec<(Kv1, Kv2)> {
+	fn eq(&self, other: &BidirMap<Kv1, Kv2, S>) -> bool {
+		other.eq_unordered(self)
+	}
+}
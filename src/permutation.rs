@@ -0,0 +1,168 @@
+//! Treating a `BidirMap` with the same type in both columns as a renaming or a permutation.
+
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+use BidirMap;
+
+
+impl<K: PartialEq> BidirMap<K, K> {
+	/// Follows the chain of renamings starting at `start`, mapping each K/V as a first K/V to its second one,
+	/// and returns the K/Vs reached, at most `max_steps` of them.
+	///
+	/// The chain ends at the first K/V that isn't a first K/V of any pair.
+	/// If it leads back to `start`, that's the last K/V returned, closing the cycle;
+	/// since every K/V has a single predecessor, no other K/V can be reached twice.
+	///
+	/// Performance: `O(n)` per step.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let renames: BidirMap<_, _> = vec![("a", "b"), ("b", "c"), ("x", "y"), ("y", "x")].into_iter().collect();
+	/// assert_eq!(renames.follow_first(&"a", 10), [&"b", &"c"]);
+	/// assert_eq!(renames.follow_first(&"a", 1), [&"b"]);
+	/// assert_eq!(renames.follow_first(&"x", 10), [&"y", &"x"]);
+	/// assert!(renames.follow_first(&"c", 10).is_empty());
+	/// ```
+	pub fn follow_first<'s>(&'s self, start: &K, max_steps: usize) -> Vec<&'s K> {
+		let mut chain = Vec::new();
+		let mut next = self.get_by_first(start);
+		while let Some(kv) = next {
+			if chain.len() == max_steps {
+				break;
+			}
+			chain.push(kv);
+			if kv == start {
+				break;
+			}
+			next = self.get_by_first(kv);
+		}
+		chain
+	}
+
+	/// Follows the chain of renamings backwards from `start`, mapping each K/V as a second K/V to its first one,
+	/// like `follow_first()` does forwards.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let renames: BidirMap<_, _> = vec![("a", "b"), ("b", "c")].into_iter().collect();
+	/// assert_eq!(renames.follow_second(&"c", 10), [&"b", &"a"]);
+	/// ```
+	pub fn follow_second<'s>(&'s self, start: &K, max_steps: usize) -> Vec<&'s K> {
+		let mut chain = Vec::new();
+		let mut next = self.get_by_second(start);
+		while let Some(kv) = next {
+			if chain.len() == max_steps {
+				break;
+			}
+			chain.push(kv);
+			if kv == start {
+				break;
+			}
+			next = self.get_by_second(kv);
+		}
+		chain
+	}
+
+	/// Returns the cycle through `start`, beginning with it, if following it with `follow_first()` leads back to it.
+	///
+	/// A pair mapping a K/V to itself is a cycle of one.
+	///
+	/// Performance: `O(n)` per K/V of the cycle.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let renames: BidirMap<_, _> = vec![(1, 2), (2, 3), (3, 1), (4, 5), (6, 6)].into_iter().collect();
+	/// assert_eq!(renames.cycle_of(&2), Some(vec![&2, &3, &1]));
+	/// assert_eq!(renames.cycle_of(&6), Some(vec![&6]));
+	/// assert_eq!(renames.cycle_of(&4), None);
+	/// ```
+	pub fn cycle_of<'s>(&'s self, start: &K) -> Option<Vec<&'s K>> {
+		let mut chain = self.follow_first(start, self.len());
+		match chain.pop() {
+			Some(last) if last == start => {
+				chain.insert(0, last);
+				Some(chain)
+			}
+			_ => None,
+		}
+	}
+
+	/// Check if the map is a permutation, its second K/Vs being exactly its first K/Vs.
+	///
+	/// Performance: `O(n log n)`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let swap: BidirMap<_, _> = vec![(1, 2), (2, 1), (3, 3)].into_iter().collect();
+	/// assert!(swap.is_permutation());
+	///
+	/// let renames: BidirMap<_, _> = vec![(1, 2), (2, 3)].into_iter().collect();
+	/// assert!(!renames.is_permutation());
+	/// ```
+	pub fn is_permutation(&self) -> bool
+		where K: Ord,
+	{
+		let firsts: BTreeSet<&K> = self.first_col().collect();
+		let seconds: BTreeSet<&K> = self.second_col().collect();
+		firsts == seconds
+	}
+
+	/// Decomposes a permutation into its cycles, or returns `None` if the map isn't one (see `is_permutation()`).
+	///
+	/// The cycles are ordered by where their first K/V is in iteration order, the earliest of each cycle,
+	/// and each continues in the order of `follow_first()`.
+	/// Every K/V is in exactly one cycle; ones mapped to themselves are cycles of one.
+	///
+	/// Performance: `O(n log n)`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let perm: BidirMap<_, _> = vec![(1, 3), (2, 2), (3, 4), (4, 1)].into_iter().collect();
+	/// assert_eq!(perm.cycles(), Some(vec![vec![&1, &3, &4], vec![&2]]));
+	/// ```
+	pub fn cycles(&self) -> Option<Vec<Vec<&K>>>
+		where K: Ord,
+	{
+		if !self.is_permutation() {
+			return None;
+		}
+
+		let successors: BTreeMap<&K, &K> = self.cont.iter().map(|kvs| (&kvs.0, &kvs.1)).collect();
+		let mut seen = BTreeSet::new();
+		let mut cycles = Vec::new();
+		for kvs in &self.cont {
+			if seen.contains(&kvs.0) {
+				continue;
+			}
+
+			let mut cycle = Vec::new();
+			let mut kv = &kvs.0;
+			loop {
+				seen.insert(kv);
+				cycle.push(kv);
+				kv = successors[kv];
+				if *kv == kvs.0 {
+					break;
+				}
+			}
+			cycles.push(cycle);
+		}
+		Some(cycles)
+	}
+}
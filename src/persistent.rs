@@ -0,0 +1,324 @@
+//! A `BidirMap` variant with structural sharing between clones.
+
+
+use core::borrow::Borrow;
+use core::iter::FromIterator;
+use core::slice;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use BidirMap;
+
+
+/// The number of pairs per chunk, the unit of copying on mutation
+const CHUNK: usize = 64;
+
+type Chunk<Kv1, Kv2> = Arc<Vec<(Kv1, Kv2)>>;
+
+
+/// A bidirectional map whose clones share their storage until they're modified.
+///
+/// The pairs are stored in reference-counted chunks of 64, so `clone()` takes `O(1)`,
+/// and the first mutation of a clone copies the list of chunks and each chunk it touches, rather than the whole map.
+/// That makes it cheap to keep snapshots around, say one per transaction.
+///
+/// The behaviour is otherwise that of `BidirMap`, including the order pairs end up in; modifying requires `Clone` on both K/Vs.
+///
+/// Performance: `O(n)`, mostly; `O(1)` clones.
+///
+/// # Examples
+///
+/// ```
+/// use bidir_map::PersistentBidirMap;
+///
+/// let mut map = PersistentBidirMap::new();
+/// map.insert(1, "a");
+///
+/// let snapshot = map.clone();
+/// map.insert(2, "b");
+/// assert_eq!(snapshot.len(), 1);
+/// assert_eq!(map.len(), 2);
+/// ```
+#[derive(Clone, Debug)]
+pub struct PersistentBidirMap<Kv1: PartialEq, Kv2: PartialEq> {
+	/// Every chunk but the last is full
+	chunks: Arc<Vec<Chunk<Kv1, Kv2>>>,
+	len: usize,
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> PersistentBidirMap<Kv1, Kv2> {
+	/// Create a new empty instance of `PersistentBidirMap`
+	pub fn new() -> Self {
+		PersistentBidirMap{
+			chunks: Arc::new(Vec::new()),
+			len: 0,
+		}
+	}
+
+	/// Gets an iterator over the entries of the map.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::PersistentBidirMap;
+	///
+	/// let mut map = PersistentBidirMap::new();
+	/// map.insert(1, "a");
+	/// map.insert(2, "b");
+	///
+	/// let first = map.iter().next().unwrap();
+	/// assert_eq!(*first, (1, "a"));
+	/// ```
+	pub fn iter<'s>(&'s self) -> Iter<'s, Kv1, Kv2> {
+		Iter{
+			chunks: self.chunks.iter(),
+			chunk: [].iter(),
+		}
+	}
+
+	/// Returns the number of elements in the map.
+	pub fn len(&self) -> usize {
+		self.len
+	}
+
+	/// Returns true if the map contains no elements.
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	/// Returns how many chunks of storage this map shares with the other one, so far unmodified by either.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::PersistentBidirMap;
+	///
+	/// let map: PersistentBidirMap<_, _> = (0..1000).map(|i| (i, -i)).collect();
+	/// let mut snapshot = map.clone();
+	/// assert_eq!(snapshot.shared_chunks(&map), 16);
+	///
+	/// snapshot.insert(1000, -1000);
+	/// assert_eq!(snapshot.shared_chunks(&map), 15);
+	/// ```
+	pub fn shared_chunks(&self, other: &Self) -> usize {
+		self.chunks.iter().zip(other.chunks.iter()).filter(|&(l, r)| Arc::ptr_eq(l, r)).count()
+	}
+
+
+	/// Returns a reference to the second K/V corresponding to the first K/V.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::PersistentBidirMap;
+	///
+	/// let mut map = PersistentBidirMap::new();
+	/// map.insert(1, "a");
+	/// assert_eq!(map.get_by_first(&1), Some(&"a"));
+	/// assert_eq!(map.get_by_first(&2), None);
+	/// ```
+	pub fn get_by_first<Q>(&self, key: &Q) -> Option<&Kv2>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.iter().find(|kvs| *key == *kvs.0.borrow()).map(|kvs| &kvs.1)
+	}
+
+	/// Returns a reference to the first K/V corresponding to the second K/V.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::PersistentBidirMap;
+	///
+	/// let mut map = PersistentBidirMap::new();
+	/// map.insert(1, "a");
+	/// assert_eq!(map.get_by_second(&"a"), Some(&1));
+	/// assert_eq!(map.get_by_second(&"b"), None);
+	/// ```
+	pub fn get_by_second<Q>(&self, key: &Q) -> Option<&Kv1>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.iter().find(|kvs| *key == *kvs.1.borrow()).map(|kvs| &kvs.0)
+	}
+
+	/// Check if the map contains the first K/V
+	pub fn contains_first_key<Q>(&self, key: &Q) -> bool
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.get_by_first(key).is_some()
+	}
+
+	/// Check if the map contains the second K/V
+	pub fn contains_second_key<Q>(&self, key: &Q) -> bool
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.get_by_second(key).is_some()
+	}
+}
+
+impl<Kv1: PartialEq + Clone, Kv2: PartialEq + Clone> PersistentBidirMap<Kv1, Kv2> {
+	/// Clears the map, removing all entries.
+	///
+	/// Other clones keep their pairs.
+	pub fn clear(&mut self) {
+		*self = PersistentBidirMap::new();
+	}
+
+	/// Inserts a K/V-K/V pair into the map, see `BidirMap::insert()`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::PersistentBidirMap;
+	///
+	/// let mut map = PersistentBidirMap::new();
+	/// map.insert(1, "a");
+	/// map.insert(2, "b");
+	/// assert_eq!(map.insert(1, "b"), Some((1, "a")));
+	/// assert_eq!(map.len(), 1);
+	/// ```
+	pub fn insert(&mut self, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)> {
+		let by_first = self.iter().position(|kvs| kvs.0 == kv1).map(|idx| self.swap_remove_at(idx));
+		let by_second = self.iter().position(|kvs| kvs.1 == kv2).map(|idx| self.swap_remove_at(idx));
+		self.push(kv1, kv2);
+		by_first.or(by_second)
+	}
+
+	/// Removes the pair corresponding to the first K/V from the map, returning it if the key was previously in the map.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::PersistentBidirMap;
+	///
+	/// let mut map = PersistentBidirMap::new();
+	/// map.insert(1, "a");
+	/// assert_eq!(map.remove_by_first(&1), Some((1, "a")));
+	/// assert_eq!(map.remove_by_first(&1), None);
+	/// ```
+	pub fn remove_by_first<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.iter().position(|kvs| *key == *kvs.0.borrow()).map(|idx| self.swap_remove_at(idx))
+	}
+
+	/// Removes the pair corresponding to the second K/V from the map, returning it if the key was previously in the map.
+	pub fn remove_by_second<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.iter().position(|kvs| *key == *kvs.1.borrow()).map(|idx| self.swap_remove_at(idx))
+	}
+
+	fn push(&mut self, kv1: Kv1, kv2: Kv2) {
+		let chunks = Arc::make_mut(&mut self.chunks);
+		if self.len.is_multiple_of(CHUNK) {
+			let mut chunk = Vec::with_capacity(CHUNK);
+			chunk.push((kv1, kv2));
+			chunks.push(Arc::new(chunk));
+		} else {
+			Arc::make_mut(chunks.last_mut().unwrap()).push((kv1, kv2));
+		}
+		self.len += 1;
+	}
+
+	/// Same as `Vec::swap_remove()` over the concatenated chunks, touching at most the two affected ones
+	fn swap_remove_at(&mut self, idx: usize) -> (Kv1, Kv2) {
+		let chunks = Arc::make_mut(&mut self.chunks);
+		let last = {
+			let last_chunk = Arc::make_mut(chunks.last_mut().unwrap());
+			last_chunk.pop().unwrap()
+		};
+		if chunks.last().unwrap().is_empty() {
+			chunks.pop();
+		}
+		self.len -= 1;
+
+		if idx == self.len {
+			last
+		} else {
+			let removed = &mut Arc::make_mut(&mut chunks[idx / CHUNK])[idx % CHUNK];
+			core::mem::replace(removed, last)
+		}
+	}
+}
+
+
+impl<Kv1: PartialEq, Kv2: PartialEq> Default for PersistentBidirMap<Kv1, Kv2> {
+	fn default() -> Self {
+		PersistentBidirMap::new()
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> PartialEq for PersistentBidirMap<Kv1, Kv2> {
+	fn eq(&self, other: &Self) -> bool {
+		self.len == other.len && self.iter().eq(other.iter())
+	}
+}
+
+impl<Kv1: Eq, Kv2: Eq> Eq for PersistentBidirMap<Kv1, Kv2> {}
+
+impl<'a, Kv1: PartialEq, Kv2: PartialEq> IntoIterator for &'a PersistentBidirMap<Kv1, Kv2> {
+	type Item = &'a (Kv1, Kv2);
+	type IntoIter = Iter<'a, Kv1, Kv2>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+impl<Kv1: PartialEq + Clone, Kv2: PartialEq + Clone> FromIterator<(Kv1, Kv2)> for PersistentBidirMap<Kv1, Kv2> {
+	/// Like `BidirMap`'s, this doesn't check for duplicates.
+	fn from_iter<T: IntoIterator<Item=(Kv1, Kv2)>>(iter: T) -> Self {
+		let mut map = PersistentBidirMap::new();
+		for (kv1, kv2) in iter {
+			map.push(kv1, kv2);
+		}
+		map
+	}
+}
+
+impl<Kv1: PartialEq + Clone, Kv2: PartialEq + Clone> From<BidirMap<Kv1, Kv2>> for PersistentBidirMap<Kv1, Kv2> {
+	fn from(map: BidirMap<Kv1, Kv2>) -> Self {
+		map.into_iter().collect()
+	}
+}
+
+impl<Kv1: PartialEq + Clone, Kv2: PartialEq + Clone> From<PersistentBidirMap<Kv1, Kv2>> for BidirMap<Kv1, Kv2> {
+	/// Pairs are moved out of chunks not shared with other clones, and cloned out of the rest.
+	fn from(map: PersistentBidirMap<Kv1, Kv2>) -> Self {
+		let mut cont = Vec::with_capacity(map.len);
+		for chunk in Arc::try_unwrap(map.chunks).unwrap_or_else(|chunks| (*chunks).clone()) {
+			match Arc::try_unwrap(chunk) {
+				Ok(chunk) => cont.extend(chunk),
+				Err(chunk) => cont.extend(chunk.iter().cloned()),
+			}
+		}
+		BidirMap::from_cont(cont)
+	}
+}
+
+
+/// An iterator over the K/V pairs contained in a `PersistentBidirMap`.
+///
+/// See documentation of `PersistentBidirMap::iter()` for more.
+pub struct Iter<'a, Kv1: 'a, Kv2: 'a> {
+	chunks: slice::Iter<'a, Chunk<Kv1, Kv2>>,
+	chunk: slice::Iter<'a, (Kv1, Kv2)>,
+}
+
+impl<'a, Kv1, Kv2> Iterator for Iter<'a, Kv1, Kv2> {
+	type Item = &'a (Kv1, Kv2);
+	fn next(&mut self) -> Option<Self::Item> {
+		loop {
+			if let Some(kvs) = self.chunk.next() {
+				return Some(kvs);
+			}
+			self.chunk = self.chunks.next()?.iter();
+		}
+	}
+}
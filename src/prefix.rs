@@ -0,0 +1,73 @@
+//! Finding the pairs of a `BidirMap` whose string K/Vs start with a prefix.
+
+
+use {BidirMap, PrefixIter};
+
+
+impl<Kv1: PartialEq, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
+	/// Gets an iterator over the pairs whose first K/V starts with `prefix`, in iteration order.
+	///
+	/// Performance: `O(n)`; `BidirSortedMap::iter_by_first_prefix()` narrows the search in `O(log n)`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert("checkout", 1);
+	/// map.insert("cherry-pick", 2);
+	/// map.insert("commit", 3);
+	///
+	/// assert_eq!(map.iter_by_first_prefix("che").collect::<Vec<_>>(), [(&"checkout", &1), (&"cherry-pick", &2)]);
+	/// assert_eq!(map.iter_by_first_prefix("").count(), 3);
+	/// ```
+	pub fn iter_by_first_prefix<'a>(&'a self, prefix: &'a str) -> PrefixIter<'a, Kv1, Kv2>
+		where Kv1: AsRef<str>,
+	{
+		PrefixIter{
+			iter: self.cont.iter(),
+			prefix: prefix.as_bytes(),
+			key: |kvs| kvs.0.as_ref().as_bytes(),
+		}
+	}
+
+	/// Gets an iterator over the pairs whose first K/V starts with the bytes of `prefix`, in iteration order.
+	///
+	/// Performance: `O(n)`.
+	pub fn iter_by_first_byte_prefix<'a>(&'a self, prefix: &'a [u8]) -> PrefixIter<'a, Kv1, Kv2>
+		where Kv1: AsRef<[u8]>,
+	{
+		PrefixIter{
+			iter: self.cont.iter(),
+			prefix,
+			key: |kvs| kvs.0.as_ref(),
+		}
+	}
+
+	/// Gets an iterator over the pairs whose second K/V starts with `prefix`, in iteration order.
+	///
+	/// Performance: `O(n)`.
+	pub fn iter_by_second_prefix<'a>(&'a self, prefix: &'a str) -> PrefixIter<'a, Kv1, Kv2>
+		where Kv2: AsRef<str>,
+	{
+		PrefixIter{
+			iter: self.cont.iter(),
+			prefix: prefix.as_bytes(),
+			key: |kvs| kvs.1.as_ref().as_bytes(),
+		}
+	}
+
+	/// Gets an iterator over the pairs whose second K/V starts with the bytes of `prefix`, in iteration order.
+	///
+	/// Performance: `O(n)`.
+	pub fn iter_by_second_byte_prefix<'a>(&'a self, prefix: &'a [u8]) -> PrefixIter<'a, Kv1, Kv2>
+		where Kv2: AsRef<[u8]>,
+	{
+		PrefixIter{
+			iter: self.cont.iter(),
+			prefix,
+			key: |kvs| kvs.1.as_ref(),
+		}
+	}
+}
@@ -0,0 +1,83 @@
+//! Searching packed columns of primitive integer K/Vs many at a time, see `PrimitiveKey`.
+
+
+#[cfg(feature = "alloc")]
+use BidirSoaMap;
+
+
+/// The number of K/Vs compared at once, enough to fill a 256-bit vector with bytes
+const LANES: usize = 32;
+
+
+/// An integer K/V type, whose columns can be searched with wide comparisons.
+///
+/// `position()` compares a whole block of K/Vs against the key without branching before looking closer,
+/// which the compiler turns into vector instructions, much like `memchr` does for bytes.
+///
+/// Used by `BidirSoaMap`'s `*_wide()` lookups, which search its columns directly;
+/// the plain `get_by_*()` and `contains_*()` remain available for every K/V type.
+pub trait PrimitiveKey: Copy + PartialEq {
+	/// Returns the index of the first K/V in `column` equal to `key`.
+	fn position(column: &[Self], key: Self) -> Option<usize> {
+		let mut blocks = column.chunks_exact(LANES);
+		for (block_idx, block) in blocks.by_ref().enumerate() {
+			if block.iter().fold(false, |found, &kv| found | (kv == key)) {
+				return block.iter().position(|&kv| kv == key).map(|idx| block_idx * LANES + idx);
+			}
+		}
+
+		let rest = blocks.remainder();
+		rest.iter().position(|&kv| kv == key).map(|idx| column.len() - rest.len() + idx)
+	}
+}
+
+macro_rules! impl_primitive_key {
+	($($int:ty),*) => {$(
+		impl PrimitiveKey for $int {}
+	)*};
+}
+
+impl_primitive_key!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, char, bool);
+
+
+#[cfg(feature = "alloc")]
+impl<Kv1: PartialEq, Kv2: PartialEq> BidirSoaMap<Kv1, Kv2> {
+	/// Returns a reference to the second K/V corresponding to the first K/V, like `get_by_first()`, searching with wide comparisons.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirSoaMap;
+	///
+	/// let map: BidirSoaMap<u32, char> = (0..26).zip('a'..='z').collect();
+	/// assert_eq!(map.get_by_first_wide(&7), Some(&'h'));
+	/// assert_eq!(map.get_by_second_wide(&'z'), Some(&25));
+	/// assert_eq!(map.get_by_first_wide(&70), None);
+	/// ```
+	pub fn get_by_first_wide(&self, key: &Kv1) -> Option<&Kv2>
+		where Kv1: PrimitiveKey,
+	{
+		PrimitiveKey::position(self.first_slice(), *key).map(|idx| &self.second_slice()[idx])
+	}
+
+	/// Returns a reference to the first K/V corresponding to the second K/V, like `get_by_second()`, searching with wide comparisons.
+	pub fn get_by_second_wide(&self, key: &Kv2) -> Option<&Kv1>
+		where Kv2: PrimitiveKey,
+	{
+		PrimitiveKey::position(self.second_slice(), *key).map(|idx| &self.first_slice()[idx])
+	}
+
+	/// Check if the map contains the first K/V, like `contains_first_key()`, searching with wide comparisons
+	pub fn contains_first_key_wide(&self, key: &Kv1) -> bool
+		where Kv1: PrimitiveKey,
+	{
+		PrimitiveKey::position(self.first_slice(), *key).is_some()
+	}
+
+	/// Check if the map contains the second K/V, like `contains_second_key()`, searching with wide comparisons
+	pub fn contains_second_key_wide(&self, key: &Kv2) -> bool
+		where Kv2: PrimitiveKey,
+	{
+		PrimitiveKey::position(self.second_slice(), *key).is_some()
+	}
+}
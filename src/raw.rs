@@ -0,0 +1,58 @@
+//! Direct access to a `BidirMap`'s storage, for callers upholding its invariant themselves.
+
+
+use alloc::vec::Vec;
+use BidirMap;
+
+
+impl<Kv1: PartialEq, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
+	/// Wraps a `Vec` of pairs as a map, as is, without any checks.
+	///
+	/// # Safety
+	///
+	/// No two pairs may share a first or a second K/V.
+	/// Breaking this won't cause undefined behaviour in this crate, but lookups and removals will silently act on only one of the pairs,
+	/// and code relying on uniqueness may misbehave. `validate()` checks it.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let pairs: Vec<_> = (0..1000).map(|i| (i, i.to_string())).collect();
+	/// let map = unsafe { BidirMap::from_raw_vec(pairs) };   // unique by construction
+	/// assert_eq!(map.get_by_second("999"), Some(&999));
+	/// ```
+	pub unsafe fn from_raw_vec(cont: Vec<(Kv1, Kv2)>) -> Self {
+		BidirMap::from_cont(cont)
+	}
+
+	/// Unwraps the map's pairs, in iteration order.
+	pub fn into_raw(self) -> Vec<(Kv1, Kv2)> {
+		self.cont
+	}
+
+	/// Gets the map's pairs, in iteration order, mutably, K/Vs included.
+	///
+	/// The caller is responsible for no two pairs sharing a first or a second K/V by the time another method is called;
+	/// `debug_validate()` can check that in debug builds.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert("a".to_string(), 1);
+	/// map.insert("b".to_string(), 2);
+	/// for kvs in map.as_raw_mut() {
+	///     kvs.0.make_ascii_uppercase();
+	/// }
+	/// map.debug_validate();
+	/// assert_eq!(map.get_by_first("B"), Some(&2));
+	/// ```
+	pub fn as_raw_mut(&mut self) -> &mut [(Kv1, Kv2)] {
+		self.mark_index_stale();
+		&mut self.cont
+	}
+}
@@ -0,0 +1,90 @@
+//! Renaming the K/Vs of one column by a rename table, see `BidirMap::remap_first_keys()`.
+
+
+use alloc::vec::Vec;
+use {BidirMap, Column, RemapError};
+
+
+impl<Kv1: PartialEq, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
+	/// Replaces every first K/V that's a first K/V of `renames` with its second K/V there, returning how many were changed.
+	///
+	/// The first K/Vs not in `renames` are kept, and every pair keeps its position.
+	/// Renames may chain or swap K/Vs, since they're all applied at once;
+	/// but if two pairs would then share a first K/V, the map is left as it was and the first pair colliding with another is reported.
+	///
+	/// Performance: `O(n·(m + r))`, for `m` renames of which `r` apply.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::{BidirMap, Column, RemapError};
+	///
+	/// let mut columns: BidirMap<_, _> = vec![("id", 0), ("name", 1), ("mail", 2)].into_iter().collect();
+	/// let renames: BidirMap<_, _> = vec![("mail", "email"), ("id", "uid")].into_iter().collect();
+	/// assert_eq!(columns.remap_first_keys(&renames), Ok(2));
+	/// assert_eq!(columns.first_col().collect::<Vec<_>>(), [&"uid", &"name", &"email"]);
+	///
+	/// let clash: BidirMap<_, _> = vec![("uid", "name")].into_iter().collect();
+	/// assert_eq!(columns.remap_first_keys(&clash), Err(RemapError{column: Column::First, indices: (0, 1)}));
+	/// ```
+	pub fn remap_first_keys(&mut self, renames: &BidirMap<Kv1, Kv1>) -> Result<usize, RemapError>
+		where Kv1: Clone,
+	{
+		let changes = plan(self.cont.iter().map(|kvs| &kvs.0).collect(), renames, Column::First)?;
+		let changed = changes.len();
+		for (idx, kv1) in changes {
+			self.cont[idx].0 = kv1;
+		}
+		if changed != 0 {
+			self.rebuild_index();
+		}
+		Ok(changed)
+	}
+
+	/// Replaces every second K/V that's a first K/V of `renames` with its second K/V there, returning how many were changed.
+	///
+	/// See `remap_first_keys()`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map: BidirMap<_, _> = vec![(1, 'a'), (2, 'b'), (3, 'c')].into_iter().collect();
+	/// let swap: BidirMap<_, _> = vec![('a', 'b'), ('b', 'a')].into_iter().collect();
+	/// assert_eq!(map.remap_second_keys(&swap), Ok(2));
+	/// assert_eq!(map.iter().cloned().collect::<Vec<_>>(), [(1, 'b'), (2, 'a'), (3, 'c')]);
+	/// ```
+	pub fn remap_second_keys(&mut self, renames: &BidirMap<Kv2, Kv2>) -> Result<usize, RemapError>
+		where Kv2: Clone,
+	{
+		let changes = plan(self.cont.iter().map(|kvs| &kvs.1).collect(), renames, Column::Second)?;
+		let changed = changes.len();
+		for (idx, kv2) in changes {
+			self.cont[idx].1 = kv2;
+		}
+		if changed != 0 {
+			self.rebuild_index();
+		}
+		Ok(changed)
+	}
+}
+
+/// The K/Vs of the column to replace, by index, if replacing them leaves the column unique
+fn plan<K: PartialEq + Clone>(column: Vec<&K>, renames: &BidirMap<K, K>, name: Column) -> Result<Vec<(usize, K)>, RemapError> {
+	let renamed: Vec<_> = column.iter().map(|&kv| renames.get_by_first(kv).filter(|&new| new != kv)).collect();
+	let new_kv = |idx: usize| renamed[idx].unwrap_or(column[idx]);
+
+	for (idx, new) in renamed.iter().enumerate() {
+		if let Some(new) = *new {
+			if let Some(other) = (0..column.len()).find(|&other| other != idx && new_kv(other) == new) {
+				return Err(RemapError{
+					column: name,
+					indices: if idx < other { (idx, other) } else { (other, idx) },
+				});
+			}
+		}
+	}
+
+	Ok(renamed.iter().enumerate().filter_map(|(idx, new)| new.map(|new| (idx, new.clone()))).collect())
+}
@@ -0,0 +1,69 @@
+//! Removing many pairs of a `BidirMap` in one pass over it.
+
+
+use core::borrow::Borrow;
+use core::hash::Hash;
+use core::mem;
+use std::collections::HashSet;
+use std::vec::Vec;
+use BidirMap;
+
+
+impl<Kv1: PartialEq, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
+	/// Removes the pairs with any of the first K/Vs, returning them in the order they were in; K/Vs not in the map are skipped.
+	///
+	/// The remaining pairs keep their order.
+	///
+	/// Performance: `O(n + m)` hashes and comparisons, for `m` keys, instead of the `O(n·m)` of calling `remove_by_first()` for each.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map: BidirMap<_, _> = vec![(1, 'a'), (2, 'b'), (3, 'c'), (4, 'd')].into_iter().collect();
+	/// assert_eq!(map.remove_all_by_first(vec![4, 2, 9]), [(2, 'b'), (4, 'd')]);
+	/// assert_eq!(map.iter().cloned().collect::<Vec<_>>(), [(1, 'a'), (3, 'c')]);
+	/// ```
+	pub fn remove_all_by_first<Q, I>(&mut self, keys: I) -> Vec<(Kv1, Kv2)>
+		where Kv1: Borrow<Q>,
+		      Q  : Hash + Eq,
+		      I  : IntoIterator<Item = Q>,
+	{
+		let keys: HashSet<Q> = keys.into_iter().collect();
+		self.remove_all(|kvs| keys.contains(kvs.0.borrow()))
+	}
+
+	/// Removes the pairs with any of the second K/Vs, returning them in the order they were in; K/Vs not in the map are skipped.
+	///
+	/// The remaining pairs keep their order.
+	///
+	/// Performance: `O(n + m)` hashes and comparisons, for `m` keys, instead of the `O(n·m)` of calling `remove_by_second()` for each.
+	pub fn remove_all_by_second<Q, I>(&mut self, keys: I) -> Vec<(Kv1, Kv2)>
+		where Kv2: Borrow<Q>,
+		      Q  : Hash + Eq,
+		      I  : IntoIterator<Item = Q>,
+	{
+		let keys: HashSet<Q> = keys.into_iter().collect();
+		self.remove_all(|kvs| keys.contains(kvs.1.borrow()))
+	}
+
+	fn remove_all<F: FnMut(&(Kv1, Kv2)) -> bool>(&mut self, mut remove: F) -> Vec<(Kv1, Kv2)> {
+		if self.cont.is_empty() {
+			return Vec::new();
+		}
+
+		let len = self.cont.len();
+		let mut removed = Vec::new();
+		for kvs in mem::replace(&mut self.cont, Vec::with_capacity(len)) {
+			if remove(&kvs) {
+				removed.push(kvs);
+			} else {
+				self.cont.push(kvs);
+			}
+		}
+		self.rebuild_index();
+		self.reclaim();
+		removed
+	}
+}
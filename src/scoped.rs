@@ -0,0 +1,119 @@
+//! Insertions into a `BidirMap` undone at the end of a scope, see `BidirMap::insert_scoped()`.
+
+
+use core::ops::{Deref, DerefMut};
+use BidirMap;
+
+
+/// A pair inserted into a `BidirMap` for as long as this guard lives.
+///
+/// Dropping the guard removes the pair, if it's still in the map as inserted,
+/// and then re-inserts the pairs its insertion displaced, displacing in turn whatever holds their K/Vs by then.
+/// Other edits made through the guard are kept, so a pair that replaced the scoped one by either K/V is only removed
+/// if it conflicts with a restored pair. The restored pairs end up last, so the order of iteration isn't restored;
+/// a `transaction()` undoes everything, order included.
+///
+/// The guard dereferences to the map, so scopes nest, being undone innermost first.
+///
+/// See documentation of `BidirMap::insert_scoped()` for more.
+#[derive(Debug)]
+pub struct ScopedInsert<'a, Kv1: PartialEq + 'a, Kv2: PartialEq + 'a> {
+	map: &'a mut BidirMap<Kv1, Kv2>,
+	inserted: Option<(Kv1, Kv2)>,
+	by_first: Option<(Kv1, Kv2)>,
+	by_second: Option<(Kv1, Kv2)>,
+}
+
+impl<Kv1: PartialEq + Clone, Kv2: PartialEq + Clone> BidirMap<Kv1, Kv2> {
+	/// Inserts a K/V-K/V pair into the map until the returned guard is dropped, then restores the pairs it displaced.
+	///
+	/// Unlike `insert()`, both pairs holding `kv1` and `kv2` are kept, to be restored.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert("x", 1);
+	/// map.insert("y", 2);
+	///
+	/// {
+	///     let mut scope = map.insert_scoped("x", 2);
+	///     assert_eq!(scope.get_by_first(&"x"), Some(&2));
+	///     assert_eq!(scope.displaced_by_first(), Some(&("x", 1)));
+	///     assert_eq!(scope.displaced_by_second(), Some(&("y", 2)));
+	///
+	///     let inner = scope.insert_scoped("z", 3);
+	///     assert_eq!(inner.len(), 2);
+	/// }
+	///
+	/// assert_eq!(map.get_by_first(&"x"), Some(&1));
+	/// assert_eq!(map.get_by_first(&"y"), Some(&2));
+	/// assert_eq!(map.get_by_first(&"z"), None);
+	/// ```
+	pub fn insert_scoped<'s>(&'s mut self, kv1: Kv1, kv2: Kv2) -> ScopedInsert<'s, Kv1, Kv2> {
+		let by_first = self.position_of_first(&kv1).map(|idx| self.swap_remove_at(idx));
+		let by_second = self.position_of_second(&kv2).map(|idx| self.swap_remove_at(idx));
+		if by_first.is_none() && by_second.is_none() {
+			self.assert_room();
+		}
+		self.push(kv1.clone(), kv2.clone());
+
+		ScopedInsert{
+			map: self,
+			inserted: Some((kv1, kv2)),
+			by_first,
+			by_second,
+		}
+	}
+}
+
+impl<'a, Kv1: PartialEq, Kv2: PartialEq> ScopedInsert<'a, Kv1, Kv2> {
+	/// Gets the pair displaced by the scoped insertion's first K/V, if any.
+	pub fn displaced_by_first(&self) -> Option<&(Kv1, Kv2)> {
+		self.by_first.as_ref()
+	}
+
+	/// Gets the pair displaced by the scoped insertion's second K/V, if any.
+	pub fn displaced_by_second(&self) -> Option<&(Kv1, Kv2)> {
+		self.by_second.as_ref()
+	}
+
+	/// Keeps the scoped pair and whatever the scope did, dropping the displaced pairs for good.
+	pub fn persist(mut self) {
+		self.inserted = None;
+		self.by_first = None;
+		self.by_second = None;
+	}
+}
+
+impl<'a, Kv1: PartialEq, Kv2: PartialEq> Deref for ScopedInsert<'a, Kv1, Kv2> {
+	type Target = BidirMap<Kv1, Kv2>;
+
+	fn deref(&self) -> &BidirMap<Kv1, Kv2> {
+		self.map
+	}
+}
+
+impl<'a, Kv1: PartialEq, Kv2: PartialEq> DerefMut for ScopedInsert<'a, Kv1, Kv2> {
+	fn deref_mut(&mut self) -> &mut BidirMap<Kv1, Kv2> {
+		self.map
+	}
+}
+
+impl<'a, Kv1: PartialEq, Kv2: PartialEq> Drop for ScopedInsert<'a, Kv1, Kv2> {
+	fn drop(&mut self) {
+		if let Some((kv1, kv2)) = self.inserted.take() {
+			if let Some(idx) = self.map.position_of_first(&kv1) {
+				if self.map.cont[idx].1 == kv2 {
+					self.map.swap_remove_at(idx);
+				}
+			}
+		}
+
+		for (kv1, kv2) in self.by_second.take().into_iter().chain(self.by_first.take()) {
+			self.map.insert(kv1, kv2);
+		}
+	}
+}
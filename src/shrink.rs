@@ -0,0 +1,40 @@
+//! Giving back a `BidirMap`'s spare capacity after removals, see `BidirMap::set_shrink_policy()`.
+
+
+/// When a `BidirMap` gives back the spare capacity of its storage after removing pairs.
+///
+/// See documentation of `BidirMap::set_shrink_policy()` for more.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ShrinkPolicy {
+	/// Keep the capacity, as `Vec` does; the default
+	#[default]
+	Never,
+	/// Shrink once fewer than `utilization` of the capacity is used, but never below `min_capacity`
+	WhenBelow {
+		/// The fraction of the capacity, at least 0 and below 1, under which the map shrinks
+		utilization: f32,
+		/// The capacity the map never shrinks below, nor past which it shrinks at all
+		min_capacity: usize,
+	},
+}
+
+impl ShrinkPolicy {
+	/// The capacity to shrink to, holding `len` pairs in `capacity`, if any
+	pub(crate) fn shrink_to(&self, len: usize, capacity: usize) -> Option<usize> {
+		match *self {
+			ShrinkPolicy::Never => None,
+			ShrinkPolicy::WhenBelow{utilization, min_capacity} => {
+				if capacity > min_capacity && (len as f32) < utilization * capacity as f32 {
+					// Leave the map used halfway between `utilization` and full, so that neither a few removals nor a few insertions reallocate again;
+					// rounded up by hand, as `f32::ceil()` needs std
+					let fill = (1.0 + utilization) / 2.0;
+					let target = (len as f32 / fill) as usize;
+					let target = if (target as f32) * fill < len as f32 { target + 1 } else { target };
+					Some(if target > min_capacity { target } else { min_capacity })
+				} else {
+					None
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,508 @@
+//! A `BidirMap` variant storing each column in its own `Vec`.
+
+
+use core::borrow::Borrow;
+use core::iter::{Extend, FromIterator, Zip};
+use core::slice;
+use alloc::vec::{self, Vec};
+use BidirMap;
+
+
+/// A bidirectional map with a structure-of-arrays layout: the first and second K/Vs are kept in two separate `Vec`s.
+///
+/// This behaves exactly like `BidirMap`, including the order pairs end up in after `insert()`s and `remove_by_*()`s,
+/// but a lookup by one column only reads that column's memory, instead of striding over whole pairs.
+/// That pays off when one K/V is much larger than the other and is rarely looked up by.
+///
+/// Since the pairs aren't stored as tuples, iterators yield `(&Kv1, &Kv2)` rather than `&(Kv1, Kv2)`.
+///
+/// Performance: `O(n)`, mostly.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct BidirSoaMap<Kv1: PartialEq, Kv2: PartialEq> {
+	first: Vec<Kv1>,
+	second: Vec<Kv2>,
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> BidirSoaMap<Kv1, Kv2> {
+	/// Create a new empty instance of `BidirSoaMap`
+	pub fn new() -> Self {
+		BidirSoaMap{
+			first: Vec::new(),
+			second: Vec::new(),
+		}
+	}
+
+	/// Create a new empty instance of `BidirSoaMap` with the specified capacity.
+	///
+	/// It will be able to hold at least `capacity` elements without reallocating.
+	pub fn with_capacity(capacity: usize) -> Self {
+		BidirSoaMap{
+			first: Vec::with_capacity(capacity),
+			second: Vec::with_capacity(capacity),
+		}
+	}
+
+	/// Clears the map, removing all entries.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirSoaMap;
+	///
+	/// let mut a = BidirSoaMap::new();
+	/// a.insert(1, "a");
+	/// a.clear();
+	/// assert!(a.is_empty());
+	/// ```
+	pub fn clear(&mut self) {
+		self.first.clear();
+		self.second.clear();
+	}
+
+	/// Inserts a K/V-K/V pair into the map.
+	///
+	/// If the map did not have this K/V-K/V pair present, `None` is returned.
+	///
+	/// If the map did have this K/V-K/V pair present, it's updated and the old K/V-K/V pair is returned.
+	///
+	/// If `kv1` and `kv2` were each present in a different pair, both of those are removed and the one holding `kv1` is returned.
+	pub fn insert(&mut self, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)> {
+		let by_first = self.first.iter().position(|k| *k == kv1).map(|idx| self.swap_remove_at(idx));
+		let by_second = self.second.iter().position(|k| *k == kv2).map(|idx| self.swap_remove_at(idx));
+
+		self.first.push(kv1);
+		self.second.push(kv2);
+
+		by_first.or(by_second)
+	}
+
+	/// Gets an iterator over the entries of the map.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirSoaMap;
+	///
+	/// let mut map = BidirSoaMap::new();
+	/// map.insert(1, "a");
+	/// map.insert(2, "b");
+	/// map.insert(3, "c");
+	///
+	/// for kv in map.iter() {
+	///     println!("{}: {}", kv.0, kv.1);
+	/// }
+	///
+	/// let first = map.iter().next().unwrap();
+	/// assert_eq!(first, (&1, &"a"));
+	/// ```
+	pub fn iter<'s>(&'s self) -> Iter<'s, Kv1, Kv2> {
+		Iter{
+			iter: self.first.iter().zip(self.second.iter()),
+		}
+	}
+
+	/// Gets a mutable iterator over the entries of the map.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirSoaMap;
+	///
+	/// let mut map = BidirSoaMap::new();
+	/// map.insert("a", 1);
+	/// map.insert("b", 2);
+	/// map.insert("c", 3);
+	///
+	/// // add 10 to the value if the key isn't "a"
+	/// for kv in map.iter_mut() {
+	///     if kv.0 != &"a" {
+	///         *kv.1 += 10;
+	///     }
+	/// }
+	/// ```
+	pub fn iter_mut<'s>(&'s mut self) -> IterMut<'s, Kv1, Kv2> {
+		IterMut{
+			iter: self.first.iter_mut().zip(self.second.iter_mut()),
+		}
+	}
+
+	/// Gets an iterator over the first K/V of the map.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirSoaMap;
+	///
+	/// let mut a = BidirSoaMap::new();
+	/// a.insert(1, "a");
+	/// a.insert(2, "b");
+	///
+	/// let keys: Vec<_> = a.first_col().cloned().collect();
+	/// assert_eq!(keys, [1, 2]);
+	/// ```
+	pub fn first_col<'s>(&'s self) -> slice::Iter<'s, Kv1> {
+		self.first.iter()
+	}
+
+	/// Gets an iterator over the second K/V of the map.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirSoaMap;
+	///
+	/// let mut a = BidirSoaMap::new();
+	/// a.insert(1, "a");
+	/// a.insert(2, "b");
+	///
+	/// let keys: Vec<_> = a.second_col().cloned().collect();
+	/// assert_eq!(keys, ["a", "b"]);
+	/// ```
+	pub fn second_col<'s>(&'s self) -> slice::Iter<'s, Kv2> {
+		self.second.iter()
+	}
+
+	/// Gets the first K/Vs as a contiguous slice, in the same order as `iter()`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirSoaMap;
+	///
+	/// let mut a = BidirSoaMap::new();
+	/// a.insert(1, "a");
+	/// a.insert(2, "b");
+	/// assert_eq!(a.first_slice(), [1, 2]);
+	/// ```
+	pub fn first_slice(&self) -> &[Kv1] {
+		&self.first
+	}
+
+	/// Gets the second K/Vs as a contiguous slice, in the same order as `iter()`.
+	pub fn second_slice(&self) -> &[Kv2] {
+		&self.second
+	}
+
+	/// Returns the number of elements in the map.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirSoaMap;
+	///
+	/// let mut a = BidirSoaMap::new();
+	/// assert_eq!(a.len(), 0);
+	/// a.insert(1, "a");
+	/// assert_eq!(a.len(), 1);
+	/// ```
+	pub fn len(&self) -> usize {
+		self.first.len()
+	}
+
+	/// Returns true if the map contains no elements.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirSoaMap;
+	///
+	/// let mut a = BidirSoaMap::new();
+	/// assert!(a.is_empty());
+	/// a.insert(1, "a");
+	/// assert!(!a.is_empty());
+	/// ```
+	pub fn is_empty(&self) -> bool {
+		self.first.is_empty()
+	}
+
+
+	/// Returns a reference to the second K/V corresponding to the first K/V.
+	///
+	/// Only the first column is scanned.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirSoaMap;
+	///
+	/// let mut map = BidirSoaMap::new();
+	/// map.insert(1, "a");
+	/// assert_eq!(map.get_by_first(&1), Some(&"a"));
+	/// assert_eq!(map.get_by_first(&2), None);
+	/// ```
+	pub fn get_by_first<Q>(&self, key: &Q) -> Option<&Kv2>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.position_of_first(key).map(|idx| &self.second[idx])
+	}
+
+	/// Returns a reference to the first K/V corresponding to the second K/V.
+	///
+	/// Only the second column is scanned.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirSoaMap;
+	///
+	/// let mut map = BidirSoaMap::new();
+	/// map.insert(1, "a");
+	/// assert_eq!(map.get_by_second(&"a"), Some(&1));
+	/// assert_eq!(map.get_by_second(&"b"), None);
+	/// ```
+	pub fn get_by_second<Q>(&self, key: &Q) -> Option<&Kv1>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.position_of_second(key).map(|idx| &self.first[idx])
+	}
+
+	/// Check if the map contains the first K/V
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirSoaMap;
+	///
+	/// let mut map = BidirSoaMap::new();
+	/// map.insert(1, "a");
+	/// assert_eq!(map.contains_first_key(&1), true);
+	/// assert_eq!(map.contains_first_key(&2), false);
+	/// ```
+	pub fn contains_first_key<Q>(&self, key: &Q) -> bool
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.position_of_first(key).is_some()
+	}
+
+	/// Check if the map contains the second K/V
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirSoaMap;
+	///
+	/// let mut map = BidirSoaMap::new();
+	/// map.insert(1, "a");
+	/// assert_eq!(map.contains_second_key(&"a"), true);
+	/// assert_eq!(map.contains_second_key(&"b"), false);
+	/// ```
+	pub fn contains_second_key<Q>(&self, key: &Q) -> bool
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.position_of_second(key).is_some()
+	}
+
+	/// Returns a mutable reference to the second K/V corresponding to the first K/V.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirSoaMap;
+	///
+	/// let mut map = BidirSoaMap::new();
+	/// map.insert(1, "a");
+	/// if let Some(x) = map.get_mut_by_first(&1) {
+	///     *x = "b";
+	/// }
+	/// assert_eq!(map.get_by_first(&1), Some(&"b"));
+	/// ```
+	pub fn get_mut_by_first<Q>(&mut self, key: &Q) -> Option<&mut Kv2>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		match self.position_of_first(key) {
+			Some(idx) => Some(&mut self.second[idx]),
+			None => None,
+		}
+	}
+
+	/// Returns a mutable reference to the first K/V corresponding to the second K/V.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirSoaMap;
+	///
+	/// let mut map = BidirSoaMap::new();
+	/// map.insert(1, "a");
+	/// if let Some(x) = map.get_mut_by_second(&"a") {
+	///     *x = 2;
+	/// }
+	/// assert_eq!(map.get_by_second(&"a"), Some(&2));
+	/// ```
+	pub fn get_mut_by_second<Q>(&mut self, key: &Q) -> Option<&mut Kv1>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		match self.position_of_second(key) {
+			Some(idx) => Some(&mut self.first[idx]),
+			None => None,
+		}
+	}
+
+	/// Removes the pair corresponding to the first K/V from the map, returning it if the key was previously in the map.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirSoaMap;
+	///
+	/// let mut map = BidirSoaMap::new();
+	/// map.insert(1, "a");
+	/// assert_eq!(map.remove_by_first(&1), Some((1, "a")));
+	/// assert_eq!(map.remove_by_first(&1), None);
+	/// ```
+	pub fn remove_by_first<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.position_of_first(key).map(|idx| self.swap_remove_at(idx))
+	}
+
+	/// Removes the pair corresponding to the second K/V from the map, returning it if the key was previously in the map.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirSoaMap;
+	///
+	/// let mut map = BidirSoaMap::new();
+	/// map.insert(1, "a");
+	/// assert_eq!(map.remove_by_second(&"a"), Some((1, "a")));
+	/// assert_eq!(map.remove_by_second(&"b"), None);
+	/// ```
+	pub fn remove_by_second<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.position_of_second(key).map(|idx| self.swap_remove_at(idx))
+	}
+
+	fn position_of_first<Q>(&self, key: &Q) -> Option<usize>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.first.iter().position(|k| *key == *k.borrow())
+	}
+
+	fn position_of_second<Q>(&self, key: &Q) -> Option<usize>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.second.iter().position(|k| *key == *k.borrow())
+	}
+
+	/// Same as `BidirMap`'s, so both types leave the pairs in the same order
+	fn swap_remove_at(&mut self, idx: usize) -> (Kv1, Kv2) {
+		(self.first.swap_remove(idx), self.second.swap_remove(idx))
+	}
+}
+
+
+impl<Kv1: PartialEq, Kv2: PartialEq> Default for BidirSoaMap<Kv1, Kv2> {
+	fn default() -> Self {
+		BidirSoaMap::new()
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> IntoIterator for BidirSoaMap<Kv1, Kv2> {
+	type Item = (Kv1, Kv2);
+	type IntoIter = IntoIter<Kv1, Kv2>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		IntoIter{
+			iter: self.first.into_iter().zip(self.second),
+		}
+	}
+}
+
+impl<'a, Kv1: PartialEq, Kv2: PartialEq> IntoIterator for &'a BidirSoaMap<Kv1, Kv2> {
+	type Item = (&'a Kv1, &'a Kv2);
+	type IntoIter = Iter<'a, Kv1, Kv2>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> FromIterator<(Kv1, Kv2)> for BidirSoaMap<Kv1, Kv2> {
+	/// Like `BidirMap`'s, this doesn't check for duplicates.
+	fn from_iter<T: IntoIterator<Item=(Kv1, Kv2)>>(iter: T) -> Self {
+		let (first, second) = iter.into_iter().unzip();
+		BidirSoaMap{
+			first,
+			second,
+		}
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> Extend<(Kv1, Kv2)> for BidirSoaMap<Kv1, Kv2> {
+	/// Like `BidirMap`'s, this doesn't check for duplicates.
+	fn extend<T: IntoIterator<Item=(Kv1, Kv2)>>(&mut self, iter: T) {
+		for (kv1, kv2) in iter {
+			self.first.push(kv1);
+			self.second.push(kv2);
+		}
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> From<BidirMap<Kv1, Kv2>> for BidirSoaMap<Kv1, Kv2> {
+	fn from(map: BidirMap<Kv1, Kv2>) -> Self {
+		map.into_iter().collect()
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> From<BidirSoaMap<Kv1, Kv2>> for BidirMap<Kv1, Kv2> {
+	fn from(map: BidirSoaMap<Kv1, Kv2>) -> Self {
+		map.into_iter().collect()
+	}
+}
+
+
+/// An iterator over the K/V pairs contained in a `BidirSoaMap`.
+///
+/// See documentation of `BidirSoaMap::iter()` for more.
+pub struct Iter<'a, Kv1: 'a, Kv2: 'a> {
+	iter: Zip<slice::Iter<'a, Kv1>, slice::Iter<'a, Kv2>>,
+}
+
+impl<'a, Kv1, Kv2> Iterator for Iter<'a, Kv1, Kv2> {
+	type Item = (&'a Kv1, &'a Kv2);
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next()
+	}
+}
+
+
+/// An iterator over mutable K/V pairs contained in a `BidirSoaMap`.
+///
+/// See documentation of `BidirSoaMap::iter_mut()` for more.
+pub struct IterMut<'a, Kv1: 'a, Kv2: 'a> {
+	iter: Zip<slice::IterMut<'a, Kv1>, slice::IterMut<'a, Kv2>>,
+}
+
+impl<'a, Kv1, Kv2> Iterator for IterMut<'a, Kv1, Kv2> {
+	type Item = (&'a mut Kv1, &'a mut Kv2);
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next()
+	}
+}
+
+
+/// An owning iterator over the K/V pairs of a `BidirSoaMap`.
+///
+/// See documentation of `BidirSoaMap::into_iter()` for more.
+pub struct IntoIter<Kv1, Kv2> {
+	iter: Zip<vec::IntoIter<Kv1>, vec::IntoIter<Kv2>>,
+}
+
+impl<Kv1, Kv2> Iterator for IntoIter<Kv1, Kv2> {
+	type Item = (Kv1, Kv2);
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next()
+	}
+}
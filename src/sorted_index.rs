@@ -0,0 +1,231 @@
+//! The optional sorted permutation indexes kept by a `BidirMap`, see `BidirMap::build_sorted_indexes()`.
+
+
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use alloc::vec::Vec;
+use BidirMap;
+
+
+impl<Kv1: PartialEq, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
+	/// Builds two permutations of the pairs, sorted by each column, making lookups `O(log n)`.
+	///
+	/// The pairs themselves stay in insertion order. Once built, the permutations are kept in sync by every mutating method
+	/// until `drop_sorted_indexes()` is called, each insertion and removal then taking `O(log n)` comparisons but `O(n)` moves of indices;
+	/// after keys have been changed in place via `iter_mut()` or `get_mut_by_*()` they're rebuilt on the next mutation.
+	/// Insertions use them to find the pairs they displace, unless there's also a hash index (see `build_index()`).
+	///
+	/// Like the hash index, they're used by the plain `get_by_*()`, `contains_*()` and `remove_by_*()` methods
+	/// when queried by the K/V type itself; the `*_sorted()` counterparts also use them for ordered borrowed forms of it.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert("b".to_string(), 2);
+	/// map.build_sorted_indexes();
+	///
+	/// map.insert("a".to_string(), 1);
+	/// assert_eq!(map.get_by_first_sorted("a"), Some(&1));
+	/// assert_eq!(map.get_by_second_sorted(&2), Some(&"b".to_string()));
+	/// assert_eq!(map.iter().next(), Some(&("b".to_string(), 2)));
+	/// ```
+	pub fn build_sorted_indexes(&mut self)
+		where Kv1: Ord,
+		      Kv2: Ord,
+	{
+		self.sorted_indexes = Some(SortedIndexes::new(&self.cont));
+	}
+
+	/// Drops the sorted indexes, if any, returning to plain linear scans.
+	pub fn drop_sorted_indexes(&mut self) {
+		self.sorted_indexes = None;
+	}
+
+	/// Check if `build_sorted_indexes()` was called and the indexes weren't dropped since.
+	pub fn has_sorted_indexes(&self) -> bool {
+		self.sorted_indexes.is_some()
+	}
+
+	/// Returns a reference to the second K/V corresponding to the first K/V, using the sorted indexes if present.
+	///
+	/// Without them (or while they're waiting to be rebuilt) this is equivalent to `get_by_first()`.
+	pub fn get_by_first_sorted<Q>(&self, key: &Q) -> Option<&Kv2>
+		where Kv1: Borrow<Q>,
+		      Q  : Ord + ?Sized,
+	{
+		self.position_by_first_sorted(key).map(|idx| &self.cont[idx].1)
+	}
+
+	/// Returns a reference to the first K/V corresponding to the second K/V, using the sorted indexes if present.
+	///
+	/// Without them (or while they're waiting to be rebuilt) this is equivalent to `get_by_second()`.
+	pub fn get_by_second_sorted<Q>(&self, key: &Q) -> Option<&Kv1>
+		where Kv2: Borrow<Q>,
+		      Q  : Ord + ?Sized,
+	{
+		self.position_by_second_sorted(key).map(|idx| &self.cont[idx].0)
+	}
+
+	/// Check if the map contains the first K/V, using the sorted indexes if present.
+	pub fn contains_first_key_sorted<Q>(&self, key: &Q) -> bool
+		where Kv1: Borrow<Q>,
+		      Q  : Ord + ?Sized,
+	{
+		self.position_by_first_sorted(key).is_some()
+	}
+
+	/// Check if the map contains the second K/V, using the sorted indexes if present.
+	pub fn contains_second_key_sorted<Q>(&self, key: &Q) -> bool
+		where Kv2: Borrow<Q>,
+		      Q  : Ord + ?Sized,
+	{
+		self.position_by_second_sorted(key).is_some()
+	}
+
+	/// Removes the pair corresponding to the first K/V from the map, using the sorted indexes if present.
+	pub fn remove_by_first_sorted<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv1: Borrow<Q>,
+		      Q  : Ord + ?Sized,
+	{
+		self.refresh_index();
+		self.position_by_first_sorted(key).map(|idx| self.swap_remove_at(idx))
+	}
+
+	/// Removes the pair corresponding to the second K/V from the map, using the sorted indexes if present.
+	pub fn remove_by_second_sorted<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv2: Borrow<Q>,
+		      Q  : Ord + ?Sized,
+	{
+		self.refresh_index();
+		self.position_by_second_sorted(key).map(|idx| self.swap_remove_at(idx))
+	}
+
+	fn position_by_first_sorted<Q>(&self, key: &Q) -> Option<usize>
+		where Kv1: Borrow<Q>,
+		      Q  : Ord + ?Sized,
+	{
+		match self.sorted_indexes {
+			Some(ref indexes) if !indexes.stale => find(&indexes.by_first, &self.cont, first, |k| Borrow::<Q>::borrow(k).cmp(key)),
+			_ => self.cont.iter().position(|kvs| *key == *kvs.0.borrow()),
+		}
+	}
+
+	fn position_by_second_sorted<Q>(&self, key: &Q) -> Option<usize>
+		where Kv2: Borrow<Q>,
+		      Q  : Ord + ?Sized,
+	{
+		match self.sorted_indexes {
+			Some(ref indexes) if !indexes.stale => find(&indexes.by_second, &self.cont, second, |k| Borrow::<Q>::borrow(k).cmp(key)),
+			_ => self.cont.iter().position(|kvs| *key == *kvs.1.borrow()),
+		}
+	}
+}
+
+
+/// Indices of the pairs, sorted by each column.
+///
+/// Like `HashIndex`, the comparisons are captured when the indexes are built, where `Kv1: Ord` and `Kv2: Ord` are known,
+/// so that the map can keep them in sync from methods that don't require ordered keys.
+#[derive(Clone)]
+pub struct SortedIndexes<Kv1, Kv2> {
+	cmp_first: fn(&Kv1, &Kv1) -> Ordering,
+	cmp_second: fn(&Kv2, &Kv2) -> Ordering,
+	by_first: Vec<usize>,
+	by_second: Vec<usize>,
+	/// Set when keys may have been changed in place, the indexes must then be rebuilt before they're used again
+	pub stale: bool,
+}
+
+fn first<Kv1, Kv2>(kvs: &(Kv1, Kv2)) -> &Kv1 {
+	&kvs.0
+}
+
+fn second<Kv1, Kv2>(kvs: &(Kv1, Kv2)) -> &Kv2 {
+	&kvs.1
+}
+
+/// The index of a pair whose K/V in the column `col` compares `Equal` by `cmp`, found by binary search in `order`
+fn find<Kv1, Kv2, K: ?Sized, F: FnMut(&K) -> Ordering>(order: &[usize], cont: &[(Kv1, Kv2)], col: fn(&(Kv1, Kv2)) -> &K, mut cmp: F) -> Option<usize> {
+	order.binary_search_by(|&i| cmp(col(&cont[i]))).ok().map(|pos| order[pos])
+}
+
+/// The position of `idx` in `order`, found by binary search and a scan over any pairs with equal K/Vs in the column `col`
+fn position_in<Kv1, Kv2, K>(order: &[usize], cont: &[(Kv1, Kv2)], col: fn(&(Kv1, Kv2)) -> &K, cmp: fn(&K, &K) -> Ordering, idx: usize) -> usize {
+	let key = col(&cont[idx]);
+	let start = order.partition_point(|&i| cmp(col(&cont[i]), key) == Ordering::Less);
+	start + order[start..].iter().position(|&i| i == idx).expect("BidirMap's sorted indexes out of sync")
+}
+
+impl<Kv1: Ord, Kv2: Ord> SortedIndexes<Kv1, Kv2> {
+	pub fn new(cont: &[(Kv1, Kv2)]) -> Self {
+		let mut indexes = SortedIndexes{
+			cmp_first: Kv1::cmp,
+			cmp_second: Kv2::cmp,
+			by_first: Vec::with_capacity(cont.len()),
+			by_second: Vec::with_capacity(cont.len()),
+			stale: false,
+		};
+		indexes.rebuild(cont);
+		indexes
+	}
+}
+
+impl<Kv1, Kv2> SortedIndexes<Kv1, Kv2> {
+	/// Indexes of no pairs with the same comparisons
+	pub fn empty_like(&self) -> Self {
+		SortedIndexes{
+			cmp_first: self.cmp_first,
+			cmp_second: self.cmp_second,
+			by_first: Vec::new(),
+			by_second: Vec::new(),
+			stale: false,
+		}
+	}
+
+	pub fn rebuild(&mut self, cont: &[(Kv1, Kv2)]) {
+		let (cmp_first, cmp_second) = (self.cmp_first, self.cmp_second);
+		self.by_first.clear();
+		self.by_first.extend(0..cont.len());
+		self.by_first.sort_by(|&l, &r| cmp_first(&cont[l].0, &cont[r].0));
+		self.by_second.clear();
+		self.by_second.extend(0..cont.len());
+		self.by_second.sort_by(|&l, &r| cmp_second(&cont[l].1, &cont[r].1));
+		self.stale = false;
+	}
+
+	/// Record that `cont[idx]` was just added
+	pub fn push(&mut self, cont: &[(Kv1, Kv2)], idx: usize) {
+		let (cmp_first, cmp_second) = (self.cmp_first, self.cmp_second);
+		let pos = self.by_first.partition_point(|&i| cmp_first(&cont[i].0, &cont[idx].0) != Ordering::Greater);
+		self.by_first.insert(pos, idx);
+		let pos = self.by_second.partition_point(|&i| cmp_second(&cont[i].1, &cont[idx].1) != Ordering::Greater);
+		self.by_second.insert(pos, idx);
+	}
+
+	/// Record that `cont[idx]` is about to be `swap_remove()`d
+	pub fn swap_remove(&mut self, cont: &[(Kv1, Kv2)], idx: usize) {
+		let last = cont.len() - 1;
+		self.by_first.remove(position_in(&self.by_first, cont, first, self.cmp_first, idx));
+		self.by_second.remove(position_in(&self.by_second, cont, second, self.cmp_second, idx));
+
+		if idx != last {
+			let pos = position_in(&self.by_first, cont, first, self.cmp_first, last);
+			self.by_first[pos] = idx;
+			let pos = position_in(&self.by_second, cont, second, self.cmp_second, last);
+			self.by_second[pos] = idx;
+		}
+	}
+
+	pub fn position_by_first(&self, cont: &[(Kv1, Kv2)], kv1: &Kv1) -> Option<usize> {
+		let cmp_first = self.cmp_first;
+		find(&self.by_first, cont, first, |k| cmp_first(k, kv1))
+	}
+
+	pub fn position_by_second(&self, cont: &[(Kv1, Kv2)], kv2: &Kv2) -> Option<usize> {
+		let cmp_second = self.cmp_second;
+		find(&self.by_second, cont, second, |k| cmp_second(k, kv2))
+	}
+}
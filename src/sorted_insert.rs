@@ -0,0 +1,116 @@
+//! Keeping a `BidirMap` sorted by its first K/Vs as pairs are inserted.
+
+
+use core::cmp::Ordering;
+use core::mem;
+use alloc::vec::Vec;
+use {BidirMap, Column, DuplicateKeyError, FromSortedVecError};
+
+
+impl<Kv1: PartialEq + Ord, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
+	/// Inserts a K/V-K/V pair where it belongs in order of the first K/Vs, returning its index and the pair it replaced, if any.
+	///
+	/// The map is assumed to already be sorted by the first K/Vs, as by only ever inserting with this;
+	/// otherwise the position is unspecified, though the map stays valid.
+	///
+	/// A pair with the same first K/V is replaced in place. A pair with the same second K/V is removed, as with `insert()`,
+	/// found with the hash index if there is one; otherwise by a scan.
+	/// If both exist, the one with the same first K/V is returned.
+	///
+	/// Performance: `O(log n)` comparisons of first K/Vs, and `O(n)` moves and comparisons of second K/Vs.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// assert_eq!(map.insert_sorted(3, "c"), (0, None));
+	/// assert_eq!(map.insert_sorted(1, "a"), (0, None));
+	/// assert_eq!(map.insert_sorted(2, "b"), (1, None));
+	/// assert_eq!(map.insert_sorted(2, "B"), (1, Some((2, "b"))));
+	///
+	/// assert_eq!(map.iter().cloned().collect::<Vec<_>>(), [(1, "a"), (2, "B"), (3, "c")]);
+	/// ```
+	pub fn insert_sorted(&mut self, kv1: Kv1, kv2: Kv2) -> (usize, Option<(Kv1, Kv2)>) {
+		let by_second = self.position_of_second(&kv2);
+		let (idx, replaced) = match self.cont.binary_search_by(|kvs| kvs.0.cmp(&kv1)) {
+			Ok(mut idx) => {
+				let by_second = by_second.filter(|&pos| pos != idx).map(|pos| {
+					if pos < idx {
+						idx -= 1;
+					}
+					self.cont.remove(pos)
+				});
+				let by_first = mem::replace(&mut self.cont[idx], (kv1, kv2));
+				(idx, Some(by_first).or(by_second))
+			}
+			Err(mut idx) => {
+				if by_second.is_none() {
+					self.assert_room();
+				}
+				let by_second = by_second.map(|pos| {
+					if pos < idx {
+						idx -= 1;
+					}
+					self.cont.remove(pos)
+				});
+				self.cont.insert(idx, (kv1, kv2));
+				(idx, by_second)
+			}
+		};
+		self.rebuild_index();
+		(idx, replaced)
+	}
+}
+
+impl<Kv1: PartialEq + Ord, Kv2: PartialEq + Ord> BidirMap<Kv1, Kv2> {
+	/// Create a `BidirMap` from pairs already sorted by the first K/V, taking ownership of the `Vec` without copying it.
+	///
+	/// Like `BidirSortedMap::from_sorted_vec()`, checking the first column takes a single pass over adjacent pairs,
+	/// and checking the second column sorts an index over it, in `O(n log n)` comparisons but no moves of the pairs.
+	/// The map is then ready for `insert_sorted()`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	/// use bidir_map::{Column, DuplicateKeyError, FromSortedVecError};
+	///
+	/// let map = BidirMap::from_sorted_vec(vec![(1, "b"), (2, "a")]).unwrap();
+	/// assert_eq!(map.get_by_second(&"a"), Some(&2));
+	///
+	/// assert_eq!(BidirMap::from_sorted_vec(vec![(2, "a"), (1, "b")]),
+	///            Err(FromSortedVecError::Unsorted{index: 1}));
+	/// assert_eq!(BidirMap::from_sorted_vec(vec![(1, "a"), (1, "b")]),
+	///            Err(FromSortedVecError::Duplicate(DuplicateKeyError{column: Column::First, index: Some(1)})));
+	/// ```
+	pub fn from_sorted_vec(cont: Vec<(Kv1, Kv2)>) -> Result<Self, FromSortedVecError> {
+		for (index, pair) in cont.windows(2).enumerate() {
+			match pair[0].0.cmp(&pair[1].0) {
+				Ordering::Less => {}
+				Ordering::Equal => return Err(FromSortedVecError::Duplicate(DuplicateKeyError{column: Column::First, index: Some(index + 1)})),
+				Ordering::Greater => return Err(FromSortedVecError::Unsorted{index: index + 1}),
+			}
+		}
+
+		let mut by_second: Vec<_> = (0..cont.len()).collect();
+		by_second.sort_by(|&l, &r| cont[l].1.cmp(&cont[r].1));
+		if let Some(pair) = by_second.windows(2).find(|pair| cont[pair[0]].1 == cont[pair[1]].1) {
+			let index = if pair[0] > pair[1] { pair[0] } else { pair[1] };
+			return Err(FromSortedVecError::Duplicate(DuplicateKeyError{column: Column::Second, index: Some(index)}));
+		}
+
+		Ok(BidirMap::from_cont(cont))
+	}
+
+	/// Create a `BidirMap` from pairs sorted by the first K/V, without checking them at all.
+	///
+	/// # Safety
+	///
+	/// The first K/Vs must be strictly increasing and the second K/Vs unique, as `from_sorted_vec()` checks;
+	/// otherwise lookups, removals and `insert_sorted()` may find any of the duplicates, or none.
+	pub unsafe fn from_sorted_vec_unchecked(cont: Vec<(Kv1, Kv2)>) -> Self {
+		BidirMap::from_cont(cont)
+	}
+}
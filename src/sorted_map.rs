@@ -0,0 +1,532 @@
+//! A bidirectional map backed by a sorted `Vec`.
+
+
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt;
+use core::iter::{Extend, FromIterator};
+use alloc::vec::{self, Vec};
+use {BidirMap, Column, DuplicateKeyError, FirstColumn, IndexedPrefixIter, Iter, PrefixIter, SecondColumn};
+
+pub use error::FromSortedVecError;
+
+
+/// A bidirectional map kept sorted by the first K/V, with `O(log n)` lookups in both directions.
+///
+/// For read-mostly data: lookups binary-search the pairs (by the first K/V) or a sorted index permutation (by the second K/V),
+/// while `insert()` and `remove_by_*()` shift elements and so take `O(n)`.
+/// Unlike `BidirBTreeMap`, every K/V is stored exactly once, contiguously.
+///
+/// Iteration yields `&(Kv1, Kv2)`, sorted by the first K/V.
+///
+/// Performance: `O(log n)` lookups, `O(n)` mutation.
+#[derive(Clone, Hash, PartialEq, Eq)]
+pub struct BidirSortedMap<Kv1: Ord, Kv2: Ord> {
+	cont: Vec<(Kv1, Kv2)>,
+	/// Indices into `cont`, sorted by the second K/V
+	by_second: Vec<usize>,
+}
+
+impl<Kv1: Ord, Kv2: Ord> BidirSortedMap<Kv1, Kv2> {
+	/// Create a new empty instance of `BidirSortedMap`
+	pub fn new() -> Self {
+		BidirSortedMap{
+			cont: Vec::new(),
+			by_second: Vec::new(),
+		}
+	}
+
+	/// Create a new empty instance of `BidirSortedMap` with the specified capacity.
+	///
+	/// It will be able to hold at least `capacity` elements without reallocating.
+	pub fn with_capacity(capacity: usize) -> Self {
+		BidirSortedMap{
+			cont: Vec::with_capacity(capacity),
+			by_second: Vec::with_capacity(capacity),
+		}
+	}
+
+	/// Create a `BidirSortedMap` from pairs already sorted by the first K/V, taking ownership of the `Vec` without copying it.
+	///
+	/// Checking the first column takes a single pass over adjacent pairs;
+	/// building the index over the second column requires sorting it, in `O(n log n)`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirSortedMap;
+	/// use bidir_map::{Column, DuplicateKeyError, FromSortedVecError};
+	///
+	/// let map = BidirSortedMap::from_sorted_vec(vec![(1, "b"), (2, "a")]).unwrap();
+	/// assert_eq!(map.get_by_second(&"a"), Some(&2));
+	///
+	/// assert_eq!(BidirSortedMap::from_sorted_vec(vec![(2, "a"), (1, "b")]),
+	///            Err(FromSortedVecError::Unsorted{index: 1}));
+	/// assert_eq!(BidirSortedMap::from_sorted_vec(vec![(1, "a"), (2, "a")]),
+	///            Err(FromSortedVecError::Duplicate(DuplicateKeyError{column: Column::Second, index: Some(1)})));
+	/// ```
+	pub fn from_sorted_vec(cont: Vec<(Kv1, Kv2)>) -> Result<Self, FromSortedVecError> {
+		for (index, pair) in cont.windows(2).enumerate() {
+			match pair[0].0.cmp(&pair[1].0) {
+				Ordering::Less => {}
+				Ordering::Equal => return Err(FromSortedVecError::Duplicate(DuplicateKeyError{column: Column::First, index: Some(index + 1)})),
+				Ordering::Greater => return Err(FromSortedVecError::Unsorted{index: index + 1}),
+			}
+		}
+
+		let by_second = Self::index_second(&cont);
+		if let Some(pair) = by_second.windows(2).find(|pair| cont[pair[0]].1 == cont[pair[1]].1) {
+			let index = if pair[0] > pair[1] { pair[0] } else { pair[1] };
+			return Err(FromSortedVecError::Duplicate(DuplicateKeyError{column: Column::Second, index: Some(index)}));
+		}
+
+		Ok(BidirSortedMap{
+			cont,
+			by_second,
+		})
+	}
+
+	/// Clears the map, removing all entries.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirSortedMap;
+	///
+	/// let mut a = BidirSortedMap::new();
+	/// a.insert(1, "a");
+	/// a.clear();
+	/// assert!(a.is_empty());
+	/// ```
+	pub fn clear(&mut self) {
+		self.cont.clear();
+		self.by_second.clear();
+	}
+
+	/// Inserts a K/V-K/V pair into the map.
+	///
+	/// If the map did not have this K/V-K/V pair present, `None` is returned.
+	///
+	/// If the map did have this K/V-K/V pair present, it's updated and the old K/V-K/V pair is returned.
+	///
+	/// If `kv1` and `kv2` were each present in a different pair, both of those are removed and the one holding `kv1` is returned.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirSortedMap;
+	///
+	/// let mut map = BidirSortedMap::new();
+	/// map.insert(1, "a");
+	/// map.insert(2, "b");
+	/// assert_eq!(map.insert(1, "b"), Some((1, "a")));
+	/// assert_eq!(map.len(), 1);
+	/// assert_eq!(map.get_by_second(&"b"), Some(&1));
+	/// ```
+	pub fn insert(&mut self, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)> {
+		let by_first = self.position_of_first(&kv1).map(|idx| self.remove_at(idx));
+		let by_second = self.position_of_second(&kv2).map(|idx| self.remove_at(idx));
+
+		let idx = self.cont.binary_search_by(|kvs| kvs.0.cmp(&kv1)).unwrap_or_else(|idx| idx);
+		let pos = {
+			let cont = &self.cont;
+			self.by_second.binary_search_by(|&i| cont[i].1.cmp(&kv2)).unwrap_or_else(|pos| pos)
+		};
+		for i in self.by_second.iter_mut().filter(|i| **i >= idx) {
+			*i += 1;
+		}
+		self.cont.insert(idx, (kv1, kv2));
+		self.by_second.insert(pos, idx);
+
+		by_first.or(by_second)
+	}
+
+	/// Gets an iterator over the entries of the map, sorted by the first K/V.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirSortedMap;
+	///
+	/// let mut map = BidirSortedMap::new();
+	/// map.insert(3, "a");
+	/// map.insert(1, "c");
+	/// map.insert(2, "b");
+	///
+	/// for kv in map.iter() {
+	///     println!("{}: {}", kv.0, kv.1);
+	/// }
+	///
+	/// let first = map.iter().next().unwrap();
+	/// assert_eq!(first, &(1, "c"));
+	/// ```
+	pub fn iter<'s>(&'s self) -> Iter<'s, Kv1, Kv2> {
+		Iter{
+			iter: self.cont.iter(),
+		}
+	}
+
+	/// Gets an iterator over the first K/V of the map, in ascending order.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirSortedMap;
+	///
+	/// let mut a = BidirSortedMap::new();
+	/// a.insert(2, "a");
+	/// a.insert(1, "b");
+	///
+	/// let keys: Vec<_> = a.first_col().cloned().collect();
+	/// assert_eq!(keys, [1, 2]);
+	/// ```
+	pub fn first_col<'s>(&'s self) -> FirstColumn<'s, Kv1, Kv2> {
+		FirstColumn{
+			iter: self.cont.iter(),
+		}
+	}
+
+	/// Gets an iterator over the second K/V of the map, in the order of the first K/V.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirSortedMap;
+	///
+	/// let mut a = BidirSortedMap::new();
+	/// a.insert(2, "a");
+	/// a.insert(1, "b");
+	///
+	/// let keys: Vec<_> = a.second_col().cloned().collect();
+	/// assert_eq!(keys, ["b", "a"]);
+	/// ```
+	pub fn second_col<'s>(&'s self) -> SecondColumn<'s, Kv1, Kv2> {
+		SecondColumn{
+			iter: self.cont.iter(),
+		}
+	}
+
+	/// Returns the number of elements in the map.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirSortedMap;
+	///
+	/// let mut a = BidirSortedMap::new();
+	/// assert_eq!(a.len(), 0);
+	/// a.insert(1, "a");
+	/// assert_eq!(a.len(), 1);
+	/// ```
+	pub fn len(&self) -> usize {
+		self.cont.len()
+	}
+
+	/// Returns true if the map contains no elements.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirSortedMap;
+	///
+	/// let mut a = BidirSortedMap::new();
+	/// assert!(a.is_empty());
+	/// a.insert(1, "a");
+	/// assert!(!a.is_empty());
+	/// ```
+	pub fn is_empty(&self) -> bool {
+		self.cont.is_empty()
+	}
+
+
+	/// Returns a reference to the second K/V corresponding to the first K/V.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirSortedMap;
+	///
+	/// let mut map = BidirSortedMap::new();
+	/// map.insert(1, "a");
+	/// assert_eq!(map.get_by_first(&1), Some(&"a"));
+	/// assert_eq!(map.get_by_first(&2), None);
+	/// ```
+	pub fn get_by_first<Q>(&self, key: &Q) -> Option<&Kv2>
+		where Kv1: Borrow<Q>,
+		      Q  : Ord + ?Sized,
+	{
+		self.position_of_first(key).map(|idx| &self.cont[idx].1)
+	}
+
+	/// Returns a reference to the first K/V corresponding to the second K/V.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirSortedMap;
+	///
+	/// let mut map = BidirSortedMap::new();
+	/// map.insert(1, "a");
+	/// assert_eq!(map.get_by_second(&"a"), Some(&1));
+	/// assert_eq!(map.get_by_second(&"b"), None);
+	/// ```
+	pub fn get_by_second<Q>(&self, key: &Q) -> Option<&Kv1>
+		where Kv2: Borrow<Q>,
+		      Q  : Ord + ?Sized,
+	{
+		self.position_of_second(key).map(|idx| &self.cont[idx].0)
+	}
+
+	/// Check if the map contains the first K/V
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirSortedMap;
+	///
+	/// let mut map = BidirSortedMap::new();
+	/// map.insert(1, "a");
+	/// assert_eq!(map.contains_first_key(&1), true);
+	/// assert_eq!(map.contains_first_key(&2), false);
+	/// ```
+	pub fn contains_first_key<Q>(&self, key: &Q) -> bool
+		where Kv1: Borrow<Q>,
+		      Q  : Ord + ?Sized,
+	{
+		self.position_of_first(key).is_some()
+	}
+
+	/// Check if the map contains the second K/V
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirSortedMap;
+	///
+	/// let mut map = BidirSortedMap::new();
+	/// map.insert(1, "a");
+	/// assert_eq!(map.contains_second_key(&"a"), true);
+	/// assert_eq!(map.contains_second_key(&"b"), false);
+	/// ```
+	pub fn contains_second_key<Q>(&self, key: &Q) -> bool
+		where Kv2: Borrow<Q>,
+		      Q  : Ord + ?Sized,
+	{
+		self.position_of_second(key).is_some()
+	}
+
+	/// Removes the pair corresponding to the first K/V from the map, returning it if the key was previously in the map.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirSortedMap;
+	///
+	/// let mut map = BidirSortedMap::new();
+	/// map.insert(1, "a");
+	/// assert_eq!(map.remove_by_first(&1), Some((1, "a")));
+	/// assert_eq!(map.remove_by_first(&1), None);
+	/// ```
+	pub fn remove_by_first<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv1: Borrow<Q>,
+		      Q  : Ord + ?Sized,
+	{
+		self.position_of_first(key).map(|idx| self.remove_at(idx))
+	}
+
+	/// Removes the pair corresponding to the second K/V from the map, returning it if the key was previously in the map.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirSortedMap;
+	///
+	/// let mut map = BidirSortedMap::new();
+	/// map.insert(1, "a");
+	/// assert_eq!(map.remove_by_second(&"a"), Some((1, "a")));
+	/// assert_eq!(map.remove_by_second(&"b"), None);
+	/// ```
+	pub fn remove_by_second<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv2: Borrow<Q>,
+		      Q  : Ord + ?Sized,
+	{
+		self.position_of_second(key).map(|idx| self.remove_at(idx))
+	}
+
+	/// Index into `cont` of the pair with this first K/V
+	fn position_of_first<Q>(&self, key: &Q) -> Option<usize>
+		where Kv1: Borrow<Q>,
+		      Q  : Ord + ?Sized,
+	{
+		self.cont.binary_search_by(|kvs| kvs.0.borrow().cmp(key)).ok()
+	}
+
+	/// Index into `cont` of the pair with this second K/V
+	fn position_of_second<Q>(&self, key: &Q) -> Option<usize>
+		where Kv2: Borrow<Q>,
+		      Q  : Ord + ?Sized,
+	{
+		let cont = &self.cont;
+		self.by_second.binary_search_by(|&i| cont[i].1.borrow().cmp(key)).ok().map(|pos| self.by_second[pos])
+	}
+
+	fn index_second(cont: &[(Kv1, Kv2)]) -> Vec<usize> {
+		let mut by_second: Vec<_> = (0..cont.len()).collect();
+		by_second.sort_by(|&l, &r| cont[l].1.cmp(&cont[r].1));
+		by_second
+	}
+
+	fn remove_at(&mut self, idx: usize) -> (Kv1, Kv2) {
+		let pos = {
+			let cont = &self.cont;
+			self.by_second.binary_search_by(|&i| cont[i].1.cmp(&cont[idx].1)).expect("BidirSortedMap's second index out of sync")
+		};
+		self.by_second.remove(pos);
+		for i in self.by_second.iter_mut().filter(|i| **i > idx) {
+			*i -= 1;
+		}
+		self.cont.remove(idx)
+	}
+}
+
+
+impl<Kv1: Ord, Kv2: Ord> BidirSortedMap<Kv1, Kv2> {
+	/// Gets an iterator over the pairs whose first K/V starts with `prefix`, sorted by the first K/V.
+	///
+	/// The matching pairs are found with two binary searches, which requires `Kv1`'s `Ord` to agree with the order of the `str`s,
+	/// as it does for `String` and `&str`.
+	///
+	/// Performance: `O(log n)` to find the first match.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirSortedMap;
+	///
+	/// let mut map = BidirSortedMap::new();
+	/// map.insert("commit", 3);
+	/// map.insert("cherry-pick", 2);
+	/// map.insert("checkout", 1);
+	///
+	/// assert_eq!(map.iter_by_first_prefix("che").collect::<Vec<_>>(), [(&"checkout", &1), (&"cherry-pick", &2)]);
+	/// ```
+	pub fn iter_by_first_prefix<'a>(&'a self, prefix: &'a str) -> PrefixIter<'a, Kv1, Kv2>
+		where Kv1: AsRef<str>,
+	{
+		let key: fn(&(Kv1, Kv2)) -> &[u8] = |kvs| kvs.0.as_ref().as_bytes();
+		self.first_prefix_range(prefix.as_bytes(), key)
+	}
+
+	/// Gets an iterator over the pairs whose first K/V starts with the bytes of `prefix`, sorted by the first K/V.
+	///
+	/// Like `iter_by_first_prefix()`, this requires `Kv1`'s `Ord` to agree with the order of the byte slices.
+	pub fn iter_by_first_byte_prefix<'a>(&'a self, prefix: &'a [u8]) -> PrefixIter<'a, Kv1, Kv2>
+		where Kv1: AsRef<[u8]>,
+	{
+		let key: fn(&(Kv1, Kv2)) -> &[u8] = |kvs| kvs.0.as_ref();
+		self.first_prefix_range(prefix, key)
+	}
+
+	/// Gets an iterator over the pairs whose second K/V starts with `prefix`, sorted by the second K/V.
+	///
+	/// Like `iter_by_first_prefix()`, this requires `Kv2`'s `Ord` to agree with the order of the `str`s.
+	pub fn iter_by_second_prefix<'a>(&'a self, prefix: &'a str) -> IndexedPrefixIter<'a, Kv1, Kv2>
+		where Kv2: AsRef<str>,
+	{
+		self.second_prefix_range(prefix.as_bytes(), |kv2| kv2.as_ref().as_bytes())
+	}
+
+	/// Gets an iterator over the pairs whose second K/V starts with the bytes of `prefix`, sorted by the second K/V.
+	///
+	/// Like `iter_by_first_prefix()`, this requires `Kv2`'s `Ord` to agree with the order of the byte slices.
+	pub fn iter_by_second_byte_prefix<'a>(&'a self, prefix: &'a [u8]) -> IndexedPrefixIter<'a, Kv1, Kv2>
+		where Kv2: AsRef<[u8]>,
+	{
+		self.second_prefix_range(prefix, |kv2| kv2.as_ref())
+	}
+
+	fn first_prefix_range<'a>(&'a self, prefix: &'a [u8], key: fn(&(Kv1, Kv2)) -> &[u8]) -> PrefixIter<'a, Kv1, Kv2> {
+		let start = self.cont.partition_point(|kvs| key(kvs) < prefix);
+		let len = self.cont[start..].partition_point(|kvs| key(kvs).starts_with(prefix));
+		PrefixIter{
+			iter: self.cont[start..start + len].iter(),
+			prefix,
+			key,
+		}
+	}
+
+	fn second_prefix_range<'a, F: Fn(&Kv2) -> &[u8]>(&'a self, prefix: &[u8], key: F) -> IndexedPrefixIter<'a, Kv1, Kv2> {
+		let cont = &self.cont;
+		let start = self.by_second.partition_point(|&idx| key(&cont[idx].1) < prefix);
+		let len = self.by_second[start..].partition_point(|&idx| key(&cont[idx].1).starts_with(prefix));
+		IndexedPrefixIter{
+			indices: self.by_second[start..start + len].iter(),
+			cont,
+		}
+	}
+}
+
+impl<Kv1: Ord + fmt::Debug, Kv2: Ord + fmt::Debug> fmt::Debug for BidirSortedMap<Kv1, Kv2> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("BidirSortedMap").field("cont", &self.cont).finish()
+	}
+}
+
+impl<Kv1: Ord, Kv2: Ord> Default for BidirSortedMap<Kv1, Kv2> {
+	fn default() -> Self {
+		BidirSortedMap::new()
+	}
+}
+
+impl<Kv1: Ord, Kv2: Ord> IntoIterator for BidirSortedMap<Kv1, Kv2> {
+	type Item = (Kv1, Kv2);
+	type IntoIter = vec::IntoIter<(Kv1, Kv2)>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.cont.into_iter()
+	}
+}
+
+impl<'a, Kv1: Ord, Kv2: Ord> IntoIterator for &'a BidirSortedMap<Kv1, Kv2> {
+	type Item = &'a (Kv1, Kv2);
+	type IntoIter = Iter<'a, Kv1, Kv2>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+impl<Kv1: Ord, Kv2: Ord> FromIterator<(Kv1, Kv2)> for BidirSortedMap<Kv1, Kv2> {
+	fn from_iter<T: IntoIterator<Item=(Kv1, Kv2)>>(iter: T) -> Self {
+		let mut map = BidirSortedMap::new();
+		map.extend(iter);
+		map
+	}
+}
+
+impl<Kv1: Ord, Kv2: Ord> Extend<(Kv1, Kv2)> for BidirSortedMap<Kv1, Kv2> {
+	fn extend<T: IntoIterator<Item=(Kv1, Kv2)>>(&mut self, iter: T) {
+		for (kv1, kv2) in iter {
+			self.insert(kv1, kv2);
+		}
+	}
+}
+
+impl<Kv1: Ord, Kv2: Ord> From<BidirMap<Kv1, Kv2>> for BidirSortedMap<Kv1, Kv2> {
+	fn from(map: BidirMap<Kv1, Kv2>) -> Self {
+		// Both columns are already unique, so there's nothing to check
+		let mut cont = map.cont;
+		cont.sort_by(|l, r| l.0.cmp(&r.0));
+		BidirSortedMap{
+			by_second: BidirSortedMap::index_second(&cont),
+			cont,
+		}
+	}
+}
+
+impl<Kv1: Ord, Kv2: Ord> From<BidirSortedMap<Kv1, Kv2>> for BidirMap<Kv1, Kv2> {
+	/// The resulting map is ordered by the first K/V.
+	fn from(map: BidirSortedMap<Kv1, Kv2>) -> Self {
+		BidirMap::from_cont(map.cont)
+	}
+}
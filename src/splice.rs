@@ -0,0 +1,141 @@
+//! Replacing a positional range of a `BidirMap`'s pairs, see `BidirMap::splice()`.
+
+
+use core::ops::{Bound, RangeBounds};
+use alloc::vec::{self, Vec};
+use {BatchInsertError, BidirMap, CapacityError, Column};
+
+
+impl<Kv1: PartialEq, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
+	/// Replaces the pairs in the positional range with the new ones, keeping the order of all pairs outside of it,
+	/// and returns an iterator over the removed pairs, like `Vec::splice()`.
+	///
+	/// The new pairs are inserted in order where the range was, if none of them shares a K/V with a pair outside the range,
+	/// or with another new pair; a pair inside the range may share K/Vs with them, since it's removed.
+	/// Otherwise, the map is left as it was and the first conflicting new pair is reported,
+	/// as by `extend_atomic()`, with `earlier: None` meaning the K/V is in one of the pairs kept.
+	/// The map's duplicate policy isn't consulted: a splice either happens whole or not at all.
+	///
+	/// The splice is done by the time this returns, so the returned iterator only hands over the removed pairs;
+	/// dropping it early drops the rest of them, the map having all of the new pairs either way.
+	/// An empty replacement makes this a removal of the range.
+	///
+	/// Performance: `O(m·n)`, for `m` new pairs.
+	///
+	/// # Panics
+	///
+	/// If the range starts after it ends or ends past the map's length,
+	/// or if the map would grow past its `max_len()`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::{BidirMap, Column};
+	///
+	/// let mut map: BidirMap<_, _> = vec![(1, "a"), (2, "b"), (3, "c"), (4, "d")].into_iter().collect();
+	///
+	/// let removed: Vec<_> = map.splice(1..3, vec![(5, "b"), (6, "f"), (7, "g")]).unwrap().collect();
+	/// assert_eq!(removed, [(2, "b"), (3, "c")]);
+	/// assert_eq!(map.iter().cloned().collect::<Vec<_>>(), [(1, "a"), (5, "b"), (6, "f"), (7, "g"), (4, "d")]);
+	///
+	/// let err = map.splice(..1, vec![(8, "d")]).unwrap_err();
+	/// assert_eq!((err.index, err.column, err.earlier), (0, Column::Second, None));
+	/// assert_eq!(map.len(), 5);
+	/// ```
+	pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Result<Splice<Kv1, Kv2>, BatchInsertError<Kv1, Kv2>>
+		where R: RangeBounds<usize>,
+		      I: IntoIterator<Item=(Kv1, Kv2)>,
+	{
+		let (start, end) = resolve(range, self.cont.len());
+		let batch: Vec<_> = replace_with.into_iter().collect();
+
+		let mut conflict = None;
+		for (index, kvs) in batch.iter().enumerate() {
+			let earlier = &batch[..index];
+			let kept = || self.cont[..start].iter().chain(&self.cont[end..]);
+			conflict = if kept().any(|other| other.0 == kvs.0) {
+				Some((index, Column::First, None))
+			} else if let Some(earlier) = earlier.iter().position(|other| other.0 == kvs.0) {
+				Some((index, Column::First, Some(earlier)))
+			} else if kept().any(|other| other.1 == kvs.1) {
+				Some((index, Column::Second, None))
+			} else {
+				earlier.iter().position(|other| other.1 == kvs.1).map(|earlier| (index, Column::Second, Some(earlier)))
+			};
+			if conflict.is_some() {
+				break;
+			}
+		}
+
+		if let Some((index, column, earlier)) = conflict {
+			return Err(BatchInsertError{
+				index,
+				column,
+				earlier,
+				pair: batch.into_iter().nth(index).unwrap(),
+			});
+		}
+
+		if let Some(max_len) = self.max_len {
+			let len = self.cont.len() - (end - start) + batch.len();
+			if len > self.cont.len() && len > max_len {
+				panic!("BidirMap: {}, refusing to grow past max_len()", CapacityError{capacity: max_len});
+			}
+		}
+
+		for _ in &batch {
+			self.stats.inserted();
+		}
+		let removed: Vec<_> = self.cont.splice(start..end, batch).collect();
+		self.stats.removed(removed.len());
+		self.rebuild_index();
+		Ok(Splice{
+			iter: removed.into_iter(),
+		})
+	}
+}
+
+/// The start and end of the range, as `Vec` would panic with for a range not in `0..=len`
+fn resolve<R: RangeBounds<usize>>(range: R, len: usize) -> (usize, usize) {
+	let start = match range.start_bound() {
+		Bound::Included(&start) => start,
+		Bound::Excluded(&start) => start.checked_add(1).expect("BidirMap::splice(): range starts past usize::MAX"),
+		Bound::Unbounded => 0,
+	};
+	let end = match range.end_bound() {
+		Bound::Included(&end) => end.checked_add(1).expect("BidirMap::splice(): range ends past usize::MAX"),
+		Bound::Excluded(&end) => end,
+		Bound::Unbounded => len,
+	};
+	assert!(start <= end, "BidirMap::splice(): range starts at {} but ends at {}", start, end);
+	assert!(end <= len, "BidirMap::splice(): range ends at {} past the map's length of {}", end, len);
+	(start, end)
+}
+
+
+/// An iterator over the pairs removed by `BidirMap::splice()`, in their former order.
+///
+/// See documentation of `BidirMap::splice()` for more.
+#[derive(Debug)]
+pub struct Splice<Kv1, Kv2> {
+	iter: vec::IntoIter<(Kv1, Kv2)>,
+}
+
+impl<Kv1, Kv2> Iterator for Splice<Kv1, Kv2> {
+	type Item = (Kv1, Kv2);
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next()
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.iter.size_hint()
+	}
+}
+
+impl<Kv1, Kv2> DoubleEndedIterator for Splice<Kv1, Kv2> {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		self.iter.next_back()
+	}
+}
+
+impl<Kv1, Kv2> ExactSizeIterator for Splice<Kv1, Kv2> {}
@@ -0,0 +1,247 @@
+//! Bidirectional maps over tables fixed at compile time, sorted into lookup indices during constant evaluation.
+
+
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::marker::PhantomData;
+use Iter;
+
+
+/// A read-only bidirectional map over a `'static` table of pairs, with `O(log n)` binary-search lookups both ways.
+///
+/// Usually built by `static_bidir_map!`, which sorts the indices and rejects repeated K/Vs at compile time.
+/// Iteration is in the order the pairs were written in.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use] extern crate bidir_map;
+/// # fn main() {
+/// static_bidir_map! {
+///     static METHODS: StaticBidirMap<u8, &'static str> = {
+///         1 => "GET",
+///         2 => "PUT",
+///         3 => "DELETE",
+///     };
+/// }
+///
+/// assert_eq!(METHODS.get_by_first(&2), Some(&"PUT"));
+/// assert_eq!(METHODS.get_by_second("DELETE"), Some(&3));
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct StaticBidirMap<'a, Kv1: 'a, Kv2: 'a> {
+	pairs: &'a [(Kv1, Kv2)],
+	by_first: &'a [usize],
+	by_second: &'a [usize],
+}
+
+impl<'a, Kv1, Kv2> StaticBidirMap<'a, Kv1, Kv2> {
+	/// Wraps a table of pairs with the indices of its pairs in order of their first and second K/Vs.
+	///
+	/// Indices that aren't thus sorted, or K/Vs repeated within a column, don't cause undefined behaviour,
+	/// but lookups will return wrong results. `static_bidir_map!` produces correct indices.
+	pub const fn from_parts(pairs: &'a [(Kv1, Kv2)], by_first: &'a [usize], by_second: &'a [usize]) -> Self {
+		assert!(by_first.len() == pairs.len() && by_second.len() == pairs.len(), "index length differs from table's");
+		StaticBidirMap{
+			pairs,
+			by_first,
+			by_second,
+		}
+	}
+
+	/// Gets an iterator over the entries of the map, in the order of the table.
+	pub fn iter(&self) -> Iter<'a, Kv1, Kv2> {
+		Iter{
+			iter: self.pairs.iter(),
+		}
+	}
+
+	/// Returns the number of elements in the map.
+	pub const fn len(&self) -> usize {
+		self.pairs.len()
+	}
+
+	/// Returns true if the map contains no elements.
+	pub const fn is_empty(&self) -> bool {
+		self.pairs.is_empty()
+	}
+
+	/// Returns the table of pairs.
+	pub const fn as_slice(&self) -> &'a [(Kv1, Kv2)] {
+		self.pairs
+	}
+}
+
+impl<'a, Kv1: Ord, Kv2: Ord> StaticBidirMap<'a, Kv1, Kv2> {
+	/// Returns a reference to the second K/V corresponding to the first K/V.
+	pub fn get_by_first<Q>(&self, key: &Q) -> Option<&'a Kv2>
+		where Kv1: Borrow<Q>,
+		      Q  : Ord + ?Sized,
+	{
+		let pairs = self.pairs;
+		self.by_first.binary_search_by(|&idx| pairs[idx].0.borrow().cmp(key)).ok().map(|pos| &pairs[self.by_first[pos]].1)
+	}
+
+	/// Returns a reference to the first K/V corresponding to the second K/V.
+	pub fn get_by_second<Q>(&self, key: &Q) -> Option<&'a Kv1>
+		where Kv2: Borrow<Q>,
+		      Q  : Ord + ?Sized,
+	{
+		let pairs = self.pairs;
+		self.by_second.binary_search_by(|&idx| pairs[idx].1.borrow().cmp(key)).ok().map(|pos| &pairs[self.by_second[pos]].0)
+	}
+
+	/// Check if the map contains the first K/V
+	pub fn contains_first_key<Q>(&self, key: &Q) -> bool
+		where Kv1: Borrow<Q>,
+		      Q  : Ord + ?Sized,
+	{
+		self.get_by_first(key).is_some()
+	}
+
+	/// Check if the map contains the second K/V
+	pub fn contains_second_key<Q>(&self, key: &Q) -> bool
+		where Kv2: Borrow<Q>,
+		      Q  : Ord + ?Sized,
+	{
+		self.get_by_second(key).is_some()
+	}
+}
+
+impl<'a, Kv1, Kv2> Clone for StaticBidirMap<'a, Kv1, Kv2> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<'a, Kv1, Kv2> Copy for StaticBidirMap<'a, Kv1, Kv2> {}
+
+impl<'a, Kv1, Kv2> IntoIterator for StaticBidirMap<'a, Kv1, Kv2> {
+	type Item = &'a (Kv1, Kv2);
+	type IntoIter = Iter<'a, Kv1, Kv2>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+
+/// Build a `static` `StaticBidirMap` from a set of K/V-K/V pairs, sorting its indices at compile time.
+///
+/// Both K/V types must be among the integers, `bool`, `char`, `&'static str` and `&'static [u8]`,
+/// which can be compared during constant evaluation, in the same order as by `Ord`.
+///
+/// A K/V repeated within a column fails compilation:
+///
+/// ```compile_fail
+/// #[macro_use] extern crate bidir_map;
+/// static_bidir_map! {
+///     static METHODS: StaticBidirMap<u8, &'static str> = {
+///         1 => "GET",
+///         2 => "GET",
+///     };
+/// }
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! static_bidir_map {
+	(@sorted $kv:ty, $pairs:ident, $column:tt, $what:expr) => {{
+		let mut idx = [0; $pairs.len()];
+		let mut i = 0;
+		while i < idx.len() {
+			idx[i] = i;
+			i += 1;
+		}
+
+		let mut i = 1;
+		while i < idx.len() {
+			let mut j = i;
+			while j > 0 && $crate::static_map::ConstOrd::<$kv>::lt(&$pairs[idx[j]].$column, &$pairs[idx[j - 1]].$column) {
+				let moved = idx[j];
+				idx[j] = idx[j - 1];
+				idx[j - 1] = moved;
+				j -= 1;
+			}
+			i += 1;
+		}
+
+		let mut i = 1;
+		while i < idx.len() {
+			if $crate::static_map::ConstOrd::<$kv>::eq(&$pairs[idx[i - 1]].$column, &$pairs[idx[i]].$column) {
+				panic!($what);
+			}
+			i += 1;
+		}
+		idx
+	}};
+
+	($(#[$attr:meta])* $vis:vis static $name:ident: StaticBidirMap<$kv1:ty, $kv2:ty> = { $($first:expr => $second:expr),* $(,)* };) => {
+		$(#[$attr])*
+		$vis static $name: $crate::static_map::StaticBidirMap<'static, $kv1, $kv2> = {
+			const PAIRS: &[($kv1, $kv2)] = &[$(($first, $second)),*];
+			const BY_FIRST: [usize; PAIRS.len()] = $crate::static_bidir_map!(@sorted $kv1, PAIRS, 0, "a first K/V is repeated in static_bidir_map!");
+			const BY_SECOND: [usize; PAIRS.len()] = $crate::static_bidir_map!(@sorted $kv2, PAIRS, 1, "a second K/V is repeated in static_bidir_map!");
+			$crate::static_map::StaticBidirMap::from_parts(PAIRS, &BY_FIRST, &BY_SECOND)
+		};
+	};
+}
+
+
+/// Comparisons usable during constant evaluation, for `static_bidir_map!`; implemented as inherent `const fn`s for each supported type.
+#[doc(hidden)]
+pub struct ConstOrd<T: ?Sized>(PhantomData<T>);
+
+macro_rules! impl_const_ord_for_primitive {
+	($($kv:ty),*) => {$(
+		impl ConstOrd<$kv> {
+			pub const fn lt(lhs: &$kv, rhs: &$kv) -> bool {
+				*lhs < *rhs
+			}
+
+			pub const fn eq(lhs: &$kv, rhs: &$kv) -> bool {
+				*lhs == *rhs
+			}
+		}
+	)*};
+}
+
+impl_const_ord_for_primitive!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, bool, char);
+
+impl ConstOrd<&'static [u8]> {
+	pub const fn lt(lhs: &&[u8], rhs: &&[u8]) -> bool {
+		matches!(cmp_bytes(lhs, rhs), Ordering::Less)
+	}
+
+	pub const fn eq(lhs: &&[u8], rhs: &&[u8]) -> bool {
+		matches!(cmp_bytes(lhs, rhs), Ordering::Equal)
+	}
+}
+
+impl ConstOrd<&'static str> {
+	pub const fn lt(lhs: &&str, rhs: &&str) -> bool {
+		matches!(cmp_bytes(lhs.as_bytes(), rhs.as_bytes()), Ordering::Less)
+	}
+
+	pub const fn eq(lhs: &&str, rhs: &&str) -> bool {
+		matches!(cmp_bytes(lhs.as_bytes(), rhs.as_bytes()), Ordering::Equal)
+	}
+}
+
+/// Lexicographic comparison, as `Ord` for slices and `str`s
+const fn cmp_bytes(lhs: &[u8], rhs: &[u8]) -> Ordering {
+	let mut i = 0;
+	while i < lhs.len() && i < rhs.len() {
+		if lhs[i] != rhs[i] {
+			return if lhs[i] < rhs[i] { Ordering::Less } else { Ordering::Greater };
+		}
+		i += 1;
+	}
+	if lhs.len() < rhs.len() {
+		Ordering::Less
+	} else if lhs.len() > rhs.len() {
+		Ordering::Greater
+	} else {
+		Ordering::Equal
+	}
+}
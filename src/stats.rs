@@ -0,0 +1,187 @@
+//! Usage counters kept by a `BidirMap`, see `BidirMap::stats()`.
+//!
+//! Without the `stats` feature the counters are empty and recording into them compiles to nothing.
+
+
+#[cfg(feature = "stats")]
+use core::sync::atomic::{AtomicU64, Ordering};
+use error::Column;
+#[cfg(feature = "stats")]
+use BidirMap;
+
+
+/// Totals of how a `BidirMap` was used since it was created or its stats were last reset.
+///
+/// See documentation of `BidirMap::stats()` for more.
+#[cfg(feature = "stats")]
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub struct MapStats {
+	/// Lookups by a first K/V, via `get_by_first()`, `get_mut_by_first()`, `contains_first_key()` or `get_index_of_first()`
+	pub lookups_by_first: u64,
+	/// Lookups by a second K/V, via `get_by_second()`, `get_mut_by_second()`, `contains_second_key()` or `get_index_of_second()`
+	pub lookups_by_second: u64,
+	/// Lookups, in either direction, that found a pair
+	pub hits: u64,
+	/// Lookups, in either direction, that didn't find a pair
+	pub misses: u64,
+	/// Pairs added, by `insert()` or `extend()`
+	pub inserts: u64,
+	/// Pairs removed, whether explicitly, by `clear()`, or displaced by an insertion
+	pub removals: u64,
+	/// K/V comparisons made by linear scans, during lookups, insertions and removals by K/V
+	pub comparisons: u64,
+}
+
+
+/// The counters behind `BidirMap::stats()`.
+///
+/// Lookups take `&self`, so the counters are relaxed atomics, which keeps the map `Sync`.
+#[derive(Default)]
+pub(crate) struct Counters {
+	#[cfg(feature = "stats")]
+	lookups_by_first: AtomicU64,
+	#[cfg(feature = "stats")]
+	lookups_by_second: AtomicU64,
+	#[cfg(feature = "stats")]
+	hits: AtomicU64,
+	#[cfg(feature = "stats")]
+	misses: AtomicU64,
+	#[cfg(feature = "stats")]
+	inserts: AtomicU64,
+	#[cfg(feature = "stats")]
+	removals: AtomicU64,
+	#[cfg(feature = "stats")]
+	comparisons: AtomicU64,
+}
+
+impl Counters {
+	#[inline]
+	pub fn lookup(&self, column: Column, found: bool) {
+		#[cfg(feature = "stats")]
+		{
+			match column {
+				Column::First => bump(&self.lookups_by_first, 1),
+				Column::Second => bump(&self.lookups_by_second, 1),
+			}
+			bump(if found { &self.hits } else { &self.misses }, 1);
+		}
+		#[cfg(not(feature = "stats"))]
+		let _ = (column, found);
+	}
+
+	#[inline]
+	pub fn compared(&self, count: usize) {
+		#[cfg(feature = "stats")]
+		bump(&self.comparisons, count as u64);
+		#[cfg(not(feature = "stats"))]
+		let _ = count;
+	}
+
+	#[inline]
+	pub fn inserted(&self) {
+		#[cfg(feature = "stats")]
+		bump(&self.inserts, 1);
+	}
+
+	#[inline]
+	pub fn removed(&self, count: usize) {
+		#[cfg(feature = "stats")]
+		bump(&self.removals, count as u64);
+		#[cfg(not(feature = "stats"))]
+		let _ = count;
+	}
+
+	#[cfg(feature = "stats")]
+	fn snapshot(&self) -> MapStats {
+		MapStats{
+			lookups_by_first: self.lookups_by_first.load(Ordering::Relaxed),
+			lookups_by_second: self.lookups_by_second.load(Ordering::Relaxed),
+			hits: self.hits.load(Ordering::Relaxed),
+			misses: self.misses.load(Ordering::Relaxed),
+			inserts: self.inserts.load(Ordering::Relaxed),
+			removals: self.removals.load(Ordering::Relaxed),
+			comparisons: self.comparisons.load(Ordering::Relaxed),
+		}
+	}
+
+	#[cfg(feature = "stats")]
+	fn from_snapshot(stats: MapStats) -> Counters {
+		Counters{
+			lookups_by_first: AtomicU64::new(stats.lookups_by_first),
+			lookups_by_second: AtomicU64::new(stats.lookups_by_second),
+			hits: AtomicU64::new(stats.hits),
+			misses: AtomicU64::new(stats.misses),
+			inserts: AtomicU64::new(stats.inserts),
+			removals: AtomicU64::new(stats.removals),
+			comparisons: AtomicU64::new(stats.comparisons),
+		}
+	}
+}
+
+impl Clone for Counters {
+	fn clone(&self) -> Counters {
+		#[cfg(feature = "stats")]
+		{
+			Counters::from_snapshot(self.snapshot())
+		}
+		#[cfg(not(feature = "stats"))]
+		Counters{}
+	}
+}
+
+#[cfg(feature = "stats")]
+fn bump(counter: &AtomicU64, by: u64) {
+	counter.fetch_add(by, Ordering::Relaxed);
+}
+
+
+#[cfg(feature = "stats")]
+impl<Kv1, Kv2, S> BidirMap<Kv1, Kv2, S> {
+	/// Returns the totals of lookups, insertions, removals, and K/V comparisons made on the map
+	/// since it was created or `reset_stats()` was last called.
+	///
+	/// Lookups are counted by the `get_by_*()`, `get_mut_by_*()`, `contains_*_key()` and `get_index_of_*()` methods,
+	/// including when other methods and types call them.
+	/// Comparisons count every K/V compared by a linear scan, so they're a proxy for the cost of lookups;
+	/// ones answered by an index, like `get_by_first_indexed()`, compare no K/Vs this way.
+	///
+	/// The counters aren't synchronised with each other, so a snapshot taken while another thread is looking K/Vs up
+	/// may see one lookup's counters only partially updated. Clones start with the stats of their original.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert(1, "a");
+	/// map.insert(2, "b");
+	///
+	/// assert_eq!(map.get_by_second(&"b"), Some(&2));
+	/// assert_eq!(map.get_by_first(&3), None);
+	///
+	/// let stats = map.stats();
+	/// assert_eq!((stats.lookups_by_first, stats.lookups_by_second), (1, 1));
+	/// assert_eq!((stats.hits, stats.misses), (1, 1));
+	/// assert_eq!(stats.inserts, 2);
+	/// ```
+	pub fn stats(&self) -> MapStats {
+		self.stats.snapshot()
+	}
+
+	/// Zeroes all of the map's stats.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::{BidirMap, MapStats};
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert(1, "a");
+	/// map.reset_stats();
+	/// assert_eq!(map.stats(), MapStats::default());
+	/// ```
+	pub fn reset_stats(&mut self) {
+		self.stats = Counters::default();
+	}
+}
@@ -0,0 +1,296 @@
+//! The containers a `BidirMap` can keep its pairs in, see `Storage`.
+
+
+use core::ops::{Deref, DerefMut};
+use core::mem::{self, MaybeUninit};
+use core::iter::FusedIterator;
+use core::{fmt, ptr, slice};
+use alloc::vec::Vec;
+
+
+/// A contiguous container of pairs, in which a `BidirMap` keeps its pairs.
+///
+/// The pairs are accessed as a slice by dereferencing, which gives iteration, `len()` and indexing,
+/// and moved out, in order, by `into_iter()`, which also turns the map into an iterator;
+/// only the methods changing the length are the storage's own.
+/// The map keeps the pairs unique, so the storage needn't know anything about them.
+///
+/// `Vec` is the default storage; `InlineStorage` keeps a bounded number of pairs without allocating.
+/// Any other growable contiguous container, such as a small-vector type, can implement it in a few lines.
+///
+/// # Examples
+///
+/// ```
+/// use bidir_map::BidirMap;
+/// use bidir_map::storage::InlineStorage;
+///
+/// let mut map: BidirMap<u8, char, InlineStorage<_, _, 4>> = BidirMap::with_storage(InlineStorage::new());
+/// map.insert(1, 'a');
+/// map.insert(2, 'b');
+/// assert_eq!(map.get_by_second(&'b'), Some(&2));
+/// assert_eq!(map.capacity(), 4);
+/// ```
+pub trait Storage<Kv1, Kv2>: DerefMut<Target=[(Kv1, Kv2)]> + IntoIterator<Item=(Kv1, Kv2)> {
+	/// Appends a pair.
+	///
+	/// May panic if the storage can't grow; `capacity()` says when that happens.
+	fn push(&mut self, kvs: (Kv1, Kv2));
+
+	/// Removes and returns the pair at `idx`, replacing it with the last pair.
+	///
+	/// `idx` is always in bounds.
+	fn swap_remove(&mut self, idx: usize) -> (Kv1, Kv2);
+
+	/// Removes and returns the pair at `idx`, shifting all pairs after it one back.
+	///
+	/// `idx` is always in bounds.
+	fn remove(&mut self, idx: usize) -> (Kv1, Kv2);
+
+	/// Removes all pairs.
+	fn clear(&mut self);
+
+	/// Returns the number of pairs the storage can hold without reallocating, or at all if it can't reallocate.
+	fn capacity(&self) -> usize;
+
+	/// Gives up the capacity past `capacity` pairs, if the storage can, keeping at least room for the pairs it holds.
+	///
+	/// The default implementation keeps all of it, for storages that can't reallocate.
+	fn shrink_to(&mut self, capacity: usize) {
+		let _ = capacity;
+	}
+
+	/// Removes all pairs for which `keep` returns `false`, in order, keeping the order of the rest.
+	///
+	/// The default implementation shifts the rest back after every removal; storages with a faster way should override it.
+	fn retain<F: FnMut(&(Kv1, Kv2)) -> bool>(&mut self, mut keep: F) {
+		let mut idx = 0;
+		while idx < self.len() {
+			if keep(&self[idx]) {
+				idx += 1;
+			} else {
+				self.remove(idx);
+			}
+		}
+	}
+}
+
+impl<Kv1, Kv2> Storage<Kv1, Kv2> for Vec<(Kv1, Kv2)> {
+	fn push(&mut self, kvs: (Kv1, Kv2)) {
+		Vec::push(self, kvs)
+	}
+
+	fn swap_remove(&mut self, idx: usize) -> (Kv1, Kv2) {
+		Vec::swap_remove(self, idx)
+	}
+
+	fn remove(&mut self, idx: usize) -> (Kv1, Kv2) {
+		Vec::remove(self, idx)
+	}
+
+	fn clear(&mut self) {
+		Vec::clear(self)
+	}
+
+	fn capacity(&self) -> usize {
+		Vec::capacity(self)
+	}
+
+	fn shrink_to(&mut self, capacity: usize) {
+		Vec::shrink_to(self, capacity)
+	}
+
+	fn retain<F: FnMut(&(Kv1, Kv2)) -> bool>(&mut self, keep: F) {
+		Vec::retain(self, keep)
+	}
+}
+
+
+/// A `Storage` of at most `N` pairs, kept inline instead of on the heap.
+///
+/// Pushing a pair into a full `InlineStorage` panics; the map's `capacity()` is `N`,
+/// so `BidirMap::try_insert_within_capacity()` refuses such pairs instead.
+pub struct InlineStorage<Kv1, Kv2, const N: usize> {
+	// The first `len` slots are initialised
+	slots: [MaybeUninit<(Kv1, Kv2)>; N],
+	len: usize,
+}
+
+impl<Kv1, Kv2, const N: usize> InlineStorage<Kv1, Kv2, N> {
+	/// Create a new empty `InlineStorage`.
+	pub fn new() -> Self {
+		InlineStorage{
+			slots: [const { MaybeUninit::uninit() }; N],
+			len: 0,
+		}
+	}
+}
+
+impl<Kv1, Kv2, const N: usize> Storage<Kv1, Kv2> for InlineStorage<Kv1, Kv2, N> {
+	fn push(&mut self, kvs: (Kv1, Kv2)) {
+		assert!(self.len < N, "InlineStorage is full at {} pairs", N);
+		self.slots[self.len].write(kvs);
+		self.len += 1;
+	}
+
+	fn swap_remove(&mut self, idx: usize) -> (Kv1, Kv2) {
+		let last = self.len - 1;
+		self.slots.swap(idx, last);
+		self.pop_last()
+	}
+
+	fn remove(&mut self, idx: usize) -> (Kv1, Kv2) {
+		self.slots[idx..self.len].rotate_left(1);
+		self.pop_last()
+	}
+
+	fn clear(&mut self) {
+		let len = self.len;
+		// Forget the pairs first, so that a panicking destructor leaks the rest rather than dropping them twice
+		self.len = 0;
+		unsafe { ptr::drop_in_place(ptr::slice_from_raw_parts_mut(self.slots.as_mut_ptr() as *mut (Kv1, Kv2), len)) }
+	}
+
+	fn capacity(&self) -> usize {
+		N
+	}
+}
+
+impl<Kv1, Kv2, const N: usize> InlineStorage<Kv1, Kv2, N> {
+	fn pop_last(&mut self) -> (Kv1, Kv2) {
+		self.len -= 1;
+		// The slot was initialised, and is now past `len`, so it won't be read again
+		unsafe { self.slots[self.len].assume_init_read() }
+	}
+}
+
+impl<Kv1, Kv2, const N: usize> IntoIterator for InlineStorage<Kv1, Kv2, N> {
+	type Item = (Kv1, Kv2);
+	type IntoIter = IntoIter<Kv1, Kv2, N>;
+
+	fn into_iter(mut self) -> Self::IntoIter {
+		let back = self.len;
+		// The pairs are the iterator's now, so the storage mustn't drop them
+		self.len = 0;
+		IntoIter{
+			slots: mem::replace(&mut self.slots, [const { MaybeUninit::uninit() }; N]),
+			front: 0,
+			back,
+		}
+	}
+}
+
+impl<Kv1, Kv2, const N: usize> Deref for InlineStorage<Kv1, Kv2, N> {
+	type Target = [(Kv1, Kv2)];
+
+	fn deref(&self) -> &[(Kv1, Kv2)] {
+		unsafe { slice::from_raw_parts(self.slots.as_ptr() as *const (Kv1, Kv2), self.len) }
+	}
+}
+
+impl<Kv1, Kv2, const N: usize> DerefMut for InlineStorage<Kv1, Kv2, N> {
+	fn deref_mut(&mut self) -> &mut [(Kv1, Kv2)] {
+		unsafe { slice::from_raw_parts_mut(self.slots.as_mut_ptr() as *mut (Kv1, Kv2), self.len) }
+	}
+}
+
+impl<Kv1, Kv2, const N: usize> Drop for InlineStorage<Kv1, Kv2, N> {
+	fn drop(&mut self) {
+		Storage::clear(self)
+	}
+}
+
+impl<Kv1: Clone, Kv2: Clone, const N: usize> Clone for InlineStorage<Kv1, Kv2, N> {
+	fn clone(&self) -> Self {
+		let mut clone = InlineStorage::new();
+		for kvs in self.iter() {
+			clone.push(kvs.clone());
+		}
+		clone
+	}
+}
+
+impl<Kv1: fmt::Debug, Kv2: fmt::Debug, const N: usize> fmt::Debug for InlineStorage<Kv1, Kv2, N> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_list().entries(self.iter()).finish()
+	}
+}
+
+impl<Kv1, Kv2, const N: usize> Default for InlineStorage<Kv1, Kv2, N> {
+	fn default() -> Self {
+		InlineStorage::new()
+	}
+}
+
+
+/// An owning iterator over the pairs of an `InlineStorage`.
+pub struct IntoIter<Kv1, Kv2, const N: usize> {
+	// The slots in `front..back` are initialised
+	slots: [MaybeUninit<(Kv1, Kv2)>; N],
+	front: usize,
+	back: usize,
+}
+
+impl<Kv1, Kv2, const N: usize> IntoIter<Kv1, Kv2, N> {
+	/// Returns the remaining pairs as a slice.
+	pub fn as_slice(&self) -> &[(Kv1, Kv2)] {
+		unsafe { slice::from_raw_parts(self.slots[self.front..self.back].as_ptr() as *const (Kv1, Kv2), self.back - self.front) }
+	}
+}
+
+impl<Kv1, Kv2, const N: usize> Iterator for IntoIter<Kv1, Kv2, N> {
+	type Item = (Kv1, Kv2);
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.front == self.back {
+			None
+		} else {
+			self.front += 1;
+			// The slot was initialised, and is now before `front`, so it won't be read again
+			Some(unsafe { self.slots[self.front - 1].assume_init_read() })
+		}
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(self.back - self.front, Some(self.back - self.front))
+	}
+}
+
+impl<Kv1, Kv2, const N: usize> DoubleEndedIterator for IntoIter<Kv1, Kv2, N> {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		if self.front == self.back {
+			None
+		} else {
+			self.back -= 1;
+			// The slot was initialised, and is now past `back`, so it won't be read again
+			Some(unsafe { self.slots[self.back].assume_init_read() })
+		}
+	}
+}
+
+impl<Kv1, Kv2, const N: usize> ExactSizeIterator for IntoIter<Kv1, Kv2, N> {}
+
+impl<Kv1, Kv2, const N: usize> FusedIterator for IntoIter<Kv1, Kv2, N> {}
+
+impl<Kv1, Kv2, const N: usize> Drop for IntoIter<Kv1, Kv2, N> {
+	fn drop(&mut self) {
+		let remaining: *mut [(Kv1, Kv2)] = ptr::slice_from_raw_parts_mut(self.slots[self.front..self.back].as_mut_ptr() as *mut (Kv1, Kv2), self.back - self.front);
+		// Forget the pairs first, so that a panicking destructor leaks the rest rather than dropping them twice
+		self.front = self.back;
+		unsafe { ptr::drop_in_place(remaining) }
+	}
+}
+
+impl<Kv1: Clone, Kv2: Clone, const N: usize> Clone for IntoIter<Kv1, Kv2, N> {
+	fn clone(&self) -> Self {
+		let mut clone = InlineStorage::new();
+		for kvs in self.as_slice() {
+			clone.push(kvs.clone());
+		}
+		clone.into_iter()
+	}
+}
+
+impl<Kv1: fmt::Debug, Kv2: fmt::Debug, const N: usize> fmt::Debug for IntoIter<Kv1, Kv2, N> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_tuple("IntoIter").field(&self.as_slice()).finish()
+	}
+}
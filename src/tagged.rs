@@ -0,0 +1,279 @@
+//! Newtypes telling a `BidirMap`'s columns apart by type, see `TaggedBidirMap`.
+
+
+use core::borrow::Borrow;
+use core::iter::FromIterator;
+use core::ops::{Deref, DerefMut};
+use {BidirMap, Iter as PairIter};
+
+
+/// A first K/V, or a reference to one, tagged as such so that it can't be passed where a second K/V is expected.
+///
+/// Dereferences to the K/V, and compares, orders and hashes as it does; `as_ref()` and `as_deref()` make lookup keys.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct First<T>(pub T);
+
+/// A second K/V, or a reference to one, tagged as such so that it can't be passed where a first K/V is expected.
+///
+/// Dereferences to the K/V, and compares, orders and hashes as it does; `as_ref()` and `as_deref()` make lookup keys.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Second<T>(pub T);
+
+macro_rules! impl_tag {
+	($tag:ident) => {
+		impl<T> $tag<T> {
+			/// Unwraps the K/V.
+			pub fn into_inner(self) -> T {
+				self.0
+			}
+
+			/// Tags a reference to the K/V, as a lookup key.
+			pub fn as_ref(&self) -> $tag<&T> {
+				$tag(&self.0)
+			}
+
+			/// Tags a reference to what the K/V dereferences to, as a lookup key: a `str` for a `String`, for example.
+			pub fn as_deref(&self) -> $tag<&T::Target>
+				where T: Deref,
+			{
+				$tag(&*self.0)
+			}
+		}
+
+		impl<T> Deref for $tag<T> {
+			type Target = T;
+
+			fn deref(&self) -> &T {
+				&self.0
+			}
+		}
+
+		impl<T> DerefMut for $tag<T> {
+			fn deref_mut(&mut self) -> &mut T {
+				&mut self.0
+			}
+		}
+
+		impl<T> Borrow<T> for $tag<T> {
+			fn borrow(&self) -> &T {
+				&self.0
+			}
+		}
+
+		impl<T> From<T> for $tag<T> {
+			fn from(kv: T) -> Self {
+				$tag(kv)
+			}
+		}
+	};
+}
+
+impl_tag!(First);
+impl_tag!(Second);
+
+
+/// A `BidirMap` whose K/Vs go in and come out tagged by their column, as `First`s and `Second`s.
+///
+/// With both columns of the same type, nothing stops a plain map's `get_by_first()` from being handed a second K/V.
+/// Here, the keys of lookups and removals are tagged references, `First<&Q>` or `Second<&Q>`, and their results tagged K/Vs,
+/// so a K/V found in one column can't be looked up in the same one by mistake:
+///
+/// ```compile_fail
+/// use bidir_map::{First, Second, TaggedBidirMap};
+///
+/// let mut users = TaggedBidirMap::new();
+/// users.insert(First("alice".to_string()), Second("alice@example.com".to_string()));
+///
+/// let mail = users.get_by_first(First("alice")).unwrap();
+/// users.get_by_first(mail);
+/// ```
+///
+/// Nor can the K/Vs of a new pair be swapped:
+///
+/// ```compile_fail
+/// use bidir_map::{First, Second, TaggedBidirMap};
+///
+/// let mut users = TaggedBidirMap::new();
+/// users.insert(First("alice".to_string()), Second("alice@example.com".to_string()));
+/// users.insert(Second("bob@example.com".to_string()), First("bob".to_string()));
+/// ```
+///
+/// The tags are zero-cost newtypes, and the pairs are stored untagged:
+/// the underlying `BidirMap` is a `From` conversion, or `inner()`, away. The map may also be built from one.
+///
+/// # Examples
+///
+/// ```
+/// use bidir_map::{First, Second, TaggedBidirMap};
+///
+/// let mut users = TaggedBidirMap::new();
+/// users.insert(First("alice".to_string()), Second("alice@example.com".to_string()));
+/// users.insert(First("bob".to_string()), Second("bob@example.com".to_string()));
+///
+/// let mail = users.get_by_first(First("alice")).unwrap();
+/// assert_eq!(*mail, "alice@example.com");
+/// assert_eq!(users.get_by_second(mail.as_deref()), Some(First(&"alice".to_string())));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct TaggedBidirMap<Kv1: PartialEq, Kv2: PartialEq> {
+	map: BidirMap<Kv1, Kv2>,
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> TaggedBidirMap<Kv1, Kv2> {
+	/// Create a new empty instance of `TaggedBidirMap`
+	pub fn new() -> Self {
+		TaggedBidirMap::from(BidirMap::new())
+	}
+
+	/// Create a new empty instance of `TaggedBidirMap` with the specified capacity.
+	///
+	/// It will be able to hold at least `capacity` elements without reallocating.
+	pub fn with_capacity(capacity: usize) -> Self {
+		TaggedBidirMap::from(BidirMap::with_capacity(capacity))
+	}
+
+	/// Unwraps the underlying `BidirMap`.
+	pub fn into_inner(self) -> BidirMap<Kv1, Kv2> {
+		self.map
+	}
+
+	/// Gets a reference to the underlying `BidirMap`.
+	pub fn inner(&self) -> &BidirMap<Kv1, Kv2> {
+		&self.map
+	}
+
+	/// Clears the map, removing all entries.
+	pub fn clear(&mut self) {
+		self.map.clear()
+	}
+
+	/// Inserts a K/V-K/V pair into the map, as `BidirMap::insert()` does, returning the displaced pair, if any.
+	pub fn insert(&mut self, kv1: First<Kv1>, kv2: Second<Kv2>) -> Option<(First<Kv1>, Second<Kv2>)> {
+		self.map.insert(kv1.0, kv2.0).map(tag)
+	}
+
+	/// Returns the second K/V corresponding to the first K/V.
+	pub fn get_by_first<Q>(&self, key: First<&Q>) -> Option<Second<&Kv2>>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.get_by_first(key.0).map(Second)
+	}
+
+	/// Returns the first K/V corresponding to the second K/V.
+	pub fn get_by_second<Q>(&self, key: Second<&Q>) -> Option<First<&Kv1>>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.get_by_second(key.0).map(First)
+	}
+
+	/// Check if the map contains the first K/V.
+	pub fn contains_first_key<Q>(&self, key: First<&Q>) -> bool
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.contains_first_key(key.0)
+	}
+
+	/// Check if the map contains the second K/V.
+	pub fn contains_second_key<Q>(&self, key: Second<&Q>) -> bool
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.contains_second_key(key.0)
+	}
+
+	/// Removes the pair with the first K/V from the map, returning it if the key was previously in the map.
+	pub fn remove_by_first<Q>(&mut self, key: First<&Q>) -> Option<(First<Kv1>, Second<Kv2>)>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.remove_by_first(key.0).map(tag)
+	}
+
+	/// Removes the pair with the second K/V from the map, returning it if the key was previously in the map.
+	pub fn remove_by_second<Q>(&mut self, key: Second<&Q>) -> Option<(First<Kv1>, Second<Kv2>)>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.remove_by_second(key.0).map(tag)
+	}
+
+	/// Gets an iterator over the entries of the map, tagged.
+	pub fn iter<'s>(&'s self) -> Iter<'s, Kv1, Kv2> {
+		Iter{
+			iter: self.map.iter(),
+		}
+	}
+
+	/// Returns the number of elements in the map.
+	pub fn len(&self) -> usize {
+		self.map.len()
+	}
+
+	/// Returns true if the map contains no elements.
+	pub fn is_empty(&self) -> bool {
+		self.map.is_empty()
+	}
+}
+
+fn tag<Kv1, Kv2>((kv1, kv2): (Kv1, Kv2)) -> (First<Kv1>, Second<Kv2>) {
+	(First(kv1), Second(kv2))
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> Default for TaggedBidirMap<Kv1, Kv2> {
+	fn default() -> Self {
+		TaggedBidirMap::new()
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> From<BidirMap<Kv1, Kv2>> for TaggedBidirMap<Kv1, Kv2> {
+	fn from(map: BidirMap<Kv1, Kv2>) -> Self {
+		TaggedBidirMap{
+			map,
+		}
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> From<TaggedBidirMap<Kv1, Kv2>> for BidirMap<Kv1, Kv2> {
+	fn from(map: TaggedBidirMap<Kv1, Kv2>) -> Self {
+		map.map
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> FromIterator<(First<Kv1>, Second<Kv2>)> for TaggedBidirMap<Kv1, Kv2> {
+	fn from_iter<I: IntoIterator<Item=(First<Kv1>, Second<Kv2>)>>(iter: I) -> Self {
+		TaggedBidirMap::from(iter.into_iter().map(|(kv1, kv2)| (kv1.0, kv2.0)).collect::<BidirMap<_, _>>())
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> Extend<(First<Kv1>, Second<Kv2>)> for TaggedBidirMap<Kv1, Kv2> {
+	fn extend<I: IntoIterator<Item=(First<Kv1>, Second<Kv2>)>>(&mut self, iter: I) {
+		self.map.extend(iter.into_iter().map(|(kv1, kv2)| (kv1.0, kv2.0)))
+	}
+}
+
+impl<'a, Kv1: PartialEq, Kv2: PartialEq> IntoIterator for &'a TaggedBidirMap<Kv1, Kv2> {
+	type Item = (First<&'a Kv1>, Second<&'a Kv2>);
+	type IntoIter = Iter<'a, Kv1, Kv2>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+
+/// An iterator over the K/V pairs contained in a `TaggedBidirMap`, tagged.
+///
+/// See documentation of `TaggedBidirMap::iter()` for more.
+pub struct Iter<'a, Kv1: 'a, Kv2: 'a> {
+	iter: PairIter<'a, Kv1, Kv2>,
+}
+
+impl<'a, Kv1, Kv2> Iterator for Iter<'a, Kv1, Kv2> {
+	type Item = (First<&'a Kv1>, Second<&'a Kv2>);
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next().map(|kvs| (First(&kvs.0), Second(&kvs.1)))
+	}
+}
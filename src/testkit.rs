@@ -0,0 +1,278 @@
+//! A behavioural test suite for implementations of `BidirMapMut`, to be instantiated with `bidir_map_conformance_tests!`.
+//!
+//! Each check builds its maps with the supplied constructor and K/Vs with the supplied generators,
+//! which must be injective: distinct indices yield distinct K/Vs.
+//! The checks panic on failure.
+//!
+//! Iteration order isn't checked, as it's up to the implementation; only that every pair is visited exactly once.
+
+
+use core::fmt::Debug;
+use alloc::vec::Vec;
+use traits::BidirMapMut;
+
+
+/// A new map is empty, and finds nothing.
+pub fn empty<Kv1, Kv2, M, N, F1, F2>(new: N, first: F1, second: F2)
+	where M : BidirMapMut<Kv1, Kv2>,
+	      N : Fn() -> M,
+	      F1: Fn(usize) -> Kv1,
+	      F2: Fn(usize) -> Kv2,
+{
+	let mut map = new();
+	assert!(map.is_empty() && map.len() == 0, "new map isn't empty");
+	assert!(map.iter().next().is_none(), "new map iterates over something");
+	assert!(map.get_by_first(&first(0)).is_none(), "new map finds a first K/V");
+	assert!(map.get_by_second(&second(0)).is_none(), "new map finds a second K/V");
+	assert!(map.remove_by_first(&first(0)).is_none(), "new map removes a first K/V");
+	assert!(map.remove_by_second(&second(0)).is_none(), "new map removes a second K/V");
+}
+
+/// Inserting unrelated pairs keeps them all, findable both ways.
+pub fn insert_and_get<Kv1, Kv2, M, N, F1, F2>(new: N, first: F1, second: F2)
+	where Kv1: PartialEq,
+	      Kv2: PartialEq,
+	      M  : BidirMapMut<Kv1, Kv2>,
+	      N  : Fn() -> M,
+	      F1 : Fn(usize) -> Kv1,
+	      F2 : Fn(usize) -> Kv2,
+{
+	let mut map = new();
+	for i in 0..50 {
+		assert!(map.insert(first(i), second(i)).is_none(), "inserting pair {} replaced something", i);
+		assert!(map.len() == i + 1, "length isn't {} after as many insertions", i + 1);
+	}
+	for i in 0..50 {
+		assert!(map.get_by_first(&first(i)) == Some(&second(i)), "get_by_first() for pair {} is wrong", i);
+		assert!(map.get_by_second(&second(i)) == Some(&first(i)), "get_by_second() for pair {} is wrong", i);
+		assert!(map.contains_first_key(&first(i)) && map.contains_second_key(&second(i)), "contains_*_key() for pair {} is wrong", i);
+	}
+	assert!(map.get_by_first(&first(50)).is_none() && map.get_by_second(&second(50)).is_none(), "found a pair never inserted");
+	assert!(!map.is_empty(), "full map is empty");
+}
+
+/// Inserting a pair sharing one K/V with an existing pair replaces and returns that pair.
+pub fn displacement<Kv1, Kv2, M, N, F1, F2>(new: N, first: F1, second: F2)
+	where Kv1: PartialEq + Debug,
+	      Kv2: PartialEq + Debug,
+	      M  : BidirMapMut<Kv1, Kv2>,
+	      N  : Fn() -> M,
+	      F1 : Fn(usize) -> Kv1,
+	      F2 : Fn(usize) -> Kv2,
+{
+	let mut map = new();
+	map.insert(first(0), second(0));
+	map.insert(first(1), second(1));
+
+	assert_eq!(map.insert(first(0), second(2)), Some((first(0), second(0))), "displacing by the first K/V");
+	assert!(map.len() == 2, "displacing by the first K/V changed the length");
+	assert!(map.get_by_second(&second(0)).is_none(), "displaced second K/V still present");
+	assert!(map.get_by_first(&first(0)) == Some(&second(2)), "displacing pair not present");
+
+	assert_eq!(map.insert(first(3), second(1)), Some((first(1), second(1))), "displacing by the second K/V");
+	assert!(map.len() == 2, "displacing by the second K/V changed the length");
+	assert!(map.get_by_first(&first(1)).is_none(), "displaced first K/V still present");
+	assert!(map.get_by_second(&second(1)) == Some(&first(3)), "displacing pair not present");
+
+	assert_eq!(map.insert(first(0), second(2)), Some((first(0), second(2))), "reinserting an identical pair");
+	assert!(map.len() == 2, "reinserting an identical pair changed the length");
+}
+
+/// Inserting a pair sharing its first K/V with one pair and its second with another removes both,
+/// returning the one sharing the first K/V.
+pub fn double_conflict<Kv1, Kv2, M, N, F1, F2>(new: N, first: F1, second: F2)
+	where Kv1: PartialEq + Debug,
+	      Kv2: PartialEq + Debug,
+	      M  : BidirMapMut<Kv1, Kv2>,
+	      N  : Fn() -> M,
+	      F1 : Fn(usize) -> Kv1,
+	      F2 : Fn(usize) -> Kv2,
+{
+	let mut map = new();
+	for i in 0..3 {
+		map.insert(first(i), second(i));
+	}
+
+	assert_eq!(map.insert(first(0), second(1)), Some((first(0), second(0))), "double conflict");
+	assert!(map.len() == 2, "double conflict didn't remove both pairs");
+	assert!(map.get_by_first(&first(1)).is_none() && map.get_by_second(&second(0)).is_none(), "a conflicting pair is still present");
+	assert!(map.get_by_first(&first(0)) == Some(&second(1)), "inserted pair not present");
+	assert!(map.get_by_first(&first(2)) == Some(&second(2)), "unrelated pair lost");
+}
+
+/// Removal works from either side, taking the entire pair, and only once.
+pub fn removal<Kv1, Kv2, M, N, F1, F2>(new: N, first: F1, second: F2)
+	where Kv1: PartialEq + Debug,
+	      Kv2: PartialEq + Debug,
+	      M  : BidirMapMut<Kv1, Kv2>,
+	      N  : Fn() -> M,
+	      F1 : Fn(usize) -> Kv1,
+	      F2 : Fn(usize) -> Kv2,
+{
+	let mut map = new();
+	for i in 0..10 {
+		map.insert(first(i), second(i));
+	}
+
+	for i in (0..10).filter(|i| i % 2 == 0) {
+		assert_eq!(map.remove_by_first(&first(i)), Some((first(i), second(i))), "removing pair {} by first K/V", i);
+		assert!(map.remove_by_first(&first(i)).is_none() && map.remove_by_second(&second(i)).is_none(), "pair {} removed twice", i);
+	}
+	for i in (0..10).filter(|i| i % 2 == 1) {
+		assert!(map.get_by_first(&first(i)) == Some(&second(i)), "pair {} lost by removing others", i);
+		assert_eq!(map.remove_by_second(&second(i)), Some((first(i), second(i))), "removing pair {} by second K/V", i);
+		assert!(map.get_by_first(&first(i)).is_none(), "first K/V of pair {} outlived its removal", i);
+	}
+	assert!(map.is_empty(), "map isn't empty after removing everything");
+
+	assert!(map.insert(first(0), second(0)).is_none(), "reinserting after removal replaced something");
+	assert!(map.len() == 1, "reinserting after removal");
+}
+
+/// Iteration visits each pair exactly once.
+pub fn iteration<Kv1, Kv2, M, N, F1, F2>(new: N, first: F1, second: F2)
+	where Kv1: PartialEq,
+	      Kv2: PartialEq,
+	      M  : BidirMapMut<Kv1, Kv2>,
+	      N  : Fn() -> M,
+	      F1 : Fn(usize) -> Kv1,
+	      F2 : Fn(usize) -> Kv2,
+{
+	let mut map = new();
+	for i in 0..30 {
+		map.insert(first(i), second(i));
+	}
+	for i in (0..30).filter(|i| i % 3 == 0) {
+		map.remove_by_first(&first(i));
+	}
+
+	let mut seen = Vec::new();
+	for (kv1, kv2) in map.iter() {
+		let i = (0..30).find(|&i| first(i) == *kv1).expect("iterated over a K/V never inserted");
+		assert!(i % 3 != 0, "iterated over removed pair {}", i);
+		assert!(second(i) == *kv2, "pair {} iterated over with the wrong second K/V", i);
+		assert!(!seen.contains(&i), "pair {} iterated over twice", i);
+		seen.push(i);
+	}
+	assert!(seen.len() == 20 && map.len() == 20, "iteration missed pairs");
+}
+
+/// A long pseudo-random sequence of insertions and removals agrees with a straightforward model of the semantics at every step.
+pub fn against_model<Kv1, Kv2, M, N, F1, F2>(new: N, first: F1, second: F2)
+	where Kv1: PartialEq + Debug,
+	      Kv2: PartialEq + Debug,
+	      M  : BidirMapMut<Kv1, Kv2>,
+	      N  : Fn() -> M,
+	      F1 : Fn(usize) -> Kv1,
+	      F2 : Fn(usize) -> Kv2,
+{
+	let mut map = new();
+	// Pairs by the indices of their K/Vs
+	let mut model: Vec<(usize, usize)> = Vec::new();
+	let mut state = 0x2545f4914f6cdd1du64;
+	let mut below = |n: usize| {
+		state ^= state << 13;
+		state ^= state >> 7;
+		state ^= state << 17;
+		(state % n as u64) as usize
+	};
+
+	for step in 0..2000 {
+		let (a, b) = (below(16), below(16));
+		match below(4) {
+			0 | 1 => {
+				let by_first = model.iter().position(|kvs| kvs.0 == a).map(|idx| model.remove(idx));
+				let by_second = model.iter().position(|kvs| kvs.1 == b).map(|idx| model.remove(idx));
+				model.push((a, b));
+				let expected = by_first.or(by_second).map(|(a, b)| (first(a), second(b)));
+				assert_eq!(map.insert(first(a), second(b)), expected, "step {}: insert({}, {})", step, a, b);
+			}
+			2 => {
+				let expected = model.iter().position(|kvs| kvs.0 == a).map(|idx| model.remove(idx)).map(|(a, b)| (first(a), second(b)));
+				assert_eq!(map.remove_by_first(&first(a)), expected, "step {}: remove_by_first({})", step, a);
+			}
+			_ => {
+				let expected = model.iter().position(|kvs| kvs.1 == b).map(|idx| model.remove(idx)).map(|(a, b)| (first(a), second(b)));
+				assert_eq!(map.remove_by_second(&second(b)), expected, "step {}: remove_by_second({})", step, b);
+			}
+		}
+
+		assert!(map.len() == model.len(), "step {}: length is {}, should be {}", step, map.len(), model.len());
+		for i in 0..16 {
+			let expected = model.iter().find(|kvs| kvs.0 == i).map(|kvs| second(kvs.1));
+			assert!(map.get_by_first(&first(i)) == expected.as_ref(), "step {}: get_by_first({}) is wrong", step, i);
+			let expected = model.iter().find(|kvs| kvs.1 == i).map(|kvs| first(kvs.0));
+			assert!(map.get_by_second(&second(i)) == expected.as_ref(), "step {}: get_by_second({}) is wrong", step, i);
+		}
+	}
+}
+
+
+/// Instantiate the `testkit` suite as `#[test]`s in a module, against a type implementing `BidirMapMut`.
+///
+/// Takes the module's name and the map's type, a constructor, and injective generators of first and second K/Vs from `usize`s.
+/// The module `use`s everything from its parent.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use] extern crate bidir_map;
+/// use bidir_map::BidirMap;
+///
+/// bidir_map_conformance_tests! {
+///     bidir_map_conformance: BidirMap<u32, String>,
+///     new: BidirMap::new,
+///     first: |i| i as u32,
+///     second: |i| format!("#{}", i),
+/// }
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! bidir_map_conformance_tests {
+	(@check $check:ident, $map:ty, $new:expr, $first:expr, $second:expr) => {
+		$crate::testkit::$check(|| -> $map { ($new)() }, $first, $second)
+	};
+
+	($module:ident: $map:ty, new: $new:expr, first: $first:expr, second: $second:expr $(,)*) => {
+		#[allow(unused_imports)]
+		mod $module {
+			use super::*;
+
+			#[test]
+			fn empty() {
+				$crate::bidir_map_conformance_tests!(@check empty, $map, $new, $first, $second)
+			}
+
+			#[test]
+			fn insert_and_get() {
+				$crate::bidir_map_conformance_tests!(@check insert_and_get, $map, $new, $first, $second)
+			}
+
+			#[test]
+			fn displacement() {
+				$crate::bidir_map_conformance_tests!(@check displacement, $map, $new, $first, $second)
+			}
+
+			#[test]
+			fn double_conflict() {
+				$crate::bidir_map_conformance_tests!(@check double_conflict, $map, $new, $first, $second)
+			}
+
+			#[test]
+			fn removal() {
+				$crate::bidir_map_conformance_tests!(@check removal, $map, $new, $first, $second)
+			}
+
+			#[test]
+			fn iteration() {
+				$crate::bidir_map_conformance_tests!(@check iteration, $map, $new, $first, $second)
+			}
+
+			#[test]
+			fn against_model() {
+				$crate::bidir_map_conformance_tests!(@check against_model, $map, $new, $first, $second)
+			}
+
+		}
+	};
+}
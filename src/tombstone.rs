@@ -0,0 +1,288 @@
+//! A `BidirMap` variant removing pairs by leaving tombstones, for workloads with heavy churn.
+
+
+use core::borrow::Borrow;
+use core::iter::FromIterator;
+use core::slice;
+use alloc::vec::Vec;
+use BidirMap;
+
+
+/// The fewest tombstones compacted away automatically, by default
+const DEFAULT_MIN_TOMBSTONES: usize = 32;
+
+
+/// A bidirectional map whose removals leave tombstones in place of the pairs, instead of moving other pairs.
+///
+/// Removal is thus `O(1)` past the lookup, and the live pairs always stay in the order they were inserted in,
+/// unlike `BidirMap::remove_by_*()`, which moves the last pair into the gap, or `shift_remove_*()`, which moves all that follow.
+///
+/// Tombstones are dropped by `compact()`, which is also run automatically once they outnumber both the live pairs
+/// and the compaction threshold (32 by default, see `set_compaction_threshold()`), keeping the memory overhead and scan lengths linear.
+///
+/// Lookups, `len()` and iteration only ever see the live pairs.
+/// Indices, as returned by `get_index_of_*()`, count only live pairs, in iteration order, so they're unaffected by compaction.
+///
+/// Performance: `O(n)` lookups, over live pairs and tombstones.
+///
+/// # Examples
+///
+/// ```
+/// use bidir_map::TombstoneBidirMap;
+///
+/// let mut map = TombstoneBidirMap::new();
+/// map.insert(1, "a");
+/// map.insert(2, "b");
+/// map.insert(3, "c");
+///
+/// map.remove_by_first(&1);
+/// assert_eq!(map.iter().cloned().collect::<Vec<_>>(), [(2, "b"), (3, "c")]);
+/// assert_eq!(map.tombstones(), 1);
+/// assert_eq!(map.get_index_of_second(&"c"), Some(1));
+/// ```
+#[derive(Clone, Debug)]
+pub struct TombstoneBidirMap<Kv1: PartialEq, Kv2: PartialEq> {
+	cont: Vec<Option<(Kv1, Kv2)>>,
+	live: usize,
+	min_tombstones: usize,
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> TombstoneBidirMap<Kv1, Kv2> {
+	/// Create a new empty instance of `TombstoneBidirMap`
+	pub fn new() -> Self {
+		TombstoneBidirMap::with_capacity(0)
+	}
+
+	/// Create a new empty instance of `TombstoneBidirMap` with the specified capacity.
+	///
+	/// It will be able to hold at least `capacity` pairs and tombstones without reallocating.
+	pub fn with_capacity(capacity: usize) -> Self {
+		TombstoneBidirMap{
+			cont: Vec::with_capacity(capacity),
+			live: 0,
+			min_tombstones: DEFAULT_MIN_TOMBSTONES,
+		}
+	}
+
+	/// Sets the fewest tombstones to be compacted away automatically, once they also outnumber the live pairs.
+	///
+	/// `usize::MAX` disables automatic compaction.
+	pub fn set_compaction_threshold(&mut self, min_tombstones: usize) {
+		self.min_tombstones = min_tombstones;
+		self.maybe_compact();
+	}
+
+	/// Clears the map, removing all entries and tombstones.
+	pub fn clear(&mut self) {
+		self.cont.clear();
+		self.live = 0;
+	}
+
+	/// Inserts a K/V-K/V pair into the map, after all live pairs.
+	///
+	/// Like `BidirMap::insert()`, pairs holding `kv1` or `kv2` are removed and the one holding `kv1` is preferably returned;
+	/// they leave tombstones, as by `remove_by_*()`.
+	pub fn insert(&mut self, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)> {
+		let by_first = self.slot_of(|kvs| kvs.0 == kv1).map(|slot| self.bury(slot));
+		let by_second = self.slot_of(|kvs| kvs.1 == kv2).map(|slot| self.bury(slot));
+
+		self.cont.push(Some((kv1, kv2)));
+		self.live += 1;
+		self.maybe_compact();
+
+		by_first.or(by_second)
+	}
+
+	/// Removes the pair with the first K/V, leaving a tombstone, and returns it.
+	pub fn remove_by_first<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		let kvs = self.slot_of(|kvs| *key == *kvs.0.borrow()).map(|slot| self.bury(slot));
+		self.maybe_compact();
+		kvs
+	}
+
+	/// Removes the pair with the second K/V, leaving a tombstone, and returns it.
+	pub fn remove_by_second<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		let kvs = self.slot_of(|kvs| *key == *kvs.1.borrow()).map(|slot| self.bury(slot));
+		self.maybe_compact();
+		kvs
+	}
+
+	/// Drops all tombstones, keeping the live pairs in order.
+	pub fn compact(&mut self) {
+		if self.cont.len() != self.live {
+			self.cont.retain(Option::is_some);
+		}
+	}
+
+	/// Returns the number of tombstones currently left by removals.
+	pub fn tombstones(&self) -> usize {
+		self.cont.len() - self.live
+	}
+
+	/// Gets an iterator over the live entries of the map, in order of insertion.
+	pub fn iter<'s>(&'s self) -> Iter<'s, Kv1, Kv2> {
+		Iter{
+			iter: self.cont.iter(),
+			live: self.live,
+		}
+	}
+
+	/// Returns the number of live elements in the map.
+	pub fn len(&self) -> usize {
+		self.live
+	}
+
+	/// Returns true if the map contains no live elements.
+	pub fn is_empty(&self) -> bool {
+		self.live == 0
+	}
+
+	/// Returns a reference to the second K/V corresponding to the first K/V.
+	pub fn get_by_first<Q>(&self, key: &Q) -> Option<&Kv2>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.iter().find(|kvs| *key == *kvs.0.borrow()).map(|kvs| &kvs.1)
+	}
+
+	/// Returns a reference to the first K/V corresponding to the second K/V.
+	pub fn get_by_second<Q>(&self, key: &Q) -> Option<&Kv1>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.iter().find(|kvs| *key == *kvs.1.borrow()).map(|kvs| &kvs.0)
+	}
+
+	/// Check if the map contains the first K/V
+	pub fn contains_first_key<Q>(&self, key: &Q) -> bool
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.get_by_first(key).is_some()
+	}
+
+	/// Check if the map contains the second K/V
+	pub fn contains_second_key<Q>(&self, key: &Q) -> bool
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.get_by_second(key).is_some()
+	}
+
+	/// Returns the index of the pair with the first K/V among the live pairs, in iteration order.
+	pub fn get_index_of_first<Q>(&self, key: &Q) -> Option<usize>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.iter().position(|kvs| *key == *kvs.0.borrow())
+	}
+
+	/// Returns the index of the pair with the second K/V among the live pairs, in iteration order.
+	pub fn get_index_of_second<Q>(&self, key: &Q) -> Option<usize>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.iter().position(|kvs| *key == *kvs.1.borrow())
+	}
+
+	/// Returns the live pair at the index, in iteration order.
+	pub fn get_index(&self, idx: usize) -> Option<&(Kv1, Kv2)> {
+		self.iter().nth(idx)
+	}
+
+	fn slot_of<F: FnMut(&(Kv1, Kv2)) -> bool>(&self, mut pred: F) -> Option<usize> {
+		self.cont.iter().position(|slot| slot.as_ref().is_some_and(&mut pred))
+	}
+
+	fn bury(&mut self, slot: usize) -> (Kv1, Kv2) {
+		self.live -= 1;
+		self.cont[slot].take().unwrap()
+	}
+
+	fn maybe_compact(&mut self) {
+		let tombstones = self.tombstones();
+		if tombstones >= self.min_tombstones && tombstones > self.live {
+			self.compact();
+		}
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> Default for TombstoneBidirMap<Kv1, Kv2> {
+	fn default() -> Self {
+		TombstoneBidirMap::new()
+	}
+}
+
+/// Compares the live pairs, in order; tombstones don't matter.
+impl<Kv1: PartialEq, Kv2: PartialEq> PartialEq for TombstoneBidirMap<Kv1, Kv2> {
+	fn eq(&self, other: &Self) -> bool {
+		self.iter().eq(other.iter())
+	}
+}
+
+impl<Kv1: Eq, Kv2: Eq> Eq for TombstoneBidirMap<Kv1, Kv2> {}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> FromIterator<(Kv1, Kv2)> for TombstoneBidirMap<Kv1, Kv2> {
+	/// Like `BidirMap`'s, this doesn't check for duplicates.
+	fn from_iter<T: IntoIterator<Item=(Kv1, Kv2)>>(iter: T) -> Self {
+		let cont: Vec<_> = iter.into_iter().map(Some).collect();
+		TombstoneBidirMap{
+			live: cont.len(),
+			cont,
+			min_tombstones: DEFAULT_MIN_TOMBSTONES,
+		}
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> From<BidirMap<Kv1, Kv2>> for TombstoneBidirMap<Kv1, Kv2> {
+	fn from(map: BidirMap<Kv1, Kv2>) -> Self {
+		map.into_iter().collect()
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> From<TombstoneBidirMap<Kv1, Kv2>> for BidirMap<Kv1, Kv2> {
+	fn from(map: TombstoneBidirMap<Kv1, Kv2>) -> Self {
+		map.cont.into_iter().flatten().collect()
+	}
+}
+
+impl<'a, Kv1: PartialEq, Kv2: PartialEq> IntoIterator for &'a TombstoneBidirMap<Kv1, Kv2> {
+	type Item = &'a (Kv1, Kv2);
+	type IntoIter = Iter<'a, Kv1, Kv2>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+
+/// An iterator over the live K/V pairs contained in a `TombstoneBidirMap`.
+///
+/// See documentation of `TombstoneBidirMap::iter()` for more.
+pub struct Iter<'a, Kv1: 'a, Kv2: 'a> {
+	iter: slice::Iter<'a, Option<(Kv1, Kv2)>>,
+	live: usize,
+}
+
+impl<'a, Kv1, Kv2> Iterator for Iter<'a, Kv1, Kv2> {
+	type Item = &'a (Kv1, Kv2);
+	fn next(&mut self) -> Option<Self::Item> {
+		let kvs = self.iter.by_ref().flatten().next();
+		if kvs.is_some() {
+			self.live -= 1;
+		}
+		kvs
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(self.live, Some(self.live))
+	}
+}
+
+impl<'a, Kv1, Kv2> ExactSizeIterator for Iter<'a, Kv1, Kv2> {}
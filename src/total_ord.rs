@@ -0,0 +1,89 @@
+//! Floats usable as K/Vs, see `TotalOrd`.
+
+
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+
+
+/// A float ordered and compared by `total_cmp()`, so that every value, `NAN`s included, equals itself.
+///
+/// Plain floats make poor K/Vs, since `NAN`s aren't equal to themselves (see `BidirMap`'s documentation).
+/// Under this order, values are equal exactly when their bits are,
+/// so `0.0` and `-0.0` are different K/Vs, as are `NAN`s with different bits.
+/// That also makes the wrapper `Eq`, `Ord` and `Hash`, as `BidirHashMap` and `BidirBTreeMap` require.
+///
+/// # Examples
+///
+/// ```
+/// use bidir_map::{BidirMap, TotalOrd};
+///
+/// let mut map = BidirMap::new();
+/// map.insert(TotalOrd(f64::NAN), "missing");
+/// map.insert(TotalOrd(1.5), "one and a half");
+/// map.insert(TotalOrd(f64::NAN), "unknown");
+///
+/// assert_eq!(map.len(), 2);
+/// assert_eq!(map.get_by_first(&TotalOrd(f64::NAN)), Some(&"unknown"));
+/// assert_eq!(map.remove_by_second(&"unknown").map(|(kv1, _)| kv1.0.is_nan()), Some(true));
+/// ```
+#[derive(Clone, Copy, Default)]
+pub struct TotalOrd<F>(pub F);
+
+macro_rules! impl_total_ord {
+	($($float:ty),*) => {$(
+		impl PartialEq for TotalOrd<$float> {
+			fn eq(&self, other: &Self) -> bool {
+				self.0.to_bits() == other.0.to_bits()
+			}
+		}
+
+		impl Eq for TotalOrd<$float> {}
+
+		impl PartialOrd for TotalOrd<$float> {
+			fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+				Some(self.cmp(other))
+			}
+		}
+
+		impl Ord for TotalOrd<$float> {
+			fn cmp(&self, other: &Self) -> Ordering {
+				self.0.total_cmp(&other.0)
+			}
+		}
+
+		impl Hash for TotalOrd<$float> {
+			fn hash<H: Hasher>(&self, state: &mut H) {
+				self.0.to_bits().hash(state)
+			}
+		}
+
+		impl From<$float> for TotalOrd<$float> {
+			fn from(f: $float) -> Self {
+				TotalOrd(f)
+			}
+		}
+	)*};
+}
+
+impl_total_ord!(f32, f64);
+
+impl<F: fmt::Debug> fmt::Debug for TotalOrd<F> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
+impl<F: fmt::Display> fmt::Display for TotalOrd<F> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
+
+/// Whether `kv` is equal to itself, which `PartialEq` doesn't guarantee
+#[cfg(feature = "alloc")]
+#[allow(clippy::eq_op)]
+pub(crate) fn equals_itself<T: PartialEq + ?Sized>(kv: &T) -> bool {
+	kv == kv
+}
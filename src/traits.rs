@@ -0,0 +1,547 @@
+//! Traits for code generic over the bidirectional map types of this crate.
+//!
+//! `BidirMapLike` covers looking pairs up and iterating over them, `BidirMapMut` adds `insert()` and removal.
+//! Both take the K/Vs themselves as keys, rather than borrowed forms, since the types differ in what they need of those:
+//! `PartialEq`, `Ord` or `Hash + Eq`.
+//!
+//! # Object safety
+//!
+//! Neither trait can be made into a trait object: `BidirMapLike::Iter` is generic over the lifetime of the borrow.
+//! Code wanting dynamic dispatch should be generic over the traits at its boundary,
+//! or wrap the lookups it needs in a trait of its own, implemented for `M: BidirMapLike`.
+//!
+//! `LruBidirMap` implements neither, as its lookups take `&mut self`,
+//! nor does `BidirEnumMap`, which doesn't store the first K/Vs to hand out references to.
+//! `BidirArrayMap` and `BoundedBidirMap` implement only `BidirMapLike`, as their `insert()`s can fail or evict.
+//!
+//! # Examples
+//!
+//! ```
+//! use bidir_map::{BidirMap, BidirSortedMap};
+//! use bidir_map::traits::BidirMapLike;
+//!
+//! fn resolve<M: BidirMapLike<u32, String>>(map: &M, ids: &[u32]) -> Vec<String> {
+//!     ids.iter().map(|id| map.get_by_first(id).cloned().unwrap_or_else(|| "?".to_string())).collect()
+//! }
+//!
+//! let mut map = BidirMap::new();
+//! map.insert(1, "one".to_string());
+//! let mut sorted = BidirSortedMap::new();
+//! sorted.insert(2, "two".to_string());
+//!
+//! assert_eq!(resolve(&map, &[1, 2]), ["one", "?"]);
+//! assert_eq!(resolve(&sorted, &[1, 2]), ["?", "two"]);
+//! ```
+
+
+#[cfg(feature = "std")]
+use core::hash::{BuildHasher, Hash};
+#[cfg(feature = "alloc")]
+use {bounded, Iter};
+#[cfg(feature = "alloc")]
+use storage::Storage;
+#[cfg(feature = "alloc")]
+use {BidirBTreeMap, BidirMap, BidirSoaMap, BidirSortedMap, BoundedBidirMap, DenseBidirMap, DenseKey, FrozenBidirMap, HandleBidirMap, MruBidirMap, PersistentBidirMap, btree_map, dense_map, persistent, soa_map};
+#[cfg(feature = "std")]
+use {BidirHashMap, FingerprintedBidirMap, hash_map};
+use {BidirArrayMap, array_map};
+
+
+/// The read-only interface shared by the bidirectional maps of this crate.
+pub trait BidirMapLike<Kv1, Kv2> {
+	/// The iterator returned by `iter()`
+	type Iter<'a>: Iterator<Item=(&'a Kv1, &'a Kv2)> where Self: 'a, Kv1: 'a, Kv2: 'a;
+
+	/// Returns a reference to the second K/V corresponding to the first K/V.
+	fn get_by_first(&self, key: &Kv1) -> Option<&Kv2>;
+
+	/// Returns a reference to the first K/V corresponding to the second K/V.
+	fn get_by_second(&self, key: &Kv2) -> Option<&Kv1>;
+
+	/// Check if the map contains the first K/V
+	fn contains_first_key(&self, key: &Kv1) -> bool {
+		self.get_by_first(key).is_some()
+	}
+
+	/// Check if the map contains the second K/V
+	fn contains_second_key(&self, key: &Kv2) -> bool {
+		self.get_by_second(key).is_some()
+	}
+
+	/// Gets an iterator over the pairs of the map, in the map's order.
+	fn iter(&self) -> Self::Iter<'_>;
+
+	/// Returns the number of elements in the map.
+	fn len(&self) -> usize;
+
+	/// Returns true if the map contains no elements.
+	fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+}
+
+/// The modifying interface shared by the growable bidirectional maps of this crate.
+pub trait BidirMapMut<Kv1, Kv2>: BidirMapLike<Kv1, Kv2> {
+	/// Inserts a K/V-K/V pair into the map, returning a pair it replaced, see `BidirMap::insert()`.
+	fn insert(&mut self, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)>;
+
+	/// Removes the pair corresponding to the first K/V from the map, returning it if the key was previously in the map.
+	fn remove_by_first(&mut self, key: &Kv1) -> Option<(Kv1, Kv2)>;
+
+	/// Removes the pair corresponding to the second K/V from the map, returning it if the key was previously in the map.
+	fn remove_by_second(&mut self, key: &Kv2) -> Option<(Kv1, Kv2)>;
+}
+
+
+/// Adapts an iterator over `&(Kv1, Kv2)` into one over `(&Kv1, &Kv2)`, for `BidirMapLike::Iter`.
+pub struct PairRefs<I> {
+	iter: I,
+}
+
+impl<'a, Kv1: 'a, Kv2: 'a, I: Iterator<Item=&'a (Kv1, Kv2)>> Iterator for PairRefs<I> {
+	type Item = (&'a Kv1, &'a Kv2);
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next().map(|kvs| (&kvs.0, &kvs.1))
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<Kv1: PartialEq, Kv2: PartialEq, S: Storage<Kv1, Kv2>> BidirMapLike<Kv1, Kv2> for BidirMap<Kv1, Kv2, S> {
+	type Iter<'a> = PairRefs<Iter<'a, Kv1, Kv2>> where Self: 'a;
+
+	fn get_by_first(&self, key: &Kv1) -> Option<&Kv2> {
+		BidirMap::get_by_first(self, key)
+	}
+
+	fn get_by_second(&self, key: &Kv2) -> Option<&Kv1> {
+		BidirMap::get_by_second(self, key)
+	}
+
+	fn iter(&self) -> Self::Iter<'_> {
+		PairRefs{
+			iter: BidirMap::iter(self),
+		}
+	}
+
+	fn len(&self) -> usize {
+		BidirMap::len(self)
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq, const N: usize> BidirMapLike<Kv1, Kv2> for BidirArrayMap<Kv1, Kv2, N> {
+	type Iter<'a> = PairRefs<array_map::Iter<'a, Kv1, Kv2>> where Self: 'a;
+
+	fn get_by_first(&self, key: &Kv1) -> Option<&Kv2> {
+		BidirArrayMap::get_by_first(self, key)
+	}
+
+	fn get_by_second(&self, key: &Kv2) -> Option<&Kv1> {
+		BidirArrayMap::get_by_second(self, key)
+	}
+
+	fn iter(&self) -> Self::Iter<'_> {
+		PairRefs{
+			iter: BidirArrayMap::iter(self),
+		}
+	}
+
+	fn len(&self) -> usize {
+		BidirArrayMap::len(self)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<Kv1: Ord, Kv2: Ord> BidirMapLike<Kv1, Kv2> for BidirBTreeMap<Kv1, Kv2> {
+	type Iter<'a> = btree_map::Iter<'a, Kv1, Kv2> where Self: 'a;
+
+	fn get_by_first(&self, key: &Kv1) -> Option<&Kv2> {
+		BidirBTreeMap::get_by_first(self, key)
+	}
+
+	fn get_by_second(&self, key: &Kv2) -> Option<&Kv1> {
+		BidirBTreeMap::get_by_second(self, key)
+	}
+
+	fn iter(&self) -> Self::Iter<'_> {
+		BidirBTreeMap::iter(self)
+	}
+
+	fn len(&self) -> usize {
+		BidirBTreeMap::len(self)
+	}
+}
+
+#[cfg(feature = "std")]
+impl<Kv1: Hash + Eq, Kv2: Hash + Eq, S: BuildHasher> BidirMapLike<Kv1, Kv2> for BidirHashMap<Kv1, Kv2, S> {
+	type Iter<'a> = hash_map::Iter<'a, Kv1, Kv2> where Self: 'a;
+
+	fn get_by_first(&self, key: &Kv1) -> Option<&Kv2> {
+		BidirHashMap::get_by_first(self, key)
+	}
+
+	fn get_by_second(&self, key: &Kv2) -> Option<&Kv1> {
+		BidirHashMap::get_by_second(self, key)
+	}
+
+	fn iter(&self) -> Self::Iter<'_> {
+		BidirHashMap::iter(self)
+	}
+
+	fn len(&self) -> usize {
+		BidirHashMap::len(self)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<Kv1: PartialEq, Kv2: PartialEq> BidirMapLike<Kv1, Kv2> for BidirSoaMap<Kv1, Kv2> {
+	type Iter<'a> = soa_map::Iter<'a, Kv1, Kv2> where Self: 'a;
+
+	fn get_by_first(&self, key: &Kv1) -> Option<&Kv2> {
+		BidirSoaMap::get_by_first(self, key)
+	}
+
+	fn get_by_second(&self, key: &Kv2) -> Option<&Kv1> {
+		BidirSoaMap::get_by_second(self, key)
+	}
+
+	fn iter(&self) -> Self::Iter<'_> {
+		BidirSoaMap::iter(self)
+	}
+
+	fn len(&self) -> usize {
+		BidirSoaMap::len(self)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<Kv1: Ord, Kv2: Ord> BidirMapLike<Kv1, Kv2> for BidirSortedMap<Kv1, Kv2> {
+	type Iter<'a> = PairRefs<Iter<'a, Kv1, Kv2>> where Self: 'a;
+
+	fn get_by_first(&self, key: &Kv1) -> Option<&Kv2> {
+		BidirSortedMap::get_by_first(self, key)
+	}
+
+	fn get_by_second(&self, key: &Kv2) -> Option<&Kv1> {
+		BidirSortedMap::get_by_second(self, key)
+	}
+
+	fn iter(&self) -> Self::Iter<'_> {
+		PairRefs{
+			iter: BidirSortedMap::iter(self),
+		}
+	}
+
+	fn len(&self) -> usize {
+		BidirSortedMap::len(self)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<Kv1: PartialEq, Kv2: PartialEq> BidirMapLike<Kv1, Kv2> for BoundedBidirMap<Kv1, Kv2> {
+	type Iter<'a> = PairRefs<bounded::Iter<'a, Kv1, Kv2>> where Self: 'a;
+
+	fn get_by_first(&self, key: &Kv1) -> Option<&Kv2> {
+		BoundedBidirMap::get_by_first(self, key)
+	}
+
+	fn get_by_second(&self, key: &Kv2) -> Option<&Kv1> {
+		BoundedBidirMap::get_by_second(self, key)
+	}
+
+	fn iter(&self) -> Self::Iter<'_> {
+		PairRefs{
+			iter: BoundedBidirMap::iter(self),
+		}
+	}
+
+	fn len(&self) -> usize {
+		BoundedBidirMap::len(self)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<Kv1: DenseKey, Kv2: PartialEq> BidirMapLike<Kv1, Kv2> for DenseBidirMap<Kv1, Kv2> {
+	type Iter<'a> = dense_map::Iter<'a, Kv1, Kv2> where Self: 'a;
+
+	fn get_by_first(&self, key: &Kv1) -> Option<&Kv2> {
+		DenseBidirMap::get_by_first(self, key)
+	}
+
+	fn get_by_second(&self, key: &Kv2) -> Option<&Kv1> {
+		DenseBidirMap::get_by_second(self, key)
+	}
+
+	fn iter(&self) -> Self::Iter<'_> {
+		DenseBidirMap::iter(self)
+	}
+
+	fn len(&self) -> usize {
+		DenseBidirMap::len(self)
+	}
+}
+
+#[cfg(feature = "std")]
+impl<Kv1: Hash + Eq, Kv2: Hash + Eq, S: BuildHasher> BidirMapLike<Kv1, Kv2> for FingerprintedBidirMap<Kv1, Kv2, S> {
+	type Iter<'a> = PairRefs<Iter<'a, Kv1, Kv2>> where Self: 'a;
+
+	fn get_by_first(&self, key: &Kv1) -> Option<&Kv2> {
+		FingerprintedBidirMap::get_by_first(self, key)
+	}
+
+	fn get_by_second(&self, key: &Kv2) -> Option<&Kv1> {
+		FingerprintedBidirMap::get_by_second(self, key)
+	}
+
+	fn iter(&self) -> Self::Iter<'_> {
+		PairRefs{
+			iter: FingerprintedBidirMap::iter(self),
+		}
+	}
+
+	fn len(&self) -> usize {
+		FingerprintedBidirMap::len(self)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<Kv1: Ord, Kv2: Ord> BidirMapLike<Kv1, Kv2> for FrozenBidirMap<Kv1, Kv2> {
+	type Iter<'a> = PairRefs<Iter<'a, Kv1, Kv2>> where Self: 'a;
+
+	fn get_by_first(&self, key: &Kv1) -> Option<&Kv2> {
+		FrozenBidirMap::get_by_first(self, key)
+	}
+
+	fn get_by_second(&self, key: &Kv2) -> Option<&Kv1> {
+		FrozenBidirMap::get_by_second(self, key)
+	}
+
+	fn iter(&self) -> Self::Iter<'_> {
+		PairRefs{
+			iter: FrozenBidirMap::iter(self),
+		}
+	}
+
+	fn len(&self) -> usize {
+		FrozenBidirMap::len(self)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<Kv1: PartialEq, Kv2: PartialEq> BidirMapLike<Kv1, Kv2> for HandleBidirMap<Kv1, Kv2> {
+	type Iter<'a> = PairRefs<Iter<'a, Kv1, Kv2>> where Self: 'a;
+
+	fn get_by_first(&self, key: &Kv1) -> Option<&Kv2> {
+		HandleBidirMap::get_by_first(self, key)
+	}
+
+	fn get_by_second(&self, key: &Kv2) -> Option<&Kv1> {
+		HandleBidirMap::get_by_second(self, key)
+	}
+
+	fn iter(&self) -> Self::Iter<'_> {
+		PairRefs{
+			iter: HandleBidirMap::iter(self),
+		}
+	}
+
+	fn len(&self) -> usize {
+		HandleBidirMap::len(self)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<Kv1: PartialEq, Kv2: PartialEq> BidirMapLike<Kv1, Kv2> for MruBidirMap<Kv1, Kv2> {
+	type Iter<'a> = PairRefs<Iter<'a, Kv1, Kv2>> where Self: 'a;
+
+	fn get_by_first(&self, key: &Kv1) -> Option<&Kv2> {
+		MruBidirMap::get_by_first(self, key)
+	}
+
+	fn get_by_second(&self, key: &Kv2) -> Option<&Kv1> {
+		MruBidirMap::get_by_second(self, key)
+	}
+
+	fn iter(&self) -> Self::Iter<'_> {
+		PairRefs{
+			iter: MruBidirMap::iter(self),
+		}
+	}
+
+	fn len(&self) -> usize {
+		MruBidirMap::len(self)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<Kv1: PartialEq, Kv2: PartialEq> BidirMapLike<Kv1, Kv2> for PersistentBidirMap<Kv1, Kv2> {
+	type Iter<'a> = PairRefs<persistent::Iter<'a, Kv1, Kv2>> where Self: 'a;
+
+	fn get_by_first(&self, key: &Kv1) -> Option<&Kv2> {
+		PersistentBidirMap::get_by_first(self, key)
+	}
+
+	fn get_by_second(&self, key: &Kv2) -> Option<&Kv1> {
+		PersistentBidirMap::get_by_second(self, key)
+	}
+
+	fn iter(&self) -> Self::Iter<'_> {
+		PairRefs{
+			iter: PersistentBidirMap::iter(self),
+		}
+	}
+
+	fn len(&self) -> usize {
+		PersistentBidirMap::len(self)
+	}
+}
+
+
+#[cfg(feature = "alloc")]
+impl<Kv1: PartialEq, Kv2: PartialEq, S: Storage<Kv1, Kv2>> BidirMapMut<Kv1, Kv2> for BidirMap<Kv1, Kv2, S> {
+	fn insert(&mut self, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)> {
+		BidirMap::insert(self, kv1, kv2)
+	}
+
+	fn remove_by_first(&mut self, key: &Kv1) -> Option<(Kv1, Kv2)> {
+		BidirMap::remove_by_first(self, key)
+	}
+
+	fn remove_by_second(&mut self, key: &Kv2) -> Option<(Kv1, Kv2)> {
+		BidirMap::remove_by_second(self, key)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<Kv1: Ord + Clone, Kv2: Ord + Clone> BidirMapMut<Kv1, Kv2> for BidirBTreeMap<Kv1, Kv2> {
+	fn insert(&mut self, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)> {
+		BidirBTreeMap::insert(self, kv1, kv2)
+	}
+
+	fn remove_by_first(&mut self, key: &Kv1) -> Option<(Kv1, Kv2)> {
+		BidirBTreeMap::remove_by_first(self, key)
+	}
+
+	fn remove_by_second(&mut self, key: &Kv2) -> Option<(Kv1, Kv2)> {
+		BidirBTreeMap::remove_by_second(self, key)
+	}
+}
+
+#[cfg(feature = "std")]
+impl<Kv1: Hash + Eq + Clone, Kv2: Hash + Eq + Clone, S: BuildHasher> BidirMapMut<Kv1, Kv2> for BidirHashMap<Kv1, Kv2, S> {
+	fn insert(&mut self, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)> {
+		BidirHashMap::insert(self, kv1, kv2)
+	}
+
+	fn remove_by_first(&mut self, key: &Kv1) -> Option<(Kv1, Kv2)> {
+		BidirHashMap::remove_by_first(self, key)
+	}
+
+	fn remove_by_second(&mut self, key: &Kv2) -> Option<(Kv1, Kv2)> {
+		BidirHashMap::remove_by_second(self, key)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<Kv1: PartialEq, Kv2: PartialEq> BidirMapMut<Kv1, Kv2> for BidirSoaMap<Kv1, Kv2> {
+	fn insert(&mut self, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)> {
+		BidirSoaMap::insert(self, kv1, kv2)
+	}
+
+	fn remove_by_first(&mut self, key: &Kv1) -> Option<(Kv1, Kv2)> {
+		BidirSoaMap::remove_by_first(self, key)
+	}
+
+	fn remove_by_second(&mut self, key: &Kv2) -> Option<(Kv1, Kv2)> {
+		BidirSoaMap::remove_by_second(self, key)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<Kv1: Ord, Kv2: Ord> BidirMapMut<Kv1, Kv2> for BidirSortedMap<Kv1, Kv2> {
+	fn insert(&mut self, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)> {
+		BidirSortedMap::insert(self, kv1, kv2)
+	}
+
+	fn remove_by_first(&mut self, key: &Kv1) -> Option<(Kv1, Kv2)> {
+		BidirSortedMap::remove_by_first(self, key)
+	}
+
+	fn remove_by_second(&mut self, key: &Kv2) -> Option<(Kv1, Kv2)> {
+		BidirSortedMap::remove_by_second(self, key)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<Kv1: DenseKey, Kv2: PartialEq> BidirMapMut<Kv1, Kv2> for DenseBidirMap<Kv1, Kv2> {
+	fn insert(&mut self, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)> {
+		DenseBidirMap::insert(self, kv1, kv2)
+	}
+
+	fn remove_by_first(&mut self, key: &Kv1) -> Option<(Kv1, Kv2)> {
+		DenseBidirMap::remove_by_first(self, key)
+	}
+
+	fn remove_by_second(&mut self, key: &Kv2) -> Option<(Kv1, Kv2)> {
+		DenseBidirMap::remove_by_second(self, key)
+	}
+}
+
+#[cfg(feature = "std")]
+impl<Kv1: Hash + Eq, Kv2: Hash + Eq, S: BuildHasher> BidirMapMut<Kv1, Kv2> for FingerprintedBidirMap<Kv1, Kv2, S> {
+	fn insert(&mut self, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)> {
+		FingerprintedBidirMap::insert(self, kv1, kv2)
+	}
+
+	fn remove_by_first(&mut self, key: &Kv1) -> Option<(Kv1, Kv2)> {
+		FingerprintedBidirMap::remove_by_first(self, key)
+	}
+
+	fn remove_by_second(&mut self, key: &Kv2) -> Option<(Kv1, Kv2)> {
+		FingerprintedBidirMap::remove_by_second(self, key)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<Kv1: PartialEq, Kv2: PartialEq> BidirMapMut<Kv1, Kv2> for HandleBidirMap<Kv1, Kv2> {
+	fn insert(&mut self, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)> {
+		HandleBidirMap::insert(self, kv1, kv2)
+	}
+
+	fn remove_by_first(&mut self, key: &Kv1) -> Option<(Kv1, Kv2)> {
+		HandleBidirMap::remove_by_first(self, key)
+	}
+
+	fn remove_by_second(&mut self, key: &Kv2) -> Option<(Kv1, Kv2)> {
+		HandleBidirMap::remove_by_second(self, key)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<Kv1: PartialEq, Kv2: PartialEq> BidirMapMut<Kv1, Kv2> for MruBidirMap<Kv1, Kv2> {
+	fn insert(&mut self, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)> {
+		MruBidirMap::insert(self, kv1, kv2)
+	}
+
+	fn remove_by_first(&mut self, key: &Kv1) -> Option<(Kv1, Kv2)> {
+		MruBidirMap::remove_by_first(self, key)
+	}
+
+	fn remove_by_second(&mut self, key: &Kv2) -> Option<(Kv1, Kv2)> {
+		MruBidirMap::remove_by_second(self, key)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<Kv1: PartialEq + Clone, Kv2: PartialEq + Clone> BidirMapMut<Kv1, Kv2> for PersistentBidirMap<Kv1, Kv2> {
+	fn insert(&mut self, kv1: Kv1, kv2: Kv2) -> Option<(Kv1, Kv2)> {
+		PersistentBidirMap::insert(self, kv1, kv2)
+	}
+
+	fn remove_by_first(&mut self, key: &Kv1) -> Option<(Kv1, Kv2)> {
+		PersistentBidirMap::remove_by_first(self, key)
+	}
+
+	fn remove_by_second(&mut self, key: &Kv2) -> Option<(Kv1, Kv2)> {
+		PersistentBidirMap::remove_by_second(self, key)
+	}
+}
@@ -0,0 +1,256 @@
+//! All-or-nothing batches of edits to a `BidirMap`, see `BidirMap::transaction()`.
+
+
+use core::borrow::Borrow;
+use alloc::vec::Vec;
+use {BatchInsertError, BidirMap, Column, Iter, TransactionError};
+
+
+/// A batch of edits to a `BidirMap`, undone unless `commit()`ed.
+///
+/// Edits are applied to the map straight away, so reads through the transaction see them,
+/// and journalled, so that dropping the transaction, or calling `rollback()`,
+/// restores the map exactly as it was, iteration order included.
+///
+/// See documentation of `BidirMap::transaction()` for more.
+#[derive(Debug)]
+pub struct Transaction<'a, Kv1: PartialEq + 'a, Kv2: PartialEq + 'a> {
+	map: &'a mut BidirMap<Kv1, Kv2>,
+	journal: Vec<Undo<Kv1, Kv2>>,
+}
+
+/// The inverse of an edit of the map's pairs
+#[derive(Debug)]
+enum Undo<Kv1, Kv2> {
+	/// Undoes a `push()`
+	Pop,
+	/// Undoes a `swap_remove_at()` of the pair at the index
+	Reinsert(usize, (Kv1, Kv2)),
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
+	/// Starts a transaction, whose edits are all undone unless it's committed.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::{BidirMap, TransactionError};
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert(1, "a");
+	///
+	/// fn batch(map: &mut BidirMap<i32, &'static str>, kv2: &'static str) -> Result<(), TransactionError<i32, &'static str>> {
+	///     let mut tx = map.transaction();
+	///     tx.remove_by_first(&1)?;
+	///     tx.insert(2, "b")?;
+	///     tx.insert(3, kv2)?;
+	///     tx.commit();
+	///     Ok(())
+	/// }
+	///
+	/// assert!(batch(&mut map, "b").is_err());
+	/// assert_eq!(map.get_by_first(&1), Some(&"a"));
+	/// assert_eq!(map.len(), 1);
+	///
+	/// assert!(batch(&mut map, "c").is_ok());
+	/// assert_eq!(map.get_by_first(&1), None);
+	/// assert_eq!(map.len(), 2);
+	/// ```
+	pub fn transaction<'s>(&'s mut self) -> Transaction<'s, Kv1, Kv2> {
+		Transaction{
+			map: self,
+			journal: Vec::new(),
+		}
+	}
+
+	/// Inserts every pair of the batch, if none of them shares a K/V with a pair in the map or with another pair of the batch,
+	/// and otherwise leaves the map as it was, reporting the first pair that does.
+	///
+	/// The whole batch is checked before the map is changed, and the pairs are then added in order.
+	/// Unlike `transaction()`, this can't remove pairs, so it never needs to undo anything.
+	///
+	/// Performance: `O(m·(n + m))`, for `m` pairs in the batch.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::{BatchInsertError, BidirMap, Column};
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert(1, "a");
+	///
+	/// assert_eq!(map.extend_atomic(vec![(2, "b"), (3, "a")]), Err(BatchInsertError{
+	///     index: 1,
+	///     column: Column::Second,
+	///     earlier: None,
+	///     pair: (3, "a"),
+	/// }));
+	/// assert_eq!(map.len(), 1);
+	///
+	/// assert_eq!(map.extend_atomic(vec![(2, "b"), (3, "c")]), Ok(()));
+	/// assert_eq!(map.len(), 3);
+	/// ```
+	pub fn extend_atomic<I: IntoIterator<Item=(Kv1, Kv2)>>(&mut self, iter: I) -> Result<(), BatchInsertError<Kv1, Kv2>> {
+		let batch: Vec<_> = iter.into_iter().collect();
+
+		let mut conflict = None;
+		for (index, kvs) in batch.iter().enumerate() {
+			let earlier = &batch[..index];
+			conflict = if self.position_of_first(&kvs.0).is_some() {
+				Some((index, Column::First, None))
+			} else if let Some(earlier) = earlier.iter().position(|other| other.0 == kvs.0) {
+				Some((index, Column::First, Some(earlier)))
+			} else if self.position_of_second(&kvs.1).is_some() {
+				Some((index, Column::Second, None))
+			} else {
+				earlier.iter().position(|other| other.1 == kvs.1).map(|earlier| (index, Column::Second, Some(earlier)))
+			};
+			if conflict.is_some() {
+				break;
+			}
+		}
+
+		if let Some((index, column, earlier)) = conflict {
+			return Err(BatchInsertError{
+				index,
+				column,
+				earlier,
+				pair: batch.into_iter().nth(index).unwrap(),
+			});
+		}
+
+		self.cont.reserve(batch.len());
+		for (kv1, kv2) in batch {
+			self.assert_room();
+			self.push(kv1, kv2);
+		}
+		Ok(())
+	}
+}
+
+impl<'a, Kv1: PartialEq, Kv2: PartialEq> Transaction<'a, Kv1, Kv2> {
+	/// Inserts a pair sharing neither K/V with a pair in the map, or refuses it, handing it back.
+	pub fn insert(&mut self, kv1: Kv1, kv2: Kv2) -> Result<(), TransactionError<Kv1, Kv2>> {
+		let column = if self.map.position_of_first(&kv1).is_some() {
+			Column::First
+		} else if self.map.position_of_second(&kv2).is_some() {
+			Column::Second
+		} else {
+			self.map.push(kv1, kv2);
+			self.journal.push(Undo::Pop);
+			return Ok(());
+		};
+		Err(TransactionError::Conflict{
+			column,
+			pair: (kv1, kv2),
+		})
+	}
+
+	/// Removes the pair with the first K/V, which must be in the map.
+	///
+	/// The pair is kept until the transaction ends, to be put back on rollback, so only a reference to it is returned.
+	pub fn remove_by_first<Q>(&mut self, key: &Q) -> Result<&(Kv1, Kv2), TransactionError<Kv1, Kv2>>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		let idx = self.map.cont.iter().position(|kvs| *key == *kvs.0.borrow()).ok_or(TransactionError::NotFound{column: Column::First})?;
+		Ok(self.remove_at(idx))
+	}
+
+	/// Removes the pair with the second K/V, which must be in the map.
+	///
+	/// The pair is kept until the transaction ends, to be put back on rollback, so only a reference to it is returned.
+	pub fn remove_by_second<Q>(&mut self, key: &Q) -> Result<&(Kv1, Kv2), TransactionError<Kv1, Kv2>>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		let idx = self.map.cont.iter().position(|kvs| *key == *kvs.1.borrow()).ok_or(TransactionError::NotFound{column: Column::Second})?;
+		Ok(self.remove_at(idx))
+	}
+
+	/// Returns a reference to the second K/V corresponding to the first K/V, as edited so far.
+	pub fn get_by_first<Q>(&self, key: &Q) -> Option<&Kv2>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.get_by_first(key)
+	}
+
+	/// Returns a reference to the first K/V corresponding to the second K/V, as edited so far.
+	pub fn get_by_second<Q>(&self, key: &Q) -> Option<&Kv1>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.get_by_second(key)
+	}
+
+	/// Check if the map contains the first K/V, as edited so far
+	pub fn contains_first_key<Q>(&self, key: &Q) -> bool
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.contains_first_key(key)
+	}
+
+	/// Check if the map contains the second K/V, as edited so far
+	pub fn contains_second_key<Q>(&self, key: &Q) -> bool
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.contains_second_key(key)
+	}
+
+	/// Gets an iterator over the entries of the map, as edited so far.
+	pub fn iter<'s>(&'s self) -> Iter<'s, Kv1, Kv2> {
+		self.map.iter()
+	}
+
+	/// Returns the number of elements in the map, as edited so far.
+	pub fn len(&self) -> usize {
+		self.map.len()
+	}
+
+	/// Returns true if the map contains no elements, as edited so far.
+	pub fn is_empty(&self) -> bool {
+		self.map.is_empty()
+	}
+
+	/// Keeps all edits made.
+	pub fn commit(mut self) {
+		self.journal.clear();
+	}
+
+	/// Undoes all edits made, as dropping the transaction does.
+	pub fn rollback(self) {}
+
+	fn remove_at(&mut self, idx: usize) -> &(Kv1, Kv2) {
+		let kvs = self.map.swap_remove_at(idx);
+		self.journal.push(Undo::Reinsert(idx, kvs));
+		match self.journal.last() {
+			Some(Undo::Reinsert(_, kvs)) => kvs,
+			_ => unreachable!(),
+		}
+	}
+}
+
+impl<'a, Kv1: PartialEq, Kv2: PartialEq> Drop for Transaction<'a, Kv1, Kv2> {
+	fn drop(&mut self) {
+		if self.journal.is_empty() {
+			return;
+		}
+
+		while let Some(undo) = self.journal.pop() {
+			match undo {
+				Undo::Pop => {
+					self.map.cont.pop();
+				}
+				Undo::Reinsert(idx, kvs) => {
+					self.map.cont.push(kvs);
+					let last = self.map.cont.len() - 1;
+					self.map.cont.swap(idx, last);
+				}
+			}
+		}
+		self.map.rebuild_index();
+	}
+}
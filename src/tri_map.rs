@@ -0,0 +1,244 @@
+//! A three-way map, with lookups by any of its columns.
+
+
+use core::borrow::Borrow;
+use core::slice;
+use alloc::vec::{self, Vec};
+
+
+/// A map between three types, unique in each column, looked up by any of them.
+///
+/// Lookups return the whole triple.
+/// Since an incoming triple can collide with up to three existing ones, `insert()` returns all of those it displaced,
+/// while `try_insert()` refuses any collision.
+///
+/// Performance: `O(n)`, mostly.
+///
+/// # Examples
+///
+/// ```
+/// use bidir_map::TriMap;
+///
+/// let mut countries = TriMap::new();
+/// countries.insert(250, "FR", "France");
+/// countries.insert(276, "DE", "Germany");
+///
+/// assert_eq!(countries.get_by_second(&"DE"), Some(&(276, "DE", "Germany")));
+/// assert_eq!(countries.get_by_third(&"France").map(|c| c.0), Some(250));
+/// ```
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct TriMap<A: PartialEq, B: PartialEq, C: PartialEq> {
+	cont: Vec<(A, B, C)>,
+}
+
+impl<A: PartialEq, B: PartialEq, C: PartialEq> TriMap<A, B, C> {
+	/// Create a new empty instance of `TriMap`
+	pub fn new() -> Self {
+		TriMap{
+			cont: Vec::new(),
+		}
+	}
+
+	/// Create a new empty instance of `TriMap` with the specified capacity.
+	///
+	/// It will be able to hold at least `capacity` elements without reallocating.
+	pub fn with_capacity(capacity: usize) -> Self {
+		TriMap{
+			cont: Vec::with_capacity(capacity),
+		}
+	}
+
+	/// Clears the map, removing all entries.
+	pub fn clear(&mut self) {
+		self.cont.clear()
+	}
+
+	/// Inserts a triple into the map, removing every existing triple it shares a value with.
+	///
+	/// The removed triples are returned in the order of the column they collided on: first, second, then third.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::TriMap;
+	///
+	/// let mut map = TriMap::new();
+	/// map.insert(1, 'a', "x");
+	/// map.insert(2, 'b', "y");
+	/// map.insert(3, 'c', "z");
+	///
+	/// assert_eq!(map.insert(1, 'b', "z"), [(1, 'a', "x"), (2, 'b', "y"), (3, 'c', "z")]);
+	/// assert_eq!(map.len(), 1);
+	/// assert_eq!(map.insert(1, 'b', "w"), [(1, 'b', "z")]);
+	/// ```
+	pub fn insert(&mut self, a: A, b: B, c: C) -> Vec<(A, B, C)> {
+		let mut displaced = Vec::new();
+		displaced.extend(self.cont.iter().position(|abc| abc.0 == a).map(|idx| self.cont.swap_remove(idx)));
+		displaced.extend(self.cont.iter().position(|abc| abc.1 == b).map(|idx| self.cont.swap_remove(idx)));
+		displaced.extend(self.cont.iter().position(|abc| abc.2 == c).map(|idx| self.cont.swap_remove(idx)));
+		self.cont.push((a, b, c));
+		displaced
+	}
+
+	/// Inserts a triple into the map, unless any of its values is already present, in which case it's handed back.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::TriMap;
+	///
+	/// let mut map = TriMap::new();
+	/// assert_eq!(map.try_insert(1, 'a', "x"), Ok(()));
+	/// assert_eq!(map.try_insert(2, 'b', "x"), Err((2, 'b', "x")));
+	/// assert_eq!(map.len(), 1);
+	/// ```
+	pub fn try_insert(&mut self, a: A, b: B, c: C) -> Result<(), (A, B, C)> {
+		if self.cont.iter().any(|abc| abc.0 == a || abc.1 == b || abc.2 == c) {
+			Err((a, b, c))
+		} else {
+			self.cont.push((a, b, c));
+			Ok(())
+		}
+	}
+
+	/// Gets an iterator over the triples of the map.
+	pub fn iter<'s>(&'s self) -> Iter<'s, A, B, C> {
+		Iter{
+			iter: self.cont.iter(),
+		}
+	}
+
+	/// Returns the number of triples in the map.
+	pub fn len(&self) -> usize {
+		self.cont.len()
+	}
+
+	/// Returns true if the map contains no elements.
+	pub fn is_empty(&self) -> bool {
+		self.cont.is_empty()
+	}
+
+
+	/// Returns a reference to the triple with the specified first value.
+	pub fn get_by_first<Q>(&self, key: &Q) -> Option<&(A, B, C)>
+		where A: Borrow<Q>,
+		      Q: PartialEq + ?Sized,
+	{
+		self.cont.iter().find(|abc| *key == *abc.0.borrow())
+	}
+
+	/// Returns a reference to the triple with the specified second value.
+	pub fn get_by_second<Q>(&self, key: &Q) -> Option<&(A, B, C)>
+		where B: Borrow<Q>,
+		      Q: PartialEq + ?Sized,
+	{
+		self.cont.iter().find(|abc| *key == *abc.1.borrow())
+	}
+
+	/// Returns a reference to the triple with the specified third value.
+	pub fn get_by_third<Q>(&self, key: &Q) -> Option<&(A, B, C)>
+		where C: Borrow<Q>,
+		      Q: PartialEq + ?Sized,
+	{
+		self.cont.iter().find(|abc| *key == *abc.2.borrow())
+	}
+
+	/// Check if the map contains the first value
+	pub fn contains_first_key<Q>(&self, key: &Q) -> bool
+		where A: Borrow<Q>,
+		      Q: PartialEq + ?Sized,
+	{
+		self.get_by_first(key).is_some()
+	}
+
+	/// Check if the map contains the second value
+	pub fn contains_second_key<Q>(&self, key: &Q) -> bool
+		where B: Borrow<Q>,
+		      Q: PartialEq + ?Sized,
+	{
+		self.get_by_second(key).is_some()
+	}
+
+	/// Check if the map contains the third value
+	pub fn contains_third_key<Q>(&self, key: &Q) -> bool
+		where C: Borrow<Q>,
+		      Q: PartialEq + ?Sized,
+	{
+		self.get_by_third(key).is_some()
+	}
+
+	/// Removes the triple with the specified first value from the map, returning it if it was in the map.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::TriMap;
+	///
+	/// let mut map = TriMap::new();
+	/// map.insert(1, 'a', "x");
+	/// assert_eq!(map.remove_by_first(&1), Some((1, 'a', "x")));
+	/// assert_eq!(map.remove_by_first(&1), None);
+	/// ```
+	pub fn remove_by_first<Q>(&mut self, key: &Q) -> Option<(A, B, C)>
+		where A: Borrow<Q>,
+		      Q: PartialEq + ?Sized,
+	{
+		self.cont.iter().position(|abc| *key == *abc.0.borrow()).map(|idx| self.cont.swap_remove(idx))
+	}
+
+	/// Removes the triple with the specified second value from the map, returning it if it was in the map.
+	pub fn remove_by_second<Q>(&mut self, key: &Q) -> Option<(A, B, C)>
+		where B: Borrow<Q>,
+		      Q: PartialEq + ?Sized,
+	{
+		self.cont.iter().position(|abc| *key == *abc.1.borrow()).map(|idx| self.cont.swap_remove(idx))
+	}
+
+	/// Removes the triple with the specified third value from the map, returning it if it was in the map.
+	pub fn remove_by_third<Q>(&mut self, key: &Q) -> Option<(A, B, C)>
+		where C: Borrow<Q>,
+		      Q: PartialEq + ?Sized,
+	{
+		self.cont.iter().position(|abc| *key == *abc.2.borrow()).map(|idx| self.cont.swap_remove(idx))
+	}
+}
+
+
+impl<A: PartialEq, B: PartialEq, C: PartialEq> Default for TriMap<A, B, C> {
+	fn default() -> Self {
+		TriMap::new()
+	}
+}
+
+impl<A: PartialEq, B: PartialEq, C: PartialEq> IntoIterator for TriMap<A, B, C> {
+	type Item = (A, B, C);
+	type IntoIter = vec::IntoIter<(A, B, C)>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.cont.into_iter()
+	}
+}
+
+impl<'a, A: PartialEq, B: PartialEq, C: PartialEq> IntoIterator for &'a TriMap<A, B, C> {
+	type Item = &'a (A, B, C);
+	type IntoIter = Iter<'a, A, B, C>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+
+/// An iterator over the triples contained in a `TriMap`.
+///
+/// See documentation of `TriMap::iter()` for more.
+pub struct Iter<'a, A: 'a, B: 'a, C: 'a> {
+	iter: slice::Iter<'a, (A, B, C)>,
+}
+
+impl<'a, A, B, C> Iterator for Iter<'a, A, B, C> {
+	type Item = &'a (A, B, C);
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next()
+	}
+}
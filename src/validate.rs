@@ -0,0 +1,139 @@
+//! Checking and restoring the uniqueness of both columns of a `BidirMap`.
+
+
+use alloc::vec::Vec;
+use {BidirMap, Column, InvariantError};
+
+
+/// Which of several colliding pairs `BidirMap::repair()` retains.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum RepairPolicy {
+	/// Keep the pair earliest in iteration order
+	KeepFirst,
+	/// Keep the pair latest in iteration order, as if the pairs had been `insert()`ed in order
+	KeepLast,
+}
+
+/// What to do with a pair repeating a K/V of an earlier one, when building a map from pairs that should be unique.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum OnDuplicate {
+	/// Fail, reporting the later pair
+	Error,
+	/// Keep the earlier pair, dropping the later one
+	FirstWins,
+	/// Replace the earlier pair with the later one, as `insert()` does
+	LastWins,
+}
+
+/// What a `BidirMap`'s `insert()` does with a pair sharing a K/V with one in the map, for each column.
+///
+/// See documentation of `BidirMap::with_duplicate_policy()` for more.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct DuplicatePolicy {
+	/// For a first K/V already in the map
+	pub on_first: OnDuplicate,
+	/// For a second K/V already in the map
+	pub on_second: OnDuplicate,
+}
+
+impl DuplicatePolicy {
+	/// The same policy for both columns
+	pub fn both(on_duplicate: OnDuplicate) -> DuplicatePolicy {
+		DuplicatePolicy{
+			on_first: on_duplicate,
+			on_second: on_duplicate,
+		}
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
+	/// Checks that no two pairs share a first or a second K/V, which changes via `iter_mut()` or `get_mut_by_*()`,
+	/// or an unchecked `FromIterator`/`Extend`, can violate.
+	///
+	/// Reports the first collision found. Takes `O(n²)`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::{BidirMap, Column, InvariantError};
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert(1, "a");
+	/// map.insert(2, "b");
+	/// assert_eq!(map.validate(), Ok(()));
+	///
+	/// *map.get_mut_by_first(&2).unwrap() = "a";
+	/// assert_eq!(map.validate(), Err(InvariantError{column: Column::Second, indices: (0, 1)}));
+	/// ```
+	pub fn validate(&self) -> Result<(), InvariantError> {
+		for (j, later) in self.cont.iter().enumerate() {
+			for (i, earlier) in self.cont[..j].iter().enumerate() {
+				if earlier.0 == later.0 {
+					return Err(InvariantError{column: Column::First, indices: (i, j)});
+				}
+				if earlier.1 == later.1 {
+					return Err(InvariantError{column: Column::Second, indices: (i, j)});
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Panics if `validate()` fails, in debug builds; does nothing in release builds.
+	pub fn debug_validate(&self) {
+		if cfg!(debug_assertions) {
+			if let Err(err) = self.validate() {
+				panic!("{}", err);
+			}
+		}
+	}
+
+	/// Removes pairs until no two share a first or a second K/V, returning how many were removed.
+	///
+	/// The remaining pairs keep their relative order.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::{BidirMap, RepairPolicy};
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert(1, "a");
+	/// map.insert(2, "b");
+	/// *map.get_mut_by_first(&2).unwrap() = "a";
+	///
+	/// let mut last = map.clone();
+	/// assert_eq!(map.repair(RepairPolicy::KeepFirst), 1);
+	/// assert_eq!(map.get_by_second(&"a"), Some(&1));
+	/// assert_eq!(last.repair(RepairPolicy::KeepLast), 1);
+	/// assert_eq!(last.get_by_second(&"a"), Some(&2));
+	/// ```
+	pub fn repair(&mut self, policy: RepairPolicy) -> usize {
+		let len = self.cont.len();
+		let mut keep = Vec::with_capacity(len);
+		keep.resize(len, false);
+		{
+			let cont = &self.cont;
+			let mut kept: Vec<usize> = Vec::with_capacity(len);
+			let mut consider = |idx: usize| {
+				if !kept.iter().any(|&k| cont[k].0 == cont[idx].0 || cont[k].1 == cont[idx].1) {
+					kept.push(idx);
+					keep[idx] = true;
+				}
+			};
+			match policy {
+				RepairPolicy::KeepFirst => (0..len).for_each(&mut consider),
+				RepairPolicy::KeepLast => (0..len).rev().for_each(&mut consider),
+			}
+		}
+
+		let removed = keep.iter().filter(|&&k| !k).count();
+		if removed != 0 {
+			let mut keep = keep.into_iter();
+			self.cont.retain(|_| keep.next().unwrap());
+			self.rebuild_index();
+		}
+		removed
+	}
+}
+
@@ -0,0 +1,249 @@
+//! A `BidirMap` wrapper checking every pair against a validator before it goes in, see `BidirMap::with_validator()`.
+
+
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+#[cfg(feature = "std")]
+use std::io::{self, Read};
+#[cfg(feature = "std")]
+use binary::BinaryKey;
+use entry::{EntryByFirst, EntryBySecond};
+use {BidirMap, Iter, ValidationError};
+
+
+/// A `BidirMap` refusing pairs its validator rejects, enforcing domain rules on its K/Vs.
+///
+/// The validator runs before every insertion, by `insert()`, `try_extend()`, `get_or_insert_with_by_*()`,
+/// and when building the map with `try_from_iter()` or `read_from()`;
+/// if it rejects a pair, its error is returned and the map is left as it was, with nothing displaced.
+///
+/// To keep every pair validated, the wrapper hands out no mutable references to K/Vs,
+/// nor to the underlying `BidirMap`, except by giving it up with `into_parts()`.
+/// It doesn't implement `Extend`, which can't fail; use `try_extend()` instead.
+///
+/// # Examples
+///
+/// ```
+/// use bidir_map::{BidirMap, ValidationError};
+///
+/// let mut map = BidirMap::with_validator(|name: &&str, title: &&str| {
+///     if !name.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_') {
+///         Err(ValidationError::new("names must match [a-z0-9_]+"))
+///     } else if title.is_empty() {
+///         Err(ValidationError::new("titles must be non-empty"))
+///     } else {
+///         Ok(())
+///     }
+/// });
+///
+/// assert_eq!(map.insert("intro", "Introduction"), Ok(None));
+/// assert_eq!(map.insert("Intro", "Introduction").unwrap_err().message, "names must match [a-z0-9_]+");
+/// assert!(map.insert("outro", "").is_err());
+/// assert_eq!(map.len(), 1);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ValidatedBidirMap<Kv1: PartialEq, Kv2: PartialEq, V: Fn(&Kv1, &Kv2) -> Result<(), ValidationError>> {
+	map: BidirMap<Kv1, Kv2>,
+	validator: V,
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
+	/// Create a new empty `ValidatedBidirMap`, checking every pair inserted into it with `validator`.
+	pub fn with_validator<V>(validator: V) -> ValidatedBidirMap<Kv1, Kv2, V>
+		where V: Fn(&Kv1, &Kv2) -> Result<(), ValidationError>,
+	{
+		ValidatedBidirMap{
+			map: BidirMap::new(),
+			validator,
+		}
+	}
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq, V: Fn(&Kv1, &Kv2) -> Result<(), ValidationError>> ValidatedBidirMap<Kv1, Kv2, V> {
+	/// Builds a map from the pairs, as `BidirMap::from_iter()` does, unless `validator` rejects any of them.
+	///
+	/// This is the way to validate a map deserialized by other means, like `BidirMap::from_csv_reader()`:
+	/// the `BidirMap` is itself an iterator of pairs.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::{BidirMap, ValidatedBidirMap, ValidationError};
+	///
+	/// let positive = |&kv1: &i32, &kv2: &i32| if kv1 > 0 && kv2 > 0 { Ok(()) } else { Err(ValidationError::new("not positive")) };
+	///
+	/// let map = ValidatedBidirMap::try_from_iter(vec![(1, 2), (3, 4)], positive).unwrap();
+	/// assert_eq!(map.get_by_first(&3), Some(&4));
+	///
+	/// let loaded: BidirMap<_, _> = vec![(1, 2), (-3, 4)].into_iter().collect();
+	/// assert!(ValidatedBidirMap::try_from_iter(loaded, positive).is_err());
+	/// ```
+	pub fn try_from_iter<I: IntoIterator<Item=(Kv1, Kv2)>>(iter: I, validator: V) -> Result<Self, ValidationError> {
+		let pairs = validate_all(&validator, iter)?;
+		Ok(ValidatedBidirMap{
+			map: pairs.into_iter().collect(),
+			validator,
+		})
+	}
+
+	/// Reads a map written by `BidirMap::write_to()`, as `BidirMap::read_from()` does,
+	/// failing with `InvalidData` carrying the `ValidationError` if `validator` rejects any of its pairs.
+	#[cfg(feature = "std")]
+	pub fn read_from<R: Read>(r: R, validator: V) -> io::Result<Self>
+		where Kv1: BinaryKey,
+		      Kv2: BinaryKey,
+	{
+		let map = BidirMap::read_from(r)?;
+		ValidatedBidirMap::try_from_iter(map, validator).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+	}
+
+	/// Unwraps the underlying `BidirMap` and the validator.
+	pub fn into_parts(self) -> (BidirMap<Kv1, Kv2>, V) {
+		(self.map, self.validator)
+	}
+
+	/// Gets a reference to the underlying `BidirMap`.
+	pub fn inner(&self) -> &BidirMap<Kv1, Kv2> {
+		&self.map
+	}
+
+	/// Clears the map, removing all entries.
+	pub fn clear(&mut self) {
+		self.map.clear()
+	}
+
+	/// Inserts a K/V-K/V pair into the map, as `BidirMap::insert()` does, if the validator accepts it.
+	///
+	/// A rejected pair is dropped, and nothing is displaced from the map.
+	pub fn insert(&mut self, kv1: Kv1, kv2: Kv2) -> Result<Option<(Kv1, Kv2)>, ValidationError> {
+		(self.validator)(&kv1, &kv2)?;
+		Ok(self.map.insert(kv1, kv2))
+	}
+
+	/// Appends the pairs to the map, as `BidirMap::extend()` does, if the validator accepts every one of them.
+	///
+	/// All pairs are validated before any is inserted, so a rejection leaves the map as it was.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::{BidirMap, ValidationError};
+	///
+	/// let mut map = BidirMap::with_validator(|_: &u8, kv2: &char| {
+	///     if kv2.is_ascii_uppercase() { Ok(()) } else { Err(ValidationError::new("not uppercase")) }
+	/// });
+	/// assert!(map.try_extend(vec![(1, 'A'), (2, 'b')]).is_err());
+	/// assert!(map.is_empty());
+	/// assert!(map.try_extend(vec![(1, 'A'), (2, 'B')]).is_ok());
+	/// assert_eq!(map.len(), 2);
+	/// ```
+	pub fn try_extend<I: IntoIterator<Item=(Kv1, Kv2)>>(&mut self, iter: I) -> Result<(), ValidationError> {
+		let pairs = validate_all(&self.validator, iter)?;
+		self.map.extend(pairs);
+		Ok(())
+	}
+
+	/// Returns the second K/V corresponding to the first K/V, inserting the pair with the one made by `default` if there's none,
+	/// as `BidirMap::entry_by_first().or_insert_with()` does – if the validator accepts the new pair.
+	///
+	/// `default` is only called, and the validator only run, if the first K/V isn't in the map.
+	pub fn get_or_insert_with_by_first<F: FnOnce() -> Kv2>(&mut self, kv1: Kv1, default: F) -> Result<&Kv2, ValidationError> {
+		match self.map.entry_by_first(kv1) {
+			EntryByFirst::Occupied(entry) => Ok(entry.into_mut()),
+			EntryByFirst::Vacant(entry) => {
+				let kv2 = default();
+				(self.validator)(entry.key(), &kv2)?;
+				Ok(entry.insert(kv2))
+			}
+		}
+	}
+
+	/// Returns the first K/V corresponding to the second K/V, inserting the pair with the one made by `default` if there's none,
+	/// as `BidirMap::entry_by_second().or_insert_with()` does – if the validator accepts the new pair.
+	///
+	/// `default` is only called, and the validator only run, if the second K/V isn't in the map.
+	pub fn get_or_insert_with_by_second<F: FnOnce() -> Kv1>(&mut self, kv2: Kv2, default: F) -> Result<&Kv1, ValidationError> {
+		match self.map.entry_by_second(kv2) {
+			EntryBySecond::Occupied(entry) => Ok(entry.into_mut()),
+			EntryBySecond::Vacant(entry) => {
+				let kv1 = default();
+				(self.validator)(&kv1, entry.key())?;
+				Ok(entry.insert(kv1))
+			}
+		}
+	}
+
+	/// Removes the pair with the first K/V from the map, returning it if the key was previously in the map.
+	pub fn remove_by_first<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.remove_by_first(key)
+	}
+
+	/// Removes the pair with the second K/V from the map, returning it if the key was previously in the map.
+	pub fn remove_by_second<Q>(&mut self, key: &Q) -> Option<(Kv1, Kv2)>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.remove_by_second(key)
+	}
+
+	/// Returns a reference to the second K/V corresponding to the first K/V.
+	pub fn get_by_first<Q>(&self, key: &Q) -> Option<&Kv2>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.get_by_first(key)
+	}
+
+	/// Returns a reference to the first K/V corresponding to the second K/V.
+	pub fn get_by_second<Q>(&self, key: &Q) -> Option<&Kv1>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.get_by_second(key)
+	}
+
+	/// Check if the map contains the first K/V.
+	pub fn contains_first_key<Q>(&self, key: &Q) -> bool
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.contains_first_key(key)
+	}
+
+	/// Check if the map contains the second K/V.
+	pub fn contains_second_key<Q>(&self, key: &Q) -> bool
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.contains_second_key(key)
+	}
+
+	/// Gets an iterator over the entries of the map.
+	pub fn iter<'s>(&'s self) -> Iter<'s, Kv1, Kv2> {
+		self.map.iter()
+	}
+
+	/// Returns the number of elements in the map.
+	pub fn len(&self) -> usize {
+		self.map.len()
+	}
+
+	/// Returns true if the map contains no elements.
+	pub fn is_empty(&self) -> bool {
+		self.map.is_empty()
+	}
+}
+
+fn validate_all<Kv1, Kv2, V, I>(validator: &V, iter: I) -> Result<Vec<(Kv1, Kv2)>, ValidationError>
+	where V: Fn(&Kv1, &Kv2) -> Result<(), ValidationError>,
+	      I: IntoIterator<Item=(Kv1, Kv2)>,
+{
+	let pairs: Vec<_> = iter.into_iter().collect();
+	for (kv1, kv2) in &pairs {
+		validator(kv1, kv2)?;
+	}
+	Ok(pairs)
+}
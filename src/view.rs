@@ -0,0 +1,317 @@
+//! Read-only views of one direction of a `BidirMap`, as an ordinary key→value map.
+
+
+use core::borrow::Borrow;
+use core::slice;
+use alloc::collections::{btree_map, BTreeMap};
+#[cfg(feature = "std")]
+use std::collections::{hash_map, HashMap};
+#[cfg(feature = "std")]
+use std::hash::{BuildHasher, Hash};
+use BidirMap;
+
+
+/// The read-only interface of a key→value map, for code generic over the kind of map it looks things up in.
+///
+/// Implemented for `FirstMap`, `SecondMap`, `BTreeMap` and, with the `std` feature, `HashMap`.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::BTreeMap;
+/// use bidir_map::BidirMap;
+/// use bidir_map::view::MapRead;
+///
+/// fn describe<M: MapRead<u32, &'static str>>(map: &M) -> &'static str {
+///     map.get(&1).cloned().unwrap_or("none")
+/// }
+///
+/// let mut btree = BTreeMap::new();
+/// btree.insert(1, "one");
+/// let mut bidir = BidirMap::new();
+/// bidir.insert("uno", 1);
+///
+/// assert_eq!(describe(&btree), "one");
+/// assert_eq!(describe(&bidir.as_second_map()), "uno");
+/// ```
+pub trait MapRead<K, V> {
+	/// The iterator returned by `iter()`
+	type Iter<'a>: Iterator<Item=(&'a K, &'a V)> where Self: 'a, K: 'a, V: 'a;
+
+	/// Returns a reference to the value corresponding to the key.
+	fn get(&self, key: &K) -> Option<&V>;
+
+	/// Check if the map contains the key
+	fn contains_key(&self, key: &K) -> bool {
+		self.get(key).is_some()
+	}
+
+	/// Gets an iterator over the key-value pairs of the map.
+	fn iter(&self) -> Self::Iter<'_>;
+
+	/// Returns the number of elements in the map.
+	fn len(&self) -> usize;
+
+	/// Returns true if the map contains no elements.
+	fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+}
+
+
+/// A `BidirMap` seen as a map from its first K/Vs to its second ones.
+///
+/// Obtained from `BidirMap::as_first_map()`.
+pub struct FirstMap<'a, Kv1: PartialEq + 'a, Kv2: PartialEq + 'a> {
+	map: &'a BidirMap<Kv1, Kv2>,
+}
+
+/// A `BidirMap` seen as a map from its second K/Vs to its first ones.
+///
+/// Obtained from `BidirMap::as_second_map()`.
+pub struct SecondMap<'a, Kv1: PartialEq + 'a, Kv2: PartialEq + 'a> {
+	map: &'a BidirMap<Kv1, Kv2>,
+}
+
+impl<Kv1: PartialEq, Kv2: PartialEq> BidirMap<Kv1, Kv2> {
+	/// Views the map as a read-only map from first K/Vs to second K/Vs.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert(1, "a");
+	///
+	/// let view = map.as_first_map();
+	/// assert_eq!(view.get(&1), Some(&"a"));
+	/// assert_eq!(view.iter().collect::<Vec<_>>(), [(&1, &"a")]);
+	/// ```
+	pub fn as_first_map<'s>(&'s self) -> FirstMap<'s, Kv1, Kv2> {
+		FirstMap{
+			map: self,
+		}
+	}
+
+	/// Views the map as a read-only map from second K/Vs to first K/Vs.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bidir_map::BidirMap;
+	///
+	/// let mut map = BidirMap::new();
+	/// map.insert(1, "a");
+	///
+	/// let view = map.as_second_map();
+	/// assert_eq!(view.get(&"a"), Some(&1));
+	/// assert_eq!(view.iter().collect::<Vec<_>>(), [(&"a", &1)]);
+	/// ```
+	pub fn as_second_map<'s>(&'s self) -> SecondMap<'s, Kv1, Kv2> {
+		SecondMap{
+			map: self,
+		}
+	}
+}
+
+impl<'a, Kv1: PartialEq, Kv2: PartialEq> FirstMap<'a, Kv1, Kv2> {
+	/// Returns a reference to the second K/V corresponding to the first K/V, see `BidirMap::get_by_first()`.
+	pub fn get<Q>(&self, key: &Q) -> Option<&'a Kv2>
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.cont.iter().find(|kvs| *key == *kvs.0.borrow()).map(|kvs| &kvs.1)
+	}
+
+	/// Check if the map contains the first K/V
+	pub fn contains_key<Q>(&self, key: &Q) -> bool
+		where Kv1: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.get(key).is_some()
+	}
+
+	/// Gets an iterator over the pairs of the map, as `(first, second)`.
+	pub fn iter(&self) -> FirstMapIter<'a, Kv1, Kv2> {
+		FirstMapIter{
+			iter: self.map.cont.iter(),
+		}
+	}
+
+	/// Returns the number of elements in the map.
+	pub fn len(&self) -> usize {
+		self.map.len()
+	}
+
+	/// Returns true if the map contains no elements.
+	pub fn is_empty(&self) -> bool {
+		self.map.is_empty()
+	}
+}
+
+impl<'a, Kv1: PartialEq, Kv2: PartialEq> SecondMap<'a, Kv1, Kv2> {
+	/// Returns a reference to the first K/V corresponding to the second K/V, see `BidirMap::get_by_second()`.
+	pub fn get<Q>(&self, key: &Q) -> Option<&'a Kv1>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.cont.iter().find(|kvs| *key == *kvs.1.borrow()).map(|kvs| &kvs.0)
+	}
+
+	/// Check if the map contains the second K/V
+	pub fn contains_key<Q>(&self, key: &Q) -> bool
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.get(key).is_some()
+	}
+
+	/// Gets an iterator over the pairs of the map, as `(second, first)`.
+	pub fn iter(&self) -> SecondMapIter<'a, Kv1, Kv2> {
+		SecondMapIter{
+			iter: self.map.cont.iter(),
+		}
+	}
+
+	/// Returns the number of elements in the map.
+	pub fn len(&self) -> usize {
+		self.map.len()
+	}
+
+	/// Returns true if the map contains no elements.
+	pub fn is_empty(&self) -> bool {
+		self.map.is_empty()
+	}
+}
+
+
+impl<'a, Kv1: PartialEq, Kv2: PartialEq> Clone for FirstMap<'a, Kv1, Kv2> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<'a, Kv1: PartialEq, Kv2: PartialEq> Copy for FirstMap<'a, Kv1, Kv2> {}
+
+impl<'a, Kv1: PartialEq, Kv2: PartialEq> Clone for SecondMap<'a, Kv1, Kv2> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<'a, Kv1: PartialEq, Kv2: PartialEq> Copy for SecondMap<'a, Kv1, Kv2> {}
+
+impl<'a, Kv1: PartialEq, Kv2: PartialEq> IntoIterator for FirstMap<'a, Kv1, Kv2> {
+	type Item = (&'a Kv1, &'a Kv2);
+	type IntoIter = FirstMapIter<'a, Kv1, Kv2>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+impl<'a, Kv1: PartialEq, Kv2: PartialEq> IntoIterator for SecondMap<'a, Kv1, Kv2> {
+	type Item = (&'a Kv2, &'a Kv1);
+	type IntoIter = SecondMapIter<'a, Kv1, Kv2>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+
+impl<'m, Kv1: PartialEq, Kv2: PartialEq> MapRead<Kv1, Kv2> for FirstMap<'m, Kv1, Kv2> {
+	type Iter<'a> = FirstMapIter<'a, Kv1, Kv2> where Self: 'a;
+
+	fn get(&self, key: &Kv1) -> Option<&Kv2> {
+		FirstMap::get(self, key)
+	}
+
+	fn iter(&self) -> Self::Iter<'_> {
+		FirstMap::iter(self)
+	}
+
+	fn len(&self) -> usize {
+		FirstMap::len(self)
+	}
+}
+
+impl<'m, Kv1: PartialEq, Kv2: PartialEq> MapRead<Kv2, Kv1> for SecondMap<'m, Kv1, Kv2> {
+	type Iter<'a> = SecondMapIter<'a, Kv1, Kv2> where Self: 'a;
+
+	fn get(&self, key: &Kv2) -> Option<&Kv1> {
+		SecondMap::get(self, key)
+	}
+
+	fn iter(&self) -> Self::Iter<'_> {
+		SecondMap::iter(self)
+	}
+
+	fn len(&self) -> usize {
+		SecondMap::len(self)
+	}
+}
+
+impl<K: Ord, V> MapRead<K, V> for BTreeMap<K, V> {
+	type Iter<'a> = btree_map::Iter<'a, K, V> where Self: 'a;
+
+	fn get(&self, key: &K) -> Option<&V> {
+		BTreeMap::get(self, key)
+	}
+
+	fn iter(&self) -> Self::Iter<'_> {
+		BTreeMap::iter(self)
+	}
+
+	fn len(&self) -> usize {
+		BTreeMap::len(self)
+	}
+}
+
+#[cfg(feature = "std")]
+impl<K: Hash + Eq, V, S: BuildHasher> MapRead<K, V> for HashMap<K, V, S> {
+	type Iter<'a> = hash_map::Iter<'a, K, V> where Self: 'a;
+
+	fn get(&self, key: &K) -> Option<&V> {
+		HashMap::get(self, key)
+	}
+
+	fn iter(&self) -> Self::Iter<'_> {
+		HashMap::iter(self)
+	}
+
+	fn len(&self) -> usize {
+		HashMap::len(self)
+	}
+}
+
+
+/// An iterator over the pairs of a `FirstMap`, as `(first, second)`.
+///
+/// See documentation of `FirstMap::iter()` for more.
+pub struct FirstMapIter<'a, Kv1: 'a, Kv2: 'a> {
+	iter: slice::Iter<'a, (Kv1, Kv2)>,
+}
+
+impl<'a, Kv1, Kv2> Iterator for FirstMapIter<'a, Kv1, Kv2> {
+	type Item = (&'a Kv1, &'a Kv2);
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next().map(|kvs| (&kvs.0, &kvs.1))
+	}
+}
+
+
+/// An iterator over the pairs of a `SecondMap`, as `(second, first)`.
+///
+/// See documentation of `SecondMap::iter()` for more.
+pub struct SecondMapIter<'a, Kv1: 'a, Kv2: 'a> {
+	iter: slice::Iter<'a, (Kv1, Kv2)>,
+}
+
+impl<'a, Kv1, Kv2> Iterator for SecondMapIter<'a, Kv1, Kv2> {
+	type Item = (&'a Kv2, &'a Kv1);
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.next().map(|kvs| (&kvs.1, &kvs.0))
+	}
+}
@@ -0,0 +1,209 @@
+//! A `BidirMap` variant holding its first K/Vs by weak reference, so the map doesn't keep them alive.
+
+
+use core::borrow::Borrow;
+use core::fmt;
+use alloc::sync::{Arc, Weak};
+use {BidirMap, Iter as PairIter};
+
+
+/// A bidirectional map from `Arc`s, held as `Weak`s, to second K/Vs.
+///
+/// The map doesn't keep the `Arc`s' referents alive: once the last `Arc` to one is dropped, its pair is dead.
+/// Dead pairs are never found nor iterated, but keep their slot, and second K/V, until `prune()` removes them;
+/// `insert()` prunes them by itself before the map would have to grow.
+///
+/// The first K/Vs are compared by identity, as by `Arc::ptr_eq()`, not by value,
+/// so `T` needn't be comparable, and two equal but separately allocated referents are different K/Vs.
+///
+/// Performance: `O(n)` lookups, over live and dead pairs.
+///
+/// # Examples
+///
+/// ```
+/// use bidir_map::WeakBidirMap;
+/// use std::sync::Arc;
+///
+/// let grass = Arc::new("grass.png");
+/// let stone = Arc::new("stone.png");
+///
+/// let mut textures = WeakBidirMap::new();
+/// textures.insert(&grass, 1);
+/// textures.insert(&stone, 2);
+/// assert_eq!(textures.get_by_first(&grass), Some(&1));
+///
+/// drop(stone);
+/// assert_eq!(textures.get_by_second(&2), None);
+/// assert_eq!(textures.prune(), 1);
+/// assert_eq!(textures.len(), 1);
+/// ```
+pub struct WeakBidirMap<T: ?Sized, Kv2: PartialEq> {
+	map: BidirMap<WeakKey<T>, Kv2>,
+}
+
+/// A `Weak` compared by the address of its referent
+struct WeakKey<T: ?Sized>(Weak<T>);
+
+impl<T: ?Sized> PartialEq for WeakKey<T> {
+	fn eq(&self, other: &Self) -> bool {
+		Weak::ptr_eq(&self.0, &other.0)
+	}
+}
+
+impl<T: ?Sized> Clone for WeakKey<T> {
+	fn clone(&self) -> Self {
+		WeakKey(self.0.clone())
+	}
+}
+
+impl<T: ?Sized, Kv2: PartialEq> WeakBidirMap<T, Kv2> {
+	/// Create a new empty instance of `WeakBidirMap`
+	pub fn new() -> Self {
+		WeakBidirMap{
+			map: BidirMap::new(),
+		}
+	}
+
+	/// Create a new empty instance of `WeakBidirMap` with the specified capacity.
+	///
+	/// It will be able to hold at least `capacity` pairs, live or dead, without reallocating.
+	pub fn with_capacity(capacity: usize) -> Self {
+		WeakBidirMap{
+			map: BidirMap::with_capacity(capacity),
+		}
+	}
+
+	/// Clears the map, removing all entries.
+	pub fn clear(&mut self) {
+		self.map.clear()
+	}
+
+	/// Inserts the pair of a weak reference to `kv1` and `kv2` into the map.
+	///
+	/// As with `BidirMap::insert()`, the pairs holding `kv1` or `kv2` are removed,
+	/// and the one holding `kv1` is preferably returned, with its first K/V as it was held; a dead pair may thus be returned.
+	///
+	/// If the map is full, dead pairs are pruned first, as by `prune()`.
+	pub fn insert(&mut self, kv1: &Arc<T>, kv2: Kv2) -> Option<(Weak<T>, Kv2)> {
+		if self.map.len() == self.map.capacity() {
+			self.prune();
+		}
+		self.map.insert(WeakKey(Arc::downgrade(kv1)), kv2).map(|(kv1, kv2)| (kv1.0, kv2))
+	}
+
+	/// Removes all dead pairs, keeping the order of the rest, and returns how many there were.
+	pub fn prune(&mut self) -> usize {
+		let len = self.map.len();
+		self.map.retain(|kv1, _| kv1.0.strong_count() != 0);
+		len - self.map.len()
+	}
+
+	/// Removes the pair with the first K/V from the map, returning its second K/V if it was in the map.
+	pub fn remove_by_first(&mut self, kv1: &Arc<T>) -> Option<Kv2> {
+		self.map.remove_by_first(&WeakKey(Arc::downgrade(kv1))).map(|(_, kv2)| kv2)
+	}
+
+	/// Removes the pair with the second K/V from the map, returning it if it was in the map, live or dead.
+	pub fn remove_by_second<Q>(&mut self, key: &Q) -> Option<(Weak<T>, Kv2)>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.remove_by_second(key).map(|(kv1, kv2)| (kv1.0, kv2))
+	}
+
+	/// Returns a reference to the second K/V corresponding to the first K/V.
+	pub fn get_by_first(&self, kv1: &Arc<T>) -> Option<&Kv2> {
+		self.map.get_by_first(&WeakKey(Arc::downgrade(kv1)))
+	}
+
+	/// Returns the first K/V corresponding to the second K/V, if it's in the map and still alive.
+	pub fn get_by_second<Q>(&self, key: &Q) -> Option<Arc<T>>
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.get_by_second(key).and_then(|kv1| kv1.0.upgrade())
+	}
+
+	/// Check if the map contains the first K/V
+	pub fn contains_first_key(&self, kv1: &Arc<T>) -> bool {
+		self.get_by_first(kv1).is_some()
+	}
+
+	/// Check if the map contains the second K/V, in a live pair
+	pub fn contains_second_key<Q>(&self, key: &Q) -> bool
+		where Kv2: Borrow<Q>,
+		      Q  : PartialEq + ?Sized,
+	{
+		self.map.get_by_second(key).is_some_and(|kv1| kv1.0.strong_count() != 0)
+	}
+
+	/// Gets an iterator over the live entries of the map, upgrading their first K/Vs.
+	pub fn iter<'s>(&'s self) -> Iter<'s, T, Kv2> {
+		Iter{
+			iter: self.map.iter(),
+		}
+	}
+
+	/// Returns the number of pairs in the map, including dead ones not yet pruned.
+	pub fn len(&self) -> usize {
+		self.map.len()
+	}
+
+	/// Returns true if the map contains no pairs, live or dead.
+	pub fn is_empty(&self) -> bool {
+		self.map.is_empty()
+	}
+
+	/// Returns the number of pairs the map can hold, live or dead, without reallocating.
+	pub fn capacity(&self) -> usize {
+		self.map.capacity()
+	}
+}
+
+impl<T: ?Sized, Kv2: PartialEq + Clone> Clone for WeakBidirMap<T, Kv2> {
+	fn clone(&self) -> Self {
+		WeakBidirMap{
+			map: self.map.clone(),
+		}
+	}
+}
+
+impl<T: ?Sized, Kv2: PartialEq + fmt::Debug> fmt::Debug for WeakBidirMap<T, Kv2> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_map().entries(self.map.iter().map(|(kv1, kv2)| (&kv1.0, kv2))).finish()
+	}
+}
+
+impl<T: ?Sized, Kv2: PartialEq> Default for WeakBidirMap<T, Kv2> {
+	fn default() -> Self {
+		WeakBidirMap::new()
+	}
+}
+
+impl<'a, T: ?Sized, Kv2: PartialEq> IntoIterator for &'a WeakBidirMap<T, Kv2> {
+	type Item = (Arc<T>, &'a Kv2);
+	type IntoIter = Iter<'a, T, Kv2>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+
+/// An iterator over the live K/V pairs contained in a `WeakBidirMap`.
+///
+/// See documentation of `WeakBidirMap::iter()` for more.
+pub struct Iter<'a, T: 'a + ?Sized, Kv2: 'a> {
+	iter: PairIter<'a, WeakKey<T>, Kv2>,
+}
+
+impl<'a, T: ?Sized, Kv2> Iterator for Iter<'a, T, Kv2> {
+	type Item = (Arc<T>, &'a Kv2);
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.by_ref().filter_map(|(kv1, kv2)| kv1.0.upgrade().map(|kv1| (kv1, kv2))).next()
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(0, self.iter.size_hint().1)
+	}
+}
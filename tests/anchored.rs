@@ -0,0 +1,71 @@
+extern crate bidir_map;
+
+mod common;
+
+use bidir_map::{BidirMap, InsertError};
+use common::pairs;
+
+
+fn abc() -> BidirMap<u32, char> {
+	vec![(1, 'a'), (2, 'b'), (3, 'c')].into_iter().collect()
+}
+
+
+#[test]
+fn anchors_at_start_middle_and_end() {
+	let mut map = abc();
+	map.insert_before_first(&1, 0, 'z').unwrap();
+	assert_eq!(pairs(&map), [(0, 'z'), (1, 'a'), (2, 'b'), (3, 'c')]);
+	map.insert_after_first(&1, 10, 'y').unwrap();
+	assert_eq!(pairs(&map), [(0, 'z'), (1, 'a'), (10, 'y'), (2, 'b'), (3, 'c')]);
+	map.insert_after_first(&3, 30, 'x').unwrap();
+	assert_eq!(pairs(&map), [(0, 'z'), (1, 'a'), (10, 'y'), (2, 'b'), (3, 'c'), (30, 'x')]);
+
+	let mut map = abc();
+	map.insert_after_second(&'c', 4, 'd').unwrap();
+	map.insert_before_second(&'b', 5, 'e').unwrap();
+	map.insert_before_second(&'a', 6, 'f').unwrap();
+	assert_eq!(pairs(&map), [(6, 'f'), (1, 'a'), (5, 'e'), (2, 'b'), (3, 'c'), (4, 'd')]);
+}
+
+#[test]
+fn missing_anchor() {
+	let mut map = abc();
+	assert_eq!(map.insert_after_first(&4, 5, 'e'), Err(InsertError{pair: (5, 'e')}));
+	assert_eq!(map.insert_before_second(&'x', 5, 'e').unwrap_err().to_string(), "anchor not in the map");
+	assert_eq!(pairs(&map), pairs(&abc()));
+
+	let mut empty = BidirMap::new();
+	assert!(empty.insert_before_first(&1, 1, 'a').is_err());
+	assert!(empty.is_empty());
+}
+
+#[test]
+fn duplicates_removed_in_order() {
+	// Colliding with pairs on either side of the anchor
+	let mut map: BidirMap<_, _> = (0..6).map(|i| (i, i * 10)).collect();
+	map.insert_after_first(&3, 1, 50).unwrap();
+	assert_eq!(map.iter().cloned().collect::<Vec<_>>(), [(0, 0), (2, 20), (3, 30), (1, 50), (4, 40)]);
+
+	// Replacing the anchor itself
+	let mut map = abc();
+	map.insert_after_first(&2, 2, 'z').unwrap();
+	assert_eq!(pairs(&map), [(1, 'a'), (2, 'z'), (3, 'c')]);
+	map.insert_before_second(&'z', 9, 'z').unwrap();
+	assert_eq!(pairs(&map), [(1, 'a'), (9, 'z'), (3, 'c')]);
+
+	// Colliding with a single pair on both K/Vs
+	map.insert_before_first(&1, 3, 'c').unwrap();
+	assert_eq!(pairs(&map), [(3, 'c'), (1, 'a'), (9, 'z')]);
+}
+
+#[test]
+fn hash_index_kept_in_sync() {
+	let mut map: BidirMap<_, _> = (0..20).map(|i| (i, i * 10)).collect();
+	map.build_index();
+	map.insert_before_first(&5, 100, 30).unwrap();
+	assert_eq!(map.get_by_second_indexed(&30), Some(&100));
+	assert_eq!(map.get_by_first_indexed(&3), None);
+	assert_eq!(map.get_by_first_indexed(&19), Some(&190));
+	assert_eq!(map.validate(), Ok(()));
+}
@@ -0,0 +1,60 @@
+extern crate bidir_map;
+
+use bidir_map::{BidirArrayMap, BidirMap};
+
+
+#[test]
+fn holds_exactly_capacity_entries() {
+	let mut map = BidirArrayMap::<_, _, 3>::new();
+	for i in 0..3 {
+		assert_eq!(map.insert(i, i * 10), Ok(None));
+	}
+	assert!(map.is_full());
+	assert_eq!(map.len(), 3);
+	for i in 0..3 {
+		assert_eq!(map.get_by_first(&i), Some(&(i * 10)));
+		assert_eq!(map.get_by_second(&(i * 10)), Some(&i));
+	}
+}
+
+#[test]
+fn insert_past_capacity_returns_pair() {
+	let mut map = BidirArrayMap::<_, _, 2>::new();
+	map.insert(1, 'a').unwrap();
+	map.insert(2, 'b').unwrap();
+
+	assert_eq!(map.insert(3, 'c'), Err((3, 'c')));
+	assert_eq!(map.len(), 2);
+	assert!(!map.contains_first_key(&3));
+
+	// Displacing inserts don't grow the map, so they're fine even when full
+	assert_eq!(map.insert(1, 'c'), Ok(Some((1, 'a'))));
+	assert_eq!(map.insert(4, 'b'), Ok(Some((2, 'b'))));
+
+	map.remove_by_first(&1);
+	assert_eq!(map.insert(3, 'z'), Ok(None));
+}
+
+#[test]
+fn zero_capacity_rejects_everything() {
+	let mut map = BidirArrayMap::<u8, u8, 0>::new();
+	assert!(map.is_full());
+	assert_eq!(map.insert(1, 2), Err((1, 2)));
+}
+
+#[test]
+fn semantics_match_heap_map() {
+	let ops = [(1, 'a'), (2, 'b'), (3, 'c'), (1, 'b'), (4, 'd'), (3, 'a'), (5, 'd')];
+
+	let mut heap = BidirMap::new();
+	let mut array = BidirArrayMap::<_, _, 8>::new();
+	for &(kv1, kv2) in &ops {
+		assert_eq!(array.insert(kv1, kv2), Ok(heap.insert(kv1, kv2)));
+		assert!(array.iter().eq(heap.iter()));
+	}
+
+	assert_eq!(array.remove_by_first(&5), heap.remove_by_first(&5));
+	assert_eq!(array.remove_by_second(&'a'), heap.remove_by_second(&'a'));
+	assert!(array.iter().eq(heap.iter()));
+	assert!(array.into_iter().eq(heap.into_iter()));
+}
@@ -0,0 +1,86 @@
+extern crate bidir_map;
+
+use bidir_map::{AuxBidirMap, BidirMap};
+
+
+fn triples(map: &AuxBidirMap<u32, &'static str, Vec<u32>>) -> Vec<(u32, &'static str, Vec<u32>)> {
+	map.iter().map(|(kv1, kv2, aux)| (*kv1, *kv2, aux.clone())).collect()
+}
+
+#[test]
+fn payload_follows_pair() {
+	let mut map = AuxBidirMap::new();
+	map.insert(1, "one", vec![1]);
+	map.insert(2, "two", vec![2]);
+	map.insert(3, "three", vec![3]);
+
+	// Removing the first pair moves the last into its place
+	assert_eq!(map.remove_by_first(&1), Some((1, "one", vec![1])));
+	assert_eq!(map.get_with_aux_by_first(&3), Some((&"three", &vec![3])));
+	assert_eq!(map.get_with_aux_by_second(&"two"), Some((&2, &vec![2])));
+
+	map.aux_mut_by_first(&2).unwrap().push(20);
+	map.aux_mut_by_second(&"three").unwrap().push(30);
+	assert_eq!(triples(&map), [(3, "three", vec![3, 30]), (2, "two", vec![2, 20])]);
+
+	assert_eq!(map.remove_by_second(&"three"), Some((3, "three", vec![3, 30])));
+	assert_eq!(map.remove_by_second(&"three"), None);
+	assert_eq!(map.aux_mut_by_first(&3), None);
+	assert_eq!(triples(&map), [(2, "two", vec![2, 20])]);
+}
+
+#[test]
+fn displacement_returns_payload() {
+	let mut map = AuxBidirMap::new();
+	map.insert(1, "a", vec![10]);
+	map.insert(2, "b", vec![20]);
+	map.insert(3, "c", vec![30]);
+
+	// Displaces both (1, "a") and (2, "b"); the former is returned
+	assert_eq!(map.insert(1, "b", vec![12]), Some((1, "a", vec![10])));
+	assert_eq!(map.len(), 2);
+	assert_eq!(map.get_with_aux_by_first(&1), Some((&"b", &vec![12])));
+	assert_eq!(map.get_with_aux_by_first(&3), Some((&"c", &vec![30])));
+	assert_eq!(map.get_by_first(&2), None);
+
+	// Displaces only by the second K/V
+	assert_eq!(map.insert(4, "c", vec![40]), Some((3, "c", vec![30])));
+	assert_eq!(triples(&map), [(1, "b", vec![12]), (4, "c", vec![40])]);
+}
+
+#[test]
+fn same_order_as_bidir_map() {
+	let mut aux = AuxBidirMap::new();
+	let mut plain = BidirMap::new();
+	let mut state = 0x2545_f491u32;
+	for step in 0..500 {
+		state ^= state << 13;
+		state ^= state >> 7;
+		state ^= state << 17;
+		let kv1 = state % 17;
+		let kv2 = (state >> 8) % 13;
+		if state.is_multiple_of(5) {
+			assert_eq!(aux.remove_by_first(&kv1).map(|(kv1, kv2, _)| (kv1, kv2)), plain.remove_by_first(&kv1));
+		} else {
+			assert_eq!(aux.insert(kv1, kv2, (kv1, kv2, step)).map(|(kv1, kv2, _)| (kv1, kv2)), plain.insert(kv1, kv2));
+		}
+
+		assert_eq!(aux.inner(), &plain);
+		assert!(aux.iter().all(|(kv1, kv2, &(aux1, aux2, _))| (*kv1, *kv2) == (aux1, aux2)));
+	}
+}
+
+#[test]
+fn from_bidir_map_and_into_parts() {
+	let mut plain = BidirMap::new();
+	plain.insert('x', 1);
+	plain.insert('y', 2);
+
+	let mut map: AuxBidirMap<_, _, u32> = plain.clone().into();
+	*map.aux_mut_by_first(&'y').unwrap() += 5;
+	assert_eq!(map.get_with_aux_by_second(&2), Some((&'y', &5)));
+
+	let (pairs, aux) = map.into_parts();
+	assert_eq!(pairs, plain);
+	assert_eq!(aux, [0, 5]);
+}
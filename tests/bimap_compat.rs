@@ -0,0 +1,30 @@
+extern crate bidir_map;
+
+use bidir_map::{BiMap, BidirMap};
+
+
+#[test]
+fn aliases() {
+	let mut map: BiMap<&str, u32> = BidirMap::new();
+	assert_eq!(map.insert_no_overwrite("a", 1), Ok(()));
+	assert_eq!(map.insert_no_overwrite("b", 2), Ok(()));
+	assert_eq!(map.insert_no_overwrite("c", 3), Ok(()));
+	assert_eq!(map.insert_no_overwrite("a", 4), Err(("a", 4)));
+	assert_eq!(map.insert_no_overwrite("d", 2), Err(("d", 2)));
+	assert_eq!(map.len(), 3);
+
+	assert_eq!(map.get_by_left(&"b"), Some(&2));
+	assert_eq!(map.get_by_left(&"z"), None);
+	assert_eq!(map.get_by_right(&3), Some(&"c"));
+	assert_eq!(map.get_by_right(&9), None);
+	assert!(map.contains_left(&"a") && !map.contains_left(&"d"));
+	assert!(map.contains_right(&1) && !map.contains_right(&4));
+
+	assert_eq!(map.left_values().cloned().collect::<Vec<_>>(), ["a", "b", "c"]);
+	assert_eq!(map.right_values().cloned().collect::<Vec<_>>(), [1, 2, 3]);
+
+	assert_eq!(map.remove_by_left(&"a"), Some(("a", 1)));
+	assert_eq!(map.remove_by_right(&3), Some(("c", 3)));
+	assert_eq!(map.remove_by_right(&3), None);
+	assert_eq!(map.iter().collect::<Vec<_>>(), [&("b", 2)]);
+}
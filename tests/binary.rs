@@ -0,0 +1,77 @@
+extern crate bidir_map;
+
+use std::io::ErrorKind;
+use bidir_map::BidirMap;
+
+
+fn encode<Kv1: PartialEq + bidir_map::binary::BinaryKey, Kv2: PartialEq + bidir_map::binary::BinaryKey>(map: &BidirMap<Kv1, Kv2>) -> Vec<u8> {
+	let mut bytes = Vec::new();
+	map.write_to(&mut bytes).unwrap();
+	bytes
+}
+
+#[test]
+fn round_trip() {
+	let names: BidirMap<u32, String> = (0..100).map(|i| (i * 3, format!("name {}", i))).collect();
+	let decoded = BidirMap::<u32, String>::read_from(&encode(&names)[..]).unwrap();
+	assert_eq!(decoded.iter().collect::<Vec<_>>(), names.iter().collect::<Vec<_>>());
+
+	let blobs: BidirMap<Vec<u8>, i128> = vec![(vec![], -1), (vec![0, 255], i128::MIN), (vec![7; 1000], i128::MAX)].into_iter().collect();
+	assert_eq!(BidirMap::read_from(&encode(&blobs)[..]).unwrap(), blobs);
+
+	let empty = BidirMap::<u8, u8>::new();
+	assert_eq!(encode(&empty), b"BDMP\x01\0\0\0\0\0\0\0\0");
+	assert_eq!(BidirMap::<u8, u8>::read_from(&encode(&empty)[..]).unwrap(), empty);
+}
+
+#[test]
+fn layout() {
+	let mut map = BidirMap::new();
+	map.insert(0x0102u16, "hi".to_string());
+	assert_eq!(encode(&map), b"BDMP\x01\x01\0\0\0\0\0\0\0\x02\x01\x02\0\0\0\0\0\0\0hi");
+}
+
+#[test]
+fn truncated() {
+	let map: BidirMap<String, u64> = (0..10).map(|i| (i.to_string().repeat(i as usize + 1), i)).collect();
+	let bytes = encode(&map);
+	for len in 0..bytes.len() {
+		let err = BidirMap::<String, u64>::read_from(&bytes[..len]).unwrap_err();
+		assert_eq!(err.kind(), ErrorKind::UnexpectedEof, "cut at {}", len);
+	}
+}
+
+#[test]
+fn corrupted() {
+	let mut map = BidirMap::new();
+	map.insert(1u8, "a".to_string());
+	map.insert(2u8, "b".to_string());
+	let bytes = encode(&map);
+
+	let mut bad_magic = bytes.clone();
+	bad_magic[0] = b'X';
+	assert_eq!(BidirMap::<u8, String>::read_from(&bad_magic[..]).unwrap_err().kind(), ErrorKind::InvalidData);
+
+	let mut bad_version = bytes.clone();
+	bad_version[4] = 2;
+	assert_eq!(BidirMap::<u8, String>::read_from(&bad_version[..]).unwrap_err().kind(), ErrorKind::InvalidData);
+
+	// Second pair's first K/V changed to 1
+	let mut duplicate = bytes.clone();
+	let second_pair = 13 + 1 + 8 + 1;
+	assert_eq!(duplicate[second_pair], 2);
+	duplicate[second_pair] = 1;
+	assert_eq!(BidirMap::<u8, String>::read_from(&duplicate[..]).unwrap_err().kind(), ErrorKind::InvalidData);
+
+	let mut not_utf8 = bytes.clone();
+	not_utf8[13 + 1 + 8] = 0xff;
+	assert_eq!(BidirMap::<u8, String>::read_from(&not_utf8[..]).unwrap_err().kind(), ErrorKind::InvalidData);
+
+	// A huge count or length runs out of input rather than memory
+	let mut huge_count = bytes.clone();
+	huge_count[5..13].copy_from_slice(&u64::MAX.to_le_bytes());
+	assert_eq!(BidirMap::<u8, String>::read_from(&huge_count[..]).unwrap_err().kind(), ErrorKind::UnexpectedEof);
+	let mut huge_len = bytes.clone();
+	huge_len[14..22].copy_from_slice(&u64::MAX.to_le_bytes());
+	assert_eq!(BidirMap::<u8, String>::read_from(&huge_len[..]).unwrap_err().kind(), ErrorKind::UnexpectedEof);
+}
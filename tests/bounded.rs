@@ -0,0 +1,70 @@
+extern crate bidir_map;
+
+use bidir_map::{BidirMap, BoundedBidirMap};
+
+
+#[test]
+fn evicts_in_insertion_order() {
+	let mut map = BoundedBidirMap::with_max_len(3);
+	for i in 0..3 {
+		assert_eq!(map.insert(i, i * 10), None);
+	}
+	assert!(map.is_full());
+
+	let evicted: Vec<_> = (3..8).map(|i| map.insert(i, i * 10).unwrap()).collect();
+	assert_eq!(evicted, [(0, 0), (1, 10), (2, 20), (3, 30), (4, 40)]);
+	assert_eq!(map.iter().cloned().collect::<Vec<_>>(), [(5, 50), (6, 60), (7, 70)]);
+}
+
+#[test]
+fn updates_dont_grow() {
+	let mut map = BoundedBidirMap::with_max_len(3);
+	map.insert("a", 1);
+	map.insert("b", 2);
+	map.insert("c", 3);
+
+	assert_eq!(map.insert("a", 10), Some(("a", 1)));
+	assert_eq!(map.insert("z", 2), Some(("b", 2)));
+	assert_eq!(map.len(), 3);
+
+	// The replacements count as newest
+	assert_eq!(map.oldest(), Some(&("c", 3)));
+	assert_eq!(map.insert("d", 4), Some(("c", 3)));
+	assert_eq!(map.insert("e", 5), Some(("a", 10)));
+	assert_eq!(map.insert("f", 6), Some(("z", 2)));
+}
+
+#[test]
+fn removal_keeps_age_order() {
+	let mut map = BoundedBidirMap::with_max_len(4);
+	for i in 0..4 {
+		map.insert(i, i * 10);
+	}
+	assert_eq!(map.remove_by_first(&0), Some((0, 0)));
+	assert_eq!(map.remove_by_second(&20), Some((2, 20)));
+
+	// Not full, so nothing's evicted until the gap is refilled
+	assert_eq!(map.insert(4, 40), None);
+	assert_eq!(map.insert(5, 50), None);
+	assert_eq!(map.insert(6, 60), Some((1, 10)));
+	assert_eq!(map.insert(7, 70), Some((3, 30)));
+	assert_eq!(map.pop_oldest(), Some((4, 40)));
+	assert_eq!(BidirMap::from(map).iter().cloned().collect::<Vec<_>>(), [(5, 50), (6, 60), (7, 70)]);
+}
+
+#[test]
+fn lookups() {
+	let mut map = BoundedBidirMap::with_max_len(1);
+	map.insert(1, "a");
+	assert_eq!(map.get_by_first(&1), Some(&"a"));
+	assert_eq!(map.get_by_second(&"a"), Some(&1));
+	assert_eq!(map.insert(2, "b"), Some((1, "a")));
+	assert!(!map.contains_first_key(&1));
+	assert!(map.contains_second_key(&"b"));
+}
+
+#[test]
+#[should_panic]
+fn zero_max_len() {
+	BoundedBidirMap::<u8, u8>::with_max_len(0);
+}
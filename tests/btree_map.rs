@@ -0,0 +1,94 @@
+extern crate bidir_map;
+
+use bidir_map::{BidirBTreeMap, BidirMap};
+
+
+fn assert_consistent(map: &BidirBTreeMap<u32, i64>) {
+	let firsts: Vec<_> = map.first_col().cloned().collect();
+	let mut sorted = firsts.clone();
+	sorted.sort();
+	assert_eq!(firsts, sorted);
+
+	let seconds: Vec<_> = map.second_col().cloned().collect();
+	let mut sorted = seconds.clone();
+	sorted.sort();
+	assert_eq!(seconds, sorted);
+
+	assert_eq!(seconds.len(), map.len());
+	for (kv1, kv2) in map {
+		assert_eq!(map.get_by_first(kv1), Some(kv2));
+		assert_eq!(map.get_by_second(kv2), Some(kv1));
+	}
+}
+
+#[test]
+fn insert_displaces_like_bidir_map() {
+	let pairs = [(5, 1), (2, 7), (5, 7), (9, -3), (9, 1), (4, -3), (1, 0)];
+
+	let mut tree = BidirBTreeMap::new();
+	let mut vec = BidirMap::new();
+	for &(kv1, kv2) in &pairs {
+		assert_eq!(tree.insert(kv1, kv2), vec.insert(kv1, kv2));
+		assert_consistent(&tree);
+	}
+
+	let mut from_vec: Vec<_> = vec.into_iter().collect();
+	from_vec.sort();
+	let from_tree: Vec<_> = tree.into_iter().collect();
+	assert_eq!(from_tree, from_vec);
+}
+
+#[test]
+fn iteration_is_sorted() {
+	let map: BidirBTreeMap<u32, i64> = [7, 3, 9, 1, 5].iter().map(|&i| (i, -(i as i64))).collect();
+	assert_consistent(&map);
+
+	let pairs: Vec<_> = map.iter().map(|(&kv1, &kv2)| (kv1, kv2)).collect();
+	assert_eq!(pairs, [(1, -1), (3, -3), (5, -5), (7, -7), (9, -9)]);
+	let rev: Vec<_> = map.iter().rev().map(|(&kv1, _)| kv1).collect();
+	assert_eq!(rev, [9, 7, 5, 3, 1]);
+	assert_eq!(map.second_col().next(), Some(&-9));
+}
+
+#[test]
+fn ranges() {
+	let map: BidirBTreeMap<u32, i64> = (0..10).map(|i| (i, (i as i64) * (i as i64))).collect();
+
+	let by_first: Vec<_> = map.range_by_first(3..=5).map(|(&kv1, _)| kv1).collect();
+	assert_eq!(by_first, [3, 4, 5]);
+	let by_second: Vec<_> = map.range_by_second(10..50).map(|(&kv1, _)| kv1).collect();
+	assert_eq!(by_second, [4, 5, 6, 7]);
+	let back: Vec<_> = map.range_by_second(..10).rev().map(|(_, &kv2)| kv2).collect();
+	assert_eq!(back, [9, 4, 1, 0]);
+	assert_eq!(map.range_by_first(20..).next(), None);
+}
+
+#[test]
+fn pops_keep_both_sides_in_sync() {
+	let mut map: BidirBTreeMap<u32, i64> = (0..5).map(|i| (i, 10 - i as i64)).collect();
+
+	assert_eq!(map.first_pair(), Some((&0, &10)));
+	assert_eq!(map.last_pair(), Some((&4, &6)));
+	assert_eq!(map.pop_first(), Some((0, 10)));
+	assert_eq!(map.pop_last(), Some((4, 6)));
+	assert_eq!(map.remove_by_second(&8), Some((2, 8)));
+	assert!(!map.contains_second_key(&10));
+	assert!(!map.contains_second_key(&6));
+	assert_eq!(map.len(), 2);
+	assert_consistent(&map);
+}
+
+#[test]
+fn converts_to_and_from_bidir_map() {
+	let mut map = BidirMap::new();
+	map.insert(3, 30);
+	map.insert(1, 10);
+	map.insert(2, 20);
+
+	let tree = BidirBTreeMap::from(map);
+	assert_consistent(&tree);
+
+	let back = BidirMap::from(tree);
+	assert_eq!(back.first_col().cloned().collect::<Vec<_>>(), [1, 2, 3]);
+	assert_eq!(back.get_by_second(&20), Some(&2));
+}
@@ -0,0 +1,73 @@
+extern crate bidir_map;
+
+mod common;
+
+use bidir_map::{BidirMap, BidirMapBuilder, BuildError, Column, DuplicateKeyError, OnDuplicate};
+use common::pairs;
+
+
+fn input() -> Vec<(u32, char)> {
+	vec![(3, 'c'), (1, 'a'), (2, 'a'), (3, 'd'), (4, 'b')]
+}
+
+
+#[test]
+fn error_policy() {
+	for builder in [BidirMap::builder(), BidirMap::builder().indexed()] {
+		assert_eq!(builder.build_from_iter(input()),
+		           Err(BuildError::Duplicate(DuplicateKeyError{column: Column::Second, index: Some(2)})));
+	}
+
+	let res = BidirMap::builder().build_from_iter(vec![(1, 'a'), (1, 'a')]);
+	assert_eq!(res, Err(BuildError::Duplicate(DuplicateKeyError{column: Column::First, index: Some(1)})));
+
+	let map = BidirMap::builder().build_from_iter(vec![(2, 'b'), (1, 'a')]).unwrap();
+	assert_eq!(pairs(&map), [(2, 'b'), (1, 'a')]);
+}
+
+#[test]
+fn first_wins() {
+	for builder in [BidirMap::builder(), BidirMap::builder().indexed()] {
+		let map = builder.on_duplicate(OnDuplicate::FirstWins).build_from_iter(input()).unwrap();
+		assert_eq!(pairs(&map), [(3, 'c'), (1, 'a'), (4, 'b')]);
+	}
+}
+
+#[test]
+fn last_wins() {
+	for builder in [BidirMap::builder(), BidirMap::builder().indexed()] {
+		let map = builder.on_duplicate(OnDuplicate::LastWins).build_from_iter(input()).unwrap();
+
+		let mut expected = BidirMap::new();
+		for (kv1, kv2) in input() {
+			expected.insert(kv1, kv2);
+		}
+		assert_eq!(pairs(&map), pairs(&expected));
+	}
+}
+
+#[test]
+fn sorting() {
+	let map = BidirMap::builder().on_duplicate(OnDuplicate::LastWins).sorted_by_first().indexed().build_from_iter(input()).unwrap();
+	assert_eq!(pairs(&map), [(2, 'a'), (3, 'd'), (4, 'b')]);
+	assert_eq!(map.get_by_second_indexed(&'d'), Some(&3));
+
+	let map = BidirMap::builder().on_duplicate(OnDuplicate::FirstWins).sorted_by_second().build_from_iter(input()).unwrap();
+	assert_eq!(pairs(&map), [(1, 'a'), (4, 'b'), (3, 'c')]);
+}
+
+#[test]
+fn conflicting_order() {
+	let builder: BidirMapBuilder<u32, char> = BidirMap::builder().sorted_by_first().sorted_by_second();
+	assert_eq!(builder.build(), Err(BuildError::ConflictingOrder));
+
+	let res = BidirMap::builder().sorted_by_second().on_duplicate(OnDuplicate::LastWins).sorted_by_first().build_from_iter(input());
+	assert_eq!(res, Err(BuildError::ConflictingOrder));
+}
+
+#[test]
+fn empty() {
+	let map: BidirMap<u32, char> = BidirMapBuilder::new().capacity(16).indexed().build().unwrap();
+	assert!(map.is_empty());
+	assert!(map.has_index());
+}
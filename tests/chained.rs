@@ -0,0 +1,74 @@
+extern crate bidir_map;
+
+use bidir_map::{BidirMap, ChainedBidirMap};
+
+
+fn layer(pairs: &[(u32, &'static str)]) -> BidirMap<u32, &'static str> {
+	pairs.iter().cloned().collect()
+}
+
+#[test]
+fn shadowing_by_first() {
+	let user = layer(&[(1, "vim")]);
+	let defaults = layer(&[(1, "nano"), (2, "emacs")]);
+	let chained = ChainedBidirMap::new(vec![&user, &defaults]);
+
+	assert_eq!(chained.get_by_first(&1), Some(&"vim"));
+	assert_eq!(chained.get_by_second(&"vim"), Some(&1));
+	assert_eq!(chained.get_by_second(&"nano"), None);
+	assert!(!chained.contains_second_key(&"nano"));
+	assert_eq!(chained.iter().cloned().collect::<Vec<_>>(), [(1, "vim"), (2, "emacs")]);
+}
+
+#[test]
+fn shadowing_by_second() {
+	let user = layer(&[(1, "vim")]);
+	let defaults = layer(&[(3, "vim"), (2, "emacs")]);
+	let chained = ChainedBidirMap::new(vec![&user, &defaults]);
+
+	assert_eq!(chained.get_by_second(&"vim"), Some(&1));
+	assert_eq!(chained.get_by_first(&3), None);
+	assert!(!chained.contains_first_key(&3));
+	assert_eq!(chained.iter().cloned().collect::<Vec<_>>(), [(1, "vim"), (2, "emacs")]);
+}
+
+#[test]
+fn bottom_layer_only() {
+	let user = layer(&[(1, "vim")]);
+	let workspace = layer(&[(2, "emacs")]);
+	let defaults = layer(&[(1, "nano"), (3, "ed"), (4, "emacs")]);
+	let mut chained = ChainedBidirMap::new(vec![&user]);
+	assert_eq!(chained.get_by_first(&3), None);
+
+	chained.push_layer(&workspace);
+	chained.push_layer(&defaults);
+	assert_eq!(chained.layers().len(), 3);
+	assert_eq!(chained.get_by_first(&3), Some(&"ed"));
+	assert_eq!(chained.get_by_second(&"ed"), Some(&3));
+	assert_eq!(chained.get_by_first(&4), None);
+	assert_eq!(chained.iter().cloned().collect::<Vec<_>>(), [(1, "vim"), (2, "emacs"), (3, "ed")]);
+}
+
+#[test]
+fn flatten_agrees_with_lookups() {
+	let mut layers = [BidirMap::new(), BidirMap::new(), BidirMap::new()];
+	let mut state = 0x9e37_79b9u32;
+	for _ in 0..60 {
+		state ^= state << 13;
+		state ^= state >> 7;
+		state ^= state << 17;
+		layers[(state % 3) as usize].insert((state >> 4) % 12, (state >> 12) % 12);
+	}
+	let chained = ChainedBidirMap::new(layers.iter().collect());
+	let flat = chained.flatten();
+
+	assert_eq!(flat.len(), chained.iter().count());
+	for key in 0..12 {
+		assert_eq!(chained.get_by_first(&key), flat.get_by_first(&key));
+		assert_eq!(chained.get_by_second(&key), flat.get_by_second(&key));
+	}
+	for (kv1, kv2) in &chained {
+		let origin = layers.iter().position(|l| l.contains_first_key(kv1) || l.contains_second_key(kv2)).unwrap();
+		assert_eq!(layers[origin].get_by_first(kv1), Some(kv2));
+	}
+}
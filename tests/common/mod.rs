@@ -0,0 +1,81 @@
+//! Helpers shared by the integration tests; each test crate uses only some of them.
+
+#![allow(dead_code)]
+
+use bidir_map::BidirMap;
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+
+
+/// Deterministic xorshift so the "random" data is the same on every run
+pub fn pseudo_random(seed: &mut u64) -> u64 {
+	*seed ^= *seed << 13;
+	*seed ^= *seed >> 7;
+	*seed ^= *seed << 17;
+	*seed
+}
+
+/// Random numbers below a bound, drawn by `pseudo_random()`
+pub struct Rng(pub u64);
+
+impl Rng {
+	pub fn below(&mut self, n: u64) -> u64 {
+		pseudo_random(&mut self.0) % n
+	}
+}
+
+/// The map's pairs, in iteration order
+pub fn pairs<Kv1: Clone, Kv2: Clone>(map: &BidirMap<Kv1, Kv2>) -> Vec<(Kv1, Kv2)> {
+	map.iter().cloned().collect()
+}
+
+/// The pairs of a map of strings, borrowed, in iteration order
+pub fn str_pairs(map: &BidirMap<String, String>) -> Vec<(&str, &str)> {
+	map.iter().map(|kvs| (&kvs.0[..], &kvs.1[..])).collect()
+}
+
+
+thread_local!(static COMPARISONS: Cell<usize> = const { Cell::new(0) });
+
+/// A key that counts how many times it's compared
+#[derive(Clone, Copy, Debug, Eq)]
+pub struct Counted(pub u32);
+
+impl PartialEq for Counted {
+	fn eq(&self, other: &Counted) -> bool {
+		count_comparison();
+		self.0 == other.0
+	}
+}
+
+impl PartialOrd for Counted {
+	fn partial_cmp(&self, other: &Counted) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl Ord for Counted {
+	fn cmp(&self, other: &Counted) -> Ordering {
+		count_comparison();
+		self.0.cmp(&other.0)
+	}
+}
+
+impl Hash for Counted {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.0.hash(state)
+	}
+}
+
+/// Counts one comparison, for other counting keys' `PartialEq`s
+pub fn count_comparison() {
+	COMPARISONS.with(|c| c.set(c.get() + 1));
+}
+
+/// The number of comparisons of counting keys `f` makes
+pub fn comparisons<F: FnOnce()>(f: F) -> usize {
+	COMPARISONS.with(|c| c.set(0));
+	f();
+	COMPARISONS.with(|c| c.get())
+}
@@ -0,0 +1,68 @@
+extern crate bidir_map;
+
+use bidir_map::{BidirMap, BidirMapWith};
+use bidir_map::comparator::{CaseInsensitive, Equality, Natural};
+
+
+fn users() -> BidirMapWith<String, u32, CaseInsensitive, Natural> {
+	let mut map = BidirMapWith::with_comparators(CaseInsensitive, Natural);
+	map.insert("foo".to_string(), 1);
+	map.insert("Bar".to_string(), 2);
+	map
+}
+
+#[test]
+fn case_insensitive_hits() {
+	let mut map = users();
+	assert_eq!(map.get_by_first("FOO"), Some(&1));
+	assert_eq!(map.get_by_first("bar"), Some(&2));
+	assert!(map.contains_first_key("bAR"));
+	assert!(!map.contains_first_key("baz"));
+	assert_eq!(map.remove_by_first("BAR"), Some(("Bar".to_string(), 2)));
+	assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn insert_conflicts() {
+	let mut map = users();
+	assert_eq!(map.insert("FOO".to_string(), 3), Some(("foo".to_string(), 1)));
+	assert_eq!(map.len(), 2);
+	assert_eq!(map.get_by_first("foo"), Some(&3));
+	assert_eq!(map.get_by_second(&1), None);
+}
+
+#[test]
+fn original_case_kept() {
+	let map = users();
+	assert_eq!(map.get_by_second(&1).map(|k| &k[..]), Some("foo"));
+	assert_eq!(map.get_by_second(&2).map(|k| &k[..]), Some("Bar"));
+
+	let plain = BidirMap::from(map.clone());
+	assert_eq!(plain.get_by_first("Bar"), Some(&2));
+	assert_eq!(plain.get_by_first("bar"), None);
+}
+
+/// Floats equal within a tolerance, deliberately named rather than a closure
+#[derive(Clone)]
+struct Within(f64);
+
+impl Equality<f64> for Within {
+	fn eq(&self, lhs: &f64, rhs: &f64) -> bool {
+		(lhs - rhs).abs() <= self.0
+	}
+}
+
+#[test]
+fn custom_relations_on_both_columns() {
+	let mut map = BidirMapWith::with_comparators(Within(0.01), |l: &&str, r: &&str| l.len() == r.len());
+	map.insert(1.0, "one");
+	map.insert(2.0, "three");
+
+	assert_eq!(map.get_by_first(&1.005), Some(&"one"));
+	assert_eq!(map.get_by_second(&"abc"), Some(&1.0));
+	assert_eq!(map.insert(2.001, "four"), Some((2.0, "three")));
+	assert_eq!(map.get_by_second(&"xxxx"), Some(&2.001));
+
+	let cloned = map.clone();
+	assert_eq!(cloned.iter().cloned().collect::<Vec<_>>(), map.iter().cloned().collect::<Vec<_>>());
+}
@@ -0,0 +1,88 @@
+extern crate bidir_map;
+
+mod common;
+
+use bidir_map::ConcurrentBidirMap;
+use std::sync::Arc;
+use std::thread;
+use common::Rng;
+
+
+#[test]
+fn single_threaded_semantics() {
+	let map = ConcurrentBidirMap::with_shards(4);
+	assert_eq!(map.insert(1, 'a'), None);
+	assert_eq!(map.insert(2, 'b'), None);
+	assert_eq!(map.insert(1, 'b'), Some((1, 'a')));
+	assert_eq!(map.len(), 1);
+	assert_eq!(map.get_by_first(&1), Some('b'));
+	assert_eq!(map.get_by_second(&'a'), None);
+	assert!(!map.contains_first_key(&2));
+
+	assert_eq!(map.remove_by_second(&'b'), Some((1, 'b')));
+	assert_eq!(map.remove_by_first(&1), None);
+	assert!(map.is_empty());
+}
+
+#[test]
+fn matches_bidir_map() {
+	let mut rng = Rng(0x9e3779b97f4a7c15);
+	let map = ConcurrentBidirMap::with_shards(3);
+	let mut model = bidir_map::BidirMap::new();
+	for _ in 0..2000 {
+		let (kv1, kv2) = (rng.below(20), rng.below(20));
+		match rng.below(3) {
+			0 => assert_eq!(map.remove_by_first(&kv1), model.remove_by_first(&kv1)),
+			1 => assert_eq!(map.remove_by_second(&kv2), model.remove_by_second(&kv2)),
+			_ => assert_eq!(map.insert(kv1, kv2), model.insert(kv1, kv2)),
+		}
+		assert_eq!(map.len(), model.len());
+	}
+	let snapshot = map.snapshot();
+	assert_eq!(snapshot.len(), model.len());
+	assert!(model.iter().all(|kvs| snapshot.get_by_first(&kvs.0) == Some(&kvs.1)));
+}
+
+#[test]
+fn stress() {
+	let map = Arc::new(ConcurrentBidirMap::with_shards(4));
+	let writers: Vec<_> = (0..4u64).map(|id| {
+		let map = map.clone();
+		thread::spawn(move || {
+			let mut rng = Rng((id + 1).wrapping_mul(0x9e3779b97f4a7c15));
+			for _ in 0..5000 {
+				let (kv1, kv2) = (rng.below(32), rng.below(32));
+				match rng.below(4) {
+					0 => { map.remove_by_first(&kv1); }
+					1 => { map.remove_by_second(&kv2); }
+					_ => { map.insert(kv1, kv2); }
+				}
+			}
+		})
+	}).collect();
+	let readers: Vec<_> = (0..2).map(|_| {
+		let map = map.clone();
+		thread::spawn(move || {
+			for _ in 0..200 {
+				// The snapshot checks both directions agree in debug builds
+				let snapshot = map.snapshot();
+				assert_eq!(snapshot.validate(), Ok(()));
+				for kv1 in 0..32u64 {
+					if let Some(kv2) = map.get_by_first(&kv1) {
+						assert!(kv2 < 32);
+					}
+				}
+			}
+		})
+	}).collect();
+
+	for thread in writers.into_iter().chain(readers) {
+		thread.join().unwrap();
+	}
+	let snapshot = map.snapshot();
+	assert_eq!(snapshot.len(), map.len());
+	for kvs in snapshot.iter() {
+		assert_eq!(map.get_by_first(&kvs.0), Some(kvs.1));
+		assert_eq!(map.get_by_second(&kvs.1), Some(kvs.0));
+	}
+}
@@ -0,0 +1,71 @@
+extern crate bidir_map;
+
+use bidir_map::BidirMap;
+use std::collections::HashMap;
+
+
+static CODES: &[&str] = &["de", "fr", "it"];
+static NAMES: &[&str] = &["Germany", "France", "Italy"];
+
+fn countries(n: usize) -> BidirMap<&'static str, &'static str> {
+	CODES.iter().cloned().zip(NAMES.iter().cloned()).chain(Some(("es", "Spain"))).take(n).collect()
+}
+
+#[test]
+fn complete() {
+	let map = countries(3);
+	assert_eq!(map.covers_first(CODES.iter().cloned()), Ok(()));
+	assert_eq!(map.covers_second(NAMES.iter().cloned()), Ok(()));
+	assert!(map.is_bijection_between(CODES, NAMES));
+}
+
+#[test]
+fn incomplete() {
+	let map = countries(1);
+	assert_eq!(map.covers_first(CODES.iter().cloned()), Err(vec!["fr", "it"]));
+	assert_eq!(map.covers_second(NAMES.iter().cloned()), Err(vec!["France", "Italy"]));
+	assert!(!map.is_bijection_between(CODES, NAMES));
+}
+
+#[test]
+fn over_full() {
+	let map = countries(4);
+	assert_eq!(map.covers_first(CODES.iter().cloned()), Ok(()));
+	assert_eq!(map.covers_second(NAMES.iter().cloned()), Ok(()));
+	assert!(!map.is_bijection_between(CODES, NAMES));
+	assert!(map.is_bijection_between(&["es", "it", "fr", "de"], &["Italy", "Spain", "France", "Germany"]));
+
+	// Pairing a first K/V of one domain with a second K/V outside the other
+	assert!(!map.is_bijection_between(&["es", "it", "fr", "de"], NAMES));
+}
+
+#[test]
+fn empty() {
+	let map = countries(0);
+	assert_eq!(map.covers_first(Vec::<&str>::new()), Ok(()));
+	assert!(map.is_bijection_between::<&str, &str>(&[], &[]));
+	assert_eq!(map.covers_first(CODES.iter().cloned()), Err(CODES.to_vec()));
+}
+
+#[test]
+fn inverse() {
+	let map = countries(4);
+	let mut mirror: BidirMap<_, _> = map.iter().map(|&(code, name)| (name, code)).collect();
+	mirror.swap_remove_index(0);
+	mirror.insert("Germany", "de");
+	assert!(map.is_inverse_of(&mirror));
+	assert!(mirror.is_inverse_of(&map));
+	let hash_map: HashMap<_, _> = mirror.iter().cloned().collect();
+	assert!(map.is_inverse_of_hash_map(&hash_map));
+
+	// The same K/Vs, but two of them paired differently
+	let mut remapped = mirror.clone();
+	remapped.insert("France", "it");
+	remapped.insert("Italy", "fr");
+	assert!(!map.is_inverse_of(&remapped));
+	assert!(!map.is_inverse_of_hash_map(&remapped.iter().cloned().collect::<HashMap<_, _>>()));
+
+	assert!(!countries(3).is_inverse_of(&mirror));
+	assert!(!map.is_inverse_of(&countries(3).iter().map(|&(code, name)| (name, code)).collect()));
+	assert!(!countries(3).is_inverse_of_hash_map(&hash_map));
+}
@@ -0,0 +1,111 @@
+extern crate bidir_map;
+
+use bidir_map::{BidirMap, CowBidirMap, DuplicatePolicy, OnDuplicate, ShrinkPolicy};
+use std::cell::Cell;
+
+
+thread_local! {
+	static CLONES: Cell<usize> = const { Cell::new(0) };
+}
+
+/// A K/V counting its clones, to tell when the map is copied
+#[derive(Debug, PartialEq)]
+struct Counted(u32);
+
+impl Clone for Counted {
+	fn clone(&self) -> Self {
+		CLONES.with(|clones| clones.set(clones.get() + 1));
+		Counted(self.0)
+	}
+}
+
+fn clones() -> usize {
+	CLONES.with(Cell::get)
+}
+
+fn table() -> CowBidirMap<Counted, &'static str> {
+	vec![(Counted(1), "one"), (Counted(2), "two"), (Counted(3), "three")].into_iter().collect()
+}
+
+
+#[test]
+fn clone_is_shallow() {
+	let map = table();
+	let before = clones();
+	let copies: Vec<_> = (0..10).map(|_| map.clone()).collect();
+	assert_eq!(clones(), before);
+	assert!(copies.iter().all(|copy| copy.ptr_eq(&map) && copy.get_by_second(&"two") == Some(&Counted(2))));
+}
+
+#[test]
+fn mutating_clone() {
+	let map = table();
+	let mut copy = map.clone();
+	let before = clones();
+	assert_eq!(copy.remove_by_second(&"two"), Some((Counted(2), "two")));
+	assert_eq!(clones(), before + 3);
+	assert!(!copy.ptr_eq(&map));
+	*copy.make_mut().get_mut_by_first(&Counted(1)).unwrap() = "uno";
+
+	assert_eq!(map.len(), 3);
+	assert_eq!(map.get_by_first(&Counted(1)), Some(&"one"));
+	assert_eq!(copy.len(), 2);
+	assert_eq!(copy.get_by_first(&Counted(1)), Some(&"uno"));
+	assert_ne!(map, copy);
+}
+
+#[test]
+fn unique_not_copied() {
+	let mut map = table();
+	let before = clones();
+	map.insert(Counted(4), "four");
+	map.remove_by_first(&Counted(1));
+	map.extend(vec![(Counted(5), "five")]);
+
+	// Removing absent K/Vs from a shared map doesn't copy it either
+	let snapshot = map.clone();
+	assert_eq!(map.remove_by_first(&Counted(9)), None);
+	assert!(map.ptr_eq(&snapshot));
+	drop(snapshot);
+
+	assert_eq!(map.into_inner().len(), 4);
+	assert_eq!(clones(), before);
+}
+
+#[test]
+fn clear_shared() {
+	let map = table();
+	let mut copy = map.clone();
+	let before = clones();
+	copy.clear();
+	assert_eq!(clones(), before);
+	assert!(copy.is_empty());
+	assert_eq!(map.len(), 3);
+}
+
+#[test]
+fn clear_shared_keeps_settings() {
+	let policy = DuplicatePolicy::both(OnDuplicate::Error);
+	let shrink = ShrinkPolicy::WhenBelow{utilization: 0.25, min_capacity: 4};
+	let mut inner = BidirMap::with_duplicate_policy(policy);
+	inner.set_max_len(2);
+	inner.set_shrink_policy(shrink);
+	inner.build_index();
+	inner.build_sorted_indexes();
+	inner.insert(1, "one");
+
+	let map = CowBidirMap::from(inner);
+	let mut copy = map.clone();
+	copy.clear();
+	assert!(!copy.ptr_eq(&map));
+	assert!(copy.is_empty());
+	assert_eq!(copy.duplicate_policy(), Some(policy));
+	assert_eq!(copy.max_len(), Some(2));
+	assert_eq!(copy.shrink_policy(), shrink);
+	assert!(copy.has_index() && copy.has_sorted_indexes());
+
+	copy.insert(2, "two");
+	assert_eq!(copy.get_by_first_indexed(&2), Some(&"two"));
+	assert_eq!(copy.get_by_second_sorted(&"two"), Some(&2));
+	assert_eq!(map.len(), 1);
+}
@@ -0,0 +1,112 @@
+extern crate bidir_map;
+
+mod common;
+
+use bidir_map::{BidirMap, Column, CsvImportError, OnDuplicate};
+use bidir_map::csv::CsvOptions;
+use common::str_pairs;
+
+
+const COUNTRIES: &str = "code,country\n\
+                         de,Germany\n\
+                         fr,France\n\
+                         \n\
+                         \"it\",\"Italy\"\r\n\
+                         fr,French Republic\n\
+                         at,Austria\n";
+
+fn read(csv: &str, on_duplicate: OnDuplicate) -> Result<BidirMap<String, String>, CsvImportError> {
+	BidirMap::from_csv_reader(csv.as_bytes(), CsvOptions{on_duplicate, ..CsvOptions::default()})
+}
+
+#[test]
+fn duplicate_policies() {
+	match read(COUNTRIES, OnDuplicate::Error) {
+		Err(CsvImportError::Duplicate{line: 6, column: Column::First, earlier_line: 3}) => {}
+		other => panic!("{:?}", other),
+	}
+
+	let first = read(COUNTRIES, OnDuplicate::FirstWins).unwrap();
+	assert_eq!(str_pairs(&first), [("de", "Germany"), ("fr", "France"), ("it", "Italy"), ("at", "Austria")]);
+
+	let last = read(COUNTRIES, OnDuplicate::LastWins).unwrap();
+	assert_eq!(last.get_by_first("fr").map(|c| &c[..]), Some("French Republic"));
+	assert_eq!(last.get_by_second("France"), None);
+	assert_eq!(last.len(), 4);
+}
+
+#[test]
+fn duplicate_second_across_multiline_field() {
+	let csv = "a,\"first\nsecond\"\nb,x\n\"c\nd\",x\n";
+	let options = CsvOptions{has_header: false, ..CsvOptions::default()};
+	match BidirMap::from_csv_reader(csv.as_bytes(), options) {
+		Err(CsvImportError::Duplicate{line: 4, column: Column::Second, earlier_line: 3}) => {}
+		other => panic!("{:?}", other),
+	}
+}
+
+#[test]
+fn malformed() {
+	match read("k,v\na,1\nb\n", OnDuplicate::Error) {
+		Err(CsvImportError::FieldCount{line: 3, fields: 1}) => {}
+		other => panic!("{:?}", other),
+	}
+	match read("k,v\na,1,2\n", OnDuplicate::Error) {
+		Err(CsvImportError::FieldCount{line: 2, fields: 3}) => {}
+		other => panic!("{:?}", other),
+	}
+	match read("k,v\na,1\n\"b,2\n", OnDuplicate::Error) {
+		Err(CsvImportError::UnterminatedQuote{line: 3}) => {}
+		other => panic!("{:?}", other),
+	}
+	match BidirMap::from_csv_reader(&[b'a', b',', 0xff][..], CsvOptions{has_header: false, ..CsvOptions::default()}) {
+		Err(CsvImportError::Io(_)) => {}
+		other => panic!("{:?}", other),
+	}
+	assert_eq!(read("k,v\nb\n", OnDuplicate::Error).unwrap_err().to_string(), "line 2: expected 2 fields, got 1");
+}
+
+#[test]
+fn typed() {
+	let csv = "port\tservice\n22\tssh\n80\thttp\nhttps\t443\n";
+	let options = CsvOptions{delimiter: '\t', ..CsvOptions::default()};
+	match BidirMap::<u16, String>::from_csv_reader_parsed(csv.as_bytes(), options) {
+		Err(CsvImportError::Parse{line: 4, column: Column::First}) => {}
+		other => panic!("{:?}", other),
+	}
+
+	let map = BidirMap::<u16, String>::from_csv_reader_parsed(&csv.as_bytes()[..csv.len() - 10], options).unwrap();
+	assert_eq!(map.get_by_second("http"), Some(&80));
+}
+
+#[test]
+fn round_trip() {
+	let mut map = BidirMap::new();
+	map.insert("plain".to_string(), "text".to_string());
+	map.insert("with;delimiter".to_string(), "with \"quotes\"".to_string());
+	map.insert("multi\nline".to_string(), "crlf\r\n".to_string());
+	map.insert(String::new(), "empty".to_string());
+
+	let mut csv = Vec::new();
+	map.write_csv(&mut csv, ';', Some(("from", "to"))).unwrap();
+	let options = CsvOptions{delimiter: ';', ..CsvOptions::default()};
+	assert_eq!(BidirMap::from_csv_reader(&csv[..], options).unwrap(), map);
+
+	let mut headerless = Vec::new();
+	map.write_csv(&mut headerless, ';', None).unwrap();
+	assert_eq!(BidirMap::from_csv_reader(&headerless[..], CsvOptions{has_header: false, ..options}).unwrap(), map);
+}
+
+#[test]
+fn max_len() {
+	let options = CsvOptions{on_duplicate: OnDuplicate::LastWins, max_len: Some(3), ..CsvOptions::default()};
+	// Line 6 replaces a pair, line 7 would grow the map
+	match BidirMap::from_csv_reader(COUNTRIES.as_bytes(), options) {
+		Err(CsvImportError::Full{line: 7, capacity: 3}) => {}
+		other => panic!("{:?}", other),
+	}
+
+	let map = BidirMap::from_csv_reader(COUNTRIES.as_bytes(), CsvOptions{max_len: Some(4), ..options}).unwrap();
+	assert_eq!(str_pairs(&map), [("de", "Germany"), ("it", "Italy"), ("fr", "French Republic"), ("at", "Austria")]);
+	assert_eq!(map.max_len(), Some(4));
+}
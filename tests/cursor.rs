@@ -0,0 +1,111 @@
+extern crate bidir_map;
+
+mod common;
+
+use bidir_map::BidirMap;
+use common::pairs;
+
+
+#[test]
+fn ends() {
+	let mut empty = BidirMap::<u8, u8>::new();
+	{
+		let mut cursor = empty.cursor_mut();
+		assert_eq!(cursor.current(), None);
+		cursor.move_next();
+		assert_eq!(cursor.index(), None);
+		cursor.move_prev();
+		assert_eq!(cursor.index(), None);
+		assert_eq!(cursor.remove_current(), None);
+
+		assert_eq!(cursor.insert_after(1, 10), None);
+		assert_eq!(cursor.index(), None);
+		cursor.move_next();
+		assert_eq!(cursor.current(), Some((&1, &10)));
+	}
+
+	let mut map: BidirMap<_, _> = vec![(1, 'a'), (2, 'b'), (3, 'c')].into_iter().collect();
+	let mut cursor = map.cursor_mut();
+	assert_eq!(cursor.index(), Some(0));
+	cursor.move_prev();
+	assert_eq!(cursor.current(), None);
+	cursor.move_prev();
+	assert_eq!(cursor.current(), Some((&3, &'c')));
+	cursor.move_next();
+	assert_eq!(cursor.current(), None);
+	cursor.move_next();
+	assert_eq!(cursor.current(), Some((&1, &'a')));
+
+	// Removing the last pair lands on the ghost
+	cursor.move_prev();
+	cursor.move_prev();
+	assert_eq!(cursor.remove_current(), Some((3, 'c')));
+	assert_eq!(cursor.index(), None);
+
+	// Inserting before the ghost appends, after it prepends
+	cursor.insert_before(4, 'd');
+	cursor.insert_after(0, 'z');
+	assert_eq!(cursor.index(), None);
+	assert_eq!(pairs(&map), [(0, 'z'), (1, 'a'), (2, 'b'), (4, 'd')]);
+}
+
+#[test]
+fn conflicts() {
+	let mut map: BidirMap<_, _> = vec![(1, 'a'), (2, 'b'), (3, 'c'), (4, 'd')].into_iter().collect();
+	map.build_index();
+	{
+		let mut cursor = map.cursor_mut();
+		cursor.move_next();
+		cursor.move_next();
+
+		// Colliding with a pair before the cursor, which stays at (3, 'c')
+		assert_eq!(cursor.insert_after(1, 'x'), Some((1, 'a')));
+		assert_eq!(cursor.current(), Some((&3, &'c')));
+		assert_eq!(cursor.index(), Some(1));
+
+		// Colliding with the pairs at the cursor and after it
+		assert_eq!(cursor.insert_before(3, 'd'), Some((3, 'c')));
+		assert_eq!(cursor.current(), Some((&3, &'d')));
+	}
+	assert_eq!(pairs(&map), [(2, 'b'), (3, 'd'), (1, 'x')]);
+	assert_eq!(map.get_by_second_indexed(&'x'), Some(&1));
+	assert_eq!(map.get_by_first_indexed(&3), Some(&'d'));
+	assert_eq!(map.validate(), Ok(()));
+}
+
+#[test]
+fn dedup_pass() {
+	// Merge pairs naming the same word in different case into one lowercase pair, renumbering as we go
+	let mut map: BidirMap<String, u32> = ["apple", "Apple", "pear", "PLUM", "APPLE", "plum", "fig"]
+		.iter().enumerate().map(|(i, w)| (w.to_string(), i as u32)).collect();
+	map.build_index();
+
+	let mut seen = Vec::new();
+	let mut displaced = Vec::new();
+	let mut next_id = 100;
+	{
+		let mut cursor = map.cursor_mut();
+		while let Some((word, _)) = cursor.current() {
+			let lower = word.to_lowercase();
+			if seen.contains(&lower) {
+				cursor.remove_current();
+			} else if *word != lower {
+				// Takes the place of a later lowercase spelling, if any
+				displaced.extend(cursor.insert_after(lower.clone(), next_id));
+				cursor.remove_current();
+				next_id += 1;
+				seen.push(lower);
+				cursor.move_next();
+			} else {
+				seen.push(lower);
+				cursor.move_next();
+			}
+		}
+	}
+
+	assert_eq!(displaced, [("plum".to_string(), 5)]);
+	assert_eq!(pairs(&map), [("apple".to_string(), 0), ("pear".to_string(), 2), ("plum".to_string(), 100), ("fig".to_string(), 6)]);
+	assert_eq!(map.get_by_first_indexed("plum"), Some(&100));
+	assert_eq!(map.get_by_second_indexed(&6).map(|w| &w[..]), Some("fig"));
+	assert_eq!(map.validate(), Ok(()));
+}
@@ -0,0 +1,53 @@
+extern crate bidir_map;
+
+mod common;
+
+use bidir_map::{BidirMap, Column, ParseError, ParseErrorKind};
+use common::str_pairs;
+
+
+#[test]
+fn happy_path() {
+	let map = BidirMap::parse_delimited("old1=new1,old2=new2", ',', '=').unwrap();
+	assert_eq!(str_pairs(&map), [("old1", "new1"), ("old2", "new2")]);
+	assert_eq!(map.to_delimited_string(',', '='), "old1=new1,old2=new2");
+
+	// Only the first K/V separator splits
+	let map = BidirMap::parse_delimited("k=v=w", ',', '=').unwrap();
+	assert_eq!(str_pairs(&map), [("k", "v=w")]);
+
+	assert!(BidirMap::parse_delimited("", ',', '=').unwrap().is_empty());
+	assert_eq!(BidirMap::<u8, u8>::new().to_delimited_string(',', '='), "");
+}
+
+#[test]
+fn trailing_separators() {
+	let map = BidirMap::parse_delimited(",a=1,,b=2,", ',', '=').unwrap();
+	assert_eq!(str_pairs(&map), [("a", "1"), ("b", "2")]);
+
+	let err = BidirMap::parse_delimited("a=1,,b", ',', '=').unwrap_err();
+	assert_eq!(err, ParseError{segment: "b".to_string(), index: 1, kind: ParseErrorKind::MissingSeparator});
+}
+
+#[test]
+fn duplicates() {
+	let err = BidirMap::parse_delimited("a=1,b=2,a=3", ',', '=').unwrap_err();
+	assert_eq!(err, ParseError{segment: "a=3".to_string(), index: 2, kind: ParseErrorKind::Duplicate(Column::First)});
+
+	let err = BidirMap::parse_delimited("a=1,b=1", ',', '=').unwrap_err();
+	assert_eq!(err, ParseError{segment: "b=1".to_string(), index: 1, kind: ParseErrorKind::Duplicate(Column::Second)});
+	assert_eq!(err.to_string(), "segment 1 (\"b=1\") repeats an earlier second K/V");
+}
+
+#[test]
+fn typed() {
+	let map: BidirMap<String, u32> = BidirMap::parse_delimited_typed("x:1;y:2", ';', ':').unwrap();
+	assert_eq!(map.get_by_second(&2).map(|k| &k[..]), Some("y"));
+	assert_eq!(BidirMap::parse_delimited_typed(&map.to_delimited_string(';', ':'), ';', ':'), Ok(map));
+
+	let err = BidirMap::<String, u32>::parse_delimited_typed("x:1;y:two", ';', ':').unwrap_err();
+	assert_eq!(err, ParseError{segment: "y:two".to_string(), index: 1, kind: ParseErrorKind::Invalid(Column::Second)});
+
+	let err = BidirMap::<u8, u32>::parse_delimited_typed("300:1", ';', ':').unwrap_err();
+	assert_eq!(err.kind, ParseErrorKind::Invalid(Column::First));
+}
@@ -0,0 +1,102 @@
+extern crate bidir_map;
+
+mod common;
+
+use bidir_map::{BidirMap, DenseBidirMap};
+use common::pseudo_random;
+
+
+fn pairs(map: &DenseBidirMap<u16, String>) -> Vec<(u16, String)> {
+	map.iter().map(|(kv1, kv2)| (*kv1, kv2.clone())).collect()
+}
+
+#[test]
+fn non_contiguous_keys() {
+	let mut map = DenseBidirMap::new();
+	for &key in &[7u16, 0, 300, 42] {
+		assert_eq!(map.insert(key, format!("#{}", key)), None);
+	}
+
+	assert_eq!(map.len(), 4);
+	assert_eq!(map.key_capacity(), 301);
+	assert_eq!(map.get_by_first(&42).map(String::as_str), Some("#42"));
+	assert_eq!(map.get_by_first(&41), None);
+	assert_eq!(map.get_by_first(&1000), None);
+	assert!(!map.contains_first_key(&299));
+	assert_eq!(pairs(&map), [(7, "#7".to_string()), (0, "#0".to_string()), (300, "#300".to_string()), (42, "#42".to_string())]);
+
+	assert_eq!(map.remove_by_first(&7), Some((7, "#7".to_string())));
+	assert_eq!(map.remove_by_first(&7), None);
+	assert_eq!(map.get_by_first(&42).map(String::as_str), Some("#42"));
+	map.get_mut_by_first(&300).unwrap().push('!');
+	assert_eq!(map.get_by_first(&300).map(String::as_str), Some("#300!"));
+
+	map.clear();
+	assert!(map.is_empty());
+	assert_eq!(map.key_capacity(), 301);
+	assert_eq!(map.get_by_first(&42), None);
+}
+
+#[test]
+fn large_key_grows_slots() {
+	let mut map = DenseBidirMap::with_capacity(2);
+	map.insert(1u32, 'a');
+	assert_eq!(map.key_capacity(), 2);
+	assert!(map.capacity() >= 2);
+
+	map.insert(1 << 20, 'z');
+	assert_eq!(map.key_capacity(), (1 << 20) + 1);
+	assert_eq!(map.get_by_first(&(1 << 20)), Some(&'z'));
+	assert_eq!(map.get_by_first(&1), Some(&'a'));
+
+	map.remove_by_first(&(1 << 20));
+	assert_eq!(map.key_capacity(), (1 << 20) + 1);
+	map.shrink_to_fit();
+	assert_eq!(map.key_capacity(), 2);
+	assert_eq!(map.get_by_second(&'a'), Some(&1));
+
+	map.clear();
+	map.shrink_to_fit();
+	assert_eq!(map.key_capacity(), 0);
+}
+
+#[test]
+fn reverse_lookups() {
+	let mut map: DenseBidirMap<u8, &str> = vec![(3, "c"), (1, "a"), (2, "b")].into_iter().collect();
+	assert_eq!(map.get_by_second(&"a"), Some(&1));
+	assert_eq!(map.get_by_second(&"d"), None);
+	assert!(map.contains_second_key(&"c"));
+
+	// Replacing by the second K/V moves it to another key
+	assert_eq!(map.insert(9, "a"), Some((1, "a")));
+	assert_eq!(map.get_by_second(&"a"), Some(&9));
+	assert_eq!(map.get_by_first(&1), None);
+
+	assert_eq!(map.remove_by_second(&"c"), Some((3, "c")));
+	assert_eq!(map.get_by_first(&3), None);
+	assert_eq!(map.get_by_first(&2), Some(&"b"));
+	assert_eq!(format!("{:?}", map), r#"{9: "a", 2: "b"}"#);
+}
+
+#[test]
+fn same_order_as_bidir_map() {
+	let mut state = 0x0123_4567_89ab_cdefu64;
+	let mut next = move || pseudo_random(&mut state);
+
+	let mut dense = DenseBidirMap::new();
+	let mut map = BidirMap::new();
+	for _ in 0..2000 {
+		let kv1 = (next() % 64) as u16;
+		let kv2 = format!("{}", next() % 64);
+		match next() % 3 {
+			0 => assert_eq!(dense.remove_by_first(&kv1), map.remove_by_first(&kv1)),
+			1 => assert_eq!(dense.remove_by_second(&kv2), map.remove_by_second(&kv2)),
+			_ => assert_eq!(dense.insert(kv1, kv2.clone()), map.insert(kv1, kv2)),
+		}
+	}
+
+	assert_eq!(pairs(&dense), map.iter().cloned().collect::<Vec<_>>());
+	for (kv1, kv2) in map.iter() {
+		assert_eq!(dense.get_by_first(kv1), Some(kv2));
+	}
+}
@@ -0,0 +1,61 @@
+#[macro_use]
+extern crate bidir_map;
+
+use bidir_map::BidirMap;
+use std::panic;
+
+
+fn assertion_message<F: FnOnce() + panic::UnwindSafe>(f: F) -> String {
+	let payload = panic::catch_unwind(f).unwrap_err();
+	payload.downcast_ref::<String>().cloned().unwrap()
+}
+
+
+#[test]
+fn same_pairs_pass() {
+	let map: BidirMap<_, _> = vec![(1, 'a'), (2, 'b'), (3, 'c')].into_iter().collect();
+	let shuffled: BidirMap<_, _> = vec![(3, 'c'), (1, 'a'), (2, 'b')].into_iter().collect();
+	assert_bidir_eq!(map, shuffled);
+	assert_bidir_eq!(map, shuffled, "shuffled {}", "map");
+	assert!(map.diff(&shuffled).is_empty());
+	assert_eq!(map.diff(&shuffled).to_string(), "no differences");
+}
+
+#[test]
+fn categories() {
+	let message = assertion_message(|| {
+		let left: BidirMap<_, _> = vec![(1, "a"), (2, "b"), (3, "c"), (5, "e")].into_iter().collect();
+		let right: BidirMap<_, _> = vec![(1, "x"), (2, "b"), (4, "c"), (6, "f")].into_iter().collect();
+		assert_bidir_eq!(left, right);
+	});
+	assert_eq!(message,
+	           "assertion failed: `left` and `right` BidirMaps differ\n\
+	            only in left:\n    (5, \"e\")\n\
+	            only in right:\n    (6, \"f\")\n\
+	            first K/Vs remapped:\n    1 => \"a\" in left, \"x\" in right\n\
+	            second K/Vs remapped:\n    \"c\" => 3 in left, 4 in right\n");
+}
+
+#[test]
+fn context() {
+	let message = assertion_message(|| {
+		let left: BidirMap<_, _> = vec![(1, 'a')].into_iter().collect();
+		let right = BidirMap::new();
+		assert_bidir_eq!(left, right, "after step {}", 7);
+	});
+	assert!(message.starts_with("assertion failed: `left` and `right` BidirMaps differ: after step 7\n"));
+	assert!(message.contains("only in left:\n    (1, 'a')\n"));
+	assert!(!message.contains("only in right"));
+	assert!(!message.contains("remapped"));
+}
+
+#[test]
+fn both_remapped() {
+	// (1, 'b') takes both K/Vs of the left map's pairs
+	let left: BidirMap<_, _> = vec![(1, 'a'), (2, 'b')].into_iter().collect();
+	let right: BidirMap<_, _> = vec![(1, 'b')].into_iter().collect();
+	let diff = left.diff(&right);
+	assert!(diff.only_left.is_empty() && diff.only_right.is_empty());
+	assert_eq!(diff.remapped_first, [(&1, &'a', &'b')]);
+	assert_eq!(diff.remapped_second, [(&'b', &2, &1)]);
+}
@@ -0,0 +1,67 @@
+extern crate bidir_map;
+
+use bidir_map::{BidirMap, Column, DuplicatePolicy, OnDuplicate};
+use bidir_map::OnDuplicate::{Error, FirstWins, LastWins};
+
+
+/// Runs the same insertions under `policy`, returning the sorted pairs left and the columns of the refused insertions
+fn script(on_first: OnDuplicate, on_second: OnDuplicate) -> (Vec<(u32, &'static str)>, Vec<Column>) {
+	let mut map = BidirMap::with_duplicate_policy(DuplicatePolicy{
+		on_first,
+		on_second,
+	});
+	let mut refused = Vec::new();
+	// New, new, first K/V repeated, second K/V repeated, both repeated in different pairs, and that again
+	for &(kv1, kv2) in &[(1, "a"), (2, "b"), (1, "x"), (9, "b"), (1, "b"), (1, "b")] {
+		if let Err(err) = map.try_insert(kv1, kv2) {
+			assert_eq!(err.pair, (kv1, kv2));
+			refused.push(err.column);
+		}
+	}
+
+	let mut pairs: Vec<_> = map.into_iter().collect();
+	pairs.sort();
+	(pairs, refused)
+}
+
+#[test]
+fn same_policy_both_columns() {
+	assert_eq!(script(LastWins, LastWins), (vec![(1, "b")], vec![]));
+	assert_eq!(script(FirstWins, FirstWins), (vec![(1, "a"), (2, "b")], vec![]));
+	assert_eq!(script(Error, Error), (vec![(1, "a"), (2, "b")], vec![Column::First, Column::Second, Column::First, Column::First]));
+}
+
+#[test]
+fn mixed_policies() {
+	assert_eq!(script(LastWins, FirstWins), (vec![(1, "x"), (2, "b")], vec![]));
+	assert_eq!(script(FirstWins, LastWins), (vec![(1, "a"), (9, "b")], vec![]));
+	assert_eq!(script(Error, LastWins), (vec![(1, "a"), (9, "b")], vec![Column::First, Column::First, Column::First]));
+	assert_eq!(script(LastWins, Error), (vec![(1, "x"), (2, "b")], vec![Column::Second, Column::Second, Column::Second]));
+	assert_eq!(script(Error, FirstWins), (vec![(1, "a"), (2, "b")], vec![Column::First, Column::First, Column::First]));
+}
+
+#[test]
+fn extend_and_reinsert() {
+	let mut map = BidirMap::with_duplicate_policy(DuplicatePolicy::both(FirstWins));
+	map.extend(vec![(1, 'a'), (2, 'a'), (1, 'b'), (3, 'c')]);
+	assert_eq!(map.iter().cloned().collect::<Vec<_>>(), [(1, 'a'), (3, 'c')]);
+	assert_eq!(map.insert(1, 'b'), Some((1, 'b')));
+	assert_eq!(map.insert(3, 'c'), Some((3, 'c')));
+	assert_eq!(map.clone().duplicate_policy(), Some(DuplicatePolicy::both(FirstWins)));
+
+	let unchecked: BidirMap<_, _> = vec![(1, 'a'), (1, 'b')].into_iter().collect();
+	assert_eq!(unchecked.duplicate_policy(), None);
+	assert_eq!(unchecked.len(), 2);
+}
+
+#[test]
+#[should_panic(expected = "second K/V already in the map")]
+fn insert_panics_on_error() {
+	let mut map = BidirMap::with_duplicate_policy(DuplicatePolicy{
+		on_first: LastWins,
+		on_second: Error,
+	});
+	map.insert(1, 'a');
+	map.insert(1, 'b');
+	map.insert(2, 'b');
+}
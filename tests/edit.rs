@@ -0,0 +1,66 @@
+extern crate bidir_map;
+
+mod common;
+
+use bidir_map::BidirMap;
+use common::pseudo_random;
+
+
+fn start() -> BidirMap<u32, String> {
+	(0..200).map(|i| (i, format!("v{}", i * 3))).collect()
+}
+
+
+#[test]
+fn matches_direct_edits() {
+	for &seed in &[0x0123_4567_89ab_cdef, 0xfeed_f00d_dead_beef, 42] {
+		let mut seed = seed;
+		let mut direct = start();
+		let mut edited = start();
+		{
+			let mut session = edited.edit();
+			for _ in 0..2000 {
+				let kv1 = (pseudo_random(&mut seed) % 300) as u32;
+				let kv2 = format!("v{}", pseudo_random(&mut seed) % 600);
+				match pseudo_random(&mut seed) % 6 {
+					0 | 1 => assert_eq!(session.insert(kv1, kv2.clone()), direct.insert(kv1, kv2)),
+					2 => assert_eq!(session.remove_by_first(&kv1), direct.remove_by_first(&kv1)),
+					3 => assert_eq!(session.remove_by_second(&kv2[..]), direct.remove_by_second(&kv2[..])),
+					4 => assert_eq!(session.get_by_first(&kv1), direct.get_by_first(&kv1)),
+					_ => {
+						assert_eq!(session.get_by_second(&kv2[..]), direct.get_by_second(&kv2[..]));
+						assert_eq!(session.contains_second_key(&kv2[..]), direct.contains_second_key(&kv2[..]));
+					}
+				}
+				assert_eq!(session.len(), direct.len());
+			}
+			assert!(session.iter().eq(direct.iter()));
+		}
+		assert_eq!(edited, direct);
+		assert!(edited.iter().eq(direct.iter()));
+	}
+}
+
+#[test]
+fn index_dropped_after() {
+	let mut map = start();
+	{
+		let mut session = map.edit();
+		session.insert(500, "new".to_string());
+	}
+	assert!(!map.has_index());
+	assert_eq!(map.get_by_second("new"), Some(&500));
+}
+
+#[test]
+fn existing_index_kept() {
+	let mut map = start();
+	map.build_index();
+	{
+		let mut session = map.edit();
+		assert_eq!(session.remove_by_first(&3), Some((3, "v9".to_string())));
+		assert!(!session.contains_first_key(&3));
+	}
+	assert!(map.has_index());
+	assert_eq!(map.get_by_first_indexed(&4), Some(&"v12".to_string()));
+}
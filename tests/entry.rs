@@ -0,0 +1,275 @@
+extern crate bidir_map;
+
+use std::borrow::Borrow;
+use std::cell::Cell;
+use bidir_map::{BidirMap, EntryByFirst, EntryBySecond, EntryRefByFirst};
+
+
+thread_local!(static CONVERSIONS: Cell<usize> = const { Cell::new(0) });
+
+/// A `String` key counting how often it's created from, or cloned into, an owned value
+#[derive(Debug, PartialEq)]
+struct Key(String);
+
+impl Clone for Key {
+	fn clone(&self) -> Self {
+		CONVERSIONS.with(|c| c.set(c.get() + 1));
+		Key(self.0.clone())
+	}
+}
+
+impl<'a> From<&'a str> for Key {
+	fn from(s: &'a str) -> Self {
+		CONVERSIONS.with(|c| c.set(c.get() + 1));
+		Key(s.to_string())
+	}
+}
+
+impl Borrow<str> for Key {
+	fn borrow(&self) -> &str {
+		&self.0
+	}
+}
+
+fn conversions() -> usize {
+	CONVERSIONS.with(|c| c.get())
+}
+
+
+#[test]
+fn vacant_insert() {
+	let mut map = BidirMap::new();
+	map.insert(1, "a");
+
+	match map.entry_by_first(2) {
+		EntryByFirst::Vacant(entry) => {
+			assert_eq!(*entry.key(), 2);
+			assert_eq!(*entry.insert("b"), "b");
+		}
+		EntryByFirst::Occupied(_) => panic!("2 isn't in the map"),
+	}
+	assert_eq!(map.get_by_first(&2), Some(&"b"));
+	assert_eq!(map.get_by_second(&"b"), Some(&2));
+
+	assert_eq!(*map.entry_by_first(3).or_insert_with(|| "c"), "c");
+	assert_eq!(map.len(), 3);
+}
+
+#[test]
+fn occupied_modify() {
+	let mut map = BidirMap::new();
+	map.insert("x", 1);
+	map.insert("y", 2);
+
+	*map.entry_by_first("x").and_modify(|v| *v += 10).or_insert(0) += 100;
+	assert_eq!(map.get_by_first(&"x"), Some(&111));
+	assert_eq!(map.get_by_second(&111), Some(&"x"));
+	assert_eq!(map.get_by_second(&1), None);
+
+	match map.entry_by_first("y") {
+		EntryByFirst::Occupied(mut entry) => {
+			assert_eq!(*entry.key(), "y");
+			assert_eq!(*entry.get(), 2);
+			assert_eq!(entry.insert(3), 2);
+			assert_eq!(*entry.get(), 3);
+			assert_eq!(entry.remove(), ("y", 3));
+		}
+		EntryByFirst::Vacant(_) => panic!("\"y\" is in the map"),
+	}
+	assert_eq!(map.len(), 1);
+	assert!(!map.contains_second_key(&3));
+}
+
+#[test]
+fn cross_conflict() {
+	let mut map = BidirMap::new();
+	map.insert(1, "a");
+	map.insert(2, "b");
+
+	// Refused: "a" belongs to (1, "a")
+	assert_eq!(map.entry_by_first(3).or_insert_checked("a"), Err("a"));
+	assert_eq!(map.len(), 2);
+
+	// Displaced, as with insert()
+	assert_eq!(*map.entry_by_first(3).or_insert("a"), "a");
+	assert_eq!(map.get_by_second(&"a"), Some(&3));
+	assert!(!map.contains_first_key(&1));
+	assert_eq!(map.len(), 2);
+
+	// Replacing an occupied pair's second K/V displaces the other holder too
+	if let EntryByFirst::Occupied(mut entry) = map.entry_by_first(2) {
+		assert_eq!(entry.insert("a"), "b");
+	}
+	assert_eq!(map.iter().cloned().collect::<Vec<_>>(), [(2, "a")]);
+}
+
+#[test]
+fn hash_index_kept_in_sync() {
+	let mut map: BidirMap<_, _> = (0..100).map(|i| (i, i * 2)).collect();
+	map.build_index();
+
+	*map.entry_by_first(5).or_insert(0) += 1;
+	map.entry_by_first(200).or_insert(400);
+	if let EntryByFirst::Occupied(entry) = map.entry_by_first(0) {
+		entry.remove();
+	}
+
+	assert_eq!(map.get_by_second_indexed(&11), Some(&5));
+	assert_eq!(map.get_by_second_indexed(&400), Some(&200));
+	assert_eq!(map.get_by_first_indexed(&0), None);
+	assert_eq!(map.get_by_first_indexed(&99), Some(&198));
+	assert_eq!(map.validate(), Ok(()));
+}
+
+/// Names to ids, minting the next id for unknown names
+struct Registry {
+	ids: BidirMap<u32, &'static str>,
+	next_id: u32,
+}
+
+impl Registry {
+	fn id_of(&mut self, name: &'static str) -> u32 {
+		let next_id = &mut self.next_id;
+		*self.ids.entry_by_second(name).or_insert_with(|| {
+			*next_id += 1;
+			*next_id - 1
+		})
+	}
+
+	fn rename(&mut self, old: &'static str, new: &'static str) -> Option<u32> {
+		match self.ids.entry_by_second(old) {
+			EntryBySecond::Occupied(entry) => {
+				let (id, _) = entry.remove();
+				self.ids.entry_by_second(new).or_insert(id);
+				Some(id)
+			}
+			EntryBySecond::Vacant(_) => None,
+		}
+	}
+}
+
+#[test]
+fn mint_ids_by_second() {
+	let mut registry = Registry{ids: BidirMap::new(), next_id: 0};
+	assert_eq!(registry.id_of("alice"), 0);
+	assert_eq!(registry.id_of("bob"), 1);
+	assert_eq!(registry.id_of("alice"), 0);
+	assert_eq!(registry.next_id, 2);
+
+	assert_eq!(registry.rename("bob", "robert"), Some(1));
+	assert_eq!(registry.rename("bob", "rob"), None);
+	assert_eq!(registry.id_of("robert"), 1);
+	assert_eq!(registry.id_of("carol"), 2);
+
+	match registry.ids.entry_by_second("alice") {
+		EntryBySecond::Occupied(mut entry) => {
+			assert_eq!(*entry.key(), "alice");
+			assert_eq!(*entry.get(), 0);
+			*entry.get_mut() = 10;
+		}
+		EntryBySecond::Vacant(_) => panic!("alice has an id"),
+	}
+	assert_eq!(registry.ids.get_by_first(&10), Some(&"alice"));
+	assert_eq!(registry.ids.len(), 3);
+}
+
+#[test]
+fn cross_conflict_by_second() {
+	let mut map = BidirMap::new();
+	map.insert(1, "a");
+
+	assert_eq!(map.entry_by_second("b").or_insert_checked(1), Err(1));
+	assert_eq!(*map.entry_by_second("b").or_insert(1), 1);
+	assert_eq!(map.iter().cloned().collect::<Vec<_>>(), [(1, "b")]);
+}
+
+#[test]
+fn entry_ref_converts_only_when_vacant() {
+	let mut map: BidirMap<Key, u32> = BidirMap::new();
+	let before = conversions();
+
+	*map.entry_by_first_ref("a").or_insert(1) += 10;
+	assert_eq!(conversions(), before + 1);
+
+	for _ in 0..10 {
+		*map.entry_by_first_ref("a").and_modify(|v| *v += 1).or_insert(0) += 1;
+	}
+	assert_eq!(conversions(), before + 1);
+	assert_eq!(map.get_by_first("a"), Some(&31));
+
+	match map.entry_by_first_ref("b") {
+		EntryRefByFirst::Vacant(entry) => {
+			assert_eq!(entry.key(), "b");
+			assert_eq!(entry.insert_checked(31), Err(31));
+		}
+		EntryRefByFirst::Occupied(_) => panic!("\"b\" isn't in the map"),
+	}
+	assert_eq!(conversions(), before + 1);
+
+	assert_eq!(*map.entry_by_first_ref("b").or_insert(2), 2);
+	assert_eq!(conversions(), before + 2);
+
+	if let EntryRefByFirst::Occupied(entry) = map.entry_by_first_ref("a") {
+		assert_eq!(entry.remove(), (Key("a".to_string()), 31));
+	}
+	assert_eq!(conversions(), before + 2);
+	assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn upsert_paths() {
+	use bidir_map::entry::Upserted;
+
+	let mut map = BidirMap::new();
+	let mut inserts = 0;
+	for _ in 0..3 {
+		map.upsert_by_first("hits", || { inserts += 1; 1 }, |v| *v *= 10);
+	}
+	assert_eq!(inserts, 1);
+	assert_eq!(map.get_by_first(&"hits"), Some(&100));
+	assert_eq!(map.get_by_second(&100), Some(&"hits"));
+
+	map.insert("misses", 7);
+	assert_eq!(map.upsert_by_first("other", || 8, |_| panic!("not present")), Upserted::Inserted(None));
+	assert_eq!(map.iter().cloned().collect::<Vec<_>>(), [("hits", 100), ("misses", 7), ("other", 8)]);
+}
+
+#[test]
+fn upsert_conflicts() {
+	use bidir_map::entry::Upserted;
+
+	let mut map: BidirMap<_, _> = vec![("a", 1), ("b", 2), ("c", 3)].into_iter().collect();
+	map.build_index();
+
+	// Updating "a" to 3 displaces ("c", 3); "a" keeps its position
+	assert_eq!(map.upsert_by_first("a", || 0, |v| *v = 3), Upserted::Updated(Some(("c", 3))));
+	assert_eq!(map.iter().cloned().collect::<Vec<_>>(), [("a", 3), ("b", 2)]);
+
+	// Updating the last pair into a collision with the first
+	assert_eq!(map.upsert_by_first("b", || 0, |v| *v = 3), Upserted::Updated(Some(("a", 3))));
+	assert_eq!(map.iter().cloned().collect::<Vec<_>>(), [("b", 3)]);
+
+	// Inserting a second K/V another pair holds
+	assert_eq!(map.upsert_by_first("d", || 3, |_| {}), Upserted::Inserted(Some(("b", 3))));
+	assert_eq!(map.get_by_second_indexed(&3), Some(&"d"));
+	assert_eq!(map.validate(), Ok(()));
+}
+
+#[test]
+fn upsert_keeps_the_last_pair_last() {
+	use bidir_map::entry::Upserted;
+
+	let mut map: BidirMap<_, _> = vec![("a", 1), ("b", 2), ("c", 3), ("d", 4)].into_iter().collect();
+	map.build_index();
+
+	assert_eq!(map.upsert_by_first("d", || 0, |v| *v = 2), Upserted::Updated(Some(("b", 2))));
+	assert_eq!(map.iter().cloned().collect::<Vec<_>>(), [("a", 1), ("c", 3), ("d", 2)]);
+	assert_eq!(map.get_by_second(&2), Some(&"d"));
+	assert_eq!(map.get_index_of_first(&"d"), Some(2));
+
+	// And a pair before the one displaced stays where it is
+	assert_eq!(map.upsert_by_first("a", || 0, |v| *v = 3), Upserted::Updated(Some(("c", 3))));
+	assert_eq!(map.iter().cloned().collect::<Vec<_>>(), [("a", 3), ("d", 2)]);
+	assert_eq!(map.get_by_second_indexed(&3), Some(&"a"));
+	assert_eq!(map.validate(), Ok(()));
+}
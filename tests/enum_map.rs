@@ -0,0 +1,91 @@
+extern crate bidir_map;
+
+use bidir_map::{BidirEnumMap, EnumLike};
+
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Weekday {
+	Mon,
+	Tue,
+	Wed,
+	Thu,
+	Fri,
+}
+
+impl EnumLike for Weekday {
+	const VARIANTS: usize = 5;
+	type Array<T> = [T; 5];
+
+	fn to_index(&self) -> usize {
+		*self as usize
+	}
+
+	fn from_index(index: usize) -> Option<Self> {
+		[Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri].get(index).cloned()
+	}
+
+	fn array_from_fn<T, F: FnMut(usize) -> T>(f: F) -> [T; 5] {
+		std::array::from_fn(f)
+	}
+}
+
+
+#[test]
+fn partially_populated() {
+	let mut map = BidirEnumMap::new();
+	map.insert(Weekday::Thu, "gym".to_string());
+	map.insert(Weekday::Mon, "standup".to_string());
+
+	assert_eq!(map.len(), 2);
+	assert_eq!(map.get_by_first(&Weekday::Mon).map(String::as_str), Some("standup"));
+	assert_eq!(map.get_by_first(&Weekday::Tue), None);
+	assert!(!map.contains_first_key(&Weekday::Fri));
+	assert_eq!(map.iter().map(|(day, _)| day).collect::<Vec<_>>(), [Weekday::Mon, Weekday::Thu]);
+	assert_eq!(map.iter().len(), 2);
+	assert_eq!(format!("{:?}", map), r#"{Mon: "standup", Thu: "gym"}"#);
+
+	map.get_mut_by_first(&Weekday::Thu).unwrap().push_str(" & pool");
+	assert_eq!(map.get_by_second("gym & pool"), Some(Weekday::Thu));
+
+	map.clear();
+	assert!(map.is_empty());
+	assert_eq!(map, BidirEnumMap::default());
+}
+
+#[test]
+fn replacement() {
+	let mut map = BidirEnumMap::new();
+	assert_eq!(map.insert(Weekday::Mon, 1), None);
+	assert_eq!(map.insert(Weekday::Tue, 2), None);
+	assert_eq!(map.insert(Weekday::Wed, 3), None);
+
+	// Same first K/V
+	assert_eq!(map.insert(Weekday::Mon, 10), Some((Weekday::Mon, 1)));
+	// Same second K/V
+	assert_eq!(map.insert(Weekday::Fri, 2), Some((Weekday::Tue, 2)));
+	// Both, in different pairs: the one with the first K/V is returned
+	assert_eq!(map.insert(Weekday::Wed, 10), Some((Weekday::Wed, 3)));
+	// Both, in the same pair
+	assert_eq!(map.insert(Weekday::Fri, 2), Some((Weekday::Fri, 2)));
+
+	assert_eq!(map.iter().map(|(day, &n)| (day, n)).collect::<Vec<_>>(), [(Weekday::Wed, 10), (Weekday::Fri, 2)]);
+	assert_eq!(map.len(), 2);
+	assert_eq!(map.clone(), map);
+}
+
+#[test]
+fn reverse_lookup() {
+	let mut map = BidirEnumMap::new();
+	for (day, letter) in [(Weekday::Mon, 'M'), (Weekday::Wed, 'W'), (Weekday::Fri, 'F')] {
+		map.insert(day, letter);
+	}
+
+	assert_eq!(map.get_by_second(&'W'), Some(Weekday::Wed));
+	assert_eq!(map.get_by_second(&'T'), None);
+	assert!(map.contains_second_key(&'F'));
+	assert_eq!(map.remove_by_second(&'M'), Some((Weekday::Mon, 'M')));
+	assert_eq!(map.remove_by_second(&'M'), None);
+	assert_eq!(map.remove_by_first(&Weekday::Fri), Some((Weekday::Fri, 'F')));
+	assert_eq!(map.remove_by_first(&Weekday::Fri), None);
+	assert_eq!((&map).into_iter().collect::<Vec<_>>(), [(Weekday::Wed, &'W')]);
+}
@@ -0,0 +1,46 @@
+extern crate bidir_map;
+
+use std::error::Error;
+use bidir_map::{BidirMap, BidirSortedMap, CapacityError, Column, DuplicateKeyError, FromSortedVecError, InsertError, LengthMismatchError};
+
+
+#[test]
+fn display() {
+	assert_eq!(DuplicateKeyError{column: Column::First, index: Some(3)}.to_string(), "pair 3 repeats an earlier pair's first K/V");
+	assert_eq!(DuplicateKeyError{column: Column::Second, index: None}.to_string(), "a second K/V is repeated");
+	assert_eq!(LengthMismatchError{first: 3, second: 2}.to_string(), "got 3 first K/Vs but 2 second K/Vs");
+	assert_eq!(CapacityError{capacity: 8}.to_string(), "map is full at 8 pairs");
+	assert_eq!(InsertError{pair: (1, 'a')}.to_string(), "anchor not in the map");
+}
+
+#[test]
+fn source_chains() {
+	let err = BidirSortedMap::from_sorted_vec(vec![(1, 'a'), (2, 'b'), (3, 'a')]).unwrap_err();
+	assert_eq!(err.to_string(), "pairs aren't unique");
+	let source = err.source().unwrap();
+	assert_eq!(source.to_string(), "pair 2 repeats an earlier pair's second K/V");
+	assert!(source.source().is_none());
+
+	let err = BidirSortedMap::from_sorted_vec(vec![(2, 'a'), (1, 'b')]).unwrap_err();
+	assert_eq!(err, FromSortedVecError::Unsorted{index: 1});
+	assert!(err.source().is_none());
+}
+
+#[test]
+fn boxable() {
+	fn fallible() -> Result<(), Box<dyn Error>> {
+		let mut map: BidirMap<u32, char> = BidirMap::new();
+		map.insert_after_first(&1, 2, 'b')?;
+		Ok(())
+	}
+	assert_eq!(fallible().unwrap_err().to_string(), "anchor not in the map");
+
+	let mut map = BidirMap::new();
+	map.insert(1, 'a');
+	map.insert(2, 'b');
+	for kvs in map.iter_mut() {
+		kvs.1 = 'c';
+	}
+	let err: Box<dyn Error> = Box::new(map.validate().unwrap_err());
+	assert_eq!(err.to_string(), "pairs 0 and 1 share their second K/V");
+}
@@ -0,0 +1,73 @@
+extern crate bidir_map;
+
+mod common;
+
+use bidir_map::{BatchInsertError, BidirMap, Column};
+use common::pairs;
+
+
+fn map() -> BidirMap<u32, char> {
+	vec![(1, 'a'), (2, 'b'), (3, 'c')].into_iter().collect()
+}
+
+
+#[test]
+fn clean_batch() {
+	for indexed in [false, true] {
+		let mut map = map();
+		if indexed {
+			map.build_index();
+		}
+		assert_eq!(map.extend_atomic(vec![(4, 'd'), (5, 'e')]), Ok(()));
+		assert_eq!(map.extend_atomic(Vec::new()), Ok(()));
+		assert_eq!(pairs(&map), [(1, 'a'), (2, 'b'), (3, 'c'), (4, 'd'), (5, 'e')]);
+		assert_eq!(map.get_by_second_indexed(&'e'), Some(&5));
+	}
+}
+
+#[test]
+fn conflict_with_map() {
+	for indexed in [false, true] {
+		let mut map = map();
+		if indexed {
+			map.build_index();
+		}
+		let generation = map.generation();
+		let capacity = map.capacity();
+
+		assert_eq!(map.extend_atomic(vec![(4, 'd'), (5, 'e'), (2, 'f'), (6, 'a')]),
+		           Err(BatchInsertError{
+		               index: 2,
+		               column: Column::First,
+		               earlier: None,
+		               pair: (2, 'f'),
+		           }));
+		assert_eq!(map.extend_atomic(vec![(4, 'd'), (5, 'c')]),
+		           Err(BatchInsertError{
+		               index: 1,
+		               column: Column::Second,
+		               earlier: None,
+		               pair: (5, 'c'),
+		           }));
+		assert_eq!(pairs(&map), [(1, 'a'), (2, 'b'), (3, 'c')]);
+		assert_eq!((map.generation(), map.capacity()), (generation, capacity));
+		assert_eq!(map.get_by_first_indexed(&4), None);
+	}
+}
+
+#[test]
+fn duplicate_within_batch() {
+	let mut map = map();
+	let generation = map.generation();
+
+	assert_eq!(map.extend_atomic(vec![(4, 'd'), (5, 'e'), (6, 'd')]),
+	           Err(BatchInsertError{
+	               index: 2,
+	               column: Column::Second,
+	               earlier: Some(0),
+	               pair: (6, 'd'),
+	           }));
+	assert_eq!(map.extend_atomic(vec![(4, 'd'), (4, 'e')]).map_err(|err| (err.column, err.earlier)), Err((Column::First, Some(0))));
+	assert_eq!(pairs(&map), [(1, 'a'), (2, 'b'), (3, 'c')]);
+	assert_eq!(map.generation(), generation);
+}
@@ -0,0 +1,65 @@
+extern crate bidir_map;
+
+use bidir_map::{BidirMap, FallbackBidirMap, Resolved};
+
+
+type Fallback = fn(&u32) -> Option<u32>;
+
+/// Transcodes a few codes, passing the other ones below 100 through unchanged
+fn transcoder() -> FallbackBidirMap<u32, u32, Fallback, Fallback> {
+	fn pass_through(code: &u32) -> Option<u32> {
+		if *code < 100 { Some(*code) } else { None }
+	}
+
+	let mut map = BidirMap::with_fallbacks(pass_through as Fallback, pass_through as Fallback);
+	map.insert(1, 41);
+	map.insert(2, 42);
+	map.insert(150, 3);
+	map
+}
+
+#[test]
+fn three_outcomes() {
+	let map = transcoder();
+	assert_eq!(map.resolve_by_first(&1), Resolved::Mapped(&41));
+	assert_eq!(map.resolve_by_first(&7), Resolved::FellBack(7));
+	assert_eq!(map.resolve_by_first(&700), Resolved::Unknown);
+	assert_eq!(map.resolve_by_second(&3), Resolved::Mapped(&150));
+	assert_eq!(map.resolve_by_second(&8), Resolved::FellBack(8));
+	assert_eq!(map.resolve_by_second(&800), Resolved::Unknown);
+
+	assert!(map.resolve_by_first(&2).is_mapped());
+	assert_eq!(map.resolve_by_first(&2).into_owned(), Some(42));
+	assert_eq!(map.resolve_by_first(&9).as_ref(), Some(&9));
+	assert_eq!(map.resolve_by_first(&900).into_owned(), None);
+}
+
+#[test]
+fn fallbacks_never_insert() {
+	let mut map = transcoder();
+	for code in 0..200 {
+		map.resolve_by_first(&code);
+		map.resolve_by_second(&code);
+	}
+	assert_eq!(map.len(), 3);
+	assert_eq!(map.get_by_first(&7), None);
+	assert!(!map.contains_second_key(&8));
+
+	assert_eq!(map.remove_by_first(&1), Some((1, 41)));
+	assert_eq!(map.resolve_by_first(&1), Resolved::FellBack(1));
+	map.inner_mut().insert(7, 70);
+	assert_eq!(map.resolve_by_first(&7), Resolved::Mapped(&70));
+}
+
+#[test]
+fn without_fallbacks_unchanged() {
+	let mut plain = BidirMap::new();
+	plain.insert(1, 41);
+	plain.insert(150, 3);
+	assert_eq!(plain.get_by_first(&7), None);
+	assert_eq!(plain.get_by_second(&3), Some(&150));
+
+	let (inner, _, _) = transcoder().into_parts();
+	assert_eq!(inner.get_by_first(&7), None);
+	assert_eq!(inner.iter().cloned().collect::<Vec<_>>(), [(1, 41), (2, 42), (150, 3)]);
+}
@@ -0,0 +1,107 @@
+extern crate bidir_map;
+
+mod common;
+
+use bidir_map::{BidirMap, FingerprintedBidirMap};
+use std::hash::{BuildHasher, BuildHasherDefault, Hash, Hasher};
+use common::{comparisons, count_comparison, pseudo_random};
+
+
+/// A key that counts how many times it's compared
+#[derive(Debug, Eq)]
+struct Counted(String);
+
+impl Hash for Counted {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		self.0.hash(state);
+	}
+}
+
+impl PartialEq for Counted {
+	fn eq(&self, other: &Counted) -> bool {
+		count_comparison();
+		self.0 == other.0
+	}
+}
+
+/// Hashes everything to 0, so every fingerprint collides
+#[derive(Default)]
+struct Colliding;
+
+impl Hasher for Colliding {
+	fn finish(&self) -> u64 {
+		0
+	}
+
+	fn write(&mut self, _: &[u8]) {}
+}
+
+
+fn key(i: u64) -> String {
+	format!("/a/rather/long/common/prefix/shared/by/every/key/{}", i)
+}
+
+fn differential<S: BuildHasher + Default>() {
+	let mut state = 0x9e37_79b9_7f4a_7c15u64;
+	let mut next = move || pseudo_random(&mut state);
+
+	let mut plain = BidirMap::new();
+	let mut fingerprinted: FingerprintedBidirMap<String, String, S> = FingerprintedBidirMap::default();
+	for _ in 0..3000 {
+		let kv1 = key(next() % 48);
+		let kv2 = key(next() % 48 + 1000);
+		match next() % 8 {
+			0 => assert_eq!(fingerprinted.remove_by_first(&kv1), plain.remove_by_first(&kv1)),
+			1 => assert_eq!(fingerprinted.remove_by_second(kv2.as_str()), plain.remove_by_second(kv2.as_str())),
+			2 => {
+				let digit = char::from(b'0' + (next() % 10) as u8);
+				fingerprinted.retain(|kv1, _| !kv1.ends_with(digit));
+				plain = plain.into_iter().filter(|kvs| !kvs.0.ends_with(digit)).collect();
+			}
+			_ => assert_eq!(fingerprinted.insert(kv1.clone(), kv2.clone()), plain.insert(kv1.clone(), kv2.clone())),
+		}
+
+		assert_eq!(fingerprinted.get_by_first(kv1.as_str()), plain.get_by_first(kv1.as_str()));
+		assert_eq!(fingerprinted.get_by_second(&kv2), plain.get_by_second(&kv2));
+		assert_eq!(fingerprinted.contains_first_key(&kv2), plain.contains_first_key(&kv2));
+		assert_eq!(fingerprinted.contains_second_key(&kv1), plain.contains_second_key(&kv1));
+		assert_eq!(fingerprinted.len(), plain.len());
+	}
+	assert!(fingerprinted.iter().eq(plain.iter()));
+
+	let back = BidirMap::from(fingerprinted);
+	assert!(back.iter().eq(plain.iter()));
+}
+
+#[test]
+fn matches_plain_map() {
+	differential::<std::collections::hash_map::RandomState>();
+}
+
+#[test]
+fn correct_despite_collisions() {
+	differential::<BuildHasherDefault<Colliding>>();
+}
+
+#[test]
+fn fewer_comparisons() {
+	let pairs: Vec<_> = (0..200).map(|i| (key(i), key(i + 1000))).collect();
+	let plain: BidirMap<Counted, Counted> = pairs.iter().cloned().map(|(kv1, kv2)| (Counted(kv1), Counted(kv2))).collect();
+	let mut fingerprinted: FingerprintedBidirMap<Counted, Counted> = pairs.iter().cloned().map(|(kv1, kv2)| (Counted(kv1), Counted(kv2))).collect();
+
+	let missing = Counted(key(5000));
+	let last = Counted(key(199));
+	assert_eq!(comparisons(|| assert!(!plain.contains_first_key(&missing))), 200);
+	assert_eq!(comparisons(|| assert!(!fingerprinted.contains_first_key(&missing))), 0);
+	assert_eq!(comparisons(|| assert!(plain.get_by_first(&last).is_some())), 200);
+	assert_eq!(comparisons(|| assert!(fingerprinted.get_by_first(&last).is_some())), 1);
+	assert_eq!(comparisons(|| assert!(fingerprinted.get_by_second(&Counted(key(1100))).is_some())), 1);
+
+	// Collisions are the only reason to compare
+	let mut colliding: FingerprintedBidirMap<Counted, Counted, BuildHasherDefault<Colliding>> = FingerprintedBidirMap::default();
+	colliding.extend(pairs.into_iter().map(|(kv1, kv2)| (Counted(kv1), Counted(kv2))));
+	assert_eq!(comparisons(|| assert!(!colliding.contains_first_key(&missing))), 200);
+
+	assert_eq!(comparisons(|| { fingerprinted.insert(Counted(key(5000)), Counted(key(6000))); }), 0);
+	assert_eq!(fingerprinted.len(), 201);
+}
@@ -0,0 +1,68 @@
+extern crate bidir_map;
+
+mod common;
+
+use bidir_map::{BidirMap, FrozenBidirMap};
+use std::sync::Arc;
+use std::thread;
+use common::pseudo_random;
+
+
+fn random_map(len: usize, seed: u64) -> BidirMap<u32, u32> {
+	let mut seed = seed;
+	let mut map = BidirMap::new();
+	for _ in 0..len {
+		map.insert((pseudo_random(&mut seed) % 2000) as u32, (pseudo_random(&mut seed) % 2000) as u32);
+	}
+	map
+}
+
+#[test]
+fn lookups_agree_with_unfrozen_map() {
+	let map = random_map(1000, 0x5eed);
+	let frozen = map.clone().freeze();
+
+	assert_eq!(frozen.len(), map.len());
+	assert!(frozen.iter().eq(map.iter()));
+	for k in 0..2000 {
+		assert_eq!(frozen.get_by_first(&k), map.get_by_first(&k));
+		assert_eq!(frozen.get_by_second(&k), map.get_by_second(&k));
+		assert_eq!(frozen.contains_first_key(&k), map.contains_first_key(&k));
+		assert_eq!(frozen.contains_second_key(&k), map.contains_second_key(&k));
+	}
+	assert_eq!(frozen.thaw(), map);
+}
+
+#[test]
+fn empty_map_freezes() {
+	let frozen = BidirMap::<u8, u8>::new().freeze();
+	assert!(frozen.is_empty());
+	assert_eq!(frozen.get_by_first(&0), None);
+	assert_eq!(frozen.get_by_second(&0), None);
+}
+
+#[test]
+fn shared_between_threads() {
+	let map = random_map(500, 0xfeed);
+	let frozen = Arc::new(map.clone().freeze());
+
+	let handles: Vec<_> = (0..4u32)
+		.map(|t| {
+			let frozen = frozen.clone();
+			thread::spawn(move || (t * 500..(t + 1) * 500).filter(|k| frozen.contains_first_key(k)).count())
+		})
+		.collect();
+	let found: usize = handles.into_iter().map(|h| h.join().unwrap()).sum();
+
+	assert_eq!(found, (0..2000).filter(|k| map.contains_first_key(k)).count());
+}
+
+#[test]
+fn many_lookups_on_large_map() {
+	let frozen: FrozenBidirMap<u32, u32> = (0..100_000).map(|i| (i, 100_000 - i)).collect::<BidirMap<_, _>>().freeze();
+	for i in (1..100_000).step_by(7) {
+		assert_eq!(frozen.get_by_first(&i), Some(&(100_000 - i)));
+		assert_eq!(frozen.get_by_second(&i), Some(&(100_000 - i)));
+	}
+	assert_eq!(frozen.get_by_second(&0), None);
+}
@@ -0,0 +1,50 @@
+extern crate bidir_map;
+
+use bidir_map::BidirMap;
+
+
+fn aliases() -> BidirMap<String, String> {
+	vec![("co", "checkout"), ("ci", "commit"), ("st", "status"), ("br", "branch"), ("sw", "switch")]
+		.into_iter()
+		.map(|(alias, name)| (alias.to_string(), name.to_string()))
+		.collect()
+}
+
+#[test]
+fn exact_match() {
+	let map = aliases();
+	assert_eq!(map.closest_by_first("st", 0).map(|(_, name, distance)| (&name[..], distance)), Some(("status", 0)));
+	assert_eq!(map.closest_by_second("commit", 3).map(|(alias, _, distance)| (&alias[..], distance)), Some(("ci", 0)));
+}
+
+#[test]
+fn near_miss() {
+	let map = aliases();
+	assert_eq!(map.closest_by_second("comit", 2).map(|(alias, _, distance)| (&alias[..], distance)), Some(("ci", 1)));
+	assert_eq!(map.closest_by_second("stauts", 2).map(|(alias, _, distance)| (&alias[..], distance)), Some(("st", 2)));
+
+	// "cx" is one edit from both "co" and "ci": the earlier one wins, and both are listed in iteration order
+	assert_eq!(map.closest_by_first("cx", 1).map(|(alias, _, distance)| (&alias[..], distance)), Some(("co", 1)));
+	let closest: Vec<_> = map.closest_n_by_first("cx", 2, 10).into_iter().map(|(alias, _, distance)| (&alias[..], distance)).collect();
+	assert_eq!(closest, [("co", 1), ("ci", 1), ("st", 2), ("br", 2), ("sw", 2)]);
+	assert_eq!(map.closest_n_by_first("cx", 2, 3).len(), 3);
+	assert_eq!(map.closest_n_by_first("co", 2, 0), []);
+
+	let closest: Vec<_> = map.closest_n_by_second("swtich", 3, 2).into_iter().map(|(_, name, distance)| (&name[..], distance)).collect();
+	assert_eq!(closest, [("switch", 2)]);
+}
+
+#[test]
+fn beyond_threshold() {
+	let map = aliases();
+	assert_eq!(map.closest_by_first("xyz", 2), None);
+	assert_eq!(map.closest_by_second("rebase", 2), None);
+	assert!(map.closest_n_by_second("rebase", 2, 5).is_empty());
+	assert_eq!(BidirMap::<&str, &str>::new().closest_by_first("", 10), None);
+
+	// Distances count chars, not bytes
+	let mut map = BidirMap::new();
+	map.insert("größe", 1);
+	assert_eq!(map.closest_by_first("grösse", 1), None);
+	assert_eq!(map.closest_by_first("grösse", 2), Some((&"größe", &1, 2)));
+}
@@ -0,0 +1,89 @@
+extern crate bidir_map;
+
+use bidir_map::{BidirMap, HandleBidirMap};
+
+
+#[test]
+fn invalidated_by_removal() {
+	let mut map = HandleBidirMap::new();
+	let (a, _) = map.insert_full(1, 'a');
+	let (b, _) = map.insert_full(2, 'b');
+
+	assert_eq!(map.remove_by_handle(a), Some((1, 'a')));
+	assert_eq!(map.get_by_handle(a), None);
+	assert_eq!(map.remove_by_handle(a), None);
+
+	assert_eq!(map.remove_by_second(&'b'), Some((2, 'b')));
+	assert!(!map.contains_handle(b));
+	assert!(map.is_empty());
+}
+
+#[test]
+fn invalidated_by_replacement() {
+	let mut map = HandleBidirMap::new();
+	let (a, _) = map.insert_full(1, 'a');
+	let (b, _) = map.insert_full(2, 'b');
+	let (c, replaced) = map.insert_full(3, 'b');
+
+	assert_eq!(replaced, Some((2, 'b')));
+	assert_eq!(map.get_by_handle(b), None);
+	assert_eq!(map.get_by_handle(c), Some((&3, &'b')));
+	assert_eq!(map.get_by_handle(a), Some((&1, &'a')));
+	assert_eq!(map.handle_of_second(&'b'), Some(c));
+}
+
+#[test]
+fn survive_unrelated_mutation() {
+	let mut map = HandleBidirMap::new();
+	let handles: Vec<_> = (0..10).map(|i| map.insert_full(i, i * 10).0).collect();
+
+	// Removing from the front moves the last pairs around
+	for i in 0..5 {
+		map.remove_by_first(&i);
+	}
+	for i in 10..20 {
+		map.insert(i, i * 10);
+	}
+	for (i, &handle) in handles.iter().enumerate() {
+		let i = i as u32;
+		if i < 5 {
+			assert_eq!(map.get_by_handle(handle), None);
+		} else {
+			assert_eq!(map.get_by_handle(handle), Some((&i, &(i * 10))));
+			assert_eq!(map.handle_of_first(&i), Some(handle));
+		}
+	}
+
+	*map.get_mut_by_handle(handles[7]).unwrap().1 += 1;
+	assert_eq!(map.get_by_first(&7), Some(&71));
+}
+
+#[test]
+fn reused_slots_dont_resurrect() {
+	let mut map = HandleBidirMap::new();
+	let (stale, _) = map.insert_full(1, 'a');
+	map.remove_by_first(&1);
+
+	let (fresh, _) = map.insert_full(1, 'a');
+	assert_ne!(stale, fresh);
+	assert_eq!(map.get_by_handle(stale), None);
+	assert_eq!(map.get_by_handle(fresh), Some((&1, &'a')));
+
+	map.clear();
+	let (reused, _) = map.insert_full(2, 'b');
+	assert_eq!(map.get_by_handle(fresh), None);
+	assert_eq!(map.get_by_handle(stale), None);
+	assert_eq!(map.get_by_handle(reused), Some((&2, &'b')));
+}
+
+#[test]
+fn conversions() {
+	let plain: BidirMap<_, _> = (0..5).map(|i| (i, -i)).collect();
+	let mut map = HandleBidirMap::from(plain.clone());
+	let three = map.handle_of_first(&3).unwrap();
+	map.remove_by_first(&0);
+	assert_eq!(map.get_by_handle(three), Some((&3, &-3)));
+	let (five, _) = map.insert_full(5, -5);
+	assert_eq!(map.get_by_handle(five), Some((&5, &-5)));
+	assert_eq!(BidirMap::from(map).len(), 5);
+}
@@ -0,0 +1,166 @@
+extern crate bidir_map;
+
+mod common;
+
+use bidir_map::BidirMap;
+use std::borrow::Borrow;
+use common::{comparisons, Counted};
+
+
+fn indexed_map(n: u32) -> BidirMap<Counted, u32> {
+	let mut map = BidirMap::new();
+	map.build_index();
+	for i in 0..n {
+		map.insert(Counted(i), i + 1000);
+	}
+	map
+}
+
+/// Every pair must be found through the index, with a handful of comparisons at most
+fn assert_consistent(map: &BidirMap<Counted, u32>) {
+	for &(kv1, kv2) in map.iter() {
+		assert!(comparisons(|| assert_eq!(map.get_by_first_indexed(&kv1), Some(&kv2))) <= 2);
+		assert_eq!(map.get_by_second_indexed(&kv2).map(|kv1| kv1.0), Some(kv1.0));
+	}
+	assert!(comparisons(|| assert_eq!(map.get_by_first_indexed(&Counted(u32::MAX)), None)) <= 2);
+	assert_eq!(map.get_by_second_indexed(&u32::MAX), None);
+}
+
+
+#[test]
+fn lookups_use_the_index() {
+	let map = indexed_map(200);
+	assert!(comparisons(|| assert_eq!(map.get_by_first_indexed(&Counted(199)), Some(&1199))) <= 2);
+	assert!(comparisons(|| assert_eq!(map.get_by_first(&Counted(199)), Some(&1199))) <= 2);
+	assert!(comparisons(|| assert!(map.contains_first_key(&Counted(150)))) <= 2);
+	assert!(comparisons(|| assert!(!map.contains_first_key(&Counted(1000)))) <= 2);
+	assert_eq!(map.get_by_second(&1150), Some(&Counted(150)));
+	assert_consistent(&map);
+}
+
+#[test]
+fn plain_removals_use_the_index() {
+	let mut map = indexed_map(200);
+	assert!(comparisons(|| assert_eq!(map.remove_by_first(&Counted(20)), Some((Counted(20), 1020)))) <= 2);
+	assert!(comparisons(|| assert_eq!(map.remove_by_second(&1199), Some((Counted(199), 1199)))) <= 2);
+	assert!(comparisons(|| assert_eq!(map.remove_full_by_first(&Counted(0)).map(|(_, kv1, kv2)| (kv1, kv2)), Some((Counted(0), 1000)))) <= 2);
+	assert!(comparisons(|| assert_eq!(map.remove_by_first(&Counted(20)), None)) <= 2);
+	assert_eq!(map.len(), 197);
+	assert_consistent(&map);
+}
+
+
+/// A K/V that can be looked up by the `Counted` it wraps
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct Wrapped(Counted);
+
+impl Borrow<Counted> for Wrapped {
+	fn borrow(&self) -> &Counted {
+		&self.0
+	}
+}
+
+#[test]
+fn borrowed_lookups_need_the_indexed_variants() {
+	let mut map: BidirMap<Wrapped, u32> = (0..200).map(|i| (Wrapped(Counted(i)), i + 1000)).collect();
+	map.build_index();
+	assert!(comparisons(|| assert_eq!(map.get_by_first(&Wrapped(Counted(199))), Some(&1199))) <= 2);
+	assert!(comparisons(|| assert_eq!(map.get_by_first(&Counted(199)), Some(&1199))) >= 200);
+	assert!(comparisons(|| assert_eq!(map.get_by_first_indexed(&Counted(199)), Some(&1199))) <= 2);
+}
+
+#[test]
+fn insert_keeps_index_in_sync() {
+	let mut map = indexed_map(50);
+	assert!(comparisons(|| assert_eq!(map.insert(Counted(100), 1100), None)) <= 4);
+
+	assert_eq!(map.insert(Counted(3), 5000), Some((Counted(3), 1003)));
+	assert_eq!(map.insert(Counted(6000), 1004), Some((Counted(4), 1004)));
+	assert_eq!(map.insert(Counted(5), 1006), Some((Counted(5), 1005)));
+	assert_eq!(map.get_by_second_indexed(&1005), None);
+	assert_eq!(map.get_by_first_indexed(&Counted(6)), None);
+	assert_eq!(map.len(), 50);
+	assert_consistent(&map);
+}
+
+#[test]
+fn removals_keep_index_in_sync() {
+	let mut map = indexed_map(50);
+	assert_eq!(map.remove_by_first(&Counted(0)), Some((Counted(0), 1000)));
+	assert_eq!(map.remove_by_second(&1010), Some((Counted(10), 1010)));
+	assert_eq!(map.remove_by_first_indexed(&Counted(20)), Some((Counted(20), 1020)));
+	assert_eq!(map.remove_by_second_indexed(&1049), Some((Counted(49), 1049)));
+	assert_eq!(map.remove_by_second_indexed(&1049), None);
+	assert_eq!(map.get_by_first_indexed(&Counted(0)), None);
+	assert_eq!(map.get_by_first_indexed(&Counted(20)), None);
+	assert_eq!(map.len(), 46);
+	assert_consistent(&map);
+
+	while let Some(&(kv1, _)) = map.iter().next() {
+		map.remove_by_first_indexed(&kv1);
+		assert_consistent(&map);
+	}
+}
+
+#[test]
+fn clear_empties_index() {
+	let mut map = indexed_map(10);
+	map.clear();
+	assert_eq!(map.get_by_first_indexed(&Counted(1)), None);
+	map.insert(Counted(1), 1);
+	assert!(map.has_index());
+	assert_consistent(&map);
+}
+
+#[test]
+fn in_place_mutation_rebuilds_index() {
+	let mut map = indexed_map(10);
+	for kvs in map.iter_mut() {
+		kvs.0 = Counted(kvs.0 .0 + 100);
+	}
+	// Stale until the next mutation, but still correct
+	assert_eq!(map.get_by_first_indexed(&Counted(105)), Some(&1005));
+	assert_eq!(map.get_by_first_indexed(&Counted(5)), None);
+
+	map.insert(Counted(0), 0);
+	assert_consistent(&map);
+
+	*map.get_mut_by_first(&Counted(105)).unwrap() = 7;
+	assert_eq!(map.get_by_second_indexed(&7).map(|k| k.0), Some(105));
+	*map.get_mut_by_second(&1006).unwrap() = Counted(6);
+	assert_eq!(map.get_by_first_indexed(&Counted(6)), Some(&1006));
+	map.remove_by_first(&Counted(0));
+	assert_consistent(&map);
+}
+
+#[test]
+fn extend_keeps_index_in_sync() {
+	let mut map = indexed_map(10);
+	map.extend(vec![(Counted(20), 20), (Counted(21), 21)]);
+	map.extend(&[(Counted(22), 22), (Counted(1), 1)]);
+	map.extend(vec![(&Counted(23), &23)]);
+	assert_eq!(map.len(), 14);
+	assert_consistent(&map);
+}
+
+#[test]
+fn index_is_cloned_but_not_collected() {
+	let map = indexed_map(10);
+	assert!(map.clone().has_index());
+	assert_eq!(map.clone(), map);
+	assert_consistent(&map.clone());
+
+	let collected: BidirMap<_, _> = map.iter().collect();
+	assert!(!collected.has_index());
+	assert_eq!(collected, map);
+}
+
+#[test]
+fn dropping_index_falls_back_to_scans() {
+	let mut map = indexed_map(100);
+	map.drop_index();
+	assert!(comparisons(|| assert_eq!(map.get_by_first_indexed(&Counted(99)), Some(&1099))) >= 100);
+	map.insert(Counted(100), 1100);
+	map.build_index();
+	assert_consistent(&map);
+}
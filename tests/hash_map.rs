@@ -0,0 +1,102 @@
+extern crate bidir_map;
+
+use bidir_map::{BidirHashMap, BidirMap};
+use std::hash::{BuildHasherDefault, Hasher};
+
+
+fn assert_consistent(map: &BidirHashMap<u32, String>) {
+	assert_eq!(map.first_col().count(), map.len());
+	assert_eq!(map.second_col().count(), map.len());
+	for (kv1, kv2) in map {
+		assert_eq!(map.get_by_first(kv1), Some(kv2));
+		assert_eq!(map.get_by_second(kv2), Some(kv1));
+	}
+}
+
+#[test]
+fn insert_displaces_both_pairs() {
+	let mut map = BidirHashMap::new();
+	map.insert(1, "a".to_string());
+	map.insert(2, "b".to_string());
+
+	assert_eq!(map.insert(1, "b".to_string()), Some((1, "a".to_string())));
+	assert_eq!(map.len(), 1);
+	assert!(!map.contains_second_key("a"));
+	assert_eq!(map.get_by_second("b"), Some(&1));
+	assert_consistent(&map);
+}
+
+#[test]
+fn insert_displaces_like_bidir_map() {
+	let pairs = [(1, "a"), (2, "b"), (1, "b"), (3, "c"), (3, "a"), (4, "c")];
+
+	let mut hash = BidirHashMap::new();
+	let mut vec = BidirMap::new();
+	for &(kv1, kv2) in &pairs {
+		assert_eq!(hash.insert(kv1, kv2), vec.insert(kv1, kv2));
+	}
+
+	let mut from_vec: Vec<_> = vec.into_iter().collect();
+	let mut from_hash: Vec<_> = hash.into_iter().collect();
+	from_vec.sort();
+	from_hash.sort();
+	assert_eq!(from_hash, from_vec);
+}
+
+#[test]
+fn removals_keep_both_sides_in_sync() {
+	let mut map: BidirHashMap<_, _> = (0..10).map(|i| (i, i.to_string())).collect();
+	assert_eq!(map.remove_by_first(&3), Some((3, "3".to_string())));
+	assert_eq!(map.remove_by_second("7"), Some((7, "7".to_string())));
+	assert_eq!(map.remove_by_second("7"), None);
+	assert!(!map.contains_second_key("3"));
+	assert!(!map.contains_first_key(&7));
+	assert_eq!(map.len(), 8);
+	assert_consistent(&map);
+}
+
+#[test]
+fn converts_to_and_from_bidir_map() {
+	let map: BidirMap<_, _> = (0..5).map(|i| (i, i.to_string())).collect();
+	let hash = BidirHashMap::from(map.clone());
+	assert_consistent(&hash);
+
+	let back = BidirMap::from(hash);
+	assert_eq!(back.len(), map.len());
+	for kvs in map.iter() {
+		assert_eq!(back.get_by_first(&kvs.0), Some(&kvs.1));
+	}
+}
+
+/// FNV-1a, as a stand-in for a third-party hasher
+#[derive(Default)]
+struct Fnv(u64);
+
+impl Hasher for Fnv {
+	fn finish(&self) -> u64 {
+		self.0
+	}
+
+	fn write(&mut self, bytes: &[u8]) {
+		if self.0 == 0 {
+			self.0 = 0xcbf29ce484222325;
+		}
+		for &b in bytes {
+			self.0 = (self.0 ^ b as u64).wrapping_mul(0x100000001b3);
+		}
+	}
+}
+
+#[test]
+fn custom_hasher() {
+	let mut map: BidirHashMap<u32, String, BuildHasherDefault<Fnv>> = BidirHashMap::with_capacity_and_hasher(4, Default::default());
+	map.extend((0..10).map(|i| (i, i.to_string())));
+	assert_eq!(map.insert(3, "4".to_string()), Some((3, "3".to_string())));
+	assert_eq!(map.get_by_second("4"), Some(&3));
+	assert_eq!(map.remove_by_first(&9), Some((9, "9".to_string())));
+	assert_eq!(map.len(), 8);
+
+	let collected: BidirHashMap<u32, String, BuildHasherDefault<Fnv>> = map.clone().into_iter().collect();
+	assert_eq!(collected, map);
+	assert_eq!(BidirMap::from(map).len(), 8);
+}
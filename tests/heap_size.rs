@@ -0,0 +1,62 @@
+extern crate bidir_map;
+
+use bidir_map::{BidirMap, HeapSize};
+use std::mem;
+
+
+const PAIR: usize = mem::size_of::<(String, Vec<u8>)>();
+
+/// A pair owning exactly `name.len()` and `len` bytes, its second K/V filled with `name`'s first byte
+fn pair(name: &str, len: usize) -> (String, Vec<u8>) {
+	let mut kv1 = String::with_capacity(name.len());
+	kv1.push_str(name);
+	let mut kv2 = Vec::with_capacity(len);
+	kv2.resize(len, name.as_bytes()[0]);
+	(kv1, kv2)
+}
+
+
+#[test]
+fn growth() {
+	let mut map = BidirMap::new();
+	assert_eq!(map.heap_size(), 0);
+
+	let (kv1, kv2) = pair("a", 100);
+	map.insert(kv1, kv2);
+	assert_eq!(map.heap_size(), PAIR + 1 + 100);
+
+	let (kv1, kv2) = pair("bcd", 1000);
+	map.insert(kv1, kv2);
+	assert_eq!(map.heap_size(), 2 * PAIR + 4 + 1100);
+	assert!(map.heap_size_with_spare() >= map.heap_size());
+
+	map.remove_by_first("a");
+	assert_eq!(map.heap_size(), PAIR + 3 + 1000);
+}
+
+#[test]
+fn spare() {
+	let mut map = BidirMap::with_capacity(16);
+	for i in 0..4 {
+		let (kv1, kv2) = pair(&i.to_string(), 10);
+		map.insert(kv1, kv2);
+	}
+	assert_eq!(map.heap_size(), 4 * (PAIR + 1 + 10));
+	assert_eq!(map.heap_size_with_spare(), map.heap_size() + 12 * PAIR);
+
+	map.shrink_to_fit();
+	assert_eq!(map.heap_size_with_spare(), map.heap_size() + (map.capacity() - 4) * PAIR);
+	assert!(map.heap_size_with_spare() < map.heap_size() + 12 * PAIR);
+}
+
+#[test]
+fn nested() {
+	let inner: BidirMap<u32, Box<str>> = vec![(1, "one".into()), (2, "two".into())].into_iter().collect();
+	let expected = inner.heap_size_with_spare();
+	assert_eq!(expected, inner.capacity() * mem::size_of::<(u32, Box<str>)>() + 6);
+
+	let outer: BidirMap<u8, Option<BidirMap<u32, Box<str>>>> = vec![(0, Some(inner)), (1, None)].into_iter().collect();
+	let pair = mem::size_of::<(u8, Option<BidirMap<u32, Box<str>>>)>();
+	assert_eq!(outer.heap_size(), 2 * pair + expected);
+	assert_eq!((&"borrowed", 7u8).heap_size(), 0);
+}
@@ -0,0 +1,98 @@
+extern crate bidir_map;
+
+use bidir_map::{BidirMap, HistoryBidirMap};
+
+
+fn contents(map: &HistoryBidirMap<u32, char>) -> Vec<(u32, char)> {
+	map.iter().cloned().collect()
+}
+
+
+#[test]
+fn displacing_insert() {
+	let mut map = BidirMap::with_history(10);
+	map.extend(vec![(1, 'a'), (2, 'b'), (3, 'c'), (4, 'd')]);
+	assert_eq!(map.history_len(), 1);
+
+	// Displaces both (1, 'a') and (3, 'c'), moving the others as it goes
+	assert_eq!(map.insert(1, 'c'), Some((1, 'a')));
+	assert_eq!(contents(&map), [(4, 'd'), (2, 'b'), (1, 'c')]);
+
+	assert!(map.undo());
+	assert_eq!(contents(&map), [(1, 'a'), (2, 'b'), (3, 'c'), (4, 'd')]);
+	assert!(map.redo());
+	assert_eq!(contents(&map), [(4, 'd'), (2, 'b'), (1, 'c')]);
+	assert!(map.undo());
+	assert!(map.undo());
+	assert!(map.is_empty());
+	assert!(!map.undo());
+
+	assert!(map.redo());
+	assert!(map.redo());
+	assert!(!map.redo());
+	assert_eq!(map.get_by_second(&'c'), Some(&1));
+	assert_eq!(map.get_by_first(&3), None);
+}
+
+#[test]
+fn removals_and_clear() {
+	let mut map = BidirMap::with_history(10);
+	map.extend(vec![(1, 'a'), (2, 'b'), (3, 'c'), (4, 'd'), (5, 'e')]);
+	assert_eq!(map.remove_by_first(&2), Some((2, 'b')));
+	assert_eq!(map.remove_by_second(&'x'), None);
+	map.retain(|&kv1, _| kv1 % 2 == 1);
+	assert_eq!(contents(&map), [(1, 'a'), (5, 'e'), (3, 'c')]);
+	map.clear();
+	assert_eq!(map.history_len(), 4);
+
+	assert!(map.undo());
+	assert_eq!(contents(&map), [(1, 'a'), (5, 'e'), (3, 'c')]);
+	assert!(map.undo());
+	assert_eq!(contents(&map), [(1, 'a'), (5, 'e'), (3, 'c'), (4, 'd')]);
+	assert!(map.undo());
+	assert_eq!(contents(&map), [(1, 'a'), (2, 'b'), (3, 'c'), (4, 'd'), (5, 'e')]);
+
+	assert!(map.redo());
+	assert!(map.redo());
+	assert!(map.redo());
+	assert!(map.is_empty());
+	assert_eq!(map.redo_len(), 0);
+}
+
+#[test]
+fn edit_forgets_redo() {
+	let mut map = BidirMap::with_history(10);
+	map.insert(1, 'a');
+	map.insert(2, 'b');
+	assert!(map.undo());
+	assert_eq!(map.redo_len(), 1);
+
+	// No-op edits aren't recorded, and keep the redo
+	map.remove_by_first(&7);
+	assert_eq!(map.redo_len(), 1);
+
+	map.insert(3, 'c');
+	assert!(!map.redo());
+	assert_eq!(contents(&map), [(1, 'a'), (3, 'c')]);
+	assert!(map.undo());
+	assert!(map.undo());
+	assert!(!map.undo());
+}
+
+#[test]
+fn bounded_depth() {
+	let mut map = BidirMap::with_history(2);
+	for i in 0..5 {
+		map.insert(i, (b'a' + i as u8) as char);
+	}
+	assert_eq!(map.history_len(), 2);
+	assert!(map.undo());
+	assert!(map.undo());
+	assert!(!map.undo());
+	assert_eq!(contents(&map), [(0, 'a'), (1, 'b'), (2, 'c')]);
+
+	let mut map = BidirMap::with_history(0);
+	map.insert(1, 'a');
+	assert!(!map.undo());
+	assert_eq!(map.into_inner().len(), 1);
+}
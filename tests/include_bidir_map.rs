@@ -0,0 +1,30 @@
+extern crate bidir_map;
+
+use bidir_map::{include_bidir_map, StaticBidirMap};
+
+
+static MIME_TYPES: StaticBidirMap<&str, u16> = include_bidir_map!("tests/data/mime_types.tsv", &'static str, u16);
+
+#[test]
+fn lookups_both_ways() {
+	assert_eq!(MIME_TYPES.len(), 5);
+	assert_eq!(MIME_TYPES.get_by_first("image/png"), Some(&20));
+	assert_eq!(MIME_TYPES.get_by_first("application/json"), Some(&40));
+	assert_eq!(MIME_TYPES.get_by_first("image/gif"), None);
+	assert_eq!(MIME_TYPES.get_by_second(&2), Some(&"text/plain"));
+	assert_eq!(MIME_TYPES.get_by_second(&21), Some(&"image/jpeg"));
+	assert_eq!(MIME_TYPES.get_by_second(&3), None);
+}
+
+#[test]
+fn file_order() {
+	assert_eq!(MIME_TYPES.iter().map(|&(kv1, _)| kv1).collect::<Vec<_>>(),
+	           ["text/html", "text/plain", "image/png", "image/jpeg", "application/json"]);
+}
+
+#[test]
+fn in_const_context() {
+	const FLAGS: StaticBidirMap<char, bool> = include_bidir_map!("tests/data/flags.tsv", char, bool);
+	assert_eq!(FLAGS.get_by_first(&'y'), Some(&true));
+	assert_eq!(FLAGS.get_by_second(&false), Some(&'n'));
+}
@@ -0,0 +1,39 @@
+extern crate bidir_map;
+
+use bidir_map::{BidirIndexMap, BidirMap};
+
+
+#[test]
+fn interning_twice_yields_the_same_id() {
+	let mut ids = BidirIndexMap::new();
+	let a = ids.get_or_intern("a".to_string());
+	let b = ids.get_or_intern("b".to_string());
+	assert_ne!(a, b);
+	assert_eq!(ids.get_or_intern("a".to_string()), a);
+	assert_eq!(ids.get_or_intern("b".to_string()), b);
+	assert_eq!(ids.len(), 2);
+}
+
+#[test]
+fn ids_are_contiguous_and_round_trip() {
+	let words = ["the", "cat", "sat", "on", "the", "mat", "cat"];
+	let mut ids = BidirIndexMap::new();
+	let assigned: Vec<_> = words.iter().map(|w| ids.get_or_intern(*w)).collect();
+	assert_eq!(assigned, [0, 1, 2, 3, 0, 4, 1]);
+
+	for id in 0..ids.len() {
+		let value = ids.resolve(id).unwrap();
+		assert_eq!(ids.lookup(value), Some(id));
+	}
+	assert_eq!(ids.resolve(ids.len()), None);
+	assert_eq!(ids.as_slice(), ["the", "cat", "sat", "on", "mat"]);
+}
+
+#[test]
+fn into_bidir_map() {
+	let ids: BidirIndexMap<_> = "abcab".chars().collect();
+	let map = BidirMap::from(ids);
+	assert_eq!(map.get_by_first(&'c'), Some(&2));
+	assert_eq!(map.get_by_second(&1), Some(&'b'));
+	assert_eq!(map.len(), 3);
+}
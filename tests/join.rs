@@ -0,0 +1,50 @@
+extern crate bidir_map;
+
+use bidir_map::BidirMap;
+
+
+fn names() -> BidirMap<u32, &'static str> {
+	vec![(1, "one"), (2, "two"), (3, "three"), (4, "four")].into_iter().collect()
+}
+
+#[test]
+fn fully_overlapping() {
+	let names = names();
+	let paths: BidirMap<_, _> = vec![(4, "/4".to_string()), (3, "/3".to_string()), (2, "/2".to_string()), (1, "/1".to_string())].into_iter().collect();
+
+	assert!(names.join_by_first(&paths).all(|(id, _, path)| path.map(|p| &p[..]) == Some(&format!("/{}", id)[..])));
+	assert_eq!(names.inner_join_by_first(&paths).map(|(id, name, _)| (*id, *name)).collect::<Vec<_>>(), names.iter().cloned().collect::<Vec<_>>());
+	assert_eq!(names.common_first_keys(&paths).cloned().collect::<Vec<_>>(), [1, 2, 3, 4]);
+	assert_eq!(paths.common_first_keys(&names).cloned().collect::<Vec<_>>(), [4, 3, 2, 1]);
+}
+
+#[test]
+fn partially_overlapping() {
+	let names = names();
+	let sizes: BidirMap<_, _> = vec![(3, 300u64), (5, 500), (1, 100)].into_iter().collect();
+
+	assert_eq!(names.join_by_first(&sizes).collect::<Vec<_>>(),
+	           [(&1, &"one", Some(&100)), (&2, &"two", None), (&3, &"three", Some(&300)), (&4, &"four", None)]);
+	assert_eq!(names.inner_join_by_first(&sizes).collect::<Vec<_>>(), [(&1, &"one", &100), (&3, &"three", &300)]);
+	assert_eq!(names.common_first_keys(&sizes).collect::<Vec<_>>(), [&1, &3]);
+
+	let only_in_names: Vec<_> = names.join_by_first(&sizes).filter(|j| j.2.is_none()).map(|j| *j.0).collect();
+	let only_in_sizes: Vec<_> = sizes.join_by_first(&names).filter(|j| j.2.is_none()).map(|j| *j.0).collect();
+	assert_eq!(only_in_names, [2, 4]);
+	assert_eq!(only_in_sizes, [5]);
+}
+
+#[test]
+fn disjoint() {
+	let names = names();
+	let others: BidirMap<u32, char> = vec![(10, 'a'), (20, 'b')].into_iter().collect();
+
+	assert!(names.join_by_first(&others).all(|(_, _, other)| other.is_none()));
+	assert_eq!(names.join_by_first(&others).count(), 4);
+	assert_eq!(names.inner_join_by_first(&others).next(), None);
+	assert_eq!(names.common_first_keys(&others).next(), None);
+
+	let empty = BidirMap::<u32, ()>::new();
+	assert_eq!(empty.join_by_first(&names).next(), None);
+	assert_eq!(names.join_by_first(&empty).count(), 4);
+}
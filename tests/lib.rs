@@ -1,6 +1,10 @@
 extern crate bidir_map;
 
+mod common;
+
 use bidir_map::BidirMap;
+use std::collections::HashMap;
+use common::{comparisons, pseudo_random, Counted};
 
 /// https://github.com/nabijaczleweli/bidir-map-rs/issues/1
 ///
@@ -11,3 +15,292 @@ fn get_with_borrowed_from_unsized_works() {
 	map.insert("asdf".to_string(), 1234);
 	assert_eq!(map.get_by_first("asdf"), Some(&1234));
 }
+
+#[test]
+fn string_keys_can_be_queried_by_str() {
+	let mut map: BidirMap<String, String> = BidirMap::new();
+	map.insert("hello".to_string(), "world".to_string());
+
+	assert_eq!(map.get_by_first("hello"), Some(&"world".to_string()));
+	assert_eq!(map.get_by_second("world"), Some(&"hello".to_string()));
+	assert!(map.contains_first_key("hello"));
+	assert!(map.contains_second_key("world"));
+	assert!(!map.contains_first_key("world"));
+	assert_eq!(map.remove_by_second("world"), Some(("hello".to_string(), "world".to_string())));
+	assert_eq!(map.remove_by_first("hello"), None);
+}
+
+#[test]
+fn vec_keys_can_be_queried_by_slice() {
+	let mut map: BidirMap<Vec<u8>, Vec<u8>> = BidirMap::new();
+	map.insert(vec![1, 2], vec![3, 4]);
+
+	assert_eq!(map.get_by_first(&[1, 2][..]), Some(&vec![3, 4]));
+	assert_eq!(map.get_by_second(&[3, 4][..]), Some(&vec![1, 2]));
+	assert!(map.contains_first_key(&[1, 2][..]));
+	assert!(map.contains_second_key(&[3, 4][..]));
+	assert_eq!(map.get_mut_by_first(&[1, 2][..]).map(|v| v.len()), Some(2));
+	assert_eq!(map.remove_by_first(&[1, 2][..]), Some((vec![1, 2], vec![3, 4])));
+	assert!(map.is_empty());
+}
+
+#[test]
+fn boxed_keys_can_be_queried_by_contents() {
+	let mut map: BidirMap<Box<u32>, Box<str>> = BidirMap::new();
+	map.insert(Box::new(1), "a".into());
+
+	assert_eq!(map.get_by_first(&1), Some(&"a".into()));
+	assert_eq!(map.get_by_second("a"), Some(&Box::new(1)));
+	assert!(map.contains_first_key(&1));
+	assert!(map.contains_second_key("a"));
+	assert_eq!(map.get_mut_by_second("a").map(|v| **v), Some(1));
+	assert_eq!(map.remove_by_second("a"), Some((Box::new(1), "a".into())));
+	assert!(map.is_empty());
+}
+
+#[test]
+fn insert_displaces_a_pair_from_each_column() {
+	let mut map = BidirMap::new();
+	map.insert(1, 'a');
+	map.insert(2, 'b');
+	map.insert(3, 'c');
+
+	assert_eq!(map.insert(1, 'b'), Some((1, 'a')));
+	assert_eq!(map.len(), 2);
+	assert_eq!(map.get_by_first(&1), Some(&'b'));
+	assert_eq!(map.get_by_second(&'b'), Some(&1));
+	assert_eq!(map.get_by_first(&2), None);
+	assert_eq!(map.get_by_second(&'a'), None);
+
+	assert_eq!(map.insert(4, 'c'), Some((3, 'c')));
+	assert_eq!(map.len(), 2);
+}
+
+#[test]
+fn collect_from_borrowed_map_iterator() {
+	let mut source = HashMap::new();
+	source.insert(1, "a".to_string());
+	source.insert(2, "b".to_string());
+
+	let map: BidirMap<i32, String> = source.iter().collect();
+	assert_eq!(map.len(), 2);
+	assert_eq!(map.get_by_first(&1), Some(&"a".to_string()));
+	assert_eq!(map.get_by_second("b"), Some(&2));
+
+	let copy: BidirMap<i32, String> = map.iter().collect();
+	assert_eq!(copy, map);
+}
+
+#[test]
+fn extend_from_slice_of_pairs() {
+	let pairs = [(1, 'a'), (2, 'b'), (1, 'c')];
+
+	let mut map = BidirMap::new();
+	map.insert(3, 'd');
+	map.extend(&pairs);
+	assert_eq!(map.len(), 3);
+	assert_eq!(map.get_by_first(&1), Some(&'c'));
+	assert_eq!(map.get_by_second(&'a'), None);
+
+	map.extend(pairs.iter().map(|(kv1, kv2)| (kv1, kv2)));
+	assert_eq!(map.get_by_first(&1), Some(&'c'));
+	assert_eq!(map.len(), 3);
+}
+
+#[test]
+fn swap_and_shift_removal_order() {
+	let original: BidirMap<_, _> = (0..5).map(|i| (i, i * 10)).collect();
+
+	let mut swapped = original.clone();
+	assert_eq!(swapped.swap_remove_index(1), Some((1, 10)));
+	assert_eq!(swapped.iter().cloned().collect::<Vec<_>>(), [(0, 0), (4, 40), (2, 20), (3, 30)]);
+
+	let mut shifted = original.clone();
+	assert_eq!(shifted.shift_remove_index(1), Some((1, 10)));
+	assert_eq!(shifted.iter().cloned().collect::<Vec<_>>(), [(0, 0), (2, 20), (3, 30), (4, 40)]);
+
+	assert_eq!(swapped.swap_remove_index(4), None);
+	assert_eq!(shifted.shift_remove_index(4), None);
+	assert_eq!(swapped.len(), 4);
+	assert_eq!(shifted.len(), 4);
+}
+
+#[test]
+fn positional_removal_and_earlier_indices() {
+	let mut map: BidirMap<_, _> = (0..6).map(|i| (i, i * 10)).collect();
+	let idx_of = |map: &BidirMap<i32, i32>, kv1| map.iter().position(|kvs| kvs.0 == kv1).unwrap();
+	let (two, four, five) = (idx_of(&map, 2), idx_of(&map, 4), idx_of(&map, 5));
+
+	// Swap removal only moves the last pair, into the vacated slot
+	map.swap_remove_index(two);
+	assert_eq!(idx_of(&map, 4), four);
+	assert_eq!(idx_of(&map, 5), two);
+	assert!(five >= map.len());
+
+	// Shift removal moves everything after it down by one
+	map.shift_remove_index(0);
+	assert_eq!(idx_of(&map, 5), two - 1);
+	assert_eq!(idx_of(&map, 4), four - 1);
+}
+
+#[test]
+fn positional_removal_keeps_hash_index_in_sync() {
+	let mut map: BidirMap<_, _> = (0..50).map(|i| (i, i * 10)).collect();
+	map.build_index();
+
+	map.swap_remove_index(3);
+	map.shift_remove_index(10);
+	assert_eq!(map.get_by_first_indexed(&3), None);
+	assert_eq!(map.get_by_second_indexed(&490), Some(&49));
+	assert_eq!(map.remove_by_first_indexed(&49), Some((49, 490)));
+	assert_eq!(map.len(), 47);
+	assert_eq!(map.validate(), Ok(()));
+}
+
+#[test]
+fn full_removal_reports_prior_index() {
+	let mut map: BidirMap<_, _> = (0..8).map(|i| (i, i * 10)).collect();
+
+	// Swap flavour: the reported index is where the pair was, now holding the former last pair
+	let idx = map.get_index_of_first(&2).unwrap();
+	assert_eq!(map.remove_full_by_first(&2), Some((idx, 2, 20)));
+	assert_eq!(map.get_index_of_first(&7), Some(idx));
+	let idx = map.get_index_of_second(&70).unwrap();
+	assert_eq!(map.remove_full_by_second(&70), Some((idx, 7, 70)));
+	assert_eq!(map.get_index_of_first(&6), Some(idx));
+
+	// Removing the last pair reports its own index
+	let last = map.len() - 1;
+	let (kv1, kv2) = *map.iter().last().unwrap();
+	assert_eq!(map.remove_full_by_first(&kv1), Some((last, kv1, kv2)));
+
+	// Shift flavour: the pairs after it move down by one
+	let before: Vec<_> = map.iter().cloned().collect();
+	let idx = map.get_index_of_second(&30).unwrap();
+	assert_eq!(map.shift_remove_full_by_second(&30), Some((idx, 3, 30)));
+	let idx = map.get_index_of_first(&1).unwrap();
+	assert_eq!(map.shift_remove_full_by_first(&1), Some((idx, 1, 10)));
+	let expected: Vec<_> = before.into_iter().filter(|kvs| kvs.0 != 3 && kvs.0 != 1).collect();
+	assert_eq!(map.iter().cloned().collect::<Vec<_>>(), expected);
+
+	assert_eq!(map.remove_full_by_first(&1), None);
+	assert_eq!(map.shift_remove_full_by_second(&30), None);
+}
+
+#[test]
+fn generation_changes_with_mutations_only() {
+	let mut map: BidirMap<u32, u32> = BidirMap::new();
+	let mut last = map.generation();
+	let mut changed = |map: &BidirMap<u32, u32>| {
+		let changed = map.generation() != last;
+		last = map.generation();
+		changed
+	};
+
+	map.insert(1, 10);
+	assert!(changed(&map));
+	map.insert(1, 11);
+	assert!(changed(&map));
+	map.extend(vec![(2, 20), (3, 30), (4, 40)]);
+	assert!(changed(&map));
+	map.iter_mut();
+	assert!(changed(&map));
+	map.get_mut_by_first(&2);
+	assert!(changed(&map));
+	map.remove_by_first(&2);
+	assert!(changed(&map));
+	map.remove_by_second(&30);
+	assert!(changed(&map));
+	map.shift_remove_index(0);
+	assert!(changed(&map));
+	map.insert_sorted(0, 0);
+	assert!(changed(&map));
+	map.clear();
+	assert!(changed(&map));
+
+	map.insert(5, 50);
+	changed(&map);
+	assert_eq!(map.get_by_first(&5), Some(&50));
+	assert_eq!(map.get_by_second(&50), Some(&5));
+	assert!(map.contains_first_key(&5));
+	assert_eq!(map.iter().count(), 1);
+	assert_eq!(map.first_col().count(), 1);
+	assert_eq!(map.remove_by_first(&6), None);
+	assert!(!changed(&map));
+
+	let clone = map.clone();
+	assert_eq!(clone.generation(), map.generation());
+	map.insert(6, 60);
+	map.remove_by_first(&6);
+	assert_ne!(clone.generation(), map.generation());
+	assert_eq!(clone, map);
+}
+
+#[test]
+fn insertion_within_capacity_never_grows() {
+	let mut map = BidirMap::with_capacity(4);
+	let capacity = map.capacity();
+
+	for i in 0..capacity {
+		assert_eq!(map.try_insert_within_capacity(i, i * 10), Ok(None));
+		assert_eq!(map.capacity(), capacity);
+	}
+	assert_eq!(map.try_insert_within_capacity(capacity, capacity * 10), Err((capacity, capacity * 10)));
+	assert_eq!(map.len(), capacity);
+
+	// Displacing when full
+	assert_eq!(map.try_insert_within_capacity(0, 5), Ok(Some((0, 0))));
+	assert_eq!(map.try_insert_within_capacity(capacity, 10), Ok(Some((1, 10))));
+	if capacity > 3 {
+		assert_eq!(map.try_insert_within_capacity(2, 30), Ok(Some((2, 20))));
+		assert_eq!(map.len(), capacity - 1);
+	}
+	assert_eq!(map.capacity(), capacity);
+
+	// Room again after a removal
+	map.remove_by_first(&0);
+	assert_eq!(map.try_insert_within_capacity(100, 1000), Ok(None));
+	assert_eq!(map.capacity(), capacity);
+}
+
+
+#[test]
+fn insert_scans_once() {
+	let mut map: BidirMap<Counted, Counted> = (0..100).map(|i| (Counted(i), Counted(i + 1000))).collect();
+
+	// A new pair is compared against every pair once per column, not once per contains+remove of each,
+	// and its K/Vs against themselves, to tell them apart from ones never equal to anything
+	assert_eq!(comparisons(|| { map.insert(Counted(500), Counted(1500)); }), 2 * 100 + 2);
+	// Replacing a pair at the front stops the scan there, rather than rescanning for the second K/V after its removal
+	assert_eq!(comparisons(|| { map.insert(Counted(0), Counted(1000)); }), 2);
+	// Now behind (500, 1500) moved to the front: the first K/V is found in the second pair, the second K/V in the third
+	assert_eq!(comparisons(|| { map.insert(Counted(1), Counted(1002)); }), 2 + 2 + 1);
+	assert_eq!(map.len(), 100);
+}
+
+#[test]
+fn insert_order_matches_remove_then_push() {
+	// The semantics from before the single scan: remove the holder of each K/V in turn, then push
+	fn reference(cont: &mut Vec<(u32, u32)>, kv1: u32, kv2: u32) -> Option<(u32, u32)> {
+		let by_first = cont.iter().position(|kvs| kvs.0 == kv1).map(|idx| cont.swap_remove(idx));
+		let by_second = cont.iter().position(|kvs| kvs.1 == kv2).map(|idx| cont.swap_remove(idx));
+		cont.push((kv1, kv2));
+		by_first.or(by_second)
+	}
+
+	let mut seed = 0x2545_f491_4f6c_dd1du64;
+	let mut map = BidirMap::new();
+	let mut indexed = BidirMap::new();
+	indexed.build_index();
+	let mut model = Vec::new();
+	for _ in 0..2000 {
+		pseudo_random(&mut seed);
+		let (kv1, kv2) = ((seed % 16) as u32, ((seed >> 8) % 16) as u32);
+
+		let expected = reference(&mut model, kv1, kv2);
+		assert_eq!(map.insert(kv1, kv2), expected);
+		assert_eq!(indexed.insert(kv1, kv2), expected);
+		assert_eq!(map.iter().cloned().collect::<Vec<_>>(), model);
+		assert_eq!(indexed.iter().cloned().collect::<Vec<_>>(), model);
+	}
+}
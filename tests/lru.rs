@@ -0,0 +1,102 @@
+extern crate bidir_map;
+
+mod common;
+
+use bidir_map::{BidirMap, LruBidirMap};
+use common::Rng;
+
+
+fn pairs(map: &LruBidirMap<u32, char>) -> Vec<(u32, char)> {
+	map.iter().cloned().collect()
+}
+
+#[test]
+fn lookups_refresh_recency() {
+	let mut map = LruBidirMap::with_capacity(3);
+	map.insert(1, 'a');
+	map.insert(2, 'b');
+	map.insert(3, 'c');
+
+	assert_eq!(map.get_by_first(&1), Some(&'a'));
+	assert_eq!(map.insert(4, 'd'), Some((2, 'b')));
+
+	assert_eq!(map.get_by_second(&'c'), Some(&3));
+	assert_eq!(map.insert(5, 'e'), Some((1, 'a')));
+
+	// Misses and peeks don't count as uses
+	assert_eq!(map.get_by_first(&1), None);
+	assert_eq!(map.peek_by_first(&4), Some(&'d'));
+	assert!(map.contains_second_key(&'d'));
+	assert_eq!(map.insert(6, 'f'), Some((4, 'd')));
+
+	assert_eq!(pairs(&map), [(6, 'f'), (5, 'e'), (3, 'c')]);
+}
+
+#[test]
+fn replacement_doesnt_evict() {
+	let mut map = LruBidirMap::with_capacity(2);
+	map.insert(1, 'a');
+	map.insert(2, 'b');
+
+	assert_eq!(map.insert(2, 'z'), Some((2, 'b')));
+	assert_eq!(map.insert(3, 'a'), Some((1, 'a')));
+	assert_eq!(map.len(), 2);
+	assert_eq!(map.least_recently_used(), Some(&(2, 'z')));
+	assert_eq!(map.insert(4, 'd'), Some((2, 'z')));
+}
+
+#[test]
+fn recency_survives_removals() {
+	let mut map = LruBidirMap::with_capacity(5);
+	for i in 0..5 {
+		map.insert(i, (b'a' + i as u8) as char);
+	}
+	map.get_by_first(&0);
+	map.get_by_first(&2);
+	// Recency, most recent first: 2 0 4 3 1; removing 0 moves the last stored pair into its slot
+	assert_eq!(map.remove_by_first(&0), Some((0, 'a')));
+	assert_eq!(map.remove_by_second(&'b'), Some((1, 'b')));
+	assert_eq!(pairs(&map), [(2, 'c'), (4, 'e'), (3, 'd')]);
+
+	map.insert(5, 'f');
+	map.insert(6, 'g');
+	let evicted: Vec<_> = (7..10).map(|i| map.insert(i, (b'a' + i as u8) as char).unwrap()).collect();
+	assert_eq!(evicted, [(3, 'd'), (4, 'e'), (2, 'c')]);
+	assert_eq!(BidirMap::from(map).iter().cloned().collect::<Vec<_>>(), [(9, 'j'), (8, 'i'), (7, 'h'), (6, 'g'), (5, 'f')]);
+}
+
+#[test]
+fn matches_naive_model() {
+	for seed in 1..20u64 {
+		let mut rng = Rng(seed.wrapping_mul(0x9e3779b97f4a7c15));
+		let mut map = LruBidirMap::with_capacity(8);
+		// Most recently used last
+		let mut model: Vec<(u64, u64)> = Vec::new();
+
+		for _ in 0..500 {
+			let (kv1, kv2) = (rng.below(16), rng.below(16));
+			match rng.below(4) {
+				0 => {
+					let found = model.iter().position(|kvs| kvs.0 == kv1);
+					assert_eq!(map.get_by_first(&kv1), found.map(|idx| &model[idx].1));
+					if let Some(idx) = found {
+						let kvs = model.remove(idx);
+						model.push(kvs);
+					}
+				}
+				1 => {
+					let found = model.iter().position(|kvs| kvs.0 == kv1);
+					assert_eq!(map.remove_by_first(&kv1), found.map(|idx| model.remove(idx)));
+				}
+				_ => {
+					let by_first = model.iter().position(|kvs| kvs.0 == kv1).map(|idx| model.remove(idx));
+					let by_second = model.iter().position(|kvs| kvs.1 == kv2).map(|idx| model.remove(idx));
+					let evicted = if model.len() == 8 { Some(model.remove(0)) } else { None };
+					model.push((kv1, kv2));
+					assert_eq!(map.insert(kv1, kv2), by_first.or(by_second).or(evicted));
+				}
+			}
+			assert_eq!(map.iter().cloned().collect::<Vec<_>>(), model.iter().rev().cloned().collect::<Vec<_>>());
+		}
+	}
+}
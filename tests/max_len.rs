@@ -0,0 +1,109 @@
+extern crate bidir_map;
+
+use bidir_map::{BidirMap, CapacityError, DuplicatePolicy, EntryByFirst, OnDuplicate, Upserted};
+
+
+fn full() -> BidirMap<u32, &'static str> {
+	let mut map = BidirMap::with_max_len(3);
+	map.insert(1, "a");
+	map.insert(2, "b");
+	map.insert(3, "c");
+	map
+}
+
+
+#[test]
+fn updates_at_limit() {
+	let mut map = full();
+	assert_eq!(map.try_insert_within_max_len(1, "A"), Ok(Some((1, "a"))));
+	assert_eq!(map.try_insert_within_max_len(4, "b"), Ok(Some((2, "b"))));
+	// Displacing two pairs for one shrinks the map, making room for another
+	assert_eq!(map.try_insert_within_max_len(3, "A"), Ok(Some((3, "c"))));
+	assert_eq!(map.len(), 2);
+	assert_eq!(map.try_insert_within_max_len(5, "e"), Ok(None));
+	assert_eq!(map.len(), 3);
+
+	// Nor does `insert()` panic updating a full map
+	assert_eq!(full().insert(2, "B"), Some((2, "b")));
+
+	// Policies keeping the old pair don't grow the map either
+	let mut first_wins = BidirMap::with_duplicate_policy(DuplicatePolicy::both(OnDuplicate::FirstWins));
+	first_wins.set_max_len(1);
+	first_wins.insert(1, "a");
+	assert_eq!(first_wins.try_insert_within_max_len(1, "b"), Ok(Some((1, "b"))));
+}
+
+#[test]
+fn updates_over_limit() {
+	let mut map = full();
+	map.set_max_len(2);
+	assert_eq!(map.try_insert_within_max_len(1, "z"), Ok(Some((1, "a"))));
+	assert_eq!(map.iter().cloned().collect::<Vec<_>>(), [(3, "c"), (2, "b"), (1, "z")]);
+	assert_eq!(map.insert(4, "b"), Some((2, "b")));
+	assert_eq!(map.upsert_by_first(3, || "x", |kv2| *kv2 = "z"), Upserted::Updated(Some((1, "z"))));
+	assert_eq!(map.upsert_by_first(5, || "b", |_| {}), Upserted::Inserted(Some((4, "b"))));
+	assert_eq!(map.len(), 2);
+
+	let mut map = full();
+	map.set_max_len(1);
+	*map.entry_by_first(1).or_insert("x") = "y";
+	if let EntryByFirst::Occupied(mut entry) = map.entry_by_first(2) {
+		assert_eq!(entry.insert("y"), "b");
+	}
+	map.entry_by_second("q").or_insert(3);
+	assert_eq!(map.iter().cloned().collect::<Vec<_>>(), [(2, "y"), (3, "q")]);
+}
+
+#[test]
+fn over_limit() {
+	let mut map = full();
+	assert_eq!(map.try_insert_within_max_len(4, "d"), Err(CapacityError{capacity: 3}));
+	assert_eq!(map.iter().cloned().collect::<Vec<_>>(), [(1, "a"), (2, "b"), (3, "c")]);
+	assert_eq!(map.max_len(), Some(3));
+
+	let mut clone = map.clone();
+	assert!(clone.try_insert_within_max_len(4, "d").is_err());
+	clone.set_max_len(4);
+	assert_eq!(clone.try_insert_within_max_len(4, "d"), Ok(None));
+}
+
+#[test]
+#[should_panic(expected = "map is full at 3 pairs")]
+fn insert_over_limit_panics() {
+	full().insert(4, "d");
+}
+
+#[test]
+#[should_panic(expected = "map is full at 3 pairs")]
+fn entry_over_limit_panics() {
+	full().entry_by_first(4).or_insert("d");
+}
+
+#[test]
+fn extend_straddling_limit() {
+	let mut map = BidirMap::with_max_len(4);
+	map.insert(1, "a");
+	map.insert(2, "b");
+	let refused = map.extend_within_max_len(vec![(3, "c"), (4, "d"), (5, "e"), (2, "B"), (6, "f"), (5, "a")]);
+	assert_eq!(refused, [(5, "e"), (6, "f")]);
+	assert_eq!(map.iter().cloned().collect::<Vec<_>>(), [(2, "B"), (4, "d"), (3, "c"), (5, "a")]);
+
+	let mut unbounded = BidirMap::new();
+	assert!(unbounded.extend_within_max_len((0..100).map(|i| (i, i))).is_empty());
+	assert_eq!(unbounded.len(), 100);
+}
+
+#[test]
+fn read_from() {
+	let map: BidirMap<u32, u32> = (0..5).map(|i| (i, i * 10)).collect();
+	let mut bytes = Vec::new();
+	map.write_to(&mut bytes).unwrap();
+
+	let mut read = BidirMap::<u32, u32>::read_from_with_max_len(&bytes[..], 5).unwrap();
+	assert_eq!(read, map);
+	assert!(read.try_insert_within_max_len(5, 50).is_err());
+
+	let err = BidirMap::<u32, u32>::read_from_with_max_len(&bytes[..], 4).unwrap_err();
+	assert_eq!(err.to_string(), "map is full at 4 pairs");
+	assert_eq!(BidirMap::<u32, u32>::read_from(&bytes[..]).unwrap().max_len(), None);
+}
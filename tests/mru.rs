@@ -0,0 +1,77 @@
+extern crate bidir_map;
+
+mod common;
+
+use bidir_map::{BidirMap, MruBidirMap};
+use common::{comparisons, Counted};
+
+
+fn map(n: u32) -> MruBidirMap<Counted, u32> {
+	let mut map = BidirMap::new();
+	for i in 0..n {
+		map.insert(Counted(i), i + 1000);
+	}
+	MruBidirMap::from(map)
+}
+
+#[test]
+fn repeated_lookups_hit_the_cache() {
+	let map = map(100);
+	assert_eq!(comparisons(|| assert_eq!(map.get_by_first(&Counted(90)), Some(&1090))), 91);
+	assert_eq!(comparisons(|| assert_eq!(map.get_by_first(&Counted(90)), Some(&1090))), 1);
+	assert_eq!(map.get_by_second(&1090).map(|k| k.0), Some(90));
+	assert_eq!(comparisons(|| assert!(map.contains_second_key(&1090))), 0);
+}
+
+#[test]
+fn removal_moving_another_pair_into_the_cached_slot() {
+	let mut map = map(5);
+	assert_eq!(map.get_by_first(&Counted(0)), Some(&1000));
+	assert_eq!(map.get_by_second(&1004).map(|k| k.0), Some(4));
+
+	// Swap-removes slot 0, moving (4, 1004) into it
+	assert_eq!(map.remove_by_first(&Counted(0)).map(|kvs| kvs.1), Some(1000));
+	assert_eq!(map.get_by_first(&Counted(0)), None);
+	assert_eq!(map.get_by_second(&1004).map(|k| k.0), Some(4));
+	assert_eq!(map.get_by_first(&Counted(4)), Some(&1004));
+}
+
+#[test]
+fn cached_slot_past_the_end() {
+	let mut map = map(5);
+	assert_eq!(map.get_by_first(&Counted(4)), Some(&1004));
+	assert_eq!(map.remove_by_second(&1004).map(|kvs| kvs.0 .0), Some(4));
+	assert_eq!(map.get_by_first(&Counted(4)), None);
+
+	map.clear();
+	assert_eq!(map.get_by_first(&Counted(1)), None);
+}
+
+#[test]
+fn insert_displacing_the_cached_pair() {
+	let mut map = map(5);
+	assert_eq!(map.get_by_first(&Counted(2)), Some(&1002));
+	assert_eq!(map.get_by_second(&1003).map(|k| k.0), Some(3));
+
+	// Displaces both (2, 1002) and (3, 1003)
+	assert_eq!(map.insert(Counted(2), 1003).map(|kvs| kvs.1), Some(1002));
+	assert_eq!(map.get_by_first(&Counted(2)), Some(&1003));
+	assert_eq!(map.get_by_second(&1003).map(|k| k.0), Some(2));
+	assert_eq!(map.get_by_second(&1002), None);
+	assert_eq!(map.get_by_first(&Counted(3)), None);
+}
+
+#[test]
+fn changes_through_mutable_references() {
+	let mut map = map(5);
+	assert_eq!(map.get_by_second(&1001).map(|k| k.0), Some(1));
+	*map.get_mut_by_second(&1001).unwrap() = Counted(7);
+	assert_eq!(map.get_by_second(&1001).map(|k| k.0), Some(7));
+	assert_eq!(map.get_by_first(&Counted(1)), None);
+
+	assert_eq!(map.get_by_first(&Counted(7)), Some(&1001));
+	map.inner_mut().remove_by_first(&Counted(0));
+	map.inner_mut().insert(Counted(7), 5);
+	assert_eq!(map.get_by_first(&Counted(7)), Some(&5));
+	assert_eq!(map.get_by_second(&1001), None);
+}
@@ -0,0 +1,57 @@
+extern crate bidir_map;
+
+use bidir_map::{BidirMap, BidirMultiMap};
+
+
+fn aliases() -> BidirMultiMap<&'static str, &'static str> {
+	let mut map = BidirMultiMap::new();
+	map.insert("js", "javascript");
+	map.insert("ecmascript", "javascript");
+	map.insert("py", "python");
+	map.insert("node", "javascript");
+	map
+}
+
+#[test]
+fn shared_second_keys() {
+	let map = aliases();
+	assert_eq!(map.len(), 4);
+	assert_eq!(map.get_by_second("javascript").cloned().collect::<Vec<_>>(), ["js", "ecmascript", "node"]);
+	assert_eq!(map.get_by_second("python").cloned().collect::<Vec<_>>(), ["py"]);
+	assert_eq!(map.get_by_first("node"), Some(&"javascript"));
+	assert!(map.contains_second_key("javascript"));
+	assert!(!map.contains_first_key("javascript"));
+}
+
+#[test]
+fn insert_displaces_only_by_first() {
+	let mut map = aliases();
+	assert_eq!(map.insert("py", "javascript"), Some(("py", "python")));
+	assert!(!map.contains_second_key("python"));
+	assert_eq!(map.get_by_second("javascript").count(), 4);
+
+	assert_eq!(map.insert("py3", "python"), None);
+	assert_eq!(map.len(), 5);
+}
+
+#[test]
+fn remove_by_second_removes_all() {
+	let mut map = aliases();
+	assert_eq!(map.remove_by_second("javascript"), [("js", "javascript"), ("ecmascript", "javascript"), ("node", "javascript")]);
+	assert_eq!(map.iter().cloned().collect::<Vec<_>>(), [("py", "python")]);
+	assert!(map.remove_by_second("javascript").is_empty());
+
+	assert_eq!(map.remove_by_first("py"), Some(("py", "python")));
+	assert!(map.is_empty());
+}
+
+#[test]
+fn from_bidir_map() {
+	let mut map = BidirMap::new();
+	map.insert(1, 'a');
+	map.insert(2, 'b');
+
+	let mut multi = BidirMultiMap::from(map);
+	multi.insert(3, 'a');
+	assert_eq!(multi.get_by_second(&'a').cloned().collect::<Vec<_>>(), [1, 3]);
+}
@@ -0,0 +1,69 @@
+extern crate bidir_map;
+
+use bidir_map::{BidirMap, MapEvent};
+
+
+fn owned(event: MapEvent<&u32, &char>) -> MapEvent<u32, char> {
+	match event {
+		MapEvent::Inserted(kv1, kv2) => MapEvent::Inserted(*kv1, *kv2),
+		MapEvent::Removed(kv1, kv2) => MapEvent::Removed(*kv1, *kv2),
+		MapEvent::Replaced{old, new} => MapEvent::Replaced{old: (*old.0, *old.1), new: (*new.0, *new.1)},
+		MapEvent::Cleared(kv1, kv2) => MapEvent::Cleared(*kv1, *kv2),
+	}
+}
+
+#[test]
+fn scripted() {
+	let mut log = Vec::new();
+	{
+		let mut map = BidirMap::new().observed(|event| log.push(owned(event)));
+		map.insert(1, 'a');
+		map.insert(2, 'b');
+		map.insert(3, 'c');
+		assert_eq!(map.insert(1, 'z'), Some((1, 'a')));
+		assert_eq!(map.insert(4, 'b'), Some((2, 'b')));
+		assert_eq!(map.insert(3, 'z'), Some((3, 'c')));
+		assert_eq!(map.remove_by_first(&9), None);
+		assert_eq!(map.remove_by_second(&'b'), Some((4, 'b')));
+		map.extend(vec![(5, 'e'), (6, 'f'), (5, 'g')]);
+		map.retain(|&kv1, _| kv1 != 6);
+		assert_eq!(map.get_by_first(&5), Some(&'g'));
+		map.clear();
+		assert!(map.is_empty());
+	}
+
+	assert_eq!(log,
+	           [MapEvent::Inserted(1, 'a'),
+	            MapEvent::Inserted(2, 'b'),
+	            MapEvent::Inserted(3, 'c'),
+	            MapEvent::Replaced{old: (1, 'a'), new: (1, 'z')},
+	            MapEvent::Replaced{old: (2, 'b'), new: (4, 'b')},
+	            MapEvent::Replaced{old: (3, 'c'), new: (3, 'z')},
+	            MapEvent::Removed(1, 'z'),
+	            MapEvent::Removed(4, 'b'),
+	            MapEvent::Inserted(5, 'e'),
+	            MapEvent::Inserted(6, 'f'),
+	            MapEvent::Replaced{old: (5, 'e'), new: (5, 'g')},
+	            MapEvent::Removed(6, 'f'),
+	            MapEvent::Cleared(3, 'z'),
+	            MapEvent::Cleared(5, 'g')]);
+}
+
+#[test]
+fn index_kept_in_sync() {
+	let mut plain = BidirMap::new();
+	plain.build_index();
+	let mut count = 0;
+	let mut map = plain.observed(|_| count += 1);
+	map.extend((0..10u32).map(|i| (i, i * 2)));
+	map.retain(|kv1, _| kv1 % 3 != 0);
+	map.insert(20, 4);
+
+	let (plain, _) = map.into_parts();
+	assert_eq!(plain.get_by_second_indexed(&4), Some(&20));
+	assert_eq!(plain.get_by_first_indexed(&2), None);
+	assert_eq!(plain.get_by_first_indexed(&3), None);
+	assert_eq!(plain.get_by_first_indexed(&4), Some(&8));
+	assert_eq!(plain.len(), 6);
+	assert_eq!(count, 10 + 4 + 1);
+}
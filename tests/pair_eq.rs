@@ -0,0 +1,67 @@
+extern crate bidir_map;
+
+use bidir_map::BidirMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+
+fn map() -> BidirMap<u32, char> {
+	vec![(1, 'a'), (2, 'b'), (3, 'c')].into_iter().collect()
+}
+
+
+#[test]
+fn any_order() {
+	let map = map();
+	assert_eq!(map, [(3, 'c'), (1, 'a'), (2, 'b')]);
+	assert_eq!(map, vec![(2, 'b'), (3, 'c'), (1, 'a')]);
+	assert_eq!(map, [(1, 'a'), (3, 'c'), (2, 'b')][..]);
+	assert!(map.eq_ordered(&[(1, 'a'), (2, 'b'), (3, 'c')]));
+	assert!(!map.eq_ordered(&[(3, 'c'), (1, 'a'), (2, 'b')]));
+	assert_eq!(BidirMap::<u32, char>::new(), []);
+}
+
+#[test]
+fn differing_content() {
+	let map = map();
+	assert_ne!(map, [(1, 'a'), (2, 'b')]);
+	assert_ne!(map, [(1, 'a'), (2, 'b'), (3, 'c'), (4, 'd')]);
+	assert_ne!(map, [(1, 'a'), (2, 'c'), (3, 'b')]);
+	assert_ne!(map, vec![(1, 'a'), (1, 'a'), (3, 'c')]);
+	assert_ne!(map, [(1, 'a'), (2, 'b'), (3, 'b')][..]);
+	assert!(!map.eq_ordered(&[(1, 'a'), (2, 'b')]));
+}
+
+#[test]
+fn either_side() {
+	let map = map();
+	assert_eq!([(2, 'b'), (1, 'a'), (3, 'c')], map);
+	assert_eq!(vec![(3, 'c'), (2, 'b'), (1, 'a')], map);
+	assert_eq!([(1, 'a'), (2, 'b'), (3, 'c')][..], map);
+	assert_ne!([(2, 'b'), (1, 'a')], map);
+	assert_ne!(vec![(3, 'c'), (3, 'c'), (1, 'a')], map);
+}
+
+fn hash_of(map: &BidirMap<u32, char>) -> u64 {
+	let mut hasher = DefaultHasher::new();
+	map.hash(&mut hasher);
+	hasher.finish()
+}
+
+#[test]
+fn between_maps() {
+	let map = map();
+	let reordered: BidirMap<u32, char> = vec![(3, 'c'), (1, 'a'), (2, 'b')].into_iter().collect();
+	assert_ne!(map, reordered);
+	assert!(map.eq_unordered(&reordered));
+	assert!(!map.eq_ordered(&reordered));
+
+	let same: BidirMap<u32, char> = vec![(1, 'a'), (2, 'b'), (3, 'c')].into_iter().collect();
+	assert_eq!(map, same);
+	assert!(map.eq_ordered(&same));
+	assert_eq!(hash_of(&map), hash_of(&same));
+
+	let remapped: BidirMap<u32, char> = vec![(3, 'c'), (1, 'b'), (2, 'a')].into_iter().collect();
+	assert!(!map.eq_unordered(&remapped));
+	assert!(!map.eq_unordered(&BidirMap::new()));
+}
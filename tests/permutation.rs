@@ -0,0 +1,41 @@
+extern crate bidir_map;
+
+use bidir_map::BidirMap;
+
+
+#[test]
+fn pure_permutation() {
+	let perm: BidirMap<_, _> = vec![(1, 2), (2, 3), (3, 1), (4, 5), (5, 4), (6, 6)].into_iter().collect();
+	assert!(perm.is_permutation());
+	assert_eq!(perm.follow_first(&1, 10), [&2, &3, &1]);
+	assert_eq!(perm.follow_first(&1, 2), [&2, &3]);
+	assert_eq!(perm.follow_second(&1, 10), [&3, &2, &1]);
+	assert_eq!(perm.cycle_of(&5), Some(vec![&5, &4]));
+	assert_eq!(perm.cycles(), Some(vec![vec![&1, &2, &3], vec![&4, &5], vec![&6]]));
+}
+
+#[test]
+fn renaming_chains() {
+	let renames: BidirMap<_, _> = vec![("old", "mid"), ("mid", "new"), ("tmp", "mid2"), ("mid2", "final")].into_iter().collect();
+	assert!(!renames.is_permutation());
+	assert_eq!(renames.cycles(), None);
+
+	assert_eq!(renames.follow_first(&"old", 10), [&"mid", &"new"]);
+	assert_eq!(renames.follow_first(&"mid2", 10), [&"final"]);
+	assert_eq!(renames.follow_first(&"new", 10), Vec::<&&str>::new());
+	assert_eq!(renames.follow_first(&"unknown", 10), Vec::<&&str>::new());
+	assert_eq!(renames.follow_second(&"final", 10), [&"mid2", &"tmp"]);
+	assert_eq!(renames.cycle_of(&"old"), None);
+}
+
+#[test]
+fn self_loop() {
+	let map: BidirMap<_, _> = vec![('a', 'a'), ('b', 'c')].into_iter().collect();
+	assert_eq!(map.follow_first(&'a', 10), [&'a']);
+	assert_eq!(map.follow_first(&'a', 0), Vec::<&char>::new());
+	assert_eq!(map.cycle_of(&'a'), Some(vec![&'a']));
+	assert!(!map.is_permutation());
+
+	let identity: BidirMap<_, _> = vec![('a', 'a')].into_iter().collect();
+	assert_eq!(identity.cycles(), Some(vec![vec![&'a']]));
+}
@@ -0,0 +1,54 @@
+extern crate bidir_map;
+
+use bidir_map::{BidirMap, PersistentBidirMap};
+
+
+#[test]
+fn mutating_a_clone_leaves_the_snapshot_alone() {
+	let mut map: PersistentBidirMap<u32, String> = (0..200).map(|i| (i, i.to_string())).collect();
+	let snapshot = map.clone();
+
+	assert_eq!(map.remove_by_first(&10), Some((10, "10".to_string())));
+	assert_eq!(map.insert(500, "150".to_string()), Some((150, "150".to_string())));
+	map.insert(1000, "1000".to_string());
+
+	assert_eq!(snapshot.len(), 200);
+	assert_eq!(snapshot.get_by_first(&10), Some(&"10".to_string()));
+	assert_eq!(snapshot.get_by_second("150"), Some(&150));
+	assert!(!snapshot.contains_first_key(&1000));
+
+	assert_eq!(map.len(), 200);
+	assert!(!map.contains_first_key(&10));
+	assert_eq!(map.get_by_second("150"), Some(&500));
+}
+
+#[test]
+fn clones_share_untouched_chunks() {
+	let map: PersistentBidirMap<u32, u32> = (0..640).map(|i| (i, i)).collect();
+	let mut clone = map.clone();
+	assert_eq!(clone.shared_chunks(&map), 10);
+
+	// Moves the last pair into the first chunk: only those two are copied
+	clone.remove_by_first(&0);
+	assert_eq!(clone.shared_chunks(&map), 8);
+	assert_eq!(map.iter().next(), Some(&(0, 0)));
+	assert_eq!(clone.iter().next(), Some(&(639, 639)));
+}
+
+#[test]
+fn matches_bidir_map_order() {
+	let mut persistent = PersistentBidirMap::new();
+	let mut vec = BidirMap::new();
+	for i in 0..300u32 {
+		let (kv1, kv2) = (i * 7 % 101, i * 13 % 103);
+		assert_eq!(persistent.insert(kv1, kv2), vec.insert(kv1, kv2));
+		if i % 5 == 0 {
+			assert_eq!(persistent.remove_by_second(&(i % 103)), vec.remove_by_second(&(i % 103)));
+		}
+	}
+
+	assert!(persistent.iter().eq(vec.iter()));
+	let snapshot = persistent.clone();
+	assert_eq!(BidirMap::from(persistent), vec);
+	assert_eq!(BidirMap::from(snapshot), vec);
+}
@@ -0,0 +1,74 @@
+extern crate bidir_map;
+
+use bidir_map::{BidirMap, BidirSortedMap};
+
+
+const WORDS: [&str; 8] = ["ab", "abc", "abd", "a", "b", "abcd", "ba", ""];
+
+fn maps() -> (BidirMap<String, String>, BidirSortedMap<String, String>) {
+	let mut map = BidirMap::new();
+	let mut sorted = BidirSortedMap::new();
+	for (i, word) in WORDS.iter().enumerate() {
+		let reversed: String = word.chars().rev().chain(i.to_string().chars()).collect();
+		map.insert(word.to_string(), reversed.clone());
+		sorted.insert(word.to_string(), reversed);
+	}
+	(map, sorted)
+}
+
+fn firsts<'a, I: Iterator<Item=(&'a String, &'a String)>>(iter: I) -> Vec<&'a str> {
+	iter.map(|(kv1, _)| kv1.as_str()).collect()
+}
+
+#[test]
+fn overlapping_prefixes() {
+	let (map, sorted) = maps();
+	assert_eq!(firsts(map.iter_by_first_prefix("ab")), ["ab", "abc", "abd", "abcd"]);
+	assert_eq!(firsts(map.iter_by_first_prefix("abc")), ["abc", "abcd"]);
+	assert_eq!(firsts(map.iter_by_first_prefix("abcde")), Vec::<&str>::new());
+	assert_eq!(firsts(sorted.iter_by_first_prefix("ab")), ["ab", "abc", "abcd", "abd"]);
+	assert_eq!(firsts(sorted.iter_by_first_prefix("abc")), ["abc", "abcd"]);
+	assert_eq!(firsts(sorted.iter_by_first_prefix("c")), Vec::<&str>::new());
+	assert_eq!(firsts(map.iter_by_first_byte_prefix(b"ab")), firsts(map.iter_by_first_prefix("ab")));
+	assert_eq!(firsts(sorted.iter_by_first_byte_prefix(b"ab")), firsts(sorted.iter_by_first_prefix("ab")));
+}
+
+#[test]
+fn empty_prefix_yields_all() {
+	let (map, sorted) = maps();
+	assert_eq!(map.iter_by_first_prefix("").count(), WORDS.len());
+	assert_eq!(map.iter_by_second_prefix("").count(), WORDS.len());
+	assert_eq!(sorted.iter_by_first_prefix("").count(), WORDS.len());
+	assert_eq!(sorted.iter_by_second_byte_prefix(b"").count(), WORDS.len());
+}
+
+#[test]
+fn second_column() {
+	let (map, sorted) = maps();
+	let expected = [("ba", "ab6"), ("ab", "ba0")];
+	for (kv1, kv2) in expected {
+		assert_eq!(map.get_by_first(kv1).map(String::as_str), Some(kv2));
+	}
+
+	assert_eq!(firsts(map.iter_by_second_prefix("ba")), ["ab"]);
+	assert_eq!(firsts(map.iter_by_second_prefix("a")), ["a", "ba"]);
+	assert_eq!(firsts(map.iter_by_second_byte_prefix(b"dcba")), ["abcd"]);
+	assert_eq!(firsts(sorted.iter_by_second_prefix("ba")), ["ab"]);
+	assert_eq!(firsts(sorted.iter_by_second_prefix("a")), ["a", "ba"]);
+	assert_eq!(firsts(sorted.iter_by_second_byte_prefix(b"d")), ["abd", "abcd"]);
+}
+
+#[test]
+fn sorted_matches_scan() {
+	let (map, sorted) = maps();
+	let prefixes = ["", "a", "ab", "abc", "abcd", "b", "ba", "bad", "z", "0", "1"];
+	for prefix in prefixes {
+		let mut scanned = firsts(map.iter_by_first_prefix(prefix));
+		scanned.sort();
+		assert_eq!(firsts(sorted.iter_by_first_prefix(prefix)), scanned);
+
+		let mut scanned: Vec<_> = map.iter_by_second_prefix(prefix).map(|(_, kv2)| kv2.as_str()).collect();
+		scanned.sort();
+		assert_eq!(sorted.iter_by_second_prefix(prefix).map(|(_, kv2)| kv2.as_str()).collect::<Vec<_>>(), scanned);
+	}
+}
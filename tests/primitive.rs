@@ -0,0 +1,45 @@
+extern crate bidir_map;
+
+mod common;
+
+use bidir_map::{BidirSoaMap, PrimitiveKey};
+use common::pseudo_random;
+
+
+#[test]
+fn position_matches_scan() {
+	let mut seed = 0x1234_5678_9abc_def1;
+	for len in 0..100 {
+		let column: Vec<u8> = (0..len).map(|_| (pseudo_random(&mut seed) % 64) as u8).collect();
+		for key in 0..64 {
+			assert_eq!(PrimitiveKey::position(&column, key), column.iter().position(|&kv| kv == key), "{:?} {}", column, key);
+		}
+	}
+}
+
+#[test]
+fn randomized_operations_match_generic_path() {
+	let mut seed = 0xdead_beef_cafe_f00d;
+	let mut map: BidirSoaMap<u32, i64> = BidirSoaMap::new();
+	for _ in 0..5000 {
+		let kv1 = (pseudo_random(&mut seed) % 200) as u32;
+		let kv2 = (pseudo_random(&mut seed) % 200) as i64 - 100;
+		match pseudo_random(&mut seed) % 4 {
+			0 | 1 => {
+				map.insert(kv1, kv2);
+			}
+			2 => {
+				map.remove_by_first(&kv1);
+			}
+			_ => {
+				map.remove_by_second(&kv2);
+			}
+		}
+
+		assert_eq!(map.get_by_first_wide(&kv1), map.get_by_first(&kv1));
+		assert_eq!(map.get_by_second_wide(&kv2), map.get_by_second(&kv2));
+		assert_eq!(map.contains_first_key_wide(&kv1), map.contains_first_key(&kv1));
+		assert_eq!(map.contains_second_key_wide(&kv2), map.contains_second_key(&kv2));
+	}
+	assert!(map.len() > 2 * 32, "too few pairs to cover whole blocks: {}", map.len());
+}
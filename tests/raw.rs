@@ -0,0 +1,51 @@
+extern crate bidir_map;
+
+mod common;
+
+use bidir_map::BidirMap;
+use common::{comparisons, Counted};
+
+
+fn pairs(n: u32) -> Vec<(Counted, Counted)> {
+	(0..n).map(|i| (Counted(i), Counted(i + n))).collect()
+}
+
+#[test]
+fn bulk_load_skips_checks() {
+	let mut raw = None;
+	assert_eq!(comparisons(|| raw = Some(unsafe { BidirMap::from_raw_vec(pairs(1000)) })), 0);
+	let raw = raw.unwrap();
+	assert_eq!(raw.len(), 1000);
+
+	let mut inserted = BidirMap::new();
+	assert!(comparisons(|| for (kv1, kv2) in pairs(1000) {
+		inserted.insert(kv1, kv2);
+	}) >= 1000 * 999);
+	assert_eq!(raw.iter().map(|kvs| (kvs.0 .0, kvs.1 .0)).collect::<Vec<_>>(),
+	           inserted.iter().map(|kvs| (kvs.0 .0, kvs.1 .0)).collect::<Vec<_>>());
+}
+
+#[test]
+fn raw_round_trip() {
+	let pairs = vec![(1, 'a'), (2, 'b'), (3, 'c')];
+	let map = unsafe { BidirMap::from_raw_vec(pairs.clone()) };
+	assert_eq!(map.get_by_second(&'b'), Some(&2));
+	assert_eq!(map.into_raw(), pairs);
+}
+
+#[test]
+fn raw_mutation_with_hash_index() {
+	let mut map: BidirMap<_, _> = (0..50).map(|i| (i, i * 10)).collect();
+	map.build_index();
+
+	for kvs in map.as_raw_mut() {
+		kvs.0 += 100;
+	}
+	map.as_raw_mut().swap(0, 49);
+
+	assert_eq!(map.get_by_first_indexed(&0), None);
+	assert_eq!(map.get_by_first_indexed(&149), Some(&490));
+	assert_eq!(map.get_by_second_indexed(&0), Some(&100));
+	assert_eq!(map.iter().next(), Some(&(149, 490)));
+	map.debug_validate();
+}
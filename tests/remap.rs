@@ -0,0 +1,56 @@
+extern crate bidir_map;
+
+use bidir_map::{BidirMap, Column, RemapError};
+
+
+fn schema() -> BidirMap<String, u32> {
+	vec!["id", "user", "mail", "created"].into_iter().zip(0..).map(|(name, col)| (name.to_string(), col)).collect()
+}
+
+fn renames(pairs: &[(&str, &str)]) -> BidirMap<String, String> {
+	pairs.iter().map(|&(old, new)| (old.to_string(), new.to_string())).collect()
+}
+
+fn names(map: &BidirMap<String, u32>) -> Vec<&str> {
+	map.first_col().map(|name| &name[..]).collect()
+}
+
+
+#[test]
+fn clean_rename() {
+	let mut map = schema();
+	map.build_index();
+	assert_eq!(map.remap_first_keys(&renames(&[("mail", "email"), ("created", "created_at"), ("absent", "x")])), Ok(2));
+	assert_eq!(names(&map), ["id", "user", "email", "created_at"]);
+	assert_eq!(map.get_by_first_indexed("email"), Some(&2));
+	assert_eq!(map.get_by_first("mail"), None);
+	// Renaming a K/V to itself changes nothing
+	assert_eq!(map.remap_first_keys(&renames(&[("id", "id")])), Ok(0));
+}
+
+#[test]
+fn collision_rejected() {
+	let mut map = schema();
+	let err = map.remap_first_keys(&renames(&[("id", "uid"), ("mail", "user")])).unwrap_err();
+	assert_eq!(err, RemapError{column: Column::First, indices: (1, 2)});
+	assert_eq!(err.to_string(), "renaming would make pairs 1 and 2 share their first K/V");
+	assert_eq!(names(&map), ["id", "user", "mail", "created"]);
+
+	// Renaming into a K/V freed by another rename is fine
+	assert_eq!(map.remap_first_keys(&renames(&[("user", "login"), ("mail", "user")])), Ok(2));
+	assert_eq!(names(&map), ["id", "login", "user", "created"]);
+}
+
+#[test]
+fn swap() {
+	let mut map = schema();
+	assert_eq!(map.remap_first_keys(&renames(&[("id", "user"), ("user", "id")])), Ok(2));
+	assert_eq!(names(&map), ["user", "id", "mail", "created"]);
+	assert_eq!(map.get_by_first("id"), Some(&1));
+
+	let mut cols = schema();
+	let swap: BidirMap<_, _> = vec![(0, 3), (3, 0)].into_iter().collect();
+	assert_eq!(cols.remap_second_keys(&swap), Ok(2));
+	assert_eq!(cols.get_by_first("id"), Some(&3));
+	assert_eq!(cols.remap_second_keys(&vec![(1, 2)].into_iter().collect()), Err(RemapError{column: Column::Second, indices: (1, 2)}));
+}
@@ -0,0 +1,82 @@
+extern crate bidir_map;
+
+mod common;
+
+use bidir_map::BidirMap;
+use std::cell::Cell;
+use std::hash::{Hash, Hasher};
+use common::pseudo_random;
+
+
+thread_local!(static OPERATIONS: Cell<usize> = const { Cell::new(0) });
+
+/// A key that counts how many times it's compared or hashed
+#[derive(Clone, Copy, Debug, Eq)]
+struct Counted(u32);
+
+impl PartialEq for Counted {
+	fn eq(&self, other: &Counted) -> bool {
+		OPERATIONS.with(|c| c.set(c.get() + 1));
+		self.0 == other.0
+	}
+}
+
+impl Hash for Counted {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		OPERATIONS.with(|c| c.set(c.get() + 1));
+		self.0.hash(state)
+	}
+}
+
+#[test]
+fn matches_naive_loop() {
+	let mut seed = 0x9e37_79b9_7f4a_7c15;
+	for _ in 0..20 {
+		let mut map = BidirMap::new();
+		for _ in 0..200 {
+			let kv = pseudo_random(&mut seed) % 300;
+			map.insert(kv, kv + 1000);
+		}
+		let keys: Vec<u64> = (0..100).map(|_| pseudo_random(&mut seed) % 400).collect();
+
+		let mut naive = map.clone();
+		let mut naive_removed: Vec<_> = keys.iter().filter_map(|key| naive.remove_by_first(key)).collect();
+		let order: Vec<_> = map.iter().cloned().collect();
+		naive_removed.sort_by_key(|kvs| order.iter().position(|o| o == kvs));
+		let naive_left: Vec<_> = order.iter().filter(|kvs| naive.contains_first_key(&kvs.0)).cloned().collect();
+
+		let mut by_first = map.clone();
+		assert_eq!(by_first.remove_all_by_first(keys.iter().cloned()), naive_removed);
+		assert_eq!(by_first.iter().cloned().collect::<Vec<_>>(), naive_left);
+
+		let mut by_second = map.clone();
+		by_second.build_index();
+		assert_eq!(by_second.remove_all_by_second(keys.iter().map(|key| key + 1000)), naive_removed);
+		assert_eq!(by_second.iter().cloned().collect::<Vec<_>>(), naive_left);
+		assert!(naive_left.iter().all(|kvs| by_second.get_by_first_indexed(&kvs.0) == Some(&kvs.1)));
+	}
+}
+
+#[test]
+fn linear_operations() {
+	let mut map: BidirMap<Counted, u32> = (0..2000).map(|i| (Counted(i), i)).collect();
+	let keys: Vec<_> = (0..2000).filter(|i| i % 3 == 0).map(Counted).collect();
+
+	OPERATIONS.with(|c| c.set(0));
+	let removed = map.remove_all_by_first(keys);
+	let operations = OPERATIONS.with(|c| c.get());
+
+	assert_eq!(removed.len(), 667);
+	assert_eq!(map.len(), 1333);
+	// A loop of remove_by_first() would take well over a million comparisons
+	assert!(operations < 10 * (2000 + 667), "{} operations", operations);
+}
+
+#[test]
+fn missing_keys() {
+	let mut map: BidirMap<_, _> = vec![(1, 'a'), (2, 'b')].into_iter().collect();
+	assert_eq!(map.remove_all_by_second(vec!['x', 'y']), []);
+	assert_eq!(map.remove_all_by_first(Vec::new()), []);
+	assert_eq!(map.len(), 2);
+	assert_eq!(BidirMap::<u8, u8>::new().remove_all_by_first(vec![1]), []);
+}
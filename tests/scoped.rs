@@ -0,0 +1,109 @@
+extern crate bidir_map;
+
+use bidir_map::BidirMap;
+
+
+fn sorted(map: &BidirMap<&'static str, u32>) -> Vec<(&'static str, u32)> {
+	let mut pairs: Vec<_> = map.iter().cloned().collect();
+	pairs.sort();
+	pairs
+}
+
+fn bindings() -> BidirMap<&'static str, u32> {
+	let mut map = BidirMap::new();
+	map.insert("x", 1);
+	map.insert("y", 2);
+	map.insert("z", 3);
+	map
+}
+
+#[test]
+fn single_scope() {
+	let mut map = bindings();
+	{
+		let scope = map.insert_scoped("w", 4);
+		assert_eq!((scope.displaced_by_first(), scope.displaced_by_second()), (None, None));
+		assert_eq!(scope.len(), 4);
+	}
+	assert_eq!(sorted(&map), [("x", 1), ("y", 2), ("z", 3)]);
+
+	{
+		let scope = map.insert_scoped("x", 9);
+		assert_eq!(scope.displaced_by_first(), Some(&("x", 1)));
+		assert_eq!(scope.displaced_by_second(), None);
+		assert_eq!(scope.get_by_first(&"x"), Some(&9));
+	}
+	assert_eq!(sorted(&map), [("x", 1), ("y", 2), ("z", 3)]);
+}
+
+#[test]
+fn both_keys_displaced() {
+	let mut map = bindings();
+	{
+		let scope = map.insert_scoped("x", 2);
+		assert_eq!(scope.displaced_by_first(), Some(&("x", 1)));
+		assert_eq!(scope.displaced_by_second(), Some(&("y", 2)));
+		assert_eq!(sorted(&scope), [("x", 2), ("z", 3)]);
+	}
+	assert_eq!(sorted(&map), [("x", 1), ("y", 2), ("z", 3)]);
+}
+
+#[test]
+fn nested_scopes() {
+	let mut map = bindings();
+	{
+		let mut outer = map.insert_scoped("x", 10);
+		{
+			let mut inner = outer.insert_scoped("x", 2);
+			assert_eq!(inner.displaced_by_first(), Some(&("x", 10)));
+			assert_eq!(inner.displaced_by_second(), Some(&("y", 2)));
+			{
+				let innermost = inner.insert_scoped("y", 3);
+				assert_eq!(sorted(&innermost), [("x", 2), ("y", 3)]);
+			}
+			assert_eq!(sorted(&inner), [("x", 2), ("z", 3)]);
+		}
+		assert_eq!(sorted(&outer), [("x", 10), ("y", 2), ("z", 3)]);
+	}
+	assert_eq!(sorted(&map), [("x", 1), ("y", 2), ("z", 3)]);
+}
+
+#[test]
+fn scoped_pair_removed_in_scope() {
+	let mut map = bindings();
+	{
+		let mut scope = map.insert_scoped("x", 2);
+		assert_eq!(scope.remove_by_first(&"x"), Some(("x", 2)));
+		scope.insert("q", 7);
+	}
+	// Nothing left to remove; the displaced pairs come back, and the unrelated edit stays
+	assert_eq!(sorted(&map), [("q", 7), ("x", 1), ("y", 2), ("z", 3)]);
+}
+
+#[test]
+fn scoped_pair_replaced_in_scope() {
+	let mut map = bindings();
+	{
+		let mut scope = map.insert_scoped("x", 9);
+		// Replaces the scoped pair by its first K/V
+		assert_eq!(scope.insert("x", 8), Some(("x", 9)));
+		// Replaces the scoped pair's successor by its second K/V, with one that doesn't conflict with "x"'s restored pair
+		assert_eq!(scope.insert("v", 8), Some(("x", 8)));
+	}
+	assert_eq!(sorted(&map), [("v", 8), ("x", 1), ("y", 2), ("z", 3)]);
+
+	{
+		let mut scope = map.insert_scoped("x", 5);
+		// Takes the second K/V of the pair that will be restored
+		assert_eq!(scope.insert("w", 1), None);
+	}
+	// ("w", 1) isn't the scoped pair, but conflicts with the restored ("x", 1)
+	assert_eq!(sorted(&map), [("v", 8), ("x", 1), ("y", 2), ("z", 3)]);
+}
+
+#[test]
+fn persisted_scope() {
+	let mut map = bindings();
+	map.insert_scoped("x", 2).persist();
+	assert_eq!(sorted(&map), [("x", 2), ("z", 3)]);
+}
@@ -0,0 +1,98 @@
+extern crate bidir_map;
+
+use bidir_map::{BidirMap, ShrinkPolicy};
+
+
+fn burst(map: &mut BidirMap<u32, u32>, n: u32) {
+	for i in 0..n {
+		map.insert(i, i + 1_000_000);
+	}
+}
+
+
+#[test]
+fn off_by_default() {
+	let mut map = BidirMap::new();
+	assert_eq!(map.shrink_policy(), ShrinkPolicy::Never);
+	burst(&mut map, 10_000);
+	let peak = map.capacity();
+	map.retain(|&kv1, _| kv1 < 10);
+	map.clear();
+	assert_eq!(map.capacity(), peak);
+}
+
+#[test]
+fn grow_shrink_cycle() {
+	let mut map = BidirMap::new();
+	map.set_shrink_policy(ShrinkPolicy::WhenBelow{utilization: 0.25, min_capacity: 100});
+	burst(&mut map, 10_000);
+	let peak = map.capacity();
+	assert!(peak >= 10_000);
+
+	// Down to exactly a quarter used
+	let quarter = (peak / 4) as u32;
+	for i in 0..10_000 - quarter {
+		map.remove_by_first(&i);
+		assert_eq!(map.capacity(), peak);
+	}
+	assert_eq!(map.len() * 4, peak);
+
+	map.remove_by_second(&(10_000 - quarter + 1_000_000));
+	assert!(map.capacity() < peak);
+	assert!(map.capacity() >= map.len() * 3 / 2);
+
+	map.retain(|&kv1, _| kv1 >= 9990);
+	assert_eq!(map.len(), 10);
+	assert_eq!(map.capacity(), 100);
+	map.clear();
+	assert_eq!(map.capacity(), 100);
+
+	burst(&mut map, 10_000);
+	assert!(map.capacity() >= 10_000);
+}
+
+#[test]
+fn no_thrashing() {
+	let mut map = BidirMap::new();
+	burst(&mut map, 4096);
+	map.set_shrink_policy(ShrinkPolicy::WhenBelow{utilization: 0.25, min_capacity: 0});
+	map.retain(|&kv1, _| kv1 < 1000);
+	let shrunk = map.capacity();
+	assert!((1000..4096).contains(&shrunk));
+
+	// Hovering around the size doesn't reallocate
+	for round in 0..100 {
+		map.insert(5000 + round, 0);
+		map.swap_remove_index(0);
+		assert_eq!(map.capacity(), shrunk);
+	}
+	for _ in 0..500 {
+		map.shift_remove_index(0);
+	}
+	assert_eq!(map.capacity(), shrunk);
+}
+
+#[test]
+fn setting_shrinks() {
+	let mut map = BidirMap::with_capacity(1000);
+	burst(&mut map, 10);
+	map.set_shrink_policy(ShrinkPolicy::WhenBelow{utilization: 0.5, min_capacity: 16});
+	assert_eq!(map.capacity(), 16);
+
+	map.set_shrink_policy(ShrinkPolicy::Never);
+	burst(&mut map, 100);
+	map.clear();
+	assert!(map.capacity() >= 100);
+}
+
+#[test]
+#[should_panic(expected = "utilization 1.5 isn't at least 0 and below 1")]
+fn utilization_above_one() {
+	BidirMap::<u32, u32>::new().set_shrink_policy(ShrinkPolicy::WhenBelow{utilization: 1.5, min_capacity: 0});
+}
+
+#[test]
+#[should_panic(expected = "utilization NaN isn't at least 0 and below 1")]
+fn utilization_nan() {
+	BidirMap::<u32, u32>::new().set_shrink_policy(ShrinkPolicy::WhenBelow{utilization: f32::NAN, min_capacity: 0});
+}
@@ -0,0 +1,86 @@
+extern crate bidir_map;
+
+mod common;
+
+use bidir_map::{BidirMap, BidirSoaMap};
+use std::cell::Cell;
+use common::Rng;
+
+
+#[test]
+fn matches_bidir_map_order_over_random_operations() {
+	for seed in 1..20u64 {
+		let mut rng = Rng(seed.wrapping_mul(0x9e3779b97f4a7c15));
+		let mut soa = BidirSoaMap::new();
+		let mut vec = BidirMap::new();
+
+		for _ in 0..300 {
+			let (kv1, kv2) = (rng.below(40), rng.below(40));
+			match rng.below(5) {
+				0 => assert_eq!(soa.remove_by_first(&kv1), vec.remove_by_first(&kv1)),
+				1 => assert_eq!(soa.remove_by_second(&kv2), vec.remove_by_second(&kv2)),
+				2 => assert_eq!(soa.get_by_second(&kv2), vec.get_by_second(&kv2)),
+				_ => assert_eq!(soa.insert(kv1, kv2), vec.insert(kv1, kv2)),
+			}
+		}
+
+		// swap_remove parity: not just the same pairs, but in the same order
+		let soa_pairs: Vec<_> = soa.iter().map(|(&kv1, &kv2)| (kv1, kv2)).collect();
+		let vec_pairs: Vec<_> = vec.iter().cloned().collect();
+		assert_eq!(soa_pairs, vec_pairs);
+		assert_eq!(soa.clone().into_iter().collect::<Vec<_>>(), vec_pairs);
+		assert_eq!(BidirMap::from(soa), vec);
+	}
+}
+
+#[test]
+fn mutation_through_iterators() {
+	let mut map: BidirSoaMap<_, _> = (0..5).map(|i| (i, i * 10)).collect();
+	for (_, kv2) in map.iter_mut() {
+		*kv2 += 1;
+	}
+	*map.get_mut_by_second(&41).unwrap() = 7;
+	assert_eq!(map.second_slice(), [1, 11, 21, 31, 41]);
+	assert_eq!(map.first_slice(), [0, 1, 2, 3, 7]);
+	assert_eq!(map.get_by_first(&7), Some(&41));
+}
+
+
+thread_local!(static TOUCHED: Cell<(usize, usize)> = const { Cell::new((usize::MAX, 0)) });
+
+/// A key that records the span of memory it's compared against
+#[derive(Clone, Copy, Debug)]
+struct Probe(u32);
+
+impl PartialEq for Probe {
+	fn eq(&self, other: &Probe) -> bool {
+		let addr = other as *const Probe as usize;
+		TOUCHED.with(|t| {
+			let (lo, hi) = t.get();
+			t.set((lo.min(addr), hi.max(addr + std::mem::size_of::<Probe>())));
+		});
+		self.0 == other.0
+	}
+}
+
+fn bytes_touched<F: FnOnce()>(f: F) -> usize {
+	TOUCHED.with(|t| t.set((usize::MAX, 0)));
+	f();
+	TOUCHED.with(|t| {
+		let (lo, hi) = t.get();
+		hi - lo
+	})
+}
+
+#[test]
+fn first_column_scans_touch_only_first_keys() {
+	const N: u32 = 100;
+	let vec: BidirMap<_, _> = (0..N).map(|i| (Probe(i), [i as u8; 256])).collect();
+	let soa: BidirSoaMap<_, _> = (0..N).map(|i| (Probe(i), [i as u8; 256])).collect();
+
+	let vec_bytes = bytes_touched(|| assert!(vec.get_by_first(&Probe(N - 1)).is_some()));
+	let soa_bytes = bytes_touched(|| assert!(soa.get_by_first(&Probe(N - 1)).is_some()));
+
+	assert_eq!(soa_bytes, N as usize * std::mem::size_of::<Probe>());
+	assert!(vec_bytes > 50 * soa_bytes, "{} vs {}", vec_bytes, soa_bytes);
+}
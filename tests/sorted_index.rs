@@ -0,0 +1,119 @@
+extern crate bidir_map;
+
+mod common;
+
+use bidir_map::BidirMap;
+use common::{comparisons, pairs, pseudo_random, Counted};
+
+
+/// Applies the same random operations to a plain map and an indexed one, checking they agree throughout
+fn differential(hash_index_too: bool) {
+	let mut state = 0xd1b5_4a32_d192_ed03u64;
+	let mut next = move || pseudo_random(&mut state);
+
+	let mut plain = BidirMap::new();
+	let mut indexed = BidirMap::new();
+	indexed.build_sorted_indexes();
+	if hash_index_too {
+		indexed.build_index();
+	}
+
+	let mut fresh = 1000;
+	for _ in 0..5000 {
+		let kv1 = (next() % 40) as u32;
+		let kv2 = (next() % 40) as u32;
+		match next() % 10 {
+			0 => assert_eq!(indexed.remove_by_first_sorted(&kv1), plain.remove_by_first(&kv1)),
+			1 => assert_eq!(indexed.remove_by_second_sorted(&kv2), plain.remove_by_second(&kv2)),
+			2 => assert_eq!(indexed.remove_by_first(&kv1), plain.remove_by_first(&kv1)),
+			3 => {
+				let idx = kv1 as usize % (plain.len() + 1);
+				assert_eq!(indexed.swap_remove_index(idx), plain.swap_remove_index(idx));
+			}
+			4 => {
+				let idx = kv1 as usize % (plain.len() + 1);
+				assert_eq!(indexed.shift_remove_index(idx), plain.shift_remove_index(idx));
+			}
+			5 => {
+				// Changing a key in place leaves the indexes to be rebuilt
+				fresh += 1;
+				if let Some(kv2) = indexed.get_mut_by_first(&kv1) {
+					*kv2 = fresh;
+				}
+				if let Some(kv2) = plain.get_mut_by_first(&kv1) {
+					*kv2 = fresh;
+				}
+			}
+			_ => assert_eq!(indexed.insert(kv1, kv2), plain.insert(kv1, kv2)),
+		}
+
+		assert_eq!(pairs(&indexed), pairs(&plain));
+		assert_eq!(indexed.get_by_first_sorted(&kv1), plain.get_by_first(&kv1));
+		assert_eq!(indexed.get_by_second_sorted(&kv2), plain.get_by_second(&kv2));
+		assert_eq!(indexed.contains_first_key_sorted(&kv2), plain.contains_first_key(&kv2));
+		assert_eq!(indexed.contains_second_key_sorted(&fresh), plain.contains_second_key(&fresh));
+	}
+
+	// Every pair is found through the indexes once they're up to date
+	indexed.insert(5000, 5000);
+	plain.insert(5000, 5000);
+	for &(kv1, kv2) in plain.iter() {
+		assert_eq!(indexed.get_by_first_sorted(&kv1), Some(&kv2));
+		assert_eq!(indexed.get_by_second_sorted(&kv2), Some(&kv1));
+	}
+}
+
+#[test]
+fn matches_unindexed() {
+	differential(false);
+}
+
+#[test]
+fn matches_unindexed_alongside_hash_index() {
+	differential(true);
+}
+
+#[test]
+fn lifecycle() {
+	let mut map: BidirMap<String, u32> = vec![("c".to_string(), 3), ("a".to_string(), 1), ("b".to_string(), 2)].into_iter().collect();
+	assert!(!map.has_sorted_indexes());
+	assert_eq!(map.get_by_first_sorted("a"), Some(&1));
+
+	map.build_sorted_indexes();
+	assert!(map.has_sorted_indexes());
+	assert_eq!(map.get_by_first_sorted("b"), Some(&2));
+	assert_eq!(map.get_by_second_sorted(&3).map(String::as_str), Some("c"));
+	assert_eq!(map.get_by_first_sorted("d"), None);
+
+	// Keys changed in place are still found, by scanning until the next mutation rebuilds the indexes
+	*map.get_mut_by_second(&1).unwrap() = "z".to_string();
+	assert_eq!(map.get_by_first_sorted("z"), Some(&1));
+	map.insert("d".to_string(), 4);
+	assert_eq!(map.get_by_first_sorted("z"), Some(&1));
+	assert_eq!(map.get_by_first_sorted("a"), None);
+
+	map.clear();
+	assert_eq!(map.get_by_first_sorted("d"), None);
+	map.insert("e".to_string(), 5);
+	assert_eq!(map.get_by_second_sorted(&5).map(String::as_str), Some("e"));
+
+	map.drop_sorted_indexes();
+	assert!(!map.has_sorted_indexes());
+	assert_eq!(map.get_by_second_sorted(&5).map(String::as_str), Some("e"));
+}
+
+#[test]
+fn plain_lookups_use_the_indexes() {
+	let mut map: BidirMap<Counted, u32> = (0..256).map(|i| (Counted(i), i + 1000)).collect();
+	map.build_sorted_indexes();
+	assert!(comparisons(|| assert_eq!(map.get_by_first(&Counted(255)), Some(&1255))) <= 9);
+	assert!(comparisons(|| assert!(!map.contains_first_key(&Counted(1000)))) <= 9);
+	assert_eq!(map.get_by_second(&1100), Some(&Counted(100)));
+	assert!(comparisons(|| assert_eq!(map.remove_by_first(&Counted(7)), Some((Counted(7), 1007)))) <= 40);
+	assert_eq!(map.remove_by_second(&1008), Some((Counted(8), 1008)));
+	assert_eq!(map.get_by_first(&Counted(255)), Some(&1255));
+	assert_eq!(map.get_by_first(&Counted(8)), None);
+
+	map.drop_sorted_indexes();
+	assert!(comparisons(|| assert!(!map.contains_first_key(&Counted(1000)))) >= 254);
+}
@@ -0,0 +1,86 @@
+extern crate bidir_map;
+
+mod common;
+
+use bidir_map::{BidirMap, Column, DuplicateKeyError, FromSortedVecError};
+use common::{pairs, pseudo_random};
+
+
+fn is_sorted(map: &BidirMap<u32, char>) -> bool {
+	pairs(map).windows(2).all(|w| w[0].0 < w[1].0)
+}
+
+#[test]
+fn positions() {
+	let mut map = BidirMap::new();
+	assert_eq!(map.insert_sorted(50, 'm'), (0, None));
+	assert_eq!(map.insert_sorted(10, 'a'), (0, None));
+	assert_eq!(map.insert_sorted(90, 'z'), (2, None));
+	assert_eq!(map.insert_sorted(30, 'f'), (1, None));
+	assert_eq!(map.insert_sorted(70, 't'), (3, None));
+	assert_eq!(pairs(&map), [(10, 'a'), (30, 'f'), (50, 'm'), (70, 't'), (90, 'z')]);
+}
+
+#[test]
+fn replacing() {
+	let mut map: BidirMap<_, _> = vec![(10, 'a'), (30, 'f'), (50, 'm'), (70, 't')].into_iter().collect();
+	map.build_index();
+
+	// Same first K/V: replaced in place
+	assert_eq!(map.insert_sorted(30, 'g'), (1, Some((30, 'f'))));
+	assert_eq!(pairs(&map), [(10, 'a'), (30, 'g'), (50, 'm'), (70, 't')]);
+
+	// Same second K/V before the new position
+	assert_eq!(map.insert_sorted(60, 'a'), (2, Some((10, 'a'))));
+	assert_eq!(pairs(&map), [(30, 'g'), (50, 'm'), (60, 'a'), (70, 't')]);
+
+	// Same second K/V after the new position
+	assert_eq!(map.insert_sorted(40, 't'), (1, Some((70, 't'))));
+	assert_eq!(pairs(&map), [(30, 'g'), (40, 't'), (50, 'm'), (60, 'a')]);
+
+	// Both, with different pairs: the one with the first K/V is returned
+	assert_eq!(map.insert_sorted(60, 'g'), (2, Some((60, 'a'))));
+	assert_eq!(pairs(&map), [(40, 't'), (50, 'm'), (60, 'g')]);
+
+	// Both, with the same pair
+	assert_eq!(map.insert_sorted(50, 'm'), (1, Some((50, 'm'))));
+
+	assert!(is_sorted(&map));
+	assert_eq!(map.get_by_second_indexed(&'g'), Some(&60));
+	assert_eq!(map.get_by_first_indexed(&40), Some(&'t'));
+	assert_eq!(map.validate(), Ok(()));
+}
+
+#[test]
+fn stays_sorted() {
+	let mut map = BidirMap::new();
+	let mut state = 0x9e3779b97f4a7c15u64;
+	for _ in 0..500 {
+		pseudo_random(&mut state);
+		let kv1 = (state % 64) as u32;
+		let kv2 = (b'0' + (state >> 32) as u8 % 64) as char;
+		let (idx, _) = map.insert_sorted(kv1, kv2);
+		assert_eq!(map.iter().nth(idx), Some(&(kv1, kv2)));
+		assert!(is_sorted(&map));
+	}
+	assert_eq!(map.validate(), Ok(()));
+}
+
+#[test]
+fn from_sorted_vec() {
+	let mut map = BidirMap::from_sorted_vec(vec![(10, 'c'), (20, 'a'), (30, 'b')]).unwrap();
+	assert_eq!(map.get_by_second(&'a'), Some(&20));
+	assert_eq!(map.validate(), Ok(()));
+	assert_eq!(map.insert_sorted(15, 'd'), (1, None));
+	assert!(is_sorted(&map));
+
+	assert_eq!(BidirMap::from_sorted_vec(vec![(1, 'a'), (3, 'c'), (2, 'b')]), Err(FromSortedVecError::Unsorted{index: 2}));
+	assert_eq!(BidirMap::from_sorted_vec(vec![(1, 'a'), (2, 'b'), (2, 'c')]),
+	           Err(FromSortedVecError::Duplicate(DuplicateKeyError{column: Column::First, index: Some(2)})));
+	assert_eq!(BidirMap::from_sorted_vec(vec![(1, 'z'), (2, 'b'), (3, 'z')]),
+	           Err(FromSortedVecError::Duplicate(DuplicateKeyError{column: Column::Second, index: Some(2)})));
+	assert_eq!(BidirMap::<u32, char>::from_sorted_vec(vec![]).map(|map| map.len()), Ok(0));
+
+	let unchecked = unsafe { BidirMap::from_sorted_vec_unchecked(vec![(10, 'c'), (20, 'a'), (30, 'b')]) };
+	assert_eq!(pairs(&unchecked), [(10, 'c'), (20, 'a'), (30, 'b')]);
+}
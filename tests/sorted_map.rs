@@ -0,0 +1,68 @@
+extern crate bidir_map;
+
+mod common;
+
+use bidir_map::{BidirMap, BidirSortedMap};
+use bidir_map::{Column, DuplicateKeyError, FromSortedVecError};
+use common::Rng;
+
+
+fn assert_same(sorted: &BidirSortedMap<u64, u64>, vec: &BidirMap<u64, u64>) {
+	let mut expected: Vec<_> = vec.iter().cloned().collect();
+	expected.sort();
+	let actual: Vec<_> = sorted.iter().cloned().collect();
+	assert_eq!(actual, expected);
+
+	for &(kv1, kv2) in &expected {
+		assert_eq!(sorted.get_by_first(&kv1), Some(&kv2));
+		assert_eq!(sorted.get_by_second(&kv2), Some(&kv1));
+	}
+}
+
+#[test]
+fn matches_bidir_map_over_random_operations() {
+	for seed in 1..20u64 {
+		let mut rng = Rng(seed.wrapping_mul(0x9e3779b97f4a7c15));
+		let mut sorted = BidirSortedMap::new();
+		let mut vec = BidirMap::new();
+
+		for _ in 0..300 {
+			let (kv1, kv2) = (rng.below(40), rng.below(40));
+			match rng.below(4) {
+				0 => assert_eq!(sorted.remove_by_first(&kv1), vec.remove_by_first(&kv1)),
+				1 => assert_eq!(sorted.remove_by_second(&kv2), vec.remove_by_second(&kv2)),
+				_ => assert_eq!(sorted.insert(kv1, kv2), vec.insert(kv1, kv2)),
+			}
+			assert_eq!(sorted.contains_first_key(&kv1), vec.contains_first_key(&kv1));
+			assert_eq!(sorted.contains_second_key(&kv2), vec.contains_second_key(&kv2));
+		}
+		assert_same(&sorted, &vec);
+	}
+}
+
+#[test]
+fn from_sorted_vec() {
+	let map = BidirSortedMap::from_sorted_vec(vec![(1, 30), (2, 10), (5, 20)]).unwrap();
+	assert_eq!(map.get_by_second(&20), Some(&5));
+	assert_eq!(map.first_col().cloned().collect::<Vec<_>>(), [1, 2, 5]);
+
+	assert_eq!(BidirSortedMap::from_sorted_vec(vec![(1, 1), (3, 3), (2, 2)]), Err(FromSortedVecError::Unsorted{index: 2}));
+	assert_eq!(BidirSortedMap::from_sorted_vec(vec![(1, 1), (1, 2)]), Err(FromSortedVecError::Duplicate(DuplicateKeyError{column: Column::First, index: Some(1)})));
+	assert_eq!(BidirSortedMap::from_sorted_vec(vec![(1, 5), (2, 1), (3, 5)]), Err(FromSortedVecError::Duplicate(DuplicateKeyError{column: Column::Second, index: Some(2)})));
+	assert_eq!(FromSortedVecError::Unsorted{index: 2}.to_string(), "pair 2 is out of order by the first K/V");
+}
+
+#[test]
+fn converts_to_and_from_bidir_map() {
+	let mut map = BidirMap::new();
+	map.insert(3, 10);
+	map.insert(1, 30);
+	map.insert(2, 20);
+
+	let sorted = BidirSortedMap::from(map.clone());
+	assert_same(&sorted, &map);
+
+	let back = BidirMap::from(sorted);
+	assert_eq!(back.first_col().cloned().collect::<Vec<_>>(), [1, 2, 3]);
+	assert_eq!(back.get_by_second(&10), Some(&3));
+}
@@ -0,0 +1,73 @@
+extern crate bidir_map;
+
+use bidir_map::{BidirMap, Column};
+
+
+fn letters() -> BidirMap<u32, char> {
+	vec![(1, 'a'), (2, 'b'), (3, 'c'), (4, 'd'), (5, 'e')].into_iter().collect()
+}
+
+fn contents(map: &BidirMap<u32, char>) -> Vec<(u32, char)> {
+	map.iter().cloned().collect()
+}
+
+
+#[test]
+fn start() {
+	let mut map = letters();
+	assert_eq!(map.splice(..2, vec![(10, 'x')]).unwrap().collect::<Vec<_>>(), [(1, 'a'), (2, 'b')]);
+	assert_eq!(contents(&map), [(10, 'x'), (3, 'c'), (4, 'd'), (5, 'e')]);
+	// An empty range inserts before the rest
+	assert_eq!(map.splice(0..0, vec![(11, 'y')]).unwrap().len(), 0);
+	assert_eq!(contents(&map)[..2], [(11, 'y'), (10, 'x')]);
+}
+
+#[test]
+fn middle() {
+	let mut map = letters();
+	// The replacements may reuse the K/Vs of the pairs replaced
+	let removed = map.splice(1..=3, vec![(4, 'b'), (2, 'c'), (6, 'f'), (7, 'g')]).unwrap();
+	assert_eq!(removed.collect::<Vec<_>>(), [(2, 'b'), (3, 'c'), (4, 'd')]);
+	assert_eq!(contents(&map), [(1, 'a'), (4, 'b'), (2, 'c'), (6, 'f'), (7, 'g'), (5, 'e')]);
+	assert_eq!(map.get_by_second(&'c'), Some(&2));
+
+	// Dropping the iterator unconsumed still leaves the map spliced
+	map.splice(2..4, vec![(8, 'h')]).unwrap().next();
+	assert_eq!(contents(&map), [(1, 'a'), (4, 'b'), (8, 'h'), (7, 'g'), (5, 'e')]);
+}
+
+#[test]
+fn end() {
+	let mut map = letters();
+	assert_eq!(map.splice(3.., vec![(6, 'f')]).unwrap().rev().collect::<Vec<_>>(), [(5, 'e'), (4, 'd')]);
+	assert_eq!(contents(&map), [(1, 'a'), (2, 'b'), (3, 'c'), (6, 'f')]);
+	assert_eq!(map.splice(4.., vec![(7, 'g')]).unwrap().len(), 0);
+	assert_eq!(contents(&map)[4], (7, 'g'));
+}
+
+#[test]
+fn conflicting() {
+	let mut map = letters();
+	let err = map.splice(1..3, vec![(6, 'f'), (5, 'g')]).unwrap_err();
+	assert_eq!((err.index, err.column, err.earlier, err.pair), (1, Column::First, None, (5, 'g')));
+	let err = map.splice(1..3, vec![(6, 'f'), (7, 'f')]).unwrap_err();
+	assert_eq!((err.index, err.column, err.earlier, err.pair), (1, Column::Second, Some(0), (7, 'f')));
+	assert_eq!(contents(&map), contents(&letters()));
+}
+
+#[test]
+fn empty_replacement() {
+	let mut map = letters();
+	map.build_index();
+	assert_eq!(map.splice(1..4, None).unwrap().collect::<Vec<_>>(), [(2, 'b'), (3, 'c'), (4, 'd')]);
+	assert_eq!(contents(&map), [(1, 'a'), (5, 'e')]);
+	assert_eq!(map.get_by_second_indexed(&'e'), Some(&5));
+	assert!(map.splice(.., None).unwrap().eq(vec![(1, 'a'), (5, 'e')]));
+	assert!(map.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "range ends at 6 past the map's length of 5")]
+fn out_of_range() {
+	let _ = letters().splice(2..6, None);
+}
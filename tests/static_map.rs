@@ -0,0 +1,66 @@
+#[macro_use]
+extern crate bidir_map;
+
+use bidir_map::StaticBidirMap;
+
+
+static_bidir_map! {
+	/// HTTP-ish methods by opcode, written out of order on purpose
+	static METHODS: StaticBidirMap<u8, &'static str> = {
+		7 => "PATCH",
+		1 => "GET",
+		4 => "DELETE",
+		2 => "PUT",
+		9 => "HEAD",
+	};
+}
+
+static_bidir_map! {
+	pub static EMPTY: StaticBidirMap<i64, char> = {};
+}
+
+static_bidir_map! {
+	static MAGICS: StaticBidirMap<&'static [u8], u32> = {
+		b"\x89PNG" => 0x89504e47,
+		b"GIF8" => 0x47494638,
+		b"\xff\xd8" => 0xffd8,
+		b"" => 0,
+	};
+}
+
+
+#[test]
+fn lookups_both_ways() {
+	for &(opcode, name) in METHODS.as_slice() {
+		assert_eq!(METHODS.get_by_first(&opcode), Some(&name));
+		assert_eq!(METHODS.get_by_second(name), Some(&opcode));
+	}
+	for absent in [0, 3, 5, 6, 8, 10, 255] {
+		assert!(!METHODS.contains_first_key(&absent));
+	}
+	for absent in ["", "GETS", "get", "A", "Z"] {
+		assert!(!METHODS.contains_second_key(absent));
+	}
+	assert_eq!(METHODS.len(), 5);
+	assert_eq!(METHODS.iter().map(|kvs| kvs.0).collect::<Vec<_>>(), [7, 1, 4, 2, 9]);
+
+	assert_eq!(MAGICS.get_by_second(&0), Some(&&b""[..]));
+	assert_eq!(MAGICS.get_by_first(&b"\xff\xd8"[..]), Some(&0xffd8));
+	assert_eq!(MAGICS.get_by_first(&b"\xff"[..]), None);
+}
+
+#[test]
+fn empty() {
+	assert!(EMPTY.is_empty());
+	assert_eq!(EMPTY.get_by_first(&0), None);
+	assert_eq!(EMPTY.get_by_second(&'a'), None);
+	assert_eq!(EMPTY.into_iter().count(), 0);
+}
+
+#[test]
+fn from_parts() {
+	const PAIRS: &[(u32, char)] = &[(3, 'a'), (1, 'c'), (2, 'b')];
+	static MAP: StaticBidirMap<u32, char> = StaticBidirMap::from_parts(PAIRS, &[1, 2, 0], &[0, 2, 1]);
+	assert_eq!(MAP.get_by_first(&2), Some(&'b'));
+	assert_eq!(MAP.get_by_second(&'c'), Some(&1));
+}
@@ -0,0 +1,68 @@
+extern crate bidir_map;
+
+use bidir_map::{BidirMap, MapStats};
+
+
+#[test]
+fn scripted() {
+	let mut map = BidirMap::new();
+	map.insert(1, "a");
+	map.insert(2, "b");
+	map.insert(3, "c");
+	assert_eq!(map.stats(),
+	           MapStats{
+	               inserts: 3,
+	               comparisons: 6,
+	               ..MapStats::default()
+	           });
+
+	map.reset_stats();
+	assert_eq!(map.get_by_first(&2), Some(&"b"));
+	assert_eq!(map.get_by_second(&"c"), Some(&3));
+	assert!(!map.contains_first_key(&9));
+	assert_eq!(map.get_index_of_second(&"a"), Some(0));
+	assert_eq!(map.remove_by_first(&1), Some((1, "a")));
+	assert_eq!(map.insert(3, "b"), Some((3, "c")));
+	assert_eq!(map.stats(),
+	           MapStats{
+	               lookups_by_first: 2,
+	               lookups_by_second: 2,
+	               hits: 3,
+	               misses: 1,
+	               inserts: 1,
+	               removals: 3,
+	               comparisons: 13,
+	           });
+
+	map.clear();
+	assert_eq!(map.stats().removals, 4);
+}
+
+#[test]
+fn mutable_lookups() {
+	let mut map: BidirMap<_, _> = vec![(1, "a"), (2, "b")].into_iter().collect();
+	*map.get_mut_by_first(&2).unwrap() = "c";
+	assert_eq!(map.get_mut_by_second(&"b"), None);
+	assert_eq!(map.stats(),
+	           MapStats{
+	               lookups_by_first: 1,
+	               lookups_by_second: 1,
+	               hits: 1,
+	               misses: 1,
+	               comparisons: 4,
+	               ..MapStats::default()
+	           });
+}
+
+#[test]
+fn clone_and_reset() {
+	let mut map = BidirMap::new();
+	map.insert("a", 1);
+	assert_eq!(map.get_by_first(&"a"), Some(&1));
+
+	let mut copy = map.clone();
+	assert_eq!(copy.stats(), map.stats());
+	copy.reset_stats();
+	assert_eq!(copy.stats(), MapStats::default());
+	assert_eq!(map.stats().lookups_by_first, 1);
+}
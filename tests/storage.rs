@@ -0,0 +1,171 @@
+extern crate bidir_map;
+
+use bidir_map::BidirMap;
+use bidir_map::storage::{InlineStorage, Storage};
+use std::cell::Cell;
+use std::ops::{Deref, DerefMut};
+use std::rc::Rc;
+
+
+/// A `Vec` counting pushes, relying on the default `retain()`
+#[derive(Default)]
+struct CountingStorage {
+	pairs: Vec<(u32, char)>,
+	pushes: usize,
+}
+
+impl Deref for CountingStorage {
+	type Target = [(u32, char)];
+
+	fn deref(&self) -> &[(u32, char)] {
+		&self.pairs
+	}
+}
+
+impl DerefMut for CountingStorage {
+	fn deref_mut(&mut self) -> &mut [(u32, char)] {
+		&mut self.pairs
+	}
+}
+
+impl IntoIterator for CountingStorage {
+	type Item = (u32, char);
+	type IntoIter = std::vec::IntoIter<(u32, char)>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.pairs.into_iter()
+	}
+}
+
+impl Storage<u32, char> for CountingStorage {
+	fn push(&mut self, kvs: (u32, char)) {
+		self.pushes += 1;
+		self.pairs.push(kvs)
+	}
+
+	fn swap_remove(&mut self, idx: usize) -> (u32, char) {
+		self.pairs.swap_remove(idx)
+	}
+
+	fn remove(&mut self, idx: usize) -> (u32, char) {
+		self.pairs.remove(idx)
+	}
+
+	fn clear(&mut self) {
+		self.pairs.clear()
+	}
+
+	fn capacity(&self) -> usize {
+		self.pairs.capacity()
+	}
+}
+
+#[derive(Debug)]
+struct DropCounted(u32, Rc<Cell<usize>>);
+
+impl PartialEq for DropCounted {
+	fn eq(&self, other: &Self) -> bool {
+		self.0 == other.0
+	}
+}
+
+impl Drop for DropCounted {
+	fn drop(&mut self) {
+		self.1.set(self.1.get() + 1);
+	}
+}
+
+
+#[test]
+fn custom_storage() {
+	let mut map = BidirMap::with_storage(CountingStorage::default());
+	map.extend(vec![(1, 'a'), (2, 'b'), (3, 'c'), (4, 'd')]);
+	assert_eq!(map.insert(2, 'B'), Some((2, 'b')));
+	map.retain(|&kv1, _| kv1 % 2 == 0);
+	assert_eq!(map.iter().cloned().collect::<Vec<_>>(), [(4, 'd'), (2, 'B')]);
+	assert_eq!(map.shift_remove_index(0), Some((4, 'd')));
+	assert_eq!(map.into_storage().pushes, 5);
+}
+
+#[test]
+fn inline_storage_capacity() {
+	let mut map: BidirMap<u8, u8, InlineStorage<u8, u8, 2>> = BidirMap::with_storage(InlineStorage::new());
+	assert_eq!(map.try_insert_within_capacity(1, 1), Ok(None));
+	assert_eq!(map.try_insert_within_capacity(2, 2), Ok(None));
+	assert_eq!(map.try_insert_within_capacity(3, 3), Err((3, 3)));
+	assert_eq!(map.try_insert_within_capacity(3, 2), Ok(Some((2, 2))));
+	assert_eq!(format!("{:?}", map), "BidirMap { cont: [(1, 1), (3, 2)] }");
+
+	let clone = map.clone();
+	assert_eq!(clone, map);
+	assert_eq!(clone.get_by_second(&2), Some(&3));
+}
+
+#[test]
+#[should_panic(expected = "InlineStorage is full at 1 pairs")]
+fn inline_storage_overflow() {
+	let mut map = BidirMap::with_storage(InlineStorage::<_, _, 1>::new());
+	map.insert(1, 'a');
+	map.insert(2, 'b');
+}
+
+#[test]
+fn inline_storage_drops() {
+	let drops = Rc::new(Cell::new(0));
+	let mut map = BidirMap::with_storage(InlineStorage::<_, _, 8>::new());
+	for i in 0..6 {
+		map.insert(DropCounted(i, drops.clone()), DropCounted(i + 100, drops.clone()));
+	}
+	assert_eq!(drops.get(), 0);
+
+	drop(map.swap_remove_index(1));
+	drop(map.shift_remove_index(0));
+	assert_eq!(drops.get(), 4);
+	map.retain(|kv1, _| kv1.0 != 3);
+	assert_eq!(drops.get(), 6);
+	assert_eq!(map.iter().map(|kvs| kvs.0 .0).collect::<Vec<_>>(), [5, 2, 4]);
+	drop(map);
+	assert_eq!(drops.get(), 12);
+}
+
+#[test]
+fn into_iter_and_collect() {
+	let map: BidirMap<u32, char, CountingStorage> = vec![(1, 'a'), (2, 'b'), (3, 'c')].into_iter().collect();
+	assert_eq!(map.get_by_second(&'b'), Some(&2));
+	let mut iter = map.into_iter();
+	assert_eq!(iter.len(), 3);
+	assert_eq!(iter.next(), Some((1, 'a')));
+	assert_eq!(iter.next_back(), Some((3, 'c')));
+	assert_eq!(iter.collect::<Vec<_>>(), [(2, 'b')]);
+
+	let map: BidirMap<u8, u8, InlineStorage<u8, u8, 4>> = [(1, 1), (2, 2), (3, 3)].iter().collect();
+	assert_eq!(map.capacity(), 4);
+	let mut iter = map.into_iter();
+	assert_eq!(iter.next_back(), Some((3, 3)));
+	assert_eq!(iter.as_slice(), [(1, 1), (2, 2)]);
+	assert_eq!(iter.clone().rev().collect::<Vec<_>>(), [(2, 2), (1, 1)]);
+	assert_eq!(iter.collect::<Vec<_>>(), [(1, 1), (2, 2)]);
+
+	// The default storage's iterator is `Vec`'s own
+	let map: BidirMap<u8, u8> = (0..4).map(|i| (i, i * 2)).collect();
+	let mut iter = map.into_iter();
+	assert_eq!(iter.next(), Some((0, 0)));
+	assert_eq!(iter.as_slice(), [(1, 2), (2, 4), (3, 6)]);
+	assert_eq!(iter.rev().collect::<Vec<_>>(), [(3, 6), (2, 4), (1, 2)]);
+}
+
+#[test]
+fn into_iter_drops_the_rest() {
+	let drops = Rc::new(Cell::new(0));
+	let mut map = BidirMap::with_storage(InlineStorage::<_, _, 8>::new());
+	for i in 0..4 {
+		map.insert(DropCounted(i, drops.clone()), DropCounted(i + 100, drops.clone()));
+	}
+	let mut iter = map.into_iter();
+	assert_eq!(iter.next().map(|kvs| kvs.0 .0), Some(0));
+	assert_eq!(drops.get(), 2);
+	assert_eq!(iter.next_back().map(|kvs| kvs.0 .0), Some(3));
+	assert_eq!(drops.get(), 4);
+	drop(iter);
+	assert_eq!(drops.get(), 8);
+}
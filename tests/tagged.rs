@@ -0,0 +1,70 @@
+extern crate bidir_map;
+
+use bidir_map::{BidirMap, First, Second, TaggedBidirMap};
+
+
+fn aliases() -> TaggedBidirMap<String, String> {
+	vec![("ls", "list"), ("rm", "remove"), ("mv", "move")]
+		.into_iter()
+		.map(|(short, long)| (First(short.to_string()), Second(long.to_string())))
+		.collect()
+}
+
+
+#[test]
+fn lookups() {
+	let map = aliases();
+	assert_eq!(map.get_by_first(First("rm")), Some(Second(&"remove".to_string())));
+	assert_eq!(map.get_by_second(Second("move")), Some(First(&"mv".to_string())));
+	assert_eq!(map.get_by_first(First("remove")), None);
+	assert_eq!(map.get_by_second(Second("rm")), None);
+	assert!(map.contains_first_key(First("ls")));
+	assert!(!map.contains_second_key(Second("ls")));
+
+	let long = map.get_by_first(First("ls")).unwrap();
+	assert_eq!(long.len(), 4);
+	assert_eq!(map.get_by_second(long.as_deref()), Some(First(&"ls".to_string())));
+}
+
+#[test]
+fn edits() {
+	let mut map = aliases();
+	assert_eq!(map.insert(First("rm".to_string()), Second("delete".to_string())),
+	           Some((First("rm".to_string()), Second("remove".to_string()))));
+	assert_eq!(map.remove_by_second(Second("delete")), Some((First("rm".to_string()), Second("delete".to_string()))));
+	assert_eq!(map.remove_by_first(First("mv")).map(|(_, long)| long.into_inner()), Some("move".to_string()));
+	assert_eq!(map.len(), 1);
+
+	map.extend(vec![(First("cp".to_string()), Second("copy".to_string()))]);
+	let pairs: Vec<_> = map.iter().map(|(short, long)| format!("{}={}", *short, *long)).collect();
+	assert_eq!(pairs, ["ls=list", "cp=copy"]);
+
+	map.clear();
+	assert!(map.is_empty());
+}
+
+#[test]
+fn untagged_interop() {
+	let mut plain = BidirMap::new();
+	plain.insert(1u32, 2u32);
+	plain.insert(3, 4);
+
+	let mut tagged = TaggedBidirMap::from(plain);
+	assert_eq!(tagged.get_by_second(Second(&2)), Some(First(&1)));
+	tagged.insert(First(5), Second(6));
+	assert_eq!(tagged.inner().get_by_first(&5), Some(&6));
+
+	let plain: BidirMap<_, _> = tagged.into();
+	assert_eq!(plain, [(1, 2), (3, 4), (5, 6)]);
+}
+
+#[test]
+fn tags() {
+	let mut kv = First(String::from("a"));
+	kv.push('b');
+	assert_eq!(*kv, "ab");
+	assert_eq!(kv.as_deref(), First("ab"));
+	assert_eq!(kv.as_ref(), First(&"ab".to_string()));
+	assert_eq!(Second::from(7).into_inner(), 7);
+	assert!(First(1) < First(2));
+}
@@ -0,0 +1,80 @@
+#[macro_use]
+extern crate bidir_map;
+
+use bidir_map::{BidirBTreeMap, BidirHashMap, BidirMap, BidirSoaMap, BidirSortedMap, HandleBidirMap, MruBidirMap, PersistentBidirMap};
+use bidir_map::storage::InlineStorage;
+
+
+bidir_map_conformance_tests! {
+	vec_map: BidirMap<u32, String>,
+	new: BidirMap::new,
+	first: |i| i as u32,
+	second: |i| format!("#{}", i),
+}
+
+bidir_map_conformance_tests! {
+	vec_map_indexed: BidirMap<String, i64>,
+	new: || {
+		let mut map = BidirMap::new();
+		map.build_index();
+		map
+	},
+	first: |i: usize| i.to_string(),
+	second: |i| -(i as i64),
+}
+
+bidir_map_conformance_tests! {
+	btree_map: BidirBTreeMap<u32, String>,
+	new: BidirBTreeMap::new,
+	first: |i| i as u32,
+	second: |i| format!("#{}", i),
+}
+
+bidir_map_conformance_tests! {
+	hash_map: BidirHashMap<u32, String>,
+	new: BidirHashMap::new,
+	first: |i| i as u32,
+	second: |i| format!("#{}", i),
+}
+
+bidir_map_conformance_tests! {
+	soa_map: BidirSoaMap<u32, char>,
+	new: BidirSoaMap::new,
+	first: |i| i as u32,
+	second: |i| (b'a' + i as u8) as char,
+}
+
+bidir_map_conformance_tests! {
+	sorted_map: BidirSortedMap<u32, String>,
+	new: BidirSortedMap::new,
+	first: |i| 1000 - i as u32,
+	second: |i| format!("#{}", i),
+}
+
+bidir_map_conformance_tests! {
+	handle_map: HandleBidirMap<u32, String>,
+	new: HandleBidirMap::new,
+	first: |i| i as u32,
+	second: |i| format!("#{}", i),
+}
+
+bidir_map_conformance_tests! {
+	mru_map: MruBidirMap<u32, String>,
+	new: MruBidirMap::new,
+	first: |i| i as u32,
+	second: |i| format!("#{}", i),
+}
+
+bidir_map_conformance_tests! {
+	persistent_map: PersistentBidirMap<u32, String>,
+	new: PersistentBidirMap::new,
+	first: |i| i as u32,
+	second: |i| format!("#{}", i),
+}
+
+bidir_map_conformance_tests! {
+	inline_storage_map: BidirMap<u32, String, InlineStorage<u32, String, 64>>,
+	new: || BidirMap::with_storage(InlineStorage::new()),
+	first: |i| i as u32,
+	second: |i| format!("#{}", i),
+}
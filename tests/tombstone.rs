@@ -0,0 +1,93 @@
+extern crate bidir_map;
+
+mod common;
+
+use bidir_map::{BidirMap, TombstoneBidirMap};
+use common::pseudo_random;
+
+
+#[test]
+fn churn_matches_reference() {
+	let mut seed = 0x0123_4567_89ab_cdef;
+	let mut map = TombstoneBidirMap::new();
+	let mut reference = BidirMap::new();
+	// The live pairs in insertion order, as the tombstone map must keep them
+	let mut order: Vec<(u32, u32)> = Vec::new();
+	let mut compacted = false;
+
+	for step in 0..5000 {
+		let kv1 = (pseudo_random(&mut seed) % 100) as u32;
+		let kv2 = (pseudo_random(&mut seed) % 100) as u32;
+		let tombstones = map.tombstones();
+		match pseudo_random(&mut seed) % 5 {
+			0 | 1 => {
+				assert_eq!(map.insert(kv1, kv2), reference.insert(kv1, kv2));
+				order.retain(|kvs| kvs.0 != kv1 && kvs.1 != kv2);
+				order.push((kv1, kv2));
+			}
+			2 | 3 => {
+				assert_eq!(map.remove_by_first(&kv1), reference.remove_by_first(&kv1));
+				order.retain(|kvs| kvs.0 != kv1);
+			}
+			_ => {
+				assert_eq!(map.remove_by_second(&kv2), reference.remove_by_second(&kv2));
+				order.retain(|kvs| kvs.1 != kv2);
+			}
+		}
+		compacted |= map.tombstones() < tombstones;
+
+		assert_eq!(map.len(), reference.len(), "step {}", step);
+		assert_eq!(map.is_empty(), reference.is_empty());
+		assert_eq!(map.get_by_first(&kv1), reference.get_by_first(&kv1));
+		assert_eq!(map.get_by_second(&kv2), reference.get_by_second(&kv2));
+		assert_eq!(map.contains_first_key(&kv2), reference.contains_first_key(&kv2));
+		assert_eq!(map.iter().len(), order.len());
+		assert!(map.iter().eq(order.iter()));
+		assert!(map.tombstones() < 2 * 32 || map.tombstones() <= map.len());
+	}
+	assert!(compacted);
+}
+
+#[test]
+fn compaction_preserves_order() {
+	let mut map: TombstoneBidirMap<u32, u32> = (0..10).map(|i| (i, i * 10)).collect();
+	map.set_compaction_threshold(usize::MAX);
+	for i in (0..10).filter(|i| i % 3 == 0) {
+		map.remove_by_first(&i);
+	}
+	map.insert(10, 0);
+	assert_eq!(map.tombstones(), 4);
+
+	let before: Vec<_> = map.iter().cloned().collect();
+	assert_eq!(before, [(1, 10), (2, 20), (4, 40), (5, 50), (7, 70), (8, 80), (10, 0)]);
+	assert_eq!(map.get_index_of_first(&7), Some(4));
+	assert_eq!(map.get_index(6), Some(&(10, 0)));
+
+	map.compact();
+	assert_eq!(map.tombstones(), 0);
+	assert!(map.iter().eq(before.iter()));
+	assert_eq!(map.get_index_of_first(&7), Some(4));
+	assert_eq!(map.get_index_of_second(&0), Some(6));
+
+	assert_eq!(BidirMap::from(map.clone()).iter().cloned().collect::<Vec<_>>(), before);
+}
+
+#[test]
+fn automatic_compaction() {
+	let mut map: TombstoneBidirMap<u32, u32> = (0..8).map(|i| (i, i)).collect();
+	map.set_compaction_threshold(4);
+	map.remove_by_first(&0);
+	map.remove_by_first(&1);
+	map.remove_by_first(&2);
+	map.remove_by_first(&3);
+	assert_eq!(map.tombstones(), 4);
+	map.remove_by_second(&4);
+	assert_eq!(map.tombstones(), 0);
+	assert_eq!(map.iter().cloned().collect::<Vec<_>>(), [(5, 5), (6, 6), (7, 7)]);
+
+	let mut other = TombstoneBidirMap::new();
+	for i in 5..8 {
+		other.insert(i, i);
+	}
+	assert_eq!(map, other);
+}
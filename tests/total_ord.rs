@@ -0,0 +1,51 @@
+extern crate bidir_map;
+
+use bidir_map::{BidirMap, TotalOrd};
+
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "the first K/V isn't equal to itself")]
+fn insert_nan_panics_in_debug() {
+	let mut map = BidirMap::new();
+	map.insert(f64::NAN, 1);
+}
+
+#[test]
+#[cfg(not(debug_assertions))]
+fn nan_displaces_nan() {
+	let mut map = BidirMap::new();
+	map.insert(f64::NAN, 1);
+	map.insert(0.5, 2);
+	assert_eq!(map.insert(f64::NAN, 3).map(|(kv1, kv2)| (kv1.is_nan(), kv2)), Some((true, 1)));
+	assert_eq!(map.insert(f64::NAN, 2).map(|(kv1, kv2)| (kv1.is_nan(), kv2)), Some((true, 3)));
+	assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn nan_pairs_removable() {
+	let mut map: BidirMap<f64, u32> = vec![(f64::NAN, 1), (0.5, 2), (f64::NAN, 3), (1.5, 4)].into_iter().collect();
+	assert_eq!(map.get_by_first(&f64::NAN), None);
+	assert_eq!(map.remove_by_first(&f64::NAN), None);
+
+	let stuck = map.iter().position(|kvs| kvs.0.is_nan()).unwrap();
+	assert_eq!(map.swap_remove_index(stuck).map(|(_, kv2)| kv2), Some(1));
+	map.retain(|kv1, _| !kv1.is_nan());
+	assert_eq!(map.iter().cloned().collect::<Vec<_>>(), [(1.5, 4), (0.5, 2)]);
+}
+
+#[test]
+fn total_ord_keys() {
+	let mut map = BidirMap::new();
+	for i in 0..10 {
+		map.insert(TotalOrd(f64::NAN), i);
+	}
+	map.insert(TotalOrd(0.0), 10);
+	map.insert(TotalOrd(-0.0), 11);
+	assert_eq!(map.len(), 3);
+	assert_eq!(map.get_by_first(&TotalOrd(f64::NAN)), Some(&9));
+	assert_eq!(map.get_by_first(&TotalOrd(-0.0)), Some(&11));
+	assert_eq!(map.remove_by_first(&TotalOrd(f64::NAN)).map(|(_, kv2)| kv2), Some(9));
+	assert!(map.iter().all(|kvs| !kvs.0 .0.is_nan()));
+	assert!(TotalOrd(f32::NAN) > TotalOrd(f32::INFINITY));
+}
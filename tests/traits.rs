@@ -0,0 +1,95 @@
+extern crate bidir_map;
+
+use bidir_map::{BidirArrayMap, BidirBTreeMap, BidirHashMap, BidirMap, BidirSoaMap, BidirSortedMap, DenseBidirMap, FrozenBidirMap, HandleBidirMap};
+use bidir_map::traits::{BidirMapLike, BidirMapMut};
+
+
+fn resolve<M: BidirMapLike<u32, String>>(map: &M, ids: &[u32]) -> Vec<Option<String>> {
+	ids.iter().map(|id| map.get_by_first(id).cloned()).collect()
+}
+
+/// Checks `get_by_*()` and `contains_*()` against `iter()`
+fn consistent<Kv1: PartialEq, Kv2: PartialEq, M: BidirMapLike<Kv1, Kv2>>(map: &M) {
+	assert_eq!(map.iter().count(), map.len());
+	assert_eq!(map.is_empty(), map.len() == 0);
+	for (kv1, kv2) in map.iter() {
+		assert!(map.get_by_first(kv1) == Some(kv2));
+		assert!(map.get_by_second(kv2) == Some(kv1));
+		assert!(map.contains_first_key(kv1) && map.contains_second_key(kv2));
+	}
+}
+
+/// Renames the pairs with the first K/Vs in `ids`, via removal and reinsertion
+fn rename<M: BidirMapMut<u32, String>>(map: &mut M, ids: &[u32]) -> usize {
+	let mut renamed = 0;
+	for &id in ids {
+		if let Some((id, name)) = map.remove_by_first(&id) {
+			assert_eq!(map.insert(id, name.to_uppercase()), None);
+			renamed += 1;
+		}
+	}
+	renamed
+}
+
+fn names() -> Vec<(u32, String)> {
+	vec![(1, "one".to_string()), (2, "two".to_string()), (3, "three".to_string())]
+}
+
+#[test]
+fn read_generically() {
+	let expected = [Some("two".to_string()), None, Some("one".to_string())];
+
+	let map: BidirMap<_, _> = names().into_iter().collect();
+	assert_eq!(resolve(&map, &[2, 4, 1]), expected);
+	consistent(&map);
+
+	let sorted: BidirSortedMap<_, _> = names().into_iter().collect();
+	assert_eq!(resolve(&sorted, &[2, 4, 1]), expected);
+	consistent(&sorted);
+
+	let hashed: BidirHashMap<_, _> = names().into_iter().collect();
+	assert_eq!(resolve(&hashed, &[2, 4, 1]), expected);
+	consistent(&hashed);
+
+	let frozen: FrozenBidirMap<_, _> = map.clone().freeze();
+	assert_eq!(resolve(&frozen, &[2, 4, 1]), expected);
+	consistent(&frozen);
+
+	let mut array = BidirArrayMap::<_, _, 4>::new();
+	for (id, name) in names() {
+		array.insert(id, name).unwrap();
+	}
+	assert_eq!(resolve(&array, &[2, 4, 1]), expected);
+	consistent(&array);
+}
+
+#[test]
+fn iteration_order() {
+	let map: BidirMap<_, _> = names().into_iter().collect();
+	let btree: BidirBTreeMap<_, _> = names().into_iter().rev().collect();
+
+	let from_map: Vec<_> = BidirMapLike::iter(&map).map(|(id, _)| *id).collect();
+	let from_btree: Vec<_> = BidirMapLike::iter(&btree).map(|(id, _)| *id).collect();
+	assert_eq!(from_map, [1, 2, 3]);
+	assert_eq!(from_btree, [1, 2, 3]);
+}
+
+#[test]
+fn modify_generically() {
+	let mut map: BidirMap<_, _> = names().into_iter().collect();
+	let mut soa: BidirSoaMap<_, _> = names().into_iter().collect();
+	let mut handles = HandleBidirMap::from(map.clone());
+	let mut dense: DenseBidirMap<_, _> = names().into_iter().collect();
+
+	assert_eq!(rename(&mut map, &[3, 1, 7]), 2);
+	assert_eq!(rename(&mut soa, &[3, 1, 7]), 2);
+	assert_eq!(rename(&mut handles, &[3, 1, 7]), 2);
+	assert_eq!(rename(&mut dense, &[3, 1, 7]), 2);
+
+	for renamed in [resolve(&map, &[1, 2, 3]), resolve(&soa, &[1, 2, 3]), resolve(&handles, &[1, 2, 3]), resolve(&dense, &[1, 2, 3])] {
+		assert_eq!(renamed, [Some("ONE".to_string()), Some("two".to_string()), Some("THREE".to_string())]);
+	}
+	consistent(&handles);
+	consistent(&dense);
+	assert_eq!(BidirMapMut::remove_by_second(&mut soa, &"two".to_string()), Some((2, "two".to_string())));
+}
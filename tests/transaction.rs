@@ -0,0 +1,108 @@
+extern crate bidir_map;
+
+mod common;
+
+use bidir_map::{BidirMap, Column, TransactionError};
+use common::pairs;
+
+
+fn map() -> BidirMap<u32, char> {
+	vec![(1, 'a'), (2, 'b'), (3, 'c'), (4, 'd')].into_iter().collect()
+}
+
+
+fn batch(map: &mut BidirMap<u32, char>, last: (u32, char)) -> Result<(), TransactionError<u32, char>> {
+	let mut tx = map.transaction();
+	assert_eq!(tx.remove_by_first(&1)?, &(1, 'a'));
+	assert_eq!(tx.remove_by_second(&'d')?, &(4, 'd'));
+	tx.insert(5, 'a')?;
+	tx.insert(6, 'e')?;
+	assert_eq!(tx.remove_by_first(&6)?, &(6, 'e'));
+	tx.insert(last.0, last.1)?;
+	tx.commit();
+	Ok(())
+}
+
+#[test]
+fn failing_edit_rolls_back() {
+	for indexed in [false, true] {
+		let mut map = map();
+		if indexed {
+			map.build_index();
+		}
+
+		assert_eq!(batch(&mut map, (7, 'b')), Err(TransactionError::Conflict{column: Column::Second, pair: (7, 'b')}));
+		assert_eq!(pairs(&map), [(1, 'a'), (2, 'b'), (3, 'c'), (4, 'd')]);
+		assert_eq!(map.get_by_first_indexed(&4), Some(&'d'));
+		assert_eq!(map.get_by_second_indexed(&'a'), Some(&1));
+		assert_eq!(map.get_by_first_indexed(&5), None);
+
+		let mut tx = map.transaction();
+		tx.remove_by_first(&2).unwrap();
+		assert_eq!(tx.remove_by_first(&2), Err(TransactionError::NotFound{column: Column::First}));
+		assert_eq!(tx.insert(3, 'z'), Err(TransactionError::Conflict{column: Column::First, pair: (3, 'z')}));
+		drop(tx);
+		assert_eq!(pairs(&map), [(1, 'a'), (2, 'b'), (3, 'c'), (4, 'd')]);
+	}
+}
+
+#[test]
+fn commit_applies_all() {
+	let mut map = map();
+	map.build_index();
+	assert_eq!(batch(&mut map, (7, 'f')), Ok(()));
+
+	let mut expected = pairs(&map);
+	expected.sort();
+	assert_eq!(expected, [(2, 'b'), (3, 'c'), (5, 'a'), (7, 'f')]);
+	assert_eq!(map.get_by_second_indexed(&'a'), Some(&5));
+	assert_eq!(map.get_by_first_indexed(&1), None);
+}
+
+#[test]
+fn reads_see_staged_edits() {
+	let mut map = map();
+	let mut tx = map.transaction();
+	tx.remove_by_second(&'b').unwrap();
+	tx.insert(2, 'y').unwrap();
+
+	assert_eq!(tx.get_by_first(&2), Some(&'y'));
+	assert_eq!(tx.get_by_second(&'b'), None);
+	assert!(tx.contains_second_key(&'y'));
+	assert!(!tx.contains_first_key(&9));
+	assert_eq!(tx.len(), 4);
+	assert!(!tx.is_empty());
+	assert_eq!(tx.iter().filter(|kvs| kvs.1 == 'y').count(), 1);
+
+	tx.rollback();
+	assert_eq!(pairs(&map), [(1, 'a'), (2, 'b'), (3, 'c'), (4, 'd')]);
+}
+
+#[test]
+fn rollback_restores_order() {
+	// xorshift
+	let mut state = 0x2545_f491_u32;
+	let mut next = move || {
+		state ^= state << 13;
+		state ^= state >> 17;
+		state ^= state << 5;
+		state
+	};
+
+	for _ in 0..50 {
+		let mut map: BidirMap<u32, u32> = (0..20).map(|i| (i, i + 100)).collect();
+		let before: Vec<_> = map.iter().cloned().collect();
+
+		let mut tx = map.transaction();
+		for _ in 0..30 {
+			let key = next() % 40;
+			if next() % 2 == 0 {
+				let _ = tx.insert(key, key + 100);
+			} else {
+				let _ = tx.remove_by_first(&key);
+			}
+		}
+		drop(tx);
+		assert_eq!(map.iter().cloned().collect::<Vec<_>>(), before);
+	}
+}
@@ -0,0 +1,77 @@
+extern crate bidir_map;
+
+use bidir_map::TriMap;
+
+
+fn map() -> TriMap<u32, &'static str, String> {
+	let mut map = TriMap::new();
+	map.insert(1, "FR", "France".to_string());
+	map.insert(2, "DE", "Germany".to_string());
+	map.insert(3, "IT", "Italy".to_string());
+	map
+}
+
+fn assert_unique(map: &TriMap<u32, &'static str, String>) {
+	for abc in map {
+		assert_eq!(map.get_by_first(&abc.0), Some(abc));
+		assert_eq!(map.get_by_second(&abc.1), Some(abc));
+		assert_eq!(map.get_by_third(&abc.2), Some(abc));
+	}
+}
+
+#[test]
+fn lookups_by_any_column() {
+	let map = map();
+	assert_eq!(map.get_by_first(&2).map(|abc| abc.1), Some("DE"));
+	assert_eq!(map.get_by_second(&"IT").map(|abc| abc.0), Some(3));
+	assert_eq!(map.get_by_third("France").map(|abc| abc.1), Some("FR"));
+	assert!(!map.contains_third_key("Spain"));
+	assert_unique(&map);
+}
+
+#[test]
+fn collision_with_one_triple() {
+	let mut map = map();
+	assert_eq!(map.insert(4, "DE", "Deutschland".to_string()), [(2, "DE", "Germany".to_string())]);
+	assert_eq!(map.len(), 3);
+	assert!(!map.contains_first_key(&2));
+	assert_unique(&map);
+}
+
+#[test]
+fn collision_with_two_triples() {
+	let mut map = map();
+	let displaced = map.insert(1, "IT", "Frantaly".to_string());
+	assert_eq!(displaced, [(1, "FR", "France".to_string()), (3, "IT", "Italy".to_string())]);
+	assert_eq!(map.len(), 2);
+	assert_unique(&map);
+}
+
+#[test]
+fn collision_with_three_triples() {
+	let mut map = map();
+	let displaced = map.insert(1, "DE", "Italy".to_string());
+	assert_eq!(displaced.len(), 3);
+	assert_eq!(map.iter().collect::<Vec<_>>(), [&(1, "DE", "Italy".to_string())]);
+}
+
+#[test]
+fn collision_with_the_same_triple_twice() {
+	let mut map = map();
+	assert_eq!(map.insert(1, "FR", "Italy".to_string()), [(1, "FR", "France".to_string()), (3, "IT", "Italy".to_string())]);
+	assert_unique(&map);
+}
+
+#[test]
+fn try_insert_rejects_any_collision() {
+	let mut map = map();
+	assert_eq!(map.try_insert(9, "FR", "Nowhere".to_string()), Err((9, "FR", "Nowhere".to_string())));
+	assert_eq!(map.try_insert(1, "XX", "Nowhere".to_string()).map_err(|abc| abc.0), Err(1));
+	assert_eq!(map.try_insert(9, "XX", "Italy".to_string()).map_err(|abc| abc.2), Err("Italy".to_string()));
+	assert_eq!(map.len(), 3);
+
+	assert_eq!(map.try_insert(4, "ES", "Spain".to_string()), Ok(()));
+	assert_eq!(map.remove_by_third("Spain"), Some((4, "ES", "Spain".to_string())));
+	assert_eq!(map.remove_by_second(&"FR").map(|abc| abc.0), Some(1));
+	assert_unique(&map);
+}
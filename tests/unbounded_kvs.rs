@@ -0,0 +1,62 @@
+extern crate bidir_map;
+
+use bidir_map::BidirMap;
+use std::rc::Rc;
+
+
+// A handle with no `PartialEq`, which can only be used positionally
+#[derive(Debug)]
+struct Handle(Rc<()>);
+
+struct Registry {
+	handles: BidirMap<Handle, String>,
+}
+
+fn new_registry(token: &Rc<()>) -> Registry {
+	Registry{
+		handles: vec!["a", "b", "c", "d"].into_iter().map(|name| (Handle(token.clone()), name.to_string())).collect(),
+	}
+}
+
+
+#[test]
+fn collect_and_iterate() {
+	let token = Rc::new(());
+	let registry = new_registry(&token);
+	assert_eq!(registry.handles.len(), 4);
+	assert_eq!(registry.handles.second_col().map(|name| &name[..]).collect::<Vec<_>>(), ["a", "b", "c", "d"]);
+	assert!(registry.handles.iter().all(|(handle, _)| Rc::ptr_eq(&handle.0, &token)));
+	assert_eq!(format!("{:?}", BidirMap::<Handle, String>::new()), "BidirMap { cont: [] }");
+
+	let single: BidirMap<_, _> = vec![(Handle(token.clone()), "e")].into_iter().collect();
+	assert_eq!(format!("{:?}", single), "BidirMap { cont: [(Handle(()), \"e\")] }");
+}
+
+#[test]
+fn positional_edits() {
+	let token = Rc::new(());
+	let mut registry = new_registry(&token);
+	assert_eq!(registry.handles.swap_remove_index(0).map(|(_, name)| name), Some("a".to_string()));
+	assert_eq!(registry.handles.shift_remove_index(1).map(|(_, name)| name), Some("b".to_string()));
+	registry.handles.retain(|_, name| name != "c");
+	assert_eq!(registry.handles.second_col().collect::<Vec<_>>(), ["d"]);
+	for (_, name) in registry.handles.iter_mut() {
+		name.push('!');
+	}
+	assert_eq!(registry.handles.into_iter().map(|(_, name)| name).collect::<Vec<_>>(), ["d!"]);
+}
+
+#[test]
+fn drop() {
+	let token = Rc::new(());
+	let mut registry = new_registry(&token);
+	assert_eq!(Rc::strong_count(&token), 5);
+	registry.handles.swap_remove_index(2);
+	assert_eq!(Rc::strong_count(&token), 4);
+	std::mem::drop(registry);
+	assert_eq!(Rc::strong_count(&token), 1);
+
+	let mut registry = new_registry(&token);
+	registry.handles.clear();
+	assert_eq!(Rc::strong_count(&token), 1);
+}
@@ -0,0 +1,65 @@
+extern crate bidir_map;
+
+use bidir_map::{BidirMap, Column, InvariantError, RepairPolicy};
+
+
+/// Pairs (i, i * 10) for i in 0..5, then corrupted through `iter_mut()`
+fn corrupted() -> BidirMap<u32, u32> {
+	let mut map = BidirMap::new();
+	for i in 0..5 {
+		map.insert(i, i * 10);
+	}
+	for kv in map.iter_mut() {
+		match kv.0 {
+			1 => kv.0 = 3,   // (3, 10) collides with (3, 30) by the first K/V
+			4 => kv.1 = 0,   // (4, 0) collides with (0, 0) by the second K/V
+			_ => {}
+		}
+	}
+	map
+}
+
+#[test]
+fn detection() {
+	let map = corrupted();
+	let err = map.validate().unwrap_err();
+	assert_eq!(err, InvariantError{column: Column::First, indices: (1, 3)});
+	assert_eq!(err.to_string(), "pairs 1 and 3 share their first K/V");
+
+	let mut fixed = map.clone();
+	*fixed.iter_mut().nth(1).unwrap() = (1, 10);
+	assert_eq!(fixed.validate(), Err(InvariantError{column: Column::Second, indices: (0, 4)}));
+}
+
+#[test]
+fn repair_keeping_first() {
+	let mut map = corrupted();
+	assert_eq!(map.repair(RepairPolicy::KeepFirst), 2);
+	assert_eq!(map.validate(), Ok(()));
+	assert_eq!(map.iter().cloned().collect::<Vec<_>>(), [(0, 0), (3, 10), (2, 20)]);
+	map.debug_validate();
+}
+
+#[test]
+fn repair_keeping_last() {
+	let mut map = corrupted();
+	assert_eq!(map.repair(RepairPolicy::KeepLast), 2);
+	assert_eq!(map.validate(), Ok(()));
+	assert_eq!(map.iter().cloned().collect::<Vec<_>>(), [(2, 20), (3, 30), (4, 0)]);
+}
+
+#[test]
+fn repair_of_valid_map_is_a_no_op() {
+	let mut map = BidirMap::new();
+	map.insert('a', 1);
+	map.insert('b', 2);
+	assert_eq!(map.repair(RepairPolicy::KeepFirst), 0);
+	assert_eq!(map.len(), 2);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "share their first K/V")]
+fn debug_validate_panics() {
+	corrupted().debug_validate();
+}
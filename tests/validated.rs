@@ -0,0 +1,85 @@
+extern crate bidir_map;
+
+use bidir_map::{BidirMap, ValidatedBidirMap, ValidationError};
+use std::io;
+
+
+// Takes `&String`s to be a `Validator`
+#[allow(clippy::ptr_arg)]
+fn slug_and_title(slug: &String, title: &String) -> Result<(), ValidationError> {
+	if slug.is_empty() || !slug.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_') {
+		Err(ValidationError::new(format!("invalid slug {:?}", slug)))
+	} else if title.is_empty() {
+		Err(ValidationError::new("empty title"))
+	} else {
+		Ok(())
+	}
+}
+
+type Validator = fn(&String, &String) -> Result<(), ValidationError>;
+
+fn pages() -> ValidatedBidirMap<String, String, Validator> {
+	let mut map = BidirMap::with_validator(slug_and_title as Validator);
+	map.insert("intro".to_string(), "Introduction".to_string()).unwrap();
+	map.insert("faq".to_string(), "Questions".to_string()).unwrap();
+	map
+}
+
+fn contents(map: &ValidatedBidirMap<String, String, Validator>) -> Vec<(&str, &str)> {
+	map.iter().map(|(kv1, kv2)| (&kv1[..], &kv2[..])).collect()
+}
+
+
+#[test]
+fn insert() {
+	let mut map = pages();
+	assert_eq!(map.insert("faq".to_string(), "FAQ".to_string()), Ok(Some(("faq".to_string(), "Questions".to_string()))));
+
+	let err = map.insert("Bad Slug".to_string(), "Introduction".to_string()).unwrap_err();
+	assert_eq!(err.message, "invalid slug \"Bad Slug\"");
+	assert_eq!(err.to_string(), "pair rejected: invalid slug \"Bad Slug\"");
+	// The rejected pair would have displaced both existing ones
+	assert_eq!(map.insert("intro".to_string(), String::new()), Err(ValidationError::new("empty title")));
+	assert_eq!(contents(&map), [("intro", "Introduction"), ("faq", "FAQ")]);
+}
+
+#[test]
+fn try_extend() {
+	let mut map = pages();
+	let err = map.try_extend(vec![("setup".to_string(), "Setup".to_string()), ("faq".to_string(), String::new())]).unwrap_err();
+	assert_eq!(err.message, "empty title");
+	assert_eq!(contents(&map), [("intro", "Introduction"), ("faq", "Questions")]);
+
+	map.try_extend(vec![("setup".to_string(), "Setup".to_string())]).unwrap();
+	assert_eq!(map.get_by_second("Setup"), Some(&"setup".to_string()));
+}
+
+#[test]
+fn get_or_insert_with() {
+	let mut map = pages();
+	assert_eq!(map.get_or_insert_with_by_first("faq".to_string(), || unreachable!()), Ok(&"Questions".to_string()));
+	assert_eq!(map.get_or_insert_with_by_first("api".to_string(), String::new).unwrap_err().message, "empty title");
+	assert_eq!(map.get_or_insert_with_by_second("Introduction".to_string(), || unreachable!()), Ok(&"intro".to_string()));
+	assert!(map.get_or_insert_with_by_second("Questions".to_string(), || "FAQ".to_string()).is_ok());
+	assert!(map.get_or_insert_with_by_second("API".to_string(), || "API".to_string()).is_err());
+	assert_eq!(contents(&map), [("intro", "Introduction"), ("faq", "Questions")]);
+
+	assert_eq!(map.get_or_insert_with_by_second("API".to_string(), || "api".to_string()), Ok(&"api".to_string()));
+	assert_eq!(map.len(), 3);
+}
+
+#[test]
+fn deserialization() {
+	let valid: BidirMap<_, _> = vec![("a".to_string(), "A".to_string())].into_iter().collect();
+	let invalid: BidirMap<_, _> = vec![("a".to_string(), "A".to_string()), ("B".to_string(), "B".to_string())].into_iter().collect();
+
+	let map = ValidatedBidirMap::try_from_iter(valid.clone(), slug_and_title as Validator).unwrap();
+	assert_eq!(map.inner(), &valid);
+	assert!(ValidatedBidirMap::try_from_iter(invalid.clone(), slug_and_title as Validator).is_err());
+
+	let mut bytes = Vec::new();
+	invalid.write_to(&mut bytes).unwrap();
+	let err = ValidatedBidirMap::read_from(&bytes[..], slug_and_title as Validator).unwrap_err();
+	assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+	assert_eq!(err.into_inner().unwrap().downcast::<ValidationError>().unwrap().message, "invalid slug \"B\"");
+}
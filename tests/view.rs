@@ -0,0 +1,53 @@
+extern crate bidir_map;
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Debug;
+use bidir_map::BidirMap;
+use bidir_map::view::MapRead;
+
+
+/// Looks up every key in `keys`, checking the answers agree with iteration
+fn lookups<K: Debug + PartialEq, V: Clone, M: MapRead<K, V>>(map: &M, keys: &[K]) -> Vec<Option<V>> {
+	assert_eq!(map.iter().count(), map.len());
+	assert_eq!(map.is_empty(), map.len() == 0);
+	for (k, v) in map.iter() {
+		assert!(map.contains_key(k));
+		assert!(map.get(k).is_some_and(|found| std::ptr::eq(found, v)));
+	}
+	keys.iter().map(|k| map.get(k).cloned()).collect()
+}
+
+#[test]
+fn both_directions() {
+	let mut map = BidirMap::new();
+	map.insert(1, "one");
+	map.insert(2, "two");
+	map.insert(3, "three");
+
+	assert_eq!(lookups(&map.as_first_map(), &[2, 4, 1]), [Some("two"), None, Some("one")]);
+	assert_eq!(lookups(&map.as_second_map(), &["three", "four"]), [Some(3), None]);
+
+	assert_eq!(map.as_first_map().iter().collect::<Vec<_>>(), [(&1, &"one"), (&2, &"two"), (&3, &"three")]);
+	assert_eq!(map.as_second_map().into_iter().collect::<Vec<_>>(), [(&"one", &1), (&"two", &2), (&"three", &3)]);
+	assert_eq!(map.as_second_map().get("two"), Some(&2));
+	assert_eq!(map.as_second_map().len(), 3);
+}
+
+#[test]
+fn agrees_with_std_maps() {
+	let map: BidirMap<_, _> = (0..20u32).map(|i| (i, i * 7 % 20)).collect();
+	let forward: BTreeMap<_, _> = map.iter().cloned().collect();
+	let backward: HashMap<_, _> = map.iter().map(|&(a, b)| (b, a)).collect();
+	let keys: Vec<_> = (0..25).collect();
+
+	assert_eq!(lookups(&map.as_first_map(), &keys), lookups(&forward, &keys));
+	assert_eq!(lookups(&map.as_second_map(), &keys), lookups(&backward, &keys));
+}
+
+#[test]
+fn empty() {
+	let map = BidirMap::<u8, char>::new();
+	assert!(map.as_first_map().is_empty());
+	assert!(MapRead::is_empty(&map.as_second_map()));
+	assert_eq!(lookups(&map.as_second_map(), &['a']), [None]);
+}
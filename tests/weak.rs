@@ -0,0 +1,73 @@
+extern crate bidir_map;
+
+use bidir_map::WeakBidirMap;
+use std::sync::Arc;
+
+
+// A texture, equal to every other one so that only identity tells them apart
+#[derive(Debug, PartialEq)]
+struct Texture;
+
+fn textures() -> (Vec<Arc<Texture>>, WeakBidirMap<Texture, u32>) {
+	let strong: Vec<_> = (0..3).map(|_| Arc::new(Texture)).collect();
+	let mut map = WeakBidirMap::new();
+	for (id, texture) in strong.iter().enumerate() {
+		assert!(map.insert(texture, id as u32).is_none());
+	}
+	(strong, map)
+}
+
+
+#[test]
+fn identity() {
+	let (strong, mut map) = textures();
+	assert_eq!(map.get_by_first(&strong[1]), Some(&1));
+	assert_eq!(map.get_by_first(&Arc::new(Texture)), None);
+	assert!(Arc::ptr_eq(&map.get_by_second(&2).unwrap(), &strong[2]));
+
+	let (old, id) = map.insert(&strong[1], 7).unwrap();
+	assert!(old.ptr_eq(&Arc::downgrade(&strong[1])));
+	assert_eq!(id, 1);
+	assert_eq!(map.remove_by_first(&strong[0]), Some(0));
+	assert_eq!(map.iter().map(|(_, &id)| id).collect::<Vec<_>>(), [7, 2]);
+}
+
+#[test]
+fn dead_lookups() {
+	let (mut strong, map) = textures();
+	let dropped = strong.remove(1);
+	let weak = Arc::downgrade(&dropped);
+	drop(dropped);
+	assert_eq!(weak.strong_count(), 0);
+
+	assert_eq!(map.get_by_second(&1), None);
+	assert!(!map.contains_second_key(&1));
+	assert!(map.contains_second_key(&2));
+	assert_eq!(map.iter().map(|(_, &id)| id).collect::<Vec<_>>(), [0, 2]);
+	assert_eq!(map.len(), 3);
+}
+
+#[test]
+fn prune() {
+	let (mut strong, mut map) = textures();
+	assert_eq!(map.prune(), 0);
+	strong.truncate(1);
+	assert_eq!(map.prune(), 2);
+	assert_eq!(map.len(), 1);
+	assert_eq!(map.get_by_first(&strong[0]), Some(&0));
+}
+
+#[test]
+fn insert_prunes_when_full() {
+	let mut map = WeakBidirMap::with_capacity(2);
+	let kept = Arc::new(Texture);
+	map.insert(&kept, 0);
+	map.insert(&Arc::new(Texture), 1);
+	assert_eq!(map.len(), map.capacity());
+
+	let capacity = map.capacity();
+	map.insert(&Arc::new(Texture), 2);
+	assert_eq!(map.capacity(), capacity);
+	assert_eq!(map.len(), 2);
+	assert!(!map.contains_second_key(&1));
+}